@@ -1,10 +1,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use bijux_atlas::domain::security::data_protection::{
-    detect_tampering, verify_artifact_checksum, verify_artifact_signature, EncryptionAtRest,
-    XorEncryption,
+    detect_tampering, verify_artifact_checksum, verify_artifact_signature, AeadEncryption,
+    EncryptionAtRest,
 };
+use bijux_atlas::domain::security::runtime::{KeyManager, KeyRecord, SystemNonceSource};
 use criterion::{criterion_group, criterion_main, Criterion};
+use std::collections::BTreeMap;
 use std::hint::black_box;
 
 fn security_data_protection_benchmarks(c: &mut Criterion) {
@@ -12,12 +14,21 @@ fn security_data_protection_benchmarks(c: &mut Criterion) {
     let checksum = bijux_atlas::domain::sha256_hex(&payload);
     let signing_key = "atlas-signing-key";
     let signature = bijux_atlas::domain::sha256_hex(format!("{checksum}:{signing_key}").as_bytes());
-    let encryption = XorEncryption::new(b"benchmark-key");
+    let encryption = AeadEncryption::new(
+        KeyManager::new(vec![KeyRecord {
+            key_id: "benchmark-key".to_string(),
+            purpose: "encryption".to_string(),
+            active: true,
+        }]),
+        BTreeMap::from([("benchmark-key".to_string(), vec![7_u8; 32])]),
+        Box::new(SystemNonceSource::default()),
+    )
+    .expect("benchmark cipher");
 
     c.bench_function("security_encrypt_decrypt_roundtrip_64kb", |b| {
         b.iter(|| {
-            let ciphertext = encryption.encrypt(black_box(&payload));
-            let plaintext = encryption.decrypt(black_box(&ciphertext));
+            let ciphertext = encryption.encrypt(black_box(&payload)).expect("encrypt");
+            let plaintext = encryption.decrypt(black_box(&ciphertext)).expect("decrypt");
             black_box(plaintext.len());
         })
     });
@@ -54,6 +54,8 @@ fn opts_for_fixture(base: &std::path::Path, fixture_dir: &str) -> IngestOptions
         max_warn_anomalies: None,
         max_error_anomalies: None,
         timestamp_policy: bijux_atlas::domain::ingest::TimestampPolicy::DeterministicZero,
+        liftover_chain: None,
+        chrom_alias_path: None,
     }
 }
 
@@ -56,6 +56,8 @@ fn bench_ingest_throughput(c: &mut Criterion) {
                 max_warn_anomalies: None,
                 max_error_anomalies: None,
                 timestamp_policy: bijux_atlas::domain::ingest::TimestampPolicy::DeterministicZero,
+                liftover_chain: None,
+                chrom_alias_path: None,
             };
             ingest_dataset(&opts).expect("ingest benchmark");
         })
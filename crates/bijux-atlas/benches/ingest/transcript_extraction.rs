@@ -53,6 +53,8 @@ fn make_options(root: &std::path::Path) -> IngestOptions {
         max_warn_anomalies: None,
         max_error_anomalies: None,
         timestamp_policy: bijux_atlas::domain::ingest::TimestampPolicy::DeterministicZero,
+        liftover_chain: None,
+        chrom_alias_path: None,
     }
 }
 
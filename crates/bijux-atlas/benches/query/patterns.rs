@@ -397,6 +397,7 @@ fn bench_query_patterns(c: &mut Criterion) {
                 biotype: false,
                 transcript_count: false,
                 sequence_length: false,
+                strand: false,
             },
         );
         b.iter(|| run_pattern(&conn, &request));
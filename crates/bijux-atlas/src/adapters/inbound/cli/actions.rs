@@ -4,7 +4,10 @@ use super::ingest_inputs::resolve_verify_and_lock_inputs;
 use super::operations;
 use super::*;
 use crate::adapters::inbound::cli::commands::ExportFormat;
-use crate::app::query::{IntervalSemantics, QuerySort, StrandMode};
+use crate::adapters::outbound::columnar;
+use crate::app::query::{
+    write_gene_record, AnnotationFormat, GeneRow, IntervalSemantics, QuerySort, StrandMode,
+};
 use crate::domain::dataset::ArtifactManifest;
 
 use std::path::PathBuf;
@@ -38,6 +41,8 @@ pub(super) fn explain_query_from_query_text(
             name_prefix: parsed.get("name_prefix").cloned(),
             biotype: parsed.get("biotype").cloned(),
             region: parsed.get("region").cloned(),
+            xref: parsed.get("xref").cloned(),
+            alias: parsed.get("alias").cloned(),
             limit,
             allow_full_scan,
         },
@@ -231,6 +236,14 @@ pub(super) fn run_ingest(args: IngestCliArgs, output_mode: OutputMode) -> Result
         ShardingPlanCli::Contig => ShardingPlan::Contig,
         ShardingPlanCli::RegionGrid => ShardingPlan::RegionGrid,
     };
+    let liftover_chain = match (args.liftover_chain, args.liftover_target_assembly) {
+        (Some(path), Some(target)) => Some(LiftoverChainInput {
+            path,
+            target_assembly: crate::domain::dataset::Assembly::parse(&target)
+                .map_err(|e| e.to_string())?,
+        }),
+        _ => None,
+    };
     let ingest_options = IngestOptions {
         gff3_path: verified_inputs.gff3_path,
         fasta_path: verified_inputs.fasta_path,
@@ -272,6 +285,8 @@ pub(super) fn run_ingest(args: IngestCliArgs, output_mode: OutputMode) -> Result
         feature_id_uniqueness_policy: crate::app::query::FeatureIdUniquenessPolicy::Reject,
         reject_normalized_seqid_collisions: true,
         timestamp_policy: TimestampPolicy::DeterministicZero,
+        liftover_chain,
+        chrom_alias_path: args.chrom_alias,
     };
 
     if args.dry_run || args.explain {
@@ -288,7 +303,9 @@ pub(super) fn run_ingest(args: IngestCliArgs, output_mode: OutputMode) -> Result
                 "inputs": {
                     "gff3": ingest_options.gff3_path,
                     "fasta": ingest_options.fasta_path,
-                    "fai": ingest_options.fai_path
+                    "fai": ingest_options.fai_path,
+                    "liftover_chain": ingest_options.liftover_chain.as_ref().map(|c| &c.path),
+                    "chrom_alias": ingest_options.chrom_alias_path
                 },
                 "output_root": ingest_options.output_root,
                 "writes_artifacts": false
@@ -307,7 +324,9 @@ pub(super) fn run_ingest(args: IngestCliArgs, output_mode: OutputMode) -> Result
             "report_only": report_only,
             "manifest": result.manifest_path,
             "sqlite": result.sqlite_path,
-            "anomaly_report": result.anomaly_report_path
+            "anomaly_report": result.anomaly_report_path,
+            "qc_report": result.qc_report_path,
+            "resources": result.resource_usage
         }),
     )?;
     Ok(())
@@ -405,6 +424,8 @@ pub(super) struct ExplainQueryArgs {
     pub(super) name_prefix: Option<String>,
     pub(super) biotype: Option<String>,
     pub(super) region: Option<String>,
+    pub(super) xref: Option<String>,
+    pub(super) alias: Option<String>,
     pub(super) limit: usize,
     pub(super) allow_full_scan: bool,
 }
@@ -462,6 +483,75 @@ pub(super) fn explain_query(args: ExplainQueryArgs, output_mode: OutputMode) ->
     Ok(())
 }
 
+pub(super) fn query_exons(
+    db: PathBuf,
+    transcript_id: Option<String>,
+    region: Option<String>,
+    limit: usize,
+    output_mode: OutputMode,
+) -> Result<(), String> {
+    let conn = Connection::open(db).map_err(|e| e.to_string())?;
+    let payload = match (transcript_id, region) {
+        (Some(tx_id), _) => {
+            let rows = crate::app::query::query_transcript_exons(&conn, &tx_id)
+                .map_err(|e| e.to_string())?
+                .ok_or_else(|| format!("transcript not found: {tx_id}"))?;
+            json!({"command": "atlas query exons", "transcript_id": tx_id, "exons": rows})
+        }
+        (None, Some(raw)) => {
+            let region = parse_region_filter(&raw)?;
+            let rows = crate::app::query::query_exons_in_region(
+                &conn,
+                &region.seqid,
+                region.start,
+                region.end,
+                limit,
+            )
+            .map_err(|e| e.to_string())?;
+            json!({
+                "command": "atlas query exons",
+                "region": {"seqid": region.seqid, "start": region.start, "end": region.end},
+                "exons": rows
+            })
+        }
+        (None, None) => return Err("either --transcript-id or --region is required".to_string()),
+    };
+    output::emit_ok(output_mode, payload)?;
+    Ok(())
+}
+
+pub(super) fn query_introns(
+    db: PathBuf,
+    transcript_id: &str,
+    output_mode: OutputMode,
+) -> Result<(), String> {
+    let conn = Connection::open(db).map_err(|e| e.to_string())?;
+    let rows = crate::app::query::query_transcript_introns(&conn, transcript_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("transcript not found: {transcript_id}"))?;
+    output::emit_ok(
+        output_mode,
+        json!({"command": "atlas query introns", "transcript_id": transcript_id, "introns": rows}),
+    )?;
+    Ok(())
+}
+
+pub(super) fn query_utrs(
+    db: PathBuf,
+    transcript_id: &str,
+    output_mode: OutputMode,
+) -> Result<(), String> {
+    let conn = Connection::open(db).map_err(|e| e.to_string())?;
+    let rows = crate::app::query::query_transcript_utrs(&conn, transcript_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("transcript not found: {transcript_id}"))?;
+    output::emit_ok(
+        output_mode,
+        json!({"command": "atlas query utrs", "transcript_id": transcript_id, "utrs": rows}),
+    )?;
+    Ok(())
+}
+
 pub(super) fn inspect_dataset(
     root: PathBuf,
     release: &str,
@@ -553,7 +643,12 @@ pub(super) fn export_query_rows(
     output_mode: OutputMode,
 ) -> Result<(), String> {
     let conn = Connection::open(args.db.clone()).map_err(|e| e.to_string())?;
-    let req = build_query_request(args)?;
+    let mut req = build_query_request(args)?;
+    // Annotation records carry a strand column.
+    req.fields.strand |= matches!(
+        format,
+        ExportFormat::Bed | ExportFormat::Gtf | ExportFormat::Gff3
+    );
     let resp = crate::app::query::query_genes(&conn, &req, &QueryLimits::default(), b"atlas-cli")
         .map_err(|e| e.to_string())?;
     if let Some(parent) = out.parent() {
@@ -609,6 +704,24 @@ pub(super) fn export_query_rows(
             }
             writer.flush().map_err(|e| e.to_string())?;
         }
+        ExportFormat::Parquet | ExportFormat::Arrow => {
+            let schema = columnar::gene_row_schema();
+            let rows = match serde_json::to_value(&resp.rows).map_err(|e| e.to_string())? {
+                Value::Array(rows) => rows,
+                _ => Vec::new(),
+            };
+            let batch = columnar::ColumnBatch::from_json_rows(&schema, &rows);
+            let bytes = if matches!(format, ExportFormat::Parquet) {
+                columnar::encode_parquet(&schema, &batch)
+            } else {
+                columnar::encode_arrow_stream(&schema, &batch)
+            }
+            .map_err(|e| e.to_string())?;
+            fs::write(&out, bytes).map_err(|e| e.to_string())?;
+        }
+        ExportFormat::Bed => write_gene_annotation(&out, AnnotationFormat::Bed, &resp.rows)?,
+        ExportFormat::Gtf => write_gene_annotation(&out, AnnotationFormat::Gtf, &resp.rows)?,
+        ExportFormat::Gff3 => write_gene_annotation(&out, AnnotationFormat::Gff3, &resp.rows)?,
     }
     output::emit_ok(
         output_mode,
@@ -620,6 +733,11 @@ pub(super) fn export_query_rows(
                 ExportFormat::Json => "json",
                 ExportFormat::Jsonl => "jsonl",
                 ExportFormat::Csv => "csv",
+                ExportFormat::Parquet => "parquet",
+                ExportFormat::Arrow => "arrow",
+                ExportFormat::Bed => "bed",
+                ExportFormat::Gtf => "gtf",
+                ExportFormat::Gff3 => "gff3",
             },
             "rows": resp.rows.len()
         }),
@@ -627,22 +745,38 @@ pub(super) fn export_query_rows(
     Ok(())
 }
 
+fn write_gene_annotation(
+    out: &std::path::Path,
+    format: AnnotationFormat,
+    rows: &[GeneRow],
+) -> Result<(), String> {
+    let mut buf = String::from(format.header());
+    for row in rows {
+        write_gene_record(&mut buf, format, row);
+    }
+    fs::write(out, buf).map_err(|e| e.to_string())
+}
+
+fn parse_region_filter(raw: &str) -> Result<RegionFilter, String> {
+    let (seqid, span) = raw
+        .split_once(':')
+        .ok_or_else(|| "region must be seqid:start-end".to_string())?;
+    let (start, end) = span
+        .split_once('-')
+        .ok_or_else(|| "region must be seqid:start-end".to_string())?;
+    Ok(RegionFilter {
+        seqid: seqid.to_string(),
+        start: start.parse::<u64>().map_err(|e| e.to_string())?,
+        end: end.parse::<u64>().map_err(|e| e.to_string())?,
+    })
+}
+
 fn build_query_request(args: ExplainQueryArgs) -> Result<GeneQueryRequest, String> {
-    let region_filter = if let Some(raw) = args.region {
-        let (seqid, span) = raw
-            .split_once(':')
-            .ok_or_else(|| "region must be seqid:start-end".to_string())?;
-        let (start, end) = span
-            .split_once('-')
-            .ok_or_else(|| "region must be seqid:start-end".to_string())?;
-        Some(RegionFilter {
-            seqid: seqid.to_string(),
-            start: start.parse::<u64>().map_err(|e| e.to_string())?,
-            end: end.parse::<u64>().map_err(|e| e.to_string())?,
-        })
-    } else {
-        None
-    };
+    let region_filter = args
+        .region
+        .as_deref()
+        .map(parse_region_filter)
+        .transpose()?;
     Ok(GeneQueryRequest {
         fields: GeneFields::default(),
         filter: GeneFilter {
@@ -654,6 +788,9 @@ fn build_query_request(args: ExplainQueryArgs) -> Result<GeneQueryRequest, Strin
             sort: QuerySort::Auto,
             interval: IntervalSemantics::Overlap,
             strand: StrandMode::Any,
+            xref: args.xref,
+            alias: args.alias,
+            ..GeneFilter::default()
         },
         limit: args.limit,
         cursor: None,
@@ -2,7 +2,7 @@
 
 use super::commands::{
     CatalogCommand, DatasetCommand, DiffCommand, ExportCommand, GcCommand, InspectCommand,
-    QueryCommand,
+    KeysCommand, QueryCommand, SequenceCommand,
 };
 use clap::{ArgAction, Parser, Subcommand, ValueEnum};
 use clap_complete::Shell;
@@ -13,7 +13,7 @@ use std::path::PathBuf as CliPathBuf;
 #[command(about = "Bijux Atlas operations CLI")]
 #[command(help_template = super::BIJUX_HELP_TEMPLATE)]
 #[command(
-    after_help = "Workflows:\n  build dataset: ingest [--dry-run|--explain] -> dataset verify -> dataset evidence-verify -> dataset publish [--dry-run|--explain] -> catalog promote\n  inspect/query: inspect dataset|provenance|db and query run|explain\n  export: export openapi or export query rows\n\nEnvironment:\n  BIJUX_LOG_LEVEL   Log verbosity override\n  BIJUX_CACHE_DIR   Shared cache directory"
+    after_help = "Workflows:\n  build dataset: ingest [--dry-run|--explain] -> dataset verify -> dataset evidence-verify -> dataset publish [--dry-run|--explain] -> catalog promote\n  inspect/query: inspect dataset|provenance|db and query run|explain\n  sequence: sequence transcript|region from a local dataset\n  export: export openapi or export query rows\n\nEnvironment:\n  BIJUX_LOG_LEVEL   Log verbosity override\n  BIJUX_CACHE_DIR   Shared cache directory"
 )]
 pub(crate) struct Cli {
     #[arg(long, global = true, default_value_t = false)]
@@ -75,6 +75,10 @@ pub(crate) enum AtlasCommand {
         #[command(subcommand)]
         command: GcCommand,
     },
+    Keys {
+        #[command(subcommand)]
+        command: KeysCommand,
+    },
     Policy {
         #[command(subcommand)]
         command: PolicyCommand,
@@ -108,6 +112,14 @@ pub(crate) enum AtlasCommand {
         ensembl_keys: String,
         #[arg(long, default_value = "")]
         seqid_aliases: String,
+        /// UCSC chain file lifting this assembly onto `--liftover-target-assembly`.
+        #[arg(long, requires = "liftover_target_assembly")]
+        liftover_chain: Option<CliPathBuf>,
+        #[arg(long, requires = "liftover_chain")]
+        liftover_target_assembly: Option<String>,
+        /// UCSC chromAlias file naming each contig in other conventions.
+        #[arg(long)]
+        chrom_alias: Option<CliPathBuf>,
         #[arg(long, default_value_t = 1)]
         max_threads: usize,
         #[arg(long, default_value_t = false)]
@@ -247,6 +259,10 @@ pub(crate) enum AtlasCommand {
         #[command(subcommand)]
         command: InspectCommand,
     },
+    Sequence {
+        #[command(subcommand)]
+        command: SequenceCommand,
+    },
     Export {
         #[command(subcommand)]
         command: ExportCommand,
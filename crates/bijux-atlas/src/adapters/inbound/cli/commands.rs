@@ -8,6 +8,20 @@ pub(crate) enum ExportFormat {
     Json,
     Jsonl,
     Csv,
+    Parquet,
+    /// Arrow IPC stream.
+    Arrow,
+    /// BED6, one line per gene.
+    Bed,
+    Gtf,
+    Gff3,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub(crate) enum ColumnarFormat {
+    Parquet,
+    /// Arrow IPC stream (`.arrows`).
+    Arrow,
 }
 
 #[derive(Subcommand)]
@@ -25,6 +39,12 @@ pub(crate) enum QueryCommand {
         biotype: Option<String>,
         #[arg(long)]
         region: Option<String>,
+        /// Cross-reference lookup such as `HGNC:1100` (GFF3 `Dbxref`/`Ontology_term`).
+        #[arg(long)]
+        xref: Option<String>,
+        /// GFF3 `Alias` lookup.
+        #[arg(long)]
+        alias: Option<String>,
         #[arg(long, default_value_t = 50)]
         limit: usize,
         #[arg(long, default_value_t = false)]
@@ -43,11 +63,79 @@ pub(crate) enum QueryCommand {
         biotype: Option<String>,
         #[arg(long)]
         region: Option<String>,
+        /// Cross-reference lookup such as `HGNC:1100` (GFF3 `Dbxref`/`Ontology_term`).
+        #[arg(long)]
+        xref: Option<String>,
+        /// GFF3 `Alias` lookup.
+        #[arg(long)]
+        alias: Option<String>,
         #[arg(long, default_value_t = 50)]
         limit: usize,
         #[arg(long, default_value_t = false)]
         allow_full_scan: bool,
     },
+    /// Exons of one transcript (5' to 3'), or exons overlapping a region.
+    Exons {
+        #[arg(long)]
+        db: PathBuf,
+        #[arg(long, conflicts_with = "region", required_unless_present = "region")]
+        transcript_id: Option<String>,
+        #[arg(long)]
+        region: Option<String>,
+        #[arg(long, default_value_t = 100)]
+        limit: usize,
+    },
+    /// Introns derived from the exon gaps of one transcript.
+    Introns {
+        #[arg(long)]
+        db: PathBuf,
+        #[arg(long)]
+        transcript_id: String,
+    },
+    /// 5' and 3' UTRs derived from the exon parts outside the CDS of one transcript.
+    Utrs {
+        #[arg(long)]
+        db: PathBuf,
+        #[arg(long)]
+        transcript_id: String,
+    },
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub(crate) enum TranscriptSequenceKindCli {
+    Cdna,
+    Cds,
+    Protein,
+}
+
+#[derive(Subcommand)]
+pub(crate) enum SequenceCommand {
+    /// Spliced cDNA, CDS or translated protein of one transcript.
+    Transcript {
+        #[arg(long)]
+        db: PathBuf,
+        #[arg(long)]
+        fasta: PathBuf,
+        /// FASTA index; defaults to `<fasta>.fai`.
+        #[arg(long)]
+        fai: Option<PathBuf>,
+        #[arg(long)]
+        transcript_id: String,
+        #[arg(long, value_enum, default_value_t = TranscriptSequenceKindCli::Cdna)]
+        kind: TranscriptSequenceKindCli,
+        /// NCBI translation table id; defaults to 2 on mitochondrial contigs and 1 elsewhere.
+        #[arg(long)]
+        genetic_code: Option<u8>,
+    },
+    /// Plus-strand genomic slice `seqid:start-end`.
+    Region {
+        #[arg(long)]
+        fasta: PathBuf,
+        #[arg(long)]
+        fai: Option<PathBuf>,
+        #[arg(long)]
+        region: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -170,6 +258,9 @@ pub(crate) enum DatasetCommand {
         assembly: String,
         #[arg(long, default_value_t = false)]
         deep: bool,
+        /// Hex Ed25519 public key trusted to sign `manifest.sig`; repeat for rotation.
+        #[arg(long = "trusted-key")]
+        trusted_keys: Vec<String>,
     },
     #[command(hide = true)]
     Validate {
@@ -193,6 +284,9 @@ pub(crate) enum DatasetCommand {
         species: String,
         #[arg(long)]
         assembly: String,
+        /// File holding the hex Ed25519 seed used to write `manifest.sig`.
+        #[arg(long)]
+        sign_key: Option<PathBuf>,
         #[arg(long, default_value_t = false)]
         dry_run: bool,
         #[arg(long, default_value_t = false)]
@@ -224,6 +318,24 @@ pub(crate) enum DatasetCommand {
         #[arg(long)]
         assembly: String,
     },
+    /// Dump every table of the dataset SQLite to typed columnar files.
+    Export {
+        #[arg(long)]
+        root: PathBuf,
+        #[arg(long)]
+        release: String,
+        #[arg(long)]
+        species: String,
+        #[arg(long)]
+        assembly: String,
+        #[arg(long)]
+        out_dir: PathBuf,
+        #[arg(long, value_enum, default_value_t = ColumnarFormat::Parquet)]
+        format: ColumnarFormat,
+        /// Export only this table; repeat for several.
+        #[arg(long = "table")]
+        tables: Vec<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -246,6 +358,46 @@ pub(crate) enum DiffCommand {
     },
 }
 
+#[derive(Subcommand)]
+pub(crate) enum KeysCommand {
+    Create {
+        #[arg(long)]
+        registry: PathBuf,
+        #[arg(long)]
+        key_id: String,
+        #[arg(long)]
+        owner: String,
+        #[arg(long = "scope")]
+        scopes: Vec<String>,
+        #[arg(long = "dataset")]
+        datasets: Vec<String>,
+        #[arg(long)]
+        tier: Option<String>,
+        #[arg(long)]
+        expires_in_days: Option<u64>,
+    },
+    List {
+        #[arg(long)]
+        registry: PathBuf,
+    },
+    Revoke {
+        #[arg(long)]
+        registry: PathBuf,
+        #[arg(long)]
+        key_id: String,
+    },
+    Rotate {
+        #[arg(long)]
+        registry: PathBuf,
+        #[arg(long)]
+        key_id: String,
+        #[arg(long)]
+        next_key_id: String,
+        #[arg(long, default_value_t = 86_400)]
+        overlap_secs: u64,
+    },
+}
+
 #[derive(Subcommand)]
 pub(crate) enum GcCommand {
     Plan {
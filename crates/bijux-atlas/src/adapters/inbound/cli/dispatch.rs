@@ -2,8 +2,8 @@
 
 use super::actions::{
     explain_query, explain_query_from_query_text, export_query_rows, inspect_dataset, inspect_db,
-    inspect_provenance, print_completion, print_config, print_version, run_ingest, run_query,
-    smoke_dataset, ExplainQueryArgs,
+    inspect_provenance, print_completion, print_config, print_version, query_exons, query_introns,
+    query_utrs, run_ingest, run_query, smoke_dataset, ExplainQueryArgs,
 };
 use super::ingest_inputs::verify_ingest_inputs;
 use super::operations;
@@ -21,8 +21,16 @@ pub(super) fn run_atlas_command(
             species,
             assembly,
             deep,
-        } => operations::validate_dataset(root, &release, &species, &assembly, deep, output_mode)
-            .map_err(CliError::from_action_error),
+        } => operations::validate_dataset(
+            root,
+            &release,
+            &species,
+            &assembly,
+            deep,
+            &[],
+            output_mode,
+        )
+        .map_err(CliError::from_action_error),
         AtlasCommand::Version => {
             print_version(log_flags.verbose > 0, output_mode).map_err(CliError::from_action_error)
         }
@@ -83,10 +91,17 @@ pub(super) fn run_atlas_command(
                 species,
                 assembly,
                 deep,
-            } => {
-                operations::validate_dataset(root, &release, &species, &assembly, deep, output_mode)
-                    .map_err(CliError::from_action_error)
-            }
+                trusted_keys,
+            } => operations::validate_dataset(
+                root,
+                &release,
+                &species,
+                &assembly,
+                deep,
+                &trusted_keys,
+                output_mode,
+            )
+            .map_err(CliError::from_action_error),
             DatasetCommand::Validate {
                 root,
                 release,
@@ -98,6 +113,7 @@ pub(super) fn run_atlas_command(
                 &species,
                 &assembly,
                 false,
+                &[],
                 output_mode,
             )
             .map_err(CliError::from_action_error),
@@ -107,6 +123,7 @@ pub(super) fn run_atlas_command(
                 release,
                 species,
                 assembly,
+                sign_key,
                 dry_run,
                 explain,
             } => operations::publish_dataset(
@@ -115,6 +132,7 @@ pub(super) fn run_atlas_command(
                 &release,
                 &species,
                 &assembly,
+                sign_key,
                 dry_run,
                 explain,
                 output_mode,
@@ -144,6 +162,27 @@ pub(super) fn run_atlas_command(
                 output_mode,
             )
             .map_err(CliError::from_action_error),
+            DatasetCommand::Export {
+                root,
+                release,
+                species,
+                assembly,
+                out_dir,
+                format,
+                tables,
+            } => operations::export_dataset_tables(
+                operations::DatasetExportArgs {
+                    root,
+                    release,
+                    species,
+                    assembly,
+                    out_dir,
+                    format,
+                    tables,
+                },
+                output_mode,
+            )
+            .map_err(CliError::from_action_error),
         },
         AtlasCommand::Diff { command } => match command {
             DiffCommand::Build {
@@ -183,6 +222,45 @@ pub(super) fn run_atlas_command(
             } => operations::gc_apply(store_root, catalog, pins, confirm, output_mode)
                 .map_err(CliError::from_action_error),
         },
+        AtlasCommand::Keys { command } => match command {
+            KeysCommand::Create {
+                registry,
+                key_id,
+                owner,
+                scopes,
+                datasets,
+                tier,
+                expires_in_days,
+            } => operations::keys_create(
+                operations::CreateApiKeyArgs {
+                    registry,
+                    key_id,
+                    owner,
+                    scopes,
+                    datasets,
+                    tier,
+                    expires_in_days,
+                },
+                output_mode,
+            )
+            .map_err(CliError::from_action_error),
+            KeysCommand::List { registry } => {
+                operations::keys_list(registry, output_mode).map_err(CliError::from_action_error)
+            }
+            KeysCommand::Revoke { registry, key_id } => {
+                operations::keys_revoke(registry, &key_id, output_mode)
+                    .map_err(CliError::from_action_error)
+            }
+            KeysCommand::Rotate {
+                registry,
+                key_id,
+                next_key_id,
+                overlap_secs,
+            } => {
+                operations::keys_rotate(registry, &key_id, &next_key_id, overlap_secs, output_mode)
+                    .map_err(CliError::from_action_error)
+            }
+        },
         AtlasCommand::Policy { command } => match command {
             PolicyCommand::Validate => {
                 operations::validate_policy(output_mode).map_err(CliError::from_action_error)
@@ -210,6 +288,9 @@ pub(super) fn run_atlas_command(
             gene_identifier_policy,
             ensembl_keys,
             seqid_aliases,
+            liftover_chain,
+            liftover_target_assembly,
+            chrom_alias,
             max_threads,
             report_only,
             strict,
@@ -242,6 +323,9 @@ pub(super) fn run_atlas_command(
                 gene_identifier_policy,
                 ensembl_keys,
                 seqid_aliases,
+                liftover_chain,
+                liftover_target_assembly,
+                chrom_alias,
                 max_threads,
                 report_only,
                 strict,
@@ -342,6 +426,8 @@ pub(super) fn run_atlas_command(
                 name_prefix,
                 biotype,
                 region,
+                xref: None,
+                alias: None,
                 limit,
                 allow_full_scan,
             },
@@ -382,6 +468,8 @@ pub(super) fn run_atlas_command(
                 name_prefix,
                 biotype,
                 region,
+                xref,
+                alias,
                 limit,
                 allow_full_scan,
             } => run_query(
@@ -392,6 +480,8 @@ pub(super) fn run_atlas_command(
                     name_prefix,
                     biotype,
                     region,
+                    xref,
+                    alias,
                     limit,
                     allow_full_scan,
                 },
@@ -404,6 +494,8 @@ pub(super) fn run_atlas_command(
                 name_prefix,
                 biotype,
                 region,
+                xref,
+                alias,
                 limit,
                 allow_full_scan,
             } => explain_query(
@@ -414,11 +506,23 @@ pub(super) fn run_atlas_command(
                     name_prefix,
                     biotype,
                     region,
+                    xref,
+                    alias,
                     limit,
                     allow_full_scan,
                 },
                 output_mode,
             ),
+            QueryCommand::Exons {
+                db,
+                transcript_id,
+                region,
+                limit,
+            } => query_exons(db, transcript_id, region, limit, output_mode),
+            QueryCommand::Introns { db, transcript_id } => {
+                query_introns(db, &transcript_id, output_mode)
+            }
+            QueryCommand::Utrs { db, transcript_id } => query_utrs(db, &transcript_id, output_mode),
         }
         .map_err(CliError::from_action_error),
         AtlasCommand::Inspect { command } => match command {
@@ -437,6 +541,34 @@ pub(super) fn run_atlas_command(
             } => inspect_provenance(root, &release, &species, &assembly, output_mode),
         }
         .map_err(CliError::from_action_error),
+        AtlasCommand::Sequence { command } => match command {
+            SequenceCommand::Transcript {
+                db,
+                fasta,
+                fai,
+                transcript_id,
+                kind,
+                genetic_code,
+            } => operations::transcript_sequence(
+                operations::TranscriptSequenceArgs {
+                    db,
+                    fasta,
+                    fai,
+                    transcript_id,
+                    kind: match kind {
+                        TranscriptSequenceKindCli::Cdna => TranscriptSequenceKind::Cdna,
+                        TranscriptSequenceKindCli::Cds => TranscriptSequenceKind::Cds,
+                        TranscriptSequenceKindCli::Protein => TranscriptSequenceKind::Protein,
+                    },
+                    genetic_code,
+                },
+                output_mode,
+            ),
+            SequenceCommand::Region { fasta, fai, region } => {
+                operations::region_sequence(fasta, fai, &region, output_mode)
+            }
+        }
+        .map_err(CliError::from_action_error),
         AtlasCommand::Export { command } => match command {
             ExportCommand::Openapi { out } => output::run_openapi_generate(out, output_mode),
             ExportCommand::Query {
@@ -458,6 +590,8 @@ pub(super) fn run_atlas_command(
                     name_prefix,
                     biotype,
                     region,
+                    xref: None,
+                    alias: None,
                     limit,
                     allow_full_scan,
                 },
@@ -1,6 +1,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::*;
+use crate::app::ingest::{gzi_path_for, InputCompression};
 use crate::domain::sha256_hex;
 
 pub(super) fn verify_ingest_inputs(
@@ -194,7 +195,7 @@ fn resolve_single_input(
     quarantine_dir: &std::path::Path,
     allow_network_inputs: bool,
 ) -> Result<(PathBuf, InputLockSource), String> {
-    let (resolved_url, source_bytes, original_filename) =
+    let (resolved_url, source_bytes, original_filename, local_source) =
         if original.starts_with("http://") || original.starts_with("https://") {
             if !allow_network_inputs {
                 return Err(format!(
@@ -207,7 +208,7 @@ fn resolve_single_input(
             }
             let bytes = resp.bytes().map_err(|e| e.to_string())?.to_vec();
             let filename = original.rsplit('/').next().unwrap_or(kind).to_string();
-            (original.to_string(), bytes, filename)
+            (original.to_string(), bytes, filename, None)
         } else if original.starts_with("s3://") {
             if !allow_network_inputs {
                 return Err(format!(
@@ -224,7 +225,7 @@ fn resolve_single_input(
             }
             let bytes = resp.bytes().map_err(|e| e.to_string())?.to_vec();
             let filename = key.rsplit('/').next().unwrap_or(kind).to_string();
-            (url, bytes, filename)
+            (url, bytes, filename, None)
         } else {
             let path = if let Some(p) = original.strip_prefix("file://") {
                 PathBuf::from(p)
@@ -237,27 +238,26 @@ fn resolve_single_input(
                 .and_then(|x| x.to_str())
                 .unwrap_or(kind)
                 .to_string();
-            (path.display().to_string(), bytes, filename)
+            (path.display().to_string(), bytes, filename, Some(path))
         };
 
-    let (normalized_bytes, final_name) =
-        match decompress_if_needed(original_filename, &source_bytes) {
-            Ok(v) => v,
-            Err(e) => {
-                let q = quarantine_dir.join(format!(
-                    "{kind}-decode-{}.bad",
-                    std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .map_err(|x| x.to_string())?
-                        .as_secs()
-                ));
-                fs::write(&q, &source_bytes).map_err(|x| x.to_string())?;
-                return Err(format!(
-                    "decompression failed for {kind}: {e}; quarantined at {}",
-                    q.display()
-                ));
-            }
-        };
+    // Compressed inputs are kept as-is; ingest decodes them on the fly.
+    if let Err(e) = validate_compressed_input(&original_filename, &source_bytes) {
+        let q = quarantine_dir.join(format!(
+            "{kind}-decode-{}.bad",
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_err(|x| x.to_string())?
+                .as_secs()
+        ));
+        fs::write(&q, &source_bytes).map_err(|x| x.to_string())?;
+        return Err(format!(
+            "decompression failed for {kind}: {e}; quarantined at {}",
+            q.display()
+        ));
+    }
+    let normalized_bytes = source_bytes;
+    let final_name = original_filename;
     let final_path = ingest_inputs_dir.join(format!("{kind}-{final_name}"));
     let tmp_path = tmp_dir.join(format!("{kind}-{final_name}.part"));
     fs::write(&tmp_path, &normalized_bytes).map_err(|e| e.to_string())?;
@@ -277,6 +277,13 @@ fn resolve_single_input(
         ));
     }
     fs::rename(&tmp_path, &final_path).map_err(|e| e.to_string())?;
+    if let Some(local) = local_source {
+        let gzi = gzi_path_for(&local);
+        if gzi.exists() {
+            fs::copy(&gzi, gzi_path_for(&final_path))
+                .map_err(|e| format!("copy gzi index failed for {kind}: {e}"))?;
+        }
+    }
     let checksum = sha256_hex(&normalized_bytes);
     let source = InputLockSource {
         kind: kind.to_string(),
@@ -291,21 +298,30 @@ fn resolve_single_input(
     Ok((final_path, source))
 }
 
-fn decompress_if_needed(filename: String, bytes: &[u8]) -> Result<(Vec<u8>, String), String> {
-    if filename.ends_with(".gz") {
-        let mut decoder = flate2::read::GzDecoder::new(std::io::Cursor::new(bytes));
-        let mut out = Vec::new();
-        std::io::Read::read_to_end(&mut decoder, &mut out).map_err(|e| e.to_string())?;
-        return Ok((out, filename.trim_end_matches(".gz").to_string()));
-    }
-    if filename.ends_with(".zst") {
-        let mut decoder = zstd::stream::read::Decoder::new(std::io::Cursor::new(bytes))
-            .map_err(|e| e.to_string())?;
-        let mut out = Vec::new();
-        std::io::Read::read_to_end(&mut decoder, &mut out).map_err(|e| e.to_string())?;
-        return Ok((out, filename.trim_end_matches(".zst").to_string()));
+fn validate_compressed_input(filename: &str, bytes: &[u8]) -> Result<(), String> {
+    let declared = [".gz", ".bgz", ".zst"]
+        .iter()
+        .any(|suffix| filename.ends_with(suffix));
+    let detected = InputCompression::detect(bytes);
+    match detected {
+        InputCompression::None if declared => Err(format!(
+            "{filename} has a compressed suffix but no gzip/bgzf/zstd header"
+        )),
+        InputCompression::None => Ok(()),
+        InputCompression::Gzip | InputCompression::Bgzf => std::io::copy(
+            &mut flate2::read::MultiGzDecoder::new(std::io::Cursor::new(bytes)),
+            &mut std::io::sink(),
+        )
+        .map(|_| ())
+        .map_err(|e| e.to_string()),
+        InputCompression::Zstd => {
+            let mut decoder = zstd::stream::read::Decoder::new(std::io::Cursor::new(bytes))
+                .map_err(|e| e.to_string())?;
+            std::io::copy(&mut decoder, &mut std::io::sink())
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        }
     }
-    Ok((bytes.to_vec(), filename))
 }
 
 #[cfg(test)]
@@ -354,6 +370,33 @@ mod tests {
         assert!(entries.count() > 0);
     }
 
+    #[test]
+    fn compressed_inputs_are_kept_verbatim_with_gzi_sidecar() {
+        use std::io::Write;
+
+        let td = tempfile::tempdir().expect("tmp");
+        let root = td.path().join("out");
+        let src = td.path().join("genes.gff3.gz");
+        let fasta = td.path().join("genome.fa.gz");
+        let fai = td.path().join("genome.fa.gz.fai");
+        let mut enc = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        enc.write_all(b"chr1\ts\tgene\t1\t2\t.\t+\t.\tID=g1\n")
+            .expect("gzip gff3");
+        let gz = enc.finish().expect("finish gzip");
+        fs::write(&src, &gz).expect("write gff3");
+        fs::write(&fasta, &gz).expect("write fasta");
+        fs::write(td.path().join("genome.fa.gz.gzi"), 0_u64.to_le_bytes()).expect("write gzi");
+        fs::write(&fai, "chr1\t2\t0\t2\t3\n").expect("write fai");
+
+        let verified = resolve_verify_and_lock_inputs(&src, &fasta, &fai, &root, false, false)
+            .expect("lock compressed inputs");
+        assert_eq!(fs::read(&verified.gff3_path).expect("read gff3"), gz);
+        assert!(verified.gff3_path.to_string_lossy().ends_with(".gz"));
+        let mut gzi = verified.fasta_path.into_os_string();
+        gzi.push(".gzi");
+        assert!(std::path::PathBuf::from(gzi).exists());
+    }
+
     #[test]
     fn network_inputs_are_forbidden_by_default() {
         let td = tempfile::tempdir().expect("tmp");
@@ -9,12 +9,13 @@ mod operations;
 pub(crate) mod output;
 
 use crate::app::ingest::{
-    diff_normalized_ids, ingest_dataset, replay_normalized_counts, IngestOptions, TimestampPolicy,
+    diff_normalized_ids, ingest_dataset, replay_normalized_counts, IngestOptions,
+    LiftoverChainInput, TimestampPolicy,
 };
 use crate::app::query::{
     classify_query, explain_query_plan, BiotypePolicy, DuplicateGeneIdPolicy, GeneFields,
     GeneFilter, GeneNamePolicy, GeneQueryRequest, QueryLimits, RegionFilter,
-    SeqidNormalizationPolicy, TranscriptTypePolicy,
+    SeqidNormalizationPolicy, TranscriptSequenceKind, TranscriptTypePolicy,
 };
 use crate::contracts::errors::{ConfigPathScope, ExitCode, MachineError};
 use crate::domain::canonical;
@@ -25,7 +26,7 @@ use clap::{error::ErrorKind, CommandFactory, Parser};
 use clap_complete::{generate, Generator};
 use commands::{
     CatalogCommand, DatasetCommand, DiffCommand, ExportCommand, GcCommand, InspectCommand,
-    QueryCommand,
+    KeysCommand, QueryCommand, SequenceCommand, TranscriptSequenceKindCli,
 };
 use rusqlite::Connection;
 use serde_json::{json, Value};
@@ -66,6 +67,9 @@ struct IngestCliArgs {
     gene_identifier_policy: GeneIdentifierPolicyCli,
     ensembl_keys: String,
     seqid_aliases: String,
+    liftover_chain: Option<PathBuf>,
+    liftover_target_assembly: Option<String>,
+    chrom_alias: Option<PathBuf>,
     max_threads: usize,
     report_only: bool,
     strict: bool,
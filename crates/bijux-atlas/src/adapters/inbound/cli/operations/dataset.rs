@@ -8,6 +8,7 @@ pub(crate) fn validate_dataset(
     species: &str,
     assembly: &str,
     deep: bool,
+    trusted_keys: &[String],
     output_mode: OutputMode,
 ) -> Result<(), String> {
     let dataset = DatasetId::new(release, species, assembly).map_err(|e| e.to_string())?;
@@ -34,6 +35,9 @@ pub(crate) fn validate_dataset(
     validate_canonical_evidence(&paths.derived_dir, &manifest)?;
     validate_sqlite_contract(&paths.sqlite)?;
     validate_shard_catalog_and_indexes(&paths.derived_dir)?;
+    if !trusted_keys.is_empty() {
+        validate_manifest_signature(&paths.derived_dir, &manifest, trusted_keys)?;
+    }
     if !deep {
         validate_dataset_qc_thresholds(&root, &dataset)?;
     }
@@ -92,6 +96,25 @@ pub(crate) fn validate_dataset(
     Ok(())
 }
 
+fn validate_manifest_signature(
+    derived_dir: &Path,
+    manifest: &ArtifactManifest,
+    trusted_keys: &[String],
+) -> Result<(), String> {
+    let trusted =
+        TrustedSigningKeys::from_public_keys_hex(trusted_keys).map_err(|e| e.to_string())?;
+    let lock_path = derived_dir.join("manifest.lock");
+    let lock_raw = fs::read(&lock_path)
+        .map_err(|_| format!("manifest.lock missing: {}", lock_path.display()))?;
+    let lock: ManifestLock = serde_json::from_slice(&lock_raw).map_err(|e| e.to_string())?;
+    let signature = match fs::read(derived_dir.join(MANIFEST_SIGNATURE_FILE)) {
+        Ok(bytes) => Some(bytes),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => return Err(e.to_string()),
+    };
+    verify_manifest_signature(&trusted, manifest, &lock, signature.as_deref())
+}
+
 pub(crate) fn validate_dataset_evidence(
     root: PathBuf,
     release: &str,
@@ -139,7 +162,11 @@ pub(crate) fn validate_dataset_evidence(
                 absolute.display()
             )
         })?;
-        verified.insert(rel.to_string(), sha256_hex(&raw));
+        verified.insert(
+            rel.to_string(),
+            crate::app::ingest::evidence_sha256(&paths, &absolute, &raw)
+                .map_err(|e| e.to_string())?,
+        );
     }
     let payload = json!({
         "schema_version": 1,
@@ -414,11 +441,19 @@ pub(crate) fn publish_dataset(
     release: &str,
     species: &str,
     assembly: &str,
+    sign_key: Option<PathBuf>,
     dry_run: bool,
     explain: bool,
     output_mode: OutputMode,
 ) -> Result<(), String> {
     let dataset = DatasetId::new(release, species, assembly).map_err(|e| e.to_string())?;
+    let signing_key = sign_key
+        .map(|path| {
+            let seed = fs::read_to_string(&path)
+                .map_err(|e| format!("sign key read failed {}: {e}", path.display()))?;
+            ArtifactSigningKey::from_seed_hex(seed.trim()).map_err(|e| e.to_string())
+        })
+        .transpose()?;
     let source_paths = crate::domain::dataset::artifact_paths(&source_root, &dataset);
     let manifest_bytes = fs::read(&source_paths.manifest).map_err(|e| e.to_string())?;
     let sqlite_bytes = fs::read(&source_paths.sqlite).map_err(|e| e.to_string())?;
@@ -451,12 +486,16 @@ pub(crate) fn publish_dataset(
                     "manifest_sha256": manifest_sha,
                     "sqlite_sha256": sqlite_sha
                 },
+                "signing_key_id": signing_key.as_ref().map(ArtifactSigningKey::key_id),
                 "writes_artifacts": false
             }),
         );
     }
 
-    let store = LocalFsStore::new(store_root);
+    let mut store = LocalFsStore::new(store_root);
+    if let Some(key) = signing_key {
+        store = store.with_signing_key(Arc::new(key));
+    }
     match store.put_dataset(
         &dataset,
         &manifest_bytes,
@@ -0,0 +1,124 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::adapters::inbound::cli::commands::ColumnarFormat;
+use crate::adapters::outbound::columnar::{
+    dataset_tables, for_each_table_batch, table_schema, ArrowStreamWriter, ColumnSpec, ColumnType,
+    ParquetWriter,
+};
+use std::io::BufWriter;
+
+pub(crate) struct DatasetExportArgs {
+    pub root: PathBuf,
+    pub release: String,
+    pub species: String,
+    pub assembly: String,
+    pub out_dir: PathBuf,
+    pub format: ColumnarFormat,
+    pub tables: Vec<String>,
+}
+
+fn columnar_file_extension(format: ColumnarFormat) -> &'static str {
+    match format {
+        ColumnarFormat::Parquet => "parquet",
+        ColumnarFormat::Arrow => "arrows",
+    }
+}
+
+fn column_type_label(column_type: ColumnType) -> &'static str {
+    match column_type {
+        ColumnType::Int64 => "int64",
+        ColumnType::Float64 => "float64",
+        ColumnType::Utf8 => "utf8",
+        ColumnType::Bool => "bool",
+        ColumnType::Binary => "binary",
+    }
+}
+
+fn export_table(
+    conn: &rusqlite::Connection,
+    table: &str,
+    schema: &[ColumnSpec],
+    out: &Path,
+    format: ColumnarFormat,
+) -> Result<usize, String> {
+    let file = BufWriter::new(
+        fs::File::create(out).map_err(|e| format!("failed to create {}: {e}", out.display()))?,
+    );
+    match format {
+        ColumnarFormat::Parquet => {
+            let mut writer =
+                ParquetWriter::new(file, schema.to_vec()).map_err(|e| e.to_string())?;
+            let rows =
+                for_each_table_batch(conn, table, schema, |batch| writer.write_batch(batch))?;
+            writer.finish().map_err(|e| e.to_string())?;
+            Ok(rows)
+        }
+        ColumnarFormat::Arrow => {
+            let mut writer =
+                ArrowStreamWriter::new(file, schema.to_vec()).map_err(|e| e.to_string())?;
+            let rows =
+                for_each_table_batch(conn, table, schema, |batch| writer.write_batch(batch))?;
+            writer.finish().map_err(|e| e.to_string())?;
+            Ok(rows)
+        }
+    }
+}
+
+/// Dumps every table of a dataset SQLite (or the selected ones) to `<out_dir>/<table>.<ext>`.
+pub(crate) fn export_dataset_tables(
+    args: DatasetExportArgs,
+    output_mode: OutputMode,
+) -> Result<(), String> {
+    let dataset =
+        DatasetId::new(&args.release, &args.species, &args.assembly).map_err(|e| e.to_string())?;
+    let paths = crate::domain::dataset::artifact_paths(&args.root, &dataset);
+    let conn = crate::adapters::outbound::sqlite::open_readonly_no_mutex(&paths.sqlite)
+        .map_err(|e| e.to_string())?;
+    let available = dataset_tables(&conn).map_err(|e| e.to_string())?;
+    let selected = if args.tables.is_empty() {
+        available
+    } else {
+        for table in &args.tables {
+            if !available.contains(table) {
+                return Err(format!(
+                    "unknown table `{table}`; available: {}",
+                    available.join(", ")
+                ));
+            }
+        }
+        args.tables
+    };
+    fs::create_dir_all(&args.out_dir).map_err(|e| e.to_string())?;
+    let extension = columnar_file_extension(args.format);
+    let mut exported = Vec::new();
+    for table in &selected {
+        let schema = table_schema(&conn, table).map_err(|e| e.to_string())?;
+        let out = args.out_dir.join(format!("{table}.{extension}"));
+        let rows = export_table(&conn, table, &schema, &out, args.format)?;
+        exported.push(json!({
+            "table": table,
+            "rows": rows,
+            "path": out,
+            "columns": schema
+                .iter()
+                .map(|spec| json!({
+                    "name": spec.name,
+                    "type": column_type_label(spec.column_type),
+                    "nullable": spec.nullable,
+                }))
+                .collect::<Vec<_>>(),
+        }));
+    }
+    emit_ok_payload(
+        output_mode,
+        json!({
+            "command": "atlas dataset export",
+            "status": "ok",
+            "dataset": dataset.canonical_string(),
+            "format": extension,
+            "out_dir": args.out_dir,
+            "tables": exported,
+        }),
+    )
+}
@@ -0,0 +1,118 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::adapters::outbound::api_keys::{ApiKeyRegistry, NewApiKey};
+
+pub(crate) struct CreateApiKeyArgs {
+    pub registry: PathBuf,
+    pub key_id: String,
+    pub owner: String,
+    pub scopes: Vec<String>,
+    pub datasets: Vec<String>,
+    pub tier: Option<String>,
+    pub expires_in_days: Option<u64>,
+}
+
+pub(crate) fn keys_create(args: CreateApiKeyArgs, output_mode: OutputMode) -> Result<(), String> {
+    let now = unix_now_secs()?;
+    let expires_at_unix_s = args
+        .expires_in_days
+        .map(|days| now.saturating_add(days.saturating_mul(86_400)));
+    let api_key = ApiKeyRegistry::edit(&args.registry, |registry| {
+        registry.create(
+            NewApiKey {
+                key_id: args.key_id.clone(),
+                owner: args.owner.clone(),
+                scopes: args.scopes,
+                datasets: args.datasets,
+                rate_limit_tier: args.tier,
+                expires_at_unix_s,
+            },
+            now,
+        )
+    })?;
+    emit_ok_payload(
+        output_mode,
+        json!({
+            "registry": args.registry.display().to_string(),
+            "key_id": args.key_id,
+            "owner": args.owner,
+            "expires_at_unix_s": expires_at_unix_s,
+            "api_key": api_key,
+            "note": "the registry keeps only the sha256 digest; store this key now"
+        }),
+    )
+}
+
+pub(crate) fn keys_list(registry_path: PathBuf, output_mode: OutputMode) -> Result<(), String> {
+    let registry = ApiKeyRegistry::read(&registry_path)?;
+    emit_ok_payload(
+        output_mode,
+        json!({
+            "registry": registry_path.display().to_string(),
+            "tiers": registry.tiers,
+            "keys": registry.summaries(unix_now_secs()?),
+        }),
+    )
+}
+
+pub(crate) fn keys_revoke(
+    registry_path: PathBuf,
+    key_id: &str,
+    output_mode: OutputMode,
+) -> Result<(), String> {
+    let now = unix_now_secs()?;
+    let revoked_at_unix_s = ApiKeyRegistry::edit(&registry_path, |registry| {
+        registry.revoke(key_id, now)?;
+        Ok(registry
+            .keys
+            .iter()
+            .find(|record| record.key_id == key_id)
+            .and_then(|record| record.revoked_at_unix_s))
+    })?;
+    emit_ok_payload(
+        output_mode,
+        json!({
+            "registry": registry_path.display().to_string(),
+            "key_id": key_id,
+            "revoked_at_unix_s": revoked_at_unix_s
+        }),
+    )
+}
+
+pub(crate) fn keys_rotate(
+    registry_path: PathBuf,
+    key_id: &str,
+    next_key_id: &str,
+    overlap_secs: u64,
+    output_mode: OutputMode,
+) -> Result<(), String> {
+    let now = unix_now_secs()?;
+    let (api_key, previous_expires_at_unix_s) = ApiKeyRegistry::edit(&registry_path, |registry| {
+        let api_key = registry.rotate(key_id, next_key_id, overlap_secs, now)?;
+        let previous_expires_at_unix_s = registry
+            .keys
+            .iter()
+            .find(|record| record.key_id == key_id)
+            .and_then(|record| record.expires_at_unix_s);
+        Ok((api_key, previous_expires_at_unix_s))
+    })?;
+    emit_ok_payload(
+        output_mode,
+        json!({
+            "registry": registry_path.display().to_string(),
+            "key_id": next_key_id,
+            "previous_key_id": key_id,
+            "previous_expires_at_unix_s": previous_expires_at_unix_s,
+            "api_key": api_key,
+            "note": "the registry keeps only the sha256 digest; store this key now"
+        }),
+    )
+}
+
+fn unix_now_secs() -> Result<u64, String> {
+    Ok(std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_err(|e| e.to_string())?
+        .as_secs())
+}
@@ -2,8 +2,9 @@
 
 use crate::adapters::inbound::cli::OutputMode;
 use crate::adapters::outbound::store::{
-    canonical_catalog_json, sorted_catalog_entries, verify_expected_sha256, ArtifactStore,
-    LocalFsStore, ManifestLock, StoreErrorCode,
+    canonical_catalog_json, sorted_catalog_entries, verify_expected_sha256,
+    verify_manifest_signature, ArtifactStore, LocalFsStore, ManifestLock, StoreErrorCode,
+    MANIFEST_SIGNATURE_FILE,
 };
 use crate::domain::canonical;
 use crate::domain::dataset::{
@@ -13,18 +14,26 @@ use crate::domain::policy::{
     canonical_config_json, load_policy_from_workspace, resolve_mode_profile, PolicyMode,
 };
 use crate::domain::query::ReleaseGeneIndex;
+use crate::domain::security::data_protection::{ArtifactSigningKey, TrustedSigningKeys};
 use crate::domain::sha256_hex;
 use serde_json::json;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use tar::{Archive, Builder, Header};
 
 mod diff;
+mod export;
 mod gc;
+mod keys;
+mod sequence;
 
 pub(crate) use diff::{build_release_diff, BuildReleaseDiffArgs};
+pub(crate) use export::{export_dataset_tables, DatasetExportArgs};
 pub(crate) use gc::{gc_apply, gc_plan};
+pub(crate) use keys::{keys_create, keys_list, keys_revoke, keys_rotate, CreateApiKeyArgs};
+pub(crate) use sequence::{region_sequence, transcript_sequence, TranscriptSequenceArgs};
 
 mod dataset;
 
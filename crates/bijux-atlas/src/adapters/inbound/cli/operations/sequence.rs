@@ -0,0 +1,138 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::app::ingest::read_decoded_window;
+use crate::domain::query::{
+    fasta_record, parse_fai_index, query_transcript_layout, FaiRecord, GeneticCode, Region,
+    TranscriptLayout, TranscriptSequenceKind,
+};
+
+pub(crate) struct TranscriptSequenceArgs {
+    pub db: PathBuf,
+    pub fasta: PathBuf,
+    pub fai: Option<PathBuf>,
+    pub transcript_id: String,
+    pub kind: TranscriptSequenceKind,
+    pub genetic_code: Option<u8>,
+}
+
+fn default_fai_path(fasta: &Path) -> PathBuf {
+    let mut raw = fasta.as_os_str().to_os_string();
+    raw.push(".fai");
+    PathBuf::from(raw)
+}
+
+fn read_fai_entry(fai: &Path, seqid: &str) -> Result<FaiRecord, String> {
+    let content =
+        fs::read_to_string(fai).map_err(|e| format!("failed to read {}: {e}", fai.display()))?;
+    parse_fai_index(&content)?
+        .remove(seqid)
+        .ok_or_else(|| format!("contig not found in {}: {seqid}", fai.display()))
+}
+
+fn read_fasta_slice(
+    fasta: &Path,
+    fai: &FaiRecord,
+    start: u64,
+    end: u64,
+) -> Result<Vec<u8>, String> {
+    if start == 0 || end < start || end > fai.len {
+        return Err(format!(
+            "region {start}-{end} outside contig bounds 1-{}",
+            fai.len
+        ));
+    }
+    fai.read_bases(start, end, |offset, len| {
+        read_decoded_window(fasta, offset, len).map_err(|e| e.to_string())
+    })
+}
+
+fn emit_sequence(
+    output_mode: OutputMode,
+    header: &str,
+    mut payload: serde_json::Value,
+    sequence: &[u8],
+) -> Result<(), String> {
+    let text = String::from_utf8_lossy(sequence);
+    if output_mode.json {
+        payload["length"] = json!(sequence.len());
+        payload["sequence"] = json!(text);
+        return emit_ok_payload(output_mode, payload);
+    }
+    print!("{}", fasta_record(header, sequence));
+    Ok(())
+}
+
+pub(crate) fn transcript_sequence(
+    args: TranscriptSequenceArgs,
+    output_mode: OutputMode,
+) -> Result<(), String> {
+    let requested_code = args
+        .genetic_code
+        .map(|id| {
+            GeneticCode::by_id(id).ok_or_else(|| format!("unknown NCBI genetic code table: {id}"))
+        })
+        .transpose()?;
+    let conn = rusqlite::Connection::open(&args.db).map_err(|e| e.to_string())?;
+    let layout: TranscriptLayout = query_transcript_layout(&conn, &args.transcript_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("transcript not found: {}", args.transcript_id))?;
+    let code = requested_code.unwrap_or_else(|| GeneticCode::default_for_contig(&layout.seqid));
+    if args.kind != TranscriptSequenceKind::Cdna && layout.cds.is_empty() {
+        return Err(format!(
+            "transcript {} has no CDS segments",
+            args.transcript_id
+        ));
+    }
+    let fai_path = args.fai.unwrap_or_else(|| default_fai_path(&args.fasta));
+    let fai = read_fai_entry(&fai_path, &layout.seqid)?;
+    let sequence = layout.assemble(args.kind, code, |start, end| {
+        read_fasta_slice(&args.fasta, &fai, start, end)
+    })?;
+    let mut header = format!(
+        "{} kind={} strand={}",
+        layout.transcript_id,
+        args.kind.as_str(),
+        layout.strand.as_symbol()
+    );
+    if args.kind == TranscriptSequenceKind::Protein {
+        header.push_str(&format!(" genetic_code={}", code.id));
+    }
+    emit_sequence(
+        output_mode,
+        &header,
+        json!({
+            "command": "atlas sequence transcript",
+            "transcript": {
+                "transcript_id": layout.transcript_id,
+                "seqid": layout.seqid,
+                "strand": layout.strand.as_symbol(),
+                "kind": args.kind.as_str(),
+                "genetic_code": code.id,
+            },
+        }),
+        &sequence,
+    )
+}
+
+pub(crate) fn region_sequence(
+    fasta: PathBuf,
+    fai: Option<PathBuf>,
+    region: &str,
+    output_mode: OutputMode,
+) -> Result<(), String> {
+    let region = Region::parse(region).map_err(|e| format!("invalid region {region}: {e}"))?;
+    let (seqid, start, end) = (region.seqid.as_str(), region.start, region.end);
+    let fai_path = fai.unwrap_or_else(|| default_fai_path(&fasta));
+    let entry = read_fai_entry(&fai_path, seqid)?;
+    let sequence = read_fasta_slice(&fasta, &entry, start, end)?;
+    emit_sequence(
+        output_mode,
+        &format!("{seqid}:{start}-{end}"),
+        json!({
+            "command": "atlas sequence region",
+            "region": {"seqid": seqid, "start": start, "end": end},
+        }),
+        &sequence,
+    )
+}
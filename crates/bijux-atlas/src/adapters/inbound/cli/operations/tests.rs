@@ -1,9 +1,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::{
-    build_release_diff, compute_gc_plan, gc_apply, promote_catalog, update_latest_alias,
-    validate_qc_thresholds, validate_shard_catalog_and_indexes, BuildReleaseDiffArgs, OutputMode,
+    build_release_diff, compute_gc_plan, gc_apply, keys_create, keys_revoke, keys_rotate,
+    promote_catalog, update_latest_alias, validate_qc_thresholds,
+    validate_shard_catalog_and_indexes, BuildReleaseDiffArgs, CreateApiKeyArgs, OutputMode,
 };
+use crate::adapters::outbound::api_keys::ApiKeyRegistry;
 use crate::domain::dataset::{
     artifact_paths, ArtifactChecksums, ArtifactManifest, Catalog, CatalogEntry, DatasetId,
     ManifestStats, ShardCatalog, ShardEntry, ShardId,
@@ -514,3 +516,66 @@ fn latest_alias_update_writes_traceable_record_after_promotion() {
     let catalog_bytes = fs::read(root.join("catalog.json")).expect("catalog bytes");
     assert_eq!(alias.catalog_sha256, sha256_hex(&catalog_bytes));
 }
+
+#[test]
+fn keys_commands_keep_only_digests_and_track_rotation_and_revocation() {
+    let root = tempdir().expect("tempdir");
+    let registry = root.path().join("keys/api-keys.json");
+    keys_create(
+        CreateApiKeyArgs {
+            registry: registry.clone(),
+            key_id: "curation".to_string(),
+            owner: "curation-team".to_string(),
+            scopes: vec!["dataset.read".to_string()],
+            datasets: vec!["110/homo_sapiens/*".to_string()],
+            tier: None,
+            expires_in_days: Some(30),
+        },
+        OutputMode { json: true },
+    )
+    .expect("create");
+    let created = ApiKeyRegistry::read(&registry).expect("registry");
+    assert_eq!(created.keys.len(), 1);
+    assert_eq!(created.keys[0].key_hash.len(), 64);
+    assert!(created.keys[0].expires_at_unix_s.is_some());
+    assert!(!fs::read_to_string(&registry)
+        .expect("registry text")
+        .contains("atlas_"));
+
+    let duplicate = keys_create(
+        CreateApiKeyArgs {
+            registry: registry.clone(),
+            key_id: "curation".to_string(),
+            owner: "curation-team".to_string(),
+            scopes: Vec::new(),
+            datasets: Vec::new(),
+            tier: Some("bulk".to_string()),
+            expires_in_days: None,
+        },
+        OutputMode { json: true },
+    )
+    .expect_err("duplicate key id");
+    assert!(duplicate.contains("already exists"));
+
+    keys_rotate(
+        registry.clone(),
+        "curation",
+        "curation-2",
+        60,
+        OutputMode { json: true },
+    )
+    .expect("rotate");
+    keys_revoke(registry.clone(), "curation", OutputMode { json: true }).expect("revoke");
+    let rotated = ApiKeyRegistry::read(&registry).expect("registry");
+    let next = rotated
+        .keys
+        .iter()
+        .find(|record| record.key_id == "curation-2")
+        .expect("rotated key");
+    assert_eq!(next.datasets, vec!["110/homo_sapiens/*".to_string()]);
+    assert!(rotated
+        .keys
+        .iter()
+        .any(|record| record.key_id == "curation" && record.revoked_at_unix_s.is_some()));
+    assert!(keys_revoke(registry, "missing", OutputMode { json: true }).is_err());
+}
@@ -94,6 +94,14 @@ pub(crate) fn query_request_from_json(v: &Value) -> Result<GeneQueryRequest, Str
     } else {
         None
     };
+    let xref = v
+        .get("xref")
+        .and_then(Value::as_str)
+        .map(ToString::to_string);
+    let alias = v
+        .get("alias")
+        .and_then(Value::as_str)
+        .map(ToString::to_string);
     let limit = v.get("limit").and_then(Value::as_u64).unwrap_or(10) as usize;
     let allow_full_scan = v
         .get("allow_full_scan")
@@ -111,6 +119,9 @@ pub(crate) fn query_request_from_json(v: &Value) -> Result<GeneQueryRequest, Str
             sort: QuerySort::Auto,
             interval: IntervalSemantics::Overlap,
             strand: StrandMode::Any,
+            xref,
+            alias,
+            ..GeneFilter::default()
         },
         limit,
         cursor: None,
@@ -0,0 +1,471 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::atlas_client::ClientLogger;
+use super::config::ClientConfig;
+use super::error::{ClientError, ErrorClass};
+use super::metrics::ClientMetrics;
+use super::pagination::{Page, PaginationCursor};
+use super::query::{DatasetQuery, DiffQuery, SequenceQuery, TranscriptSequenceQuery};
+use super::request::RequestBuilder;
+use super::response::decode_response;
+use super::retry::run_with_retry_async;
+use super::tracing::TraceContext;
+use crate::contracts::api::dto::{
+    CatalogEntryDto, DatasetItemDto, DatasetListDto, DiffPageDto, DiffRecordDto, GenePageDto,
+    GeneRowDto, GeneTranscriptsDto, ResponseEnvelopeDto, SequenceDto, TranscriptDto,
+    TranscriptExonsDto, TranscriptIntronsDto, TranscriptRowDto, TranscriptUtrsDto, VersionDto,
+};
+use crate::contracts::api::DatasetKeyDto;
+use futures::Stream;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::Client as HttpClient;
+use serde::de::DeserializeOwned;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Async client over the v1 API for tokio-based services.
+///
+/// Shares configuration, retry policy, metrics and logging hooks with
+/// [`AtlasClient`](super::AtlasClient) and decodes responses into the
+/// `contracts::api` DTOs.
+#[derive(Clone)]
+pub struct AsyncAtlasClient {
+    config: ClientConfig,
+    http: HttpClient,
+    metrics: Option<Arc<dyn ClientMetrics>>,
+    logger: Option<Arc<dyn ClientLogger>>,
+    trace: Option<TraceContext>,
+}
+
+impl AsyncAtlasClient {
+    /// Creates a configured client with validated base URL and default headers.
+    ///
+    /// # Errors
+    /// Returns [`ClientError`] when configuration validation fails, headers are
+    /// invalid, or the underlying HTTP client cannot be constructed.
+    pub fn new(config: ClientConfig) -> Result<Self, ClientError> {
+        config
+            .validate()
+            .map_err(|err| ClientError::new(ErrorClass::InvalidConfig, err))?;
+
+        let mut headers = HeaderMap::new();
+        for (k, v) in &config.default_headers {
+            let name = HeaderName::from_bytes(k.as_bytes())
+                .map_err(|_| ClientError::new(ErrorClass::InvalidConfig, "invalid header name"))?;
+            let value = HeaderValue::from_str(v)
+                .map_err(|_| ClientError::new(ErrorClass::InvalidConfig, "invalid header value"))?;
+            headers.insert(name, value);
+        }
+
+        let http = HttpClient::builder()
+            .timeout(Duration::from_millis(config.timeout_millis))
+            .default_headers(headers)
+            .build()
+            .map_err(|err| ClientError::new(ErrorClass::Transport, err.to_string()))?;
+
+        Ok(Self {
+            config,
+            http,
+            metrics: None,
+            logger: None,
+            trace: None,
+        })
+    }
+
+    #[must_use]
+    pub fn with_metrics(mut self, metrics: Arc<dyn ClientMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    #[must_use]
+    pub fn with_logger(mut self, logger: Arc<dyn ClientLogger>) -> Self {
+        self.logger = Some(logger);
+        self
+    }
+
+    /// Propagates request and trace ids on every request made by the returned client.
+    #[must_use]
+    pub fn with_trace(mut self, trace: TraceContext) -> Self {
+        self.trace = Some(trace);
+        self
+    }
+
+    /// Fetches one page of `/v1/genes`.
+    ///
+    /// # Errors
+    /// Returns [`ClientError`] when transport, decoding, or response
+    /// classification fails.
+    pub async fn genes(&self, query: &DatasetQuery) -> Result<Page<GeneRowDto>, ClientError> {
+        let envelope: ResponseEnvelopeDto<GenePageDto> = self.get(&query.to_request()).await?;
+        Ok(page_of(envelope, |data| data.rows))
+    }
+
+    /// Streams every page of `/v1/genes`, starting at `query.cursor`.
+    pub fn gene_pages(
+        &self,
+        query: &DatasetQuery,
+    ) -> impl Stream<Item = Result<Page<GeneRowDto>, ClientError>> + '_ {
+        let query = query.clone();
+        page_stream(query.cursor.clone(), move |cursor| {
+            let mut current = query.clone();
+            current.cursor = cursor;
+            async move { self.genes(&current).await }
+        })
+    }
+
+    /// Fetches one page of `/v1/genes/{gene_id}/transcripts`.
+    ///
+    /// # Errors
+    /// Returns [`ClientError`] if the request fails.
+    pub async fn gene_transcripts(
+        &self,
+        dataset: &DatasetKeyDto,
+        gene_id: &str,
+        limit: u32,
+        cursor: Option<&str>,
+    ) -> Result<Page<TranscriptRowDto>, ClientError> {
+        let request = RequestBuilder::new(format!("/v1/genes/{}/transcripts", segment(gene_id)))
+            .with_endpoint("/v1/genes/{gene_id}/transcripts")
+            .with_dataset(dataset)
+            .with_param("limit", limit.to_string())
+            .with_optional_param("cursor", cursor);
+        let envelope: ResponseEnvelopeDto<GeneTranscriptsDto> = self.get(&request).await?;
+        Ok(page_of(envelope, |data| data.rows))
+    }
+
+    /// Streams every page of `/v1/genes/{gene_id}/transcripts`.
+    pub fn gene_transcript_pages<'a>(
+        &'a self,
+        dataset: &DatasetKeyDto,
+        gene_id: &str,
+        limit: u32,
+    ) -> impl Stream<Item = Result<Page<TranscriptRowDto>, ClientError>> + 'a {
+        let dataset = dataset.clone();
+        let gene_id = gene_id.to_string();
+        page_stream(None, move |cursor| {
+            let dataset = dataset.clone();
+            let gene_id = gene_id.clone();
+            async move {
+                self.gene_transcripts(&dataset, &gene_id, limit, cursor.as_deref())
+                    .await
+            }
+        })
+    }
+
+    /// Fetches `/v1/transcripts/{tx_id}`.
+    ///
+    /// # Errors
+    /// Returns [`ClientError`] if the request fails.
+    pub async fn transcript(
+        &self,
+        dataset: &DatasetKeyDto,
+        tx_id: &str,
+    ) -> Result<ResponseEnvelopeDto<TranscriptDto>, ClientError> {
+        self.get_transcript_resource(dataset, tx_id, "").await
+    }
+
+    /// Fetches `/v1/transcripts/{tx_id}/exons`, 5' to 3'.
+    ///
+    /// # Errors
+    /// Returns [`ClientError`] if the request fails.
+    pub async fn transcript_exons(
+        &self,
+        dataset: &DatasetKeyDto,
+        tx_id: &str,
+    ) -> Result<ResponseEnvelopeDto<TranscriptExonsDto>, ClientError> {
+        self.get_transcript_resource(dataset, tx_id, "/exons").await
+    }
+
+    /// Fetches `/v1/transcripts/{tx_id}/introns`, 5' to 3'.
+    ///
+    /// # Errors
+    /// Returns [`ClientError`] if the request fails.
+    pub async fn transcript_introns(
+        &self,
+        dataset: &DatasetKeyDto,
+        tx_id: &str,
+    ) -> Result<ResponseEnvelopeDto<TranscriptIntronsDto>, ClientError> {
+        self.get_transcript_resource(dataset, tx_id, "/introns")
+            .await
+    }
+
+    /// Fetches `/v1/transcripts/{tx_id}/utrs`, 5' to 3'.
+    ///
+    /// # Errors
+    /// Returns [`ClientError`] if the request fails.
+    pub async fn transcript_utrs(
+        &self,
+        dataset: &DatasetKeyDto,
+        tx_id: &str,
+    ) -> Result<ResponseEnvelopeDto<TranscriptUtrsDto>, ClientError> {
+        self.get_transcript_resource(dataset, tx_id, "/utrs").await
+    }
+
+    /// Fetches `/v1/sequence/region` for a `seqid:start-end` region.
+    ///
+    /// # Errors
+    /// Returns [`ClientError`] if the request fails.
+    pub async fn region_sequence(
+        &self,
+        dataset: &DatasetKeyDto,
+        region: &str,
+        options: &SequenceQuery,
+    ) -> Result<ResponseEnvelopeDto<SequenceDto>, ClientError> {
+        let request = options.apply(
+            RequestBuilder::new("/v1/sequence/region")
+                .with_dataset(dataset)
+                .with_param("region", region),
+        );
+        self.get(&request).await
+    }
+
+    /// Fetches `/v1/genes/{gene_id}/sequence`.
+    ///
+    /// # Errors
+    /// Returns [`ClientError`] if the request fails.
+    pub async fn gene_sequence(
+        &self,
+        dataset: &DatasetKeyDto,
+        gene_id: &str,
+        options: &SequenceQuery,
+    ) -> Result<ResponseEnvelopeDto<SequenceDto>, ClientError> {
+        let request = options.apply(
+            RequestBuilder::new(format!("/v1/genes/{}/sequence", segment(gene_id)))
+                .with_endpoint("/v1/genes/{gene_id}/sequence")
+                .with_dataset(dataset),
+        );
+        self.get(&request).await
+    }
+
+    /// Fetches `/v1/transcripts/{tx_id}/sequence`.
+    ///
+    /// # Errors
+    /// Returns [`ClientError`] if the request fails.
+    pub async fn transcript_sequence(
+        &self,
+        dataset: &DatasetKeyDto,
+        tx_id: &str,
+        options: &TranscriptSequenceQuery,
+    ) -> Result<ResponseEnvelopeDto<SequenceDto>, ClientError> {
+        let request = RequestBuilder::new(format!("/v1/transcripts/{}/sequence", segment(tx_id)))
+            .with_endpoint("/v1/transcripts/{tx_id}/sequence")
+            .with_dataset(dataset)
+            .with_optional_param("kind", options.kind.as_deref())
+            .with_optional_param(
+                "genetic_code",
+                options.genetic_code.map(|code| code.to_string()),
+            );
+        self.get(&request).await
+    }
+
+    /// Fetches one page of `/v1/diff/genes`, or `/v1/diff/region` when the
+    /// query carries a region.
+    ///
+    /// # Errors
+    /// Returns [`ClientError`] if the request fails.
+    pub async fn diff(&self, query: &DiffQuery) -> Result<Page<DiffRecordDto>, ClientError> {
+        let path = if query.region.is_some() {
+            "/v1/diff/region"
+        } else {
+            "/v1/diff/genes"
+        };
+        let request = RequestBuilder::new(path)
+            .with_param("species", &query.species)
+            .with_param("assembly", &query.assembly)
+            .with_optional_param("to_assembly", query.to_assembly.as_deref())
+            .with_param("from_release", &query.from_release)
+            .with_param("to_release", &query.to_release)
+            .with_param("limit", query.limit.to_string())
+            .with_optional_param("region", query.region.as_deref())
+            .with_optional_param("cursor", query.cursor.as_deref());
+        let envelope: ResponseEnvelopeDto<DiffPageDto> = self.get(&request).await?;
+        Ok(page_of(envelope, |data| data.diff))
+    }
+
+    /// Streams every page of a diff, starting at `query.cursor`.
+    pub fn diff_pages(
+        &self,
+        query: &DiffQuery,
+    ) -> impl Stream<Item = Result<Page<DiffRecordDto>, ClientError>> + '_ {
+        let query = query.clone();
+        page_stream(query.cursor.clone(), move |cursor| {
+            let mut current = query.clone();
+            current.cursor = cursor;
+            async move { self.diff(&current).await }
+        })
+    }
+
+    /// Fetches one page of the dataset catalog from `/v1/datasets`.
+    ///
+    /// # Errors
+    /// Returns [`ClientError`] if the request fails.
+    pub async fn datasets(
+        &self,
+        limit: u32,
+        cursor: Option<&str>,
+    ) -> Result<Page<CatalogEntryDto>, ClientError> {
+        let request = RequestBuilder::new("/v1/datasets")
+            .with_param("limit", limit.to_string())
+            .with_optional_param("cursor", cursor);
+        let envelope: ResponseEnvelopeDto<DatasetListDto> = self.get(&request).await?;
+        Ok(page_of(envelope, |data| data.items))
+    }
+
+    /// Streams every page of the dataset catalog.
+    pub fn dataset_pages(
+        &self,
+        limit: u32,
+    ) -> impl Stream<Item = Result<Page<CatalogEntryDto>, ClientError>> + '_ {
+        page_stream(None, move |cursor| async move {
+            self.datasets(limit, cursor.as_deref()).await
+        })
+    }
+
+    /// Fetches `/v1/datasets/{release}/{species}/{assembly}`.
+    ///
+    /// # Errors
+    /// Returns [`ClientError`] if the request fails.
+    pub async fn dataset(
+        &self,
+        dataset: &DatasetKeyDto,
+    ) -> Result<ResponseEnvelopeDto<DatasetItemDto>, ClientError> {
+        let request = RequestBuilder::new(format!(
+            "/v1/datasets/{}/{}/{}",
+            segment(&dataset.release),
+            segment(&dataset.species),
+            segment(&dataset.assembly)
+        ))
+        .with_endpoint("/v1/datasets/{release}/{species}/{assembly}");
+        self.get(&request).await
+    }
+
+    /// Fetches `/v1/version`.
+    ///
+    /// # Errors
+    /// Returns [`ClientError`] if the request fails.
+    pub async fn version(&self) -> Result<VersionDto, ClientError> {
+        self.get(&RequestBuilder::new("/v1/version")).await
+    }
+
+    async fn get_transcript_resource<T: DeserializeOwned>(
+        &self,
+        dataset: &DatasetKeyDto,
+        tx_id: &str,
+        suffix: &str,
+    ) -> Result<T, ClientError> {
+        let request = RequestBuilder::new(format!("/v1/transcripts/{}{suffix}", segment(tx_id)))
+            .with_endpoint(format!("/v1/transcripts/{{tx_id}}{suffix}"))
+            .with_dataset(dataset);
+        self.get(&request).await
+    }
+
+    async fn get<T: DeserializeOwned>(&self, request: &RequestBuilder) -> Result<T, ClientError> {
+        run_with_retry_async(
+            self.config.retry_attempts,
+            self.config.retry_backoff_millis,
+            || self.attempt(request),
+        )
+        .await
+    }
+
+    async fn attempt<T: DeserializeOwned>(
+        &self,
+        request: &RequestBuilder,
+    ) -> Result<T, ClientError> {
+        let started = Instant::now();
+        let url = format!("{}{}", self.config.base_url, request.path());
+        let mut call = self.http.get(url).query(request.query());
+        if let Some(trace) = &self.trace {
+            if let Some(request_id) = &trace.request_id {
+                call = call.header("x-request-id", request_id);
+            }
+            if let Some(trace_id) = &trace.trace_id {
+                call = call.header("x-trace-id", trace_id);
+            }
+        }
+        let response = call.send().await.map_err(|err| {
+            if err.is_timeout() {
+                ClientError::new(ErrorClass::Timeout, err.to_string())
+            } else {
+                ClientError::new(ErrorClass::Transport, err.to_string())
+            }
+        })?;
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|err| ClientError::new(ErrorClass::Transport, err.to_string()))?;
+        let elapsed = started.elapsed().as_millis();
+
+        if let Some(logger) = &self.logger {
+            logger.log(&format!(
+                "atlas-client request path={} status={}",
+                request.path(),
+                status
+            ));
+        }
+        if let Some(metrics) = &self.metrics {
+            metrics.observe_request(request.endpoint(), elapsed, status.is_success());
+        }
+        decode_response(status, &body)
+    }
+}
+
+impl SequenceQuery {
+    fn apply(&self, request: RequestBuilder) -> RequestBuilder {
+        let mut request = request
+            .with_optional_param("strand", self.strand.as_deref())
+            .with_optional_param("upstream", self.upstream.map(|v| v.to_string()))
+            .with_optional_param("downstream", self.downstream.map(|v| v.to_string()))
+            .with_optional_param("mask", self.mask.as_deref());
+        if self.include_stats {
+            request = request.with_param("include_stats", "true");
+        }
+        request
+    }
+}
+
+fn page_of<T, R>(envelope: ResponseEnvelopeDto<T>, rows: impl FnOnce(T) -> Vec<R>) -> Page<R> {
+    let next = envelope
+        .next_cursor()
+        .map(|cursor| PaginationCursor(cursor.to_string()));
+    Page {
+        items: rows(envelope.data),
+        next,
+    }
+}
+
+/// Follows `next_cursor` from `cursor` until a page comes back without one;
+/// the first error ends the stream.
+fn page_stream<T, F, Fut>(
+    cursor: Option<String>,
+    fetch: F,
+) -> impl Stream<Item = Result<Page<T>, ClientError>>
+where
+    F: FnMut(Option<String>) -> Fut,
+    Fut: Future<Output = Result<Page<T>, ClientError>>,
+{
+    futures::stream::try_unfold((Some(cursor), fetch), |(cursor, mut fetch)| async move {
+        let Some(cursor) = cursor else {
+            return Ok(None);
+        };
+        let page = fetch(cursor).await?;
+        let next = page.next.as_ref().map(|next| Some(next.0.clone()));
+        Ok(Some((page, (next, fetch))))
+    })
+}
+
+/// Percent-encodes one path segment so ids cannot alter the route.
+fn segment(raw: &str) -> String {
+    let mut out = String::with_capacity(raw.len());
+    for byte in raw.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    out
+}
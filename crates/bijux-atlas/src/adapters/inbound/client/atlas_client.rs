@@ -6,6 +6,7 @@ use super::metrics::ClientMetrics;
 use super::pagination::{Page, PaginationCursor};
 use super::query::{DatasetQuery, QueryResult, StreamQuery};
 use super::request::RequestBuilder;
+use super::response::decode_response;
 use super::retry::run_with_retry;
 use super::tracing::TraceContext;
 use reqwest::blocking::Client as HttpClient;
@@ -82,37 +83,7 @@ impl AtlasClient {
         query: &DatasetQuery,
         trace: Option<&TraceContext>,
     ) -> Result<Page<QueryResult>, ClientError> {
-        let mut builder = RequestBuilder::new("/v1/genes")
-            .with_param("release", &query.release)
-            .with_param("species", &query.species)
-            .with_param("assembly", &query.assembly)
-            .with_param("limit", query.limit.to_string());
-        if let Some(cursor) = &query.cursor {
-            builder = builder.with_param("cursor", cursor);
-        }
-        if let Some(gene_id) = &query.filter.gene_id {
-            builder = builder.with_param("gene_id", gene_id);
-        }
-        if let Some(biotype) = &query.filter.biotype {
-            builder = builder.with_param("biotype", biotype);
-        }
-        if let Some(contig) = &query.filter.contig {
-            builder = builder.with_param("contig", contig);
-        }
-        let mut include = Vec::new();
-        if query.projection.include_coords {
-            include.push("coords");
-        }
-        if query.projection.include_counts {
-            include.push("counts");
-        }
-        if query.projection.include_biotype {
-            include.push("biotype");
-        }
-        if !include.is_empty() {
-            builder = builder.with_param("include", include.join(","));
-        }
-        let json = self.send(&builder, trace)?;
+        let json = self.send(&query.to_request(), trace)?;
         let rows = json
             .get("data")
             .and_then(|v| v.get("rows"))
@@ -225,26 +196,9 @@ impl AtlasClient {
                     ));
                 }
                 if let Some(metrics) = &self.metrics {
-                    metrics.observe_request(request.path(), elapsed, status.is_success());
-                }
-
-                if status.as_u16() == 429 {
-                    return Err(ClientError::new(ErrorClass::RateLimited, "rate limited"));
-                }
-                if status.is_server_error() {
-                    return Err(ClientError::new(
-                        ErrorClass::Server,
-                        format!("server error {status}"),
-                    ));
-                }
-                if status.is_client_error() {
-                    return Err(ClientError::new(
-                        ErrorClass::Client,
-                        format!("client error {status}"),
-                    ));
+                    metrics.observe_request(request.endpoint(), elapsed, status.is_success());
                 }
-                serde_json::from_str(&body)
-                    .map_err(|err| ClientError::new(ErrorClass::Decode, err.to_string()))
+                decode_response(status, &body)
             },
         )
     }
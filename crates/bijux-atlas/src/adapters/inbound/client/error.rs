@@ -1,5 +1,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::contracts::api::ApiError;
 use std::error::Error;
 use std::fmt::{Display, Formatter};
 
@@ -18,6 +19,8 @@ pub enum ErrorClass {
 pub struct ClientError {
     pub class: ErrorClass,
     pub message: String,
+    /// Error envelope returned by the server, when the body decoded as one.
+    pub api_error: Option<ApiError>,
 }
 
 impl ClientError {
@@ -25,8 +28,15 @@ impl ClientError {
         Self {
             class,
             message: message.into(),
+            api_error: None,
         }
     }
+
+    #[must_use]
+    pub fn with_api_error(mut self, api_error: ApiError) -> Self {
+        self.api_error = Some(api_error);
+        self
+    }
 }
 
 impl Display for ClientError {
@@ -1,5 +1,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(feature = "client-async")]
+mod async_client;
 mod atlas_client;
 #[cfg(test)]
 mod client_tests;
@@ -9,15 +11,23 @@ mod metrics;
 mod pagination;
 mod query;
 mod request;
+mod response;
 pub mod retry;
 mod tracing;
 
+#[cfg(feature = "client-async")]
+pub use async_client::AsyncAtlasClient;
 pub use atlas_client::{AtlasClient, ClientLogger};
 pub use config::ClientConfig;
 pub use error::{ClientError, ErrorClass};
 pub use metrics::{ClientMetrics, InMemoryMetrics};
 pub use pagination::{Page, PaginationCursor};
-pub use query::{DatasetQuery, QueryFilter, QueryProjection, QueryResult, StreamQuery};
+pub use query::{
+    DatasetQuery, DiffQuery, QueryFilter, QueryProjection, QueryResult, SequenceQuery, StreamQuery,
+    TranscriptSequenceQuery,
+};
 pub use request::RequestBuilder;
 pub use retry::run_with_retry;
+#[cfg(feature = "client-async")]
+pub use retry::run_with_retry_async;
 pub use tracing::TraceContext;
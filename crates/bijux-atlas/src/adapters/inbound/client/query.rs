@@ -1,6 +1,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::pagination::Page;
+use super::request::RequestBuilder;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -94,6 +95,40 @@ impl DatasetQuery {
         self.projection.include_biotype = true;
         self
     }
+
+    pub(super) fn to_request(&self) -> RequestBuilder {
+        let mut builder = RequestBuilder::new("/v1/genes")
+            .with_param("release", &self.release)
+            .with_param("species", &self.species)
+            .with_param("assembly", &self.assembly)
+            .with_param("limit", self.limit.to_string());
+        if let Some(cursor) = &self.cursor {
+            builder = builder.with_param("cursor", cursor);
+        }
+        if let Some(gene_id) = &self.filter.gene_id {
+            builder = builder.with_param("gene_id", gene_id);
+        }
+        if let Some(biotype) = &self.filter.biotype {
+            builder = builder.with_param("biotype", biotype);
+        }
+        if let Some(contig) = &self.filter.contig {
+            builder = builder.with_param("contig", contig);
+        }
+        let mut include = Vec::new();
+        if self.projection.include_coords {
+            include.push("coords");
+        }
+        if self.projection.include_counts {
+            include.push("counts");
+        }
+        if self.projection.include_biotype {
+            include.push("biotype");
+        }
+        if !include.is_empty() {
+            builder = builder.with_param("include", include.join(","));
+        }
+        builder
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -105,3 +140,122 @@ pub struct QueryResult {
 pub struct StreamQuery {
     pub pages: Vec<Page<QueryResult>>,
 }
+
+/// Options for `/v1/sequence/region` and `/v1/genes/{gene_id}/sequence`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SequenceQuery {
+    pub strand: Option<String>,
+    pub upstream: Option<u64>,
+    pub downstream: Option<u64>,
+    pub mask: Option<String>,
+    pub include_stats: bool,
+}
+
+impl SequenceQuery {
+    #[must_use]
+    pub fn with_strand(mut self, strand: impl Into<String>) -> Self {
+        self.strand = Some(strand.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_flanks(mut self, upstream: u64, downstream: u64) -> Self {
+        self.upstream = Some(upstream);
+        self.downstream = Some(downstream);
+        self
+    }
+
+    #[must_use]
+    pub fn with_mask(mut self, mask: impl Into<String>) -> Self {
+        self.mask = Some(mask.into());
+        self
+    }
+
+    #[must_use]
+    pub fn include_stats(mut self) -> Self {
+        self.include_stats = true;
+        self
+    }
+}
+
+/// Options for `/v1/transcripts/{tx_id}/sequence`; the server defaults to
+/// `kind=cdna` and the standard genetic code.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TranscriptSequenceQuery {
+    pub kind: Option<String>,
+    pub genetic_code: Option<u8>,
+}
+
+impl TranscriptSequenceQuery {
+    #[must_use]
+    pub fn with_kind(mut self, kind: impl Into<String>) -> Self {
+        self.kind = Some(kind.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_genetic_code(mut self, genetic_code: u8) -> Self {
+        self.genetic_code = Some(genetic_code);
+        self
+    }
+}
+
+/// Gene-level diff between two releases of one species and assembly,
+/// optionally restricted to a region. Region diffs may target another
+/// assembly through `to_assembly` when the source dataset carries liftover chains.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DiffQuery {
+    pub species: String,
+    pub assembly: String,
+    pub to_assembly: Option<String>,
+    pub from_release: String,
+    pub to_release: String,
+    pub region: Option<String>,
+    pub limit: u32,
+    pub cursor: Option<String>,
+}
+
+impl DiffQuery {
+    #[must_use]
+    pub fn new(
+        species: impl Into<String>,
+        assembly: impl Into<String>,
+        from_release: impl Into<String>,
+        to_release: impl Into<String>,
+    ) -> Self {
+        Self {
+            species: species.into(),
+            assembly: assembly.into(),
+            to_assembly: None,
+            from_release: from_release.into(),
+            to_release: to_release.into(),
+            region: None,
+            limit: 100,
+            cursor: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_region(mut self, region: impl Into<String>) -> Self {
+        self.region = Some(region.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_to_assembly(mut self, to_assembly: impl Into<String>) -> Self {
+        self.to_assembly = Some(to_assembly.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_limit(mut self, limit: u32) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    #[must_use]
+    pub fn with_cursor(mut self, cursor: impl Into<String>) -> Self {
+        self.cursor = Some(cursor.into());
+        self
+    }
+}
@@ -1,10 +1,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::contracts::api::DatasetKeyDto;
 use std::collections::BTreeMap;
 
 #[derive(Debug, Clone, Default)]
 pub struct RequestBuilder {
     path: String,
+    endpoint: Option<String>,
     query: BTreeMap<String, String>,
 }
 
@@ -13,6 +15,7 @@ impl RequestBuilder {
     pub fn new(path: impl Into<String>) -> Self {
         Self {
             path: path.into(),
+            endpoint: None,
             query: BTreeMap::new(),
         }
     }
@@ -23,11 +26,44 @@ impl RequestBuilder {
         self
     }
 
+    #[must_use]
+    pub fn with_optional_param(
+        self,
+        key: impl Into<String>,
+        value: Option<impl Into<String>>,
+    ) -> Self {
+        match value {
+            Some(value) => self.with_param(key, value),
+            None => self,
+        }
+    }
+
+    #[must_use]
+    pub fn with_dataset(self, dataset: &DatasetKeyDto) -> Self {
+        self.with_param("release", &dataset.release)
+            .with_param("species", &dataset.species)
+            .with_param("assembly", &dataset.assembly)
+    }
+
+    /// Labels the request with its route template, e.g.
+    /// `/v1/transcripts/{tx_id}`, so metrics stay low-cardinality.
+    #[must_use]
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = Some(endpoint.into());
+        self
+    }
+
     #[must_use]
     pub fn path(&self) -> &str {
         &self.path
     }
 
+    /// Route template when set, otherwise the concrete path.
+    #[must_use]
+    pub fn endpoint(&self) -> &str {
+        self.endpoint.as_deref().unwrap_or(&self.path)
+    }
+
     #[must_use]
     pub fn query(&self) -> &BTreeMap<String, String> {
         &self.query
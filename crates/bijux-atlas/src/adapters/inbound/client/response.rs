@@ -0,0 +1,35 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::error::{ClientError, ErrorClass};
+use crate::contracts::api::ApiError;
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct ErrorBody {
+    error: ApiError,
+}
+
+/// Classifies a response status and decodes the body of a success.
+///
+/// Failures keep the server error envelope when the body carries one.
+pub(super) fn decode_response<T: DeserializeOwned>(
+    status: StatusCode,
+    body: &str,
+) -> Result<T, ClientError> {
+    let failure = if status.as_u16() == 429 {
+        ClientError::new(ErrorClass::RateLimited, "rate limited")
+    } else if status.is_server_error() {
+        ClientError::new(ErrorClass::Server, format!("server error {status}"))
+    } else if status.is_client_error() {
+        ClientError::new(ErrorClass::Client, format!("client error {status}"))
+    } else {
+        return serde_json::from_str(body)
+            .map_err(|err| ClientError::new(ErrorClass::Decode, err.to_string()));
+    };
+    Err(match serde_json::from_str::<ErrorBody>(body) {
+        Ok(parsed) => failure.with_api_error(parsed.error),
+        Err(_) => failure,
+    })
+}
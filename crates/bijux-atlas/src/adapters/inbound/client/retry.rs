@@ -27,3 +27,34 @@ where
     }
     Err(last_error.unwrap_or_else(|| ClientError::new(ErrorClass::Client, "retry failure")))
 }
+
+/// Async counterpart of [`run_with_retry`]; waits on the tokio timer instead
+/// of blocking the worker thread between attempts.
+///
+/// # Errors
+/// Returns the last operation error after all attempts are exhausted.
+#[cfg(feature = "client-async")]
+pub async fn run_with_retry_async<T, F, Fut>(
+    attempts: u32,
+    backoff_millis: u64,
+    mut f: F,
+) -> Result<T, ClientError>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T, ClientError>>,
+{
+    let total = attempts.max(1);
+    let mut last_error = None;
+    for index in 0..total {
+        match f().await {
+            Ok(value) => return Ok(value),
+            Err(err) => {
+                last_error = Some(err);
+                if index + 1 < total {
+                    tokio::time::sleep(Duration::from_millis(backoff_millis)).await;
+                }
+            }
+        }
+    }
+    Err(last_error.unwrap_or_else(|| ClientError::new(ErrorClass::Client, "retry failure")))
+}
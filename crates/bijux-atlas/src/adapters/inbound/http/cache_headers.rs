@@ -114,6 +114,22 @@ pub(crate) fn wants_text(headers: &HeaderMap) -> bool {
         .is_some_and(|v| v.contains("text/plain"))
 }
 
+pub(crate) fn wants_arrow(headers: &HeaderMap) -> bool {
+    headers
+        .get("accept")
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.contains(crate::adapters::outbound::columnar::ARROW_STREAM_MEDIA_TYPE))
+}
+
+/// `format=bed|gtf|gff3`; unknown values read as absent, so validate before relying on it.
+pub(crate) fn annotation_format(
+    params: &HashMap<String, String>,
+) -> Option<crate::domain::query::AnnotationFormat> {
+    params
+        .get("format")
+        .and_then(|v| crate::domain::query::AnnotationFormat::parse(v))
+}
+
 pub(crate) fn bool_query_flag(params: &HashMap<String, String>, name: &str) -> bool {
     params
         .get(name)
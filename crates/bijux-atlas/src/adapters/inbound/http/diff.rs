@@ -7,13 +7,17 @@ use crate::adapters::inbound::http::handlers::{
     maybe_compress_response, normalize_query, put_cache_headers, serialize_payload_with_capacity,
     with_request_id, CachePolicy,
 };
+use crate::adapters::inbound::http::middleware::quota::{with_metered_usage, MeteredUsage};
+use crate::adapters::inbound::http::sequence::dataset_contig_aliases;
+use crate::contracts::api::resolve_region_alias;
 use crate::domain::dataset::Catalog;
 use crate::domain::query::{
-    DiffPage, DiffRecord, DiffScope, DiffStatus, ReleaseGeneIndex, ReleaseGeneIndexEntry,
+    ContigAliases, DiffPage, DiffRecord, DiffScope, DiffStatus, Liftover, ReleaseGeneIndex,
+    ReleaseGeneIndexEntry, SeqId, DEFAULT_LIFTOVER_MIN_MATCH,
 };
 use crate::*;
 use serde_json::json;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use tracing::info;
 
 struct QueueGuard {
@@ -88,6 +92,67 @@ fn overlaps(entry: &ReleaseGeneIndexEntry, region: &RegionFilter) -> bool {
     entry.seqid.as_str() == region.seqid && entry.start <= region.end && region.start <= entry.end
 }
 
+/// Moves `entries` onto the target assembly of `liftover`, naming lifted
+/// contigs the way the target dataset does through `target_aliases`. Genes
+/// that do not lift keep their source coordinates and are returned by id.
+fn lift_entries(
+    entries: Vec<ReleaseGeneIndexEntry>,
+    liftover: &Liftover,
+    target_aliases: &ContigAliases,
+) -> (Vec<ReleaseGeneIndexEntry>, HashSet<String>) {
+    let mut unmapped = HashSet::new();
+    let lifted = entries
+        .into_iter()
+        .map(|mut entry| {
+            let target = liftover
+                .lift_with_min_match(
+                    entry.seqid.as_str(),
+                    entry.start,
+                    entry.end,
+                    DEFAULT_LIFTOVER_MIN_MATCH,
+                )
+                .and_then(|t| {
+                    SeqId::parse(&target_aliases.resolve(&t.seqid).seqid)
+                        .ok()
+                        .map(|seqid| (seqid, t))
+                });
+            match target {
+                Some((seqid, t)) => {
+                    entry.seqid = seqid;
+                    entry.start = t.start;
+                    entry.end = t.end;
+                }
+                None => {
+                    unmapped.insert(entry.gene_id.as_str().to_string());
+                }
+            }
+            entry
+        })
+        .collect();
+    (lifted, unmapped)
+}
+
+/// Whether a gene present on both sides changed. Within one assembly the
+/// signatures decide; across assemblies the lifted coordinates are compared
+/// and the coordinate-free attribute signatures when both indexes carry them.
+fn entry_changed(
+    from: &ReleaseGeneIndexEntry,
+    to: &ReleaseGeneIndexEntry,
+    unmapped: Option<&HashSet<String>>,
+) -> bool {
+    let Some(unmapped) = unmapped else {
+        return from.signature_sha256 != to.signature_sha256;
+    };
+    if unmapped.contains(from.gene_id.as_str()) {
+        return true;
+    }
+    let attributes_changed = match (&from.attributes_sha256, &to.attributes_sha256) {
+        (Some(a), Some(b)) => a != b,
+        _ => false,
+    };
+    attributes_changed || (&from.seqid, from.start, from.end) != (&to.seqid, to.start, to.end)
+}
+
 fn load_index(path: &std::path::Path) -> Result<Vec<ReleaseGeneIndexEntry>, ApiError> {
     let bytes =
         crate::adapters::inbound::http::effects_adapters::read_bytes(path).map_err(|e| {
@@ -134,6 +199,7 @@ fn resolve_explicit_release_alias(
 async fn diff_common(
     State(state): State<AppState>,
     headers: HeaderMap,
+    access: Option<axum::Extension<DatasetAccess>>,
     params: HashMap<String, String>,
     scope: DiffScope,
 ) -> Response {
@@ -182,6 +248,21 @@ async fn diff_common(
 
     let from_release_raw = params.get("from_release").map(String::as_str).unwrap_or("");
     let to_release_raw = params.get("to_release").map(String::as_str).unwrap_or("");
+    let to_assembly = params
+        .get("to_assembly")
+        .cloned()
+        .unwrap_or_else(|| assembly.clone());
+    let cross_assembly = to_assembly != assembly;
+    if cross_assembly && scope != DiffScope::Region {
+        let resp = api_error_response(
+            StatusCode::BAD_REQUEST,
+            ApiError::invalid_param(
+                "to_assembly",
+                "cross-assembly diffs require /v1/diff/region",
+            ),
+        );
+        return with_request_id(resp, &request_id);
+    }
     if from_release_raw.is_empty() || to_release_raw.is_empty() {
         let resp = api_error_response(
             StatusCode::BAD_REQUEST,
@@ -203,7 +284,7 @@ async fn diff_common(
         return with_request_id(resp, &request_id);
     };
     let Some(to_release) =
-        resolve_explicit_release_alias(to_release_raw, &species, &assembly, &catalog)
+        resolve_explicit_release_alias(to_release_raw, &species, &to_assembly, &catalog)
     else {
         let resp = api_error_response(
             StatusCode::NOT_FOUND,
@@ -226,7 +307,7 @@ async fn diff_common(
             return with_request_id(resp, &request_id);
         }
     };
-    let to_dataset = match DatasetId::new(&to_release, &species, &assembly) {
+    let to_dataset = match DatasetId::new(&to_release, &species, &to_assembly) {
         Ok(v) => v,
         Err(e) => {
             let resp = api_error_response(
@@ -236,6 +317,21 @@ async fn diff_common(
             return with_request_id(resp, &request_id);
         }
     };
+    for dataset in [&from_dataset, &to_dataset] {
+        if let Some(resp) = enforce_dataset_access(
+            &state,
+            access.as_deref(),
+            route,
+            "GET",
+            dataset,
+            started,
+            &request_id,
+        )
+        .await
+        {
+            return resp;
+        }
+    }
 
     let mut limit = params
         .get("limit")
@@ -325,7 +421,7 @@ async fn diff_common(
     } else {
         None
     };
-    let region = match scope {
+    let mut region = match scope {
         DiffScope::Genes => None,
         DiffScope::Region => match parse_region(&params) {
             Ok(v) => v,
@@ -335,6 +431,11 @@ async fn diff_common(
             }
         },
     };
+    // The region is read in the target release's coordinates, so its aliases apply.
+    let to_aliases = dataset_contig_aliases(&state, &to_dataset).await;
+    let contig = region
+        .as_mut()
+        .map(|r| resolve_region_alias(r, to_aliases.as_ref()));
 
     let from_index_path = match state
         .cache
@@ -372,6 +473,20 @@ async fn diff_common(
             return with_request_id(resp, &request_id);
         }
     };
+    let liftover = if cross_assembly {
+        match crate::adapters::inbound::http::liftover::dataset_liftover(
+            &state,
+            &from_dataset,
+            Some(to_assembly.as_str()),
+        )
+        .await
+        {
+            Ok(v) => Some(v),
+            Err(resp) => return with_request_id(resp, &request_id),
+        }
+    } else {
+        None
+    };
     let from_entries = match load_index(&from_index_path) {
         Ok(v) => v,
         Err(e) => {
@@ -391,6 +506,14 @@ async fn diff_common(
         }
     };
 
+    let (from_entries, unmapped) = match &liftover {
+        Some(l) => {
+            let (lifted, unmapped) = lift_entries(from_entries, l, &to_aliases);
+            (lifted, Some(unmapped))
+        }
+        None => (from_entries, None),
+    };
+
     let mut i = 0usize;
     let mut j = 0usize;
     let mut rows: Vec<DiffRecord> = Vec::new();
@@ -408,7 +531,7 @@ async fn diff_common(
                 std::cmp::Ordering::Equal => {
                     i += 1;
                     j += 1;
-                    if a.signature_sha256 != b.signature_sha256 {
+                    if entry_changed(a, b, unmapped.as_ref()) {
                         Some((a.gene_id.clone(), DiffStatus::Changed, Some(b)))
                     } else {
                         None
@@ -438,7 +561,11 @@ async fn diff_common(
             let Some(src) = coord_src else {
                 continue;
             };
-            if !overlaps(src, r) {
+            let unplaced = matches!(status, DiffStatus::Removed)
+                && unmapped
+                    .as_ref()
+                    .is_some_and(|u| u.contains(gene_id.as_str()));
+            if unplaced || !overlaps(src, r) {
                 continue;
             }
         }
@@ -509,7 +636,7 @@ async fn diff_common(
         .iter()
         .filter(|x| matches!(x.status, DiffStatus::Changed))
         .count();
-    let qc = json!({
+    let mut qc = json!({
         "added": added,
         "removed": removed,
         "changed": changed,
@@ -517,12 +644,28 @@ async fn diff_common(
         "from_release": from_release,
         "to_release": to_release
     });
+    if let Some(unmapped) = &unmapped {
+        qc["liftover"] = json!({
+            "from_assembly": from_dataset.assembly,
+            "to_assembly": to_dataset.assembly,
+            "min_match": DEFAULT_LIFTOVER_MIN_MATCH,
+            "unmapped_genes": unmapped.len(),
+        });
+    }
+    let usage = MeteredUsage {
+        rows_returned: page.rows.len() as u64,
+        ..MeteredUsage::default()
+    };
     let provenance =
         crate::adapters::inbound::http::handlers::dataset_provenance(&state, &to_dataset).await;
+    let mut data = json!({"diff": page.rows, "qc": qc, "provenance": provenance});
+    if let Some(contig) = contig {
+        data["contig"] = json!(contig);
+    }
     let payload = crate::adapters::inbound::http::handlers::json_envelope(
         Some(json!(to_dataset)),
         Some(json!({ "next_cursor": page.next_cursor.clone() })),
-        json!({"diff": page.rows, "qc": qc, "provenance": provenance}),
+        data,
         page.next_cursor.map(|c| json!({ "next_cursor": c })),
         None,
     );
@@ -596,21 +739,23 @@ async fn diff_common(
         .metrics
         .observe_request(route, StatusCode::OK, started.elapsed())
         .await;
-    with_request_id(resp, &request_id)
+    with_request_id(with_metered_usage(resp, usage), &request_id)
 }
 
 pub(crate) async fn diff_genes_handler(
     state: State<AppState>,
     headers: HeaderMap,
+    access: Option<axum::Extension<DatasetAccess>>,
     axum::extract::Query(params): axum::extract::Query<HashMap<String, String>>,
 ) -> Response {
-    diff_common(state, headers, params, DiffScope::Genes).await
+    diff_common(state, headers, access, params, DiffScope::Genes).await
 }
 
 pub(crate) async fn diff_region_handler(
     state: State<AppState>,
     headers: HeaderMap,
+    access: Option<axum::Extension<DatasetAccess>>,
     axum::extract::Query(params): axum::extract::Query<HashMap<String, String>>,
 ) -> Response {
-    diff_common(state, headers, params, DiffScope::Region).await
+    diff_common(state, headers, access, params, DiffScope::Region).await
 }
@@ -1,9 +1,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use std::fs::File;
-use std::io::{Read, Seek, SeekFrom};
 use std::path::Path;
 
+use crate::domain::ingest::read_decoded_window;
 use crate::CacheError;
 
 pub(crate) fn read_bytes(path: &Path) -> Result<Vec<u8>, CacheError> {
@@ -19,11 +18,7 @@ pub(crate) fn read_fasta_window(
     offset: u64,
     len: usize,
 ) -> Result<Vec<u8>, CacheError> {
-    let mut file = File::open(fasta_path).map_err(|e| CacheError(e.to_string()))?;
-    file.seek(SeekFrom::Start(offset))
-        .map_err(|e| CacheError(e.to_string()))?;
-    let mut buf = vec![0_u8; len];
-    file.read_exact(&mut buf)
-        .map_err(|e| CacheError(e.to_string()))?;
-    Ok(buf)
+    // FAI offsets address the decoded stream, so compressed FASTA goes through the
+    // ingest decoder, which seeks via a sibling `.gzi` index when one is present.
+    read_decoded_window(fasta_path, offset, len).map_err(|e| CacheError(e.to_string()))
 }
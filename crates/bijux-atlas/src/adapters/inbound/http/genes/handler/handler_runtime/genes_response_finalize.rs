@@ -1,4 +1,6 @@
 use crate::adapters::inbound::http::handlers;
+use crate::adapters::inbound::http::middleware::quota::{with_metered_usage, MeteredUsage};
+use crate::adapters::outbound::columnar;
 use crate::app::server::cache::hot::HotEntry;
 use crate::*;
 use serde_json::json;
@@ -40,23 +42,42 @@ pub(super) async fn finalize_genes_success_response<R>(
         coalesce_key,
         request_id,
     } = ctx;
+    let mut rows_returned = 0;
     if let Some(rows) = payload
         .get("data")
         .and_then(|d| d.get("rows"))
         .and_then(serde_json::Value::as_array)
     {
+        rows_returned = rows.len() as u64;
         state
             .metrics
             .observe_query_row_count("/v1/genes", rows.len())
             .await;
     }
+    let usage = MeteredUsage {
+        rows_returned,
+        ..MeteredUsage::default()
+    };
+    let annotation = handlers::annotation_format(params);
+    let arrow = annotation.is_none() && handlers::wants_arrow(headers);
+    let envelope = annotation.is_none() && !arrow;
     let serialize_started = Instant::now();
     let bytes = match info_span!("serialize_response").in_scope(|| {
-        handlers::serialize_payload_with_capacity(
-            &payload,
-            handlers::wants_pretty(params),
-            state.api.response_max_bytes / 4,
-        )
+        if let Some(format) = annotation {
+            handlers::serialize_gene_rows_as_annotation(
+                &payload,
+                format,
+                !params.contains_key("cursor"),
+            )
+        } else if arrow {
+            handlers::serialize_rows_as_arrow(&payload, &columnar::gene_row_schema())
+        } else {
+            handlers::serialize_payload_with_capacity(
+                &payload,
+                handlers::wants_pretty(params),
+                state.api.response_max_bytes / 4,
+            )
+        }
     }) {
         Ok(v) => v,
         Err(err) => {
@@ -110,7 +131,7 @@ pub(super) async fn finalize_genes_success_response<R>(
             .await;
         return handlers::with_request_id(resp, request_id);
     }
-    if state.api.enable_redis_response_cache {
+    if state.api.enable_redis_response_cache && envelope {
         if let (Some(redis), Some(cache_key), Some(_)) =
             (&state.redis_backend, redis_cache_key, exact_gene_id)
         {
@@ -143,7 +164,7 @@ pub(super) async fn finalize_genes_success_response<R>(
         .metrics
         .observe_response_size("/v1/genes", response_bytes.len())
         .await;
-    if handlers::wants_text(headers) {
+    if envelope && handlers::wants_text(headers) {
         let text = String::from_utf8_lossy(&response_bytes).to_string();
         let mut resp = (StatusCode::OK, text).into_response();
         handlers::put_cache_headers(
@@ -157,7 +178,7 @@ pub(super) async fn finalize_genes_success_response<R>(
             .metrics
             .observe_request("/v1/genes", StatusCode::OK, started.elapsed())
             .await;
-        return handlers::with_request_id(resp, request_id);
+        return handlers::with_request_id(with_metered_usage(resp, usage), request_id);
     }
     let mut resp = Response::builder()
         .status(StatusCode::OK)
@@ -176,13 +197,18 @@ pub(super) async fn finalize_genes_success_response<R>(
         etag,
         handlers::CachePolicy::ImmutableDataset,
     );
+    if let Some(format) = annotation {
+        handlers::put_row_stream_headers(resp.headers_mut(), format.media_type(), &payload);
+    } else if arrow {
+        handlers::put_arrow_headers(resp.headers_mut(), &payload);
+    }
     handlers::cache_debug_headers(
         resp.headers_mut(),
         state.api.enable_debug_datasets,
         artifact_hash,
         cache_key_debug,
     );
-    if class == QueryClass::Heavy || class == QueryClass::Cheap {
+    if envelope && (class == QueryClass::Heavy || class == QueryClass::Cheap) {
         let mut cache = state.hot_query_cache.lock().await;
         cache.insert(
             coalesce_key,
@@ -190,6 +216,7 @@ pub(super) async fn finalize_genes_success_response<R>(
                 body: response_bytes,
                 etag: etag.to_string(),
                 created_at: Instant::now(),
+                rows_returned,
             },
         );
     }
@@ -198,5 +225,19 @@ pub(super) async fn finalize_genes_success_response<R>(
         .observe_request("/v1/genes", StatusCode::OK, started.elapsed())
         .await;
     tracing::info!(request_id = %request_id, status = 200_u16, "request complete");
-    handlers::with_request_id(resp, request_id)
+    handlers::with_request_id(with_metered_usage(resp, usage), request_id)
+}
+
+/// Rows in a cached `/v1/genes` envelope.
+pub(super) fn envelope_rows_returned(body: &[u8]) -> u64 {
+    serde_json::from_slice::<serde_json::Value>(body)
+        .ok()
+        .and_then(|payload| {
+            payload
+                .get("data")?
+                .get("rows")?
+                .as_array()
+                .map(|rows| rows.len() as u64)
+        })
+        .unwrap_or(0)
 }
@@ -3,8 +3,11 @@
 use crate::adapters::inbound::http::genes::{
     admission as genes_admission, response as genes_response,
 };
+use crate::adapters::inbound::http::middleware::quota::{with_metered_usage, MeteredUsage};
+use crate::adapters::inbound::http::sequence::dataset_contig_aliases;
 use crate::adapters::inbound::http::{genes_support, handlers};
 use crate::app::query as app_query;
+use crate::contracts::api::resolve_region_alias;
 use crate::domain::dataset::artifact_paths;
 use crate::domain::dataset::ShardCatalog;
 use crate::domain::sha256_hex;
@@ -13,12 +16,13 @@ use serde_json::json;
 use tracing::{info, info_span, warn};
 
 use super::genes_response_finalize::{
-    finalize_genes_success_response, GenesResponseFinalizeContext,
+    envelope_rows_returned, finalize_genes_success_response, GenesResponseFinalizeContext,
 };
 
 pub(crate) async fn genes_handler(
     State(state): State<AppState>,
     headers: HeaderMap,
+    access: Option<axum::Extension<DatasetAccess>>,
     axum::extract::Query(params): axum::extract::Query<HashMap<String, String>>,
 ) -> Response {
     let started = Instant::now();
@@ -88,6 +92,26 @@ pub(crate) async fn genes_handler(
                 return handlers::with_request_id(resp, &request_id);
             }
         };
+    if let Some(resp) = enforce_dataset_access(
+        &state,
+        access.as_deref(),
+        "/v1/genes",
+        "GET",
+        &dataset,
+        started,
+        &request_id,
+    )
+    .await
+    {
+        return resp;
+    }
+    let contigs = match req.filter.region.as_mut() {
+        Some(region) => vec![resolve_region_alias(
+            region,
+            dataset_contig_aliases(&state, &dataset).await.as_ref(),
+        )],
+        None => Vec::new(),
+    };
     let class = classify_query(&req);
     let estimated_cost = app_query::estimate_work_units(&req);
     info!(
@@ -121,6 +145,7 @@ pub(crate) async fn genes_handler(
     if overloaded
         && state.api.allow_min_viable_response
         && handlers::wants_min_viable_response(&params)
+        && handlers::annotation_format(&params).is_none()
     {
         req.fields = GeneFields {
             gene_id: true,
@@ -129,6 +154,7 @@ pub(crate) async fn genes_handler(
             biotype: false,
             transcript_count: false,
             sequence_length: false,
+            strand: false,
         };
     }
     if let Some(error) = genes_support::check_serialization_budget(&req, &state.limits) {
@@ -243,7 +269,20 @@ pub(crate) async fn genes_handler(
     };
     let manifest_summary = state.cache.fetch_manifest_summary(&dataset).await.ok();
     let artifact_hash = handlers::dataset_artifact_hash(manifest_summary.as_ref(), &dataset);
-    let etag = handlers::dataset_etag(&artifact_hash, "/v1/genes", &params);
+    // Arrow and JSON bodies of the same query are distinct representations;
+    // `format=` already keys the etag through the query string.
+    let annotation = handlers::annotation_format(&params);
+    let arrow = annotation.is_none() && handlers::wants_arrow(&headers);
+    let envelope = annotation.is_none() && !arrow;
+    let etag = handlers::dataset_etag(
+        &artifact_hash,
+        if arrow {
+            "/v1/genes;arrow"
+        } else {
+            "/v1/genes"
+        },
+        &params,
+    );
     let cache_key_debug = format!("/v1/genes?{normalized}");
     state
         .metrics
@@ -251,7 +290,7 @@ pub(crate) async fn genes_handler(
         .await;
     let explain_mode = handlers::bool_query_flag(&params, "explain");
     let mut redis_fill_guard = None;
-    if state.api.enable_redis_response_cache {
+    if state.api.enable_redis_response_cache && envelope {
         if let (Some(redis), Some(cache_key)) = (&state.redis_backend, &redis_cache_key) {
             match redis.get_gene_cache(cache_key).await {
                 Ok(Some(cached_bytes)) => {
@@ -274,6 +313,10 @@ pub(crate) async fn genes_handler(
                             .await;
                         return handlers::with_request_id(resp, &request_id);
                     }
+                    let usage = MeteredUsage {
+                        rows_returned: envelope_rows_returned(&cached_bytes),
+                        ..MeteredUsage::default()
+                    };
                     let mut resp = Response::builder()
                         .status(StatusCode::OK)
                         .body(Body::from(cached_bytes))
@@ -294,7 +337,7 @@ pub(crate) async fn genes_handler(
                         .metrics
                         .observe_request("/v1/genes", StatusCode::OK, started.elapsed())
                         .await;
-                    return handlers::with_request_id(resp, &request_id);
+                    return handlers::with_request_id(with_metered_usage(resp, usage), &request_id);
                 }
                 Ok(None) => {
                     let guard = redis.acquire_fill_lock(cache_key).await;
@@ -319,6 +362,10 @@ pub(crate) async fn genes_handler(
                                     .await;
                                 return handlers::with_request_id(resp, &request_id);
                             }
+                            let usage = MeteredUsage {
+                                rows_returned: envelope_rows_returned(&cached_bytes),
+                                ..MeteredUsage::default()
+                            };
                             let mut resp = Response::builder()
                                 .status(StatusCode::OK)
                                 .body(Body::from(cached_bytes))
@@ -343,7 +390,10 @@ pub(crate) async fn genes_handler(
                                 .metrics
                                 .observe_request("/v1/genes", StatusCode::OK, started.elapsed())
                                 .await;
-                            return handlers::with_request_id(resp, &request_id);
+                            return handlers::with_request_id(
+                                with_metered_usage(resp, usage),
+                                &request_id,
+                            );
                         }
                         Ok(None) => {
                             redis_fill_guard = Some(guard);
@@ -370,7 +420,7 @@ pub(crate) async fn genes_handler(
         format!("ds-{}", &hash[..12])
     };
     state.metrics.observe_dataset_query(&dataset_key).await;
-    if class == QueryClass::Heavy || class == QueryClass::Cheap {
+    if envelope && (class == QueryClass::Heavy || class == QueryClass::Cheap) {
         let mut cache = state.hot_query_cache.lock().await;
         if let Some(entry) = info_span!(
             "cache_lookup_hot_query",
@@ -388,6 +438,10 @@ pub(crate) async fn genes_handler(
                 "hot query cache hit"
             );
             state.metrics.observe_query_cache_hit();
+            let usage = MeteredUsage {
+                rows_returned: entry.rows_returned,
+                ..MeteredUsage::default()
+            };
             let mut resp = Response::builder()
                 .status(StatusCode::OK)
                 .body(Body::from(entry.body))
@@ -405,7 +459,7 @@ pub(crate) async fn genes_handler(
                 .metrics
                 .observe_request("/v1/genes", StatusCode::OK, started.elapsed())
                 .await;
-            return handlers::with_request_id(resp, &request_id);
+            return handlers::with_request_id(with_metered_usage(resp, usage), &request_id);
         }
         info!(
             event_id = "cache_miss_hot_query",
@@ -482,6 +536,7 @@ pub(crate) async fn genes_handler(
                     && !req.fields.biotype
                     && !req.fields.transcript_count
                     && !req.fields.sequence_length
+                    && !req.fields.strand
                 {
                     if let Some(bytes) =
                         app_query::query_gene_id_name_json_minimal(&c.conn, gene_id)
@@ -506,6 +561,7 @@ pub(crate) async fn genes_handler(
                                 biotype: None,
                                 transcript_count: None,
                                 sequence_length: None,
+                                strand: None,
                             }],
                             next_cursor: None,
                         });
@@ -630,6 +686,7 @@ pub(crate) async fn genes_handler(
                     resp,
                     explain_mode,
                     provenance,
+                    &contigs,
                 )
             })
         }
@@ -655,92 +712,11 @@ pub(crate) async fn genes_handler(
                 },
                 "query error classified"
             );
-            if msg.contains("limit")
-                || msg.contains("span")
-                || msg.contains("scan")
-                || msg.contains("name_prefix")
-            {
-                let (code, reason_code) = if msg.contains("region span exceeds") {
-                    (ApiErrorCode::RangeTooLarge, "RANGE_TOO_LARGE")
-                } else if msg.contains("estimated query cost") {
-                    (ApiErrorCode::QueryTooExpensive, "QUERY_TOO_EXPENSIVE")
-                } else {
-                    (ApiErrorCode::QueryRejectedByPolicy, "QUERY_REJECTED")
-                };
-                let status = if matches!(
-                    code,
-                    ApiErrorCode::RangeTooLarge | ApiErrorCode::QueryTooExpensive
-                ) {
-                    StatusCode::PAYLOAD_TOO_LARGE
-                } else {
-                    StatusCode::UNPROCESSABLE_ENTITY
-                };
-                let resp = handlers::api_error_response(
-                    status,
-                    handlers::error_json(
-                        code,
-                        "query rejected",
-                        json!({"message": msg, "reason_code": reason_code}),
-                    ),
-                );
-                state
-                    .metrics
-                    .observe_request("/v1/genes", status, started.elapsed())
-                    .await;
-                return handlers::with_request_id(resp, &request_id);
-            }
-            if msg.contains("Validation:") || msg.contains("seqid does not exist in dataset") {
-                let resp = handlers::api_error_response(
-                    StatusCode::BAD_REQUEST,
-                    handlers::error_json(
-                        ApiErrorCode::InvalidQueryParameter,
-                        "invalid query parameter",
-                        json!({"message": msg}),
-                    ),
-                );
-                state
-                    .metrics
-                    .observe_request("/v1/genes", StatusCode::BAD_REQUEST, started.elapsed())
-                    .await;
-                return handlers::with_request_id(resp, &request_id);
-            }
-            if req.cursor.is_some() {
-                let reason_code = if msg.contains("UnsupportedVersion") {
-                    "CURSOR_VERSION_UNSUPPORTED"
-                } else if msg.contains("DatasetMismatch") {
-                    "CURSOR_DATASET_MISMATCH"
-                } else {
-                    "CURSOR_INVALID"
-                };
-                let resp = handlers::api_error_response(
-                    StatusCode::BAD_REQUEST,
-                    handlers::error_json(
-                        ApiErrorCode::InvalidCursor,
-                        "invalid cursor",
-                        json!({"message": msg, "reason_code": reason_code}),
-                    ),
-                );
-                state
-                    .metrics
-                    .observe_request("/v1/genes", StatusCode::BAD_REQUEST, started.elapsed())
-                    .await;
-                return handlers::with_request_id(resp, &request_id);
-            }
-            let resp = handlers::api_error_response(
-                StatusCode::SERVICE_UNAVAILABLE,
-                handlers::error_json(
-                    ApiErrorCode::Internal,
-                    "query failed",
-                    json!({"message": msg}),
-                ),
-            );
+            let (status, error) = genes_support::query_failure(&msg, req.cursor.is_some());
+            let resp = handlers::api_error_response(status, error);
             state
                 .metrics
-                .observe_request(
-                    "/v1/genes",
-                    StatusCode::SERVICE_UNAVAILABLE,
-                    started.elapsed(),
-                )
+                .observe_request("/v1/genes", status, started.elapsed())
                 .await;
             return handlers::with_request_id(resp, &request_id);
         }
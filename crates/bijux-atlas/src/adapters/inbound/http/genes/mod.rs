@@ -4,6 +4,8 @@
 
 pub(crate) mod admission;
 mod handler;
+mod query;
 pub(crate) mod response;
 
 pub(crate) use self::handler::genes_handler;
+pub(crate) use self::query::genes_query_handler;
@@ -0,0 +1,293 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! `POST /v1/genes/query`: the `/v1/genes` query engine driven by a JSON body,
+//! so filters can carry lists (batch gene id lookups, several biotypes or regions).
+
+use crate::adapters::inbound::http::genes::response as genes_response;
+use crate::adapters::inbound::http::middleware::quota::{with_metered_usage, MeteredUsage};
+use crate::adapters::inbound::http::middleware::shedding;
+use crate::adapters::inbound::http::sequence::dataset_contig_aliases;
+use crate::adapters::inbound::http::{genes_support, handlers};
+use crate::contracts::api::params::{resolve_region_aliases, GeneQueryBody};
+use crate::*;
+use serde_json::json;
+use tracing::{info, warn};
+
+const ROUTE: &str = "/v1/genes/query";
+
+fn reject(status: StatusCode, err: ApiError, request_id: &str) -> Response {
+    handlers::with_request_id(handlers::api_error_response(status, err), request_id)
+}
+
+pub(crate) async fn genes_query_handler(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    access: Option<axum::Extension<DatasetAccess>>,
+    Json(body): Json<GeneQueryBody>,
+) -> Response {
+    let started = Instant::now();
+    let request_id = handlers::propagated_request_id(&headers, &state);
+    let resp = run_query(&state, &headers, access.as_deref(), body, &request_id).await;
+    state
+        .metrics
+        .observe_request_with_method(ROUTE, "POST", resp.status(), started.elapsed())
+        .await;
+    resp
+}
+
+async fn run_query(
+    state: &AppState,
+    headers: &HeaderMap,
+    access: Option<&DatasetAccess>,
+    body: GeneQueryBody,
+    request_id: &str,
+) -> Response {
+    if !state.accepting_requests.load(Ordering::Relaxed) {
+        crate::record_shed_reason(state, "draining").await;
+        return reject(
+            StatusCode::SERVICE_UNAVAILABLE,
+            handlers::error_json(
+                ApiErrorCode::QueryRejectedByPolicy,
+                "server draining; refusing new requests",
+                json!({}),
+            ),
+            request_id,
+        );
+    }
+    let adaptive_rl = genes_support::adaptive_rl_factor(state, shedding::overloaded(state).await);
+    if let Some(ip) = handlers::normalized_forwarded_for(headers) {
+        if !state
+            .ip_limiter
+            .allow_with_factor(&ip, &state.api.rate_limit_per_ip, adaptive_rl)
+            .await
+        {
+            crate::record_shed_reason(state, "ip_rate_limited").await;
+            return reject(
+                StatusCode::TOO_MANY_REQUESTS,
+                handlers::error_json(
+                    ApiErrorCode::RateLimited,
+                    "rate limit exceeded",
+                    json!({"scope":"ip"}),
+                ),
+                request_id,
+            );
+        }
+    }
+    if state.api.enable_api_key_rate_limit {
+        if let Some(key) = handlers::normalized_api_key(headers) {
+            if !state
+                .api_key_limiter
+                .allow_with_factor(&key, &state.api.rate_limit_per_api_key, adaptive_rl)
+                .await
+            {
+                crate::record_shed_reason(state, "api_key_rate_limited").await;
+                return reject(
+                    StatusCode::TOO_MANY_REQUESTS,
+                    handlers::error_json(
+                        ApiErrorCode::RateLimited,
+                        "rate limit exceeded",
+                        json!({"scope":"api_key"}),
+                    ),
+                    request_id,
+                );
+            }
+        }
+    }
+
+    let explain_mode = body.explain;
+    let (dataset, mut req) = match genes_support::build_body_query(body, &state.limits) {
+        Ok(v) => v,
+        Err(e) => return reject(StatusCode::BAD_REQUEST, e, request_id),
+    };
+    if let Some(resp) = dataset_access_denial(state, access, ROUTE, &dataset, request_id).await {
+        return resp;
+    }
+    let contigs = if req.filter.regions.is_empty() {
+        Vec::new()
+    } else {
+        let aliases = dataset_contig_aliases(state, &dataset).await;
+        resolve_region_aliases(&mut req.filter.regions, &aliases)
+    };
+    let class = classify_query(&req);
+    info!(
+        request_id = %request_id,
+        route = ROUTE,
+        query_class = ?class,
+        gene_ids = req.filter.gene_ids.len(),
+        biotypes = req.filter.biotypes.len(),
+        regions = req.filter.regions.len(),
+        "policy_applied"
+    );
+    if let Some(error) = genes_support::check_serialization_budget(&req, &state.limits) {
+        return reject(StatusCode::UNPROCESSABLE_ENTITY, error, request_id);
+    }
+    if class == QueryClass::Heavy && req.limit > state.limits.heavy_projection_limit {
+        return reject(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            handlers::error_json(
+                ApiErrorCode::QueryRejectedByPolicy,
+                "heavy projection limit exceeded",
+                json!({"limit": req.limit, "max": state.limits.heavy_projection_limit}),
+            ),
+            request_id,
+        );
+    }
+    let overloaded = state
+        .metrics
+        .should_shed_heavy(
+            state.api.shed_latency_min_samples,
+            state.api.shed_latency_p95_threshold_ms,
+        )
+        .await;
+    genes_support::record_overload_cheap(state, class, overloaded);
+    genes_support::cap_heavy_limit(&mut req, state, class, overloaded);
+    if (class == QueryClass::Heavy && state.api.shed_load_enabled && overloaded)
+        || shedding::should_shed_noncheap(state, class).await
+    {
+        crate::record_shed_reason(state, "bulkhead_shed_heavy").await;
+        let backoff = shedding::heavy_backoff_ms(state);
+        let mut resp = reject(
+            StatusCode::SERVICE_UNAVAILABLE,
+            handlers::error_json(
+                ApiErrorCode::QueryRejectedByPolicy,
+                "server is shedding heavy query load",
+                json!({"class":"heavy","retry_after_ms": backoff}),
+            ),
+            request_id,
+        );
+        if let Ok(v) = HeaderValue::from_str(&(backoff / 1000).max(1).to_string()) {
+            resp.headers_mut().insert("retry-after", v);
+        }
+        return resp;
+    }
+    let _queue_guard = match genes_support::try_enter_queue(state) {
+        Ok(g) => g,
+        Err(e) => {
+            crate::record_shed_reason(state, "queue_depth_exceeded").await;
+            return reject(StatusCode::TOO_MANY_REQUESTS, e, request_id);
+        }
+    };
+    let _class_permit = match genes_support::acquire_class_permit(state, class).await {
+        Ok(v) => v,
+        Err(e) => {
+            crate::record_shed_reason(state, "class_permit_saturated").await;
+            return reject(StatusCode::TOO_MANY_REQUESTS, e, request_id);
+        }
+    };
+    let _heavy_worker_permit = if class == QueryClass::Heavy {
+        match state.heavy_workers.clone().try_acquire_owned() {
+            Ok(permit) => Some(permit),
+            Err(_) => {
+                crate::record_shed_reason(state, "heavy_worker_saturated").await;
+                return reject(
+                    StatusCode::TOO_MANY_REQUESTS,
+                    handlers::error_json(
+                        ApiErrorCode::QueryRejectedByPolicy,
+                        "heavy worker pool is saturated",
+                        json!({"class":"heavy"}),
+                    ),
+                    request_id,
+                );
+            }
+        }
+    } else {
+        None
+    };
+
+    let work = async {
+        let c = state.cache.open_dataset_connection(&dataset).await?;
+        let deadline = Instant::now() + state.api.sql_timeout;
+        let _ = c
+            .conn
+            .progress_handler(1_000, Some(move || Instant::now() > deadline));
+        let query_started = Instant::now();
+        let result = query_genes(&c.conn, &req, &state.limits, b"atlas-server-cursor-secret")
+            .map_err(|e| CacheError(e.to_string()));
+        let _ = c.conn.progress_handler(1_000, None::<fn() -> bool>);
+        Ok::<_, CacheError>((result?, query_started.elapsed()))
+    };
+    let (resp, query_elapsed) = match timeout(state.api.request_timeout, work).await {
+        Ok(Ok(v)) => v,
+        Ok(Err(err)) => {
+            let msg = err.to_string();
+            warn!(
+                event_id = "query_error_classified",
+                request_id = %request_id,
+                route = ROUTE,
+                message = %msg,
+                "query error classified"
+            );
+            let (status, error) = genes_support::query_failure(&msg, req.cursor.is_some());
+            return reject(status, error, request_id);
+        }
+        Err(_) => {
+            warn!(
+                event_id = "request_timeout",
+                request_id = %request_id,
+                route = ROUTE,
+                timeout_ms = state.api.request_timeout.as_millis(),
+                "request timed out"
+            );
+            return reject(
+                StatusCode::GATEWAY_TIMEOUT,
+                handlers::error_json(ApiErrorCode::Timeout, "request timed out", json!({})),
+                request_id,
+            );
+        }
+    };
+    state
+        .metrics
+        .observe_sqlite_query(&format!("{class:?}").to_lowercase(), query_elapsed)
+        .await;
+    let rows_returned = resp.rows.len() as u64;
+    state
+        .metrics
+        .observe_query_row_count(ROUTE, resp.rows.len())
+        .await;
+    let provenance = handlers::dataset_provenance(state, &dataset).await;
+    let payload = genes_response::build_success_payload(
+        &dataset,
+        &req,
+        class,
+        resp,
+        explain_mode,
+        provenance,
+        &contigs,
+    );
+    let bytes = match handlers::serialize_payload_with_capacity(
+        &payload,
+        false,
+        state.api.response_max_bytes / 4,
+    ) {
+        Ok(v) => v,
+        Err(err) => return reject(StatusCode::INTERNAL_SERVER_ERROR, err, request_id),
+    };
+    if bytes.len() > state.api.response_max_bytes {
+        return reject(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            handlers::error_json(
+                ApiErrorCode::ResponseTooLarge,
+                "response exceeds configured size guard",
+                json!({"bytes": bytes.len(), "max": state.api.response_max_bytes}),
+            ),
+            request_id,
+        );
+    }
+    state
+        .metrics
+        .observe_response_size(ROUTE, bytes.len())
+        .await;
+    let mut resp = Response::new(Body::from(bytes));
+    resp.headers_mut()
+        .insert("content-type", HeaderValue::from_static("application/json"));
+    resp.headers_mut()
+        .insert("cache-control", HeaderValue::from_static("no-store"));
+    let resp = with_metered_usage(
+        resp,
+        MeteredUsage {
+            rows_returned,
+            ..MeteredUsage::default()
+        },
+    );
+    handlers::with_request_id(handlers::with_query_class(resp, class), request_id)
+}
@@ -2,7 +2,9 @@
 
 use crate::adapters::inbound::http::handlers;
 use crate::domain::dataset::DatasetId;
-use crate::domain::query::{BiotypePolicy, GeneNamePolicy, GeneQueryRequest, QueryClass};
+use crate::domain::query::{
+    BiotypePolicy, ContigResolution, GeneNamePolicy, GeneQueryRequest, QueryClass,
+};
 use serde_json::{json, Value};
 
 pub(super) fn build_success_payload(
@@ -12,6 +14,7 @@ pub(super) fn build_success_payload(
     resp: bijux_atlas::domain::query::GeneQueryResponse,
     explain_mode: bool,
     provenance: serde_json::Value,
+    contigs: &[ContigResolution],
 ) -> serde_json::Value {
     let next_cursor = resp.next_cursor;
     let has_more = next_cursor.is_some();
@@ -37,6 +40,9 @@ pub(super) fn build_success_payload(
         next_cursor.clone().map(|c| json!({ "next_cursor": c })),
         Some(warnings),
     );
+    if !contigs.is_empty() {
+        payload["data"]["contigs"] = json!(contigs);
+    }
     if explain_mode {
         let name_policy = GeneNamePolicy::default();
         let biotype_policy = BiotypePolicy::default();
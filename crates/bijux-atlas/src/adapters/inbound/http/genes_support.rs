@@ -1,6 +1,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::contracts::api::params::{IncludeField, SortKey};
+use crate::contracts::api::params::{
+    GeneQueryBody, IncludeField, IntervalMode, SortKey, StrandMode,
+};
 use crate::contracts::api::{ApiError, ApiErrorCode};
 use crate::domain::canonical::sha256_hex;
 use crate::domain::dataset::DatasetId;
@@ -9,6 +11,7 @@ use crate::domain::query::{
     QuerySort, RegionFilter,
 };
 use crate::AppState;
+use axum::http::StatusCode;
 use serde_json::json;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -56,6 +59,7 @@ pub(super) fn parse_include(include: Option<Vec<IncludeField>>) -> GeneFields {
             biotype: false,
             transcript_count: false,
             sequence_length: false,
+            strand: false,
         };
         for field in list {
             match field {
@@ -63,6 +67,7 @@ pub(super) fn parse_include(include: Option<Vec<IncludeField>>) -> GeneFields {
                 IncludeField::Biotype => out.biotype = true,
                 IncludeField::Counts => out.transcript_count = true,
                 IncludeField::Length => out.sequence_length = true,
+                IncludeField::Strand => out.strand = true,
             }
         }
         out
@@ -74,6 +79,7 @@ pub(super) fn parse_include(include: Option<Vec<IncludeField>>) -> GeneFields {
             biotype: false,
             transcript_count: false,
             sequence_length: false,
+            strand: false,
         }
     }
 }
@@ -123,7 +129,7 @@ pub(super) async fn acquire_class_permit(
 ) -> Result<tokio::sync::OwnedSemaphorePermit, ApiError> {
     let sem = match class {
         QueryClass::Cheap => state.class_cheap.clone(),
-        QueryClass::Medium => state.class_medium.clone(),
+        QueryClass::Medium | QueryClass::Search => state.class_medium.clone(),
         QueryClass::Heavy => state.class_heavy.clone(),
     };
     sem.try_acquire_owned().map_err(|_| {
@@ -151,6 +157,7 @@ pub(super) fn check_serialization_budget(
         req.fields.biotype,
         req.fields.transcript_count,
         req.fields.sequence_length,
+        req.fields.strand,
     ]
     .into_iter()
     .filter(|x| *x)
@@ -186,56 +193,68 @@ pub(super) fn build_dataset_query(
             json!({}),
         ));
     }
-    if matches!(
-        parsed.strand,
-        Some(crate::contracts::api::params::StrandMode::Plus)
-            | Some(crate::contracts::api::params::StrandMode::Minus)
-            | Some(crate::contracts::api::params::StrandMode::Unknown)
-    ) {
-        return Err(super::handlers::error_json(
-            ApiErrorCode::InvalidQueryParameter,
-            "strand filter is explicit but unsupported for current dataset schema",
-            json!({"allowed": "omit strand or use strand=any"}),
-        ));
-    }
     let region = parse_region(parsed.range)?;
     let name_prefix = parsed.name_like.as_ref().map(|v| v.trim_end_matches('*'));
+    // Annotation records need coordinates and strand whatever `include` says.
+    let fields = if parsed.format.is_some() {
+        GeneFields {
+            strand: true,
+            ..GeneFields::default()
+        }
+    } else {
+        parse_include(parsed.include)
+    };
     let req = GeneQueryRequest {
-        fields: parse_include(parsed.include),
+        fields,
         filter: GeneFilter {
             gene_id: parsed.gene_id,
             name: parsed.name,
             name_prefix: name_prefix.map(ToString::to_string),
             biotype: parsed.biotype,
             region,
-            sort: match parsed.sort {
-                Some(SortKey::GeneIdAsc) => QuerySort::GeneIdAsc,
-                Some(SortKey::RegionAsc) => QuerySort::RegionAsc,
-                None => QuerySort::Auto,
-            },
-            interval: match parsed.interval_mode {
-                Some(crate::contracts::api::params::IntervalMode::Containment) => {
-                    IntervalSemantics::Containment
-                }
-                Some(crate::contracts::api::params::IntervalMode::BoundaryTouch) => {
-                    IntervalSemantics::BoundaryTouch
-                }
-                _ => IntervalSemantics::Overlap,
-            },
-            strand: match parsed.strand {
-                Some(crate::contracts::api::params::StrandMode::Any) | None => {
-                    crate::domain::query::StrandMode::Any
-                }
-                Some(crate::contracts::api::params::StrandMode::Plus) => {
-                    crate::domain::query::StrandMode::Plus
-                }
-                Some(crate::contracts::api::params::StrandMode::Minus) => {
-                    crate::domain::query::StrandMode::Minus
-                }
-                Some(crate::contracts::api::params::StrandMode::Unknown) => {
-                    crate::domain::query::StrandMode::Unknown
-                }
-            },
+            sort: query_sort(parsed.sort),
+            interval: interval_semantics(parsed.interval_mode),
+            strand: strand_mode(parsed.strand),
+            xref: parsed.xref,
+            alias: parsed.alias,
+            ..GeneFilter::default()
+        },
+        limit: parsed.limit,
+        cursor: parsed.cursor,
+        dataset_key: Some(dataset.canonical_string()),
+        allow_full_scan: false,
+    };
+    Ok((dataset, req))
+}
+
+/// Maps a validated `POST /v1/genes/query` body onto the same request `/v1/genes` builds.
+pub(super) fn build_body_query(
+    body: GeneQueryBody,
+    limits: &QueryLimits,
+) -> Result<(DatasetId, GeneQueryRequest), ApiError> {
+    let parsed = crate::contracts::api::params::parse_gene_query_body(
+        body,
+        100,
+        limits.max_limit,
+        limits.max_filter_values,
+    )?;
+    let dataset = DatasetId::new(&parsed.release, &parsed.species, &parsed.assembly)
+        .map_err(|e| ApiError::invalid_param("dataset", &e.to_string()))?;
+    let name_prefix = parsed.name_like.as_ref().map(|v| v.trim_end_matches('*'));
+    let req = GeneQueryRequest {
+        fields: parse_include(parsed.include),
+        filter: GeneFilter {
+            name: parsed.name,
+            name_prefix: name_prefix.map(ToString::to_string),
+            sort: query_sort(parsed.sort),
+            interval: interval_semantics(parsed.interval_mode),
+            strand: strand_mode(parsed.strand),
+            xref: parsed.xref,
+            alias: parsed.alias,
+            gene_ids: parsed.gene_ids,
+            biotypes: parsed.biotypes,
+            regions: parsed.regions,
+            ..GeneFilter::default()
         },
         limit: parsed.limit,
         cursor: parsed.cursor,
@@ -245,6 +264,99 @@ pub(super) fn build_dataset_query(
     Ok((dataset, req))
 }
 
+fn query_sort(sort: Option<SortKey>) -> QuerySort {
+    match sort {
+        Some(SortKey::GeneIdAsc) => QuerySort::GeneIdAsc,
+        Some(SortKey::RegionAsc) => QuerySort::RegionAsc,
+        None => QuerySort::Auto,
+    }
+}
+
+fn interval_semantics(mode: Option<IntervalMode>) -> IntervalSemantics {
+    match mode {
+        Some(IntervalMode::Containment) => IntervalSemantics::Containment,
+        Some(IntervalMode::BoundaryTouch) => IntervalSemantics::BoundaryTouch,
+        _ => IntervalSemantics::Overlap,
+    }
+}
+
+fn strand_mode(strand: Option<StrandMode>) -> crate::domain::query::StrandMode {
+    match strand {
+        Some(StrandMode::Any) | None => crate::domain::query::StrandMode::Any,
+        Some(StrandMode::Plus) => crate::domain::query::StrandMode::Plus,
+        Some(StrandMode::Minus) => crate::domain::query::StrandMode::Minus,
+        Some(StrandMode::Unknown) => crate::domain::query::StrandMode::Unknown,
+    }
+}
+
+/// Maps a gene query engine failure message onto the response status and error body.
+pub(super) fn query_failure(msg: &str, has_cursor: bool) -> (StatusCode, ApiError) {
+    if msg.contains("limit")
+        || msg.contains("span")
+        || msg.contains("scan")
+        || msg.contains("name_prefix")
+    {
+        let (code, reason_code) = if msg.contains("region span exceeds") {
+            (ApiErrorCode::RangeTooLarge, "RANGE_TOO_LARGE")
+        } else if msg.contains("estimated query cost") {
+            (ApiErrorCode::QueryTooExpensive, "QUERY_TOO_EXPENSIVE")
+        } else {
+            (ApiErrorCode::QueryRejectedByPolicy, "QUERY_REJECTED")
+        };
+        let status = if matches!(
+            code,
+            ApiErrorCode::RangeTooLarge | ApiErrorCode::QueryTooExpensive
+        ) {
+            StatusCode::PAYLOAD_TOO_LARGE
+        } else {
+            StatusCode::UNPROCESSABLE_ENTITY
+        };
+        return (
+            status,
+            super::handlers::error_json(
+                code,
+                "query rejected",
+                json!({"message": msg, "reason_code": reason_code}),
+            ),
+        );
+    }
+    if msg.contains("Validation:") || msg.contains("seqid does not exist in dataset") {
+        return (
+            StatusCode::BAD_REQUEST,
+            super::handlers::error_json(
+                ApiErrorCode::InvalidQueryParameter,
+                "invalid query parameter",
+                json!({"message": msg}),
+            ),
+        );
+    }
+    if has_cursor {
+        let reason_code = if msg.contains("UnsupportedVersion") {
+            "CURSOR_VERSION_UNSUPPORTED"
+        } else if msg.contains("DatasetMismatch") {
+            "CURSOR_DATASET_MISMATCH"
+        } else {
+            "CURSOR_INVALID"
+        };
+        return (
+            StatusCode::BAD_REQUEST,
+            super::handlers::error_json(
+                ApiErrorCode::InvalidCursor,
+                "invalid cursor",
+                json!({"message": msg, "reason_code": reason_code}),
+            ),
+        );
+    }
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        super::handlers::error_json(
+            ApiErrorCode::Internal,
+            "query failed",
+            json!({"message": msg}),
+        ),
+    )
+}
+
 pub(super) fn exact_lookup_cache_keys(
     dataset: &DatasetId,
     req: &GeneQueryRequest,
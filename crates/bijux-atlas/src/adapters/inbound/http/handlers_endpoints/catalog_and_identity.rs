@@ -199,6 +199,7 @@ pub(crate) async fn datasets_handler(
 pub(crate) async fn dataset_identity_handler(
     State(state): State<AppState>,
     headers: HeaderMap,
+    access: Option<axum::Extension<DatasetAccess>>,
     axum::extract::Path((release, species, assembly)): axum::extract::Path<(
         String,
         String,
@@ -231,6 +232,19 @@ pub(crate) async fn dataset_identity_handler(
         }
     };
 
+    if let Some(resp) = enforce_dataset_access(
+        &state,
+        access.as_deref(),
+        "/v1/datasets/{release}/{species}/{assembly}",
+        "GET",
+        &dataset,
+        started,
+        &request_id,
+    )
+    .await
+    {
+        return resp;
+    }
     let _ = state.cache.refresh_catalog().await;
     let catalog = state
         .cache
@@ -292,9 +306,15 @@ pub(crate) async fn dataset_identity_handler(
     let endpoints = json!([
         "/v1/genes",
         "/v1/genes/count",
+        "/v1/genes/search",
         "/v1/genes/{gene_id}/transcripts",
         "/v1/genes/{gene_id}/sequence",
         "/v1/transcripts/{tx_id}",
+        "/v1/transcripts/{tx_id}/sequence",
+        "/v1/transcripts/{tx_id}/exons",
+        "/v1/transcripts/{tx_id}/introns",
+        "/v1/transcripts/{tx_id}/utrs",
+        "/v1/exons",
         "/v1/sequence/region"
     ]);
     let query = format!(
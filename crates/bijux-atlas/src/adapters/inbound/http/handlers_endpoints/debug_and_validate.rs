@@ -636,3 +636,227 @@ pub(crate) async fn cache_stats_dump_handler(State(state): State<AppState>) -> i
         .await;
     with_request_id(response, &request_id)
 }
+
+/// Evaluates a hypothetical request with the checks the request path runs:
+/// `route` is the path and query to judge; `principal`, `subject`, `groups`
+/// (comma separated), `api_key_owner` and `api_key_id` describe the caller;
+/// `dataset` supplies the `release/species/assembly` a POST body would name.
+pub(crate) async fn authz_explain_handler(
+    State(state): State<AppState>,
+    axum::extract::Query(params): axum::extract::Query<HashMap<String, String>>,
+) -> impl IntoResponse {
+    let started = Instant::now();
+    let request_id = make_request_id(&state);
+    let reject = |status: StatusCode, code: ApiErrorCode, message: &str| {
+        api_error_response(status, error_json(code, message, json!({})))
+    };
+    let route = params
+        .get("route")
+        .filter(|route| route.starts_with('/'))
+        .and_then(|route| route.parse::<axum::http::Uri>().ok());
+    let (status, resp) = if !state.api.enable_debug_datasets {
+        (
+            StatusCode::NOT_FOUND,
+            reject(
+                StatusCode::NOT_FOUND,
+                ApiErrorCode::InvalidQueryParameter,
+                "debug endpoint disabled",
+            ),
+        )
+    } else if let Some(route) = route {
+        let principal = params.get("principal").map_or("user", String::as_str);
+        let subject = params.get("subject").map_or(principal, String::as_str);
+        let groups: Vec<String> = params
+            .get("groups")
+            .map(|raw| {
+                raw.split(',')
+                    .map(str::trim)
+                    .filter(|group| !group.is_empty())
+                    .map(ToString::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let api_key = params
+            .get("api_key_id")
+            .map(|key_id| state.api_keys.record(key_id).ok_or(key_id));
+        let dataset = params
+            .get("dataset")
+            .map(|raw| DatasetId::from_canonical_string(raw).map_err(|_| raw));
+        let caller = crate::adapters::inbound::http::request_policies::DatasetAccess {
+            principal: principal.to_string(),
+            subject: subject.to_string(),
+            groups: groups.clone(),
+            api_key_owner: params.get("api_key_owner").cloned(),
+            api_key: api_key.clone().and_then(Result::ok),
+        };
+        match (api_key, dataset) {
+            (Some(Err(key_id)), _) => (
+                StatusCode::BAD_REQUEST,
+                api_error_response(
+                    StatusCode::BAD_REQUEST,
+                    error_json(
+                        ApiErrorCode::InvalidQueryParameter,
+                        "unknown api key",
+                        json!({"api_key_id": key_id}),
+                    ),
+                ),
+            ),
+            (_, Some(Err(raw))) => (
+                StatusCode::BAD_REQUEST,
+                api_error_response(
+                    StatusCode::BAD_REQUEST,
+                    error_json(
+                        ApiErrorCode::InvalidQueryParameter,
+                        "dataset must be release/species/assembly",
+                        json!({"dataset": raw}),
+                    ),
+                ),
+            ),
+            (_, dataset) => match crate::adapters::inbound::http::request_policies::explain_request_authorization(
+                &state,
+                &caller,
+                &route,
+                dataset.and_then(Result::ok).as_ref(),
+            ) {
+            Some(explanation) => (
+                StatusCode::OK,
+                Json(json!({
+                    "route": route.to_string(),
+                    "principal": principal,
+                    "subject": subject,
+                    "groups": groups,
+                    "bindings_source": state
+                        .role_bindings
+                        .source()
+                        .map_or_else(|| "packaged".to_string(), |path| path.display().to_string()),
+                    "bindings_fingerprint": state.role_bindings.active_fingerprint(),
+                    "explanation": explanation,
+                }))
+                .into_response(),
+            ),
+            None => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                reject(
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    ApiErrorCode::NotReady,
+                    "no role bindings are active",
+                ),
+            ),
+            },
+        }
+    } else {
+        (
+            StatusCode::BAD_REQUEST,
+            reject(
+                StatusCode::BAD_REQUEST,
+                ApiErrorCode::InvalidQueryParameter,
+                "route must be an absolute request path",
+            ),
+        )
+    };
+    state
+        .metrics
+        .observe_request("/debug/authz/explain", status, started.elapsed())
+        .await;
+    with_request_id(resp, &request_id)
+}
+
+pub(crate) async fn api_keys_handler(State(state): State<AppState>) -> impl IntoResponse {
+    let started = Instant::now();
+    let request_id = make_request_id(&state);
+    let (status, resp) = if !state.api.enable_debug_datasets {
+        (
+            StatusCode::NOT_FOUND,
+            api_error_response(
+                StatusCode::NOT_FOUND,
+                error_json(
+                    ApiErrorCode::InvalidQueryParameter,
+                    "debug endpoint disabled",
+                    json!({}),
+                ),
+            ),
+        )
+    } else if !state.api_keys.is_configured() {
+        (
+            StatusCode::OK,
+            Json(json!({"configured": false, "keys": []})).into_response(),
+        )
+    } else if let Some(keys) = state
+        .api_keys
+        .summaries(crate::adapters::inbound::http::request_policies::chrono_like_unix_secs())
+    {
+        (
+            StatusCode::OK,
+            Json(json!({
+                "configured": true,
+                "source": state.api_keys.source().map(|path| path.display().to_string()),
+                "fingerprint": state.api_keys.active_fingerprint(),
+                "keys": keys,
+            }))
+            .into_response(),
+        )
+    } else {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            api_error_response(
+                StatusCode::SERVICE_UNAVAILABLE,
+                error_json(
+                    ApiErrorCode::NotReady,
+                    "no api key registry is active",
+                    json!({}),
+                ),
+            ),
+        )
+    };
+    state
+        .metrics
+        .observe_request("/debug/api-keys", status, started.elapsed())
+        .await;
+    with_request_id(resp, &request_id)
+}
+
+pub(crate) async fn api_keys_reload_handler(State(state): State<AppState>) -> impl IntoResponse {
+    let started = Instant::now();
+    let request_id = make_request_id(&state);
+    let (status, resp) = if !state.api.enable_debug_datasets {
+        (
+            StatusCode::NOT_FOUND,
+            api_error_response(
+                StatusCode::NOT_FOUND,
+                error_json(
+                    ApiErrorCode::InvalidQueryParameter,
+                    "debug endpoint disabled",
+                    json!({}),
+                ),
+            ),
+        )
+    } else {
+        match state.api_keys.reload_if_changed() {
+            Ok(reloaded) => (
+                StatusCode::OK,
+                Json(json!({
+                    "configured": state.api_keys.is_configured(),
+                    "reloaded": reloaded,
+                    "fingerprint": state.api_keys.active_fingerprint(),
+                }))
+                .into_response(),
+            ),
+            Err(err) => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                api_error_response(
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    error_json(
+                        ApiErrorCode::NotReady,
+                        "api key registry reload failed; active keys unchanged",
+                        json!({"error": err}),
+                    ),
+                ),
+            ),
+        }
+    };
+    state
+        .metrics
+        .observe_request("/debug/api-keys/reload", status, started.elapsed())
+        .await;
+    with_request_id(resp, &request_id)
+}
@@ -1,18 +1,23 @@
 use super::*;
 use crate::adapters::inbound::http::genes;
-use crate::domain::query::query_gene_count;
+use crate::adapters::inbound::http::middleware::quota::{with_metered_usage, MeteredUsage};
+use crate::domain::query::{
+    plan_gene_search, query_gene_count, search_genes, GeneSearchRequest, QueryErrorCode,
+};
 use serde_json::json;
 
 pub(crate) async fn genes_handler(
     State(state): State<AppState>,
     headers: HeaderMap,
+    access: Option<axum::Extension<DatasetAccess>>,
     axum::extract::Query(params): axum::extract::Query<HashMap<String, String>>,
 ) -> Response {
-    genes::genes_handler(State(state), headers, axum::extract::Query(params)).await
+    genes::genes_handler(State(state), headers, access, axum::extract::Query(params)).await
 }
 
 pub(crate) async fn genes_count_handler(
     State(state): State<AppState>,
+    access: Option<axum::Extension<DatasetAccess>>,
     axum::extract::Query(params): axum::extract::Query<HashMap<String, String>>,
 ) -> Response {
     let started = Instant::now();
@@ -52,6 +57,19 @@ pub(crate) async fn genes_count_handler(
                 return with_request_id(resp, &request_id);
             }
         };
+    if let Some(resp) = enforce_dataset_access(
+        &state,
+        access.as_deref(),
+        "/v1/genes/count",
+        "GET",
+        &dataset,
+        started,
+        &request_id,
+    )
+    .await
+    {
+        return resp;
+    }
 
     match state.cache.open_dataset_connection(&dataset).await {
         Ok(c) => {
@@ -113,3 +131,194 @@ pub(crate) async fn genes_count_handler(
         }
     }
 }
+
+pub(crate) async fn genes_search_handler(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    access: Option<axum::Extension<DatasetAccess>>,
+    axum::extract::Query(params): axum::extract::Query<HashMap<String, String>>,
+) -> Response {
+    let started = Instant::now();
+    let request_id = propagated_request_id(&headers, &state);
+    let route = "/v1/genes/search";
+    let dataset = match DatasetId::new(
+        params.get("release").map_or("", String::as_str),
+        params.get("species").map_or("", String::as_str),
+        params.get("assembly").map_or("", String::as_str),
+    ) {
+        Ok(v) => v,
+        Err(e) => {
+            let resp = api_error_response(
+                StatusCode::BAD_REQUEST,
+                error_json(
+                    ApiErrorCode::MissingDatasetDimension,
+                    "missing dataset dimensions",
+                    json!({"message": e.to_string()}),
+                ),
+            );
+            state
+                .metrics
+                .observe_request(route, StatusCode::BAD_REQUEST, started.elapsed())
+                .await;
+            return with_request_id(resp, &request_id);
+        }
+    };
+    if let Some(resp) = enforce_dataset_access(
+        &state,
+        access.as_deref(),
+        route,
+        "GET",
+        &dataset,
+        started,
+        &request_id,
+    )
+    .await
+    {
+        return resp;
+    }
+    let limit = match params.get("limit").map(|v| v.parse::<usize>()) {
+        None => 20_usize.min(state.limits.max_limit),
+        Some(Ok(v)) if v >= 1 && v <= state.limits.max_limit => v,
+        Some(_) => {
+            let resp = api_error_response(
+                StatusCode::BAD_REQUEST,
+                ApiError::invalid_param("limit", params.get("limit").map_or("", String::as_str)),
+            );
+            state
+                .metrics
+                .observe_request(route, StatusCode::BAD_REQUEST, started.elapsed())
+                .await;
+            return with_request_id(resp, &request_id);
+        }
+    };
+    let req = GeneSearchRequest {
+        q: params.get("q").cloned().unwrap_or_default(),
+        limit,
+        dataset_key: Some(dataset.canonical_string()),
+    };
+    let class = match plan_gene_search(&req, &state.limits) {
+        Ok(plan) => plan.class,
+        Err(e) => {
+            let resp = api_error_response(
+                StatusCode::BAD_REQUEST,
+                error_json(
+                    ApiErrorCode::InvalidQueryParameter,
+                    "invalid gene search",
+                    json!({"message": e.message}),
+                ),
+            );
+            state
+                .metrics
+                .observe_request(route, StatusCode::BAD_REQUEST, started.elapsed())
+                .await;
+            return with_request_id(resp, &request_id);
+        }
+    };
+    if crate::adapters::inbound::http::middleware::shedding::should_shed_noncheap(&state, class)
+        .await
+    {
+        crate::record_shed_reason(&state, "bulkhead_shed_noncheap").await;
+        let resp = api_error_response(
+            StatusCode::SERVICE_UNAVAILABLE,
+            error_json(
+                ApiErrorCode::QueryRejectedByPolicy,
+                "server is shedding non-cheap query load",
+                json!({"class": "search"}),
+            ),
+        );
+        state
+            .metrics
+            .observe_request(route, StatusCode::SERVICE_UNAVAILABLE, started.elapsed())
+            .await;
+        return with_request_id(resp, &request_id);
+    }
+    let _class_permit = match state.class_medium.clone().try_acquire_owned() {
+        Ok(v) => v,
+        Err(_) => {
+            let resp = api_error_response(
+                StatusCode::TOO_MANY_REQUESTS,
+                error_json(
+                    ApiErrorCode::QueryRejectedByPolicy,
+                    "search query concurrency limit exceeded",
+                    json!({"class": "search"}),
+                ),
+            );
+            state
+                .metrics
+                .observe_request(route, StatusCode::TOO_MANY_REQUESTS, started.elapsed())
+                .await;
+            return with_request_id(resp, &request_id);
+        }
+    };
+    let conn = match state.cache.open_dataset_connection(&dataset).await {
+        Ok(c) => c,
+        Err(e) => {
+            let resp = api_error_response(
+                StatusCode::SERVICE_UNAVAILABLE,
+                error_json(
+                    ApiErrorCode::NotReady,
+                    "dataset unavailable",
+                    json!({"message": e.to_string()}),
+                ),
+            );
+            state
+                .metrics
+                .observe_request(route, StatusCode::SERVICE_UNAVAILABLE, started.elapsed())
+                .await;
+            return with_request_id(resp, &request_id);
+        }
+    };
+    match search_genes(&conn.conn, &req, &state.limits) {
+        Ok(found) => {
+            let usage = MeteredUsage {
+                rows_returned: found.hits.len() as u64,
+                ..MeteredUsage::default()
+            };
+            let provenance = dataset_provenance(&state, &dataset).await;
+            let body = Json(json_envelope(
+                Some(json!(dataset)),
+                None,
+                json!({
+                    "provenance": provenance,
+                    "q": req.q,
+                    "hits": found.hits
+                }),
+                None,
+                None,
+            ))
+            .into_response();
+            state
+                .metrics
+                .observe_request(route, StatusCode::OK, started.elapsed())
+                .await;
+            with_request_id(
+                with_metered_usage(with_query_class(body, class), usage),
+                &request_id,
+            )
+        }
+        Err(e) => {
+            let (status, code, message) = if e.code == QueryErrorCode::Validation {
+                (
+                    StatusCode::BAD_REQUEST,
+                    ApiErrorCode::InvalidQueryParameter,
+                    "invalid gene search",
+                )
+            } else {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    ApiErrorCode::Internal,
+                    "query failed",
+                )
+            };
+            let resp = api_error_response(
+                status,
+                error_json(code, message, json!({"message": e.message})),
+            );
+            state
+                .metrics
+                .observe_request(route, status, started.elapsed())
+                .await;
+            with_request_id(resp, &request_id)
+        }
+    }
+}
@@ -1,12 +1,20 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::adapters::inbound::http::handlers_utilities::*;
+use crate::adapters::inbound::http::middleware::quota::{with_metered_usage, MeteredUsage};
+use crate::adapters::inbound::http::sequence::dataset_contig_aliases;
+use crate::contracts::api::resolve_region_alias;
+use crate::domain::query::{
+    query_transcript_layout, write_transcript_records, AnnotationFormat, QueryError, Strand,
+    TranscriptLayout, TranscriptRow,
+};
 use crate::*;
 use serde_json::json;
 
 pub(crate) async fn gene_transcripts_handler(
     State(state): State<AppState>,
     headers: HeaderMap,
+    access: Option<axum::Extension<DatasetAccess>>,
     axum::extract::Path(gene_id): axum::extract::Path<String>,
     axum::extract::Query(params): axum::extract::Query<HashMap<String, String>>,
 ) -> Response {
@@ -56,17 +64,53 @@ pub(crate) async fn gene_transcripts_handler(
             return with_request_id(resp, &request_id);
         }
     };
+    if let Some(resp) = enforce_dataset_access(
+        &state,
+        access.as_deref(),
+        "/v1/genes/{gene_id}/transcripts",
+        "GET",
+        &dataset,
+        started,
+        &request_id,
+    )
+    .await
+    {
+        return resp;
+    }
+    let annotation = annotation_format(&params);
+    if params.contains_key("format") && annotation.is_none() {
+        let resp = api_error_response(
+            StatusCode::BAD_REQUEST,
+            ApiError::invalid_param("format", "allowed: bed,gtf,gff3"),
+        );
+        state
+            .metrics
+            .observe_request(
+                "/v1/genes/{gene_id}/transcripts",
+                StatusCode::BAD_REQUEST,
+                started.elapsed(),
+            )
+            .await;
+        return with_request_id(resp, &request_id);
+    }
     let limit = params
         .get("limit")
         .and_then(|v| v.parse::<usize>().ok())
         .unwrap_or(50)
         .min(state.limits.max_transcript_limit);
-    let filter = TranscriptFilter {
+    let mut filter = TranscriptFilter {
         parent_gene_id: Some(gene_id.clone()),
         biotype: params.get("biotype").cloned(),
         transcript_type: params.get("type").cloned(),
         region: parse_region_opt(params.get("region").cloned()),
     };
+    let contig = match filter.region.as_mut() {
+        Some(region) => Some(resolve_region_alias(
+            region,
+            dataset_contig_aliases(&state, &dataset).await.as_ref(),
+        )),
+        None => None,
+    };
     let req = TranscriptQueryRequest {
         filter,
         limit,
@@ -155,19 +199,60 @@ pub(crate) async fn gene_transcripts_handler(
     };
     match bijux_atlas::domain::query::query_transcripts(&conn.conn, &req) {
         Ok(resp) => {
+            let usage = MeteredUsage {
+                rows_returned: resp.rows.len() as u64,
+                ..MeteredUsage::default()
+            };
+            let annotation_body = annotation.map(|format| {
+                transcript_annotation_body(&conn.conn, format, &resp.rows, req.cursor.is_none())
+            });
             let provenance = dataset_provenance(&state, &dataset).await;
-            let body = Json(json_envelope(
+            let mut data = json!({
+                "provenance": provenance,
+                "gene_id": gene_id,
+                "rows": resp.rows
+            });
+            if let Some(contig) = contig {
+                data["contig"] = json!(contig);
+            }
+            let payload = json_envelope(
                 Some(json!(dataset)),
                 Some(json!({ "next_cursor": resp.next_cursor.clone() })),
-                json!({
-                    "provenance": provenance,
-                    "gene_id": gene_id,
-                    "rows": resp.rows
-                }),
+                data,
                 resp.next_cursor.map(|c| json!({ "next_cursor": c })),
                 None,
-            ))
-            .into_response();
+            );
+            let body = if let (Some(format), Some(rendered)) = (annotation, annotation_body) {
+                match rendered {
+                    Ok(bytes) => {
+                        let mut resp = (StatusCode::OK, bytes).into_response();
+                        put_row_stream_headers(resp.headers_mut(), format.media_type(), &payload);
+                        resp
+                    }
+                    Err(e) => api_error_response(
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        error_json(
+                            ApiErrorCode::Internal,
+                            "annotation serialization failed",
+                            json!({"message": e.to_string()}),
+                        ),
+                    ),
+                }
+            } else if wants_arrow(&headers) {
+                match serialize_rows_as_arrow(
+                    &payload,
+                    &crate::adapters::outbound::columnar::transcript_row_schema(),
+                ) {
+                    Ok(bytes) => {
+                        let mut resp = (StatusCode::OK, bytes).into_response();
+                        put_arrow_headers(resp.headers_mut(), &payload);
+                        resp
+                    }
+                    Err(err) => api_error_response(StatusCode::INTERNAL_SERVER_ERROR, err),
+                }
+            } else {
+                Json(payload).into_response()
+            };
             state
                 .metrics
                 .observe_request(
@@ -176,7 +261,7 @@ pub(crate) async fn gene_transcripts_handler(
                     started.elapsed(),
                 )
                 .await;
-            with_request_id(body, &request_id)
+            with_request_id(with_metered_usage(body, usage), &request_id)
         }
         Err(e) => {
             let resp = api_error_response(
@@ -200,9 +285,36 @@ pub(crate) async fn gene_transcripts_handler(
     }
 }
 
+/// Renders one transcript page with each row's exon and CDS layout.
+fn transcript_annotation_body(
+    conn: &Connection,
+    format: AnnotationFormat,
+    rows: &[TranscriptRow],
+    first_page: bool,
+) -> Result<Vec<u8>, QueryError> {
+    let mut out = String::new();
+    if first_page {
+        out.push_str(format.header());
+    }
+    for row in rows {
+        let layout = query_transcript_layout(conn, &row.transcript_id)?.unwrap_or_else(|| {
+            TranscriptLayout {
+                transcript_id: row.transcript_id.clone(),
+                seqid: row.seqid.clone(),
+                strand: Strand::Unknown,
+                exons: Vec::new(),
+                cds: Vec::new(),
+            }
+        });
+        write_transcript_records(&mut out, format, row, &layout);
+    }
+    Ok(out.into_bytes())
+}
+
 pub(crate) async fn transcript_summary_handler(
     State(state): State<AppState>,
     headers: HeaderMap,
+    access: Option<axum::Extension<DatasetAccess>>,
     axum::extract::Path(tx_id): axum::extract::Path<String>,
     axum::extract::Query(params): axum::extract::Query<HashMap<String, String>>,
 ) -> Response {
@@ -234,6 +346,19 @@ pub(crate) async fn transcript_summary_handler(
             return with_request_id(resp, &request_id);
         }
     };
+    if let Some(resp) = enforce_dataset_access(
+        &state,
+        access.as_deref(),
+        "/v1/transcripts/{tx_id}",
+        "GET",
+        &dataset,
+        started,
+        &request_id,
+    )
+    .await
+    {
+        return resp;
+    }
     let class = QueryClass::Medium;
     if crate::adapters::inbound::http::middleware::shedding::should_shed_noncheap(&state, class)
         .await
@@ -366,3 +491,412 @@ pub(crate) async fn transcript_summary_handler(
         }
     }
 }
+
+#[derive(Clone, Copy)]
+enum TranscriptFeatureKind {
+    Exons,
+    Introns,
+    Utrs,
+}
+
+impl TranscriptFeatureKind {
+    const fn route(self) -> &'static str {
+        match self {
+            Self::Exons => "/v1/transcripts/{tx_id}/exons",
+            Self::Introns => "/v1/transcripts/{tx_id}/introns",
+            Self::Utrs => "/v1/transcripts/{tx_id}/utrs",
+        }
+    }
+
+    const fn key(self) -> &'static str {
+        match self {
+            Self::Exons => "exons",
+            Self::Introns => "introns",
+            Self::Utrs => "utrs",
+        }
+    }
+
+    fn query(
+        self,
+        conn: &rusqlite::Connection,
+        tx_id: &str,
+    ) -> Result<Option<serde_json::Value>, bijux_atlas::domain::query::QueryError> {
+        use bijux_atlas::domain::query::{
+            query_transcript_exons, query_transcript_introns, query_transcript_utrs,
+        };
+        Ok(match self {
+            Self::Exons => query_transcript_exons(conn, tx_id)?.map(|rows| json!(rows)),
+            Self::Introns => query_transcript_introns(conn, tx_id)?.map(|rows| json!(rows)),
+            Self::Utrs => query_transcript_utrs(conn, tx_id)?.map(|rows| json!(rows)),
+        })
+    }
+}
+
+/// Shared admission and envelope for the per-transcript exon, intron and UTR listings.
+async fn transcript_features_response(
+    state: AppState,
+    headers: HeaderMap,
+    access: Option<&DatasetAccess>,
+    tx_id: String,
+    params: HashMap<String, String>,
+    kind: TranscriptFeatureKind,
+) -> Response {
+    let started = Instant::now();
+    let request_id = propagated_request_id(&headers, &state);
+    let route = kind.route();
+    let dataset = match DatasetId::new(
+        params.get("release").map_or("", String::as_str),
+        params.get("species").map_or("", String::as_str),
+        params.get("assembly").map_or("", String::as_str),
+    ) {
+        Ok(v) => v,
+        Err(e) => {
+            let resp = api_error_response(
+                StatusCode::BAD_REQUEST,
+                error_json(
+                    ApiErrorCode::MissingDatasetDimension,
+                    "missing dataset dimensions",
+                    json!({"message": e.to_string()}),
+                ),
+            );
+            state
+                .metrics
+                .observe_request(route, StatusCode::BAD_REQUEST, started.elapsed())
+                .await;
+            return with_request_id(resp, &request_id);
+        }
+    };
+    if let Some(resp) =
+        enforce_dataset_access(&state, access, route, "GET", &dataset, started, &request_id).await
+    {
+        return resp;
+    }
+    if crate::adapters::inbound::http::middleware::shedding::should_shed_noncheap(
+        &state,
+        QueryClass::Medium,
+    )
+    .await
+    {
+        let resp = api_error_response(
+            StatusCode::SERVICE_UNAVAILABLE,
+            error_json(
+                ApiErrorCode::QueryRejectedByPolicy,
+                "server is shedding non-cheap query load",
+                json!({"class":"medium"}),
+            ),
+        );
+        state
+            .metrics
+            .observe_request(route, StatusCode::SERVICE_UNAVAILABLE, started.elapsed())
+            .await;
+        return with_request_id(resp, &request_id);
+    }
+    let _class_permit = match state.class_medium.clone().try_acquire_owned() {
+        Ok(v) => v,
+        Err(_) => {
+            let resp = api_error_response(
+                StatusCode::TOO_MANY_REQUESTS,
+                error_json(
+                    ApiErrorCode::QueryRejectedByPolicy,
+                    "transcript feature medium bulkhead saturated",
+                    json!({}),
+                ),
+            );
+            state
+                .metrics
+                .observe_request(route, StatusCode::TOO_MANY_REQUESTS, started.elapsed())
+                .await;
+            return with_request_id(resp, &request_id);
+        }
+    };
+    let conn = match state.cache.open_dataset_connection(&dataset).await {
+        Ok(c) => c,
+        Err(e) => {
+            let resp =
+                crate::adapters::inbound::http::sequence::dataset_unavailable(&e.to_string());
+            let status = resp.status();
+            state
+                .metrics
+                .observe_request(route, status, started.elapsed())
+                .await;
+            return with_request_id(resp, &request_id);
+        }
+    };
+    let (status, resp) = match kind.query(&conn.conn, &tx_id) {
+        Ok(Some(rows)) => {
+            let usage = MeteredUsage {
+                rows_returned: rows.as_array().map_or(0, Vec::len) as u64,
+                ..MeteredUsage::default()
+            };
+            let provenance = dataset_provenance(&state, &dataset).await;
+            let mut data = json!({"provenance": provenance, "transcript_id": tx_id});
+            data[kind.key()] = rows;
+            let body = Json(json_envelope(Some(json!(dataset)), None, data, None, None));
+            (
+                StatusCode::OK,
+                with_metered_usage(body.into_response(), usage),
+            )
+        }
+        Ok(None) => (
+            StatusCode::NOT_FOUND,
+            api_error_response(
+                StatusCode::NOT_FOUND,
+                error_json(
+                    ApiErrorCode::InvalidQueryParameter,
+                    "transcript not found",
+                    json!({"transcript_id": tx_id}),
+                ),
+            ),
+        ),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            api_error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                error_json(
+                    ApiErrorCode::Internal,
+                    "transcript feature query failed",
+                    json!({"message": e.to_string()}),
+                ),
+            ),
+        ),
+    };
+    state
+        .metrics
+        .observe_request(route, status, started.elapsed())
+        .await;
+    with_request_id(resp, &request_id)
+}
+
+pub(crate) async fn transcript_exons_handler(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    access: Option<axum::Extension<DatasetAccess>>,
+    axum::extract::Path(tx_id): axum::extract::Path<String>,
+    axum::extract::Query(params): axum::extract::Query<HashMap<String, String>>,
+) -> Response {
+    transcript_features_response(
+        state,
+        headers,
+        access.as_deref(),
+        tx_id,
+        params,
+        TranscriptFeatureKind::Exons,
+    )
+    .await
+}
+
+pub(crate) async fn transcript_introns_handler(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    access: Option<axum::Extension<DatasetAccess>>,
+    axum::extract::Path(tx_id): axum::extract::Path<String>,
+    axum::extract::Query(params): axum::extract::Query<HashMap<String, String>>,
+) -> Response {
+    transcript_features_response(
+        state,
+        headers,
+        access.as_deref(),
+        tx_id,
+        params,
+        TranscriptFeatureKind::Introns,
+    )
+    .await
+}
+
+pub(crate) async fn transcript_utrs_handler(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    access: Option<axum::Extension<DatasetAccess>>,
+    axum::extract::Path(tx_id): axum::extract::Path<String>,
+    axum::extract::Query(params): axum::extract::Query<HashMap<String, String>>,
+) -> Response {
+    transcript_features_response(
+        state,
+        headers,
+        access.as_deref(),
+        tx_id,
+        params,
+        TranscriptFeatureKind::Utrs,
+    )
+    .await
+}
+
+/// Exons overlapping `region`, served from the exon R-tree.
+pub(crate) async fn exons_region_handler(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    access: Option<axum::Extension<DatasetAccess>>,
+    axum::extract::Query(params): axum::extract::Query<HashMap<String, String>>,
+) -> Response {
+    const ROUTE: &str = "/v1/exons";
+    let started = Instant::now();
+    let request_id = propagated_request_id(&headers, &state);
+    let reject = |status: StatusCode, err: ApiError| {
+        let state = state.clone();
+        let request_id = request_id.clone();
+        async move {
+            state
+                .metrics
+                .observe_request(ROUTE, status, started.elapsed())
+                .await;
+            with_request_id(api_error_response(status, err), &request_id)
+        }
+    };
+    let dataset = match DatasetId::new(
+        params.get("release").map_or("", String::as_str),
+        params.get("species").map_or("", String::as_str),
+        params.get("assembly").map_or("", String::as_str),
+    ) {
+        Ok(v) => v,
+        Err(e) => {
+            return reject(
+                StatusCode::BAD_REQUEST,
+                error_json(
+                    ApiErrorCode::MissingDatasetDimension,
+                    "missing dataset dimensions",
+                    json!({"message": e.to_string()}),
+                ),
+            )
+            .await;
+        }
+    };
+    if let Some(resp) = enforce_dataset_access(
+        &state,
+        access.as_deref(),
+        ROUTE,
+        "GET",
+        &dataset,
+        started,
+        &request_id,
+    )
+    .await
+    {
+        return resp;
+    }
+    let raw_region = params.get("region").cloned().unwrap_or_default();
+    let mut region = match parse_region_opt(Some(raw_region.clone())) {
+        Some(r) if r.start >= 1 && r.end >= r.start => r,
+        _ => {
+            return reject(
+                StatusCode::BAD_REQUEST,
+                ApiError::invalid_param("region", &raw_region),
+            )
+            .await;
+        }
+    };
+    let span = region.end - region.start + 1;
+    if span > state.limits.max_region_span {
+        return reject(
+            StatusCode::BAD_REQUEST,
+            error_json(
+                ApiErrorCode::RangeTooLarge,
+                "region span exceeds limit",
+                json!({"span": span, "max": state.limits.max_region_span}),
+            ),
+        )
+        .await;
+    }
+    let limit = match params.get("limit") {
+        None => 100,
+        Some(raw) => match raw.parse::<usize>() {
+            Ok(v) if v >= 1 && v <= state.limits.max_limit => v,
+            _ => {
+                return reject(
+                    StatusCode::BAD_REQUEST,
+                    ApiError::invalid_param("limit", raw),
+                )
+                .await;
+            }
+        },
+    };
+    if crate::adapters::inbound::http::middleware::shedding::should_shed_noncheap(
+        &state,
+        QueryClass::Medium,
+    )
+    .await
+    {
+        return reject(
+            StatusCode::SERVICE_UNAVAILABLE,
+            error_json(
+                ApiErrorCode::QueryRejectedByPolicy,
+                "server is shedding non-cheap query load",
+                json!({"class":"medium"}),
+            ),
+        )
+        .await;
+    }
+    let Ok(_class_permit) = state.class_medium.clone().try_acquire_owned() else {
+        return reject(
+            StatusCode::TOO_MANY_REQUESTS,
+            error_json(
+                ApiErrorCode::QueryRejectedByPolicy,
+                "exon region medium bulkhead saturated",
+                json!({}),
+            ),
+        )
+        .await;
+    };
+    let conn = match state.cache.open_dataset_connection(&dataset).await {
+        Ok(c) => c,
+        Err(e) => {
+            let resp =
+                crate::adapters::inbound::http::sequence::dataset_unavailable(&e.to_string());
+            let status = resp.status();
+            state
+                .metrics
+                .observe_request(ROUTE, status, started.elapsed())
+                .await;
+            return with_request_id(resp, &request_id);
+        }
+    };
+    let contig = resolve_region_alias(
+        &mut region,
+        dataset_contig_aliases(&state, &dataset).await.as_ref(),
+    );
+    let mut rows = match bijux_atlas::domain::query::query_exons_in_region(
+        &conn.conn,
+        &region.seqid,
+        region.start,
+        region.end,
+        limit + 1,
+    ) {
+        Ok(rows) => rows,
+        Err(e) => {
+            return reject(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                error_json(
+                    ApiErrorCode::Internal,
+                    "exon region query failed",
+                    json!({"message": e.to_string()}),
+                ),
+            )
+            .await;
+        }
+    };
+    let truncated = rows.len() > limit;
+    rows.truncate(limit);
+    let usage = MeteredUsage {
+        rows_returned: rows.len() as u64,
+        ..MeteredUsage::default()
+    };
+    let provenance = dataset_provenance(&state, &dataset).await;
+    let body = Json(json_envelope(
+        Some(json!(dataset)),
+        None,
+        json!({
+            "provenance": provenance,
+            "region": {"seqid": region.seqid, "start": region.start, "end": region.end},
+            "contig": contig,
+            "exons": rows,
+            "truncated": truncated
+        }),
+        None,
+        None,
+    ))
+    .into_response();
+    state
+        .metrics
+        .observe_request(ROUTE, StatusCode::OK, started.elapsed())
+        .await;
+    with_request_id(with_metered_usage(body, usage), &request_id)
+}
@@ -0,0 +1,228 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#![deny(clippy::redundant_clone)]
+
+use crate::adapters::inbound::http::handlers::{
+    api_error_response, dataset_artifact_hash, dataset_etag, error_json, if_none_match,
+    put_cache_headers, serialize_payload_with_capacity, with_request_id, CachePolicy,
+};
+use crate::adapters::inbound::http::sequence::{
+    dataset_contig_aliases, parse_dataset, parse_region_with_aliases,
+};
+use crate::*;
+use bijux_atlas::domain::query::{ContigAliases, Liftover, DEFAULT_LIFTOVER_MIN_MATCH};
+use serde_json::json;
+use std::collections::HashMap;
+use tracing::info;
+
+const ROUTE: &str = "/v1/liftover";
+
+/// Chains attached to `dataset` that lift onto `to_assembly`, or the error response to send.
+pub(super) async fn dataset_liftover(
+    state: &AppState,
+    dataset: &DatasetId,
+    to_assembly: Option<&str>,
+) -> Result<Arc<Liftover>, Response> {
+    let liftover = match state.cache.load_liftover(dataset).await {
+        Ok(v) => v,
+        Err(e) => {
+            return Err(api_error_response(
+                StatusCode::SERVICE_UNAVAILABLE,
+                error_json(
+                    ApiErrorCode::NotReady,
+                    "liftover chains unavailable",
+                    json!({"message": e.to_string()}),
+                ),
+            ))
+        }
+    };
+    match liftover {
+        Some(l) if to_assembly.is_none_or(|a| l.chain_set().target_assembly.as_str() == a) => Ok(l),
+        other => Err(api_error_response(
+            StatusCode::NOT_FOUND,
+            error_json(
+                ApiErrorCode::DatasetNotFound,
+                "no liftover chain for assembly pair",
+                json!({
+                    "dataset": dataset.canonical_string(),
+                    "to_assembly": to_assembly,
+                    "available": other.map(|l| l.chain_set().target_assembly.as_str().to_string()),
+                }),
+            ),
+        )),
+    }
+}
+
+/// Aliases of the dataset a lift from `dataset` lands on: the newest catalogued
+/// release of the same species on the chain set's target assembly. Chain files
+/// name target contigs in their own convention, so lifted seqids resolve
+/// through these; an assembly the server does not hold has none.
+async fn target_contig_aliases(
+    state: &AppState,
+    dataset: &DatasetId,
+    liftover: &Liftover,
+) -> Arc<ContigAliases> {
+    let target_assembly = &liftover.chain_set().target_assembly;
+    let _ = state.cache.refresh_catalog().await;
+    let target = state.cache.current_catalog().await.and_then(|catalog| {
+        catalog
+            .datasets
+            .iter()
+            .filter(|e| {
+                e.dataset.species == dataset.species && &e.dataset.assembly == target_assembly
+            })
+            .map(|e| e.dataset.clone())
+            .max_by(|a, b| a.release.as_str().cmp(b.release.as_str()))
+    });
+    match target {
+        Some(target) => dataset_contig_aliases(state, &target).await,
+        None => Arc::default(),
+    }
+}
+
+fn parse_min_match(params: &HashMap<String, String>) -> Result<f64, ApiError> {
+    match params.get("min_match") {
+        None => Ok(DEFAULT_LIFTOVER_MIN_MATCH),
+        Some(raw) => raw
+            .parse::<f64>()
+            .ok()
+            .filter(|v| (0.0..=1.0).contains(v))
+            .ok_or_else(|| ApiError::invalid_param("min_match", raw)),
+    }
+}
+
+pub(crate) async fn liftover_handler(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    access: Option<axum::Extension<DatasetAccess>>,
+    axum::extract::Query(params): axum::extract::Query<HashMap<String, String>>,
+) -> Response {
+    let started = Instant::now();
+    let request_id =
+        crate::adapters::inbound::http::handlers::propagated_request_id(&headers, &state);
+    info!(request_id = %request_id, route = ROUTE, "request start");
+    let bad_request =
+        |e: ApiError| with_request_id(api_error_response(StatusCode::BAD_REQUEST, e), &request_id);
+    let dataset = match parse_dataset(&params) {
+        Ok(v) => v,
+        Err(e) => return bad_request(e),
+    };
+    if let Some(resp) = enforce_dataset_access(
+        &state,
+        access.as_deref(),
+        ROUTE,
+        "GET",
+        &dataset,
+        started,
+        &request_id,
+    )
+    .await
+    {
+        return resp;
+    }
+    let Some(raw_region) = params.get("region") else {
+        return bad_request(ApiError::invalid_param("region", "missing"));
+    };
+    let aliases = dataset_contig_aliases(&state, &dataset).await;
+    let (contig, start, end) = match parse_region_with_aliases(raw_region, &aliases) {
+        Ok(v) => v,
+        Err(e) => return bad_request(e),
+    };
+    let seqid = contig.seqid.clone();
+    let min_match = match parse_min_match(&params) {
+        Ok(v) => v,
+        Err(e) => return bad_request(e),
+    };
+    let liftover = match dataset_liftover(
+        &state,
+        &dataset,
+        params.get("to_assembly").map(String::as_str),
+    )
+    .await
+    {
+        Ok(v) => v,
+        Err(resp) => return with_request_id(resp, &request_id),
+    };
+
+    let manifest_summary = state.cache.fetch_manifest_summary(&dataset).await.ok();
+    let artifact_hash = dataset_artifact_hash(manifest_summary.as_ref(), &dataset);
+    let etag = dataset_etag(&artifact_hash, ROUTE, &params);
+    if if_none_match(&headers).as_deref() == Some(etag.as_str()) {
+        let mut resp = StatusCode::NOT_MODIFIED.into_response();
+        put_cache_headers(
+            resp.headers_mut(),
+            state.api.immutable_gene_ttl,
+            &etag,
+            CachePolicy::ImmutableDataset,
+        );
+        state
+            .metrics
+            .observe_request(ROUTE, StatusCode::NOT_MODIFIED, started.elapsed())
+            .await;
+        return with_request_id(resp, &request_id);
+    }
+
+    let source_len = end - start + 1;
+    let target_aliases = target_contig_aliases(&state, &dataset, &liftover).await;
+    let target = liftover
+        .lift_with_min_match(&seqid, start, end, min_match)
+        .map(|mut t| {
+            t.seqid = target_aliases.resolve(&t.seqid).seqid;
+            t
+        });
+    let payload = crate::adapters::inbound::http::handlers::json_envelope(
+        Some(json!(dataset)),
+        None,
+        json!({
+            "liftover": {
+                "source": {
+                    "assembly": dataset.assembly,
+                    "seqid": seqid,
+                    "start": start,
+                    "end": end,
+                },
+                "target_assembly": liftover.chain_set().target_assembly,
+                "min_match": min_match,
+                "mapped": target.is_some(),
+                "target": target.map(|t| json!({
+                    "seqid": t.seqid,
+                    "start": t.start,
+                    "end": t.end,
+                    "strand": t.strand.as_symbol(),
+                    "chain_id": t.chain_id,
+                    "mapped_bases": t.mapped_bases,
+                    "mapped_fraction": t.mapped_fraction(source_len),
+                })),
+            },
+            "contig": contig,
+        }),
+        None,
+        None,
+    );
+    let body = match serialize_payload_with_capacity(&payload, false, 512) {
+        Ok(v) => v,
+        Err(e) => {
+            return with_request_id(
+                api_error_response(StatusCode::INTERNAL_SERVER_ERROR, e),
+                &request_id,
+            )
+        }
+    };
+    let mut out_headers = HeaderMap::new();
+    put_cache_headers(
+        &mut out_headers,
+        state.api.immutable_gene_ttl,
+        &etag,
+        CachePolicy::ImmutableDataset,
+    );
+    out_headers.insert(
+        "content-type",
+        HeaderValue::from_static("application/json; charset=utf-8"),
+    );
+    let resp = (StatusCode::OK, out_headers, body).into_response();
+    state
+        .metrics
+        .observe_request(ROUTE, StatusCode::OK, started.elapsed())
+        .await;
+    with_request_id(resp, &request_id)
+}
@@ -1,5 +1,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub(crate) mod error_envelope;
+pub(crate) mod quota;
 pub(crate) mod request_tracing;
 pub(crate) mod shedding;
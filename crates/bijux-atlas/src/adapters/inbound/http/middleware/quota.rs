@@ -0,0 +1,255 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Per-tenant quota enforcement.
+//!
+//! `security_middleware` attaches a [`TenantQuota`] to requests whose API key
+//! or token subject resolves to a tier. This layer charges the tier's request
+//! bucket, refuses metered routes once their daily budget is spent, and adds
+//! the [`MeteredUsage`] a handler reports to the tenant's daily counters. A
+//! request that starts under budget always completes, so a tenant can overrun
+//! a daily budget by at most one response.
+//!
+//! Every route that returns a list of records draws from the rows budget.
+//! Routes that answer with a single record or an aggregate (`/v1/genes/count`,
+//! `/v1/liftover`, `/v1/transcripts/{id}`, dataset and usage listings) only
+//! charge the request bucket.
+
+use crate::adapters::inbound::http::handlers;
+use crate::adapters::inbound::http::request_policies::chrono_like_unix_secs;
+use crate::adapters::outbound::api_keys::TenantQuota;
+use crate::adapters::outbound::telemetry::quota_ledger::{QuotaMetric, SECS_PER_DAY};
+use crate::adapters::outbound::telemetry::rate_limiter::RateLimitDecision;
+use crate::contracts::api::ApiErrorCode;
+use crate::AppState;
+use axum::body::Body;
+use axum::extract::State;
+use axum::http::{HeaderValue, Request, StatusCode};
+use axum::middleware::Next;
+use axum::response::Response;
+use serde_json::json;
+
+/// Daily budget consumption a handler reports as a response extension.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct MeteredUsage {
+    pub sequence_bases: u64,
+    pub rows_returned: u64,
+}
+
+impl MeteredUsage {
+    pub(crate) const fn amount(self, metric: QuotaMetric) -> u64 {
+        match metric {
+            QuotaMetric::SequenceBases => self.sequence_bases,
+            QuotaMetric::RowsReturned => self.rows_returned,
+        }
+    }
+}
+
+pub(crate) fn with_metered_usage(mut response: Response, usage: MeteredUsage) -> Response {
+    response.extensions_mut().insert(usage);
+    response
+}
+
+/// The daily budget `route` draws from, if any.
+fn metered_budget(route: &str) -> Option<QuotaMetric> {
+    if route.starts_with("/v1/sequence/")
+        || (route.starts_with("/v1/") && route.ends_with("/sequence"))
+    {
+        Some(QuotaMetric::SequenceBases)
+    } else if matches!(
+        route,
+        "/v1/genes" | "/v1/genes/query" | "/v1/genes/search" | "/v1/exons"
+    ) || route.starts_with("/v1/diff/")
+        || (route.starts_with("/v1/genes/") && route.ends_with("/transcripts"))
+        || (route.starts_with("/v1/transcripts/")
+            && ["/exons", "/introns", "/utrs"]
+                .iter()
+                .any(|feature| route.ends_with(feature)))
+    {
+        Some(QuotaMetric::RowsReturned)
+    } else {
+        None
+    }
+}
+
+/// UTC day number of `now_unix_s` and the seconds left in it.
+pub(crate) const fn utc_day(now_unix_s: u64) -> (u64, u64) {
+    (
+        now_unix_s / SECS_PER_DAY,
+        SECS_PER_DAY - now_unix_s % SECS_PER_DAY,
+    )
+}
+
+pub(crate) async fn quota_middleware(
+    State(state): State<AppState>,
+    mut req: Request<Body>,
+    next: Next,
+) -> Response {
+    let Some(quota) = req.extensions().get::<TenantQuota>().cloned() else {
+        return next.run(req).await;
+    };
+    let route = req.uri().path().to_string();
+    let request_id = handlers::propagated_request_id(req.headers(), &state);
+    let decision = state
+        .tenant_limiter
+        .charge(&quota.tenant, &quota.tier.requests, 1.0, 1)
+        .await;
+    if !decision.allowed {
+        crate::record_shed_reason(&state, "tenant_rate_limited").await;
+        let resp = handlers::api_error_response(
+            StatusCode::TOO_MANY_REQUESTS,
+            handlers::error_json(
+                ApiErrorCode::RateLimited,
+                "rate limit exceeded",
+                json!({"scope": "tenant", "tier": quota.tier_name}),
+            ),
+        );
+        return with_quota_headers(
+            handlers::with_request_id(resp, &request_id),
+            &quota,
+            &decision,
+            Some(decision.retry_after_secs),
+        );
+    }
+    let (day, day_reset_secs) = utc_day(chrono_like_unix_secs());
+    if let Some(metric) = metered_budget(&route) {
+        if let Some(budget) = metric.daily_budget(&quota.tier) {
+            let used = state.quota_ledger.used(&quota.tenant, metric, day).await;
+            if used >= budget {
+                crate::record_shed_reason(&state, "tenant_quota_exhausted").await;
+                let resp = handlers::api_error_response(
+                    StatusCode::TOO_MANY_REQUESTS,
+                    handlers::error_json(
+                        ApiErrorCode::RateLimited,
+                        "daily quota exhausted",
+                        json!({
+                            "scope": "tenant",
+                            "tier": quota.tier_name,
+                            "metric": metric.as_str(),
+                            "limit": budget,
+                            "used": used
+                        }),
+                    ),
+                );
+                return with_quota_headers(
+                    handlers::with_request_id(resp, &request_id),
+                    &quota,
+                    &decision,
+                    Some(day_reset_secs),
+                );
+            }
+        }
+    }
+    req.extensions_mut().insert(decision);
+    let resp = next.run(req).await;
+    if let Some(usage) = resp.extensions().get::<MeteredUsage>().copied() {
+        for metric in QuotaMetric::ALL {
+            let amount = usage.amount(metric);
+            if amount > 0 {
+                state
+                    .quota_ledger
+                    .add(&quota.tenant, metric, day, amount)
+                    .await;
+            }
+        }
+    }
+    with_quota_headers(resp, &quota, &decision, None)
+}
+
+/// Adds `RateLimit-Policy` and `RateLimit` (draft-ietf-httpapi-ratelimit-headers)
+/// for the request bucket, plus `Retry-After` on refusals.
+fn with_quota_headers(
+    mut response: Response,
+    quota: &TenantQuota,
+    decision: &RateLimitDecision,
+    retry_after_secs: Option<u64>,
+) -> Response {
+    let name = structured_string(&quota.tier_name);
+    let headers = response.headers_mut();
+    if let Ok(v) = HeaderValue::from_str(&format!(
+        "{name};q={};w={}",
+        decision.limit, decision.window_secs
+    )) {
+        headers.insert("ratelimit-policy", v);
+    }
+    if let Ok(v) = HeaderValue::from_str(&format!(
+        "{name};r={};t={}",
+        decision.remaining, decision.reset_secs
+    )) {
+        headers.insert("ratelimit", v);
+    }
+    if let Some(secs) = retry_after_secs {
+        headers.insert("retry-after", HeaderValue::from(secs.max(1)));
+    }
+    response
+}
+
+/// `value` as an RFC 8941 string item.
+fn structured_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for ch in value.chars() {
+        if ch == '"' || ch == '\\' {
+            out.push('\\');
+        }
+        out.push(ch);
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{metered_budget, structured_string, utc_day};
+    use crate::adapters::outbound::telemetry::quota_ledger::QuotaMetric;
+
+    #[test]
+    fn routes_map_to_the_budget_they_consume() {
+        for route in [
+            "/v1/sequence/region",
+            "/v1/sequence/batch",
+            "/v1/genes/g1/sequence",
+            "/v1/transcripts/tx1/sequence",
+        ] {
+            assert_eq!(
+                metered_budget(route),
+                Some(QuotaMetric::SequenceBases),
+                "{route}"
+            );
+        }
+        for route in [
+            "/v1/genes",
+            "/v1/genes/query",
+            "/v1/genes/search",
+            "/v1/genes/g1/transcripts",
+            "/v1/exons",
+            "/v1/transcripts/tx1/exons",
+            "/v1/transcripts/tx1/introns",
+            "/v1/transcripts/tx1/utrs",
+            "/v1/diff/genes",
+            "/v1/diff/region",
+        ] {
+            assert_eq!(
+                metered_budget(route),
+                Some(QuotaMetric::RowsReturned),
+                "{route}"
+            );
+        }
+        for route in [
+            "/v1/genes/count",
+            "/v1/liftover",
+            "/v1/transcripts/tx1",
+            "/v1/datasets",
+            "/v1/usage",
+        ] {
+            assert_eq!(metered_budget(route), None, "{route}");
+        }
+    }
+
+    #[test]
+    fn headers_quote_names_and_days_roll_over_at_utc_midnight() {
+        assert_eq!(structured_string("gold"), "\"gold\"");
+        assert_eq!(structured_string("a\"b\\c"), "\"a\\\"b\\\\c\"");
+        assert_eq!(utc_day(86_400 * 3), (3, 86_400));
+        assert_eq!(utc_day(86_400 * 3 + 86_399), (3, 1));
+    }
+}
@@ -9,6 +9,7 @@ pub(crate) mod genes_support;
 pub(crate) mod handlers;
 pub(crate) mod handlers_endpoints;
 pub(crate) mod handlers_utilities;
+pub(crate) mod liftover;
 pub(crate) mod middleware;
 pub(crate) mod presenters;
 pub(crate) mod request_identity;
@@ -18,3 +19,5 @@ pub(crate) mod response_contract;
 pub(crate) mod response_encoding;
 pub mod router;
 pub(crate) mod sequence;
+pub(crate) mod sequence_batch;
+pub(crate) mod usage;
@@ -61,6 +61,7 @@ pub(crate) fn with_query_class(mut response: Response, class: QueryClass) -> Res
         QueryClass::Cheap => "cheap",
         QueryClass::Medium => "medium",
         QueryClass::Heavy => "heavy",
+        QueryClass::Search => "search",
     };
     response
         .headers_mut()
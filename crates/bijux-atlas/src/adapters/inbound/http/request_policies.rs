@@ -1,17 +1,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::adapters::inbound::tls::TlsPeer;
+use crate::adapters::outbound::oidc::OidcValidationError;
 use crate::app::server::state::AppState;
 use crate::contracts::api::{ApiError, ApiErrorCode};
 use crate::domain::dataset::DatasetId;
 use crate::domain::security::authorization::{
-    AuthorizationDecision, AuthorizationEngine, AuthorizationPolicy, PermissionCatalog,
-    PermissionEvaluator, RoleCatalog, RoleRegistry,
+    AuthorizationDecision, AuthorizationExplanation, AuthorizationIdentity, AuthorizationRequest,
 };
 use crate::domain::security::data_protection::https_enforced;
-use crate::packaged::{AUTH_POLICY_YAML, PERMISSIONS_YAML, ROLES_YAML};
+use crate::packaged::AUTH_POLICY_YAML;
 use crate::sha256_hex;
 use axum::body::Body;
-use axum::extract::State;
+use axum::extract::{ConnectInfo, State};
 use axum::http::{HeaderMap, HeaderValue, Request, StatusCode, Uri};
 use axum::middleware::Next;
 use axum::response::{IntoResponse, Response};
@@ -30,7 +31,7 @@ pub fn chrono_like_unix_millis() -> u128 {
         .map_or(0, |d| d.as_millis())
 }
 
-fn chrono_like_unix_secs() -> u64 {
+pub(crate) fn chrono_like_unix_secs() -> u64 {
     (chrono_like_unix_millis() / 1000) as u64
 }
 
@@ -41,11 +42,13 @@ struct AuthenticationContext {
     subject: String,
     issuer: Option<String>,
     scopes: Vec<String>,
+    groups: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
 struct ApiKeyRecord {
     key_hash: String,
+    owner: Option<String>,
     not_before_unix_s: Option<u64>,
     expires_unix_s: Option<u64>,
     revoked: bool,
@@ -76,6 +79,7 @@ impl ApiKeyStore {
             }
             let parsed = parse_api_key_record_line(trimmed).unwrap_or_else(|| ApiKeyRecord {
                 key_hash: hash_api_key(trimmed),
+                owner: None,
                 not_before_unix_s: None,
                 expires_unix_s: Some(expires_unix_s),
                 revoked: false,
@@ -85,7 +89,12 @@ impl ApiKeyStore {
         Self { records }
     }
 
-    fn validate(&self, raw_key: &str, now_unix_s: u64) -> Result<(), ApiKeyValidationError> {
+    /// Owner recorded for the key, if any, once it is known to be usable.
+    fn validate(
+        &self,
+        raw_key: &str,
+        now_unix_s: u64,
+    ) -> Result<Option<&str>, ApiKeyValidationError> {
         let candidate_hash = hash_api_key(raw_key);
         let Some(record) = self
             .records
@@ -107,7 +116,7 @@ impl ApiKeyStore {
                 return Err(ApiKeyValidationError::Expired);
             }
         }
-        Ok(())
+        Ok(record.owner.as_deref())
     }
 }
 
@@ -120,6 +129,7 @@ fn parse_api_key_record_line(input: &str) -> Option<ApiKeyRecord> {
         return None;
     }
     let mut hash = None;
+    let mut owner = None;
     let mut not_before_unix_s = None;
     let mut expires_unix_s = None;
     let mut revoked = false;
@@ -129,6 +139,7 @@ fn parse_api_key_record_line(input: &str) -> Option<ApiKeyRecord> {
         let value = kv.next().unwrap_or_default();
         match key {
             "hash" => hash = Some(value.to_string()),
+            "owner" => owner = Some(value.to_string()).filter(|value| !value.is_empty()),
             "not_before" => not_before_unix_s = value.parse::<u64>().ok(),
             "expires" => expires_unix_s = value.parse::<u64>().ok(),
             "revoked" => revoked = value.eq_ignore_ascii_case("true"),
@@ -141,6 +152,7 @@ fn parse_api_key_record_line(input: &str) -> Option<ApiKeyRecord> {
     }
     Some(ApiKeyRecord {
         key_hash,
+        owner,
         not_before_unix_s,
         expires_unix_s,
         revoked,
@@ -167,6 +179,7 @@ struct TokenClaims {
     jti: Option<String>,
     scope: Option<String>,
     scopes: Option<Vec<String>>,
+    groups: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -285,6 +298,7 @@ fn validate_signed_token(
         subject,
         issuer: claims.iss,
         scopes,
+        groups: claims.groups.unwrap_or_default(),
     })
 }
 
@@ -336,6 +350,9 @@ fn route_is_admin_endpoint(route: &str) -> bool {
             | "/debug/cluster/register"
             | "/debug/cluster/heartbeat"
             | "/debug/cluster/mode"
+            | "/debug/authz/explain"
+            | "/debug/api-keys"
+            | "/debug/api-keys/reload"
             | "/v1/_debug/echo"
     )
 }
@@ -362,33 +379,6 @@ fn parse_embedded_auth_policy(raw: &str) -> Result<serde_yaml::Value, String> {
     serde_yaml::from_str(raw).map_err(|err| format!("embedded auth policy: {err}"))
 }
 
-fn build_embedded_authorization_engine(
-    permissions_raw: &str,
-    roles_raw: &str,
-    policy_raw: &str,
-) -> Result<AuthorizationEngine, String> {
-    let permissions: PermissionCatalog = serde_yaml::from_str(permissions_raw)
-        .map_err(|err| format!("embedded permission catalog: {err}"))?;
-    let roles: RoleCatalog =
-        serde_yaml::from_str(roles_raw).map_err(|err| format!("embedded role catalog: {err}"))?;
-    let policy: AuthorizationPolicy = serde_yaml::from_str(policy_raw)
-        .map_err(|err| format!("embedded authorization policy: {err}"))?;
-    let evaluator = PermissionEvaluator::new(permissions);
-    let mut registry = RoleRegistry::new();
-    for role in roles.roles {
-        registry.upsert_role(role);
-    }
-    for (principal_id, role_id) in [
-        ("user", "role.user.readonly"),
-        ("service-account", "role.service.readonly"),
-        ("operator", "role.operator.admin"),
-        ("ci", "role.automation.release"),
-    ] {
-        registry.assign_role(principal_id, role_id);
-    }
-    Ok(AuthorizationEngine::new(registry, evaluator, policy))
-}
-
 fn embedded_policy_allows(principal: &str, action: &str, resource_kind: &str, route: &str) -> bool {
     static POLICY: std::sync::OnceLock<Result<serde_yaml::Value, String>> =
         std::sync::OnceLock::new();
@@ -470,31 +460,187 @@ fn embedded_policy_allows(principal: &str, action: &str, resource_kind: &str, ro
     default_allow
 }
 
-fn embedded_authorization_allows(
-    principal: &str,
-    action: &str,
-    resource_kind: &str,
+/// The authenticated caller. `security_middleware` attaches it to every
+/// request so handlers can authorize the dataset they resolve from params or
+/// body before reading it.
+#[derive(Debug, Clone)]
+pub(crate) struct DatasetAccess {
+    pub(crate) principal: String,
+    pub(crate) subject: String,
+    pub(crate) groups: Vec<String>,
+    pub(crate) api_key_owner: Option<String>,
+    pub(crate) api_key: Option<crate::domain::security::auth::ApiKeyRecord>,
+}
+
+impl DatasetAccess {
+    fn identity(&self) -> AuthorizationIdentity<'_> {
+        AuthorizationIdentity {
+            principal: &self.principal,
+            subject: &self.subject,
+            groups: &self.groups,
+            api_key_owner: self.api_key_owner.as_deref(),
+        }
+    }
+}
+
+/// Decision for `caller` on `route`, and on `dataset` once the handler has
+/// resolved it: role bindings and dataset scopes, the embedded policy, and
+/// the restrictions of a registered api key. `None` when no bindings are
+/// loaded and the request must be denied.
+pub(crate) fn authorize_access(
+    state: &AppState,
+    caller: &DatasetAccess,
     route: &str,
-) -> bool {
-    static ENGINE: std::sync::OnceLock<Result<AuthorizationEngine, String>> =
-        std::sync::OnceLock::new();
-    let engine = match ENGINE.get_or_init(|| {
-        build_embedded_authorization_engine(PERMISSIONS_YAML, ROLES_YAML, AUTH_POLICY_YAML)
-    }) {
-        Ok(engine) => engine,
-        Err(err) => {
-            error!(
-                event_id = "embedded_authorization_invalid",
-                error = %err,
-                "embedded authorization contracts are invalid; denying request"
-            );
-            return false;
+    dataset: Option<&DatasetId>,
+) -> Option<AuthorizationExplanation> {
+    let Some(engine) = state.role_bindings.engine() else {
+        error!(
+            event_id = "role_bindings_unavailable",
+            route = route,
+            "no role bindings are active; denying request"
+        );
+        return None;
+    };
+    let action = route_action_id(route);
+    let resource_kind = route_resource_kind(route);
+    let mut explanation = engine.explain(&AuthorizationRequest {
+        identity: caller.identity(),
+        action,
+        resource_kind,
+        route,
+        dataset,
+    });
+    if explanation.decision != AuthorizationDecision::Allow {
+        return Some(explanation);
+    }
+    let denial = if !embedded_policy_allows(&caller.principal, action, resource_kind, route) {
+        Some("embedded_policy")
+    } else if let Some(key) = &caller.api_key {
+        if !key.allows_action(action) {
+            Some("api_key_scope")
+        } else if dataset.is_some_and(|dataset| !key.allows_dataset(dataset)) {
+            Some("api_key_dataset")
+        } else {
+            None
         }
+    } else {
+        None
     };
-    matches!(
-        engine.evaluate(principal, action, resource_kind, route),
-        AuthorizationDecision::Allow
-    )
+    if let Some(reason) = denial {
+        explanation.decision = AuthorizationDecision::Deny;
+        explanation.reason = reason;
+    }
+    Some(explanation)
+}
+
+/// What `authorize_access` would decide for a request to `uri`. On routes
+/// that read a dataset, one `uri` does not name (and `dataset` does not
+/// supply) is denied, as the handler would never serve it.
+pub(crate) fn explain_request_authorization(
+    state: &AppState,
+    caller: &DatasetAccess,
+    uri: &Uri,
+    dataset: Option<&DatasetId>,
+) -> Option<AuthorizationExplanation> {
+    let route = uri.path();
+    let resolved = dataset.cloned().or_else(|| dataset_from_uri(uri));
+    let mut explanation = authorize_access(state, caller, route, resolved.as_ref())?;
+    if explanation.decision == AuthorizationDecision::Allow
+        && resolved.is_none()
+        && route_reads_dataset(route)
+    {
+        explanation.decision = AuthorizationDecision::Deny;
+        explanation.reason = "dataset_unresolved";
+    }
+    Some(explanation)
+}
+
+/// Checks the dataset a handler resolved against the caller the security
+/// middleware attached, returning the response to send when access is
+/// denied. A request that reached the handler without a caller is denied.
+pub(crate) async fn enforce_dataset_access(
+    state: &AppState,
+    caller: Option<&DatasetAccess>,
+    route: &'static str,
+    method: &str,
+    dataset: &DatasetId,
+    started: Instant,
+    request_id: &str,
+) -> Option<Response> {
+    let resp = dataset_access_denial(state, caller, route, dataset, request_id).await?;
+    state
+        .metrics
+        .observe_request_with_method(route, method, resp.status(), started.elapsed())
+        .await;
+    Some(resp)
+}
+
+/// [`enforce_dataset_access`] for handlers that record request metrics
+/// themselves once the response is built.
+pub(crate) async fn dataset_access_denial(
+    state: &AppState,
+    caller: Option<&DatasetAccess>,
+    route: &'static str,
+    dataset: &DatasetId,
+    request_id: &str,
+) -> Option<Response> {
+    let explanation =
+        caller.and_then(|caller| authorize_access(state, caller, route, Some(dataset)));
+    if explanation
+        .as_ref()
+        .is_some_and(|explanation| explanation.decision == AuthorizationDecision::Allow)
+    {
+        return None;
+    }
+    let action = route_action_id(route);
+    let resource_kind = route_resource_kind(route);
+    let reason = explanation
+        .as_ref()
+        .map_or("caller_unknown", |explanation| explanation.reason);
+    let principal = caller.map_or("anonymous", |caller| caller.principal.as_str());
+    emit_auth_policy_decision(state.api.auth_mode, principal, route, false);
+    record_authorization_denial(state, route, action, resource_kind).await;
+    if state.api.audit.enabled {
+        let rule = explanation
+            .as_ref()
+            .and_then(|explanation| {
+                explanation
+                    .dataset_scopes
+                    .last()
+                    .filter(|_| explanation.reason == "dataset_scope")
+                    .or(explanation.policy_rule.as_ref())
+            })
+            .map_or("none", String::as_str);
+        emit_audit_event(
+            &state.api.audit,
+            "authorization_denied",
+            Some(principal),
+            action,
+            resource_kind,
+            &dataset.canonical_string(),
+            &[
+                ("decision", "deny"),
+                ("reason", reason),
+                ("rule", rule),
+                ("route", route),
+            ],
+        );
+    }
+    let err = Json(ApiError::new(
+        auth_error_code(StatusCode::FORBIDDEN),
+        "request denied by access policy",
+        serde_json::json!({
+            "action": action,
+            "resource_kind": resource_kind,
+            "dataset": dataset.canonical_string(),
+            "reason": reason
+        }),
+        request_id.to_string(),
+    ));
+    Some(crate::adapters::inbound::http::handlers::with_request_id(
+        (StatusCode::FORBIDDEN, err).into_response(),
+        request_id,
+    ))
 }
 
 fn emit_auth_policy_decision(
@@ -561,6 +707,8 @@ fn audit_dynamic_field_allowed(key: &str) -> bool {
             | "source"
             | "outcome"
             | "auth_mode"
+            | "rule"
+            | "key_id"
             | "admin_endpoints_enabled"
             | "audit_enabled"
             | "catalog_configured"
@@ -660,45 +808,70 @@ fn emit_audit_event(
     );
 }
 
-fn parse_dataset_from_uri(uri: &Uri) -> Option<DatasetId> {
-    let path = uri.path();
-    let mut release: Option<String> = None;
-    let mut species: Option<String> = None;
-    let mut assembly: Option<String> = None;
-
-    if let Some(q) = uri.query() {
-        for part in q.split('&') {
-            let mut kv = part.splitn(2, '=');
-            let k = kv.next().unwrap_or_default();
-            let v = kv.next().unwrap_or_default();
-            match k {
-                "release" => release = Some(v.to_string()),
-                "species" => species = Some(v.to_string()),
-                "assembly" => assembly = Some(v.to_string()),
-                _ => {}
-            }
+/// Routes whose handlers read one or more datasets and authorize them.
+fn route_reads_dataset(route: &str) -> bool {
+    route_resource_kind(route) == "dataset-id"
+        && !matches!(route, "/v1/datasets" | "/v1/usage" | "/v1/query/validate")
+        && !route.starts_with("/v1/releases/")
+}
+
+/// Dataset a request URI names through `release`/`species`/`assembly` query
+/// parameters, a `dataset=release/species/assembly` selector, or a
+/// `/v1/datasets/{release}/{species}/{assembly}` or
+/// `/v1/releases/{release}/species/{species}/assemblies/{assembly}` path,
+/// decoded the way the handlers' extractors decode them.
+fn dataset_from_uri(uri: &Uri) -> Option<DatasetId> {
+    let params =
+        axum::extract::Query::<std::collections::HashMap<String, String>>::try_from_uri(uri)
+            .map(|query| query.0)
+            .unwrap_or_default();
+    if let Some(selector) = params.get("dataset") {
+        let mut parts = selector.split('/');
+        let (release, species, assembly) = (parts.next()?, parts.next()?, parts.next()?);
+        if parts.next().is_some() {
+            return None;
         }
+        return DatasetId::new(release, species, assembly).ok();
     }
+    if let (Some(release), Some(species), Some(assembly)) = (
+        params.get("release"),
+        params.get("species"),
+        params.get("assembly"),
+    ) {
+        return DatasetId::new(release, species, assembly).ok();
+    }
+    let segments: Vec<String> = uri.path().split('/').map(percent_decode_segment).collect();
+    let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+    match segments.as_slice() {
+        ["", "v1", "datasets", release, species, assembly]
+        | ["", "v1", "releases", release, "species", species, "assemblies", assembly, ..] => {
+            DatasetId::new(release, species, assembly).ok()
+        }
+        _ => None,
+    }
+}
 
-    if release.is_none() || species.is_none() || assembly.is_none() {
-        let seg: Vec<&str> = path.split('/').collect();
-        if seg.len() >= 8 && seg.get(1) == Some(&"v1") && seg.get(2) == Some(&"releases") {
-            release = seg.get(3).map(|x| (*x).to_string());
-            if seg.get(4) == Some(&"species") {
-                species = seg.get(5).map(|x| (*x).to_string());
+fn percent_decode_segment(segment: &str) -> String {
+    let bytes = segment.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|pair| std::str::from_utf8(pair).ok())
+            .and_then(|pair| u8::from_str_radix(pair, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                out.push(byte);
+                i += 3;
             }
-            if seg.get(6) == Some(&"assemblies") {
-                assembly = seg.get(7).map(|x| (*x).to_string());
+            (byte, _) => {
+                out.push(byte);
+                i += 1;
             }
         }
     }
-
-    DatasetId::new(
-        release.as_deref().unwrap_or_default(),
-        species.as_deref().unwrap_or_default(),
-        assembly.as_deref().unwrap_or_default(),
-    )
-    .ok()
+    String::from_utf8_lossy(&out).into_owned()
 }
 
 pub(crate) async fn cors_middleware(
@@ -760,11 +933,14 @@ pub(crate) async fn provenance_headers_middleware(
     req: Request<Body>,
     next: Next,
 ) -> Response {
-    let dataset = parse_dataset_from_uri(req.uri());
+    let dataset = dataset_from_uri(req.uri());
     let mut resp = next.run(req).await;
 
+    // Denied and failed requests carry no provenance for the dataset they named.
     let (dataset_hash, release, artifact_hash): (Option<String>, Option<String>, Option<String>) =
-        if let Some(ds) = dataset {
+        if let Some(ds) =
+            dataset.filter(|_| !resp.status().is_client_error() && !resp.status().is_server_error())
+        {
             let artifact_hash = state
                 .cache
                 .fetch_manifest_summary(&ds)
@@ -867,10 +1043,13 @@ pub(crate) async fn resilience_middleware(
 
 fn is_heavy_endpoint_path(path: &str) -> bool {
     path == "/v1/genes"
+        || path == "/v1/genes/query"
         || path == "/v1/sequence/region"
+        || path == "/v1/sequence/batch"
         || path == "/v1/diff/genes"
         || path == "/v1/diff/region"
         || (path.starts_with("/v1/genes/") && path.ends_with("/sequence"))
+        || (path.starts_with("/v1/transcripts/") && path.ends_with("/sequence"))
 }
 
 pub(super) fn normalized_header_value(
@@ -920,6 +1099,16 @@ fn proxy_authenticated_principal(
     }
 }
 
+/// Subject a quota tier can be bound to. Proxy identities name only a
+/// principal kind, so they share no per-subject budget.
+fn quota_subject(context: &AuthenticationContext) -> Option<&str> {
+    match context.mechanism {
+        "token" | "oidc" => Some(context.subject.as_str()),
+        "mtls" if context.issuer.is_some() => Some(context.subject.as_str()),
+        _ => None,
+    }
+}
+
 fn build_hmac_signature(secret: &str, method: &str, uri: &str, ts: &str) -> Option<String> {
     let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).ok()?;
     let payload = format!("{method}\n{uri}\n{ts}\n");
@@ -994,7 +1183,7 @@ pub(crate) async fn record_invariant_violation(state: &AppState, invariant: &str
 
 pub(crate) async fn security_middleware(
     State(state): State<AppState>,
-    req: Request<Body>,
+    mut req: Request<Body>,
     next: Next,
 ) -> Response {
     let uri_text = req.uri().to_string();
@@ -1009,7 +1198,11 @@ pub(crate) async fn security_middleware(
         "authentication evaluation started"
     );
     let auth_exempt = route_auth_exempt(&route);
-    if state.api.require_https {
+    let tls_peer = req
+        .extensions()
+        .get::<ConnectInfo<TlsPeer>>()
+        .map(|info| info.0.clone());
+    if state.api.require_https && tls_peer.is_none() {
         info!(
             event_id = "transport_https_policy",
             event = "transport_https_policy",
@@ -1126,17 +1319,57 @@ pub(crate) async fn security_middleware(
             &request_id,
         );
     }
+    let mut api_key_owner = None;
+    let mut registered_key = None;
     if let Some(key) = &api_key {
-        if !api_key_store.records.is_empty()
-            && api_key_store
-                .validate(key, chrono_like_unix_secs())
-                .is_err()
-        {
+        if state.api_keys.is_configured() {
+            match state.api_keys.authenticate(key, chrono_like_unix_secs()) {
+                Ok(record) => {
+                    api_key_owner = Some(record.owner.clone());
+                    registered_key = Some(record);
+                }
+                Err(err) => {
+                    emit_auth_policy_decision(state.api.auth_mode, "user", &route, false);
+                    record_auth_failure(&state, err.as_code(), &route).await;
+                    let err = Json(ApiError::new(
+                        auth_error_code(StatusCode::UNAUTHORIZED),
+                        "invalid api key",
+                        serde_json::json!({"class": "authentication", "reason": err.as_code()}),
+                        request_id.clone(),
+                    ));
+                    return crate::adapters::inbound::http::handlers::with_request_id(
+                        (StatusCode::UNAUTHORIZED, err).into_response(),
+                        &request_id,
+                    );
+                }
+            }
+        } else if !api_key_store.records.is_empty() {
+            let Ok(owner) = api_key_store.validate(key, chrono_like_unix_secs()) else {
+                emit_auth_policy_decision(state.api.auth_mode, "user", &route, false);
+                record_auth_failure(&state, "api_key_invalid", &route).await;
+                let err = Json(ApiError::new(
+                    auth_error_code(StatusCode::UNAUTHORIZED),
+                    "invalid api key",
+                    serde_json::json!({}),
+                    request_id.clone(),
+                ));
+                return crate::adapters::inbound::http::handlers::with_request_id(
+                    (StatusCode::UNAUTHORIZED, err).into_response(),
+                    &request_id,
+                );
+            };
+            api_key_owner = owner.map(ToString::to_string);
+        }
+    }
+
+    let token = token_header_value(req.headers());
+    let token_context = if matches!(state.api.auth_mode, crate::runtime::config::AuthMode::Token) {
+        let Some(raw_token) = token.as_deref() else {
             emit_auth_policy_decision(state.api.auth_mode, "user", &route, false);
-            record_auth_failure(&state, "api_key_invalid", &route).await;
+            record_auth_failure(&state, "token_missing", &route).await;
             let err = Json(ApiError::new(
                 auth_error_code(StatusCode::UNAUTHORIZED),
-                "invalid api key",
+                "bearer token required",
                 serde_json::json!({}),
                 request_id.clone(),
             ));
@@ -1144,11 +1377,29 @@ pub(crate) async fn security_middleware(
                 (StatusCode::UNAUTHORIZED, err).into_response(),
                 &request_id,
             );
+        };
+        match validate_signed_token(raw_token, &state.api) {
+            Ok(context) => Some(context),
+            Err(err) => {
+                emit_auth_policy_decision(state.api.auth_mode, "user", &route, false);
+                record_auth_failure(&state, err.as_code(), &route).await;
+                let err = Json(ApiError::new(
+                    auth_error_code(StatusCode::UNAUTHORIZED),
+                    "invalid bearer token",
+                    serde_json::json!({"class": "authentication", "reason": err.as_code()}),
+                    request_id.clone(),
+                ));
+                return crate::adapters::inbound::http::handlers::with_request_id(
+                    (StatusCode::UNAUTHORIZED, err).into_response(),
+                    &request_id,
+                );
+            }
         }
-    }
-
-    let token = token_header_value(req.headers());
-    let token_context = if matches!(state.api.auth_mode, crate::runtime::config::AuthMode::Token) {
+    } else if let Some(keys) = state.oidc_keys.as_deref().filter(|_| {
+        state.api.auth_mode == crate::runtime::config::AuthMode::Oidc
+            && !auth_exempt
+            && !route_is_admin_endpoint(&route)
+    }) {
         let Some(raw_token) = token.as_deref() else {
             emit_auth_policy_decision(state.api.auth_mode, "user", &route, false);
             record_auth_failure(&state, "token_missing", &route).await;
@@ -1163,9 +1414,16 @@ pub(crate) async fn security_middleware(
                 &request_id,
             );
         };
-        match validate_signed_token(raw_token, &state.api) {
-            Ok(context) => Some(context),
-            Err(err) => {
+        match keys.validate(raw_token, chrono_like_unix_secs()).await {
+            Ok(claims) => Some(AuthenticationContext {
+                principal: "user",
+                mechanism: "oidc",
+                scopes: claims.scopes(),
+                groups: claims.groups,
+                subject: claims.sub,
+                issuer: Some(claims.iss),
+            }),
+            Err(OidcValidationError::Token(err)) => {
                 emit_auth_policy_decision(state.api.auth_mode, "user", &route, false);
                 record_auth_failure(&state, err.as_code(), &route).await;
                 let err = Json(ApiError::new(
@@ -1179,6 +1437,24 @@ pub(crate) async fn security_middleware(
                     &request_id,
                 );
             }
+            Err(OidcValidationError::KeysUnavailable(detail)) => {
+                error!(
+                    event_id = "oidc_keys_unavailable",
+                    route = route.as_str(),
+                    detail = detail.as_str(),
+                    "oidc signing keys unavailable"
+                );
+                let err = Json(ApiError::new(
+                    ApiErrorCode::NotReady,
+                    "identity provider signing keys unavailable",
+                    serde_json::json!({"auth_mode": state.api.auth_mode.as_str()}),
+                    request_id.clone(),
+                ));
+                return crate::adapters::inbound::http::handlers::with_request_id(
+                    (StatusCode::SERVICE_UNAVAILABLE, err).into_response(),
+                    &request_id,
+                );
+            }
         }
     } else {
         None
@@ -1260,6 +1536,7 @@ pub(crate) async fn security_middleware(
             subject: "operator".to_string(),
             issuer: None,
             scopes: Vec::new(),
+            groups: Vec::new(),
         }
     } else if auth_exempt || state.api.auth_mode == crate::runtime::config::AuthMode::Disabled {
         AuthenticationContext {
@@ -1268,9 +1545,38 @@ pub(crate) async fn security_middleware(
             subject: "anonymous".to_string(),
             issuer: None,
             scopes: Vec::new(),
+            groups: Vec::new(),
         }
     } else if let Some(context) = token_context {
         context
+    } else if state.api.auth_mode == crate::runtime::config::AuthMode::Mtls
+        && !state.api.mtls_trust_proxy_headers
+    {
+        let Some(identity) = tls_peer
+            .as_ref()
+            .and_then(|peer| peer.client_certificate.clone())
+        else {
+            emit_auth_policy_decision(state.api.auth_mode, "service-account", &route, false);
+            record_auth_failure(&state, "client_certificate_missing", &route).await;
+            let err = Json(ApiError::new(
+                auth_error_code(StatusCode::UNAUTHORIZED),
+                "verified client certificate required",
+                serde_json::json!({"auth_mode": state.api.auth_mode.as_str()}),
+                request_id.clone(),
+            ));
+            return crate::adapters::inbound::http::handlers::with_request_id(
+                (StatusCode::UNAUTHORIZED, err).into_response(),
+                &request_id,
+            );
+        };
+        AuthenticationContext {
+            principal: "service-account",
+            mechanism: "mtls",
+            subject: identity.principal.clone(),
+            issuer: Some(identity.issuer.clone()),
+            scopes: Vec::new(),
+            groups: Vec::new(),
+        }
     } else if matches!(
         state.api.auth_mode,
         crate::runtime::config::AuthMode::Oidc | crate::runtime::config::AuthMode::Mtls
@@ -1296,14 +1602,18 @@ pub(crate) async fn security_middleware(
             subject: principal.to_string(),
             issuer: None,
             scopes: Vec::new(),
+            groups: Vec::new(),
         }
     } else {
         AuthenticationContext {
             principal: "service-account",
             mechanism: "api-key",
-            subject: "service-account".to_string(),
+            subject: api_key_owner
+                .clone()
+                .unwrap_or_else(|| "service-account".to_string()),
             issuer: None,
             scopes: Vec::new(),
+            groups: Vec::new(),
         }
     };
     let principal = auth_context.principal;
@@ -1316,19 +1626,17 @@ pub(crate) async fn security_middleware(
         route = route.as_str(),
         "authorization evaluation started"
     );
-    let policy_allowed = embedded_authorization_allows(
-        principal,
-        route_action_id(&route),
-        route_resource_kind(&route),
-        &route,
-    );
-    let policy_allowed = policy_allowed
-        && embedded_policy_allows(
-            principal,
-            route_action_id(&route),
-            route_resource_kind(&route),
-            &route,
-        );
+    let caller = DatasetAccess {
+        principal: principal.to_string(),
+        subject: auth_context.subject.clone(),
+        groups: auth_context.groups.clone(),
+        api_key_owner: api_key_owner.clone(),
+        api_key: registered_key.clone().filter(|_| !auth_exempt),
+    };
+    let explanation = authorize_access(&state, &caller, &route, None);
+    let policy_allowed = explanation
+        .as_ref()
+        .is_some_and(|explanation| explanation.decision == AuthorizationDecision::Allow);
     info!(
         event_id = "authentication_context",
         event = "authentication_context",
@@ -1343,6 +1651,15 @@ pub(crate) async fn security_middleware(
     );
     emit_auth_policy_decision(state.api.auth_mode, principal, &route, policy_allowed);
     if !policy_allowed {
+        let reason = explanation
+            .as_ref()
+            .map_or("role_bindings_unavailable", |explanation| {
+                explanation.reason
+            });
+        let key_id = caller
+            .api_key
+            .as_ref()
+            .map_or("none", |key| key.key_id.as_str());
         record_authorization_denial(
             &state,
             &route,
@@ -1351,6 +1668,16 @@ pub(crate) async fn security_middleware(
         )
         .await;
         if state.api.audit.enabled {
+            let rule = explanation
+                .as_ref()
+                .and_then(|explanation| {
+                    explanation
+                        .dataset_scopes
+                        .last()
+                        .filter(|_| explanation.reason == "dataset_scope")
+                        .or(explanation.policy_rule.as_ref())
+                })
+                .map_or("none", String::as_str);
             emit_audit_event(
                 &state.api.audit,
                 "authorization_denied",
@@ -1360,17 +1687,25 @@ pub(crate) async fn security_middleware(
                 &route,
                 &[
                     ("decision", "deny"),
-                    ("reason", "policy_denied"),
+                    ("reason", reason),
+                    ("rule", rule),
+                    ("key_id", key_id),
                     ("route", route.as_str()),
                 ],
             );
         }
+        let message = if reason.starts_with("api_key_") {
+            "request outside api key restrictions"
+        } else {
+            "request denied by access policy"
+        };
         let err = Json(ApiError::new(
             auth_error_code(StatusCode::FORBIDDEN),
-            "request denied by access policy",
+            message,
             serde_json::json!({
                 "action": route_action_id(&route),
-                "resource_kind": route_resource_kind(&route)
+                "resource_kind": route_resource_kind(&route),
+                "reason": reason
             }),
             request_id.clone(),
         ));
@@ -1380,6 +1715,16 @@ pub(crate) async fn security_middleware(
         );
     }
 
+    req.extensions_mut().insert(caller);
+    if !auth_exempt && !route_is_admin_endpoint(&route) {
+        if let Some(quota) = state
+            .api_keys
+            .quota_for(registered_key.as_ref(), quota_subject(&auth_context))
+        {
+            req.extensions_mut().insert(quota);
+        }
+    }
+
     let started = Instant::now();
     let method = req.method().clone();
     let path = req.uri().path().to_string();
@@ -1578,22 +1923,6 @@ mod tests {
         assert!(event.get("unknown_field").is_none());
     }
 
-    #[test]
-    fn embedded_authorization_enforces_operator_admin_boundary() {
-        assert!(embedded_authorization_allows(
-            "operator",
-            "ops.admin",
-            "namespace",
-            "/debug/runtime-config"
-        ));
-        assert!(!embedded_authorization_allows(
-            "user",
-            "ops.admin",
-            "namespace",
-            "/debug/runtime-config"
-        ));
-    }
-
     #[test]
     fn https_enforcement_requires_https_proto_header() {
         assert!(https_enforced(Some("https"), true));
@@ -1606,23 +1935,6 @@ mod tests {
         assert!(err.contains("embedded auth policy"));
     }
 
-    #[test]
-    fn invalid_embedded_authorization_contracts_fail_closed_without_panicking() {
-        let permissions_err =
-            build_embedded_authorization_engine("permissions: [", ROLES_YAML, AUTH_POLICY_YAML)
-                .expect_err("bad permissions");
-        let roles_err =
-            build_embedded_authorization_engine(PERMISSIONS_YAML, "roles: [", AUTH_POLICY_YAML)
-                .expect_err("bad roles");
-        let policy_err =
-            build_embedded_authorization_engine(PERMISSIONS_YAML, ROLES_YAML, "rules: [")
-                .expect_err("bad policy");
-
-        assert!(permissions_err.contains("embedded permission catalog"));
-        assert!(roles_err.contains("embedded role catalog"));
-        assert!(policy_err.contains("embedded authorization policy"));
-    }
-
     fn signed_token(payload: serde_json::Value, secret: &str) -> String {
         let header = base64::engine::general_purpose::URL_SAFE_NO_PAD
             .encode(r#"{"alg":"HS256","typ":"JWT"}"#);
@@ -1,5 +1,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::adapters::outbound::columnar::{
+    encode_arrow_stream, ColumnBatch, ColumnSpec, ARROW_STREAM_MEDIA_TYPE,
+};
+use crate::domain::query::{write_gene_record, AnnotationFormat, GeneRow};
 use crate::*;
 use brotli::CompressorWriter;
 use flate2::{write::GzEncoder, Compression};
@@ -12,6 +16,12 @@ pub(crate) fn is_gene_id_exact_query(req: &GeneQueryRequest) -> Option<&str> {
         && req.filter.name_prefix.is_none()
         && req.filter.biotype.is_none()
         && req.filter.region.is_none()
+        && req.filter.strand == crate::domain::query::StrandMode::Any
+        && req.filter.xref.is_none()
+        && req.filter.alias.is_none()
+        && req.filter.gene_ids.is_empty()
+        && req.filter.biotypes.is_empty()
+        && req.filter.regions.is_empty()
         && req.cursor.is_none()
         && req.limit <= 1
     {
@@ -23,13 +33,14 @@ pub(crate) fn is_gene_id_exact_query(req: &GeneQueryRequest) -> Option<&str> {
 
 pub(crate) fn gene_fields_key(fields: &GeneFields) -> String {
     format!(
-        "{}{}{}{}{}{}",
+        "{}{}{}{}{}{}{}",
         fields.gene_id as u8,
         fields.name as u8,
         fields.coords as u8,
         fields.biotype as u8,
         fields.transcript_count as u8,
-        fields.sequence_length as u8
+        fields.sequence_length as u8,
+        fields.strand as u8
     )
 }
 
@@ -72,6 +83,77 @@ pub(crate) fn serialize_payload_with_capacity(
     Ok(out)
 }
 
+/// Encodes the row objects under `data.rows` as an Arrow IPC stream with `schema`.
+pub(crate) fn serialize_rows_as_arrow(
+    payload: &Value,
+    schema: &[ColumnSpec],
+) -> Result<Vec<u8>, ApiError> {
+    let rows = payload
+        .get("data")
+        .and_then(|d| d.get("rows"))
+        .and_then(Value::as_array)
+        .map_or(&[][..], Vec::as_slice);
+    let batch = ColumnBatch::from_json_rows(schema, rows);
+    encode_arrow_stream(schema, &batch).map_err(|e| {
+        crate::adapters::inbound::http::presenters::error_json(
+            ApiErrorCode::Internal,
+            "arrow serialization failed",
+            json!({"message": e.to_string()}),
+        )
+    })
+}
+
+/// Renders `data.rows` of a gene page; the GFF3 header is written on the first page only.
+pub(crate) fn serialize_gene_rows_as_annotation(
+    payload: &Value,
+    format: AnnotationFormat,
+    first_page: bool,
+) -> Result<Vec<u8>, ApiError> {
+    let rows: Vec<GeneRow> = payload
+        .get("data")
+        .and_then(|d| d.get("rows"))
+        .map(|rows| serde_json::from_value(rows.clone()))
+        .transpose()
+        .map_err(|e| {
+            crate::adapters::inbound::http::presenters::error_json(
+                ApiErrorCode::Internal,
+                "annotation serialization failed",
+                json!({"message": e.to_string()}),
+            )
+        })?
+        .unwrap_or_default();
+    let mut out = String::new();
+    if first_page {
+        out.push_str(format.header());
+    }
+    for row in &rows {
+        write_gene_record(&mut out, format, row);
+    }
+    Ok(out.into_bytes())
+}
+
+pub(crate) fn put_arrow_headers(headers: &mut HeaderMap, payload: &Value) {
+    put_row_stream_headers(headers, ARROW_STREAM_MEDIA_TYPE, payload);
+    headers.insert("vary", HeaderValue::from_static("accept, accept-encoding"));
+}
+
+/// Non-JSON row bodies carry no envelope, so the page cursor travels in `x-atlas-next-cursor`.
+pub(crate) fn put_row_stream_headers(
+    headers: &mut HeaderMap,
+    media_type: &'static str,
+    payload: &Value,
+) {
+    headers.insert("content-type", HeaderValue::from_static(media_type));
+    if let Some(cursor) = payload
+        .get("page")
+        .and_then(|p| p.get("next_cursor"))
+        .and_then(Value::as_str)
+        .and_then(|c| HeaderValue::from_str(c).ok())
+    {
+        headers.insert("x-atlas-next-cursor", cursor);
+    }
+}
+
 pub(crate) fn maybe_compress_response(
     headers: &HeaderMap,
     state: &AppState,
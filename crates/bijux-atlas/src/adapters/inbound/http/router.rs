@@ -5,7 +5,11 @@ use crate::adapters::inbound::http::request_policies::{
     cors_middleware, debug_route_hardening_middleware, provenance_headers_middleware,
     resilience_middleware, security_middleware,
 };
+use crate::adapters::outbound::api_keys::ApiKeyRegistryStore;
+use crate::adapters::outbound::oidc::JwksCache;
 use crate::adapters::outbound::redis::RedisBackend;
+use crate::adapters::outbound::role_bindings::RoleBindingStore;
+use crate::adapters::outbound::telemetry::quota_ledger::QuotaLedger;
 use crate::adapters::outbound::telemetry::rate_limiter::RateLimiter;
 use crate::app::server::cache;
 use crate::app::server::state::{AppState, DatasetCacheManager, RequestMetrics};
@@ -162,6 +166,23 @@ impl AppState {
                 },
                 "api_key",
             )),
+            tenant_limiter: Arc::new(RateLimiter::new(
+                if api.enable_redis_rate_limit {
+                    api.redis_url.as_deref().and_then(|u| {
+                        RedisBackend::new(u, &api.redis_prefix, redis_policy.clone()).ok()
+                    })
+                } else {
+                    None
+                },
+                "tenant",
+            )),
+            quota_ledger: Arc::new(QuotaLedger::new(if api.enable_redis_rate_limit {
+                api.redis_url.as_deref().and_then(|u| {
+                    RedisBackend::new(u, &api.redis_prefix, redis_policy.clone()).ok()
+                })
+            } else {
+                None
+            })),
             metrics: Self::init_request_metrics(),
             request_id_seed: Arc::new(AtomicU64::new(1)),
             accepting_requests: Arc::new(AtomicBool::new(true)),
@@ -175,6 +196,9 @@ impl AppState {
                 .as_deref()
                 .and_then(|u| RedisBackend::new(u, &api.redis_prefix, redis_policy).ok())
                 .map(Arc::new),
+            oidc_keys: JwksCache::from_api_config(&api).map(Arc::new),
+            role_bindings: Arc::new(RoleBindingStore::from_api_config(&api)),
+            api_keys: Arc::new(ApiKeyRegistryStore::from_api_config(&api)),
             queued_requests: Arc::new(AtomicU64::new(0)),
             membership: Arc::new(Mutex::new(Self::init_membership_registry())),
             shard_registry: Arc::new(Mutex::new(Self::init_shard_registry())),
@@ -223,12 +247,22 @@ pub fn build_router(state: AppState) -> Router {
             post(http::handlers::query_validate_handler),
         )
         .route("/v1/genes/count", get(http::handlers::genes_count_handler))
+        .route("/v1/genes/query", post(http::genes::genes_query_handler))
+        .route(
+            "/v1/genes/search",
+            get(http::handlers::genes_search_handler),
+        )
         .route("/v1/diff/genes", get(http::diff::diff_genes_handler))
         .route("/v1/diff/region", get(http::diff::diff_region_handler))
+        .route("/v1/liftover", get(http::liftover::liftover_handler))
         .route(
             "/v1/sequence/region",
             get(http::sequence::sequence_region_handler),
         )
+        .route(
+            "/v1/sequence/batch",
+            post(http::sequence_batch::sequence_batch_handler),
+        )
         .route(
             "/v1/genes/{gene_id}/sequence",
             get(http::sequence::gene_sequence_handler),
@@ -240,7 +274,25 @@ pub fn build_router(state: AppState) -> Router {
         .route(
             "/v1/transcripts/{tx_id}",
             get(http::handlers::transcript_summary_handler),
-        );
+        )
+        .route(
+            "/v1/transcripts/{tx_id}/sequence",
+            get(http::sequence::transcript_sequence_handler),
+        )
+        .route(
+            "/v1/transcripts/{tx_id}/exons",
+            get(http::handlers::transcript_exons_handler),
+        )
+        .route(
+            "/v1/transcripts/{tx_id}/introns",
+            get(http::handlers::transcript_introns_handler),
+        )
+        .route(
+            "/v1/transcripts/{tx_id}/utrs",
+            get(http::handlers::transcript_utrs_handler),
+        )
+        .route("/v1/exons", get(http::handlers::exons_region_handler))
+        .route("/v1/usage", get(http::usage::usage_handler));
     if state.api.enable_admin_endpoints {
         router = router
             .route(
@@ -340,6 +392,15 @@ pub fn build_router(state: AppState) -> Router {
                 post(http::handlers::failure_injection_handler),
             )
             .route("/debug/chaos/run", post(http::handlers::chaos_run_handler))
+            .route(
+                "/debug/authz/explain",
+                get(http::handlers::authz_explain_handler),
+            )
+            .route("/debug/api-keys", get(http::handlers::api_keys_handler))
+            .route(
+                "/debug/api-keys/reload",
+                post(http::handlers::api_keys_reload_handler),
+            )
             .route("/v1/_debug/echo", get(http::handlers::debug_echo_handler));
     }
     router
@@ -348,6 +409,10 @@ pub fn build_router(state: AppState) -> Router {
             crate::adapters::inbound::http::middleware::request_tracing::request_tracing_middleware,
         ))
         .layer(from_fn_with_state(state.clone(), cors_middleware))
+        .layer(from_fn_with_state(
+            state.clone(),
+            crate::adapters::inbound::http::middleware::quota::quota_middleware,
+        ))
         .layer(from_fn_with_state(state.clone(), security_middleware))
         .layer(from_fn_with_state(state.clone(), resilience_middleware))
         .layer(from_fn_with_state(
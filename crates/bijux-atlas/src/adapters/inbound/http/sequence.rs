@@ -7,8 +7,15 @@ use crate::adapters::inbound::http::handlers::{
     if_none_match, maybe_compress_response, normalize_query, put_cache_headers,
     serialize_payload_with_capacity, wants_text, with_request_id, CachePolicy,
 };
+use crate::adapters::inbound::http::middleware::quota::{with_metered_usage, MeteredUsage};
 use crate::*;
 use axum::extract::Path as AxumPath;
+use bijux_atlas::domain::query::{
+    fasta_record, parse_fai_index, query_transcript_layout, ContigAliases, ContigResolution,
+    FaiRecord, FlankedSpan, GeneticCode, RegionTransform, SoftMaskMode, Strand, TranscriptLayout,
+    TranscriptSequenceKind,
+};
+use rusqlite::OptionalExtension;
 use serde_json::json;
 use std::collections::HashMap;
 use tracing::info;
@@ -23,15 +30,151 @@ impl Drop for QueueGuard {
     }
 }
 
-#[derive(Debug, Clone)]
-struct FaiRecord {
-    len: u64,
-    offset: u64,
-    line_bases: u64,
-    line_bytes: u64,
+/// What `sequence_common` assembles once admission checks have passed.
+enum SequenceTarget {
+    /// A `seqid:start-end` genomic slice with optional strand, flanks and masking.
+    Region {
+        raw: String,
+        transform: RegionTransform,
+    },
+    /// A spliced transcript sequence, optionally translated.
+    Transcript {
+        layout: TranscriptLayout,
+        kind: TranscriptSequenceKind,
+        code: &'static GeneticCode,
+    },
 }
 
-fn parse_dataset(params: &HashMap<String, String>) -> Result<DatasetId, ApiError> {
+impl SequenceTarget {
+    fn span(&self) -> Result<(String, u64, u64), ApiError> {
+        match self {
+            Self::Region { raw, .. } => parse_region(raw),
+            Self::Transcript { layout, .. } => {
+                let (start, end) = layout.bounds().ok_or_else(|| {
+                    error_json(
+                        ApiErrorCode::InvalidQueryParameter,
+                        "transcript has no exons",
+                        json!({"transcript_id": layout.transcript_id}),
+                    )
+                })?;
+                Ok((layout.seqid.clone(), start, end))
+            }
+        }
+    }
+
+    /// Genomic bases read from the FASTA, which is what the size limits meter.
+    /// Flanks count in full since they are only clamped once the FAI is loaded.
+    fn requested_bases(&self, start: u64, end: u64) -> u64 {
+        match self {
+            Self::Region { transform, .. } => {
+                (end - start + 1).saturating_add(transform.requested_flank_bases())
+            }
+            Self::Transcript { layout, kind, .. } => layout.genomic_bases(*kind),
+        }
+    }
+
+    fn coalesce_label(&self) -> String {
+        match self {
+            Self::Region { raw, .. } => raw.clone(),
+            Self::Transcript { layout, kind, code } => {
+                format!("{}:{}:{}", layout.transcript_id, kind.as_str(), code.id)
+            }
+        }
+    }
+
+    fn flank(&self, start: u64, end: u64, contig_len: u64) -> FlankedSpan {
+        match self {
+            Self::Region { transform, .. } => transform.flank(start, end, contig_len),
+            Self::Transcript { .. } => FlankedSpan {
+                start,
+                end,
+                upstream: 0,
+                downstream: 0,
+            },
+        }
+    }
+
+    fn assemble(
+        &self,
+        fasta_path: &std::path::Path,
+        rec: &FaiRecord,
+        span: &FlankedSpan,
+    ) -> Result<String, ApiError> {
+        match self {
+            Self::Region { transform, .. } => {
+                extract_sequence(fasta_path, rec, span.start, span.end).map(|seq| {
+                    String::from_utf8_lossy(&transform.apply(seq.into_bytes())).into_owned()
+                })
+            }
+            Self::Transcript { layout, kind, code } => layout
+                .assemble(*kind, code, |s, e| {
+                    extract_sequence(fasta_path, rec, s, e).map(String::into_bytes)
+                })
+                .map(|bytes| String::from_utf8_lossy(&bytes).into_owned()),
+        }
+    }
+
+    fn describe(&self, seqid: &str, span: &FlankedSpan) -> (&'static str, serde_json::Value) {
+        match self {
+            Self::Region { transform, .. } => (
+                "region",
+                json!({
+                    "seqid": seqid,
+                    "start": span.start,
+                    "end": span.end,
+                    "strand": transform.strand.as_symbol(),
+                    "upstream": span.upstream,
+                    "downstream": span.downstream,
+                    "mask": transform.mask.as_str(),
+                }),
+            ),
+            Self::Transcript { layout, kind, code } => {
+                let mut out = json!({
+                    "transcript_id": layout.transcript_id,
+                    "seqid": seqid,
+                    "strand": layout.strand.as_symbol(),
+                    "kind": kind.as_str(),
+                });
+                if *kind == TranscriptSequenceKind::Protein {
+                    out["genetic_code"] = json!(code.id);
+                }
+                ("transcript", out)
+            }
+        }
+    }
+
+    fn fasta_header(&self, seqid: &str, span: &FlankedSpan) -> String {
+        match self {
+            Self::Region { transform, .. } => transform.fasta_header(seqid, span),
+            Self::Transcript { layout, kind, code } => {
+                let mut header = format!(
+                    "{} {seqid}:{}-{} kind={} strand={}",
+                    layout.transcript_id,
+                    span.start,
+                    span.end,
+                    kind.as_str(),
+                    layout.strand.as_symbol()
+                );
+                if *kind == TranscriptSequenceKind::Protein {
+                    header.push_str(&format!(" genetic_code={}", code.id));
+                }
+                header
+            }
+        }
+    }
+
+    fn is_nucleotide(&self) -> bool {
+        !matches!(
+            self,
+            Self::Transcript {
+                kind: TranscriptSequenceKind::Protein,
+                ..
+            }
+        )
+    }
+}
+
+pub(super) fn parse_dataset(params: &HashMap<String, String>) -> Result<DatasetId, ApiError> {
     let release = params
         .get("release")
         .ok_or_else(|| ApiError::missing_dataset_dim("release"))?;
@@ -45,7 +188,7 @@ fn parse_dataset(params: &HashMap<String, String>) -> Result<DatasetId, ApiError
         .map_err(|e| ApiError::invalid_param("dataset", &e.to_string()))
 }
 
-fn parse_region(raw: &str) -> Result<(String, u64, u64), ApiError> {
+pub(super) fn parse_region(raw: &str) -> Result<(String, u64, u64), ApiError> {
     let (seqid, span) = raw.split_once(':').ok_or_else(|| {
         error_json(
             ApiErrorCode::InvalidQueryParameter,
@@ -84,7 +227,81 @@ fn parse_region(raw: &str) -> Result<(String, u64, u64), ApiError> {
     Ok((seqid.to_string(), start, end))
 }
 
-fn parse_fai(path: &std::path::Path) -> Result<HashMap<String, FaiRecord>, ApiError> {
+/// [`parse_region`] with the seqid moved onto the name the dataset stores.
+pub(super) fn parse_region_with_aliases(
+    raw: &str,
+    aliases: &ContigAliases,
+) -> Result<(ContigResolution, u64, u64), ApiError> {
+    let (seqid, start, end) = parse_region(raw)?;
+    Ok((aliases.resolve(&seqid), start, end))
+}
+
+/// Contig aliases of `dataset`. An alias table that cannot be read resolves
+/// nothing, so the request fails on the name it gave.
+pub(super) async fn dataset_contig_aliases(
+    state: &AppState,
+    dataset: &DatasetId,
+) -> Arc<ContigAliases> {
+    state
+        .cache
+        .load_contig_aliases(dataset)
+        .await
+        .unwrap_or_default()
+}
+
+/// FAI key for `seqid`, with the seqid reported back. Names the FAI lacks go
+/// through `aliases`, trying the canonical seqid and then its other names,
+/// since the FASTA keeps source names when a seqid policy renamed contigs.
+pub(super) fn resolve_fai_contig(
+    fai: &HashMap<String, FaiRecord>,
+    aliases: Option<&ContigAliases>,
+    seqid: &str,
+) -> Option<(ContigResolution, String)> {
+    if fai.contains_key(seqid) {
+        return Some((ContigResolution::unchanged(seqid), seqid.to_string()));
+    }
+    let aliases = aliases?;
+    let resolution = aliases.resolve(seqid);
+    let key = std::iter::once(resolution.seqid.as_str())
+        .chain(aliases.aliases_of(&resolution.seqid))
+        .find(|name| fai.contains_key(*name))?
+        .to_string();
+    Some((resolution, key))
+}
+
+pub(super) fn parse_strand(raw: Option<&str>) -> Result<Strand, ApiError> {
+    match raw {
+        None | Some("plus") => Ok(Strand::Plus),
+        Some("minus") => Ok(Strand::Minus),
+        Some(other) => Err(ApiError::invalid_param("strand", other)),
+    }
+}
+
+pub(super) fn parse_mask(raw: Option<&str>) -> Result<SoftMaskMode, ApiError> {
+    match raw {
+        None => Ok(SoftMaskMode::Preserve),
+        Some(raw) => SoftMaskMode::parse(raw).ok_or_else(|| ApiError::invalid_param("mask", raw)),
+    }
+}
+
+/// Reads `strand=plus|minus`, `upstream=`, `downstream=` and `mask=preserve|upper|hard`.
+fn parse_region_transform(params: &HashMap<String, String>) -> Result<RegionTransform, ApiError> {
+    let strand = parse_strand(params.get("strand").map(String::as_str))?;
+    let flank = |name: &str| match params.get(name) {
+        None => Ok(0),
+        Some(raw) => raw
+            .parse::<u64>()
+            .map_err(|_| ApiError::invalid_param(name, raw)),
+    };
+    Ok(RegionTransform {
+        strand,
+        upstream: flank("upstream")?,
+        downstream: flank("downstream")?,
+        mask: parse_mask(params.get("mask").map(String::as_str))?,
+    })
+}
+
+pub(super) fn parse_fai(path: &std::path::Path) -> Result<HashMap<String, FaiRecord>, ApiError> {
     let content =
         crate::adapters::inbound::http::effects_adapters::read_to_string(path).map_err(|e| {
             error_json(
@@ -93,57 +310,32 @@ fn parse_fai(path: &std::path::Path) -> Result<HashMap<String, FaiRecord>, ApiEr
                 json!({"message": e.0}),
             )
         })?;
-    let mut out = HashMap::new();
-    for line in content.lines() {
-        if line.trim().is_empty() {
-            continue;
-        }
-        let parts: Vec<&str> = line.split('\t').collect();
-        if parts.len() < 5 {
-            continue;
-        }
-        let rec = FaiRecord {
-            len: parts[1].parse::<u64>().unwrap_or(0),
-            offset: parts[2].parse::<u64>().unwrap_or(0),
-            line_bases: parts[3].parse::<u64>().unwrap_or(0),
-            line_bytes: parts[4].parse::<u64>().unwrap_or(0),
-        };
-        out.insert(parts[0].to_string(), rec);
-    }
-    Ok(out)
+    parse_fai_index(&content).map_err(|message| {
+        error_json(
+            ApiErrorCode::Internal,
+            "fai read failed",
+            json!({"message": message}),
+        )
+    })
 }
 
-fn extract_sequence(
+pub(super) fn extract_sequence(
     fasta_path: &std::path::Path,
     rec: &FaiRecord,
     start: u64,
     end: u64,
 ) -> Result<String, ApiError> {
-    let mut out = String::with_capacity((end - start + 1) as usize);
-    let mut pos = start;
-    while pos <= end {
-        let zero = pos - 1;
-        let line = zero / rec.line_bases;
-        let col = zero % rec.line_bases;
-        let line_remaining = rec.line_bases - col;
-        let want = (end - pos + 1).min(line_remaining);
-        let byte_offset = rec.offset + line * rec.line_bytes + col;
-        let buf = crate::adapters::inbound::http::effects_adapters::read_fasta_window(
-            fasta_path,
-            byte_offset,
-            want as usize,
-        )
-        .map_err(|e| {
-            error_json(
-                ApiErrorCode::Internal,
-                "fasta read failed",
-                json!({"message": e.0}),
-            )
-        })?;
-        out.push_str(&String::from_utf8_lossy(&buf));
-        pos += want;
-    }
-    Ok(out)
+    let bases = rec.read_bases(start, end, |offset, len| {
+        crate::adapters::inbound::http::effects_adapters::read_fasta_window(fasta_path, offset, len)
+            .map_err(|e| {
+                error_json(
+                    ApiErrorCode::Internal,
+                    "fasta read failed",
+                    json!({"message": e.0}),
+                )
+            })
+    })?;
+    Ok(String::from_utf8_lossy(&bases).into_owned())
 }
 
 fn sequence_meta(sequence: &str) -> serde_json::Value {
@@ -168,13 +360,32 @@ fn sequence_meta(sequence: &str) -> serde_json::Value {
     })
 }
 
-async fn acquire_class_permit_for_sequence(
+pub(super) fn dataset_unavailable(msg: &str) -> Response {
+    let (status, code) = if msg.contains("quarantined") {
+        (StatusCode::CONFLICT, ApiErrorCode::ArtifactQuarantined)
+    } else if msg.contains("corrupt") {
+        (StatusCode::CONFLICT, ApiErrorCode::ArtifactCorrupted)
+    } else {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            ApiErrorCode::UpstreamStoreUnavailable,
+        )
+    };
+    let details = if code == ApiErrorCode::UpstreamStoreUnavailable {
+        json!({"message": msg, "retryable": true})
+    } else {
+        json!({"message": msg})
+    };
+    api_error_response(status, error_json(code, "dataset unavailable", details))
+}
+
+pub(super) async fn acquire_class_permit_for_sequence(
     state: &AppState,
     class: QueryClass,
 ) -> Result<tokio::sync::OwnedSemaphorePermit, ApiError> {
     let sem = match class {
         QueryClass::Cheap => state.class_cheap.clone(),
-        QueryClass::Medium => state.class_medium.clone(),
+        QueryClass::Medium | QueryClass::Search => state.class_medium.clone(),
         QueryClass::Heavy => state.class_heavy.clone(),
     };
     sem.try_acquire_owned().map_err(|_| {
@@ -189,9 +400,10 @@ async fn acquire_class_permit_for_sequence(
 async fn sequence_common(
     state: AppState,
     headers: HeaderMap,
+    access: Option<&DatasetAccess>,
     params: HashMap<String, String>,
-    route: &str,
-    region_raw: String,
+    route: &'static str,
+    target: SequenceTarget,
 ) -> Response {
     let started = Instant::now();
     let request_id =
@@ -258,7 +470,12 @@ async fn sequence_common(
             return with_request_id(resp, &request_id);
         }
     };
-    let (seqid, start, end) = match parse_region(&region_raw) {
+    if let Some(resp) =
+        enforce_dataset_access(&state, access, route, "GET", &dataset, started, &request_id).await
+    {
+        return resp;
+    }
+    let (seqid, start, end) = match target.span() {
         Ok(v) => v,
         Err(e) => {
             let resp = api_error_response(StatusCode::BAD_REQUEST, e);
@@ -269,7 +486,7 @@ async fn sequence_common(
             return with_request_id(resp, &request_id);
         }
     };
-    let requested_bases = end - start + 1;
+    let requested_bases = target.requested_bases(start, end);
     let class = if requested_bases as usize >= state.api.sequence_api_key_required_bases {
         QueryClass::Heavy
     } else {
@@ -350,7 +567,7 @@ async fn sequence_common(
     let coalesce_key = format!(
         "sequence:{}:{}:{}",
         dataset.canonical_string(),
-        region_raw,
+        target.coalesce_label(),
         normalize_query(&params)
     );
     state
@@ -389,7 +606,12 @@ async fn sequence_common(
             return with_request_id(resp, &request_id);
         }
     };
-    let Some(rec) = fai.get(&seqid) else {
+    let aliases = if fai.contains_key(&seqid) {
+        None
+    } else {
+        Some(dataset_contig_aliases(&state, &dataset).await)
+    };
+    let Some((contig, fai_key)) = resolve_fai_contig(&fai, aliases.as_deref(), &seqid) else {
         let resp = api_error_response(
             StatusCode::UNPROCESSABLE_ENTITY,
             error_json(
@@ -404,6 +626,8 @@ async fn sequence_common(
             .await;
         return with_request_id(resp, &request_id);
     };
+    let seqid = contig.seqid.clone();
+    let rec = &fai[fai_key.as_str()];
     if end > rec.len {
         let resp = api_error_response(
             StatusCode::UNPROCESSABLE_ENTITY,
@@ -419,7 +643,8 @@ async fn sequence_common(
             .await;
         return with_request_id(resp, &request_id);
     }
-    let sequence = match extract_sequence(&fasta_path, rec, start, end) {
+    let span = target.flank(start, end, rec.len);
+    let sequence = match target.assemble(&fasta_path, rec, &span) {
         Ok(v) => v,
         Err(e) => {
             let resp = api_error_response(StatusCode::INTERNAL_SERVER_ERROR, e);
@@ -457,18 +682,23 @@ async fn sequence_common(
     }
 
     let include_stats = bool_query_flag(&params, "include_stats");
+    let usage = MeteredUsage {
+        sequence_bases: requested_bases,
+        ..MeteredUsage::default()
+    };
     let provenance =
         crate::adapters::inbound::http::handlers::dataset_provenance(&state, &dataset).await;
     let serialize_stage = Instant::now();
     if wants_text(&headers) {
-        let sequence_len = sequence.len();
-        if sequence.len() > state.api.response_max_bytes {
+        let record = fasta_record(&target.fasta_header(&seqid, &span), sequence.as_bytes());
+        let record_len = record.len();
+        if record_len > state.api.response_max_bytes {
             let resp = api_error_response(
                 StatusCode::PAYLOAD_TOO_LARGE,
                 error_json(
                     ApiErrorCode::QueryRejectedByPolicy,
                     "response size exceeds configured limit",
-                    json!({"size_bytes": sequence.len(), "max": state.api.response_max_bytes}),
+                    json!({"size_bytes": record_len, "max": state.api.response_max_bytes}),
                 ),
             );
             state
@@ -488,20 +718,17 @@ async fn sequence_common(
             "content-type",
             HeaderValue::from_static("text/plain; charset=utf-8"),
         );
-        let mut resp = (StatusCode::OK, h, sequence).into_response();
+        let mut resp = (StatusCode::OK, h, record).into_response();
         state
             .metrics
             .observe_stage("serialization", serialize_stage.elapsed())
             .await;
-        state
-            .metrics
-            .observe_response_size(route, sequence_len)
-            .await;
+        state.metrics.observe_response_size(route, record_len).await;
         state
             .metrics
             .observe_request(route, StatusCode::OK, started.elapsed())
             .await;
-        resp = with_request_id(resp, &request_id);
+        resp = with_request_id(with_metered_usage(resp, usage), &request_id);
         return resp;
     }
 
@@ -513,13 +740,17 @@ async fn sequence_common(
     } else {
         None
     };
+    let (target_key, target_value) = target.describe(&seqid, &span);
     let mut data = json!({
         "provenance": provenance,
-        "region": {"seqid": seqid, "start": start, "end": end},
         "length": sequence.len(),
         "sequence": sequence
     });
-    if include_stats {
+    data[target_key] = target_value;
+    if matches!(target, SequenceTarget::Region { .. }) {
+        data["contig"] = json!(contig);
+    }
+    if include_stats && target.is_nucleotide() {
         data["sequence_meta"] = sequence_meta(&sequence);
     }
     let payload = crate::adapters::inbound::http::handlers::json_envelope(
@@ -594,12 +825,13 @@ async fn sequence_common(
         .metrics
         .observe_request(route, StatusCode::OK, started.elapsed())
         .await;
-    with_request_id(resp, &request_id)
+    with_request_id(with_metered_usage(resp, usage), &request_id)
 }
 
 pub(crate) async fn sequence_region_handler(
     State(state): State<AppState>,
     headers: HeaderMap,
+    access: Option<axum::Extension<DatasetAccess>>,
     axum::extract::Query(params): axum::extract::Query<HashMap<String, String>>,
 ) -> Response {
     let request_id =
@@ -611,12 +843,52 @@ pub(crate) async fn sequence_region_handler(
         );
         return with_request_id(resp, &request_id);
     };
-    sequence_common(state, headers, params, "/v1/sequence/region", region).await
+    let transform = match parse_region_transform(&params) {
+        Ok(v) => v,
+        Err(e) => {
+            let resp = api_error_response(StatusCode::BAD_REQUEST, e);
+            return with_request_id(resp, &request_id);
+        }
+    };
+    sequence_common(
+        state,
+        headers,
+        access.as_deref(),
+        params,
+        "/v1/sequence/region",
+        SequenceTarget::Region {
+            raw: region,
+            transform,
+        },
+    )
+    .await
+}
+
+/// `(seqid, start, end)` of a gene; rows with negative coordinates count as missing.
+pub(super) fn query_gene_span(
+    conn: &rusqlite::Connection,
+    gene_id: &str,
+) -> rusqlite::Result<Option<(String, u64, u64)>> {
+    let mut stmt =
+        conn.prepare_cached("SELECT seqid,start,end FROM gene_summary WHERE gene_id = ?1 LIMIT 1")?;
+    let row = stmt
+        .query_row([gene_id], |r| {
+            Ok((
+                r.get::<_, String>(0)?,
+                r.get::<_, i64>(1)?,
+                r.get::<_, i64>(2)?,
+            ))
+        })
+        .optional()?;
+    Ok(row.and_then(|(seqid, start, end)| {
+        Some((seqid, u64::try_from(start).ok()?, u64::try_from(end).ok()?))
+    }))
 }
 
 pub(crate) async fn gene_sequence_handler(
     State(state): State<AppState>,
     headers: HeaderMap,
+    access: Option<axum::Extension<DatasetAccess>>,
     AxumPath(gene_id): AxumPath<String>,
     axum::extract::Query(mut params): axum::extract::Query<HashMap<String, String>>,
 ) -> Response {
@@ -629,86 +901,178 @@ pub(crate) async fn gene_sequence_handler(
             return with_request_id(resp, &request_id);
         }
     };
-    let conn = match state.cache.open_dataset_connection(&dataset).await {
+    let mut transform = match parse_region_transform(&params) {
         Ok(v) => v,
         Err(e) => {
-            let msg = e.to_string();
-            let (status, code) = if msg.contains("quarantined") {
-                (StatusCode::CONFLICT, ApiErrorCode::ArtifactQuarantined)
-            } else if msg.contains("corrupt") {
-                (StatusCode::CONFLICT, ApiErrorCode::ArtifactCorrupted)
-            } else {
-                (
-                    StatusCode::SERVICE_UNAVAILABLE,
-                    ApiErrorCode::UpstreamStoreUnavailable,
-                )
-            };
-            let details = if code == ApiErrorCode::UpstreamStoreUnavailable {
-                json!({"message": msg, "retryable": true})
-            } else {
-                json!({"message": msg})
-            };
-            let resp = api_error_response(status, error_json(code, "dataset unavailable", details));
+            let resp = api_error_response(StatusCode::BAD_REQUEST, e);
             return with_request_id(resp, &request_id);
         }
     };
-    let row = {
-        let mut stmt = match conn
-            .conn
-            .prepare("SELECT seqid,start,end FROM gene_summary WHERE gene_id = ?1 LIMIT 1")
-        {
-            Ok(v) => v,
-            Err(e) => {
-                let resp = api_error_response(
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    error_json(
-                        ApiErrorCode::Internal,
-                        "query prepare failed",
-                        json!({"message": e.to_string()}),
-                    ),
-                );
-                return with_request_id(resp, &request_id);
-            }
-        };
-        match stmt.query_row([gene_id.as_str()], |r| {
-            let seqid: String = r.get(0)?;
-            let start_i64: i64 = r.get(1)?;
-            let end_i64: i64 = r.get(2)?;
-            let start = u64::try_from(start_i64).map_err(|_| rusqlite::Error::InvalidQuery)?;
-            let end = u64::try_from(end_i64).map_err(|_| rusqlite::Error::InvalidQuery)?;
-            Ok((seqid, start, end))
-        }) {
-            Ok(v) => v,
-            Err(_) => {
-                let resp = api_error_response(
-                    StatusCode::NOT_FOUND,
-                    error_json(
-                        ApiErrorCode::GeneNotFound,
-                        "gene not found",
-                        json!({"gene_id": gene_id}),
-                    ),
-                );
-                return with_request_id(resp, &request_id);
-            }
+    if let Some(resp) = enforce_dataset_access(
+        &state,
+        access.as_deref(),
+        "/v1/genes/{gene_id}/sequence",
+        "GET",
+        &dataset,
+        Instant::now(),
+        &request_id,
+    )
+    .await
+    {
+        return resp;
+    }
+    let conn = match state.cache.open_dataset_connection(&dataset).await {
+        Ok(v) => v,
+        Err(e) => return with_request_id(dataset_unavailable(&e.to_string()), &request_id),
+    };
+    let row = match query_gene_span(&conn.conn, &gene_id) {
+        Ok(Some(v)) => v,
+        Ok(None) => {
+            let resp = api_error_response(
+                StatusCode::NOT_FOUND,
+                error_json(
+                    ApiErrorCode::GeneNotFound,
+                    "gene not found",
+                    json!({"gene_id": gene_id}),
+                ),
+            );
+            return with_request_id(resp, &request_id);
+        }
+        Err(e) => {
+            let resp = api_error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                error_json(
+                    ApiErrorCode::Internal,
+                    "query prepare failed",
+                    json!({"message": e.to_string()}),
+                ),
+            );
+            return with_request_id(resp, &request_id);
         }
     };
+    // `flank` predates strand-relative flanks and pads both sides.
     let flank = params
         .get("flank")
         .and_then(|x| x.parse::<u64>().ok())
         .unwrap_or(0);
-    let region = format!(
-        "{}:{}-{}",
-        row.0,
-        row.1.saturating_sub(flank).max(1),
-        row.2.saturating_add(flank)
-    );
+    transform.upstream = transform.upstream.saturating_add(flank);
+    transform.downstream = transform.downstream.saturating_add(flank);
+    let region = format!("{}:{}-{}", row.0, row.1, row.2);
     params.insert("region".to_string(), region.clone());
     sequence_common(
         state,
         headers,
+        access.as_deref(),
         params,
         "/v1/genes/{gene_id}/sequence",
-        region,
+        SequenceTarget::Region {
+            raw: region,
+            transform,
+        },
+    )
+    .await
+}
+
+pub(crate) async fn transcript_sequence_handler(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    access: Option<axum::Extension<DatasetAccess>>,
+    AxumPath(tx_id): AxumPath<String>,
+    axum::extract::Query(mut params): axum::extract::Query<HashMap<String, String>>,
+) -> Response {
+    let request_id =
+        crate::adapters::inbound::http::handlers::propagated_request_id(&headers, &state);
+    let kind_raw = params.get("kind").map_or("cdna", String::as_str);
+    let Some(kind) = TranscriptSequenceKind::parse(kind_raw) else {
+        let resp = api_error_response(
+            StatusCode::BAD_REQUEST,
+            ApiError::invalid_param("kind", kind_raw),
+        );
+        return with_request_id(resp, &request_id);
+    };
+    let requested_code = match params.get("genetic_code") {
+        None => None,
+        Some(raw) => match raw.parse::<u8>().ok().and_then(GeneticCode::by_id) {
+            Some(code) => Some(code),
+            None => {
+                let resp = api_error_response(
+                    StatusCode::BAD_REQUEST,
+                    ApiError::invalid_param("genetic_code", raw),
+                );
+                return with_request_id(resp, &request_id);
+            }
+        },
+    };
+    let dataset = match parse_dataset(&params) {
+        Ok(v) => v,
+        Err(e) => {
+            let resp = api_error_response(StatusCode::BAD_REQUEST, e);
+            return with_request_id(resp, &request_id);
+        }
+    };
+    if let Some(resp) = enforce_dataset_access(
+        &state,
+        access.as_deref(),
+        "/v1/transcripts/{tx_id}/sequence",
+        "GET",
+        &dataset,
+        Instant::now(),
+        &request_id,
+    )
+    .await
+    {
+        return resp;
+    }
+    let conn = match state.cache.open_dataset_connection(&dataset).await {
+        Ok(v) => v,
+        Err(e) => return with_request_id(dataset_unavailable(&e.to_string()), &request_id),
+    };
+    let layout = match query_transcript_layout(&conn.conn, &tx_id) {
+        Ok(Some(v)) => v,
+        Ok(None) => {
+            let resp = api_error_response(
+                StatusCode::NOT_FOUND,
+                error_json(
+                    ApiErrorCode::GeneNotFound,
+                    "transcript not found",
+                    json!({"transcript_id": tx_id}),
+                ),
+            );
+            return with_request_id(resp, &request_id);
+        }
+        Err(e) => {
+            let resp = api_error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                error_json(
+                    ApiErrorCode::Internal,
+                    "transcript layout query failed",
+                    json!({"message": e.to_string()}),
+                ),
+            );
+            return with_request_id(resp, &request_id);
+        }
+    };
+    drop(conn);
+    let code = requested_code.unwrap_or_else(|| GeneticCode::default_for_contig(&layout.seqid));
+    if kind != TranscriptSequenceKind::Cdna && layout.cds.is_empty() {
+        let resp = api_error_response(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            error_json(
+                ApiErrorCode::InvalidQueryParameter,
+                "transcript has no CDS segments",
+                json!({"transcript_id": tx_id, "kind": kind.as_str()}),
+            ),
+        );
+        return with_request_id(resp, &request_id);
+    }
+    params.insert("transcript_id".to_string(), tx_id);
+    sequence_common(
+        state,
+        headers,
+        access.as_deref(),
+        params,
+        "/v1/transcripts/{tx_id}/sequence",
+        SequenceTarget::Transcript { layout, kind, code },
     )
     .await
 }
@@ -0,0 +1,447 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! `POST /v1/sequence/batch`: many regions or genes in one request, streamed
+//! back as FASTA or JSONL in input order with per-item errors inline.
+
+use crate::adapters::inbound::http::handlers::{api_error_response, error_json, with_request_id};
+use crate::adapters::inbound::http::middleware::quota::{with_metered_usage, MeteredUsage};
+use crate::adapters::inbound::http::sequence::{
+    acquire_class_permit_for_sequence, dataset_contig_aliases, dataset_unavailable,
+    extract_sequence, parse_fai, parse_mask, parse_region, parse_strand, query_gene_span,
+    resolve_fai_contig,
+};
+use crate::*;
+use axum::body::Bytes;
+use bijux_atlas::domain::query::{
+    fasta_record, ContigAliases, ContigResolution, FaiRecord, FlankedSpan, RegionTransform,
+};
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+use tokio_stream::wrappers::ReceiverStream;
+
+const ROUTE: &str = "/v1/sequence/batch";
+/// Records produced ahead of a slow client before the reader blocks.
+const STREAM_BUFFER_RECORDS: usize = 64;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum BatchFormat {
+    #[default]
+    Fasta,
+    Jsonl,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct SequenceBatchRequest {
+    release: String,
+    species: String,
+    assembly: String,
+    #[serde(default)]
+    format: BatchFormat,
+    items: Vec<SequenceBatchItem>,
+}
+
+/// One window: either `region` or `gene_id`, with the same transforms as the GET endpoints.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SequenceBatchItem {
+    region: Option<String>,
+    gene_id: Option<String>,
+    strand: Option<String>,
+    #[serde(default)]
+    upstream: u64,
+    #[serde(default)]
+    downstream: u64,
+    #[serde(default)]
+    flank: u64,
+    mask: Option<String>,
+}
+
+/// An item resolved to genomic coordinates before any FASTA is read.
+struct PlannedItem {
+    gene_id: Option<String>,
+    seqid: String,
+    /// Name of the contig in the FAI, which may be an alias of `seqid`.
+    fai_key: String,
+    start: u64,
+    end: u64,
+    transform: RegionTransform,
+    /// How a region item's seqid was resolved; gene items carry stored seqids.
+    contig: Option<ContigResolution>,
+}
+
+impl PlannedItem {
+    fn resolve_contig(
+        &mut self,
+        fai: &HashMap<String, FaiRecord>,
+        aliases: Option<&ContigAliases>,
+    ) {
+        if let Some((resolution, key)) = resolve_fai_contig(fai, aliases, &self.seqid) {
+            self.seqid.clone_from(&resolution.seqid);
+            self.fai_key = key;
+            if self.gene_id.is_none() {
+                self.contig = Some(resolution);
+            }
+        }
+    }
+
+    fn requested_bases(&self) -> u64 {
+        (self.end - self.start + 1).saturating_add(self.transform.requested_flank_bases())
+    }
+}
+
+fn item_label(item: &SequenceBatchItem) -> String {
+    item.gene_id
+        .clone()
+        .or_else(|| item.region.clone())
+        .unwrap_or_default()
+}
+
+fn plan_item(
+    item: &SequenceBatchItem,
+    conn: Option<&rusqlite::Connection>,
+    max_bases: usize,
+) -> Result<PlannedItem, ApiError> {
+    let transform = RegionTransform {
+        strand: parse_strand(item.strand.as_deref())?,
+        upstream: item.upstream.saturating_add(item.flank),
+        downstream: item.downstream.saturating_add(item.flank),
+        mask: parse_mask(item.mask.as_deref())?,
+    };
+    let (gene_id, (seqid, start, end)) = match (&item.region, &item.gene_id) {
+        (Some(region), None) => (None, parse_region(region)?),
+        (None, Some(gene_id)) => {
+            let span = conn
+                .map(|conn| query_gene_span(conn, gene_id))
+                .transpose()
+                .map_err(|e| {
+                    error_json(
+                        ApiErrorCode::Internal,
+                        "gene lookup failed",
+                        json!({"message": e.to_string()}),
+                    )
+                })?
+                .flatten()
+                .ok_or_else(|| {
+                    error_json(
+                        ApiErrorCode::GeneNotFound,
+                        "gene not found",
+                        json!({"gene_id": gene_id}),
+                    )
+                })?;
+            (Some(gene_id.clone()), span)
+        }
+        _ => {
+            return Err(ApiError::invalid_param(
+                "items",
+                "each item needs exactly one of region or gene_id",
+            ))
+        }
+    };
+    let planned = PlannedItem {
+        gene_id,
+        fai_key: seqid.clone(),
+        seqid,
+        start,
+        end,
+        transform,
+        contig: None,
+    };
+    if planned.requested_bases() as usize > max_bases {
+        return Err(error_json(
+            ApiErrorCode::QueryRejectedByPolicy,
+            "requested region exceeds max bases",
+            json!({"requested_bases": planned.requested_bases(), "max_sequence_bases": max_bases}),
+        ));
+    }
+    Ok(planned)
+}
+
+/// Reads one planned item; contig checks happen here since they need the FAI.
+fn fetch_item(
+    planned: &PlannedItem,
+    fasta_path: &std::path::Path,
+    fai: &HashMap<String, FaiRecord>,
+) -> Result<(FlankedSpan, Vec<u8>), ApiError> {
+    let Some(rec) = fai.get(&planned.fai_key) else {
+        return Err(error_json(
+            ApiErrorCode::InvalidQueryParameter,
+            "contig not found",
+            json!({"seqid": planned.seqid}),
+        ));
+    };
+    if planned.end > rec.len {
+        return Err(error_json(
+            ApiErrorCode::InvalidQueryParameter,
+            "region outside contig bounds",
+            json!({"seqid": planned.seqid, "contig_length": rec.len, "end": planned.end}),
+        ));
+    }
+    let span = planned.transform.flank(planned.start, planned.end, rec.len);
+    let raw = extract_sequence(fasta_path, rec, span.start, span.end)?;
+    Ok((span, planned.transform.apply(raw.into_bytes())))
+}
+
+fn render_record(
+    format: BatchFormat,
+    index: usize,
+    label: &str,
+    outcome: Result<(&PlannedItem, FlankedSpan, Vec<u8>), ApiError>,
+) -> String {
+    match (format, outcome) {
+        (BatchFormat::Fasta, Ok((planned, span, sequence))) => {
+            let mut header = planned.transform.fasta_header(&planned.seqid, &span);
+            if let Some(gene_id) = &planned.gene_id {
+                header = format!("{gene_id} {header}");
+            }
+            fasta_record(&header, &sequence)
+        }
+        (BatchFormat::Fasta, Err(err)) => format!(
+            ">{label} error={} message={:?}\n",
+            json!(err.code).as_str().unwrap_or_default(),
+            err.message
+        ),
+        (BatchFormat::Jsonl, Ok((planned, span, sequence))) => {
+            let mut line = json!({
+                "index": index,
+                "region": {
+                    "seqid": planned.seqid,
+                    "start": span.start,
+                    "end": span.end,
+                    "strand": planned.transform.strand.as_symbol(),
+                    "upstream": span.upstream,
+                    "downstream": span.downstream,
+                    "mask": planned.transform.mask.as_str(),
+                },
+                "length": sequence.len(),
+                "sequence": String::from_utf8_lossy(&sequence),
+            });
+            if let Some(gene_id) = &planned.gene_id {
+                line["gene_id"] = json!(gene_id);
+            }
+            if let Some(contig) = &planned.contig {
+                line["contig"] = json!(contig);
+            }
+            format!("{line}\n")
+        }
+        (BatchFormat::Jsonl, Err(err)) => format!(
+            "{}\n",
+            json!({
+                "index": index,
+                "input": label,
+                "error": {"code": err.code, "message": err.message, "details": err.details},
+            })
+        ),
+    }
+}
+
+fn reject(status: StatusCode, err: ApiError, request_id: &str) -> Response {
+    with_request_id(api_error_response(status, err), request_id)
+}
+
+pub(crate) async fn sequence_batch_handler(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    access: Option<axum::Extension<DatasetAccess>>,
+    Json(request): Json<SequenceBatchRequest>,
+) -> Response {
+    let started = Instant::now();
+    let request_id =
+        crate::adapters::inbound::http::handlers::propagated_request_id(&headers, &state);
+    let resp = stream_batch(&state, &headers, access.as_deref(), request, &request_id).await;
+    state
+        .metrics
+        .observe_request_with_method(ROUTE, "POST", resp.status(), started.elapsed())
+        .await;
+    resp
+}
+
+async fn stream_batch(
+    state: &AppState,
+    headers: &HeaderMap,
+    access: Option<&DatasetAccess>,
+    request: SequenceBatchRequest,
+    request_id: &str,
+) -> Response {
+    let item_count = request.items.len();
+    if item_count == 0 || item_count > state.api.max_sequence_batch_items {
+        return reject(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            error_json(
+                ApiErrorCode::QueryRejectedByPolicy,
+                "batch item count outside allowed range",
+                json!({"items": item_count, "max_items": state.api.max_sequence_batch_items}),
+            ),
+            request_id,
+        );
+    }
+    // Each item would have been its own GET, so each one costs a limiter token.
+    if let Some(ip) = super::handlers::normalized_forwarded_for(headers) {
+        let factor =
+            if crate::adapters::inbound::http::middleware::shedding::overloaded(state).await {
+                state.api.adaptive_rate_limit_factor
+            } else {
+                1.0
+            };
+        if !state
+            .sequence_ip_limiter
+            .allow_cost_with_factor(
+                &ip,
+                &state.api.sequence_rate_limit_per_ip,
+                factor,
+                item_count as u64,
+            )
+            .await
+        {
+            return reject(
+                StatusCode::TOO_MANY_REQUESTS,
+                error_json(
+                    ApiErrorCode::RateLimited,
+                    "sequence rate limit exceeded",
+                    json!({"scope": "sequence_ip", "cost": item_count}),
+                ),
+                request_id,
+            );
+        }
+    }
+    let dataset = match DatasetId::new(&request.release, &request.species, &request.assembly) {
+        Ok(v) => v,
+        Err(e) => {
+            return reject(
+                StatusCode::BAD_REQUEST,
+                ApiError::invalid_param("dataset", &e.to_string()),
+                request_id,
+            )
+        }
+    };
+    if let Some(resp) = dataset_access_denial(state, access, ROUTE, &dataset, request_id).await {
+        return resp;
+    }
+
+    let conn = if request.items.iter().any(|item| item.gene_id.is_some()) {
+        match state.cache.open_dataset_connection(&dataset).await {
+            Ok(v) => Some(v),
+            Err(e) => return with_request_id(dataset_unavailable(&e.to_string()), request_id),
+        }
+    } else {
+        None
+    };
+    let mut planned: Vec<(String, Result<PlannedItem, ApiError>)> = request
+        .items
+        .iter()
+        .map(|item| {
+            (
+                item_label(item),
+                plan_item(
+                    item,
+                    conn.as_ref().map(|c| &c.conn),
+                    state.api.max_sequence_bases,
+                ),
+            )
+        })
+        .collect();
+    drop(conn);
+
+    let total_bases: u64 = planned
+        .iter()
+        .filter_map(|(_, item)| item.as_ref().ok())
+        .map(PlannedItem::requested_bases)
+        .fold(0, u64::saturating_add);
+    if total_bases as usize > state.api.max_sequence_batch_bases {
+        return reject(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            error_json(
+                ApiErrorCode::QueryRejectedByPolicy,
+                "batch exceeds total bases budget",
+                json!({"requested_bases": total_bases, "max_batch_bases": state.api.max_sequence_batch_bases}),
+            ),
+            request_id,
+        );
+    }
+    if total_bases as usize >= state.api.sequence_api_key_required_bases
+        && super::handlers::normalized_api_key(headers).is_none()
+    {
+        return reject(
+            StatusCode::UNAUTHORIZED,
+            error_json(
+                ApiErrorCode::QueryRejectedByPolicy,
+                "api key required for large sequence request",
+                json!({"threshold_bases": state.api.sequence_api_key_required_bases}),
+            ),
+            request_id,
+        );
+    }
+    let permit = match acquire_class_permit_for_sequence(state, QueryClass::Heavy).await {
+        Ok(v) => v,
+        Err(e) => {
+            crate::record_shed_reason(state, "class_permit_saturated").await;
+            return reject(StatusCode::TOO_MANY_REQUESTS, e, request_id);
+        }
+    };
+    let (fasta_path, fai_path) = match state.cache.ensure_sequence_inputs_cached(&dataset).await {
+        Ok(v) => v,
+        Err(e) => {
+            return reject(
+                StatusCode::SERVICE_UNAVAILABLE,
+                error_json(
+                    ApiErrorCode::NotReady,
+                    "sequence inputs unavailable",
+                    json!({"message": e.to_string()}),
+                ),
+                request_id,
+            )
+        }
+    };
+    let fai = match parse_fai(&fai_path) {
+        Ok(v) => v,
+        Err(e) => return reject(StatusCode::INTERNAL_SERVER_ERROR, e, request_id),
+    };
+    let needs_aliases = planned
+        .iter()
+        .any(|(_, item)| item.as_ref().is_ok_and(|p| !fai.contains_key(&p.seqid)));
+    let aliases = if needs_aliases {
+        Some(dataset_contig_aliases(state, &dataset).await)
+    } else {
+        None
+    };
+    for item in planned
+        .iter_mut()
+        .filter_map(|(_, item)| item.as_mut().ok())
+    {
+        item.resolve_contig(&fai, aliases.as_deref());
+    }
+
+    let format = request.format;
+    let (tx, rx) =
+        tokio::sync::mpsc::channel::<Result<Bytes, std::io::Error>>(STREAM_BUFFER_RECORDS);
+    tokio::task::spawn_blocking(move || {
+        // The heavy permit is held until the last record is produced.
+        let _permit = permit;
+        for (index, (label, item)) in planned.iter().enumerate() {
+            let outcome = item.as_ref().map_err(Clone::clone).and_then(|planned| {
+                fetch_item(planned, &fasta_path, &fai).map(|(span, seq)| (planned, span, seq))
+            });
+            let record = render_record(format, index, label, outcome);
+            if tx.blocking_send(Ok(Bytes::from(record))).is_err() {
+                break;
+            }
+        }
+    });
+
+    let content_type = match format {
+        BatchFormat::Fasta => "text/x-fasta; charset=utf-8",
+        BatchFormat::Jsonl => "application/x-ndjson",
+    };
+    let mut resp = Response::new(Body::from_stream(ReceiverStream::new(rx)));
+    resp.headers_mut()
+        .insert("content-type", HeaderValue::from_static(content_type));
+    resp.headers_mut()
+        .insert("cache-control", HeaderValue::from_static("no-store"));
+    let usage = MeteredUsage {
+        sequence_bases: total_bases,
+        ..MeteredUsage::default()
+    };
+    with_request_id(with_metered_usage(resp, usage), request_id)
+}
@@ -0,0 +1,72 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! `GET /v1/usage`: the caller's quota tier and what is left of it.
+
+use crate::adapters::inbound::http::handlers::{propagated_request_id, with_request_id};
+use crate::adapters::inbound::http::middleware::quota::utc_day;
+use crate::adapters::inbound::http::request_policies::chrono_like_unix_secs;
+use crate::adapters::outbound::api_keys::TenantQuota;
+use crate::adapters::outbound::telemetry::quota_ledger::QuotaMetric;
+use crate::adapters::outbound::telemetry::rate_limiter::RateLimitDecision;
+use crate::*;
+use axum::Extension;
+use serde_json::{json, Map, Value};
+
+const ROUTE: &str = "/v1/usage";
+
+/// Unmetered callers (no tier resolved) get `metered: false` and nulls.
+pub(crate) async fn usage_handler(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    quota: Option<Extension<TenantQuota>>,
+    requests: Option<Extension<RateLimitDecision>>,
+) -> Response {
+    let started = Instant::now();
+    let request_id = propagated_request_id(&headers, &state);
+    let payload = match (quota, requests) {
+        (Some(Extension(quota)), Some(Extension(requests))) => {
+            let (day, day_reset_secs) = utc_day(chrono_like_unix_secs());
+            let mut daily = Map::new();
+            daily.insert("reset_secs".to_string(), json!(day_reset_secs));
+            for metric in QuotaMetric::ALL {
+                let used = state.quota_ledger.used(&quota.tenant, metric, day).await;
+                let limit = metric.daily_budget(&quota.tier);
+                daily.insert(
+                    metric.as_str().to_string(),
+                    json!({
+                        "limit": limit,
+                        "used": used,
+                        "remaining": limit.map(|limit| limit.saturating_sub(used))
+                    }),
+                );
+            }
+            json!({
+                "metered": true,
+                "tenant": quota.tenant,
+                "tier": quota.tier_name,
+                "requests": {
+                    "limit": requests.limit,
+                    "remaining": requests.remaining,
+                    "window_secs": requests.window_secs,
+                    "reset_secs": requests.reset_secs
+                },
+                "daily": Value::Object(daily)
+            })
+        }
+        _ => json!({
+            "metered": false,
+            "tenant": null,
+            "tier": null,
+            "requests": null,
+            "daily": null
+        }),
+    };
+    let mut resp = Json(payload).into_response();
+    resp.headers_mut()
+        .insert("cache-control", HeaderValue::from_static("no-store"));
+    state
+        .metrics
+        .observe_request(ROUTE, StatusCode::OK, started.elapsed())
+        .await;
+    with_request_id(resp, &request_id)
+}
@@ -6,6 +6,7 @@ use std::future::Future;
 pub mod cli;
 pub mod client;
 pub mod http;
+pub mod tls;
 
 /// Build the HTTP server router through the inbound adapter surface.
 pub fn build_server_router(state: AppState) -> axum::Router {
@@ -25,3 +26,21 @@ where
         .with_graceful_shutdown(shutdown)
         .await
 }
+
+/// Serve the HTTP router over in-process TLS; handlers see the verified client
+/// certificate through `ConnectInfo<tls::TlsPeer>`.
+pub async fn serve_server_router_tls_with_shutdown<F>(
+    listener: tls::TlsListener,
+    state: AppState,
+    shutdown: F,
+) -> std::io::Result<()>
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    axum::serve(
+        listener,
+        build_server_router(state).into_make_service_with_connect_info::<tls::TlsPeer>(),
+    )
+    .with_graceful_shutdown(shutdown)
+    .await
+}
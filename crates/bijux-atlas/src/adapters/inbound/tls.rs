@@ -0,0 +1,453 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! In-process TLS termination for the server listener.
+//!
+//! Handshakes run off the accept loop, so a slow client cannot stall new
+//! connections. When a client CA bundle is configured, client certificates are
+//! verified against it and the verified identity travels with the connection
+//! as [`TlsPeer`]; certificates stay optional at the handshake so probes can
+//! reach auth-exempt routes, and the security middleware decides per route.
+//! Certificate, key and CA files are re-read on an interval and swapped in
+//! through [`CertificateRotationState`] without dropping open connections.
+
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
+use axum::extract::connect_info::Connected;
+use axum::serve::{IncomingStream, Listener};
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::WebPkiClientVerifier;
+use rustls::{ProtocolVersion, RootCertStore, ServerConfig};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio_rustls::server::TlsStream;
+use tokio_rustls::TlsAcceptor;
+use tracing::{info, warn};
+use x509_cert::attr::AttributeValue;
+use x509_cert::der::oid::ObjectIdentifier;
+use x509_cert::der::{Decode, Encode};
+use x509_cert::ext::pkix::name::{DirectoryString, GeneralName};
+use x509_cert::ext::pkix::SubjectAltName;
+use x509_cert::Certificate;
+
+use crate::domain::security::data_protection::{
+    load_certificate_bundle, tls_handshake_allowed, validate_certificate_bundle,
+    CertificateRotationState, LoadedCertificate, TlsConfig,
+};
+use crate::domain::sha256_hex;
+
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+const SUBJECT_ALT_NAME_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.5.29.17");
+const COMMON_NAME_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.5.4.3");
+
+/// Identity of a client certificate that passed verification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientCertificateIdentity {
+    /// First URI, DNS or email subject alternative name, else the subject
+    /// common name, else the full subject.
+    pub principal: String,
+    pub subject: String,
+    pub issuer: String,
+    pub fingerprint_sha256: String,
+}
+
+/// Connection info for requests served over [`TlsListener`].
+#[derive(Debug, Clone)]
+pub struct TlsPeer {
+    pub remote_addr: SocketAddr,
+    pub protocol_version: &'static str,
+    pub client_certificate: Option<Arc<ClientCertificateIdentity>>,
+}
+
+impl Connected<IncomingStream<'_, TlsListener>> for TlsPeer {
+    fn connect_info(stream: IncomingStream<'_, TlsListener>) -> Self {
+        stream.remote_addr().clone()
+    }
+}
+
+/// Parses the identity a verified client certificate presents.
+pub fn client_certificate_identity(der: &[u8]) -> Result<ClientCertificateIdentity, String> {
+    let cert =
+        Certificate::from_der(der).map_err(|e| format!("client certificate decode failed: {e}"))?;
+    let tbs = &cert.tbs_certificate;
+    let subject = tbs.subject.to_string();
+    let mut alt_names: Vec<(u8, String)> = Vec::new();
+    for ext in tbs.extensions.as_deref().unwrap_or_default() {
+        if ext.extn_id != SUBJECT_ALT_NAME_OID {
+            continue;
+        }
+        let san = SubjectAltName::from_der(ext.extn_value.as_bytes())
+            .map_err(|e| format!("subjectAltName decode failed: {e}"))?;
+        for name in san.0 {
+            match name {
+                GeneralName::UniformResourceIdentifier(value) => {
+                    alt_names.push((0, value.to_string()));
+                }
+                GeneralName::DnsName(value) => alt_names.push((1, value.to_string())),
+                GeneralName::Rfc822Name(value) => alt_names.push((2, value.to_string())),
+                _ => {}
+            }
+        }
+    }
+    alt_names.sort_by_key(|(rank, _)| *rank);
+    let common_name = tbs
+        .subject
+        .0
+        .iter()
+        .flat_map(|rdn| rdn.0.iter())
+        .find(|atv| atv.oid == COMMON_NAME_OID)
+        .and_then(|atv| directory_string(&atv.value));
+    let principal = alt_names
+        .into_iter()
+        .map(|(_, name)| name)
+        .next()
+        .or(common_name)
+        .unwrap_or_else(|| subject.clone());
+    Ok(ClientCertificateIdentity {
+        principal,
+        subject,
+        issuer: tbs.issuer.to_string(),
+        fingerprint_sha256: sha256_hex(der),
+    })
+}
+
+/// Text of an X.520 `DirectoryString` attribute value, unescaped; values of
+/// any other type yield `None`.
+fn directory_string(value: &AttributeValue) -> Option<String> {
+    let decoded = DirectoryString::from_der(&value.to_der().ok()?).ok()?;
+    Some(match decoded {
+        DirectoryString::PrintableString(text) => text.to_string(),
+        DirectoryString::TeletexString(text) => text.to_string(),
+        DirectoryString::Utf8String(text) => text,
+    })
+}
+
+fn bundle_fingerprint(bundle: &LoadedCertificate) -> String {
+    let mut material = Vec::new();
+    for part in [
+        bundle.cert_pem.as_str(),
+        bundle.key_pem.as_str(),
+        bundle.ca_pem.as_deref().unwrap_or_default(),
+    ] {
+        material.extend_from_slice(part.as_bytes());
+        material.push(0);
+    }
+    sha256_hex(&material)
+}
+
+/// Builds the rustls configuration for a certificate bundle. Only TLS 1.2 and
+/// 1.3 exist in rustls, so a `min_version` of `1.3` is the one restriction.
+pub fn build_server_config(
+    bundle: &LoadedCertificate,
+    min_version: &str,
+) -> Result<ServerConfig, String> {
+    validate_certificate_bundle(bundle).map_err(|e| format!("invalid tls bundle: {e:?}"))?;
+    let chain = CertificateDer::pem_slice_iter(bundle.cert_pem.as_bytes())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("tls certificate parse failed: {e}"))?;
+    let key = PrivateKeyDer::from_pem_slice(bundle.key_pem.as_bytes())
+        .map_err(|e| format!("tls private key parse failed: {e}"))?;
+    let versions: &[&'static rustls::SupportedProtocolVersion] = match min_version {
+        "1.3" => &[&rustls::version::TLS13],
+        "1.2" => &[&rustls::version::TLS13, &rustls::version::TLS12],
+        other => return Err(format!("unsupported tls min version: {other}")),
+    };
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let builder = ServerConfig::builder_with_provider(Arc::clone(&provider))
+        .with_protocol_versions(versions)
+        .map_err(|e| format!("tls protocol versions rejected: {e}"))?;
+    let builder = match bundle.ca_pem.as_deref() {
+        Some(ca_pem) => {
+            let mut roots = RootCertStore::empty();
+            for cert in CertificateDer::pem_slice_iter(ca_pem.as_bytes()) {
+                let cert = cert.map_err(|e| format!("tls client ca parse failed: {e}"))?;
+                roots
+                    .add(cert)
+                    .map_err(|e| format!("tls client ca rejected: {e}"))?;
+            }
+            let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider)
+                .allow_unauthenticated()
+                .build()
+                .map_err(|e| format!("tls client verifier failed: {e}"))?;
+            builder.with_client_cert_verifier(verifier)
+        }
+        None => builder.with_no_client_auth(),
+    };
+    let mut config = builder
+        .with_single_cert(chain, key)
+        .map_err(|e| format!("tls certificate rejected: {e}"))?;
+    config.alpn_protocols = vec![b"http/1.1".to_vec()];
+    Ok(config)
+}
+
+/// Current server configuration plus the rotation bookkeeping for the files
+/// it was built from.
+pub struct TlsReloader {
+    tls: TlsConfig,
+    config: RwLock<Arc<ServerConfig>>,
+    rotation: Mutex<CertificateRotationState>,
+}
+
+impl TlsReloader {
+    pub fn load(tls: &TlsConfig) -> Result<Self, String> {
+        let bundle = load_certificate_bundle(tls).map_err(|e| format!("tls bundle: {e:?}"))?;
+        let config = build_server_config(&bundle, &tls.min_version)?;
+        let mut rotation = CertificateRotationState::default();
+        rotation.stage_next(&bundle_fingerprint(&bundle));
+        rotation.promote_next();
+        Ok(Self {
+            tls: tls.clone(),
+            config: RwLock::new(Arc::new(config)),
+            rotation: Mutex::new(rotation),
+        })
+    }
+
+    #[must_use]
+    pub fn active_fingerprint(&self) -> Option<String> {
+        self.rotation
+            .lock()
+            .ok()
+            .and_then(|state| state.active_cert_fingerprint.clone())
+    }
+
+    /// Re-reads the bundle and swaps it in when its contents changed. A bundle
+    /// that fails to load or build stays staged and the active one keeps
+    /// serving.
+    pub fn reload_if_changed(&self) -> Result<bool, String> {
+        let bundle =
+            load_certificate_bundle(&self.tls).map_err(|e| format!("tls bundle: {e:?}"))?;
+        let fingerprint = bundle_fingerprint(&bundle);
+        let mut rotation = self
+            .rotation
+            .lock()
+            .map_err(|_| "tls rotation state poisoned".to_string())?;
+        if rotation.active_cert_fingerprint.as_deref() == Some(fingerprint.as_str()) {
+            return Ok(false);
+        }
+        rotation.stage_next(&fingerprint);
+        let config = build_server_config(&bundle, &self.tls.min_version)?;
+        *self
+            .config
+            .write()
+            .map_err(|_| "tls config lock poisoned".to_string())? = Arc::new(config);
+        rotation.promote_next();
+        Ok(true)
+    }
+
+    fn acceptor(&self) -> TlsAcceptor {
+        let config = self.config.read().map_or_else(
+            |poisoned| Arc::clone(&poisoned.into_inner()),
+            |c| Arc::clone(&c),
+        );
+        TlsAcceptor::from(config)
+    }
+
+    /// Polls the certificate files until the reloader is dropped elsewhere.
+    pub fn spawn_reload_task(self: &Arc<Self>, interval: Duration) {
+        let reloader = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let Some(reloader) = reloader.upgrade() else {
+                    return;
+                };
+                match reloader.reload_if_changed() {
+                    Ok(true) => info!(
+                        event_id = "tls_certificate_rotated",
+                        fingerprint = reloader.active_fingerprint().unwrap_or_default(),
+                        "tls certificate bundle reloaded"
+                    ),
+                    Ok(false) => {}
+                    Err(err) => warn!(
+                        event_id = "tls_certificate_reload_failed",
+                        error = err.as_str(),
+                        "tls certificate reload failed; keeping active bundle"
+                    ),
+                }
+            }
+        });
+    }
+}
+
+fn negotiated_version(version: Option<ProtocolVersion>) -> &'static str {
+    match version {
+        Some(ProtocolVersion::TLSv1_3) => "1.3",
+        Some(ProtocolVersion::TLSv1_2) => "1.2",
+        _ => "unknown",
+    }
+}
+
+/// Accepts TCP connections and yields them once their TLS handshake completed
+/// and satisfied `tls_handshake_allowed`.
+pub struct TlsListener {
+    local_addr: SocketAddr,
+    ready: mpsc::Receiver<(TlsStream<TcpStream>, TlsPeer)>,
+}
+
+impl TlsListener {
+    pub fn new(listener: TcpListener, reloader: Arc<TlsReloader>) -> std::io::Result<Self> {
+        let local_addr = listener.local_addr()?;
+        let (tx, ready) = mpsc::channel(256);
+        tokio::spawn(async move {
+            loop {
+                let (stream, remote_addr) = tokio::select! {
+                    () = tx.closed() => return,
+                    accepted = listener.accept() => match accepted {
+                        Ok(pair) => pair,
+                        Err(err) => {
+                            warn!(event_id = "tls_accept_failed", error = %err, "tcp accept failed");
+                            tokio::time::sleep(Duration::from_millis(50)).await;
+                            continue;
+                        }
+                    },
+                };
+                let acceptor = reloader.acceptor();
+                let min_version = reloader.tls.min_version.clone();
+                let tx = tx.clone();
+                tokio::spawn(async move {
+                    let stream = match tokio::time::timeout(
+                        HANDSHAKE_TIMEOUT,
+                        acceptor.accept(stream),
+                    )
+                    .await
+                    {
+                        Ok(Ok(stream)) => stream,
+                        Ok(Err(err)) => {
+                            warn!(
+                                event_id = "tls_handshake_failed",
+                                remote_addr = %remote_addr,
+                                error = %err,
+                                "tls handshake failed"
+                            );
+                            return;
+                        }
+                        Err(_) => {
+                            warn!(
+                                event_id = "tls_handshake_failed",
+                                remote_addr = %remote_addr,
+                                error = "timeout",
+                                "tls handshake failed"
+                            );
+                            return;
+                        }
+                    };
+                    let (_, session) = stream.get_ref();
+                    let protocol_version = negotiated_version(session.protocol_version());
+                    if !tls_handshake_allowed(true, &min_version, protocol_version) {
+                        warn!(
+                            event_id = "tls_handshake_rejected",
+                            remote_addr = %remote_addr,
+                            negotiated = protocol_version,
+                            min_version = min_version.as_str(),
+                            "tls version below configured minimum"
+                        );
+                        return;
+                    }
+                    let client_certificate = match session
+                        .peer_certificates()
+                        .and_then(|chain| chain.first())
+                        .map(|leaf| client_certificate_identity(leaf.as_ref()))
+                    {
+                        Some(Ok(identity)) => Some(Arc::new(identity)),
+                        Some(Err(err)) => {
+                            warn!(
+                                event_id = "tls_client_identity_unreadable",
+                                remote_addr = %remote_addr,
+                                error = err.as_str(),
+                                "verified client certificate could not be parsed"
+                            );
+                            return;
+                        }
+                        None => None,
+                    };
+                    let peer = TlsPeer {
+                        remote_addr,
+                        protocol_version,
+                        client_certificate,
+                    };
+                    let _ = tx.send((stream, peer)).await;
+                });
+            }
+        });
+        Ok(Self { local_addr, ready })
+    }
+}
+
+impl Listener for TlsListener {
+    type Io = TlsStream<TcpStream>;
+    type Addr = TlsPeer;
+
+    async fn accept(&mut self) -> (Self::Io, Self::Addr) {
+        match self.ready.recv().await {
+            Some(connection) => connection,
+            // The accept loop only stops once this receiver is gone.
+            None => std::future::pending().await,
+        }
+    }
+
+    fn local_addr(&self) -> std::io::Result<Self::Addr> {
+        Ok(TlsPeer {
+            remote_addr: self.local_addr,
+            protocol_version: "unknown",
+            client_certificate: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_server_config, client_certificate_identity};
+    use crate::domain::security::data_protection::LoadedCertificate;
+    use rustls::pki_types::pem::PemObject;
+    use rustls::pki_types::CertificateDer;
+
+    fn fixture(name: &str) -> String {
+        std::fs::read_to_string(
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests/fixtures/tls")
+                .join(name),
+        )
+        .expect("tls fixture")
+    }
+
+    #[test]
+    fn client_identity_prefers_uri_san_over_common_name() {
+        let pem = fixture("client.pem");
+        let der = CertificateDer::from_pem_slice(pem.as_bytes()).expect("client cert");
+        let identity = client_certificate_identity(der.as_ref()).expect("identity");
+        assert_eq!(identity.principal, "spiffe://atlas.test/ingest-bot");
+        assert!(identity.subject.contains("CN=ingest-bot"));
+        assert_eq!(identity.issuer, "CN=Atlas Test CA");
+        assert_eq!(identity.fingerprint_sha256.len(), 64);
+    }
+
+    #[test]
+    fn client_identity_falls_back_to_the_decoded_common_name() {
+        let pem = fixture("cn-only-client.pem");
+        let der = CertificateDer::from_pem_slice(pem.as_bytes()).expect("client cert");
+        let identity = client_certificate_identity(der.as_ref()).expect("identity");
+        assert_eq!(identity.principal, "batch, nightly+1");
+
+        let pem = fixture("ca.pem");
+        let der = CertificateDer::from_pem_slice(pem.as_bytes()).expect("ca cert");
+        let identity = client_certificate_identity(der.as_ref()).expect("identity");
+        assert_eq!(identity.principal, "Atlas Test CA");
+    }
+
+    #[test]
+    fn server_config_honours_min_version_and_rejects_older_floors() {
+        let bundle = LoadedCertificate {
+            cert_pem: fixture("server.pem"),
+            key_pem: fixture("server-key.pem"),
+            ca_pem: Some(fixture("ca.pem")),
+        };
+        assert!(build_server_config(&bundle, "1.2").is_ok());
+        assert!(build_server_config(&bundle, "1.3").is_ok());
+        let err = build_server_config(&bundle, "1.1").expect_err("tls 1.1 floor");
+        assert!(err.contains("unsupported tls min version"));
+    }
+}
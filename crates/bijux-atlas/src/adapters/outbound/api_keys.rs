@@ -0,0 +1,821 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Persistent API key registry.
+//!
+//! The registry is a JSON file holding only `hash_api_key` digests, each with
+//! an owner, optional route scopes, dataset restrictions and a named quota
+//! tier. Token principals bind to tiers by subject, and `default_tier`
+//! covers every other authenticated caller. `bijux-atlas keys` edits it; the server reads it when
+//! `api_key_registry_path` is set and re-reads it every
+//! `api_key_registry_reload_interval_secs`, so revocations apply without a
+//! restart. A file that fails to parse or validate leaves the active registry
+//! in place.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tracing::{error, info, warn};
+
+use crate::domain::security::auth::{
+    generate_api_key, hash_api_key, rotate_api_key, validate_dataset_pattern, ApiKeyRecord,
+    ApiKeyStore, AuthValidationError,
+};
+use crate::domain::sha256_hex;
+use crate::runtime::config::{ApiConfig, RateLimitConfig};
+
+pub const API_KEY_REGISTRY_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiKeyRegistry {
+    pub schema_version: u32,
+    #[serde(default)]
+    pub tiers: BTreeMap<String, QuotaTier>,
+    /// Tier for authenticated callers that name none, or none to leave them
+    /// unmetered.
+    #[serde(default)]
+    pub default_tier: Option<String>,
+    #[serde(default)]
+    pub principals: Vec<PrincipalQuota>,
+    #[serde(default)]
+    pub keys: Vec<ApiKeyRecord>,
+}
+
+/// Request bucket plus optional budgets counted per UTC day.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuotaTier {
+    #[serde(flatten)]
+    pub requests: RateLimitConfig,
+    #[serde(default)]
+    pub daily_sequence_bases: Option<u64>,
+    #[serde(default)]
+    pub daily_rows_returned: Option<u64>,
+}
+
+impl From<RateLimitConfig> for QuotaTier {
+    fn from(requests: RateLimitConfig) -> Self {
+        Self {
+            requests,
+            daily_sequence_bases: None,
+            daily_rows_returned: None,
+        }
+    }
+}
+
+/// Binds a bearer token or client certificate subject to a tier.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrincipalQuota {
+    pub subject: String,
+    pub tier: String,
+}
+
+/// The budget a request is metered against.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TenantQuota {
+    /// `key:<key_id>` or `sub:<subject>`.
+    pub tenant: String,
+    pub tier_name: String,
+    pub tier: QuotaTier,
+}
+
+impl Default for ApiKeyRegistry {
+    fn default() -> Self {
+        Self {
+            schema_version: API_KEY_REGISTRY_SCHEMA_VERSION,
+            tiers: BTreeMap::new(),
+            default_tier: None,
+            principals: Vec::new(),
+            keys: Vec::new(),
+        }
+    }
+}
+
+/// `.<file>.<suffix>` next to the registry file.
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("api-keys.json");
+    path.with_file_name(format!(".{name}.{suffix}"))
+}
+
+/// Exclusive hold on a registry file for one read-modify-write; released on drop.
+struct RegistryLock(PathBuf);
+
+impl RegistryLock {
+    fn acquire(path: &Path) -> Result<Self, String> {
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            std::fs::create_dir_all(parent).map_err(|err| {
+                format!("api key registry dir {} failed: {err}", parent.display())
+            })?;
+        }
+        let lock = sibling(path, "lock");
+        std::fs::OpenOptions::new()
+            .create_new(true)
+            .write(true)
+            .open(&lock)
+            .map(|_| Self(lock.clone()))
+            .map_err(|err| {
+                format!(
+                    "api key registry {} is being edited ({err}); remove {} if no edit is running",
+                    path.display(),
+                    lock.display()
+                )
+            })
+    }
+}
+
+impl Drop for RegistryLock {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// Attributes of a key issued by [`ApiKeyRegistry::create`].
+#[derive(Debug, Clone, Default)]
+pub struct NewApiKey {
+    pub key_id: String,
+    pub owner: String,
+    pub scopes: Vec<String>,
+    pub datasets: Vec<String>,
+    pub rate_limit_tier: Option<String>,
+    pub expires_at_unix_s: Option<u64>,
+}
+
+/// Registry entry without its digest, for listings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ApiKeySummary {
+    pub key_id: String,
+    pub owner: String,
+    pub status: &'static str,
+    pub scopes: Vec<String>,
+    pub datasets: Vec<String>,
+    pub rate_limit_tier: Option<String>,
+    pub created_at_unix_s: Option<u64>,
+    pub expires_at_unix_s: Option<u64>,
+    pub revoked_at_unix_s: Option<u64>,
+}
+
+impl ApiKeyRegistry {
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let registry: Self =
+            serde_json::from_str(raw).map_err(|err| format!("api key registry: {err}"))?;
+        registry
+            .validate()
+            .map_err(|err| format!("api key registry: {err}"))?;
+        Ok(registry)
+    }
+
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let raw = std::fs::read_to_string(path)
+            .map_err(|err| format!("api key registry read failed for {}: {err}", path.display()))?;
+        Self::parse(&raw)
+    }
+
+    /// The registry at `path`, or an empty one when the file does not exist yet.
+    pub fn read_or_default(path: &Path) -> Result<Self, String> {
+        if path.exists() {
+            Self::read(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// Writes through a temporary sibling so a reloading server never sees a
+    /// partial file.
+    pub fn write(&self, path: &Path) -> Result<(), String> {
+        self.validate()?;
+        let mut bytes =
+            serde_json::to_vec_pretty(self).map_err(|err| format!("api key registry: {err}"))?;
+        bytes.push(b'\n');
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            std::fs::create_dir_all(parent).map_err(|err| {
+                format!("api key registry dir {} failed: {err}", parent.display())
+            })?;
+        }
+        let tmp = sibling(path, &format!("tmp.{}", std::process::id()));
+        std::fs::write(&tmp, bytes)
+            .map_err(|err| format!("api key registry write {} failed: {err}", tmp.display()))?;
+        std::fs::rename(&tmp, path).map_err(|err| {
+            let _ = std::fs::remove_file(&tmp);
+            format!("api key registry write {} failed: {err}", path.display())
+        })
+    }
+
+    /// Reads the registry at `path` (empty when missing), applies `change`
+    /// and writes the result back while holding `.<file>.lock`, so two
+    /// concurrent `keys` commands cannot drop each other's edits. Nothing is
+    /// written when `change` fails.
+    pub fn edit<T>(
+        path: &Path,
+        change: impl FnOnce(&mut Self) -> Result<T, String>,
+    ) -> Result<T, String> {
+        let _lock = RegistryLock::acquire(path)?;
+        let mut registry = Self::read_or_default(path)?;
+        let out = change(&mut registry)?;
+        registry.write(path)?;
+        Ok(out)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.schema_version != API_KEY_REGISTRY_SCHEMA_VERSION {
+            return Err(format!(
+                "unsupported schema_version {}",
+                self.schema_version
+            ));
+        }
+        for (name, tier) in &self.tiers {
+            if !(tier.requests.capacity > 0.0 && tier.requests.refill_per_sec > 0.0) {
+                return Err(format!("tier {name} needs capacity and refill_per_sec > 0"));
+            }
+            if tier.daily_sequence_bases == Some(0) || tier.daily_rows_returned == Some(0) {
+                return Err(format!("tier {name} daily budgets must be > 0 when set"));
+            }
+        }
+        if let Some(tier) = &self.default_tier {
+            if !self.tiers.contains_key(tier) {
+                return Err(format!("default_tier names unknown tier {tier}"));
+            }
+        }
+        let mut subjects = BTreeSet::new();
+        for principal in &self.principals {
+            if principal.subject.trim().is_empty() {
+                return Err("principal subject must not be empty".to_string());
+            }
+            if !subjects.insert(principal.subject.as_str()) {
+                return Err(format!("duplicate principal {}", principal.subject));
+            }
+            if !self.tiers.contains_key(&principal.tier) {
+                return Err(format!(
+                    "principal {} names unknown tier {}",
+                    principal.subject, principal.tier
+                ));
+            }
+        }
+        let mut key_ids = BTreeSet::new();
+        let mut hashes = BTreeSet::new();
+        for key in &self.keys {
+            if key.key_id.trim().is_empty() {
+                return Err("key_id must not be empty".to_string());
+            }
+            if !key_ids.insert(key.key_id.as_str()) {
+                return Err(format!("duplicate key_id {}", key.key_id));
+            }
+            if key.key_hash.len() != 64 || !key.key_hash.chars().all(|ch| ch.is_ascii_hexdigit()) {
+                return Err(format!("key {} must store a sha256 key_hash", key.key_id));
+            }
+            if !hashes.insert(key.key_hash.as_str()) {
+                return Err(format!("key {} reuses another key's hash", key.key_id));
+            }
+            if let Some(tier) = &key.rate_limit_tier {
+                if !self.tiers.contains_key(tier) {
+                    return Err(format!("key {} names unknown tier {tier}", key.key_id));
+                }
+            }
+            for pattern in &key.datasets {
+                validate_dataset_pattern(pattern)
+                    .map_err(|err| format!("key {}: {err}", key.key_id))?;
+            }
+        }
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn store(&self) -> ApiKeyStore {
+        let mut store = ApiKeyStore::new();
+        for key in &self.keys {
+            store.upsert(key.clone());
+        }
+        store
+    }
+
+    #[must_use]
+    pub fn tier(&self, name: &str) -> Option<&QuotaTier> {
+        self.tiers.get(name)
+    }
+
+    /// Quota for a registered key, or else for a token `subject`; each falls
+    /// back to `default_tier`.
+    #[must_use]
+    pub fn quota_for(
+        &self,
+        key: Option<&ApiKeyRecord>,
+        subject: Option<&str>,
+    ) -> Option<TenantQuota> {
+        let (tenant, tier_name) = if let Some(key) = key {
+            (
+                format!("key:{}", key.key_id),
+                key.rate_limit_tier
+                    .as_ref()
+                    .or(self.default_tier.as_ref())?,
+            )
+        } else {
+            let subject = subject?;
+            let bound = self
+                .principals
+                .iter()
+                .find(|principal| principal.subject == subject)
+                .map(|principal| &principal.tier);
+            (
+                format!("sub:{subject}"),
+                bound.or(self.default_tier.as_ref())?,
+            )
+        };
+        Some(TenantQuota {
+            tenant,
+            tier_name: tier_name.clone(),
+            tier: self.tier(tier_name)?.clone(),
+        })
+    }
+
+    /// Adds a key and returns its raw value, which is not stored anywhere.
+    pub fn create(&mut self, key: NewApiKey, now_unix_s: u64) -> Result<String, String> {
+        if self.keys.iter().any(|record| record.key_id == key.key_id) {
+            return Err(format!("key {} already exists", key.key_id));
+        }
+        let raw = generate_api_key()?;
+        self.keys.push(ApiKeyRecord {
+            key_id: key.key_id,
+            owner: key.owner,
+            key_hash: hash_api_key(&raw),
+            expires_at_unix_s: key.expires_at_unix_s,
+            revoked_at_unix_s: None,
+            created_at_unix_s: Some(now_unix_s),
+            scopes: key.scopes,
+            datasets: key.datasets,
+            rate_limit_tier: key.rate_limit_tier,
+        });
+        if let Err(err) = self.validate() {
+            self.keys.pop();
+            return Err(err);
+        }
+        Ok(raw)
+    }
+
+    /// Marks a key revoked; revoking it again keeps the first timestamp.
+    pub fn revoke(&mut self, key_id: &str, now_unix_s: u64) -> Result<(), String> {
+        let record = self
+            .keys
+            .iter_mut()
+            .find(|record| record.key_id == key_id)
+            .ok_or_else(|| format!("key {key_id} not found"))?;
+        record.revoked_at_unix_s.get_or_insert(now_unix_s);
+        Ok(())
+    }
+
+    /// Issues `next_key_id` with the settings of `key_id`, which keeps working
+    /// for `overlap_secs`. Returns the new raw key.
+    pub fn rotate(
+        &mut self,
+        key_id: &str,
+        next_key_id: &str,
+        overlap_secs: u64,
+        now_unix_s: u64,
+    ) -> Result<String, String> {
+        let mut store = self.store();
+        let current = store
+            .get(key_id)
+            .ok_or_else(|| format!("key {key_id} not found"))?;
+        if !current.is_active(now_unix_s) {
+            return Err(format!("key {key_id} is revoked or expired"));
+        }
+        if store.get(next_key_id).is_some() {
+            return Err(format!("key {next_key_id} already exists"));
+        }
+        let owner = current.owner.clone();
+        let raw = rotate_api_key(
+            &mut store,
+            key_id,
+            next_key_id,
+            &owner,
+            overlap_secs,
+            now_unix_s,
+        )?;
+        self.keys = store.records().cloned().collect();
+        Ok(raw)
+    }
+
+    #[must_use]
+    pub fn summaries(&self, now_unix_s: u64) -> Vec<ApiKeySummary> {
+        self.keys
+            .iter()
+            .map(|record| ApiKeySummary {
+                key_id: record.key_id.clone(),
+                owner: record.owner.clone(),
+                status: if record.revoked_at_unix_s.is_some() {
+                    "revoked"
+                } else if record.is_active(now_unix_s) {
+                    "active"
+                } else {
+                    "expired"
+                },
+                scopes: record.scopes.clone(),
+                datasets: record.datasets.clone(),
+                rate_limit_tier: record.rate_limit_tier.clone(),
+                created_at_unix_s: record.created_at_unix_s,
+                expires_at_unix_s: record.expires_at_unix_s,
+                revoked_at_unix_s: record.revoked_at_unix_s,
+            })
+            .collect()
+    }
+}
+
+struct ActiveRegistry {
+    registry: ApiKeyRegistry,
+    store: ApiKeyStore,
+    fingerprint: String,
+}
+
+pub struct ApiKeyRegistryStore {
+    path: Option<PathBuf>,
+    active: RwLock<Option<Arc<ActiveRegistry>>>,
+}
+
+impl ApiKeyRegistryStore {
+    /// The registry at `path`; without one the store stays unconfigured and
+    /// `allowed_api_keys` applies instead.
+    pub fn load(path: Option<&Path>) -> Result<Self, String> {
+        let store = Self {
+            path: path.map(Path::to_path_buf),
+            active: RwLock::new(None),
+        };
+        if store.path.is_some() {
+            store.reload_if_changed()?;
+        }
+        Ok(store)
+    }
+
+    /// The store `AppState` starts with. A registry that cannot be loaded
+    /// leaves no active keys, so every presented key is rejected until a
+    /// reload succeeds.
+    #[must_use]
+    pub fn from_api_config(api: &ApiConfig) -> Self {
+        let path = api.api_key_registry_path.as_deref().map(Path::new);
+        Self::load(path).unwrap_or_else(|err| {
+            error!(
+                event_id = "api_key_registry_invalid",
+                error = err.as_str(),
+                "api key registry failed to load; rejecting api keys"
+            );
+            Self {
+                path: path.map(Path::to_path_buf),
+                active: RwLock::new(None),
+            }
+        })
+    }
+
+    #[must_use]
+    pub fn is_configured(&self) -> bool {
+        self.path.is_some()
+    }
+
+    #[must_use]
+    pub fn source(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    fn active(&self) -> Option<Arc<ActiveRegistry>> {
+        self.active.read().ok()?.as_ref().map(Arc::clone)
+    }
+
+    #[must_use]
+    pub fn active_fingerprint(&self) -> Option<String> {
+        self.active().map(|active| active.fingerprint.clone())
+    }
+
+    /// Record for `raw_key` when it is registered, unrevoked and unexpired.
+    pub fn authenticate(
+        &self,
+        raw_key: &str,
+        now_unix_s: u64,
+    ) -> Result<ApiKeyRecord, AuthValidationError> {
+        let active = self.active().ok_or(AuthValidationError::ApiKeyInvalid)?;
+        active.store.validate_raw_key(raw_key, now_unix_s).cloned()
+    }
+
+    /// Registered record for `key_id`, whatever its status.
+    #[must_use]
+    pub fn record(&self, key_id: &str) -> Option<ApiKeyRecord> {
+        self.active()?.store.get(key_id).cloned()
+    }
+
+    /// See [`ApiKeyRegistry::quota_for`]; `None` without an active registry.
+    #[must_use]
+    pub fn quota_for(
+        &self,
+        key: Option<&ApiKeyRecord>,
+        subject: Option<&str>,
+    ) -> Option<TenantQuota> {
+        self.active()?.registry.quota_for(key, subject)
+    }
+
+    #[must_use]
+    pub fn summaries(&self, now_unix_s: u64) -> Option<Vec<ApiKeySummary>> {
+        self.active()
+            .map(|active| active.registry.summaries(now_unix_s))
+    }
+
+    /// Re-reads the registry file and swaps it in when its contents changed.
+    pub fn reload_if_changed(&self) -> Result<bool, String> {
+        let Some(path) = &self.path else {
+            return Ok(false);
+        };
+        let raw = std::fs::read_to_string(path)
+            .map_err(|err| format!("api key registry read failed for {}: {err}", path.display()))?;
+        let fingerprint = sha256_hex(raw.as_bytes());
+        if self.active_fingerprint().as_deref() == Some(fingerprint.as_str()) {
+            return Ok(false);
+        }
+        let registry = ApiKeyRegistry::parse(&raw)?;
+        let store = registry.store();
+        *self
+            .active
+            .write()
+            .map_err(|_| "api key registry lock poisoned".to_string())? =
+            Some(Arc::new(ActiveRegistry {
+                registry,
+                store,
+                fingerprint,
+            }));
+        Ok(true)
+    }
+
+    /// Polls the registry file until the store is dropped elsewhere.
+    pub fn spawn_reload_task(self: &Arc<Self>, interval: Duration) {
+        if self.path.is_none() {
+            return;
+        }
+        let store = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let Some(store) = store.upgrade() else {
+                    return;
+                };
+                match store.reload_if_changed() {
+                    Ok(true) => info!(
+                        event_id = "api_key_registry_reloaded",
+                        fingerprint = store.active_fingerprint().unwrap_or_default(),
+                        "api key registry reloaded"
+                    ),
+                    Ok(false) => {}
+                    Err(err) => warn!(
+                        event_id = "api_key_registry_reload_failed",
+                        error = err.as_str(),
+                        "api key registry reload failed; keeping active keys"
+                    ),
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ApiKeyRegistry, ApiKeyRegistryStore, NewApiKey, PrincipalQuota, QuotaTier};
+    use crate::domain::security::auth::AuthValidationError;
+    use crate::runtime::config::RateLimitConfig;
+
+    fn new_key(key_id: &str) -> NewApiKey {
+        NewApiKey {
+            key_id: key_id.to_string(),
+            owner: "curation-team".to_string(),
+            scopes: vec!["dataset.read".to_string()],
+            datasets: vec!["110/homo_sapiens/*".to_string()],
+            rate_limit_tier: Some("bulk".to_string()),
+            expires_at_unix_s: None,
+        }
+    }
+
+    fn registry_with_tier() -> ApiKeyRegistry {
+        let mut registry = ApiKeyRegistry::default();
+        registry.tiers.insert(
+            "bulk".to_string(),
+            QuotaTier {
+                requests: RateLimitConfig {
+                    capacity: 500.0,
+                    refill_per_sec: 100.0,
+                },
+                daily_sequence_bases: Some(1_000_000),
+                daily_rows_returned: None,
+            },
+        );
+        registry
+    }
+
+    #[test]
+    fn registry_stores_only_digests_and_round_trips() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("keys/api-keys.json");
+        let mut registry = registry_with_tier();
+        let raw = registry.create(new_key("ci"), 1_000).expect("create");
+        registry.write(&path).expect("write");
+
+        let text = std::fs::read_to_string(&path).expect("read");
+        assert!(!text.contains(&raw));
+        assert_eq!(ApiKeyRegistry::read(&path).expect("reread"), registry);
+        assert!(registry.create(new_key("ci"), 1_001).is_err());
+
+        let mut unknown_tier = new_key("batch");
+        unknown_tier.rate_limit_tier = Some("gold".to_string());
+        assert!(registry
+            .create(unknown_tier, 1_002)
+            .expect_err("unknown tier")
+            .contains("unknown tier gold"));
+        assert_eq!(registry.keys.len(), 1);
+    }
+
+    #[test]
+    fn edits_hold_a_lock_and_leave_no_temporary_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("api-keys.json");
+        let untiered = |key_id| NewApiKey {
+            rate_limit_tier: None,
+            ..new_key(key_id)
+        };
+        ApiKeyRegistry::edit(&path, |registry| registry.create(untiered("ci"), 1_000))
+            .expect("create");
+        assert!(ApiKeyRegistry::edit(&path, |registry| registry.revoke("absent", 1_001)).is_err());
+        let names: Vec<String> = std::fs::read_dir(dir.path())
+            .expect("list")
+            .map(|entry| {
+                entry
+                    .expect("entry")
+                    .file_name()
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect();
+        assert_eq!(names, vec!["api-keys.json".to_string()]);
+
+        let lock = dir.path().join(".api-keys.json.lock");
+        std::fs::write(&lock, b"").expect("hold lock");
+        let err = ApiKeyRegistry::edit(&path, |registry| registry.create(untiered("batch"), 1_002))
+            .expect_err("locked");
+        assert!(err.contains("being edited"), "{err}");
+        std::fs::remove_file(&lock).expect("release lock");
+        ApiKeyRegistry::edit(&path, |registry| registry.create(untiered("batch"), 1_003))
+            .expect("create after release");
+        assert_eq!(ApiKeyRegistry::read(&path).expect("read").keys.len(), 2);
+    }
+
+    #[test]
+    fn rotation_and_revocation_update_key_status() {
+        let mut registry = registry_with_tier();
+        let old = registry.create(new_key("ci"), 1_000).expect("create");
+        let next = registry.rotate("ci", "ci-2", 60, 2_000).expect("rotate");
+        let store = registry.store();
+        assert!(store.validate_raw_key(&old, 2_060).is_ok());
+        assert_eq!(
+            store.validate_raw_key(&old, 2_061),
+            Err(AuthValidationError::ApiKeyExpired)
+        );
+        assert_eq!(
+            store
+                .validate_raw_key(&next, 2_061)
+                .map(|record| record.rate_limit_tier.clone()),
+            Ok(Some("bulk".to_string()))
+        );
+
+        registry.revoke("ci-2", 3_000).expect("revoke");
+        registry.revoke("ci-2", 3_500).expect("revoke again");
+        let statuses: Vec<_> = registry
+            .summaries(3_600)
+            .into_iter()
+            .map(|summary| (summary.key_id, summary.status, summary.revoked_at_unix_s))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("ci".to_string(), "expired", None),
+                ("ci-2".to_string(), "revoked", Some(3_000)),
+            ]
+        );
+        assert!(registry.revoke("missing", 3_000).is_err());
+        assert!(registry.rotate("ci-2", "ci-3", 60, 3_600).is_err());
+    }
+
+    #[test]
+    fn store_reloads_revocations_and_keeps_keys_when_file_breaks() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("api-keys.json");
+        let mut registry = registry_with_tier();
+        let raw = registry.create(new_key("ci"), 1_000).expect("create");
+        registry.write(&path).expect("write");
+
+        let store = ApiKeyRegistryStore::load(Some(&path)).expect("load");
+        assert_eq!(
+            store.authenticate(&raw, 1_001).map(|record| record.owner),
+            Ok("curation-team".to_string())
+        );
+        let record = store.authenticate(&raw, 1_001).expect("record");
+        assert_eq!(
+            store
+                .quota_for(Some(&record), None)
+                .map(|quota| (quota.tenant, quota.tier.requests.capacity)),
+            Some(("key:ci".to_string(), 500.0))
+        );
+        assert!(!store.reload_if_changed().expect("unchanged"));
+
+        registry.revoke("ci", 1_002).expect("revoke");
+        registry.write(&path).expect("rewrite");
+        assert!(store.reload_if_changed().expect("changed"));
+        assert_eq!(
+            store.authenticate(&raw, 1_003),
+            Err(AuthValidationError::ApiKeyRevoked)
+        );
+
+        std::fs::write(&path, "{\"schema_version\": 2}").expect("break");
+        let active = store.active_fingerprint();
+        assert!(store.reload_if_changed().is_err());
+        assert_eq!(store.active_fingerprint(), active);
+
+        let unconfigured = ApiKeyRegistryStore::load(None).expect("unconfigured");
+        assert!(!unconfigured.is_configured());
+        assert_eq!(
+            unconfigured.authenticate(&raw, 1_003),
+            Err(AuthValidationError::ApiKeyInvalid)
+        );
+    }
+
+    #[test]
+    fn quotas_resolve_by_key_then_subject_then_default_tier() {
+        let mut registry = registry_with_tier();
+        registry.tiers.insert(
+            "free".to_string(),
+            QuotaTier::from(RateLimitConfig {
+                capacity: 5.0,
+                refill_per_sec: 1.0,
+            }),
+        );
+        let mut untiered = new_key("untiered");
+        untiered.rate_limit_tier = None;
+        registry.create(new_key("ci"), 1_000).expect("create");
+        registry.create(untiered, 1_000).expect("create");
+        let store = registry.store();
+        let ci = store.get("ci").expect("ci").clone();
+        let untiered = store.get("untiered").expect("untiered").clone();
+
+        assert_eq!(registry.quota_for(Some(&untiered), None), None);
+        assert_eq!(registry.quota_for(None, Some("alice")), None);
+
+        registry.principals.push(PrincipalQuota {
+            subject: "alice".to_string(),
+            tier: "bulk".to_string(),
+        });
+        registry.default_tier = Some("free".to_string());
+        registry.validate().expect("valid");
+        let resolved = |key, subject| {
+            registry
+                .quota_for(key, subject)
+                .map(|quota| (quota.tenant, quota.tier_name))
+        };
+        assert_eq!(
+            resolved(Some(&ci), Some("alice")),
+            Some(("key:ci".to_string(), "bulk".to_string()))
+        );
+        assert_eq!(
+            resolved(Some(&untiered), None),
+            Some(("key:untiered".to_string(), "free".to_string()))
+        );
+        assert_eq!(
+            resolved(None, Some("alice")),
+            Some(("sub:alice".to_string(), "bulk".to_string()))
+        );
+        assert_eq!(
+            resolved(None, Some("bob")),
+            Some(("sub:bob".to_string(), "free".to_string()))
+        );
+        assert_eq!(resolved(None, None), None);
+
+        // Tiers written before daily budgets existed still parse.
+        let legacy = ApiKeyRegistry::parse(
+            r#"{"schema_version":1,"tiers":{"gold":{"capacity":10.0,"refill_per_sec":2.0}}}"#,
+        )
+        .expect("legacy tiers");
+        assert_eq!(legacy.tiers["gold"].daily_rows_returned, None);
+
+        registry.principals[0].tier = "platinum".to_string();
+        assert!(registry
+            .validate()
+            .expect_err("unknown tier")
+            .contains("principal alice names unknown tier platinum"));
+        registry.principals[0].tier = "bulk".to_string();
+        registry
+            .tiers
+            .get_mut("free")
+            .expect("free")
+            .daily_rows_returned = Some(0);
+        assert!(registry.validate().is_err());
+    }
+}
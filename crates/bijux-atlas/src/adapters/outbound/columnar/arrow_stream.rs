@@ -0,0 +1,36 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Arrow IPC streaming format: a schema message, one record batch message per
+//! [`ColumnBatch`], then the end-of-stream marker.
+
+use std::io::{self, Write};
+
+use arrow_ipc::writer::StreamWriter;
+
+use super::{arrow_schema, record_batch, ColumnBatch, ColumnSpec};
+
+pub struct ArrowStreamWriter<W: Write> {
+    inner: StreamWriter<W>,
+    schema: Vec<ColumnSpec>,
+}
+
+impl<W: Write> ArrowStreamWriter<W> {
+    /// Writes the schema message.
+    pub fn new(out: W, schema: Vec<ColumnSpec>) -> io::Result<Self> {
+        let inner = StreamWriter::try_new(out, &arrow_schema(&schema)).map_err(io::Error::other)?;
+        Ok(Self { inner, schema })
+    }
+
+    pub fn write_batch(&mut self, batch: &ColumnBatch) -> io::Result<()> {
+        let batch = record_batch(&self.schema, batch)?;
+        self.inner.write(&batch).map_err(io::Error::other)
+    }
+
+    /// Writes the end-of-stream marker and returns the sink.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.inner.finish().map_err(io::Error::other)?;
+        let mut out = self.inner.into_inner().map_err(io::Error::other)?;
+        out.flush()?;
+        Ok(out)
+    }
+}
@@ -0,0 +1,412 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Columnar export of query rows and dataset tables as Arrow IPC streams and Parquet files.
+//!
+//! Column types follow the OpenAPI field types of the row contracts: `integer` maps to
+//! Int64, `number` to Float64, `boolean` to Bool and `string` to Utf8. SQLite tables
+//! without a row contract fall back to the declared column affinity.
+
+mod arrow_stream;
+mod parquet_file;
+
+use std::sync::Arc;
+
+use arrow_array::{
+    ArrayRef, BinaryArray, BooleanArray, Float64Array, Int64Array, RecordBatch, StringArray,
+};
+use arrow_schema::{DataType, Field, Schema};
+use rusqlite::types::ValueRef;
+use rusqlite::Connection;
+use serde_json::Value;
+
+pub use arrow_stream::ArrowStreamWriter;
+pub use parquet_file::ParquetWriter;
+
+/// Media type negotiated through `Accept` for Arrow IPC stream responses.
+pub const ARROW_STREAM_MEDIA_TYPE: &str = "application/vnd.apache.arrow.stream";
+
+/// Rows per record batch / row group when streaming a whole table.
+pub const EXPORT_BATCH_ROWS: usize = 65_536;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnType {
+    Int64,
+    Float64,
+    Utf8,
+    Bool,
+    Binary,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnSpec {
+    pub name: String,
+    pub column_type: ColumnType,
+    pub nullable: bool,
+}
+
+impl ColumnSpec {
+    #[must_use]
+    pub fn new(name: &str, column_type: ColumnType, nullable: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            column_type,
+            nullable,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnData {
+    Int64(Vec<Option<i64>>),
+    Float64(Vec<Option<f64>>),
+    Utf8(Vec<Option<String>>),
+    Bool(Vec<Option<bool>>),
+    Binary(Vec<Option<Vec<u8>>>),
+}
+
+impl ColumnData {
+    fn empty(column_type: ColumnType) -> Self {
+        match column_type {
+            ColumnType::Int64 => Self::Int64(Vec::new()),
+            ColumnType::Float64 => Self::Float64(Vec::new()),
+            ColumnType::Utf8 => Self::Utf8(Vec::new()),
+            ColumnType::Bool => Self::Bool(Vec::new()),
+            ColumnType::Binary => Self::Binary(Vec::new()),
+        }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Int64(v) => v.len(),
+            Self::Float64(v) => v.len(),
+            Self::Utf8(v) => v.len(),
+            Self::Bool(v) => v.len(),
+            Self::Binary(v) => v.len(),
+        }
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[must_use]
+    pub fn null_count(&self) -> usize {
+        (0..self.len()).filter(|&i| !self.is_valid(i)).count()
+    }
+
+    #[must_use]
+    pub fn is_valid(&self, row: usize) -> bool {
+        match self {
+            Self::Int64(v) => v[row].is_some(),
+            Self::Float64(v) => v[row].is_some(),
+            Self::Utf8(v) => v[row].is_some(),
+            Self::Bool(v) => v[row].is_some(),
+            Self::Binary(v) => v[row].is_some(),
+        }
+    }
+
+    fn push_json(&mut self, value: Option<&Value>) {
+        let value = value.filter(|v| !v.is_null());
+        match self {
+            Self::Int64(v) => v.push(value.and_then(|x| {
+                x.as_i64()
+                    .or_else(|| x.as_u64().and_then(|u| i64::try_from(u).ok()))
+                    .or_else(|| x.as_bool().map(i64::from))
+            })),
+            Self::Float64(v) => v.push(value.and_then(Value::as_f64)),
+            Self::Utf8(v) => v.push(value.map(|x| match x {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            })),
+            Self::Bool(v) => {
+                v.push(value.and_then(|x| x.as_bool().or_else(|| x.as_i64().map(|n| n != 0))))
+            }
+            Self::Binary(v) => v.push(value.and_then(Value::as_str).map(|s| s.as_bytes().to_vec())),
+        }
+    }
+
+    fn push_sqlite(&mut self, value: ValueRef<'_>) {
+        match self {
+            Self::Int64(v) => v.push(match value {
+                ValueRef::Integer(n) => Some(n),
+                ValueRef::Real(f) => Some(f as i64),
+                ValueRef::Text(t) => std::str::from_utf8(t).ok().and_then(|s| s.parse().ok()),
+                ValueRef::Null | ValueRef::Blob(_) => None,
+            }),
+            Self::Float64(v) => v.push(match value {
+                ValueRef::Integer(n) => Some(n as f64),
+                ValueRef::Real(f) => Some(f),
+                ValueRef::Text(t) => std::str::from_utf8(t).ok().and_then(|s| s.parse().ok()),
+                ValueRef::Null | ValueRef::Blob(_) => None,
+            }),
+            Self::Utf8(v) => v.push(match value {
+                ValueRef::Null => None,
+                ValueRef::Integer(n) => Some(n.to_string()),
+                ValueRef::Real(f) => Some(f.to_string()),
+                ValueRef::Text(t) | ValueRef::Blob(t) => Some(String::from_utf8_lossy(t).into()),
+            }),
+            Self::Bool(v) => v.push(match value {
+                ValueRef::Integer(n) => Some(n != 0),
+                ValueRef::Real(f) => Some(f != 0.0),
+                ValueRef::Text(t) => match t {
+                    b"1" | b"true" => Some(true),
+                    b"0" | b"false" => Some(false),
+                    _ => None,
+                },
+                ValueRef::Null | ValueRef::Blob(_) => None,
+            }),
+            Self::Binary(v) => v.push(match value {
+                ValueRef::Null => None,
+                ValueRef::Integer(n) => Some(n.to_string().into_bytes()),
+                ValueRef::Real(f) => Some(f.to_string().into_bytes()),
+                ValueRef::Text(t) | ValueRef::Blob(t) => Some(t.to_vec()),
+            }),
+        }
+    }
+}
+
+/// One record batch (Arrow) or row group (Parquet) worth of columns.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnBatch {
+    pub columns: Vec<ColumnData>,
+}
+
+impl ColumnBatch {
+    #[must_use]
+    pub fn empty(schema: &[ColumnSpec]) -> Self {
+        Self {
+            columns: schema
+                .iter()
+                .map(|spec| ColumnData::empty(spec.column_type))
+                .collect(),
+        }
+    }
+
+    #[must_use]
+    pub fn num_rows(&self) -> usize {
+        self.columns.first().map_or(0, ColumnData::len)
+    }
+
+    /// Builds a batch from JSON row objects, reading each column by name; missing keys are null.
+    #[must_use]
+    pub fn from_json_rows(schema: &[ColumnSpec], rows: &[Value]) -> Self {
+        let mut batch = Self::empty(schema);
+        for row in rows {
+            for (spec, column) in schema.iter().zip(batch.columns.iter_mut()) {
+                column.push_json(row.get(&spec.name));
+            }
+        }
+        batch
+    }
+}
+
+/// Gene row columns as exposed by `/v1/genes`.
+#[must_use]
+pub fn gene_row_schema() -> Vec<ColumnSpec> {
+    vec![
+        ColumnSpec::new("gene_id", ColumnType::Utf8, false),
+        ColumnSpec::new("name", ColumnType::Utf8, true),
+        ColumnSpec::new("seqid", ColumnType::Utf8, true),
+        ColumnSpec::new("start", ColumnType::Int64, true),
+        ColumnSpec::new("end", ColumnType::Int64, true),
+        ColumnSpec::new("biotype", ColumnType::Utf8, true),
+        ColumnSpec::new("transcript_count", ColumnType::Int64, true),
+        ColumnSpec::new("sequence_length", ColumnType::Int64, true),
+        ColumnSpec::new("strand", ColumnType::Utf8, true),
+    ]
+}
+
+/// Transcript row columns as exposed by `/v1/genes/{gene_id}/transcripts`.
+#[must_use]
+pub fn transcript_row_schema() -> Vec<ColumnSpec> {
+    vec![
+        ColumnSpec::new("transcript_id", ColumnType::Utf8, false),
+        ColumnSpec::new("parent_gene_id", ColumnType::Utf8, false),
+        ColumnSpec::new("transcript_type", ColumnType::Utf8, false),
+        ColumnSpec::new("biotype", ColumnType::Utf8, true),
+        ColumnSpec::new("seqid", ColumnType::Utf8, false),
+        ColumnSpec::new("start", ColumnType::Int64, false),
+        ColumnSpec::new("end", ColumnType::Int64, false),
+        ColumnSpec::new("exon_count", ColumnType::Int64, false),
+        ColumnSpec::new("total_exon_span", ColumnType::Int64, false),
+        ColumnSpec::new("cds_present", ColumnType::Bool, false),
+    ]
+}
+
+/// Encodes one batch as a complete Arrow IPC stream.
+pub fn encode_arrow_stream(schema: &[ColumnSpec], batch: &ColumnBatch) -> std::io::Result<Vec<u8>> {
+    let mut writer = ArrowStreamWriter::new(Vec::new(), schema.to_vec())?;
+    writer.write_batch(batch)?;
+    writer.finish()
+}
+
+/// Encodes one batch as a complete Parquet file.
+pub fn encode_parquet(schema: &[ColumnSpec], batch: &ColumnBatch) -> std::io::Result<Vec<u8>> {
+    let mut writer = ParquetWriter::new(Vec::new(), schema.to_vec())?;
+    writer.write_batch(batch)?;
+    writer.finish()
+}
+
+/// User tables of a dataset SQLite, skipping SQLite internals and R-tree shadow tables.
+pub fn dataset_tables(conn: &Connection) -> rusqlite::Result<Vec<String>> {
+    let mut stmt = conn.prepare(
+        "SELECT name, sql FROM sqlite_master WHERE type='table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+    )?;
+    let rows = stmt.query_map([], |r| {
+        Ok((r.get::<_, String>(0)?, r.get::<_, Option<String>>(1)?))
+    })?;
+    let all = rows.collect::<rusqlite::Result<Vec<_>>>()?;
+    let virtual_tables: Vec<String> = all
+        .iter()
+        .filter(|(_, sql)| {
+            sql.as_deref()
+                .is_some_and(|s| s.to_ascii_uppercase().starts_with("CREATE VIRTUAL TABLE"))
+        })
+        .map(|(name, _)| name.clone())
+        .collect();
+    Ok(all
+        .into_iter()
+        .map(|(name, _)| name)
+        .filter(|name| {
+            !virtual_tables.iter().any(|v| {
+                name.strip_prefix(v.as_str())
+                    .is_some_and(|rest| matches!(rest, "_node" | "_rowid" | "_parent"))
+            })
+        })
+        .collect())
+}
+
+/// Column schema of a dataset table. Columns shared with a row contract take the contract
+/// type; the rest follow SQLite type affinity.
+pub fn table_schema(conn: &Connection, table: &str) -> rusqlite::Result<Vec<ColumnSpec>> {
+    let contract: Vec<ColumnSpec> = match table {
+        "gene_summary" => gene_row_schema(),
+        "transcript_summary" => transcript_row_schema(),
+        _ => Vec::new(),
+    };
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", quote_ident(table)))?;
+    let rows = stmt.query_map([], |r| {
+        Ok((
+            r.get::<_, String>(1)?,
+            r.get::<_, Option<String>>(2)?.unwrap_or_default(),
+            r.get::<_, i64>(3)? != 0,
+            r.get::<_, i64>(5)? != 0,
+        ))
+    })?;
+    let mut schema = Vec::new();
+    for row in rows {
+        let (name, declared, not_null, primary_key) = row?;
+        let column_type = contract
+            .iter()
+            .find(|spec| spec.name == name)
+            .map_or_else(|| affinity_type(&declared), |spec| spec.column_type);
+        schema.push(ColumnSpec::new(
+            &name,
+            column_type,
+            !(not_null || primary_key),
+        ));
+    }
+    Ok(schema)
+}
+
+fn affinity_type(declared: &str) -> ColumnType {
+    let upper = declared.to_ascii_uppercase();
+    if upper.contains("INT") {
+        ColumnType::Int64
+    } else if upper.contains("CHAR") || upper.contains("CLOB") || upper.contains("TEXT") {
+        ColumnType::Utf8
+    } else if upper.contains("BLOB") {
+        ColumnType::Binary
+    } else if upper.contains("REAL") || upper.contains("FLOA") || upper.contains("DOUB") {
+        ColumnType::Float64
+    } else if upper.contains("BOOL") {
+        ColumnType::Bool
+    } else {
+        ColumnType::Utf8
+    }
+}
+
+fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Streams every row of `table` through `sink` in batches of [`EXPORT_BATCH_ROWS`].
+pub fn for_each_table_batch(
+    conn: &Connection,
+    table: &str,
+    schema: &[ColumnSpec],
+    mut sink: impl FnMut(&ColumnBatch) -> std::io::Result<()>,
+) -> Result<usize, String> {
+    let columns = schema
+        .iter()
+        .map(|spec| quote_ident(&spec.name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut stmt = conn
+        .prepare(&format!("SELECT {columns} FROM {}", quote_ident(table)))
+        .map_err(|e| e.to_string())?;
+    let mut rows = stmt.query([]).map_err(|e| e.to_string())?;
+    let mut batch = ColumnBatch::empty(schema);
+    let mut total = 0_usize;
+    while let Some(row) = rows.next().map_err(|e| e.to_string())? {
+        for (i, column) in batch.columns.iter_mut().enumerate() {
+            column.push_sqlite(row.get_ref(i).map_err(|e| e.to_string())?);
+        }
+        if batch.num_rows() == EXPORT_BATCH_ROWS {
+            total += batch.num_rows();
+            sink(&batch).map_err(|e| e.to_string())?;
+            batch = ColumnBatch::empty(schema);
+        }
+    }
+    if batch.num_rows() > 0 || total == 0 {
+        total += batch.num_rows();
+        sink(&batch).map_err(|e| e.to_string())?;
+    }
+    Ok(total)
+}
+
+fn arrow_schema(schema: &[ColumnSpec]) -> Schema {
+    Schema::new(
+        schema
+            .iter()
+            .map(|spec| {
+                let data_type = match spec.column_type {
+                    ColumnType::Int64 => DataType::Int64,
+                    ColumnType::Float64 => DataType::Float64,
+                    ColumnType::Utf8 => DataType::Utf8,
+                    ColumnType::Bool => DataType::Boolean,
+                    ColumnType::Binary => DataType::Binary,
+                };
+                Field::new(&spec.name, data_type, spec.nullable)
+            })
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// Arrow record batch for `batch`; rejects nulls in non-nullable columns and ragged columns.
+fn record_batch(schema: &[ColumnSpec], batch: &ColumnBatch) -> std::io::Result<RecordBatch> {
+    if batch.columns.len() != schema.len() {
+        return Err(std::io::Error::other(
+            "batch column count does not match schema",
+        ));
+    }
+    let arrays = batch
+        .columns
+        .iter()
+        .map(|column| -> ArrayRef {
+            match column {
+                ColumnData::Int64(v) => Arc::new(Int64Array::from(v.clone())),
+                ColumnData::Float64(v) => Arc::new(Float64Array::from(v.clone())),
+                ColumnData::Utf8(v) => Arc::new(StringArray::from(v.clone())),
+                ColumnData::Bool(v) => Arc::new(BooleanArray::from(v.clone())),
+                ColumnData::Binary(v) => Arc::new(BinaryArray::from_iter(v.iter())),
+            }
+        })
+        .collect();
+    RecordBatch::try_new(Arc::new(arrow_schema(schema)), arrays).map_err(std::io::Error::other)
+}
+
+#[cfg(test)]
+mod tests;
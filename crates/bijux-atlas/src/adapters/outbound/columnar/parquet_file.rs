@@ -0,0 +1,39 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Parquet writer: one row group per non-empty [`ColumnBatch`], uncompressed.
+
+use std::io::{self, Write};
+
+use parquet::arrow::ArrowWriter;
+
+use super::{arrow_schema, record_batch, ColumnBatch, ColumnSpec};
+
+pub struct ParquetWriter<W: Write + Send> {
+    inner: ArrowWriter<W>,
+    schema: Vec<ColumnSpec>,
+}
+
+impl<W: Write + Send> ParquetWriter<W> {
+    pub fn new(out: W, schema: Vec<ColumnSpec>) -> io::Result<Self> {
+        let inner = ArrowWriter::try_new(out, arrow_schema(&schema).into(), None)
+            .map_err(io::Error::other)?;
+        Ok(Self { inner, schema })
+    }
+
+    /// Writes `batch` as one row group; empty batches are skipped.
+    pub fn write_batch(&mut self, batch: &ColumnBatch) -> io::Result<()> {
+        if batch.num_rows() == 0 {
+            return Ok(());
+        }
+        let batch = record_batch(&self.schema, batch)?;
+        self.inner.write(&batch).map_err(io::Error::other)?;
+        self.inner.flush().map_err(io::Error::other)
+    }
+
+    /// Writes the footer and returns the sink.
+    pub fn finish(self) -> io::Result<W> {
+        let mut out = self.inner.into_inner().map_err(io::Error::other)?;
+        out.flush()?;
+        Ok(out)
+    }
+}
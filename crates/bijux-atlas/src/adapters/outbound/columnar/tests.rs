@@ -0,0 +1,227 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use serde_json::json;
+
+fn sample() -> (Vec<ColumnSpec>, ColumnBatch) {
+    let schema = vec![
+        ColumnSpec::new("gene_id", ColumnType::Utf8, false),
+        ColumnSpec::new("start", ColumnType::Int64, true),
+        ColumnSpec::new("score", ColumnType::Float64, true),
+        ColumnSpec::new("flag", ColumnType::Bool, true),
+    ];
+    let rows = vec![
+        json!({"gene_id":"g1","start":10,"score":1.5,"flag":true}),
+        json!({"gene_id":"g2","start":null,"flag":false}),
+        json!({"gene_id":"g3","start":30,"flag":null}),
+    ];
+    let batch = ColumnBatch::from_json_rows(&schema, &rows);
+    (schema, batch)
+}
+
+#[test]
+fn json_rows_map_missing_and_null_values_to_nulls() {
+    let (_, batch) = sample();
+    assert_eq!(batch.num_rows(), 3);
+    assert_eq!(
+        batch.columns[1],
+        ColumnData::Int64(vec![Some(10), None, Some(30)])
+    );
+    assert_eq!(
+        batch.columns[2],
+        ColumnData::Float64(vec![Some(1.5), None, None])
+    );
+    assert_eq!(batch.columns[3].null_count(), 1);
+}
+
+#[test]
+fn arrow_stream_round_trips_through_the_ipc_reader() {
+    use arrow_array::{Array, Float64Array, Int64Array, StringArray};
+    use arrow_ipc::reader::StreamReader;
+
+    let (schema, batch) = sample();
+    let bytes = encode_arrow_stream(&schema, &batch).expect("encode");
+    let reader = StreamReader::try_new(std::io::Cursor::new(bytes), None).expect("stream");
+    assert_eq!(reader.schema().as_ref(), &arrow_schema(&schema));
+    let batches = reader
+        .collect::<Result<Vec<_>, _>>()
+        .expect("record batches");
+    assert_eq!(batches.len(), 1);
+    let decoded = &batches[0];
+    assert_eq!(decoded.num_rows(), 3);
+    let gene_ids = decoded
+        .column(0)
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .expect("utf8");
+    assert_eq!(
+        gene_ids.iter().collect::<Vec<_>>(),
+        vec![Some("g1"), Some("g2"), Some("g3")]
+    );
+    let starts = decoded
+        .column(1)
+        .as_any()
+        .downcast_ref::<Int64Array>()
+        .expect("int64");
+    assert_eq!(
+        starts.iter().collect::<Vec<_>>(),
+        vec![Some(10), None, Some(30)]
+    );
+    let scores = decoded
+        .column(2)
+        .as_any()
+        .downcast_ref::<Float64Array>()
+        .expect("float64");
+    assert_eq!(scores.null_count(), 2);
+    assert_eq!(decoded, &record_batch(&schema, &batch).expect("batch"));
+}
+
+#[test]
+fn parquet_round_trips_through_the_file_reader_with_one_row_group_per_batch() {
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use parquet::basic::Type as PhysicalType;
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use parquet::record::Field;
+
+    let (schema, batch) = sample();
+    let mut writer = ParquetWriter::new(Vec::new(), schema.clone()).expect("writer");
+    writer.write_batch(&batch).expect("first group");
+    writer.write_batch(&batch).expect("second group");
+    writer
+        .write_batch(&ColumnBatch::empty(&schema))
+        .expect("empty batch is skipped");
+    let bytes = writer.finish().expect("finish");
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("rows.parquet");
+    std::fs::write(&path, &bytes).expect("write parquet");
+
+    let reader = SerializedFileReader::new(std::fs::File::open(&path).expect("open"))
+        .expect("parquet reader");
+    let metadata = reader.metadata();
+    assert_eq!(metadata.num_row_groups(), 2);
+    assert_eq!(metadata.file_metadata().num_rows(), 6);
+    let columns: Vec<_> = metadata
+        .file_metadata()
+        .schema_descr()
+        .columns()
+        .iter()
+        .map(|c| {
+            (
+                c.name().to_string(),
+                c.physical_type(),
+                c.self_type().is_optional(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        columns,
+        vec![
+            ("gene_id".to_string(), PhysicalType::BYTE_ARRAY, false),
+            ("start".to_string(), PhysicalType::INT64, true),
+            ("score".to_string(), PhysicalType::DOUBLE, true),
+            ("flag".to_string(), PhysicalType::BOOLEAN, true),
+        ]
+    );
+    let rows: Vec<Vec<Field>> = reader
+        .get_row_iter(None)
+        .expect("rows")
+        .map(|row| {
+            row.expect("row")
+                .get_column_iter()
+                .map(|(_, field)| field.clone())
+                .collect()
+        })
+        .collect();
+    assert_eq!(rows.len(), 6);
+    assert_eq!(
+        rows[..3],
+        [
+            vec![
+                Field::Str("g1".into()),
+                Field::Long(10),
+                Field::Double(1.5),
+                Field::Bool(true),
+            ],
+            vec![
+                Field::Str("g2".into()),
+                Field::Null,
+                Field::Null,
+                Field::Bool(false),
+            ],
+            vec![
+                Field::Str("g3".into()),
+                Field::Long(30),
+                Field::Null,
+                Field::Null,
+            ],
+        ]
+    );
+    assert_eq!(rows[..3], rows[3..]);
+
+    let arrow_reader =
+        ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(&path).expect("open"))
+            .expect("arrow reader")
+            .build()
+            .expect("batches");
+    let batches = arrow_reader
+        .collect::<Result<Vec<_>, _>>()
+        .expect("record batches");
+    let expected = record_batch(&schema, &batch).expect("batch");
+    assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 6);
+    assert!(batches
+        .iter()
+        .all(|b| b.schema().fields() == expected.schema().fields()));
+}
+
+#[test]
+fn required_column_with_nulls_is_rejected() {
+    let schema = vec![ColumnSpec::new("gene_id", ColumnType::Utf8, false)];
+    let batch = ColumnBatch {
+        columns: vec![ColumnData::Utf8(vec![Some("g1".into()), None])],
+    };
+    assert!(encode_parquet(&schema, &batch).is_err());
+    assert!(encode_arrow_stream(&schema, &batch).is_err());
+}
+
+#[test]
+fn dataset_tables_skip_rtree_shadow_tables_and_use_contract_types() {
+    let conn = Connection::open_in_memory().expect("sqlite");
+    conn.execute_batch(
+        "CREATE TABLE transcript_summary(id INTEGER PRIMARY KEY, transcript_id TEXT NOT NULL, cds_present INTEGER, score REAL, raw BLOB);
+         CREATE VIRTUAL TABLE gene_summary_rtree USING rtree(gene_rowid, start, end);
+         INSERT INTO transcript_summary VALUES (1,'tx1',1,0.5,x'00ff'), (2,'tx2',0,NULL,NULL);",
+    )
+    .expect("seed");
+    let tables = dataset_tables(&conn).expect("tables");
+    assert_eq!(tables, vec!["gene_summary_rtree", "transcript_summary"]);
+    let schema = table_schema(&conn, "transcript_summary").expect("schema");
+    let types: Vec<_> = schema
+        .iter()
+        .map(|s| (s.name.as_str(), s.column_type, s.nullable))
+        .collect();
+    assert_eq!(
+        types,
+        vec![
+            ("id", ColumnType::Int64, false),
+            ("transcript_id", ColumnType::Utf8, false),
+            ("cds_present", ColumnType::Bool, true),
+            ("score", ColumnType::Float64, true),
+            ("raw", ColumnType::Binary, true),
+        ]
+    );
+    let mut batches = Vec::new();
+    let rows = for_each_table_batch(&conn, "transcript_summary", &schema, |batch| {
+        batches.push(batch.clone());
+        Ok(())
+    })
+    .expect("stream");
+    assert_eq!(rows, 2);
+    assert_eq!(
+        batches[0].columns[2],
+        ColumnData::Bool(vec![Some(true), Some(false)])
+    );
+    assert_eq!(
+        batches[0].columns[4],
+        ColumnData::Binary(vec![Some(vec![0x00, 0xff]), None])
+    );
+}
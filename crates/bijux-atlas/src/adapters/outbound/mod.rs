@@ -1,7 +1,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod api_keys;
+pub mod columnar;
 pub mod fs;
+pub mod oidc;
 pub mod redis;
+pub mod role_bindings;
 pub mod sqlite;
 pub mod store;
 pub mod telemetry;
@@ -0,0 +1,307 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! JSON Web Key Set source for `auth_mode=oidc`.
+//!
+//! Keys are fetched from the identity provider (or read from a local file) and
+//! kept for `oidc_jwks_ttl_secs`. A token signed with a key id the cached set
+//! does not know triggers one early refetch, so provider key rotation is picked
+//! up without waiting for the TTL. Refetches are spaced by a minimum interval,
+//! and a failed refetch keeps serving the last good key set.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tokio::sync::RwLock;
+use tracing::warn;
+
+use crate::domain::security::auth::{AuthValidationError, TokenClaims, TokenValidationPolicy};
+use crate::domain::security::jwks::{validate_jwt, JwkSet};
+use crate::runtime::config::ApiConfig;
+
+const DEFAULT_MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JwksSource {
+    Url(String),
+    File(PathBuf),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OidcValidationError {
+    Token(AuthValidationError),
+    /// No key set could be loaded yet; the request cannot be judged either way.
+    KeysUnavailable(String),
+}
+
+#[derive(Default)]
+struct CachedKeys {
+    keys: Option<Arc<JwkSet>>,
+    fetched_at: Option<Instant>,
+    attempted_at: Option<Instant>,
+    last_error: Option<String>,
+}
+
+impl CachedKeys {
+    fn fresh(&self, ttl: Duration) -> Option<Arc<JwkSet>> {
+        match (&self.keys, self.fetched_at) {
+            (Some(keys), Some(at)) if at.elapsed() < ttl => Some(Arc::clone(keys)),
+            _ => None,
+        }
+    }
+}
+
+pub struct JwksCache {
+    source: JwksSource,
+    ttl: Duration,
+    min_refresh_interval: Duration,
+    leeway_secs: u64,
+    policy: TokenValidationPolicy,
+    client: reqwest::Client,
+    state: RwLock<CachedKeys>,
+}
+
+impl JwksCache {
+    #[must_use]
+    pub fn new(
+        source: JwksSource,
+        policy: TokenValidationPolicy,
+        ttl: Duration,
+        leeway_secs: u64,
+    ) -> Self {
+        Self {
+            source,
+            ttl,
+            min_refresh_interval: DEFAULT_MIN_REFRESH_INTERVAL,
+            leeway_secs,
+            policy,
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .redirect(reqwest::redirect::Policy::limited(3))
+                .build()
+                .unwrap_or_else(|_| reqwest::Client::new()),
+            state: RwLock::new(CachedKeys::default()),
+        }
+    }
+
+    #[must_use]
+    pub fn with_min_refresh_interval(mut self, interval: Duration) -> Self {
+        self.min_refresh_interval = interval;
+        self
+    }
+
+    /// The cache `auth_mode=oidc` validates against, or `None` when no JWKS
+    /// source is configured (proxy-trusting deployments).
+    #[must_use]
+    pub fn from_api_config(api: &ApiConfig) -> Option<Self> {
+        let source = match (api.oidc_jwks_url.as_deref(), api.oidc_jwks_path.as_deref()) {
+            (Some(url), _) => JwksSource::Url(url.to_string()),
+            (None, Some(path)) => JwksSource::File(PathBuf::from(path)),
+            (None, None) => return None,
+        };
+        let mut policy = TokenValidationPolicy::new(
+            api.token_required_issuer.as_deref().unwrap_or_default(),
+            api.token_required_audience.as_deref().unwrap_or_default(),
+        );
+        policy.required_scopes = api.token_required_scopes.clone();
+        policy.revoked_token_ids = api.token_revoked_ids.iter().cloned().collect();
+        Some(Self::new(
+            source,
+            policy,
+            Duration::from_secs(api.oidc_jwks_ttl_secs),
+            api.oidc_clock_skew_secs,
+        ))
+    }
+
+    #[must_use]
+    pub fn source(&self) -> &JwksSource {
+        &self.source
+    }
+
+    pub async fn validate(
+        &self,
+        token: &str,
+        now_unix_s: u64,
+    ) -> Result<TokenClaims, OidcValidationError> {
+        let keys = self.current_keys().await?;
+        match validate_jwt(token, &keys, &self.policy, now_unix_s, self.leeway_secs) {
+            Err(AuthValidationError::TokenKeyUnknown) => {
+                let refreshed = self.refresh(true).await?;
+                if Arc::ptr_eq(&refreshed, &keys) {
+                    return Err(OidcValidationError::Token(
+                        AuthValidationError::TokenKeyUnknown,
+                    ));
+                }
+                validate_jwt(
+                    token,
+                    &refreshed,
+                    &self.policy,
+                    now_unix_s,
+                    self.leeway_secs,
+                )
+                .map_err(OidcValidationError::Token)
+            }
+            other => other.map_err(OidcValidationError::Token),
+        }
+    }
+
+    async fn current_keys(&self) -> Result<Arc<JwkSet>, OidcValidationError> {
+        if let Some(keys) = self.state.read().await.fresh(self.ttl) {
+            return Ok(keys);
+        }
+        self.refresh(false).await
+    }
+
+    /// Refetches the key set unless one was attempted within the minimum
+    /// interval. `rotation` forces a refetch even while the cached set is fresh.
+    async fn refresh(&self, rotation: bool) -> Result<Arc<JwkSet>, OidcValidationError> {
+        let mut state = self.state.write().await;
+        if !rotation {
+            if let Some(keys) = state.fresh(self.ttl) {
+                return Ok(keys);
+            }
+        }
+        let throttled = state
+            .attempted_at
+            .is_some_and(|at| at.elapsed() < self.min_refresh_interval);
+        if !throttled {
+            state.attempted_at = Some(Instant::now());
+            match self.fetch().await {
+                Ok(keys) => {
+                    let keys = Arc::new(keys);
+                    state.keys = Some(Arc::clone(&keys));
+                    state.fetched_at = Some(Instant::now());
+                    state.last_error = None;
+                    return Ok(keys);
+                }
+                Err(err) => {
+                    warn!(
+                        event_id = "oidc_jwks_refresh_failed",
+                        error = err.as_str(),
+                        "oidc jwks refresh failed"
+                    );
+                    state.last_error = Some(err);
+                }
+            }
+        }
+        state.keys.clone().ok_or_else(|| {
+            OidcValidationError::KeysUnavailable(
+                state
+                    .last_error
+                    .clone()
+                    .unwrap_or_else(|| "jwks not loaded".to_string()),
+            )
+        })
+    }
+
+    async fn fetch(&self) -> Result<JwkSet, String> {
+        let bytes = match &self.source {
+            JwksSource::Url(url) => {
+                let response = self
+                    .client
+                    .get(url)
+                    .header(reqwest::header::ACCEPT, "application/json")
+                    .send()
+                    .await
+                    .map_err(|e| format!("jwks fetch failed: {e}"))?;
+                if !response.status().is_success() {
+                    return Err(format!("jwks fetch returned {}", response.status()));
+                }
+                response
+                    .bytes()
+                    .await
+                    .map_err(|e| format!("jwks read failed: {e}"))?
+                    .to_vec()
+            }
+            JwksSource::File(path) => std::fs::read(path)
+                .map_err(|e| format!("jwks read failed for {}: {e}", path.display()))?,
+        };
+        JwkSet::from_json(&bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{JwksCache, JwksSource, OidcValidationError};
+    use crate::domain::security::auth::{AuthValidationError, TokenValidationPolicy};
+    use base64::Engine;
+    use std::time::Duration;
+
+    const JWKS: &str = include_str!("../../../tests/fixtures/oidc/jwks.json");
+
+    fn unsigned_token_with_kid(kid: &str) -> String {
+        let b64 = |v: serde_json::Value| {
+            base64::engine::general_purpose::URL_SAFE_NO_PAD
+                .encode(serde_json::to_vec(&v).expect("encode"))
+        };
+        format!(
+            "{}.{}.AAAA",
+            b64(serde_json::json!({"alg": "RS256", "kid": kid})),
+            b64(serde_json::json!({"sub": "alice"}))
+        )
+    }
+
+    #[tokio::test]
+    async fn unknown_key_id_refetches_rotated_key_set() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("jwks.json");
+        let set: serde_json::Value = serde_json::from_str(JWKS).expect("jwks");
+        let first = serde_json::json!({"keys": [set["keys"][1].clone()]});
+        std::fs::write(&path, serde_json::to_vec(&first).expect("encode")).expect("write");
+        let cache = JwksCache::new(
+            JwksSource::File(path.clone()),
+            TokenValidationPolicy::new("https://idp.example", "atlas-api"),
+            Duration::from_secs(3600),
+            0,
+        )
+        .with_min_refresh_interval(Duration::ZERO);
+        let token = unsigned_token_with_kid("atlas-test-rs256");
+        assert_eq!(
+            cache.validate(&token, 0).await,
+            Err(OidcValidationError::Token(
+                AuthValidationError::TokenKeyUnknown
+            ))
+        );
+
+        // The provider rotates in the RSA key; the next token using it is
+        // judged on its signature rather than rejected as unknown.
+        std::fs::write(&path, JWKS).expect("rotate");
+        assert_eq!(
+            cache.validate(&token, 0).await,
+            Err(OidcValidationError::Token(
+                AuthValidationError::TokenSignatureInvalid
+            ))
+        );
+
+        // A broken refetch keeps the last good keys.
+        std::fs::write(&path, b"not json").expect("break");
+        assert_eq!(
+            cache.validate(&unsigned_token_with_kid("gone"), 0).await,
+            Err(OidcValidationError::Token(
+                AuthValidationError::TokenKeyUnknown
+            ))
+        );
+        assert_eq!(
+            cache.validate(&token, 0).await,
+            Err(OidcValidationError::Token(
+                AuthValidationError::TokenSignatureInvalid
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_key_set_reports_keys_unavailable() {
+        let cache = JwksCache::new(
+            JwksSource::File("/nonexistent/jwks.json".into()),
+            TokenValidationPolicy::new("https://idp.example", "atlas-api"),
+            Duration::from_secs(60),
+            0,
+        );
+        assert!(matches!(
+            cache
+                .validate(&unsigned_token_with_kid("atlas-test-rs256"), 0)
+                .await,
+            Err(OidcValidationError::KeysUnavailable(_))
+        ));
+    }
+}
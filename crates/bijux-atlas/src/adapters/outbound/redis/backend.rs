@@ -5,10 +5,43 @@ use redis::AsyncCommands;
 use std::collections::{HashMap, HashSet};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant};
 use tokio::sync::{Mutex, OwnedMutexGuard};
 use tokio::time::timeout;
 
+/// Two days, so yesterday's counter is still readable just after midnight.
+const QUOTA_COUNTER_TTL_SECS: i64 = 172_800;
+
+/// Token bucket refilled from the server clock so every replica shares one
+/// notion of time. Returns `{allowed, tokens_left}`; the bucket expires once
+/// it would have refilled completely.
+const TOKEN_BUCKET_SCRIPT: &str = r#"
+local capacity = tonumber(ARGV[1])
+local refill = tonumber(ARGV[2])
+local cost = tonumber(ARGV[3])
+local clock = redis.call("TIME")
+local now = tonumber(clock[1]) + tonumber(clock[2]) / 1000000
+local state = redis.call("HMGET", KEYS[1], "tokens", "at")
+local tokens = tonumber(state[1]) or capacity
+local at = tonumber(state[2]) or now
+tokens = math.min(capacity, tokens + math.max(0, now - at) * refill)
+local allowed = 0
+if tokens >= cost then
+  tokens = tokens - cost
+  allowed = 1
+end
+redis.call("HSET", KEYS[1], "tokens", tostring(tokens), "at", tostring(now))
+redis.call("EXPIRE", KEYS[1], math.ceil(capacity / refill) + 1)
+return {allowed, tostring(tokens)}
+"#;
+
+/// INCRBY and EXPIRE applied together, so a counter never outlives its TTL.
+const QUOTA_ADD_SCRIPT: &str = r#"
+local used = redis.call("INCRBY", KEYS[1], ARGV[1])
+redis.call("EXPIRE", KEYS[1], ARGV[2])
+return used
+"#;
+
 #[derive(Clone, Debug)]
 pub(crate) struct RedisPolicy {
     pub timeout: Duration,
@@ -144,6 +177,16 @@ impl RedisBackend {
         Err(last.unwrap_or_else(|| "redis failure".to_string()))
     }
 
+    /// Runs a non-idempotent command exactly once under the policy timeout.
+    async fn once<T>(
+        &self,
+        op: impl std::future::Future<Output = Result<T, String>>,
+    ) -> Result<T, String> {
+        timeout(self.policy.timeout, op)
+            .await
+            .unwrap_or_else(|_| Err("redis timeout".to_string()))
+    }
+
     pub(crate) async fn acquire_fill_lock(&self, key: &str) -> OwnedMutexGuard<()> {
         let lock = {
             let mut inflight = self.inflight.lock().await;
@@ -175,46 +218,99 @@ impl RedisBackend {
         }
     }
 
+    /// Charges `cost` tokens from a shared bucket with the in-memory
+    /// limiter's capacity and refill, returning whether it fit and the tokens
+    /// left. Runs once: a retried charge that had landed would bill twice.
     pub(crate) async fn rate_limit_allow(
         &self,
         scope: &str,
         key: &str,
         cfg: &RateLimitConfig,
-    ) -> Result<bool, String> {
+        cost: f64,
+    ) -> Result<(bool, f64), String> {
         self.breaker_check().await?;
-        let sec = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map_err(|e| e.to_string())?
-            .as_secs();
-        let window_key = format!("{}:rl:{scope}:{key}:{sec}", self.prefix);
-        let cap = cfg.refill_per_sec.ceil().max(1.0) as i64;
-        let this = self.clone();
+        let bucket_key = format!("{}:rl:{scope}:{key}", self.prefix);
         let result = self
-            .with_retry(move || {
+            .once(async {
+                let mut conn = self
+                    .client
+                    .get_multiplexed_async_connection()
+                    .await
+                    .map_err(|e| e.to_string())?;
+                let (allowed, tokens): (i64, String) = redis::Script::new(TOKEN_BUCKET_SCRIPT)
+                    .key(&bucket_key)
+                    .arg(cfg.capacity)
+                    .arg(cfg.refill_per_sec)
+                    .arg(cost)
+                    .invoke_async(&mut conn)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                let tokens = tokens.parse::<f64>().map_err(|e| e.to_string())?;
+                Ok((allowed == 1, tokens))
+            })
+            .await;
+        match result {
+            Ok(v) => {
+                self.record_success().await;
+                Ok(v)
+            }
+            Err(e) => Err(self
+                .record_failure(&self.metrics.rate_limit_fallbacks, &e)
+                .await),
+        }
+    }
+
+    /// Adds `amount` to a tenant's counter for UTC day `day` and returns the
+    /// new total; `amount == 0` only reads it. Counters expire after two days.
+    /// Reads retry; an add runs once so a lost reply cannot double count.
+    pub(crate) async fn quota_add(
+        &self,
+        tenant: &str,
+        metric: &str,
+        day: u64,
+        amount: u64,
+    ) -> Result<u64, String> {
+        self.breaker_check().await?;
+        let counter_key = format!("{}:quota:{tenant}:{day}:{metric}", self.prefix);
+        let amount = i64::try_from(amount).map_err(|e| e.to_string())?;
+        let result = if amount == 0 {
+            let this = self.clone();
+            self.with_retry(move || {
                 let this = this.clone();
-                let window_key = window_key.clone();
+                let counter_key = counter_key.clone();
                 async move {
                     let mut conn = this
                         .client
                         .get_multiplexed_async_connection()
                         .await
                         .map_err(|e| e.to_string())?;
-                    let count: i64 = conn
-                        .incr(&window_key, 1_i64)
-                        .await
-                        .map_err(|e| e.to_string())?;
-                    let _: bool = conn
-                        .expire(&window_key, 2_i64)
-                        .await
-                        .map_err(|e| e.to_string())?;
-                    Ok(count <= cap)
+                    let used: Option<i64> =
+                        conn.get(&counter_key).await.map_err(|e| e.to_string())?;
+                    Ok(used.unwrap_or(0))
                 }
             })
-            .await;
+            .await
+        } else {
+            self.once(async {
+                let mut conn = self
+                    .client
+                    .get_multiplexed_async_connection()
+                    .await
+                    .map_err(|e| e.to_string())?;
+                redis::Script::new(QUOTA_ADD_SCRIPT)
+                    .key(&counter_key)
+                    .arg(amount)
+                    .arg(QUOTA_COUNTER_TTL_SECS)
+                    .invoke_async::<i64>(&mut conn)
+                    .await
+                    .map_err(|e| e.to_string())
+            })
+            .await
+        };
         match result {
-            Ok(v) => {
+            Ok(used) => {
                 self.record_success().await;
-                Ok(v)
+                Ok(u64::try_from(used).unwrap_or(0))
             }
             Err(e) => Err(self
                 .record_failure(&self.metrics.rate_limit_fallbacks, &e)
@@ -310,3 +406,57 @@ impl RedisBackend {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn live_backend() -> Option<RedisBackend> {
+        let url = std::env::var("REDIS_URL").ok()?;
+        let prefix = format!("atlas-test-{}-{}", std::process::id(), line!());
+        let policy = RedisPolicy {
+            timeout: Duration::from_millis(500),
+            ..RedisPolicy::default()
+        };
+        RedisBackend::new(&url, &prefix, policy).ok()
+    }
+
+    #[tokio::test]
+    #[ignore = "requires REDIS_URL and local Redis; non-CI integration test"]
+    async fn shared_bucket_spends_its_burst_then_refills_at_the_tier_rate() {
+        let Some(redis) = live_backend() else {
+            eprintln!("skipping: REDIS_URL not set");
+            return;
+        };
+        let cfg = RateLimitConfig {
+            capacity: 3.0,
+            refill_per_sec: 0.5,
+        };
+        for left in [2.0, 1.0, 0.0] {
+            let (allowed, tokens) = redis
+                .rate_limit_allow("test", "burst", &cfg, 1.0)
+                .await
+                .expect("charge");
+            assert!(allowed);
+            assert!((tokens - left).abs() < 0.1, "{tokens}");
+        }
+        let (allowed, _) = redis
+            .rate_limit_allow("test", "burst", &cfg, 1.0)
+            .await
+            .expect("charge");
+        assert!(!allowed);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires REDIS_URL and local Redis; non-CI integration test"]
+    async fn quota_adds_accumulate_and_reads_do_not_count() {
+        let Some(redis) = live_backend() else {
+            eprintln!("skipping: REDIS_URL not set");
+            return;
+        };
+        assert_eq!(redis.quota_add("t", "rows", 1, 0).await, Ok(0));
+        assert_eq!(redis.quota_add("t", "rows", 1, 5).await, Ok(5));
+        assert_eq!(redis.quota_add("t", "rows", 1, 2).await, Ok(7));
+        assert_eq!(redis.quota_add("t", "rows", 1, 0).await, Ok(7));
+    }
+}
@@ -0,0 +1,272 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Role bindings the request authorizer evaluates against.
+//!
+//! Without a configured file the packaged `role-assignments.yaml` applies.
+//! With `authz_role_bindings_path` set, that file replaces the packaged
+//! assignments and may add dataset scopes; it is re-read every
+//! `authz_reload_interval_secs`, and a file that fails to parse or names
+//! unknown roles leaves the active bindings in place.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use tracing::{error, info, warn};
+
+use crate::domain::security::authorization::{
+    AuthorizationEngine, AuthorizationPolicy, PermissionCatalog, PermissionEvaluator,
+    RoleAssignmentCatalog, RoleCatalog, RoleRegistry,
+};
+use crate::domain::sha256_hex;
+use crate::packaged::{AUTH_POLICY_YAML, PERMISSIONS_YAML, ROLES_YAML, ROLE_ASSIGNMENTS_YAML};
+use crate::runtime::config::ApiConfig;
+
+pub fn build_authorization_engine(
+    permissions_raw: &str,
+    roles_raw: &str,
+    policy_raw: &str,
+    assignments_raw: &str,
+) -> Result<AuthorizationEngine, String> {
+    let permissions: PermissionCatalog = serde_yaml::from_str(permissions_raw)
+        .map_err(|err| format!("embedded permission catalog: {err}"))?;
+    let roles: RoleCatalog =
+        serde_yaml::from_str(roles_raw).map_err(|err| format!("embedded role catalog: {err}"))?;
+    let policy: AuthorizationPolicy = serde_yaml::from_str(policy_raw)
+        .map_err(|err| format!("embedded authorization policy: {err}"))?;
+    let assignments: RoleAssignmentCatalog =
+        serde_yaml::from_str(assignments_raw).map_err(|err| format!("role bindings: {err}"))?;
+    assignments
+        .validate(&roles)
+        .map_err(|err| format!("role bindings: {err}"))?;
+    let mut registry = RoleRegistry::new();
+    for role in roles.roles {
+        registry.upsert_role(role);
+    }
+    for assignment in &assignments.assignments {
+        registry.bind(assignment.selector()?, &assignment.role_id);
+    }
+    Ok(
+        AuthorizationEngine::new(registry, PermissionEvaluator::new(permissions), policy)
+            .with_dataset_scopes(assignments.dataset_scopes),
+    )
+}
+
+struct ActiveBindings {
+    engine: Arc<AuthorizationEngine>,
+    fingerprint: String,
+}
+
+pub struct RoleBindingStore {
+    path: Option<PathBuf>,
+    active: RwLock<Option<ActiveBindings>>,
+}
+
+impl RoleBindingStore {
+    /// Packaged bindings, or the file at `path` when one is given.
+    pub fn load(path: Option<&Path>) -> Result<Self, String> {
+        let store = Self {
+            path: path.map(Path::to_path_buf),
+            active: RwLock::new(None),
+        };
+        store.reload_if_changed()?;
+        Ok(store)
+    }
+
+    /// The store `AppState` starts with. A binding file that cannot be loaded
+    /// leaves no active bindings, so every protected request is denied until a
+    /// reload succeeds.
+    #[must_use]
+    pub fn from_api_config(api: &ApiConfig) -> Self {
+        let path = api.authz_role_bindings_path.as_deref().map(Path::new);
+        Self::load(path).unwrap_or_else(|err| {
+            error!(
+                event_id = "role_bindings_invalid",
+                error = err.as_str(),
+                "role bindings failed to load; denying requests"
+            );
+            Self {
+                path: path.map(Path::to_path_buf),
+                active: RwLock::new(None),
+            }
+        })
+    }
+
+    #[must_use]
+    pub fn source(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    #[must_use]
+    pub fn engine(&self) -> Option<Arc<AuthorizationEngine>> {
+        self.active
+            .read()
+            .ok()?
+            .as_ref()
+            .map(|active| Arc::clone(&active.engine))
+    }
+
+    #[must_use]
+    pub fn active_fingerprint(&self) -> Option<String> {
+        self.active
+            .read()
+            .ok()?
+            .as_ref()
+            .map(|active| active.fingerprint.clone())
+    }
+
+    /// Re-reads the binding file and swaps it in when its contents changed.
+    pub fn reload_if_changed(&self) -> Result<bool, String> {
+        let assignments_raw = match &self.path {
+            Some(path) => std::fs::read_to_string(path).map_err(|err| {
+                format!("role bindings read failed for {}: {err}", path.display())
+            })?,
+            None => ROLE_ASSIGNMENTS_YAML.to_string(),
+        };
+        let fingerprint = sha256_hex(assignments_raw.as_bytes());
+        if self.active_fingerprint().as_deref() == Some(fingerprint.as_str()) {
+            return Ok(false);
+        }
+        let engine = build_authorization_engine(
+            PERMISSIONS_YAML,
+            ROLES_YAML,
+            AUTH_POLICY_YAML,
+            &assignments_raw,
+        )?;
+        *self
+            .active
+            .write()
+            .map_err(|_| "role bindings lock poisoned".to_string())? = Some(ActiveBindings {
+            engine: Arc::new(engine),
+            fingerprint,
+        });
+        Ok(true)
+    }
+
+    /// Polls the binding file until the store is dropped elsewhere.
+    pub fn spawn_reload_task(self: &Arc<Self>, interval: Duration) {
+        if self.path.is_none() {
+            return;
+        }
+        let store = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let Some(store) = store.upgrade() else {
+                    return;
+                };
+                match store.reload_if_changed() {
+                    Ok(true) => info!(
+                        event_id = "role_bindings_reloaded",
+                        fingerprint = store.active_fingerprint().unwrap_or_default(),
+                        "role bindings reloaded"
+                    ),
+                    Ok(false) => {}
+                    Err(err) => warn!(
+                        event_id = "role_bindings_reload_failed",
+                        error = err.as_str(),
+                        "role bindings reload failed; keeping active bindings"
+                    ),
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_authorization_engine, RoleBindingStore};
+    use crate::domain::security::authorization::AuthorizationDecision;
+    use crate::packaged::{AUTH_POLICY_YAML, PERMISSIONS_YAML, ROLES_YAML, ROLE_ASSIGNMENTS_YAML};
+
+    #[test]
+    fn packaged_bindings_enforce_operator_admin_boundary() {
+        let engine = RoleBindingStore::load(None)
+            .expect("packaged bindings")
+            .engine()
+            .expect("engine");
+        assert_eq!(
+            engine.evaluate(
+                "operator",
+                "ops.admin",
+                "namespace",
+                "/debug/runtime-config"
+            ),
+            AuthorizationDecision::Allow
+        );
+        assert_eq!(
+            engine.evaluate("user", "ops.admin", "namespace", "/debug/runtime-config"),
+            AuthorizationDecision::Deny
+        );
+    }
+
+    #[test]
+    fn invalid_authorization_contracts_fail_closed_without_panicking() {
+        let build = |permissions, roles, policy, assignments| {
+            build_authorization_engine(permissions, roles, policy, assignments)
+                .expect_err("invalid contract")
+        };
+        assert!(build(
+            "permissions: [",
+            ROLES_YAML,
+            AUTH_POLICY_YAML,
+            ROLE_ASSIGNMENTS_YAML
+        )
+        .contains("embedded permission catalog"));
+        assert!(build(
+            PERMISSIONS_YAML,
+            "roles: [",
+            AUTH_POLICY_YAML,
+            ROLE_ASSIGNMENTS_YAML
+        )
+        .contains("embedded role catalog"));
+        assert!(build(
+            PERMISSIONS_YAML,
+            ROLES_YAML,
+            "rules: [",
+            ROLE_ASSIGNMENTS_YAML
+        )
+        .contains("embedded authorization policy"));
+        assert!(build(
+            PERMISSIONS_YAML,
+            ROLES_YAML,
+            AUTH_POLICY_YAML,
+            "schema_version: 1\nassignments:\n  - group: admins\n    role_id: role.root\n"
+        )
+        .contains("unknown role role.root"));
+    }
+
+    #[test]
+    fn binding_file_changes_are_reloaded_and_broken_files_keep_active_bindings() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("role-bindings.yaml");
+        std::fs::write(
+            &path,
+            "schema_version: 1\nassignments:\n  - principal: user\n    role_id: role.user.readonly\n",
+        )
+        .expect("write");
+        let store = RoleBindingStore::load(Some(&path)).expect("load");
+        assert!(!store.reload_if_changed().expect("unchanged"));
+        let first = store.active_fingerprint();
+
+        std::fs::write(&path, "schema_version: 1\nassignments: []\n").expect("rewrite");
+        assert!(store.reload_if_changed().expect("changed"));
+        assert_ne!(store.active_fingerprint(), first);
+        assert_eq!(
+            store.engine().expect("engine").evaluate(
+                "user",
+                "dataset.read",
+                "dataset-id",
+                "/v1/genes"
+            ),
+            AuthorizationDecision::Deny
+        );
+
+        std::fs::write(&path, "assignments: [").expect("break");
+        let active = store.active_fingerprint();
+        assert!(store.reload_if_changed().is_err());
+        assert_eq!(store.active_fingerprint(), active);
+    }
+}
@@ -3,10 +3,11 @@
 #[cfg(feature = "backend-s3")]
 use super::super::catalog::validate_catalog_strict;
 #[cfg(feature = "backend-s3")]
-use super::super::manifest::ManifestLock;
+use super::super::manifest::{verify_manifest_signature, ManifestLock};
 #[cfg(feature = "backend-s3")]
 use super::super::paths::{
-    dataset_key_prefix, dataset_manifest_key, dataset_manifest_lock_key, dataset_sqlite_key,
+    dataset_key_prefix, dataset_manifest_key, dataset_manifest_lock_key,
+    dataset_manifest_signature_key, dataset_sqlite_key, MANIFEST_SIGNATURE_FILE,
 };
 #[cfg(feature = "backend-s3")]
 use crate::app::ports::store::{
@@ -16,6 +17,8 @@ use crate::app::ports::store::{
 #[cfg(feature = "backend-s3")]
 use crate::domain::dataset::{ArtifactManifest, Catalog, DatasetId};
 #[cfg(feature = "backend-s3")]
+use crate::domain::security::data_protection::TrustedSigningKeys;
+#[cfg(feature = "backend-s3")]
 use reqwest::blocking::{Client, Response};
 #[cfg(feature = "backend-s3")]
 use reqwest::header::{ETAG, IF_NONE_MATCH};
@@ -38,6 +41,7 @@ pub struct HttpReadonlyStore {
     pub base_url: String,
     pub cached_only_mode: bool,
     pub cache_root: Option<PathBuf>,
+    trusted_keys: Option<TrustedSigningKeys>,
     client: Client,
     etags: Arc<Mutex<HashMap<String, String>>>,
     catalog_state: Arc<Mutex<CatalogCacheState>>,
@@ -60,6 +64,7 @@ impl HttpReadonlyStore {
             base_url,
             cached_only_mode: false,
             cache_root: None,
+            trusted_keys: None,
             client: Client::builder()
                 .redirect(reqwest::redirect::Policy::none())
                 .build()
@@ -77,6 +82,12 @@ impl HttpReadonlyStore {
         self
     }
 
+    #[must_use]
+    pub fn with_trusted_keys(mut self, keys: TrustedSigningKeys) -> Self {
+        self.trusted_keys = Some(keys);
+        self
+    }
+
     #[must_use]
     pub fn with_instrumentation(mut self, instrumentation: Arc<dyn StoreInstrumentation>) -> Self {
         self.instrumentation = instrumentation;
@@ -224,6 +235,18 @@ impl ArtifactStore for HttpReadonlyStore {
         manifest
             .validate_strict()
             .map_err(|e| StoreError::new(StoreErrorCode::Validation, e.to_string()))?;
+        if let Some(trusted) = &self.trusted_keys {
+            let signature = match self.fetch_bytes(
+                &dataset_manifest_signature_key(dataset),
+                &self.url_for(dataset, MANIFEST_SIGNATURE_FILE),
+            ) {
+                Ok(bytes) => Some(bytes),
+                Err(err) if err.code == StoreErrorCode::NotFound => None,
+                Err(err) => return Err(err),
+            };
+            verify_manifest_signature(trusted, &manifest, &lock, signature.as_deref())
+                .map_err(|e| StoreError::new(StoreErrorCode::Validation, e))?;
+        }
         Ok(manifest)
     }
 
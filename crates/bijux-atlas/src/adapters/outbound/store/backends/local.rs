@@ -1,16 +1,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::super::catalog::validate_catalog_strict;
-use super::super::manifest::ManifestLock;
+use super::super::manifest::{sign_manifest_bytes, verify_manifest_signature, ManifestLock};
 use super::super::paths::{
     dataset_artifact_paths, immutability_marker_path, lifecycle_state_path,
-    lifecycle_transitions_path, manifest_lock_path, publish_lock_path, CATALOG_FILE,
+    lifecycle_transitions_path, manifest_lock_path, manifest_signature_path, publish_lock_path,
+    CATALOG_FILE,
 };
 use crate::app::ports::store::{
     ArtifactStore, NoopInstrumentation, PublishLockGuard, StoreError, StoreErrorCode,
     StoreInstrumentation,
 };
 use crate::domain::dataset::{ArtifactManifest, Catalog, DatasetId};
+use crate::domain::security::data_protection::{ArtifactSigningKey, TrustedSigningKeys};
 use std::fs::{self, OpenOptions};
 use std::io::Write;
 use std::path::{Path, PathBuf};
@@ -19,6 +21,8 @@ use std::time::Instant;
 
 pub struct LocalFsStore {
     pub root: PathBuf,
+    signing_key: Option<Arc<ArtifactSigningKey>>,
+    trusted_keys: Option<TrustedSigningKeys>,
     instrumentation: Arc<dyn StoreInstrumentation>,
 }
 
@@ -27,10 +31,26 @@ impl LocalFsStore {
     pub fn new(root: PathBuf) -> Self {
         Self {
             root,
+            signing_key: None,
+            trusted_keys: None,
             instrumentation: Arc::new(NoopInstrumentation),
         }
     }
 
+    /// Publishes a detached `manifest.sig` with every dataset.
+    #[must_use]
+    pub fn with_signing_key(mut self, key: Arc<ArtifactSigningKey>) -> Self {
+        self.signing_key = Some(key);
+        self
+    }
+
+    /// Rejects manifests that are unsigned or not signed by one of `keys`.
+    #[must_use]
+    pub fn with_trusted_keys(mut self, keys: TrustedSigningKeys) -> Self {
+        self.trusted_keys = Some(keys);
+        self
+    }
+
     #[must_use]
     pub fn with_instrumentation(mut self, instrumentation: Arc<dyn StoreInstrumentation>) -> Self {
         self.instrumentation = instrumentation;
@@ -77,6 +97,15 @@ impl ArtifactStore for LocalFsStore {
         manifest
             .validate_strict()
             .map_err(|e| StoreError::new(StoreErrorCode::Validation, e.to_string()))?;
+        if let Some(trusted) = &self.trusted_keys {
+            let signature = match fs::read(manifest_signature_path(&self.root, dataset)) {
+                Ok(bytes) => Some(bytes),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+                Err(e) => return Err(StoreError::new(StoreErrorCode::Io, e.to_string())),
+            };
+            verify_manifest_signature(trusted, &manifest, &lock, signature.as_deref())
+                .map_err(|e| StoreError::new(StoreErrorCode::Validation, e))?;
+        }
         Ok(manifest)
     }
 
@@ -116,6 +145,12 @@ impl ArtifactStore for LocalFsStore {
         let lock_bytes = serde_json::to_vec(&lock)
             .map_err(|e| StoreError::new(StoreErrorCode::Internal, e.to_string()))?;
         write_and_sync(&lock_tmp, &lock_bytes)?;
+        let signature_tmp = paths.derived_dir.join("manifest.sig.tmp");
+        if let Some(key) = &self.signing_key {
+            let signature_bytes = sign_manifest_bytes(key, manifest_bytes, &lock)
+                .map_err(|e| StoreError::new(StoreErrorCode::Validation, e))?;
+            write_and_sync(&signature_tmp, &signature_bytes)?;
+        }
 
         fs::rename(&manifest_tmp, &paths.manifest)
             .map_err(|e| StoreError::new(StoreErrorCode::Io, e.to_string()))?;
@@ -126,6 +161,13 @@ impl ArtifactStore for LocalFsStore {
             manifest_lock_path(Path::new(&self.root), dataset),
         )
         .map_err(|e| StoreError::new(StoreErrorCode::Io, e.to_string()))?;
+        if self.signing_key.is_some() {
+            fs::rename(
+                &signature_tmp,
+                manifest_signature_path(Path::new(&self.root), dataset),
+            )
+            .map_err(|e| StoreError::new(StoreErrorCode::Io, e.to_string()))?;
+        }
         let marker_path = immutability_marker_path(Path::new(&self.root), dataset);
         let marker_tmp = paths.derived_dir.join("immutable.release.json.tmp");
         let marker = serde_json::json!({
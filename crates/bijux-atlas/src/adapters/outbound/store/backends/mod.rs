@@ -5,3 +5,7 @@ pub mod http;
 pub mod local;
 #[cfg(feature = "backend-s3")]
 pub mod s3;
+#[cfg(feature = "backend-s3")]
+pub mod s3_lock;
+#[cfg(feature = "backend-s3")]
+pub mod s3_multipart;
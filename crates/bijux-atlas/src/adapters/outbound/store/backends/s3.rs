@@ -3,15 +3,26 @@
 #[cfg(feature = "backend-s3")]
 use super::super::catalog::validate_catalog_strict;
 #[cfg(feature = "backend-s3")]
-use super::super::manifest::{verify_expected_sha256, ManifestLock};
+use super::super::manifest::{
+    sign_manifest_bytes, verify_expected_sha256, verify_manifest_signature, ManifestLock,
+};
 #[cfg(feature = "backend-s3")]
 use super::super::paths::{
-    dataset_key_prefix, dataset_manifest_key, dataset_manifest_lock_key, dataset_sqlite_key,
-    CATALOG_FILE,
+    dataset_key_prefix, dataset_manifest_key, dataset_manifest_lock_key,
+    dataset_manifest_signature_key, dataset_publish_lock_key, dataset_sqlite_key, CATALOG_FILE,
 };
 #[cfg(feature = "backend-s3")]
 use super::super::retry::{BackoffPolicy, RetryPolicy};
 #[cfg(feature = "backend-s3")]
+use super::super::sigv4::{AddressingStyle, SigV4Signer};
+#[cfg(feature = "backend-s3")]
+use super::s3_lock::{new_lease_token, LeaseWrite, PublishLease, PublishLockConfig};
+#[cfg(feature = "backend-s3")]
+use super::s3_multipart::{
+    part_checksum, sha256_file_hex, xml_tag, CompletedPart, MultipartConfig, MultipartUploadState,
+    PartSource,
+};
+#[cfg(feature = "backend-s3")]
 use crate::app::ports::store::{
     ArtifactStore, NoopInstrumentation, PublishLockGuard, StoreError, StoreErrorCode,
     StoreInstrumentation,
@@ -19,27 +30,40 @@ use crate::app::ports::store::{
 #[cfg(feature = "backend-s3")]
 use crate::domain::dataset::{ArtifactManifest, Catalog, DatasetId};
 #[cfg(feature = "backend-s3")]
-use reqwest::blocking::Client;
+use crate::domain::security::data_protection::{ArtifactSigningKey, TrustedSigningKeys};
+#[cfg(feature = "backend-s3")]
+use crate::domain::sha256_hex;
+#[cfg(feature = "backend-s3")]
+use reqwest::blocking::{Client, RequestBuilder, Response};
+#[cfg(feature = "backend-s3")]
+use reqwest::Url;
 #[cfg(feature = "backend-s3")]
 use std::fs;
 #[cfg(feature = "backend-s3")]
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 #[cfg(feature = "backend-s3")]
-use std::sync::Arc;
+use std::sync::{mpsc, Arc, Mutex, PoisonError};
 #[cfg(feature = "backend-s3")]
 use std::thread;
 #[cfg(feature = "backend-s3")]
-use std::time::Instant;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
 #[cfg(feature = "backend-s3")]
+#[derive(Clone)]
 pub struct S3LikeStore {
     pub endpoint: String,
     pub presigned_endpoint: Option<String>,
     pub bucket: String,
     pub bearer_token: Option<String>,
+    pub addressing: AddressingStyle,
     pub retry: RetryPolicy,
     pub cached_only_mode: bool,
     pub cache_root: Option<PathBuf>,
+    pub multipart: MultipartConfig,
+    pub publish_lock: PublishLockConfig,
+    signing_key: Option<Arc<ArtifactSigningKey>>,
+    trusted_keys: Option<TrustedSigningKeys>,
+    sigv4: Option<SigV4Signer>,
     client: Client,
     instrumentation: Arc<dyn StoreInstrumentation>,
 }
@@ -53,20 +77,51 @@ impl S3LikeStore {
             presigned_endpoint: None,
             bucket,
             bearer_token: None,
+            addressing: AddressingStyle::Path,
             retry: RetryPolicy::default(),
             cached_only_mode: false,
             cache_root: None,
+            multipart: MultipartConfig::default(),
+            publish_lock: PublishLockConfig::default(),
+            signing_key: None,
+            trusted_keys: None,
+            sigv4: None,
             client: Client::new(),
             instrumentation: Arc::new(NoopInstrumentation),
         }
     }
 
+    #[must_use]
+    pub fn with_signing_key(mut self, key: Arc<ArtifactSigningKey>) -> Self {
+        self.signing_key = Some(key);
+        self
+    }
+
+    #[must_use]
+    pub fn with_trusted_keys(mut self, keys: TrustedSigningKeys) -> Self {
+        self.trusted_keys = Some(keys);
+        self
+    }
+
     #[must_use]
     pub fn with_bearer_token(mut self, token: Option<String>) -> Self {
         self.bearer_token = token;
         self
     }
 
+    /// Signs every request with SigV4; takes precedence over the bearer token.
+    #[must_use]
+    pub fn with_sigv4(mut self, signer: Option<SigV4Signer>) -> Self {
+        self.sigv4 = signer;
+        self
+    }
+
+    #[must_use]
+    pub fn with_addressing_style(mut self, addressing: AddressingStyle) -> Self {
+        self.addressing = addressing;
+        self
+    }
+
     #[must_use]
     pub fn with_presigned_endpoint(mut self, endpoint: Option<String>) -> Self {
         self.presigned_endpoint = endpoint
@@ -88,6 +143,18 @@ impl S3LikeStore {
         self
     }
 
+    #[must_use]
+    pub fn with_multipart(mut self, multipart: MultipartConfig) -> Self {
+        self.multipart = multipart;
+        self
+    }
+
+    #[must_use]
+    pub fn with_publish_lock(mut self, publish_lock: PublishLockConfig) -> Self {
+        self.publish_lock = publish_lock;
+        self
+    }
+
     #[must_use]
     pub fn with_instrumentation(mut self, instrumentation: Arc<dyn StoreInstrumentation>) -> Self {
         self.instrumentation = instrumentation;
@@ -95,21 +162,52 @@ impl S3LikeStore {
     }
 
     fn object_url(&self, key: &str) -> String {
-        let base = self.presigned_endpoint.as_deref().unwrap_or(&self.endpoint);
+        let key = key.trim_start_matches('/');
+        if let Some(base) = self.presigned_endpoint.as_deref() {
+            return format!("{}/{}/{key}", base.trim_end_matches('/'), self.bucket);
+        }
         format!(
-            "{}/{}/{}",
-            base.trim_end_matches('/'),
-            self.bucket,
-            key.trim_start_matches('/')
+            "{}/{key}",
+            self.addressing.bucket_url(&self.endpoint, &self.bucket)
         )
     }
 
-    fn get_with_retry(&self, key: &str) -> Result<Vec<u8>, StoreError> {
+    /// Attaches SigV4 headers when configured, else the bearer token.
+    /// Presigned URLs already carry their signature in the query string.
+    fn authorize(
+        &self,
+        request: RequestBuilder,
+        method: &str,
+        url: &str,
+        headers: &[(&str, &str)],
+        payload: &[u8],
+    ) -> Result<RequestBuilder, StoreError> {
+        if let Some(signer) = self
+            .sigv4
+            .as_ref()
+            .filter(|_| self.presigned_endpoint.is_none())
+        {
+            let parsed = Url::parse(url)
+                .map_err(|e| StoreError::new(StoreErrorCode::Validation, e.to_string()))?;
+            let signed = signer
+                .sign_now(method, &parsed, headers, &sha256_hex(payload))
+                .map_err(|e| StoreError::new(StoreErrorCode::Validation, e))?;
+            return Ok(signed.into_iter().fold(request, |request, (name, value)| {
+                request.header(name, value)
+            }));
+        }
+        Ok(match &self.bearer_token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        })
+    }
+
+    /// Cached copy of `key`, or an error when cached-only mode forbids going to the network.
+    fn cached_object(&self, key: &str) -> Result<Option<PathBuf>, StoreError> {
         if let Some(cache_root) = &self.cache_root {
             let cached = cache_root.join(key.replace('/', "__"));
             if cached.exists() {
-                return fs::read(&cached)
-                    .map_err(|e| StoreError::new(StoreErrorCode::Io, e.to_string()));
+                return Ok(Some(cached));
             }
             if self.cached_only_mode {
                 return Err(StoreError::new(
@@ -123,19 +221,28 @@ impl S3LikeStore {
                 "cached-only mode enabled without cache root",
             ));
         }
+        Ok(None)
+    }
+
+    fn get_with_retry(&self, key: &str) -> Result<Vec<u8>, StoreError> {
+        if let Some(cached) = self.cached_object(key)? {
+            return fs::read(&cached)
+                .map_err(|e| StoreError::new(StoreErrorCode::Io, e.to_string()));
+        }
 
         let mut attempt = 0usize;
         let mut buffer: Vec<u8> = Vec::new();
         loop {
             let started = Instant::now();
-            let mut request = self.client.get(self.object_url(key));
+            let url = self.object_url(key);
+            let mut request = self.client.get(&url);
+            let range = format!("bytes={}-", buffer.len());
+            let mut signed_headers = Vec::new();
             if !buffer.is_empty() {
-                request =
-                    request.header(reqwest::header::RANGE, format!("bytes={}-", buffer.len()));
-            }
-            if let Some(token) = &self.bearer_token {
-                request = request.bearer_auth(token);
+                request = request.header(reqwest::header::RANGE, &range);
+                signed_headers.push(("range", range.as_str()));
             }
+            let request = self.authorize(request, "GET", &url, &signed_headers, &[])?;
             match request.send() {
                 Ok(response) => {
                     if response.status().is_success() || response.status().as_u16() == 206 {
@@ -187,6 +294,12 @@ impl S3LikeStore {
                             "object not found",
                         ));
                     }
+                    if matches!(response.status().as_u16(), 401 | 403) {
+                        return Err(StoreError::new(
+                            StoreErrorCode::Network,
+                            format!("s3-like get rejected: {}", response.status()),
+                        ));
+                    }
                 }
                 Err(err) => {
                     self.instrumentation
@@ -201,13 +314,520 @@ impl S3LikeStore {
         }
     }
 
-    fn put_bytes(&self, key: &str, bytes: &[u8]) -> Result<(), StoreError> {
+    /// Streams `key` into `dest` with ranged GETs of `multipart.part_size` bytes.
+    ///
+    /// With a cache configured the object lands in the cache first and is copied
+    /// out, so a partial cache file from an earlier attempt is resumed.
+    fn download_to_path(&self, key: &str, dest: &Path) -> Result<u64, StoreError> {
+        let io = |e: std::io::Error| StoreError::new(StoreErrorCode::Io, e.to_string());
+        if let Some(cached) = self.cached_object(key)? {
+            return fs::copy(&cached, dest).map_err(io);
+        }
+        let Some(cache_root) = &self.cache_root else {
+            return self.stream_object_to(key, dest);
+        };
+        fs::create_dir_all(cache_root).map_err(io)?;
+        let cached = cache_root.join(key.replace('/', "__"));
+        let partial = cache_root.join(format!("{}.partial", key.replace('/', "__")));
+        self.stream_object_to(key, &partial)?;
+        fs::rename(&partial, &cached).map_err(io)?;
+        fs::copy(&cached, dest).map_err(io)
+    }
+
+    fn stream_object_to(&self, key: &str, target: &Path) -> Result<u64, StoreError> {
+        let io = |e: std::io::Error| StoreError::new(StoreErrorCode::Io, e.to_string());
         let started = Instant::now();
-        let mut request = self.client.put(self.object_url(key)).body(bytes.to_vec());
-        if let Some(token) = &self.bearer_token {
-            request = request.bearer_auth(token);
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(target)
+            .map_err(io)?;
+        let mut offset = file.metadata().map_err(io)?.len();
+        let mut total: Option<u64> = None;
+        let mut attempt = 0usize;
+        let part_size = self.multipart.part_size.max(1);
+        let url = self.object_url(key);
+        while total.is_none_or(|total| offset < total) {
+            let range = format!("bytes={offset}-{}", offset + part_size - 1);
+            let request = self.client.get(&url).header(reqwest::header::RANGE, &range);
+            let request =
+                self.authorize(request, "GET", &url, &[("range", range.as_str())], &[])?;
+            let failure = match request.send() {
+                Ok(mut response) => match response.status().as_u16() {
+                    206 => {
+                        total = content_range_total(&response).or(total);
+                        let copied = response.copy_to(&mut file);
+                        let written = file.metadata().map_err(io)?.len() - offset;
+                        offset += written;
+                        match copied {
+                            Ok(_) if written > 0 => {
+                                attempt = 0;
+                                if total.is_none() {
+                                    total = Some(offset).filter(|_| written < part_size);
+                                }
+                                continue;
+                            }
+                            Ok(_) => "empty ranged response".to_string(),
+                            Err(e) => e.to_string(),
+                        }
+                    }
+                    200 => {
+                        // Range ignored: the body is the whole object.
+                        file.set_len(0).map_err(io)?;
+                        match response.copy_to(&mut file) {
+                            Ok(n) => {
+                                offset = n;
+                                total = Some(n);
+                                continue;
+                            }
+                            Err(e) => e.to_string(),
+                        }
+                    }
+                    416 if offset > 0 => {
+                        match content_range_total(&response) {
+                            // A stale partial that overshoots the object: start over.
+                            Some(size) if size != offset => {
+                                file.set_len(0).map_err(io)?;
+                                offset = 0;
+                            }
+                            _ => total = Some(offset),
+                        }
+                        continue;
+                    }
+                    404 => {
+                        return Err(StoreError::new(
+                            StoreErrorCode::NotFound,
+                            "object not found",
+                        ))
+                    }
+                    401 | 403 => {
+                        return Err(StoreError::new(
+                            StoreErrorCode::Network,
+                            format!("s3-like get rejected: {}", response.status()),
+                        ))
+                    }
+                    status => format!("s3-like ranged get failed: {status}"),
+                },
+                Err(err) => {
+                    self.instrumentation
+                        .observe_error("s3like", StoreErrorCode::Network);
+                    err.to_string()
+                }
+            };
+            attempt += 1;
+            if attempt >= self.retry.max_attempts {
+                return Err(StoreError::new(StoreErrorCode::Network, failure));
+            }
+            thread::sleep(self.retry.delay_for_attempt(attempt));
+        }
+        file.sync_all().map_err(io)?;
+        self.instrumentation
+            .observe_download("s3like", offset as usize, started.elapsed());
+        Ok(offset)
+    }
+
+    /// Sends a request, retrying transport errors and 5xx responses.
+    fn send_with_retry(
+        &self,
+        method: &str,
+        url: &str,
+        headers: &[(&str, &str)],
+        body: &[u8],
+    ) -> Result<Response, StoreError> {
+        let method_value = reqwest::Method::from_bytes(method.as_bytes())
+            .map_err(|e| StoreError::new(StoreErrorCode::Internal, e.to_string()))?;
+        let mut attempt = 0usize;
+        loop {
+            let request = headers.iter().fold(
+                self.client
+                    .request(method_value.clone(), url)
+                    .body(body.to_vec()),
+                |request, (name, value)| request.header(*name, *value),
+            );
+            let failure = match self.authorize(request, method, url, headers, body)?.send() {
+                Ok(response) if !response.status().is_server_error() => return Ok(response),
+                Ok(response) => format!("s3-like {method} failed: {}", response.status()),
+                Err(err) => {
+                    self.instrumentation
+                        .observe_error("s3like", StoreErrorCode::Network);
+                    err.to_string()
+                }
+            };
+            attempt += 1;
+            if attempt >= self.retry.max_attempts {
+                return Err(StoreError::new(StoreErrorCode::Network, failure));
+            }
+            thread::sleep(self.retry.delay_for_attempt(attempt));
+        }
+    }
+
+    fn multipart_url(&self, key: &str, query: &[(&str, &str)]) -> Result<String, StoreError> {
+        let mut url = Url::parse(&self.object_url(key))
+            .map_err(|e| StoreError::new(StoreErrorCode::Validation, e.to_string()))?;
+        if query.is_empty() {
+            url.set_query(Some("uploads"));
+        } else {
+            url.query_pairs_mut().extend_pairs(query);
+        }
+        Ok(url.to_string())
+    }
+
+    /// Uploads a source whose SHA-256 is already known, in parts when above the threshold.
+    fn put_source(
+        &self,
+        key: &str,
+        source: &PartSource<'_>,
+        source_sha256: &str,
+    ) -> Result<(), StoreError> {
+        let io = |e: std::io::Error| StoreError::new(StoreErrorCode::Io, e.to_string());
+        let size = source.len().map_err(io)?;
+        if size <= self.multipart.threshold {
+            return self.put_bytes(key, &source.read_range(0, size).map_err(io)?);
+        }
+        self.put_multipart(key, source, size, source_sha256)
+    }
+
+    /// Uploads `source` in parts, resuming a matching upload recorded in `state_dir`.
+    fn put_multipart(
+        &self,
+        key: &str,
+        source: &PartSource<'_>,
+        size: u64,
+        source_sha256: &str,
+    ) -> Result<(), StoreError> {
+        let io = |e: std::io::Error| StoreError::new(StoreErrorCode::Io, e.to_string());
+        let state_dir = self.multipart.state_dir.as_deref();
+        let part_size = self.multipart.part_size_for(size);
+        let saved = state_dir
+            .and_then(|dir| MultipartUploadState::load(dir, key))
+            .filter(|state| state.resumes(source_sha256, size, part_size));
+        let mut state = match saved {
+            Some(state) => state,
+            None => MultipartUploadState::new(
+                key,
+                self.initiate_multipart(key)?,
+                source_sha256,
+                size,
+                part_size,
+            ),
+        };
+        if let Some(dir) = state_dir {
+            state.persist(dir).map_err(io)?;
+        }
+        for part_number in 1..=state.part_count() {
+            if state.has_part(part_number) {
+                continue;
+            }
+            let offset = (part_number - 1) * part_size;
+            let bytes = source
+                .read_range(offset, part_size.min(size - offset))
+                .map_err(io)?;
+            match self.upload_part(key, &state.upload_id, part_number, &bytes) {
+                Ok(part) => state.parts.push(part),
+                Err(err) if err.code == StoreErrorCode::NotFound => {
+                    if let Some(dir) = state_dir {
+                        MultipartUploadState::discard(dir, key);
+                    }
+                    return Err(StoreError::new(
+                        StoreErrorCode::Conflict,
+                        format!(
+                            "multipart upload {} expired; retry publish",
+                            state.upload_id
+                        ),
+                    ));
+                }
+                Err(err) => return Err(err),
+            }
+            if let Some(dir) = state_dir {
+                state.persist(dir).map_err(io)?;
+            }
+        }
+        self.complete_multipart(key, &state)?;
+        if let Some(dir) = state_dir {
+            MultipartUploadState::discard(dir, key);
+        }
+        Ok(())
+    }
+
+    /// Declares SHA-256 up front; without it the part checksums sent on
+    /// completion are not checked against the assembled object.
+    fn initiate_multipart(&self, key: &str) -> Result<String, StoreError> {
+        let url = self.multipart_url(key, &[])?;
+        let headers = [("x-amz-checksum-algorithm", "SHA256")];
+        let response = self.send_with_retry("POST", &url, &headers, &[])?;
+        let status = response.status();
+        let body = response
+            .text()
+            .map_err(|e| StoreError::new(StoreErrorCode::Network, e.to_string()))?;
+        if !status.is_success() {
+            return Err(StoreError::new(
+                StoreErrorCode::Network,
+                format!("s3-like multipart initiate failed: {status}"),
+            ));
+        }
+        xml_tag(&body, "UploadId")
+            .filter(|id| !id.is_empty())
+            .map(str::to_string)
+            .ok_or_else(|| {
+                StoreError::new(
+                    StoreErrorCode::Validation,
+                    "multipart initiate response is missing UploadId",
+                )
+            })
+    }
+
+    fn upload_part(
+        &self,
+        key: &str,
+        upload_id: &str,
+        part_number: u64,
+        bytes: &[u8],
+    ) -> Result<CompletedPart, StoreError> {
+        let started = Instant::now();
+        let part = part_number.to_string();
+        let url = self.multipart_url(key, &[("partNumber", &part), ("uploadId", upload_id)])?;
+        let checksum = part_checksum(bytes);
+        let headers = [("x-amz-checksum-sha256", checksum.as_str())];
+        let response = self.send_with_retry("PUT", &url, &headers, bytes)?;
+        if response.status().as_u16() == 404 {
+            return Err(StoreError::new(
+                StoreErrorCode::NotFound,
+                "multipart upload not found",
+            ));
+        }
+        if !response.status().is_success() {
+            return Err(StoreError::new(
+                StoreErrorCode::Network,
+                format!("s3-like part {part_number} failed: {}", response.status()),
+            ));
+        }
+        let etag = response
+            .headers()
+            .get(reqwest::header::ETAG)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+            .ok_or_else(|| {
+                StoreError::new(
+                    StoreErrorCode::Validation,
+                    format!("s3-like part {part_number} response is missing ETag"),
+                )
+            })?;
+        self.instrumentation
+            .observe_upload("s3like", bytes.len(), started.elapsed());
+        Ok(CompletedPart {
+            part_number,
+            etag,
+            checksum_sha256: checksum,
+        })
+    }
+
+    fn complete_multipart(
+        &self,
+        key: &str,
+        state: &MultipartUploadState,
+    ) -> Result<(), StoreError> {
+        let url = self.multipart_url(key, &[("uploadId", &state.upload_id)])?;
+        let body = state.complete_body();
+        let response = self.send_with_retry("POST", &url, &[], body.as_bytes())?;
+        let status = response.status();
+        let text = response
+            .text()
+            .map_err(|e| StoreError::new(StoreErrorCode::Network, e.to_string()))?;
+        // S3 can report a failed completion inside a 200 body.
+        if !status.is_success() || text.contains("<Error>") {
+            let code = xml_tag(&text, "Code").unwrap_or_default();
+            return Err(StoreError::new(
+                StoreErrorCode::Network,
+                format!("s3-like multipart complete failed: {status} {code}"),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Writes the lease object under `condition` (`If-None-Match: *` or `If-Match: <etag>`).
+    fn write_lease(
+        &self,
+        key: &str,
+        lease: &PublishLease,
+        condition: (&str, &str),
+    ) -> Result<LeaseWrite, StoreError> {
+        let body = serde_json::to_vec(lease)
+            .map_err(|e| StoreError::new(StoreErrorCode::Internal, e.to_string()))?;
+        let url = self.object_url(key);
+        let response = self.send_with_retry("PUT", &url, &[condition], &body)?;
+        match response.status().as_u16() {
+            // 409 is returned for a conditional write racing another one.
+            409 | 412 => Ok(LeaseWrite::Lost),
+            _ if response.status().is_success() => Ok(LeaseWrite::Written(
+                response
+                    .headers()
+                    .get(reqwest::header::ETAG)
+                    .and_then(|v| v.to_str().ok())
+                    .map(str::to_string),
+            )),
+            _ => Err(StoreError::new(
+                StoreErrorCode::Network,
+                format!("s3-like publish lock write failed: {}", response.status()),
+            )),
+        }
+    }
+
+    /// Like [`Self::write_lease`], but a lost precondition is checked against
+    /// the stored lease: when a retried PUT finds our token under a new ETag,
+    /// the first attempt landed and only its reply was lost.
+    fn write_lease_confirmed(
+        &self,
+        key: &str,
+        lease: &PublishLease,
+        condition: (&str, &str),
+    ) -> Result<LeaseWrite, StoreError> {
+        match self.write_lease(key, lease, condition)? {
+            LeaseWrite::Lost => match self.read_lease(key)? {
+                Some((current, etag)) if current.same_acquisition(lease) && etag != condition.1 => {
+                    Ok(LeaseWrite::Written(Some(etag)))
+                }
+                _ => Ok(LeaseWrite::Lost),
+            },
+            written => Ok(written),
+        }
+    }
+
+    /// Extends the held lease with `If-Match` on its ETag; `Ok(false)` once
+    /// another publisher owns the lease.
+    fn renew_publish_lock(&self, key: &str, held: &mut HeldLease) -> Result<bool, StoreError> {
+        let etag = match held.etag.clone() {
+            Some(etag) => etag,
+            None => match self.read_lease(key)? {
+                Some((current, etag)) if current.same_acquisition(&held.lease) => etag,
+                _ => return Ok(false),
+            },
+        };
+        let now = unix_now()?;
+        let renewed = held.lease.renewed(self.publish_lock.ttl, now);
+        match self.write_lease_confirmed(key, &renewed, ("if-match", etag.as_str()))? {
+            LeaseWrite::Written(etag) => {
+                held.lease = renewed;
+                held.etag = etag;
+                Ok(true)
+            }
+            LeaseWrite::Lost => Ok(false),
+        }
+    }
+
+    /// Renews the lease every [`PublishLockConfig::renew_interval`] until
+    /// `released` fires or the lease is lost.
+    fn renew_until_released(
+        &self,
+        key: &str,
+        held: &Mutex<HeldLease>,
+        released: &mpsc::Receiver<()>,
+    ) {
+        let Some(every) = self.publish_lock.renew_interval() else {
+            return;
+        };
+        while let Err(mpsc::RecvTimeoutError::Timeout) = released.recv_timeout(every) {
+            let mut held = held.lock().unwrap_or_else(PoisonError::into_inner);
+            match self.renew_publish_lock(key, &mut held) {
+                Ok(true) => {}
+                Ok(false) => {
+                    held.lost = true;
+                    tracing::warn!(
+                        event_id = "store_publish_lock_lost",
+                        backend = "s3like",
+                        key,
+                        "publish lock lease was taken over before the publish finished"
+                    );
+                    return;
+                }
+                Err(err) => tracing::warn!(
+                    event_id = "store_publish_lock_renew_failed",
+                    backend = "s3like",
+                    key,
+                    "publish lock renewal failed: {err}"
+                ),
+            }
+        }
+    }
+
+    /// Current lease and its ETag, or `None` when no lease object exists.
+    fn read_lease(&self, key: &str) -> Result<Option<(PublishLease, String)>, StoreError> {
+        let url = self.object_url(key);
+        let response = self.send_with_retry("GET", &url, &[], &[])?;
+        if response.status().as_u16() == 404 {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            return Err(StoreError::new(
+                StoreErrorCode::Network,
+                format!("s3-like publish lock read failed: {}", response.status()),
+            ));
         }
-        let response = request
+        let etag = response
+            .headers()
+            .get(reqwest::header::ETAG)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+            .ok_or_else(|| {
+                StoreError::new(
+                    StoreErrorCode::Validation,
+                    "publish lock response is missing ETag",
+                )
+            })?;
+        let body = response
+            .bytes()
+            .map_err(|e| StoreError::new(StoreErrorCode::Network, e.to_string()))?;
+        let lease = serde_json::from_slice(&body).map_err(|e| {
+            StoreError::new(
+                StoreErrorCode::Validation,
+                format!("publish lock object is malformed: {e}"),
+            )
+        })?;
+        Ok(Some((lease, etag)))
+    }
+
+    /// Deletes the lease only if it is still the one we wrote. Without an
+    /// ETag from the last write, the stored lease's token is checked first.
+    fn release_publish_lock(&self, key: &str, held: &HeldLease) {
+        if held.lost {
+            return;
+        }
+        let etag = match &held.etag {
+            Some(etag) => etag.clone(),
+            None => match self.read_lease(key) {
+                Ok(Some((current, etag))) if current.same_acquisition(&held.lease) => etag,
+                Ok(_) => return,
+                Err(err) => {
+                    tracing::warn!(
+                        event_id = "store_publish_lock_release_failed",
+                        backend = "s3like",
+                        key,
+                        "publish lock release skipped: {err}"
+                    );
+                    return;
+                }
+            },
+        };
+        let url = self.object_url(key);
+        if let Err(err) = self.send_with_retry("DELETE", &url, &[("if-match", &etag)], &[]) {
+            tracing::warn!(
+                event_id = "store_publish_lock_release_failed",
+                backend = "s3like",
+                key,
+                "publish lock release failed: {err}"
+            );
+        }
+    }
+
+    fn put_bytes(&self, key: &str, bytes: &[u8]) -> Result<(), StoreError> {
+        if bytes.len() as u64 > self.multipart.threshold {
+            let sha256 = sha256_hex(bytes);
+            return self.put_multipart(key, &PartSource::Bytes(bytes), bytes.len() as u64, &sha256);
+        }
+        let started = Instant::now();
+        let url = self.object_url(key);
+        let request = self.client.put(&url).body(bytes.to_vec());
+        let response = self
+            .authorize(request, "PUT", &url, &[], bytes)?
             .send()
             .map_err(|e| StoreError::new(StoreErrorCode::Network, e.to_string()))?;
         if !response.status().is_success() {
@@ -222,6 +842,77 @@ impl S3LikeStore {
     }
 }
 
+#[cfg(feature = "backend-s3")]
+fn content_range_total(response: &Response) -> Option<u64> {
+    response
+        .headers()
+        .get("content-range")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split('/').nth(1))
+        .and_then(|v| v.parse::<u64>().ok())
+}
+
+#[cfg(feature = "backend-s3")]
+impl S3LikeStore {
+    /// Writes the dataset objects, checking before each one that `guard`
+    /// still holds the lease so a publisher that lost it stops writing.
+    fn publish_objects(
+        &self,
+        guard: &PublishLockGuard,
+        dataset: &DatasetId,
+        manifest_bytes: &[u8],
+        sqlite: &PartSource<'_>,
+        sqlite_sha256: &str,
+    ) -> Result<(), StoreError> {
+        let held = || {
+            if guard.is_held() {
+                Ok(())
+            } else {
+                Err(StoreError::new(
+                    StoreErrorCode::Conflict,
+                    format!(
+                        "publish lock for {} was lost before the publish finished",
+                        dataset.canonical_string()
+                    ),
+                ))
+            }
+        };
+        let prefix = dataset_key_prefix(dataset);
+        held()?;
+        self.put_bytes(&format!("{prefix}/manifest.json.tmp"), manifest_bytes)?;
+        self.put_source(
+            &format!("{prefix}/gene_summary.sqlite.tmp"),
+            sqlite,
+            sqlite_sha256,
+        )?;
+
+        let lock = ManifestLock {
+            manifest_sha256: sha256_hex(manifest_bytes),
+            sqlite_sha256: sqlite_sha256.to_string(),
+        };
+        let lock_json = serde_json::to_vec(&lock)
+            .map_err(|e| StoreError::new(StoreErrorCode::Internal, e.to_string()))?;
+        held()?;
+        self.put_bytes(&format!("{prefix}/manifest.lock"), &lock_json)?;
+        if let Some(key) = &self.signing_key {
+            let signature = sign_manifest_bytes(key, manifest_bytes, &lock)
+                .map_err(|e| StoreError::new(StoreErrorCode::Validation, e))?;
+            held()?;
+            self.put_bytes(&dataset_manifest_signature_key(dataset), &signature)?;
+        }
+
+        held()?;
+        self.put_bytes(&format!("{prefix}/manifest.json"), manifest_bytes)?;
+        held()?;
+        self.put_source(
+            &format!("{prefix}/gene_summary.sqlite"),
+            sqlite,
+            sqlite_sha256,
+        )?;
+        Ok(())
+    }
+}
+
 #[cfg(feature = "backend-s3")]
 impl ArtifactStore for S3LikeStore {
     fn list_datasets(&self) -> Result<Vec<DatasetId>, StoreError> {
@@ -247,6 +938,15 @@ impl ArtifactStore for S3LikeStore {
         manifest
             .validate_strict()
             .map_err(|e| StoreError::new(StoreErrorCode::Validation, e.to_string()))?;
+        if let Some(trusted) = &self.trusted_keys {
+            let signature = match self.get_with_retry(&dataset_manifest_signature_key(dataset)) {
+                Ok(bytes) => Some(bytes),
+                Err(err) if err.code == StoreErrorCode::NotFound => None,
+                Err(err) => return Err(err),
+            };
+            verify_manifest_signature(trusted, &manifest, &lock, signature.as_deref())
+                .map_err(|e| StoreError::new(StoreErrorCode::Validation, e))?;
+        }
         Ok(manifest)
     }
 
@@ -262,6 +962,7 @@ impl ArtifactStore for S3LikeStore {
         expected_manifest_sha256: &str,
         expected_sqlite_sha256: &str,
     ) -> Result<(), StoreError> {
+        let guard = self.acquire_publish_lock(dataset)?;
         if self.exists(dataset)? {
             return Err(StoreError::new(
                 StoreErrorCode::Conflict,
@@ -274,18 +975,69 @@ impl ArtifactStore for S3LikeStore {
         verify_expected_sha256(sqlite_bytes, expected_sqlite_sha256)
             .map_err(|e| StoreError::new(StoreErrorCode::Validation, e))?;
 
-        let prefix = dataset_key_prefix(dataset);
-        self.put_bytes(&format!("{prefix}/manifest.json.tmp"), manifest_bytes)?;
-        self.put_bytes(&format!("{prefix}/gene_summary.sqlite.tmp"), sqlite_bytes)?;
+        self.publish_objects(
+            &guard,
+            dataset,
+            manifest_bytes,
+            &PartSource::Bytes(sqlite_bytes),
+            expected_sqlite_sha256,
+        )
+    }
 
-        let lock = ManifestLock::from_bytes(manifest_bytes, sqlite_bytes);
-        let lock_json = serde_json::to_vec(&lock)
-            .map_err(|e| StoreError::new(StoreErrorCode::Internal, e.to_string()))?;
-        self.put_bytes(&format!("{prefix}/manifest.lock"), &lock_json)?;
+    /// Streams the SQLite object to `dest` and checks it against the digest
+    /// pinned by `manifest.lock`; a mismatch removes `dest` and the cached copy.
+    fn download_sqlite_to(&self, dataset: &DatasetId, dest: &Path) -> Result<u64, StoreError> {
+        let lock_bytes = self.get_with_retry(&dataset_manifest_lock_key(dataset))?;
+        let lock: ManifestLock = serde_json::from_slice(&lock_bytes)
+            .map_err(|e| StoreError::new(StoreErrorCode::Validation, e.to_string()))?;
+        let key = dataset_sqlite_key(dataset);
+        let written = self.download_to_path(&key, dest)?;
+        let actual = sha256_file_hex(dest)
+            .map_err(|e| StoreError::new(StoreErrorCode::Io, e.to_string()))?;
+        if actual != lock.sqlite_sha256 {
+            let _ = fs::remove_file(dest);
+            if let Some(cache_root) = &self.cache_root {
+                let _ = fs::remove_file(cache_root.join(key.replace('/', "__")));
+            }
+            return Err(StoreError::new(
+                StoreErrorCode::Validation,
+                format!(
+                    "sha256 mismatch expected={} actual={actual}",
+                    lock.sqlite_sha256
+                ),
+            ));
+        }
+        Ok(written)
+    }
 
-        self.put_bytes(&format!("{prefix}/manifest.json"), manifest_bytes)?;
-        self.put_bytes(&format!("{prefix}/gene_summary.sqlite"), sqlite_bytes)?;
-        Ok(())
+    fn put_dataset_from_path(
+        &self,
+        dataset: &DatasetId,
+        manifest_bytes: &[u8],
+        sqlite_path: &Path,
+        expected_manifest_sha256: &str,
+        expected_sqlite_sha256: &str,
+    ) -> Result<(), StoreError> {
+        let guard = self.acquire_publish_lock(dataset)?;
+        if self.exists(dataset)? {
+            return Err(StoreError::new(
+                StoreErrorCode::Conflict,
+                "dataset already exists and cannot be overwritten",
+            ));
+        }
+
+        verify_expected_sha256(manifest_bytes, expected_manifest_sha256)
+            .map_err(|e| StoreError::new(StoreErrorCode::Validation, e))?;
+        let sqlite = PartSource::File(sqlite_path);
+        let actual = sha256_file_hex(sqlite_path)
+            .map_err(|e| StoreError::new(StoreErrorCode::Io, e.to_string()))?;
+        if actual != expected_sqlite_sha256 {
+            return Err(StoreError::new(
+                StoreErrorCode::Validation,
+                format!("sha256 mismatch expected={expected_sqlite_sha256} actual={actual}"),
+            ));
+        }
+        self.publish_objects(&guard, dataset, manifest_bytes, &sqlite, &actual)
     }
 
     fn exists(&self, dataset: &DatasetId) -> Result<bool, StoreError> {
@@ -296,10 +1048,115 @@ impl ArtifactStore for S3LikeStore {
         }
     }
 
-    fn acquire_publish_lock(&self, _dataset: &DatasetId) -> Result<PublishLockGuard, StoreError> {
+    fn acquire_publish_lock(&self, dataset: &DatasetId) -> Result<PublishLockGuard, StoreError> {
+        let key = dataset_publish_lock_key(dataset);
+        let now = unix_now()?;
+        let lease = PublishLease::new(
+            &self.publish_lock.owner,
+            new_lease_token()?,
+            self.publish_lock.ttl,
+            now,
+        );
+        // A second pass covers a lease released between our create and our read.
+        for _ in 0..2 {
+            let etag = match self.write_lease_confirmed(&key, &lease, ("if-none-match", "*"))? {
+                LeaseWrite::Written(etag) => etag,
+                LeaseWrite::Lost => {
+                    let Some((current, current_etag)) = self.read_lease(&key)? else {
+                        continue;
+                    };
+                    if !current.is_expired(now) {
+                        return Err(StoreError::new(
+                            StoreErrorCode::Conflict,
+                            format!(
+                                "publish lock for {} held by {} until {}",
+                                dataset.canonical_string(),
+                                current.owner,
+                                current.expires_at_unix
+                            ),
+                        ));
+                    }
+                    tracing::warn!(
+                        event_id = "store_publish_lock_takeover",
+                        backend = "s3like",
+                        dataset = %dataset.canonical_string(),
+                        stale_owner = %current.owner,
+                        "taking over expired publish lock"
+                    );
+                    match self.write_lease_confirmed(
+                        &key,
+                        &lease,
+                        ("if-match", current_etag.as_str()),
+                    )? {
+                        LeaseWrite::Written(etag) => etag,
+                        LeaseWrite::Lost => {
+                            return Err(StoreError::new(
+                                StoreErrorCode::Conflict,
+                                "publish lock was taken over by another publisher",
+                            ))
+                        }
+                    }
+                }
+            };
+            return Ok(self.hold_publish_lock(key, lease, etag));
+        }
         Err(StoreError::new(
-            StoreErrorCode::Unsupported,
-            "s3-like backend does not support local publish lock guard",
+            StoreErrorCode::Conflict,
+            "publish lock is contended; retry publish",
         ))
     }
 }
+
+/// The lease this process wrote and the ETag of its latest write.
+#[cfg(feature = "backend-s3")]
+struct HeldLease {
+    lease: PublishLease,
+    etag: Option<String>,
+    /// Set once a renewal finds another publisher's lease.
+    lost: bool,
+}
+
+#[cfg(feature = "backend-s3")]
+impl S3LikeStore {
+    /// Guard that renews the lease in the background and releases it on drop;
+    /// it stops reporting the lock as held once the lease is lost or expires.
+    fn hold_publish_lock(
+        &self,
+        key: String,
+        lease: PublishLease,
+        etag: Option<String>,
+    ) -> PublishLockGuard {
+        let held = Arc::new(Mutex::new(HeldLease {
+            lease,
+            etag,
+            lost: false,
+        }));
+        let (release, released) = mpsc::channel::<()>();
+        let renewer = {
+            let store = self.clone();
+            let key = key.clone();
+            let held = Arc::clone(&held);
+            thread::spawn(move || store.renew_until_released(&key, &held, &released))
+        };
+        let store = self.clone();
+        let validity = Arc::clone(&held);
+        PublishLockGuard::with_release(move || {
+            drop(release);
+            let _ = renewer.join();
+            let held = held.lock().unwrap_or_else(PoisonError::into_inner);
+            store.release_publish_lock(&key, &held);
+        })
+        .with_validity(move || {
+            let held = validity.lock().unwrap_or_else(PoisonError::into_inner);
+            !held.lost && unix_now().is_ok_and(|now| !held.lease.is_expired(now))
+        })
+    }
+}
+
+#[cfg(feature = "backend-s3")]
+fn unix_now() -> Result<u64, StoreError> {
+    Ok(SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| StoreError::new(StoreErrorCode::Internal, e.to_string()))?
+        .as_secs())
+}
@@ -0,0 +1,154 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Lease object backing the S3-like publish lock.
+//!
+//! The lease is created with `If-None-Match: *` and replaced with `If-Match`
+//! on the observed ETag, so only one writer wins each transition even when
+//! several publishers race against the same bucket. The holder renews it the
+//! same way while a publish runs, so a slow upload never outlives its lease.
+//! Each acquisition carries a random token: owners are only labels and may
+//! collide (same `HOSTNAME`, pid 1 in every container), the token may not.
+
+use crate::app::ports::store::{StoreError, StoreErrorCode};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Long enough to cover a multi-GB multipart publish.
+pub const DEFAULT_PUBLISH_LOCK_TTL: Duration = Duration::from_secs(30 * 60);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublishLockConfig {
+    /// How long a lease stays valid before another publisher may take it over.
+    pub ttl: Duration,
+    /// Identity recorded in the lease, shown to publishers that lose the race.
+    pub owner: String,
+}
+
+impl Default for PublishLockConfig {
+    fn default() -> Self {
+        Self {
+            ttl: DEFAULT_PUBLISH_LOCK_TTL,
+            owner: default_lock_owner(),
+        }
+    }
+}
+
+impl PublishLockConfig {
+    /// How often a held lease is extended; `None` when leases never live.
+    #[must_use]
+    pub fn renew_interval(&self) -> Option<Duration> {
+        (!self.ttl.is_zero()).then(|| (self.ttl / 3).max(Duration::from_millis(100)))
+    }
+}
+
+#[must_use]
+pub fn default_lock_owner() -> String {
+    let host = std::env::var("HOSTNAME").unwrap_or_else(|_| "atlas".to_string());
+    format!("{host}:{}", std::process::id())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PublishLease {
+    pub owner: String,
+    /// Random per-acquisition nonce; identifies our lease when a reply is lost.
+    pub token: String,
+    pub acquired_at_unix: u64,
+    pub expires_at_unix: u64,
+}
+
+impl PublishLease {
+    #[must_use]
+    pub fn new(owner: &str, token: String, ttl: Duration, now_unix: u64) -> Self {
+        Self {
+            owner: owner.to_string(),
+            token,
+            acquired_at_unix: now_unix,
+            expires_at_unix: now_unix.saturating_add(ttl.as_secs()),
+        }
+    }
+
+    #[must_use]
+    pub fn is_expired(&self, now_unix: u64) -> bool {
+        now_unix >= self.expires_at_unix
+    }
+
+    /// The same lease extended to `ttl` past `now_unix`.
+    #[must_use]
+    pub fn renewed(&self, ttl: Duration, now_unix: u64) -> Self {
+        Self {
+            expires_at_unix: now_unix.saturating_add(ttl.as_secs()),
+            ..self.clone()
+        }
+    }
+
+    /// Whether `other` was written by the same acquisition, renewals included.
+    #[must_use]
+    pub fn same_acquisition(&self, other: &Self) -> bool {
+        self.token == other.token
+    }
+}
+
+/// 128-bit random token for a new lease.
+pub fn new_lease_token() -> Result<String, StoreError> {
+    let mut token = [0_u8; 16];
+    SystemRandom::new().fill(&mut token).map_err(|_| {
+        StoreError::new(
+            StoreErrorCode::Internal,
+            "failed to generate publish lock token",
+        )
+    })?;
+    Ok(hex::encode(token))
+}
+
+/// Result of a conditional lease write.
+pub(crate) enum LeaseWrite {
+    /// Written; carries the new ETag when the store returns one.
+    Written(Option<String>),
+    /// The precondition did not hold: someone else created or replaced the lease.
+    Lost,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lease_expires_at_ttl_boundary() {
+        let lease = PublishLease::new("host:1", "t1".to_string(), Duration::from_secs(60), 1_000);
+        assert_eq!(lease.expires_at_unix, 1_060);
+        assert!(!lease.is_expired(1_059));
+        assert!(lease.is_expired(1_060));
+        let json = serde_json::to_vec(&lease).expect("lease json");
+        assert_eq!(
+            serde_json::from_slice::<PublishLease>(&json).expect("lease"),
+            lease
+        );
+        let renewed = lease.renewed(Duration::from_secs(60), 1_050);
+        assert_eq!(
+            (renewed.acquired_at_unix, renewed.expires_at_unix),
+            (1_000, 1_110)
+        );
+        assert!(renewed.same_acquisition(&lease));
+    }
+
+    #[test]
+    fn leases_with_the_same_owner_and_second_are_told_apart_by_token() {
+        let ttl = Duration::from_secs(60);
+        let first = PublishLease::new("atlas:1", new_lease_token().expect("token"), ttl, 1_000);
+        let second = PublishLease::new("atlas:1", new_lease_token().expect("token"), ttl, 1_000);
+        assert_eq!(first.token.len(), 32);
+        assert!(!first.same_acquisition(&second));
+    }
+
+    #[test]
+    fn leases_renew_three_times_per_ttl() {
+        let config = |secs| PublishLockConfig {
+            ttl: Duration::from_secs(secs),
+            owner: "host:1".to_string(),
+        };
+        assert_eq!(config(90).renew_interval(), Some(Duration::from_secs(30)));
+        assert_eq!(config(0).renew_interval(), None);
+    }
+}
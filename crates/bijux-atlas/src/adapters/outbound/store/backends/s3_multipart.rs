@@ -0,0 +1,249 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Multipart upload bookkeeping for the S3-like store.
+//!
+//! Completed parts are recorded in a small JSON state file so an interrupted
+//! publish resumes with the same upload id instead of re-sending every part.
+
+use base64::Engine as _;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_PART_SIZE: u64 = 16 * 1024 * 1024;
+pub const DEFAULT_MULTIPART_THRESHOLD: u64 = 64 * 1024 * 1024;
+/// S3 caps a single upload at 10,000 parts.
+pub const MAX_PARTS: u64 = 10_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultipartConfig {
+    /// Bytes per uploaded part and per ranged GET on download.
+    pub part_size: u64,
+    /// Objects larger than this are uploaded in parts.
+    pub threshold: u64,
+    /// Where in-flight upload state is persisted; `None` disables resume.
+    pub state_dir: Option<PathBuf>,
+}
+
+impl Default for MultipartConfig {
+    fn default() -> Self {
+        Self {
+            part_size: DEFAULT_PART_SIZE,
+            threshold: DEFAULT_MULTIPART_THRESHOLD,
+            state_dir: None,
+        }
+    }
+}
+
+impl MultipartConfig {
+    /// Part size for an object of `size` bytes, grown when needed to stay under [`MAX_PARTS`].
+    #[must_use]
+    pub fn part_size_for(&self, size: u64) -> u64 {
+        self.part_size.max(1).max(size.div_ceil(MAX_PARTS))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct CompletedPart {
+    pub part_number: u64,
+    pub etag: String,
+    pub checksum_sha256: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct MultipartUploadState {
+    pub key: String,
+    pub upload_id: String,
+    pub source_sha256: String,
+    pub size: u64,
+    pub part_size: u64,
+    pub parts: Vec<CompletedPart>,
+}
+
+impl MultipartUploadState {
+    pub(crate) fn new(
+        key: &str,
+        upload_id: String,
+        source_sha256: &str,
+        size: u64,
+        part_size: u64,
+    ) -> Self {
+        Self {
+            key: key.to_string(),
+            upload_id,
+            source_sha256: source_sha256.to_string(),
+            size,
+            part_size,
+            parts: Vec::new(),
+        }
+    }
+
+    pub(crate) fn path(state_dir: &Path, key: &str) -> PathBuf {
+        state_dir.join(format!("{}.upload.json", key.replace('/', "__")))
+    }
+
+    /// Loads saved state for `key`; unreadable or foreign state is treated as absent.
+    pub(crate) fn load(state_dir: &Path, key: &str) -> Option<Self> {
+        let bytes = fs::read(Self::path(state_dir, key)).ok()?;
+        serde_json::from_slice::<Self>(&bytes)
+            .ok()
+            .filter(|state| state.key == key)
+    }
+
+    pub(crate) fn persist(&self, state_dir: &Path) -> std::io::Result<()> {
+        fs::create_dir_all(state_dir)?;
+        let path = Self::path(state_dir, &self.key);
+        let tmp = path.with_extension("json.tmp");
+        let bytes = serde_json::to_vec(self).map_err(std::io::Error::other)?;
+        fs::write(&tmp, bytes)?;
+        fs::rename(tmp, path)
+    }
+
+    pub(crate) fn discard(state_dir: &Path, key: &str) {
+        let _ = fs::remove_file(Self::path(state_dir, key));
+    }
+
+    /// True when the saved upload was started for this exact source.
+    pub(crate) fn resumes(&self, source_sha256: &str, size: u64, part_size: u64) -> bool {
+        self.source_sha256 == source_sha256 && self.size == size && self.part_size == part_size
+    }
+
+    pub(crate) fn part_count(&self) -> u64 {
+        self.size.div_ceil(self.part_size).max(1)
+    }
+
+    pub(crate) fn has_part(&self, part_number: u64) -> bool {
+        self.parts
+            .iter()
+            .any(|part| part.part_number == part_number)
+    }
+
+    pub(crate) fn complete_body(&self) -> String {
+        let mut parts = self.parts.clone();
+        parts.sort_by_key(|part| part.part_number);
+        let mut body = String::from("<CompleteMultipartUpload>");
+        for part in parts {
+            body.push_str(&format!(
+                "<Part><PartNumber>{}</PartNumber><ETag>{}</ETag><ChecksumSHA256>{}</ChecksumSHA256></Part>",
+                part.part_number,
+                xml_escape(&part.etag),
+                part.checksum_sha256
+            ));
+        }
+        body.push_str("</CompleteMultipartUpload>");
+        body
+    }
+}
+
+/// Where part bytes come from: an in-memory buffer or a file read part by part.
+pub(crate) enum PartSource<'a> {
+    Bytes(&'a [u8]),
+    File(&'a Path),
+}
+
+impl PartSource<'_> {
+    pub(crate) fn len(&self) -> std::io::Result<u64> {
+        match self {
+            Self::Bytes(bytes) => Ok(bytes.len() as u64),
+            Self::File(path) => Ok(fs::metadata(path)?.len()),
+        }
+    }
+
+    pub(crate) fn read_range(&self, offset: u64, len: u64) -> std::io::Result<Vec<u8>> {
+        match self {
+            Self::Bytes(bytes) => {
+                let start = offset as usize;
+                let end = (offset + len) as usize;
+                Ok(bytes[start..end].to_vec())
+            }
+            Self::File(path) => {
+                let mut file = fs::File::open(path)?;
+                file.seek(SeekFrom::Start(offset))?;
+                let mut out = vec![0_u8; len as usize];
+                file.read_exact(&mut out)?;
+                Ok(out)
+            }
+        }
+    }
+}
+
+/// Base64 SHA-256, the encoding S3 expects in `x-amz-checksum-sha256`.
+pub(crate) fn part_checksum(bytes: &[u8]) -> String {
+    base64::engine::general_purpose::STANDARD.encode(Sha256::digest(bytes))
+}
+
+pub(crate) fn sha256_file_hex(path: &Path) -> std::io::Result<String> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0_u8; 1 << 20];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Text of the first `<tag>` element; enough for the flat S3 multipart responses.
+pub(crate) fn xml_tag<'a>(body: &'a str, tag: &str) -> Option<&'a str> {
+    let open = format!("<{tag}>");
+    let close = format!("</{tag}>");
+    let start = body.find(&open)? + open.len();
+    let end = body[start..].find(&close)? + start;
+    Some(body[start..end].trim())
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn upload_state_round_trips_and_only_resumes_matching_source() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let mut state = MultipartUploadState::new("a/b.sqlite", "up-1".to_string(), "abc", 10, 4);
+        assert_eq!(state.part_count(), 3);
+        state.parts.push(CompletedPart {
+            part_number: 2,
+            etag: "\"e2\"".to_string(),
+            checksum_sha256: part_checksum(b"part"),
+        });
+        state.persist(tmp.path()).expect("persist");
+        let loaded = MultipartUploadState::load(tmp.path(), "a/b.sqlite").expect("load");
+        assert_eq!(loaded, state);
+        assert!(loaded.has_part(2) && !loaded.has_part(1));
+        assert!(loaded.resumes("abc", 10, 4));
+        assert!(!loaded.resumes("abd", 10, 4));
+        assert!(MultipartUploadState::load(tmp.path(), "a/c.sqlite").is_none());
+        assert!(loaded
+            .complete_body()
+            .contains("<ETag>&quot;e2&quot;</ETag>"));
+        MultipartUploadState::discard(tmp.path(), "a/b.sqlite");
+        assert!(MultipartUploadState::load(tmp.path(), "a/b.sqlite").is_none());
+    }
+
+    #[test]
+    fn part_size_grows_to_respect_part_limit() {
+        let cfg = MultipartConfig {
+            part_size: 4,
+            ..MultipartConfig::default()
+        };
+        assert_eq!(cfg.part_size_for(100), 4);
+        assert_eq!(cfg.part_size_for(MAX_PARTS * 10), 10);
+        assert_eq!(
+            xml_tag("<R><UploadId> x-1 </UploadId></R>", "UploadId"),
+            Some("x-1")
+        );
+    }
+}
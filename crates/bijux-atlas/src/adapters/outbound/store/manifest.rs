@@ -1,5 +1,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::domain::dataset::ArtifactManifest;
+use crate::domain::security::data_protection::{
+    ArtifactSigningKey, ManifestSignature, TrustedSigningKeys,
+};
 use crate::domain::sha256_hex;
 use serde::{Deserialize, Serialize};
 
@@ -49,3 +53,33 @@ pub fn verify_expected_sha256(bytes: &[u8], expected: &str) -> Result<(), String
     }
     Ok(())
 }
+
+/// Signs the parsed manifest against the lock's SQLite digest and returns `manifest.sig` bytes.
+pub fn sign_manifest_bytes(
+    key: &ArtifactSigningKey,
+    manifest_bytes: &[u8],
+    lock: &ManifestLock,
+) -> Result<Vec<u8>, String> {
+    let manifest: ArtifactManifest =
+        serde_json::from_slice(manifest_bytes).map_err(|e| e.to_string())?;
+    let signature = key
+        .sign_manifest(&manifest, &lock.sqlite_sha256)
+        .map_err(|e| e.to_string())?;
+    crate::domain::canonical::stable_json_bytes(&signature).map_err(|e| e.to_string())
+}
+
+/// Checks `manifest.sig` bytes (`None` when the store has none) against `trusted`.
+pub fn verify_manifest_signature(
+    trusted: &TrustedSigningKeys,
+    manifest: &ArtifactManifest,
+    lock: &ManifestLock,
+    signature_bytes: Option<&[u8]>,
+) -> Result<(), String> {
+    let signature = signature_bytes
+        .map(serde_json::from_slice::<ManifestSignature>)
+        .transpose()
+        .map_err(|e| format!("manifest.sig parse failed: {e}"))?;
+    trusted
+        .verify_manifest(manifest, &lock.sqlite_sha256, signature.as_ref())
+        .map_err(|e| e.to_string())
+}
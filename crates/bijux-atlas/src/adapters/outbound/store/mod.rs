@@ -10,6 +10,7 @@ mod manifest;
 mod paths;
 pub mod registry;
 mod retry;
+pub mod sigv4;
 pub mod testing;
 
 pub use crate::app::ports::store::{
@@ -23,17 +24,25 @@ pub use backends::http::HttpReadonlyStore;
 pub use backends::local::LocalFsStore;
 #[cfg(feature = "backend-s3")]
 pub use backends::s3::S3LikeStore;
+#[cfg(feature = "backend-s3")]
+pub use backends::s3_lock::{PublishLease, PublishLockConfig};
+#[cfg(feature = "backend-s3")]
+pub use backends::s3_multipart::MultipartConfig;
 pub use catalog::{
     canonical_catalog_json, merge_catalogs, sorted_catalog_entries, validate_catalog_strict,
 };
-pub use manifest::{verify_expected_sha256, ManifestLock};
+pub use manifest::{
+    sign_manifest_bytes, verify_expected_sha256, verify_manifest_signature, ManifestLock,
+};
 pub use paths::{
     dataset_artifact_paths, dataset_key_prefix, dataset_manifest_key, dataset_manifest_lock_key,
-    dataset_sqlite_key, manifest_lock_path, publish_lock_path, CATALOG_FILE, MANIFEST_FILE,
-    MANIFEST_LOCK_FILE, SQLITE_FILE,
+    dataset_manifest_signature_key, dataset_publish_lock_key, dataset_sqlite_key,
+    manifest_lock_path, manifest_signature_path, publish_lock_path, CATALOG_FILE, MANIFEST_FILE,
+    MANIFEST_LOCK_FILE, MANIFEST_SIGNATURE_FILE, SQLITE_FILE,
 };
 pub use registry::backends::{LocalFsBackend, RetryPolicy, S3LikeBackend};
 pub use registry::federated::{FederatedBackend, RegistrySource};
 pub use retry::BackoffPolicy;
+pub use sigv4::{AddressingStyle, SigV4Credentials, SigV4Signer};
 
 pub const CRATE_NAME: &str = "bijux-atlas";
@@ -7,6 +7,7 @@ pub const CATALOG_FILE: &str = "catalog.json";
 pub const MANIFEST_FILE: &str = "manifest.json";
 pub const SQLITE_FILE: &str = "gene_summary.sqlite";
 pub const MANIFEST_LOCK_FILE: &str = "manifest.lock";
+pub const MANIFEST_SIGNATURE_FILE: &str = "manifest.sig";
 pub const PUBLISH_LOCK_FILE: &str = ".publish.lock";
 pub const IMMUTABILITY_MARKER_FILE: &str = "immutable.release.json";
 pub const LIFECYCLE_STATE_FILE: &str = "lifecycle.state.json";
@@ -37,6 +38,20 @@ pub fn dataset_manifest_lock_key(dataset: &DatasetId) -> String {
     format!("{}/{}", dataset_key_prefix(dataset), MANIFEST_LOCK_FILE)
 }
 
+#[must_use]
+pub fn dataset_publish_lock_key(dataset: &DatasetId) -> String {
+    format!("{}/{}", dataset_key_prefix(dataset), PUBLISH_LOCK_FILE)
+}
+
+#[must_use]
+pub fn dataset_manifest_signature_key(dataset: &DatasetId) -> String {
+    format!(
+        "{}/{}",
+        dataset_key_prefix(dataset),
+        MANIFEST_SIGNATURE_FILE
+    )
+}
+
 #[must_use]
 pub fn manifest_lock_path(root: &Path, dataset: &DatasetId) -> PathBuf {
     dataset_artifact_paths(root, dataset)
@@ -44,6 +59,13 @@ pub fn manifest_lock_path(root: &Path, dataset: &DatasetId) -> PathBuf {
         .join(MANIFEST_LOCK_FILE)
 }
 
+#[must_use]
+pub fn manifest_signature_path(root: &Path, dataset: &DatasetId) -> PathBuf {
+    dataset_artifact_paths(root, dataset)
+        .derived_dir
+        .join(MANIFEST_SIGNATURE_FILE)
+}
+
 #[must_use]
 pub fn publish_lock_path(root: &Path, dataset: &DatasetId) -> PathBuf {
     dataset_artifact_paths(root, dataset)
@@ -1,16 +1,24 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use super::super::paths::MANIFEST_SIGNATURE_FILE;
+use super::super::sigv4::{AddressingStyle, SigV4Signer};
 use crate::domain::dataset::{artifact_paths, ArtifactManifest, ArtifactPaths, Catalog, DatasetId};
+use crate::domain::security::data_protection::ManifestSignature;
 use crate::domain::sha256_hex;
 use crate::{CacheError, CatalogFetch, DatasetStoreBackend};
 use async_trait::async_trait;
 use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, ETAG, IF_NONE_MATCH, RANGE};
 use std::fs;
+use std::io::Write as _;
 use std::net::IpAddr;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 use tracing::instrument;
 
+/// `x-amz-content-sha256` of the empty body every read sends.
+const EMPTY_PAYLOAD_SHA256: &str =
+    "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
 #[derive(Debug, Clone)]
 pub struct RetryPolicy {
     pub max_attempts: usize,
@@ -42,6 +50,11 @@ impl LocalFsBackend {
     }
 
     fn read_safe(&self, path: &Path) -> Result<Vec<u8>, CacheError> {
+        self.check_within_root(path)?;
+        fs::read(path).map_err(|e| CacheError(format!("read failed: {e}")))
+    }
+
+    fn check_within_root(&self, path: &Path) -> Result<(), CacheError> {
         let root = self
             .root
             .canonicalize()
@@ -55,7 +68,7 @@ impl LocalFsBackend {
         if !canonical_parent.starts_with(&root) {
             return Err(CacheError("path traversal blocked".to_string()));
         }
-        fs::read(path).map_err(|e| CacheError(format!("read failed: {e}")))
+        Ok(())
     }
 
     fn validate_catalog_integrity(&self, catalog: &Catalog) -> Result<(), CacheError> {
@@ -115,6 +128,17 @@ impl DatasetStoreBackend for LocalFsBackend {
             .map_err(|e| CacheError(format!("sqlite read failed: {e}")))
     }
 
+    async fn fetch_sqlite_to_path(
+        &self,
+        dataset: &DatasetId,
+        dest: &Path,
+    ) -> Result<u64, CacheError> {
+        let path = self.safe_dataset_paths(dataset)?.sqlite;
+        self.check_within_root(&path)
+            .map_err(|e| CacheError(format!("sqlite read failed: {e}")))?;
+        fs::copy(&path, dest).map_err(|e| CacheError(format!("sqlite copy failed: {e}")))
+    }
+
     async fn fetch_fasta_bytes(&self, dataset: &DatasetId) -> Result<Vec<u8>, CacheError> {
         let path = self.safe_dataset_paths(dataset)?.fasta;
         self.read_safe(&path)
@@ -127,6 +151,19 @@ impl DatasetStoreBackend for LocalFsBackend {
             .map_err(|e| CacheError(format!("fai read failed: {e}")))
     }
 
+    async fn fetch_fasta_gzi_bytes(
+        &self,
+        dataset: &DatasetId,
+    ) -> Result<Option<Vec<u8>>, CacheError> {
+        let path = self.safe_dataset_paths(dataset)?.fasta_gzi;
+        if !path.exists() {
+            return Ok(None);
+        }
+        self.read_safe(&path)
+            .map(Some)
+            .map_err(|e| CacheError(format!("fasta gzi read failed: {e}")))
+    }
+
     async fn fetch_release_gene_index_bytes(
         &self,
         dataset: &DatasetId,
@@ -135,6 +172,36 @@ impl DatasetStoreBackend for LocalFsBackend {
         self.read_safe(&path)
             .map_err(|e| CacheError(format!("release gene index read failed: {e}")))
     }
+
+    async fn fetch_liftover_chains_bytes(
+        &self,
+        dataset: &DatasetId,
+    ) -> Result<Option<Vec<u8>>, CacheError> {
+        let path = self.safe_dataset_paths(dataset)?.liftover_chains;
+        if !path.exists() {
+            return Ok(None);
+        }
+        self.read_safe(&path)
+            .map(Some)
+            .map_err(|e| CacheError(format!("liftover chains read failed: {e}")))
+    }
+
+    async fn fetch_manifest_signature(
+        &self,
+        dataset: &DatasetId,
+    ) -> Result<Option<ManifestSignature>, CacheError> {
+        let path = self
+            .safe_dataset_paths(dataset)?
+            .derived_dir
+            .join(MANIFEST_SIGNATURE_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let bytes = self
+            .read_safe(&path)
+            .map_err(|e| CacheError(format!("manifest signature read failed: {e}")))?;
+        parse_manifest_signature(&bytes).map(Some)
+    }
 }
 
 pub struct S3LikeBackend {
@@ -143,6 +210,8 @@ pub struct S3LikeBackend {
     auth_bearer: Option<String>,
     retry: RetryPolicy,
     allow_private_hosts: bool,
+    sigv4: Option<SigV4Signer>,
+    client: reqwest::Client,
 }
 
 impl S3LikeBackend {
@@ -162,9 +231,39 @@ impl S3LikeBackend {
             auth_bearer,
             retry,
             allow_private_hosts,
+            sigv4: None,
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(15))
+                .redirect(reqwest::redirect::Policy::none())
+                .build()
+                .unwrap_or_else(|_| reqwest::Client::new()),
         }
     }
 
+    /// Replaces the HTTP client, e.g. to pin DNS for a bucket host or add
+    /// trust roots. It should keep redirects disabled.
+    #[must_use]
+    pub fn with_client(mut self, client: reqwest::Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Reads from `bucket` under the endpoint given to [`Self::new`], placed
+    /// in the URL according to `addressing`.
+    #[must_use]
+    pub fn with_bucket(mut self, bucket: &str, addressing: AddressingStyle) -> Self {
+        self.base_url = addressing.bucket_url(&self.base_url, bucket);
+        self
+    }
+
+    /// Signs every request with SigV4; takes precedence over the bearer
+    /// token. Presigned URLs already carry their signature and stay unsigned.
+    #[must_use]
+    pub fn with_sigv4(mut self, signer: Option<SigV4Signer>) -> Self {
+        self.sigv4 = signer;
+        self
+    }
+
     fn object_url(&self, dataset: &DatasetId, file: &str) -> String {
         let base = self.presigned_base_url.as_deref().unwrap_or(&self.base_url);
         format!(
@@ -182,11 +281,7 @@ impl S3LikeBackend {
     }
 
     fn client(&self) -> reqwest::Client {
-        reqwest::Client::builder()
-            .timeout(Duration::from_secs(15))
-            .redirect(reqwest::redirect::Policy::none())
-            .build()
-            .unwrap_or_else(|_| reqwest::Client::new())
+        self.client.clone()
     }
 
     fn validate_url(&self, url: &str) -> Result<(), CacheError> {
@@ -213,8 +308,17 @@ impl S3LikeBackend {
         Ok(())
     }
 
+    fn signer(&self) -> Option<&SigV4Signer> {
+        self.sigv4
+            .as_ref()
+            .filter(|_| self.presigned_base_url.is_none())
+    }
+
     fn auth_headers(&self) -> Result<HeaderMap, CacheError> {
         let mut headers = HeaderMap::new();
+        if self.signer().is_some() {
+            return Ok(headers);
+        }
         if let Some(token) = &self.auth_bearer {
             let value = HeaderValue::from_str(&format!("Bearer {token}"))
                 .map_err(|e| CacheError(format!("invalid auth header: {e}")))?;
@@ -223,6 +327,36 @@ impl S3LikeBackend {
         Ok(headers)
     }
 
+    /// `headers` plus the SigV4 headers for a GET of `url`, when signing.
+    fn signed(&self, url: &str, mut headers: HeaderMap) -> Result<HeaderMap, CacheError> {
+        let Some(signer) = self.signer() else {
+            return Ok(headers);
+        };
+        let parsed =
+            reqwest::Url::parse(url).map_err(|e| CacheError(format!("invalid store url: {e}")))?;
+        let covered: Vec<(String, String)> = headers
+            .iter()
+            .filter_map(|(name, value)| {
+                Some((name.as_str().to_string(), value.to_str().ok()?.to_string()))
+            })
+            .collect();
+        let covered: Vec<(&str, &str)> = covered
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .collect();
+        let signature = signer
+            .sign_now("GET", &parsed, &covered, EMPTY_PAYLOAD_SHA256)
+            .map_err(CacheError)?;
+        for (name, value) in signature {
+            let name = reqwest::header::HeaderName::from_bytes(name.as_bytes())
+                .map_err(|e| CacheError(format!("invalid sigv4 header: {e}")))?;
+            let value = HeaderValue::from_str(&value)
+                .map_err(|e| CacheError(format!("invalid sigv4 header: {e}")))?;
+            headers.insert(name, value);
+        }
+        Ok(headers)
+    }
+
     #[instrument(name = "store_s3_get_with_retry", skip(self))]
     async fn get_with_retry(&self, url: &str) -> Result<Vec<u8>, CacheError> {
         self.validate_url(url)?;
@@ -231,7 +365,7 @@ impl S3LikeBackend {
         let mut attempt = 0;
         loop {
             attempt += 1;
-            let req = client.get(url).headers(headers.clone());
+            let req = client.get(url).headers(self.signed(url, headers.clone())?);
             match req.send().await {
                 Ok(resp) if resp.status().is_success() => {
                     return resp
@@ -295,7 +429,7 @@ impl S3LikeBackend {
                         .map_err(|e| CacheError(format!("invalid if-none-match header: {e}")))?,
                 );
             }
-            let req = client.get(url).headers(headers);
+            let req = client.get(url).headers(self.signed(url, headers)?);
             match req.send().await {
                 Ok(resp) if resp.status().as_u16() == 304 => return Ok(CatalogFetch::NotModified),
                 Ok(resp) if resp.status().is_success() => {
@@ -370,7 +504,7 @@ impl S3LikeBackend {
                         .map_err(|e| CacheError(format!("invalid range header: {e}")))?,
                 );
             }
-            let req = client.get(url).headers(headers);
+            let req = client.get(url).headers(self.signed(url, headers)?);
             match req.send().await {
                 Ok(resp) if resp.status().is_success() || resp.status().as_u16() == 206 => {
                     let status = resp.status();
@@ -443,18 +577,135 @@ impl S3LikeBackend {
         }
     }
 
+    /// Ranged GET that appends to `dest`, resuming from whatever it already holds.
+    #[instrument(name = "store_s3_download_to_path", skip(self))]
+    async fn download_resume_to_path(&self, url: &str, dest: &Path) -> Result<u64, CacheError> {
+        self.validate_url(url)?;
+        let client = self.client();
+        let base_headers = self.auth_headers()?;
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let offset = fs::metadata(dest).map_or(0, |m| m.len());
+            let mut headers = base_headers.clone();
+            if offset > 0 {
+                headers.insert(
+                    RANGE,
+                    HeaderValue::from_str(&format!("bytes={offset}-"))
+                        .map_err(|e| CacheError(format!("invalid range header: {e}")))?,
+                );
+            }
+            let req = client.get(url).headers(self.signed(url, headers)?);
+            match req.send().await {
+                Ok(resp) if resp.status().as_u16() == 416 && offset > 0 => return Ok(offset),
+                Ok(mut resp) if resp.status().is_success() => {
+                    let partial = resp.status().as_u16() == 206;
+                    let total = resp
+                        .headers()
+                        .get("content-range")
+                        .and_then(|v| v.to_str().ok())
+                        .and_then(|v| v.split('/').nth(1))
+                        .and_then(|v| v.parse::<u64>().ok())
+                        .or_else(|| resp.content_length().filter(|_| !partial));
+                    let mut file = if partial {
+                        fs::OpenOptions::new().append(true).open(dest)
+                    } else {
+                        fs::File::create(dest)
+                    }
+                    .map_err(|e| CacheError(format!("download write failed: {e}")))?;
+                    let mut written = if partial { offset } else { 0 };
+                    let mut interrupted = None;
+                    loop {
+                        match resp.chunk().await {
+                            Ok(Some(chunk)) => {
+                                file.write_all(&chunk).map_err(|e| {
+                                    CacheError(format!("download write failed: {e}"))
+                                })?;
+                                written += chunk.len() as u64;
+                            }
+                            Ok(None) => break,
+                            Err(e) => {
+                                interrupted = Some(e);
+                                break;
+                            }
+                        }
+                    }
+                    file.sync_all()
+                        .map_err(|e| CacheError(format!("download write failed: {e}")))?;
+                    if interrupted.is_none() && total.is_none_or(|total| written >= total) {
+                        return Ok(written);
+                    }
+                    if attempt >= self.retry.max_attempts {
+                        return Err(CacheError(format!(
+                            "resumable download incomplete after {written} bytes url={url}"
+                        )));
+                    }
+                    tracing::warn!(
+                        event_id = "store_resume_retry",
+                        backend = "http_s3",
+                        attempt,
+                        written,
+                        "streaming download resuming after short read"
+                    );
+                }
+                Ok(resp) => {
+                    if attempt >= self.retry.max_attempts {
+                        return Err(CacheError(format!(
+                            "resumable download failed status={} url={url}",
+                            resp.status()
+                        )));
+                    }
+                    tracing::warn!(
+                        event_id = "store_resume_retry",
+                        backend = "http_s3",
+                        status = %resp.status(),
+                        attempt,
+                        "streaming download retrying after status error"
+                    );
+                }
+                Err(e) => {
+                    if attempt >= self.retry.max_attempts {
+                        return Err(CacheError(format!(
+                            "resumable download failed url={url}: {e}"
+                        )));
+                    }
+                    tracing::warn!(
+                        event_id = "store_resume_retry",
+                        backend = "http_s3",
+                        attempt,
+                        is_timeout = e.is_timeout(),
+                        "streaming download retrying after transport error"
+                    );
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(
+                self.retry.base_backoff_ms.saturating_mul(attempt as u64),
+            ))
+            .await;
+        }
+    }
+
     #[instrument(name = "store_s3_get_optional_checksum_with_retry", skip(self))]
     async fn get_optional_checksum_with_retry(
         &self,
         url: &str,
     ) -> Result<Option<String>, CacheError> {
+        self.get_optional_with_retry(url)
+            .await?
+            .map(|bytes| parse_sha256_bytes(&bytes))
+            .transpose()
+    }
+
+    /// GET that maps 404 to `None` for objects a dataset may legitimately omit.
+    #[instrument(name = "store_s3_get_optional_with_retry", skip(self))]
+    async fn get_optional_with_retry(&self, url: &str) -> Result<Option<Vec<u8>>, CacheError> {
         self.validate_url(url)?;
         let client = self.client();
         let headers = self.auth_headers()?;
         let mut attempt = 0;
         loop {
             attempt += 1;
-            let req = client.get(url).headers(headers.clone());
+            let req = client.get(url).headers(self.signed(url, headers.clone())?);
             match req.send().await {
                 Ok(resp) if resp.status().as_u16() == 404 => return Ok(None),
                 Ok(resp) if resp.status().is_success() => {
@@ -462,14 +713,13 @@ impl S3LikeBackend {
                         .bytes()
                         .await
                         .map(|b| b.to_vec())
-                        .map_err(|e| CacheError(format!("read checksum body failed: {e}")))?;
-                    let parsed = parse_sha256_bytes(&bytes)?;
-                    return Ok(Some(parsed));
+                        .map_err(|e| CacheError(format!("read body failed: {e}")))?;
+                    return Ok(Some(bytes));
                 }
                 Ok(resp) => {
                     if attempt >= self.retry.max_attempts {
                         return Err(CacheError(format!(
-                            "checksum download failed status={} url={url}",
+                            "optional download failed status={} url={url}",
                             resp.status()
                         )));
                     }
@@ -484,7 +734,7 @@ impl S3LikeBackend {
                 Err(e) => {
                     if attempt >= self.retry.max_attempts {
                         return Err(CacheError(format!(
-                            "checksum download failed url={url}: {e}"
+                            "optional download failed url={url}: {e}"
                         )));
                     }
                     tracing::warn!(
@@ -543,6 +793,15 @@ impl DatasetStoreBackend for S3LikeBackend {
         self.get_resume_with_retry(&url).await
     }
 
+    async fn fetch_sqlite_to_path(
+        &self,
+        dataset: &DatasetId,
+        dest: &Path,
+    ) -> Result<u64, CacheError> {
+        let url = self.object_url(dataset, "gene_summary.sqlite");
+        self.download_resume_to_path(&url, dest).await
+    }
+
     async fn fetch_fasta_bytes(&self, dataset: &DatasetId) -> Result<Vec<u8>, CacheError> {
         let url = self.object_url_input(dataset, "genome.fa.bgz");
         self.get_resume_with_retry(&url).await
@@ -553,6 +812,14 @@ impl DatasetStoreBackend for S3LikeBackend {
         self.get_with_retry(&url).await
     }
 
+    async fn fetch_fasta_gzi_bytes(
+        &self,
+        dataset: &DatasetId,
+    ) -> Result<Option<Vec<u8>>, CacheError> {
+        let url = self.object_url_input(dataset, "genome.fa.bgz.gzi");
+        self.get_optional_with_retry(&url).await
+    }
+
     async fn fetch_release_gene_index_bytes(
         &self,
         dataset: &DatasetId,
@@ -560,6 +827,25 @@ impl DatasetStoreBackend for S3LikeBackend {
         let url = self.object_url(dataset, "release_gene_index.json");
         self.get_with_retry(&url).await
     }
+
+    async fn fetch_liftover_chains_bytes(
+        &self,
+        dataset: &DatasetId,
+    ) -> Result<Option<Vec<u8>>, CacheError> {
+        let url = self.object_url(dataset, "liftover_chains.json");
+        self.get_optional_with_retry(&url).await
+    }
+
+    async fn fetch_manifest_signature(
+        &self,
+        dataset: &DatasetId,
+    ) -> Result<Option<ManifestSignature>, CacheError> {
+        let url = self.object_url(dataset, MANIFEST_SIGNATURE_FILE);
+        match self.get_optional_with_retry(&url).await? {
+            Some(bytes) => parse_manifest_signature(&bytes).map(Some),
+            None => Ok(None),
+        }
+    }
 }
 
 fn catalog_digest(catalog: &Catalog) -> Result<String, CacheError> {
@@ -568,6 +854,11 @@ fn catalog_digest(catalog: &Catalog) -> Result<String, CacheError> {
     Ok(sha256_hex(&bytes))
 }
 
+fn parse_manifest_signature(bytes: &[u8]) -> Result<ManifestSignature, CacheError> {
+    serde_json::from_slice(bytes)
+        .map_err(|e| CacheError(format!("manifest signature parse failed: {e}")))
+}
+
 fn read_sha256_file(path: &Path) -> Result<String, CacheError> {
     let bytes = fs::read(path).map_err(|e| CacheError(format!("checksum read failed: {e}")))?;
     parse_sha256_bytes(&bytes)
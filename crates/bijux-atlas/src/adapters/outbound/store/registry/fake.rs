@@ -1,6 +1,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::domain::dataset::{ArtifactManifest, Catalog, DatasetId};
+use crate::domain::security::data_protection::ManifestSignature;
 use crate::{CacheError, CatalogFetch, DatasetStoreBackend};
 use async_trait::async_trait;
 use std::collections::HashMap;
@@ -13,7 +14,10 @@ pub struct FakeStore {
     pub sqlite: Mutex<HashMap<DatasetId, Vec<u8>>>,
     pub fasta: Mutex<HashMap<DatasetId, Vec<u8>>>,
     pub fai: Mutex<HashMap<DatasetId, Vec<u8>>>,
+    pub fasta_gzi: Mutex<HashMap<DatasetId, Vec<u8>>>,
     pub release_gene_index: Mutex<HashMap<DatasetId, Vec<u8>>>,
+    pub liftover_chains: Mutex<HashMap<DatasetId, Vec<u8>>>,
+    pub signatures: Mutex<HashMap<DatasetId, ManifestSignature>>,
     pub fetch_calls: std::sync::atomic::AtomicU64,
     pub etag: Mutex<String>,
     pub slow_read: bool,
@@ -28,7 +32,10 @@ impl Default for FakeStore {
             sqlite: Mutex::new(HashMap::new()),
             fasta: Mutex::new(HashMap::new()),
             fai: Mutex::new(HashMap::new()),
+            fasta_gzi: Mutex::new(HashMap::new()),
             release_gene_index: Mutex::new(HashMap::new()),
+            liftover_chains: Mutex::new(HashMap::new()),
+            signatures: Mutex::new(HashMap::new()),
             fetch_calls: std::sync::atomic::AtomicU64::new(0),
             etag: Mutex::new(String::new()),
             slow_read: false,
@@ -108,6 +115,13 @@ impl DatasetStoreBackend for FakeStore {
             .ok_or_else(|| CacheError("fai missing".to_string()))
     }
 
+    async fn fetch_fasta_gzi_bytes(
+        &self,
+        dataset: &DatasetId,
+    ) -> Result<Option<Vec<u8>>, CacheError> {
+        Ok(self.fasta_gzi.lock().await.get(dataset).cloned())
+    }
+
     async fn fetch_release_gene_index_bytes(
         &self,
         dataset: &DatasetId,
@@ -119,4 +133,18 @@ impl DatasetStoreBackend for FakeStore {
             .cloned()
             .ok_or_else(|| CacheError("release gene index missing".to_string()))
     }
+
+    async fn fetch_liftover_chains_bytes(
+        &self,
+        dataset: &DatasetId,
+    ) -> Result<Option<Vec<u8>>, CacheError> {
+        Ok(self.liftover_chains.lock().await.get(dataset).cloned())
+    }
+
+    async fn fetch_manifest_signature(
+        &self,
+        dataset: &DatasetId,
+    ) -> Result<Option<ManifestSignature>, CacheError> {
+        Ok(self.signatures.lock().await.get(dataset).cloned())
+    }
 }
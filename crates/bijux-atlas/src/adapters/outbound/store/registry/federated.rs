@@ -1,10 +1,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::domain::dataset::{ArtifactManifest, Catalog, CatalogEntry, DatasetId};
+use crate::domain::security::data_protection::ManifestSignature;
 use crate::domain::sha256_hex;
 use crate::{CacheError, CatalogFetch, DatasetStoreBackend, RegistrySourceHealth};
 use async_trait::async_trait;
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
@@ -300,6 +302,27 @@ impl DatasetStoreBackend for FederatedBackend {
         Err(Self::combined_fetch_errors(errors))
     }
 
+    async fn fetch_sqlite_to_path(
+        &self,
+        dataset: &DatasetId,
+        dest: &Path,
+    ) -> Result<u64, CacheError> {
+        let order = self.get_primary_source_order(dataset).await;
+        let mut errors = Vec::new();
+        for idx in order {
+            let source = &self.sources[idx];
+            match source.backend.fetch_sqlite_to_path(dataset, dest).await {
+                Ok(v) => return Ok(v),
+                Err(e) => {
+                    // A partial body from one source must not be resumed from another.
+                    let _ = std::fs::remove_file(dest);
+                    errors.push(format!("{}: {}", source.name, e));
+                }
+            }
+        }
+        Err(Self::combined_fetch_errors(errors))
+    }
+
     async fn fetch_fasta_bytes(&self, dataset: &DatasetId) -> Result<Vec<u8>, CacheError> {
         let order = self.get_primary_source_order(dataset).await;
         let mut errors = Vec::new();
@@ -326,6 +349,22 @@ impl DatasetStoreBackend for FederatedBackend {
         Err(Self::combined_fetch_errors(errors))
     }
 
+    async fn fetch_fasta_gzi_bytes(
+        &self,
+        dataset: &DatasetId,
+    ) -> Result<Option<Vec<u8>>, CacheError> {
+        let order = self.get_primary_source_order(dataset).await;
+        let mut errors = Vec::new();
+        for idx in order {
+            let source = &self.sources[idx];
+            match source.backend.fetch_fasta_gzi_bytes(dataset).await {
+                Ok(v) => return Ok(v),
+                Err(e) => errors.push(format!("{}: {}", source.name, e)),
+            }
+        }
+        Err(Self::combined_fetch_errors(errors))
+    }
+
     async fn fetch_release_gene_index_bytes(
         &self,
         dataset: &DatasetId,
@@ -342,6 +381,38 @@ impl DatasetStoreBackend for FederatedBackend {
         Err(Self::combined_fetch_errors(errors))
     }
 
+    async fn fetch_liftover_chains_bytes(
+        &self,
+        dataset: &DatasetId,
+    ) -> Result<Option<Vec<u8>>, CacheError> {
+        let order = self.get_primary_source_order(dataset).await;
+        let mut errors = Vec::new();
+        for idx in order {
+            let source = &self.sources[idx];
+            match source.backend.fetch_liftover_chains_bytes(dataset).await {
+                Ok(v) => return Ok(v),
+                Err(e) => errors.push(format!("{}: {}", source.name, e)),
+            }
+        }
+        Err(Self::combined_fetch_errors(errors))
+    }
+
+    async fn fetch_manifest_signature(
+        &self,
+        dataset: &DatasetId,
+    ) -> Result<Option<ManifestSignature>, CacheError> {
+        let order = self.get_primary_source_order(dataset).await;
+        let mut errors = Vec::new();
+        for idx in order {
+            let source = &self.sources[idx];
+            match source.backend.fetch_manifest_signature(dataset).await {
+                Ok(v) => return Ok(v),
+                Err(e) => errors.push(format!("{}: {}", source.name, e)),
+            }
+        }
+        Err(Self::combined_fetch_errors(errors))
+    }
+
     async fn registry_health(&self) -> Vec<RegistrySourceHealth> {
         self.source_health().await
     }
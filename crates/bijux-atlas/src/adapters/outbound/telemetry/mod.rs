@@ -4,5 +4,6 @@ pub mod generated;
 pub mod logging;
 pub(crate) mod metrics;
 pub(crate) mod metrics_endpoint;
+pub(crate) mod quota_ledger;
 pub(crate) mod rate_limiter;
 pub mod tracing;
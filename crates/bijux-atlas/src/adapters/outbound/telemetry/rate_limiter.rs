@@ -3,18 +3,67 @@
 use crate::adapters::outbound::redis::RedisBackend;
 use crate::runtime::config::RateLimitConfig;
 use std::collections::HashMap;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
 
+/// How often a charge also drops buckets that have refilled completely.
+const IDLE_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
 #[derive(Debug, Clone)]
 struct Bucket {
     tokens: f64,
     last_refill: Instant,
+    capacity: f64,
+    refill_per_sec: f64,
+}
+
+impl Bucket {
+    fn tokens_at(&self, now: Instant) -> f64 {
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        (self.tokens + elapsed * self.refill_per_sec).min(self.capacity)
+    }
+}
+
+#[derive(Default)]
+struct BucketTable {
+    buckets: HashMap<String, Bucket>,
+    last_sweep: Option<Instant>,
+}
+
+impl BucketTable {
+    /// A full bucket is indistinguishable from a missing one, so dropping it
+    /// loses nothing and keeps one-off keys from accumulating.
+    fn sweep_idle(&mut self, now: Instant) {
+        if self
+            .last_sweep
+            .is_some_and(|last| now.duration_since(last) < IDLE_SWEEP_INTERVAL)
+        {
+            return;
+        }
+        self.last_sweep = Some(now);
+        self.buckets
+            .retain(|_, bucket| bucket.tokens_at(now) < bucket.capacity);
+    }
+}
+
+/// Outcome of one charge, in the units the `RateLimit` headers report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct RateLimitDecision {
+    pub allowed: bool,
+    /// Whole requests the bucket or window admits when full.
+    pub limit: u64,
+    pub remaining: u64,
+    /// Seconds for an empty bucket to refill, or the window length.
+    pub window_secs: u64,
+    /// Seconds until the bucket is full or the window restarts.
+    pub reset_secs: u64,
+    /// Seconds until the denied charge would fit; zero when allowed.
+    pub retry_after_secs: u64,
 }
 
 #[derive(Default)]
 pub(crate) struct RateLimiter {
-    buckets: Mutex<HashMap<String, Bucket>>,
+    table: Mutex<BucketTable>,
     redis: Option<RedisBackend>,
     scope: String,
 }
@@ -22,7 +71,7 @@ pub(crate) struct RateLimiter {
 impl RateLimiter {
     pub(crate) fn new(redis: Option<RedisBackend>, scope: &str) -> Self {
         Self {
-            buckets: Mutex::new(HashMap::new()),
+            table: Mutex::new(BucketTable::default()),
             redis,
             scope: scope.to_string(),
         }
@@ -34,34 +83,210 @@ impl RateLimiter {
         cfg: &RateLimitConfig,
         factor: f64,
     ) -> bool {
+        self.allow_cost_with_factor(key, cfg, factor, 1).await
+    }
+
+    /// Charges `cost` tokens at once; a cost above the bucket capacity is
+    /// clamped so one request can drain the bucket but is never unservable.
+    pub(crate) async fn allow_cost_with_factor(
+        &self,
+        key: &str,
+        cfg: &RateLimitConfig,
+        factor: f64,
+        cost: u64,
+    ) -> bool {
+        self.charge(key, cfg, factor, cost).await.allowed
+    }
+
+    /// Like [`Self::allow_cost_with_factor`], reporting what is left.
+    pub(crate) async fn charge(
+        &self,
+        key: &str,
+        cfg: &RateLimitConfig,
+        factor: f64,
+        cost: u64,
+    ) -> RateLimitDecision {
         let factor = factor.clamp(0.1, 1.0);
         let effective = RateLimitConfig {
             capacity: (cfg.capacity * factor).max(1.0),
             refill_per_sec: (cfg.refill_per_sec * factor).max(0.5),
         };
         if let Some(redis) = &self.redis {
-            match redis.rate_limit_allow(&self.scope, key, &effective).await {
-                Ok(v) => return v,
+            let cost = charged_tokens(cost, &effective);
+            match redis
+                .rate_limit_allow(&self.scope, key, &effective, cost)
+                .await
+            {
+                Ok((allowed, tokens)) => return bucket_decision(allowed, tokens, cost, &effective),
                 Err(e) => {
                     tracing::warn!(scope = %self.scope, "redis rate-limit fallback: {e}");
                 }
             }
         }
-        let now = Instant::now();
-        let mut lock = self.buckets.lock().await;
-        let bucket = lock.entry(key.to_string()).or_insert_with(|| Bucket {
+        let mut table = self.table.lock().await;
+        charge_bucket(&mut table, key, &effective, cost, Instant::now())
+    }
+
+    #[cfg(test)]
+    async fn tracked_buckets(&self) -> usize {
+        self.table.lock().await.buckets.len()
+    }
+}
+
+/// `cost` in tokens, clamped to what a full bucket holds.
+fn charged_tokens(cost: u64, effective: &RateLimitConfig) -> f64 {
+    (cost.max(1) as f64).min(effective.capacity)
+}
+
+fn charge_bucket(
+    table: &mut BucketTable,
+    key: &str,
+    effective: &RateLimitConfig,
+    cost: u64,
+    now: Instant,
+) -> RateLimitDecision {
+    table.sweep_idle(now);
+    let cost = charged_tokens(cost, effective);
+    let bucket = table
+        .buckets
+        .entry(key.to_string())
+        .or_insert_with(|| Bucket {
             tokens: effective.capacity,
             last_refill: now,
+            capacity: effective.capacity,
+            refill_per_sec: effective.refill_per_sec,
         });
-        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
-        bucket.last_refill = now;
-        bucket.tokens =
-            (bucket.tokens + (elapsed * effective.refill_per_sec)).min(effective.capacity);
-        if bucket.tokens >= 1.0 {
-            bucket.tokens -= 1.0;
-            true
+    bucket.capacity = effective.capacity;
+    bucket.refill_per_sec = effective.refill_per_sec;
+    bucket.tokens = bucket.tokens_at(now);
+    bucket.last_refill = now;
+    let allowed = bucket.tokens >= cost;
+    if allowed {
+        bucket.tokens -= cost;
+    }
+    bucket_decision(allowed, bucket.tokens, cost, effective)
+}
+
+/// The decision for a charge of `cost` that left `tokens` in a bucket shaped
+/// by `effective`, wherever that bucket lives.
+fn bucket_decision(
+    allowed: bool,
+    tokens: f64,
+    cost: f64,
+    effective: &RateLimitConfig,
+) -> RateLimitDecision {
+    let secs_until = |tokens: f64| (tokens.max(0.0) / effective.refill_per_sec).ceil() as u64;
+    RateLimitDecision {
+        allowed,
+        limit: effective.capacity.floor() as u64,
+        remaining: tokens.max(0.0).floor() as u64,
+        window_secs: secs_until(effective.capacity),
+        reset_secs: secs_until(effective.capacity - tokens),
+        retry_after_secs: if allowed {
+            0
         } else {
-            false
+            secs_until(cost - tokens).max(1)
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(capacity: f64, refill_per_sec: f64) -> RateLimitConfig {
+        RateLimitConfig {
+            capacity,
+            refill_per_sec,
         }
     }
+
+    #[test]
+    fn decisions_report_remaining_reset_and_retry_after() {
+        let mut table = BucketTable::default();
+        let cfg = config(3.0, 1.0);
+        let start = Instant::now();
+        let first = charge_bucket(&mut table, "tenant", &cfg, 1, start);
+        assert_eq!(
+            first,
+            RateLimitDecision {
+                allowed: true,
+                limit: 3,
+                remaining: 2,
+                window_secs: 3,
+                reset_secs: 1,
+                retry_after_secs: 0,
+            }
+        );
+        let drained = charge_bucket(&mut table, "tenant", &cfg, 2, start);
+        assert!(drained.allowed);
+        assert_eq!((drained.remaining, drained.reset_secs), (0, 3));
+        let denied = charge_bucket(&mut table, "tenant", &cfg, 1, start);
+        assert!(!denied.allowed);
+        assert_eq!(denied.retry_after_secs, 1);
+        let refilled = charge_bucket(
+            &mut table,
+            "tenant",
+            &cfg,
+            1,
+            start + Duration::from_secs(2),
+        );
+        assert!(refilled.allowed);
+        assert_eq!(refilled.remaining, 1);
+    }
+
+    #[test]
+    fn shared_buckets_report_the_same_capacity_model_as_local_ones() {
+        let cfg = config(10.0, 0.5);
+        let mut table = BucketTable::default();
+        let local = charge_bucket(&mut table, "tenant", &cfg, 4, Instant::now());
+        assert_eq!(local, bucket_decision(true, 6.0, 4.0, &cfg));
+        assert_eq!(
+            (
+                local.limit,
+                local.remaining,
+                local.window_secs,
+                local.reset_secs
+            ),
+            (10, 6, 20, 8)
+        );
+        let denied = bucket_decision(false, 1.5, charged_tokens(40, &cfg), &cfg);
+        assert_eq!((denied.limit, denied.remaining), (10, 1));
+        assert_eq!(denied.retry_after_secs, 17);
+    }
+
+    #[test]
+    fn idle_sweep_drops_only_refilled_buckets() {
+        let mut table = BucketTable::default();
+        let start = Instant::now();
+        charge_bucket(&mut table, "slow", &config(100.0, 0.5), 50, start);
+        charge_bucket(&mut table, "fast", &config(2.0, 1.0), 1, start);
+        assert_eq!(table.buckets.len(), 2);
+
+        let later = start + IDLE_SWEEP_INTERVAL;
+        charge_bucket(&mut table, "other", &config(2.0, 1.0), 1, later);
+        let mut keys: Vec<_> = table.buckets.keys().cloned().collect();
+        keys.sort();
+        assert_eq!(keys, vec!["other".to_string(), "slow".to_string()]);
+
+        // The next sweep waits for the interval even though "other" refills.
+        charge_bucket(
+            &mut table,
+            "fast",
+            &config(2.0, 1.0),
+            1,
+            later + Duration::from_secs(5),
+        );
+        assert_eq!(table.buckets.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn limiter_without_redis_counts_buckets_per_key() {
+        let limiter = RateLimiter::new(None, "test");
+        let cfg = config(1.0, 0.5);
+        assert!(limiter.allow_with_factor("a", &cfg, 1.0).await);
+        assert!(!limiter.allow_with_factor("a", &cfg, 1.0).await);
+        assert!(limiter.allow_with_factor("b", &cfg, 1.0).await);
+        assert_eq!(limiter.tracked_buckets().await, 2);
+    }
 }
@@ -2,7 +2,11 @@
 
 use std::path::Path;
 
-pub use crate::domain::ingest::{IngestError, IngestOptions, IngestResult, TimestampPolicy};
+pub use crate::domain::ingest::{
+    build_gzi_index, encode_gzi_index, evidence_sha256, gzi_path_for, read_decoded_window,
+    IngestError, IngestOptions, IngestResult, InputCompression, LiftoverChainInput,
+    TimestampPolicy,
+};
 
 pub fn ingest_dataset(options: &IngestOptions) -> Result<IngestResult, IngestError> {
     crate::domain::ingest::ingest_dataset(options)
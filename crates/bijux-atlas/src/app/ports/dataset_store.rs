@@ -2,7 +2,9 @@
 
 use crate::app::cache::{CacheError, RegistrySourceHealth};
 use crate::domain::dataset::{ArtifactManifest, Catalog, DatasetId};
+use crate::domain::security::data_protection::ManifestSignature;
 use async_trait::async_trait;
+use std::path::Path;
 
 /// Runtime read port used by the server cache and query-serving path.
 ///
@@ -28,13 +30,51 @@ pub trait DatasetStoreBackend: Send + Sync + 'static {
     async fn fetch_catalog(&self, if_none_match: Option<&str>) -> Result<CatalogFetch, CacheError>;
     async fn fetch_manifest(&self, dataset: &DatasetId) -> Result<ArtifactManifest, CacheError>;
     async fn fetch_sqlite_bytes(&self, dataset: &DatasetId) -> Result<Vec<u8>, CacheError>;
+
+    /// Streams the SQLite artifact into `dest`, resuming from any bytes already
+    /// there when the backend supports ranged reads. Returns the final size.
+    /// The default buffers through [`Self::fetch_sqlite_bytes`].
+    async fn fetch_sqlite_to_path(
+        &self,
+        dataset: &DatasetId,
+        dest: &Path,
+    ) -> Result<u64, CacheError> {
+        let bytes = self.fetch_sqlite_bytes(dataset).await?;
+        std::fs::write(dest, &bytes)
+            .map_err(|e| CacheError(format!("sqlite write failed: {e}")))?;
+        Ok(bytes.len() as u64)
+    }
     async fn fetch_fasta_bytes(&self, dataset: &DatasetId) -> Result<Vec<u8>, CacheError>;
     async fn fetch_fai_bytes(&self, dataset: &DatasetId) -> Result<Vec<u8>, CacheError>;
+
+    /// `.gzi` block index of a BGZF FASTA; `Ok(None)` when none was published.
+    async fn fetch_fasta_gzi_bytes(
+        &self,
+        _dataset: &DatasetId,
+    ) -> Result<Option<Vec<u8>>, CacheError> {
+        Ok(None)
+    }
     async fn fetch_release_gene_index_bytes(
         &self,
         dataset: &DatasetId,
     ) -> Result<Vec<u8>, CacheError>;
 
+    /// Liftover chain set attached to the dataset; `Ok(None)` when none was ingested.
+    async fn fetch_liftover_chains_bytes(
+        &self,
+        _dataset: &DatasetId,
+    ) -> Result<Option<Vec<u8>>, CacheError> {
+        Ok(None)
+    }
+
+    /// Detached manifest signature; `Ok(None)` when the store publishes none.
+    async fn fetch_manifest_signature(
+        &self,
+        _dataset: &DatasetId,
+    ) -> Result<Option<ManifestSignature>, CacheError> {
+        Ok(None)
+    }
+
     async fn registry_health(&self) -> Vec<RegistrySourceHealth> {
         vec![RegistrySourceHealth {
             name: "primary".to_string(),
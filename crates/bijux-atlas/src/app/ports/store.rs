@@ -5,7 +5,7 @@ use crate::domain::dataset::{ArtifactManifest, DatasetId};
 use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 use std::time::Duration;
 
@@ -174,6 +174,39 @@ pub trait ArtifactStore {
         Ok(sqlite_bytes)
     }
 
+    /// Writes the SQLite artifact to `dest` and returns its size.
+    ///
+    /// Backends that can stream override this so multi-GB artifacts never sit in
+    /// memory; the default buffers through [`Self::get_sqlite_bytes`].
+    fn download_sqlite_to(&self, dataset: &DatasetId, dest: &Path) -> Result<u64, StoreError> {
+        let bytes = self.get_sqlite_bytes(dataset)?;
+        fs::write(dest, &bytes).map_err(|e| StoreError::new(StoreErrorCode::Io, e.to_string()))?;
+        Ok(bytes.len() as u64)
+    }
+
+    /// Publishes a dataset whose SQLite artifact is read from `sqlite_path`.
+    ///
+    /// Streaming backends upload it in parts; the default reads it into memory
+    /// and delegates to [`Self::put_dataset`].
+    fn put_dataset_from_path(
+        &self,
+        dataset: &DatasetId,
+        manifest_bytes: &[u8],
+        sqlite_path: &Path,
+        expected_manifest_sha256: &str,
+        expected_sqlite_sha256: &str,
+    ) -> Result<(), StoreError> {
+        let sqlite_bytes = fs::read(sqlite_path)
+            .map_err(|e| StoreError::new(StoreErrorCode::Io, e.to_string()))?;
+        self.put_dataset(
+            dataset,
+            manifest_bytes,
+            &sqlite_bytes,
+            expected_manifest_sha256,
+            expected_sqlite_sha256,
+        )
+    }
+
     fn publish_atomic(
         &self,
         dataset: &DatasetId,
@@ -194,19 +227,57 @@ pub trait ArtifactStore {
     fn acquire_publish_lock(&self, dataset: &DatasetId) -> Result<PublishLockGuard, StoreError>;
 }
 
+/// Held for the duration of a publish; releases the lock when dropped.
 pub struct PublishLockGuard {
-    lock_path: PathBuf,
+    release: Option<Box<dyn FnOnce() + Send>>,
+    validity: Option<Box<dyn Fn() -> bool + Send + Sync>>,
 }
 
 impl PublishLockGuard {
     pub(crate) fn new(lock_path: PathBuf) -> Self {
-        Self { lock_path }
+        Self::with_release(move || {
+            let _ = fs::remove_file(&lock_path);
+        })
+    }
+
+    /// Guard for locks that live outside the local filesystem, e.g. a lease object in a bucket.
+    pub(crate) fn with_release(release: impl FnOnce() + Send + 'static) -> Self {
+        Self {
+            release: Some(Box::new(release)),
+            validity: None,
+        }
+    }
+
+    /// Reports the lock as held only while `validity` returns true, e.g. until a lease is lost.
+    #[cfg(feature = "backend-s3")]
+    pub(crate) fn with_validity(
+        mut self,
+        validity: impl Fn() -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.validity = Some(Box::new(validity));
+        self
+    }
+
+    /// Whether the lock is still ours; publishers check this before each final write.
+    #[must_use]
+    pub fn is_held(&self) -> bool {
+        self.release.is_some() && self.validity.as_ref().is_none_or(|valid| valid())
+    }
+}
+
+impl std::fmt::Debug for PublishLockGuard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PublishLockGuard")
+            .field("held", &self.is_held())
+            .finish()
     }
 }
 
 impl Drop for PublishLockGuard {
     fn drop(&mut self) {
-        let _ = fs::remove_file(&self.lock_path);
+        if let Some(release) = self.release.take() {
+            release();
+        }
     }
 }
 
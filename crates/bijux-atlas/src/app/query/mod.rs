@@ -2,11 +2,12 @@
 
 use crate::domain::dataset::ShardCatalog;
 pub use crate::domain::query::{
-    classify_query, explain_query_plan, query_genes, BiotypePolicy, DuplicateGeneIdPolicy,
-    DuplicateTranscriptIdPolicy, FeatureIdUniquenessPolicy, GeneFields, GeneFilter, GeneNamePolicy,
-    GeneQueryRequest, IntervalSemantics, QueryLimits, QuerySort, RegionFilter,
-    SeqidNormalizationPolicy, StrandMode, TranscriptIdPolicy, TranscriptTypePolicy,
-    UnknownFeaturePolicy,
+    classify_query, explain_query_plan, query_exons_in_region, query_genes, query_transcript_exons,
+    query_transcript_introns, query_transcript_utrs, write_gene_record, AnnotationFormat,
+    BiotypePolicy, DuplicateGeneIdPolicy, DuplicateTranscriptIdPolicy, FeatureIdUniquenessPolicy,
+    GeneFields, GeneFilter, GeneNamePolicy, GeneQueryRequest, GeneRow, IntervalSemantics,
+    QueryLimits, QuerySort, RegionFilter, SeqidNormalizationPolicy, StrandMode, TranscriptIdPolicy,
+    TranscriptSequenceKind, TranscriptTypePolicy, UnknownFeaturePolicy,
 };
 use rusqlite::Connection;
 
@@ -8,6 +8,8 @@ pub struct HotEntry {
     pub body: Vec<u8>,
     pub etag: String,
     pub created_at: Instant,
+    /// Rows in `body`, charged against daily quotas on every hit.
+    pub rows_returned: u64,
 }
 
 pub struct HotQueryCache {
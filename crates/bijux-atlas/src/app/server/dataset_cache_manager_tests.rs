@@ -296,6 +296,99 @@ async fn missing_cached_sqlite_is_redownloaded_on_next_open() {
     assert!(calls >= 2, "expected re-fetch after deletion, got {calls}");
 }
 
+#[tokio::test]
+async fn encrypted_sqlite_artifact_is_decrypted_on_download() {
+    use crate::domain::security::data_protection::{AeadEncryption, EncryptionAtRest};
+    use crate::domain::security::runtime::{KeyManager, KeyRecord, SystemNonceSource};
+
+    let (ds, manifest, sqlite) = mk_dataset();
+    let cipher = AeadEncryption::new(
+        KeyManager::new(vec![KeyRecord {
+            key_id: "cache-k1".to_string(),
+            purpose: "encryption".to_string(),
+            active: true,
+        }]),
+        std::collections::BTreeMap::from([("cache-k1".to_string(), vec![3_u8; 32])]),
+        Box::new(SystemNonceSource::default()),
+    )
+    .expect("cipher");
+    let sealed = cipher.encrypt(&sqlite).expect("encrypt sqlite");
+    let store = Arc::new(FakeStore::default());
+    store.manifest.lock().await.insert(ds.clone(), manifest);
+    store.sqlite.lock().await.insert(ds.clone(), sealed);
+
+    let tmp = tempdir().expect("tempdir");
+    let cfg = DatasetCacheConfig {
+        disk_root: tmp.path().to_path_buf(),
+        ..Default::default()
+    };
+    let without_keys = DatasetCacheManager::new(cfg.clone(), store.clone());
+    let err = match without_keys.open_dataset_connection(&ds).await {
+        Ok(_) => panic!("sealed artifact must not open without keys"),
+        Err(err) => err,
+    };
+    assert!(err.to_string().contains("encrypted"), "{err}");
+
+    let tmp = tempdir().expect("tempdir");
+    let cfg = DatasetCacheConfig {
+        disk_root: tmp.path().to_path_buf(),
+        ..Default::default()
+    };
+    let mgr = DatasetCacheManager::new_with_encryption(cfg, store, Some(Arc::new(cipher)));
+    let conn = mgr
+        .open_dataset_connection(&ds)
+        .await
+        .expect("open decrypted dataset");
+    let count: i64 = conn
+        .conn
+        .query_row("SELECT COUNT(*) FROM gene_summary", [], |row| row.get(0))
+        .expect("query decrypted sqlite");
+    assert_eq!(count, 1);
+}
+
+#[tokio::test]
+async fn signed_datasets_are_required_when_configured() {
+    use crate::domain::security::data_protection::ArtifactSigningKey;
+
+    let signer = ArtifactSigningKey::from_seed_hex(&"11".repeat(32)).expect("signing key");
+    let (signed_ds, signed_manifest, signed_sqlite) = mk_dataset_for("110");
+    let (unsigned_ds, unsigned_manifest, unsigned_sqlite) = mk_dataset_for("111");
+    let signature = signer
+        .sign_manifest(&signed_manifest, &signed_manifest.checksums.sqlite_sha256)
+        .expect("sign manifest");
+    let store = Arc::new(FakeStore::default());
+    {
+        let mut manifests = store.manifest.lock().await;
+        manifests.insert(signed_ds.clone(), signed_manifest);
+        manifests.insert(unsigned_ds.clone(), unsigned_manifest);
+        let mut sqlite = store.sqlite.lock().await;
+        sqlite.insert(signed_ds.clone(), signed_sqlite);
+        sqlite.insert(unsigned_ds.clone(), unsigned_sqlite);
+    }
+    store
+        .signatures
+        .lock()
+        .await
+        .insert(signed_ds.clone(), signature);
+
+    let tmp = tempdir().expect("tempdir");
+    let cfg = DatasetCacheConfig {
+        disk_root: tmp.path().to_path_buf(),
+        trusted_signing_keys: vec![signer.public_key_hex()],
+        require_signed_datasets: true,
+        ..Default::default()
+    };
+    let mgr = DatasetCacheManager::new(cfg, store);
+    mgr.open_dataset_connection(&signed_ds)
+        .await
+        .expect("open signed dataset");
+    let err = match mgr.open_dataset_connection(&unsigned_ds).await {
+        Ok(_) => panic!("unsigned dataset must be refused"),
+        Err(err) => err,
+    };
+    assert!(err.to_string().contains("signature missing"), "{err}");
+}
+
 #[tokio::test]
 async fn failover_across_replicas_one_fails_other_serves() {
     let (ds, manifest, sqlite) = mk_dataset();
@@ -1,21 +1,31 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::adapters::inbound::serve_server_router_with_shutdown;
+use crate::adapters::inbound::tls::{TlsListener, TlsReloader};
+use crate::adapters::inbound::{
+    serve_server_router_tls_with_shutdown, serve_server_router_with_shutdown,
+};
+use crate::adapters::outbound::api_keys::ApiKeyRegistryStore;
+use crate::adapters::outbound::role_bindings::RoleBindingStore;
 use crate::adapters::outbound::store::{
-    FederatedBackend, LocalFsBackend, RegistrySource, RetryPolicy, S3LikeBackend,
+    AddressingStyle, FederatedBackend, LocalFsBackend, RegistrySource, RetryPolicy, S3LikeBackend,
+    SigV4Credentials, SigV4Signer,
 };
 use crate::adapters::outbound::telemetry::logging::LoggingConfig;
 use crate::adapters::outbound::telemetry::tracing::{init_tracing, TraceConfig, TraceExporterKind};
 use crate::app::ports::DatasetStoreBackend;
 use crate::app::server::{AppState, DatasetCacheConfig, DatasetCacheManager};
 use crate::domain::dataset::DatasetId;
+use crate::domain::security::data_protection::{AeadEncryption, EncryptionAtRest};
+use crate::domain::security::runtime::{
+    EnvSecretsProvider, KeyManager, KeyRecord, StaticSecretsProvider, SystemNonceSource,
+};
 use crate::domain::sha256_hex;
 use crate::runtime::config::{
     effective_runtime_config_payload, load_runtime_config, runtime_governance_version,
-    runtime_release_id, StoreMode,
+    runtime_release_id, StoreConfig, StoreMode,
 };
 use clap::Parser;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
@@ -377,6 +387,50 @@ async fn wait_for_shutdown_signal() -> Result<(), String> {
     Ok(())
 }
 
+/// SigV4 signer for S3 stores, present when the environment holds an access key pair.
+fn store_sigv4_signer(store: &StoreConfig) -> Option<SigV4Signer> {
+    SigV4Credentials::from_secrets(&EnvSecretsProvider)
+        .map(|credentials| SigV4Signer::new(credentials, store.s3_region.clone()))
+}
+
+/// Parses `ATLAS_STORE_ENCRYPTION_KEYS` (`key_id:hex,...`, first entry active).
+fn artifact_encryption_from_spec(
+    spec: Option<&str>,
+) -> Result<Option<Arc<dyn EncryptionAtRest + Send + Sync>>, String> {
+    let Some(spec) = spec else {
+        return Ok(None);
+    };
+    let mut records = Vec::new();
+    let mut secrets = StaticSecretsProvider::default();
+    for (idx, entry) in spec
+        .split(',')
+        .map(str::trim)
+        .filter(|x| !x.is_empty())
+        .enumerate()
+    {
+        let (key_id, hex_key) = entry
+            .split_once(':')
+            .ok_or_else(|| "ATLAS_STORE_ENCRYPTION_KEYS entries must be key_id:hex".to_string())?;
+        records.push(KeyRecord {
+            key_id: key_id.to_string(),
+            purpose: "encryption".to_string(),
+            active: idx == 0,
+        });
+        secrets
+            .values
+            .insert(key_id.to_string(), hex_key.to_string());
+    }
+    let key_ids: Vec<&str> = secrets.values.keys().map(String::as_str).collect();
+    let encryption = AeadEncryption::from_secrets(
+        KeyManager::new(records),
+        &secrets,
+        &key_ids,
+        Box::new(SystemNonceSource::default()),
+    )
+    .map_err(|e| format!("ATLAS_STORE_ENCRYPTION_KEYS: {e}"))?;
+    Ok(Some(Arc::new(encryption)))
+}
+
 fn observability_release_id() -> String {
     runtime_release_id()
 }
@@ -488,6 +542,7 @@ pub async fn main_entry() -> Result<(), String> {
         max_attempts: runtime.store.retry.max_attempts,
         base_backoff_ms: runtime.store.retry.base_backoff_ms,
     };
+    let sigv4 = store_sigv4_signer(&runtime.store);
     let backend: Arc<dyn DatasetStoreBackend> = if !runtime.store.registry_sources.is_empty() {
         let ttl = cache_cfg.registry_ttl;
         let registries: Result<Vec<RegistrySource>, String> = runtime
@@ -499,13 +554,16 @@ pub async fn main_entry() -> Result<(), String> {
                     "local" => {
                         Arc::new(LocalFsBackend::new(std::path::PathBuf::from(&row.endpoint)))
                     }
-                    "s3" => Arc::new(S3LikeBackend::new(
-                        row.endpoint.clone(),
-                        runtime.store.s3_presigned_base_url.clone(),
-                        runtime.store.s3_bearer.clone(),
-                        retry.clone(),
-                        runtime.store.allow_private_hosts,
-                    )),
+                    "s3" => Arc::new(
+                        S3LikeBackend::new(
+                            row.endpoint.clone(),
+                            runtime.store.s3_presigned_base_url.clone(),
+                            runtime.store.s3_bearer.clone(),
+                            retry.clone(),
+                            runtime.store.allow_private_hosts,
+                        )
+                        .with_sigv4(sigv4.clone()),
+                    ),
                     "http" => Arc::new(S3LikeBackend::new(
                         row.endpoint.clone(),
                         None,
@@ -534,17 +592,27 @@ pub async fn main_entry() -> Result<(), String> {
             .s3_base_url
             .clone()
             .ok_or_else(|| "ATLAS_STORE_S3_BASE_URL is required when S3 enabled".to_string())?;
-        Arc::new(S3LikeBackend::new(
+        let mut backend = S3LikeBackend::new(
             base_url,
             runtime.store.s3_presigned_base_url.clone(),
             runtime.store.s3_bearer.clone(),
             retry,
             runtime.store.allow_private_hosts,
-        ))
+        )
+        .with_sigv4(sigv4);
+        if let Some(bucket) = runtime.store.s3_bucket.as_deref() {
+            let addressing =
+                AddressingStyle::parse(&runtime.store.s3_addressing).ok_or_else(|| {
+                    "ATLAS_STORE_S3_ADDRESSING must be path or virtual-host".to_string()
+                })?;
+            backend = backend.with_bucket(bucket, addressing);
+        }
+        Arc::new(backend)
     } else {
         Arc::new(LocalFsBackend::new(runtime.store.local_root.clone()))
     };
-    let cache = DatasetCacheManager::new(cache_cfg.clone(), backend);
+    let encryption = artifact_encryption_from_spec(runtime.store.encryption_keys.as_deref())?;
+    let cache = DatasetCacheManager::new_with_encryption(cache_cfg.clone(), backend, encryption);
     cache
         .metrics
         .warmup_lock_contention_total
@@ -620,6 +688,26 @@ pub async fn main_entry() -> Result<(), String> {
     let mut state = AppState::with_config(cache.clone(), runtime.api.clone(), query_limits);
     state.runtime_policy_hash = Arc::new(runtime_policy_hash);
     state.runtime_policy_mode = Arc::new(policy_mode);
+    let role_bindings_path = runtime
+        .api
+        .authz_role_bindings_path
+        .as_deref()
+        .map(Path::new);
+    state.role_bindings = Arc::new(
+        RoleBindingStore::load(role_bindings_path)
+            .map_err(|e| format!("role bindings setup failed: {e}"))?,
+    );
+    state
+        .role_bindings
+        .spawn_reload_task(Duration::from_secs(runtime.api.authz_reload_interval_secs));
+    let api_key_registry_path = runtime.api.api_key_registry_path.as_deref().map(Path::new);
+    state.api_keys = Arc::new(
+        ApiKeyRegistryStore::load(api_key_registry_path)
+            .map_err(|e| format!("api key registry setup failed: {e}"))?,
+    );
+    state.api_keys.spawn_reload_task(Duration::from_secs(
+        runtime.api.api_key_registry_reload_interval_secs,
+    ));
     // Ready only after first successful catalog refresh when required.
     state.ready.store(false, Ordering::Relaxed);
     if let Err(e) = cache.refresh_catalog().await {
@@ -698,7 +786,7 @@ pub async fn main_entry() -> Result<(), String> {
     }
     let accepting = state.accepting_requests.clone();
     let state_for_shutdown = state.clone();
-    serve_server_router_with_shutdown(listener, state.clone(), async move {
+    let shutdown = async move {
         if let Err(err) = wait_for_shutdown_signal().await {
             warn!("shutdown signal handler failed: {err}");
         }
@@ -706,9 +794,27 @@ pub async fn main_entry() -> Result<(), String> {
         // Stop admitting heavy work first, then drain remaining requests.
         state_for_shutdown.begin_shutdown_drain_heavy();
         tokio::time::sleep(Duration::from_millis(shutdown_drain_ms)).await;
-    })
-    .await
-    .map_err(|e| format!("server failed: {e}"))
+    };
+    if !runtime.tls.enabled {
+        return serve_server_router_with_shutdown(listener, state.clone(), shutdown)
+            .await
+            .map_err(|e| format!("server failed: {e}"));
+    }
+    let reloader =
+        Arc::new(TlsReloader::load(&runtime.tls).map_err(|e| format!("tls setup failed: {e}"))?);
+    reloader.spawn_reload_task(Duration::from_secs(runtime.tls_reload_interval_secs));
+    info!(
+        event_id = "tls_enabled",
+        min_version = runtime.tls.min_version.as_str(),
+        client_verification = runtime.tls.ca_path.is_some(),
+        fingerprint = reloader.active_fingerprint().unwrap_or_default(),
+        "in-process tls termination enabled"
+    );
+    let listener = TlsListener::new(listener, Arc::clone(&reloader))
+        .map_err(|e| format!("tls listener failed: {e}"))?;
+    serve_server_router_tls_with_shutdown(listener, state.clone(), shutdown)
+        .await
+        .map_err(|e| format!("server failed: {e}"))
 }
 
 #[cfg(test)]
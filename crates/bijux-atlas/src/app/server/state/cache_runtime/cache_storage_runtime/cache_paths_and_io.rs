@@ -7,9 +7,11 @@ pub(crate) struct LocalCachePaths {
     pub(crate) derived_dir: PathBuf,
     pub(crate) fasta: PathBuf,
     pub(crate) fai: PathBuf,
+    pub(crate) fasta_gzi: PathBuf,
     pub(crate) sqlite: PathBuf,
     pub(crate) manifest: PathBuf,
     pub(crate) release_gene_index: PathBuf,
+    pub(crate) liftover_chains: PathBuf,
 }
 
 pub(crate) fn local_cache_paths(root: &Path, cache_key: &str) -> LocalCachePaths {
@@ -22,9 +24,11 @@ pub(crate) fn local_cache_paths(root: &Path, cache_key: &str) -> LocalCachePaths
         derived_dir: derived_dir.clone(),
         fasta: inputs_dir.join("genome.fa.bgz"),
         fai: inputs_dir.join("genome.fa.bgz.fai"),
+        fasta_gzi: inputs_dir.join("genome.fa.bgz.gzi"),
         sqlite: derived_dir.join("gene_summary.sqlite"),
         manifest: derived_dir.join("manifest.json"),
         release_gene_index: derived_dir.join("release_gene_index.json"),
+        liftover_chains: derived_dir.join("liftover_chains.json"),
     }
 }
 
@@ -51,6 +55,34 @@ pub(super) fn safe_cache_key(key: &str) -> Result<(), CacheError> {
     Ok(())
 }
 
+/// SHA-256 of a file read in fixed-size chunks, so large artifacts are never buffered whole.
+pub(super) fn sha256_file_hex(path: &Path) -> Result<String, CacheError> {
+    use sha2::{Digest, Sha256};
+    use std::io::Read as _;
+    let mut file = std::fs::File::open(path).map_err(|e| CacheError(e.to_string()))?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0_u8; 1 << 20];
+    loop {
+        let n = file.read(&mut buf).map_err(|e| CacheError(e.to_string()))?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Peeks at the leading bytes so plaintext downloads skip the in-memory decrypt pass.
+pub(super) fn file_starts_with_encryption_header(path: &Path) -> Result<bool, CacheError> {
+    use std::io::Read as _;
+    let file = std::fs::File::open(path).map_err(|e| CacheError(e.to_string()))?;
+    let mut head = Vec::with_capacity(8);
+    file.take(8)
+        .read_to_end(&mut head)
+        .map_err(|e| CacheError(e.to_string()))?;
+    Ok(is_encrypted_artifact(&head))
+}
+
 pub(super) fn write_atomic_file(path: &Path, bytes: &[u8]) -> Result<(), CacheError> {
     let parent = path
         .parent()
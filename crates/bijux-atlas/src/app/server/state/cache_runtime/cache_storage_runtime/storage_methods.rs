@@ -1,7 +1,17 @@
 use super::*;
+use crate::app::ingest::{build_gzi_index, encode_gzi_index, InputCompression};
 
 impl DatasetCacheManager {
     pub fn new(cfg: DatasetCacheConfig, store: Arc<dyn DatasetStoreBackend>) -> Arc<Self> {
+        Self::new_with_encryption(cfg, store, None)
+    }
+
+    /// Like [`Self::new`], decrypting sealed SQLite artifacts with `encryption` on download.
+    pub fn new_with_encryption(
+        cfg: DatasetCacheConfig,
+        store: Arc<dyn DatasetStoreBackend>,
+        encryption: Option<Arc<dyn EncryptionAtRest + Send + Sync>>,
+    ) -> Arc<Self> {
         let disk_root = crate::runtime::config::resolve_runtime_path(cfg.disk_root.clone());
         let cfg = DatasetCacheConfig { disk_root, ..cfg };
         let max_concurrent_downloads = cfg
@@ -26,6 +36,9 @@ impl DatasetCacheManager {
             download_semaphore: Arc::new(Semaphore::new(max_concurrent_downloads)),
             retry_budget_remaining: AtomicU64::new(retry_budget),
             dataset_retry_budget: Mutex::new(HashMap::new()),
+            liftovers: Mutex::new(HashMap::new()),
+            contig_aliases: Mutex::new(HashMap::new()),
+            encryption,
             metrics: Arc::new(CacheMetrics::default()),
         })
     }
@@ -69,8 +82,35 @@ impl DatasetCacheManager {
         if sha256_hex(&fai) != manifest.checksums.fai_sha256 {
             return Err(CacheError("fai checksum verification failed".to_string()));
         }
+        let gzi = if manifest.checksums.fasta_gzi_sha256.is_empty() {
+            None
+        } else {
+            let gzi = self
+                .store
+                .fetch_fasta_gzi_bytes(dataset)
+                .await?
+                .ok_or_else(|| CacheError("fasta gzi missing from store".to_string()))?;
+            if sha256_hex(&gzi) != manifest.checksums.fasta_gzi_sha256 {
+                return Err(CacheError(
+                    "fasta gzi checksum verification failed".to_string(),
+                ));
+            }
+            Some(gzi)
+        };
         std::fs::create_dir_all(&paths.inputs_dir).map_err(|e| CacheError(e.to_string()))?;
+        // The FAI goes last: once it exists, BGZF windows can seek through the `.gzi`.
+        if let Some(gzi) = &gzi {
+            write_atomic_file(&paths.fasta_gzi, gzi)?;
+        }
         write_atomic_file(&paths.fasta, &fasta)?;
+        if gzi.is_none()
+            && InputCompression::detect(&fasta[..fasta.len().min(18)]) == InputCompression::Bgzf
+        {
+            // Artifacts published before the index was: build it from the block headers.
+            let entries = build_gzi_index(&paths.fasta)
+                .map_err(|e| CacheError(format!("fasta gzi build failed: {}", e.0)))?;
+            write_atomic_file(&paths.fasta_gzi, &encode_gzi_index(&entries))?;
+        }
         write_atomic_file(&paths.fai, &fai)?;
         Ok((paths.fasta, paths.fai))
     }
@@ -111,6 +151,92 @@ impl DatasetCacheManager {
         Ok(paths.release_gene_index)
     }
 
+    /// Path of the cached liftover chain set, or `None` when the dataset has none.
+    pub async fn ensure_liftover_chains_cached(
+        &self,
+        dataset: &DatasetId,
+    ) -> Result<Option<PathBuf>, CacheError> {
+        async { self.ensure_dataset_cached(dataset).await }
+            .instrument(tracing::info_span!(
+                "dataset_loading",
+                dataset = %dataset.canonical_string()
+            ))
+            .await?;
+        let paths = self.resolve_cache_paths(dataset).await?;
+        if paths.liftover_chains.exists() {
+            return Ok(Some(paths.liftover_chains));
+        }
+        if self.cfg.cached_only_mode {
+            return Err(CacheError(
+                "liftover chains missing from cache and cached-only mode is enabled".to_string(),
+            ));
+        }
+        if self.cfg.read_only_fs {
+            return Err(CacheError(
+                "liftover chains missing from cache and read-only filesystem mode is enabled"
+                    .to_string(),
+            ));
+        }
+        let bytes = async { self.store.fetch_liftover_chains_bytes(dataset).await }
+            .instrument(tracing::info_span!(
+                "artifact_loading",
+                dataset = %dataset.canonical_string()
+            ))
+            .await?;
+        let Some(bytes) = bytes else {
+            return Ok(None);
+        };
+        ensure_secure_dir(&paths.derived_dir)?;
+        write_atomic_file(&paths.liftover_chains, &bytes)?;
+        Ok(Some(paths.liftover_chains))
+    }
+
+    /// Parsed liftover chains of `dataset`, kept in memory after the first load.
+    pub async fn load_liftover(
+        &self,
+        dataset: &DatasetId,
+    ) -> Result<Option<Arc<Liftover>>, CacheError> {
+        if let Some(hit) = self.liftovers.lock().await.get(dataset) {
+            return Ok(Some(Arc::clone(hit)));
+        }
+        let Some(path) = self.ensure_liftover_chains_cached(dataset).await? else {
+            return Ok(None);
+        };
+        let bytes = std::fs::read(&path)
+            .map_err(|e| CacheError(format!("liftover chains read failed: {e}")))?;
+        let set: LiftoverChainSet = serde_json::from_slice(&bytes)
+            .map_err(|e| CacheError(format!("liftover chains parse failed: {e}")))?;
+        set.validate()
+            .map_err(|e| CacheError(format!("liftover chains invalid: {e}")))?;
+        let liftover = Arc::new(Liftover::new(set));
+        self.liftovers
+            .lock()
+            .await
+            .insert(dataset.clone(), Arc::clone(&liftover));
+        Ok(Some(liftover))
+    }
+
+    /// Contig aliases of `dataset`, kept in memory after the first load.
+    pub async fn load_contig_aliases(
+        &self,
+        dataset: &DatasetId,
+    ) -> Result<Arc<ContigAliases>, CacheError> {
+        if let Some(hit) = self.contig_aliases.lock().await.get(dataset) {
+            return Ok(Arc::clone(hit));
+        }
+        let aliases = {
+            let c = self.open_dataset_connection(dataset).await?;
+            query_contig_aliases(&c.conn)
+                .map_err(|e| CacheError(format!("contig aliases read failed: {e}")))?
+        };
+        let aliases = Arc::new(aliases);
+        self.contig_aliases
+            .lock()
+            .await
+            .insert(dataset.clone(), Arc::clone(&aliases));
+        Ok(aliases)
+    }
+
     pub async fn open_dataset_connection(
         &self,
         dataset: &DatasetId,
@@ -328,7 +454,9 @@ impl DatasetCacheManager {
             .acquire_owned()
             .await
             .map_err(|e| CacheError(e.to_string()))?;
-        let (manifest, sqlite, release_gene_index) = async {
+        let tmp_dir = self.cfg.disk_root.join(".tmp-atlas-download");
+        ensure_secure_dir(&tmp_dir)?;
+        let (manifest, tmp_sqlite, release_gene_index) = async {
             let manifest = match self.store.fetch_manifest(dataset).await {
                 Ok(v) => v,
                 Err(e) => {
@@ -342,8 +470,18 @@ impl DatasetCacheManager {
                 .lock()
                 .await
                 .push(started.elapsed().as_nanos() as u64);
-            let sqlite = match self.store.fetch_sqlite_bytes(dataset).await {
-                Ok(v) => v,
+            // Keyed by dataset, expected digest and process so a retry resumes
+            // its own partial file and never appends to another process's.
+            let tmp_sqlite = tmp_dir.join(format!(
+                "{}__{}__{}.{}.{}.sqlite.partial",
+                dataset.release.as_str(),
+                dataset.species.as_str(),
+                dataset.assembly.as_str(),
+                manifest.checksums.sqlite_sha256,
+                std::process::id()
+            ));
+            match self.store.fetch_sqlite_to_path(dataset, &tmp_sqlite).await {
+                Ok(_) => {}
                 Err(e) => {
                     self.record_store_download_failure(self.store.backend_tag(), &e.to_string())
                         .await;
@@ -355,7 +493,7 @@ impl DatasetCacheManager {
                 .fetch_release_gene_index_bytes(dataset)
                 .await
                 .ok();
-            Ok::<_, CacheError>((manifest, sqlite, release_gene_index))
+            Ok::<_, CacheError>((manifest, tmp_sqlite, release_gene_index))
         }
         .instrument(tracing::info_span!(
             "ingest_pipeline",
@@ -367,15 +505,37 @@ impl DatasetCacheManager {
             backend = self.store.backend_tag()
         ))
         .await?;
-        let sqlite_hash = async { sha256_hex(&sqlite) }
+        self.verify_manifest_signature(dataset, &manifest).await?;
+        let tmp_sqlite = if file_starts_with_encryption_header(&tmp_sqlite)? {
+            let opened = tmp_dir.join(format!(
+                "{}__{}__{}.sqlite.{}.tmp",
+                dataset.release.as_str(),
+                dataset.species.as_str(),
+                dataset.assembly.as_str(),
+                std::process::id()
+            ));
+            let decrypted = self
+                .decrypt_sqlite_artifact(dataset, &tmp_sqlite, &opened)
+                .await;
+            let _ = std::fs::remove_file(&tmp_sqlite);
+            if let Err(e) = decrypted {
+                let _ = std::fs::remove_file(&opened);
+                return Err(e);
+            }
+            opened
+        } else {
+            tmp_sqlite
+        };
+        let sqlite_hash = async { sha256_file_hex(&tmp_sqlite) }
             .instrument(tracing::info_span!(
                 "encryption_integrity_verify",
                 dataset = %dataset.canonical_string()
             ))
-            .await;
+            .await?;
         self.record_data_protection_event("encryption.operation", dataset)
             .await;
         if sqlite_hash != manifest.checksums.sqlite_sha256 {
+            let _ = std::fs::remove_file(&tmp_sqlite);
             error!(
                 event_id = "ingest_anomaly_checksum_mismatch",
                 dataset_id = %dataset.canonical_string(),
@@ -405,11 +565,6 @@ impl DatasetCacheManager {
         let lease_path = paths.cache_root.join(".lease.lock");
         let _lease = acquire_artifact_lease(&lease_path, Duration::from_secs(10))?;
 
-        let tmp_dir = self.cfg.disk_root.join(".tmp-atlas-download");
-        ensure_secure_dir(&tmp_dir)?;
-
-        let tmp_sqlite = tmp_dir.join(format!("gene_summary.sqlite.{}.tmp", std::process::id()));
-        write_atomic_file(&tmp_sqlite, &sqlite)?;
         std::fs::rename(&tmp_sqlite, &paths.sqlite).map_err(|e| CacheError(e.to_string()))?;
         if let Some(parent) = paths.sqlite.parent() {
             if let Ok(dir) = std::fs::File::open(parent) {
@@ -490,6 +645,80 @@ impl DatasetCacheManager {
         Ok(())
     }
 
+    /// Enforces trusted Ed25519 manifest signatures when keys are configured.
+    async fn verify_manifest_signature(
+        &self,
+        dataset: &DatasetId,
+        manifest: &ArtifactManifest,
+    ) -> Result<(), CacheError> {
+        if self.cfg.trusted_signing_keys.is_empty() && !self.cfg.require_signed_datasets {
+            return Ok(());
+        }
+        let trusted = TrustedSigningKeys::from_public_keys_hex(&self.cfg.trusted_signing_keys)
+            .map_err(|e| CacheError(e.to_string()))?;
+        let signature = self.store.fetch_manifest_signature(dataset).await?;
+        if signature.is_none() && !self.cfg.require_signed_datasets {
+            return Ok(());
+        }
+        if let Err(e) = trusted.verify_manifest(
+            manifest,
+            &manifest.checksums.sqlite_sha256,
+            signature.as_ref(),
+        ) {
+            error!(
+                event_id = "ingest_anomaly_signature_rejected",
+                dataset_id = %dataset.canonical_string(),
+                "dataset signature rejected: {e}"
+            );
+            self.record_data_protection_event("tamper.detected", dataset)
+                .await;
+            self.record_store_download_failure(
+                self.store.backend_tag(),
+                "signature verification failed",
+            )
+            .await;
+            return Err(CacheError(format!(
+                "refusing dataset {}: {e}",
+                dataset.canonical_string()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Streams the AEAD-sealed artifact at `sealed` into `plain`.
+    async fn decrypt_sqlite_artifact(
+        &self,
+        dataset: &DatasetId,
+        sealed: &Path,
+        plain: &Path,
+    ) -> Result<(), CacheError> {
+        let Some(encryption) = self.encryption.as_ref() else {
+            return Err(CacheError(
+                "sqlite artifact is encrypted but no encryption keys are configured".to_string(),
+            ));
+        };
+        match encryption.decrypt_file(sealed, plain) {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                error!(
+                    event_id = "ingest_anomaly_decrypt_failed",
+                    dataset_id = %dataset.canonical_string(),
+                    "dataset decrypt failed: {e}"
+                );
+                self.record_data_protection_event("tamper.detected", dataset)
+                    .await;
+                self.metrics
+                    .store_download_failures
+                    .fetch_add(1, Ordering::Relaxed);
+                self.record_store_download_failure(self.store.backend_tag(), "decryption failed")
+                    .await;
+                Err(CacheError(format!(
+                    "sqlite artifact decryption failed: {e}"
+                )))
+            }
+        }
+    }
+
     async fn is_cached_and_verified(&self, dataset: &DatasetId) -> Result<bool, CacheError> {
         let paths = self.resolve_cache_paths(dataset).await?;
         if !paths.sqlite.exists() || !paths.manifest.exists() {
@@ -701,9 +930,11 @@ impl DatasetCacheManager {
                     derived_dir: derived_dir.clone(),
                     fasta: inputs_dir.join("genome.fa.bgz"),
                     fai: inputs_dir.join("genome.fa.bgz.fai"),
+                    fasta_gzi: inputs_dir.join("genome.fa.bgz.gzi"),
                     sqlite: derived_dir.join("gene_summary.sqlite"),
                     manifest: derived_dir.join("manifest.json"),
                     release_gene_index: derived_dir.join("release_gene_index.json"),
+                    liftover_chains: derived_dir.join("liftover_chains.json"),
                 });
             }
         }
@@ -723,9 +954,11 @@ impl DatasetCacheManager {
             derived_dir: legacy.derived_dir,
             fasta: legacy.fasta,
             fai: legacy.fai,
+            fasta_gzi: legacy.fasta_gzi,
             sqlite: legacy.sqlite,
             manifest: legacy.manifest,
             release_gene_index: legacy.release_gene_index,
+            liftover_chains: legacy.liftover_chains,
         })
     }
 }
@@ -8,11 +8,16 @@ use crate::domain::cluster::replication::ReplicaRegistry;
 use crate::domain::cluster::resilience::FailureRecoveryRegistry;
 use crate::domain::cluster::sharding::ShardRegistry;
 use crate::domain::dataset::{artifact_paths, ArtifactManifest, Catalog, DatasetId};
+use crate::domain::security::data_protection::{
+    is_encrypted_artifact, EncryptionAtRest, TrustedSigningKeys,
+};
 use crate::domain::sha256_hex;
 use crate::runtime::config::ApiConfig;
 use crate::StatusCode;
 use crate::{chrono_like_unix_millis, route_sli_class};
-use bijux_atlas::domain::query::QueryLimits;
+use bijux_atlas::domain::query::{
+    query_contig_aliases, ContigAliases, Liftover, LiftoverChainSet, QueryLimits,
+};
 use rusqlite::Connection;
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::{Path, PathBuf};
@@ -62,6 +67,10 @@ pub struct DatasetCacheConfig {
     pub quarantine_retry_ttl: Duration,
     pub registry_ttl: Duration,
     pub registry_freeze_mode: bool,
+    /// Hex Ed25519 public keys whose manifest signatures are accepted.
+    pub trusted_signing_keys: Vec<String>,
+    /// Refuse datasets without a manifest signature from a trusted key.
+    pub require_signed_datasets: bool,
 }
 
 impl Default for DatasetCacheConfig {
@@ -102,6 +111,8 @@ impl Default for DatasetCacheConfig {
             quarantine_retry_ttl: Duration::from_secs(300),
             registry_ttl: Duration::from_secs(15),
             registry_freeze_mode: false,
+            trusted_signing_keys: Vec::new(),
+            require_signed_datasets: false,
         }
     }
 }
@@ -395,7 +406,11 @@ pub(crate) struct StoreBreakerState {
     pub(crate) open_until: Option<Instant>,
 }
 
+use crate::adapters::outbound::api_keys::ApiKeyRegistryStore;
+use crate::adapters::outbound::oidc::JwksCache;
 use crate::adapters::outbound::redis::RedisBackend;
+use crate::adapters::outbound::role_bindings::RoleBindingStore;
+use crate::adapters::outbound::telemetry::quota_ledger::QuotaLedger;
 use crate::adapters::outbound::telemetry::rate_limiter::RateLimiter;
 
 pub struct DatasetConnection {
@@ -431,6 +446,9 @@ pub struct DatasetCacheManager {
     pub(crate) shard_open_semaphore: Arc<Semaphore>,
     pub(crate) retry_budget_remaining: AtomicU64,
     pub(crate) dataset_retry_budget: Mutex<HashMap<DatasetId, u32>>,
+    pub(crate) liftovers: Mutex<HashMap<DatasetId, Arc<Liftover>>>,
+    pub(crate) contig_aliases: Mutex<HashMap<DatasetId, Arc<ContigAliases>>>,
+    pub(crate) encryption: Option<Arc<dyn EncryptionAtRest + Send + Sync>>,
     pub metrics: Arc<CacheMetrics>,
 }
 
@@ -444,6 +462,8 @@ pub struct AppState {
     pub(crate) ip_limiter: Arc<RateLimiter>,
     pub(crate) sequence_ip_limiter: Arc<RateLimiter>,
     pub(crate) api_key_limiter: Arc<RateLimiter>,
+    pub(crate) tenant_limiter: Arc<RateLimiter>,
+    pub(crate) quota_ledger: Arc<QuotaLedger>,
     pub class_cheap: Arc<Semaphore>,
     pub class_medium: Arc<Semaphore>,
     pub class_heavy: Arc<Semaphore>,
@@ -453,6 +473,9 @@ pub struct AppState {
     pub(crate) coalescer: Arc<cache::coalesce::QueryCoalescer>,
     pub(crate) hot_query_cache: Arc<Mutex<cache::hot::HotQueryCache>>,
     pub(crate) redis_backend: Option<Arc<RedisBackend>>,
+    pub(crate) oidc_keys: Option<Arc<JwksCache>>,
+    pub role_bindings: Arc<RoleBindingStore>,
+    pub api_keys: Arc<ApiKeyRegistryStore>,
     pub(crate) queued_requests: Arc<AtomicU64>,
     pub(crate) membership: Arc<Mutex<MembershipRegistry>>,
     pub(crate) shard_registry: Arc<Mutex<ShardRegistry>>,
@@ -88,6 +88,14 @@ fn gene_row_dto(
                 .map_or(Value::Null, |x| Value::Number(x.into())),
         );
     }
+    if include_field(requested, IncludeField::Strand) {
+        map.insert(
+            "strand".to_string(),
+            row.strand
+                .as_ref()
+                .map_or(Value::Null, |x| Value::String(x.clone())),
+        );
+    }
     Ok(Value::Object(map))
 }
 
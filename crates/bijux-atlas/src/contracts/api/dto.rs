@@ -60,3 +60,266 @@ pub struct ListGenesResponseDto {
     pub data: GeneRowsDto,
     pub links: Option<LinkCursorDto>,
 }
+
+/// Success envelope shared by the v1 JSON endpoints, as read by clients.
+///
+/// Read-side DTOs below accept unknown fields so additive server changes do
+/// not break deployed clients.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResponseEnvelopeDto<T> {
+    pub api_version: String,
+    pub contract_version: String,
+    #[serde(default)]
+    pub dataset: Option<DatasetKeyDto>,
+    #[serde(default)]
+    pub page: Option<PageCursorDto>,
+    pub data: T,
+    #[serde(default)]
+    pub links: Option<LinkCursorDto>,
+    #[serde(default)]
+    pub meta: Option<Value>,
+}
+
+impl<T> ResponseEnvelopeDto<T> {
+    #[must_use]
+    pub fn next_cursor(&self) -> Option<&str> {
+        self.page
+            .as_ref()
+            .and_then(|page| page.next_cursor.as_deref())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GeneRowDto {
+    pub gene_id: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub seqid: Option<String>,
+    #[serde(default)]
+    pub start: Option<u64>,
+    #[serde(default)]
+    pub end: Option<u64>,
+    #[serde(default)]
+    pub biotype: Option<String>,
+    #[serde(default)]
+    pub transcript_count: Option<u64>,
+    #[serde(default)]
+    pub sequence_length: Option<u64>,
+    #[serde(default)]
+    pub strand: Option<String>,
+}
+
+/// How a requested seqid was mapped onto the name the dataset stores.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContigResolutionDto {
+    pub requested: String,
+    pub seqid: String,
+    pub alias_applied: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GenePageDto {
+    #[serde(default)]
+    pub class: Option<String>,
+    pub rows: Vec<GeneRowDto>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub contigs: Vec<ContigResolutionDto>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranscriptRowDto {
+    pub transcript_id: String,
+    pub parent_gene_id: String,
+    pub transcript_type: String,
+    #[serde(default)]
+    pub biotype: Option<String>,
+    pub seqid: String,
+    pub start: u64,
+    pub end: u64,
+    pub exon_count: u64,
+    pub total_exon_span: u64,
+    pub cds_present: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GeneTranscriptsDto {
+    pub gene_id: String,
+    pub rows: Vec<TranscriptRowDto>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contig: Option<ContigResolutionDto>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranscriptDto {
+    pub transcript: TranscriptRowDto,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExonDto {
+    pub exon_id: String,
+    pub transcript_id: String,
+    pub seqid: String,
+    pub start: u64,
+    pub end: u64,
+    pub length: u64,
+    #[serde(default)]
+    pub rank: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntronDto {
+    pub transcript_id: String,
+    pub seqid: String,
+    pub start: u64,
+    pub end: u64,
+    pub length: u64,
+    pub rank: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UtrDto {
+    pub transcript_id: String,
+    pub seqid: String,
+    pub kind: String,
+    pub start: u64,
+    pub end: u64,
+    pub length: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranscriptExonsDto {
+    pub transcript_id: String,
+    pub exons: Vec<ExonDto>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranscriptIntronsDto {
+    pub transcript_id: String,
+    pub introns: Vec<IntronDto>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranscriptUtrsDto {
+    pub transcript_id: String,
+    pub utrs: Vec<UtrDto>,
+}
+
+/// Genomic slice a sequence was cut from, after strand and flank handling.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SequenceRegionDto {
+    pub seqid: String,
+    pub start: u64,
+    pub end: u64,
+    #[serde(default)]
+    pub strand: Option<String>,
+    #[serde(default)]
+    pub upstream: u64,
+    #[serde(default)]
+    pub downstream: u64,
+    #[serde(default)]
+    pub mask: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SequenceTranscriptDto {
+    pub transcript_id: String,
+    pub seqid: String,
+    pub strand: String,
+    pub kind: String,
+    #[serde(default)]
+    pub genetic_code: Option<u8>,
+}
+
+/// Region, gene and transcript sequence payloads; exactly one of `region`
+/// and `transcript` is set.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SequenceDto {
+    pub length: u64,
+    pub sequence: String,
+    #[serde(default)]
+    pub region: Option<SequenceRegionDto>,
+    #[serde(default)]
+    pub transcript: Option<SequenceTranscriptDto>,
+    #[serde(default)]
+    pub sequence_meta: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contig: Option<ContigResolutionDto>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiffRecordDto {
+    pub gene_id: String,
+    pub status: String,
+    #[serde(default)]
+    pub seqid: Option<String>,
+    #[serde(default)]
+    pub start: Option<u64>,
+    #[serde(default)]
+    pub end: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiffPageDto {
+    pub diff: Vec<DiffRecordDto>,
+    #[serde(default)]
+    pub qc: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contig: Option<ContigResolutionDto>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CatalogEntryDto {
+    pub dataset: DatasetKeyDto,
+    pub manifest_path: String,
+    pub sqlite_path: String,
+    #[serde(default)]
+    pub diff_artifacts: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DatasetListDto {
+    pub items: Vec<CatalogEntryDto>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DatasetDetailDto {
+    pub dataset: DatasetKeyDto,
+    pub artifact_hash: String,
+    #[serde(default)]
+    pub available_endpoints: Vec<String>,
+    #[serde(default)]
+    pub metadata: Option<Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DatasetItemDto {
+    pub item: DatasetDetailDto,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionPluginDto {
+    pub name: String,
+    pub version: String,
+    pub compatible_umbrella: String,
+    pub build_hash: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionServerDto {
+    #[serde(rename = "crate")]
+    pub crate_name: String,
+    pub config_schema_version: String,
+    pub runtime_policy_hash: String,
+    #[serde(default)]
+    pub artifact_schema_versions: std::collections::BTreeMap<String, String>,
+}
+
+/// `/v1/version` is not enveloped; it is decoded directly.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionDto {
+    pub api_version: String,
+    pub contract_version: String,
+    pub plugin: VersionPluginDto,
+    pub server: VersionServerDto,
+}
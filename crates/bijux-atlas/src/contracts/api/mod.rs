@@ -32,7 +32,8 @@ pub use errors::{ApiError, ApiErrorCode};
 pub use openapi::openapi_v1_spec;
 pub use params::{
     parse_list_genes_params, parse_list_genes_params_with_limit, parse_range_filter,
-    parse_region_filter, IncludeField, ListGenesParams, MAX_CURSOR_BYTES,
+    parse_region_filter, resolve_region_alias, resolve_region_aliases, IncludeField,
+    ListGenesParams, MAX_CURSOR_BYTES,
 };
 pub use responses::{ApiContentType, ApiResponseEnvelope, ContentNegotiation};
 pub use wire::{list_genes_v1, QueryAdapter};
@@ -107,6 +108,26 @@ mod tests {
         assert_eq!(err.code, ApiErrorCode::InvalidQueryParameter);
     }
 
+    #[test]
+    fn region_aliases_resolve_and_collapse_duplicate_spellings() {
+        use crate::domain::query::{ContigAlias, ContigAliases};
+        let aliases = ContigAliases::new([ContigAlias {
+            alias: "1".to_string(),
+            contig: "chr1".to_string(),
+            source: "chr_prefix".to_string(),
+        }]);
+        let mut regions: Vec<_> = ["1:10-20", "chr1:10-20", "chr2:5-6"]
+            .into_iter()
+            .filter_map(|raw| parse_region_filter(Some(raw.to_string())).expect("region"))
+            .collect();
+        let resolved = resolve_region_aliases(&mut regions, &aliases);
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions[0].seqid, "chr1");
+        assert_eq!(resolved.len(), 3);
+        assert!(resolved[0].alias_applied && resolved[0].seqid == "chr1");
+        assert!(!resolved[1].alias_applied);
+    }
+
     #[test]
     fn parse_range_strict_and_stable() {
         let parsed = parse_range_filter(Some("chr1:10-20".to_string())).expect("range parse");
@@ -134,12 +155,22 @@ mod tests {
             "/v1/releases/{release}/species/{species}/assemblies/{assembly}",
             "/v1/genes",
             "/v1/genes/count",
+            "/v1/genes/query",
+            "/v1/genes/search",
             "/v1/diff/genes",
             "/v1/diff/region",
+            "/v1/liftover",
             "/v1/sequence/region",
             "/v1/genes/{gene_id}/sequence",
             "/v1/genes/{gene_id}/transcripts",
             "/v1/transcripts/{tx_id}",
+            "/v1/transcripts/{tx_id}/sequence",
+            "/v1/transcripts/{tx_id}/exons",
+            "/v1/transcripts/{tx_id}/introns",
+            "/v1/transcripts/{tx_id}/utrs",
+            "/v1/exons",
+            "/v1/sequence/batch",
+            "/v1/usage",
             "/debug/datasets",
             "/debug/dataset-health",
         ] {
@@ -3,7 +3,7 @@
 use serde_json::{json, Map, Value};
 
 pub const OPENAPI_V1_PINNED_SHA256: &str =
-    "20bccf9b12503924f3f88c5bd8eca391386fc330be106efbd54fd41d4af9afff";
+    "215b6f3e6f474af8966ccf2c9a22acd5aaac81d0c9ea6c769d00b57bf9667080";
 
 #[must_use]
 pub fn openapi_v1_spec() -> Value {
@@ -139,9 +139,12 @@ pub fn openapi_v1_spec() -> Value {
               {"name": "name_like", "in": "query", "schema": {"type": "string"}},
               {"name": "biotype", "in": "query", "schema": {"type": "string"}},
               {"name": "contig", "in": "query", "schema": {"type": "string"}},
-              {"name": "range", "in": "query", "schema": {"type": "string", "pattern": "^[^:]+:[0-9]+-[0-9]+$"}},
+              {"name": "range", "in": "query", "schema": {"type": "string", "pattern": "^[^:]+:[0-9]+-[0-9]+$", "description": "seqid may be any contig alias recorded at ingest; the stored name is reported in data.contigs"}},
               {"name": "min_transcripts", "in": "query", "schema": {"type": "integer", "minimum": 0}},
               {"name": "max_transcripts", "in": "query", "schema": {"type": "integer", "minimum": 0}},
+              {"name": "strand", "in": "query", "schema": {"type": "string", "enum": ["any", "plus", "minus", "unknown"]}},
+              {"name": "xref", "in": "query", "schema": {"type": "string", "pattern": "^[^:]+:.+$", "description": "GFF3 Dbxref/Ontology_term cross-reference, e.g. HGNC:1100"}},
+              {"name": "alias", "in": "query", "schema": {"type": "string", "minLength": 1, "description": "GFF3 Alias value"}},
               {"name": "sort", "in": "query", "schema": {"type": "string", "enum": ["gene_id:asc", "region:asc"]}},
               {"name": "limit", "in": "query", "schema": {"type": "integer", "minimum": 1, "maximum": 500}},
               {"name": "cursor", "in": "query", "schema": {"type": "string", "maxLength": 4096}},
@@ -152,11 +155,13 @@ pub fn openapi_v1_spec() -> Value {
                   {"type": "string", "enum": ["coords"]},
                   {"type": "string", "enum": ["biotype"]},
                   {"type": "string", "enum": ["counts"]},
-                  {"type": "string", "enum": ["length"]}
+                  {"type": "string", "enum": ["length"]},
+                  {"type": "string", "enum": ["strand"]}
                 ]
               }},
               {"name": "pretty", "in": "query", "schema": {"type": "boolean"}},
-              {"name": "explain", "in": "query", "schema": {"type": "boolean", "description": "embed extraction policy details"}}
+              {"name": "explain", "in": "query", "schema": {"type": "boolean", "description": "embed extraction policy details"}},
+              {"name": "format", "in": "query", "schema": {"type": "string", "enum": ["bed", "gtf", "gff3"], "description": "annotation records instead of the JSON envelope; the page cursor is returned in x-atlas-next-cursor"}}
             ],
             "responses": {
               "200": {
@@ -175,7 +180,13 @@ pub fn openapi_v1_spec() -> Value {
                         }
                       }
                     }
-                  }
+                  },
+                  "application/vnd.apache.arrow.stream": {
+                    "schema": {"type": "string", "format": "binary", "description": "Arrow IPC stream of data.rows; the page cursor is returned in x-atlas-next-cursor"}
+                  },
+                  "text/x-bed": {"schema": {"type": "string", "description": "BED6, one line per gene"}},
+                  "text/x-gtf": {"schema": {"type": "string"}},
+                  "text/x-gff3": {"schema": {"type": "string"}}
                 }
               },
               "304": {"description": "not modified"},
@@ -210,6 +221,73 @@ pub fn openapi_v1_spec() -> Value {
             }
           }
         },
+        "/v1/genes/query": {
+          "post": {
+            "requestBody": {
+              "required": true,
+              "content": {"application/json": {"schema": {
+                "type":"object",
+                "additionalProperties":false,
+                "properties": {
+                  "dataset":{"type":"string","description":"canonical dataset selector release/species/assembly"},
+                  "release":{"type":"string"},
+                  "species":{"type":"string"},
+                  "assembly":{"type":"string"},
+                  "filter":{
+                    "type":"object",
+                    "additionalProperties":false,
+                    "description":"list values are ORed; distinct filters are ANDed",
+                    "properties": {
+                      "gene_id":{"oneOf":[{"type":"string"},{"type":"array","items":{"type":"string","minLength":1},"maxItems":5000}]},
+                      "biotype":{"oneOf":[{"type":"string"},{"type":"array","items":{"type":"string","minLength":1},"maxItems":5000}]},
+                      "region":{"oneOf":[{"type":"string","pattern":"^[^:]+:[0-9]+-[0-9]+$"},{"type":"array","items":{"type":"string","pattern":"^[^:]+:[0-9]+-[0-9]+$"},"maxItems":5000}]},
+                      "name":{"type":"string"},
+                      "name_like":{"type":"string"},
+                      "strand":{"type":"string","enum":["any","plus","minus","unknown"]},
+                      "xref":{"type":"string","pattern":"^[^:]+:.+$"},
+                      "alias":{"type":"string","minLength":1}
+                    }
+                  },
+                  "include":{"type":"array","items":{"type":"string","enum":["coords","biotype","counts","length","strand"]}},
+                  "sort":{"type":"string","enum":["gene_id:asc","region:asc"]},
+                  "interval_mode":{"type":"string","enum":["overlap","containment","boundary_touch"]},
+                  "page":{
+                    "type":"object",
+                    "additionalProperties":false,
+                    "properties": {
+                      "limit":{"type":"integer","minimum":1,"maximum":500},
+                      "cursor":{"type":"string","maxLength":4096}
+                    }
+                  },
+                  "explain":{"type":"boolean"}
+                }
+              }}}
+            },
+            "responses": {
+              "200": {"description": "gene page with the same envelope as /v1/genes; resend the body with page.cursor for the next page"},
+              "400": {"description": "invalid query", "content": {"application/json": {"schema": {"$ref": "#/components/schemas/ApiError"}}}},
+              "413": {"description": "response too large or query too expensive", "content": {"application/json": {"schema": {"$ref": "#/components/schemas/ApiError"}}}},
+              "422": {"description": "query rejected by policy", "content": {"application/json": {"schema": {"$ref": "#/components/schemas/ApiError"}}}},
+              "429": {"description": "rate limited", "content": {"application/json": {"schema": {"$ref": "#/components/schemas/ApiError"}}}},
+              "503": {"description": "not ready / upstream unavailable", "content": {"application/json": {"schema": {"$ref": "#/components/schemas/ApiError"}}}}
+            }
+          }
+        },
+        "/v1/genes/search": {
+          "get": {
+            "parameters": [
+              {"name": "release", "in": "query", "required": true, "schema": {"type": "string"}},
+              {"name": "species", "in": "query", "required": true, "schema": {"type": "string"}},
+              {"name": "assembly", "in": "query", "required": true, "schema": {"type": "string"}},
+              {"name": "q", "in": "query", "required": true, "schema": {"type": "string", "minLength": 2, "description": "matched against gene symbol, description and aliases"}},
+              {"name": "limit", "in": "query", "schema": {"type": "integer", "minimum": 1, "default": 20}}
+            ],
+            "responses": {
+              "200": {"description": "ranked hits; match_kind is exact, prefix, token or fuzzy, in that order"},
+              "400": {"description": "invalid search or dataset without a search index", "content": {"application/json": {"schema": {"$ref": "#/components/schemas/ApiError"}}}}
+            }
+          }
+        },
         "/v1/query/validate": {
           "post": {
             "responses": {
@@ -251,15 +329,36 @@ pub fn openapi_v1_spec() -> Value {
               {"name":"from_release","in":"query","required":true,"schema":{"type":"string"}},
               {"name":"to_release","in":"query","required":true,"schema":{"type":"string"}},
               {"name":"species","in":"query","required":true,"schema":{"type":"string"}},
-              {"name":"assembly","in":"query","required":true,"schema":{"type":"string"}},
+              {"name":"assembly","in":"query","required":true,"schema":{"type":"string","description":"assembly of from_release, and of to_release unless to_assembly is set"}},
+              {"name":"to_assembly","in":"query","schema":{"type":"string","description":"assembly of to_release; from_release genes are lifted through its liftover chains"}},
               {"name":"region","in":"query","required":true,"schema":{"type":"string","pattern":"^[^:]+:[0-9]+-[0-9]+$"}},
               {"name":"limit","in":"query","schema":{"type":"integer","minimum":1,"maximum":500}},
               {"name":"cursor","in":"query","schema":{"type":"string"}}
             ],
             "responses": {
-              "200": {"description":"region-scoped cross-release diff page"},
+              "200": {"description":"region-scoped cross-release diff page; region coordinates are on to_assembly"},
               "304": {"description":"not modified"},
-              "400": {"description":"invalid query/cursor", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}}
+              "400": {"description":"invalid query/cursor", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}},
+              "404": {"description":"no liftover chain from assembly to to_assembly", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}}
+            }
+          }
+        },
+        "/v1/liftover": {
+          "get": {
+            "parameters": [
+              {"name":"release","in":"query","required":true,"schema":{"type":"string"}},
+              {"name":"species","in":"query","required":true,"schema":{"type":"string"}},
+              {"name":"assembly","in":"query","required":true,"schema":{"type":"string","description":"source assembly carrying the liftover chains"}},
+              {"name":"region","in":"query","required":true,"schema":{"type":"string","pattern":"^[^:]+:[0-9]+-[0-9]+$"}},
+              {"name":"to_assembly","in":"query","schema":{"type":"string","description":"expected target assembly of the chains"}},
+              {"name":"min_match","in":"query","schema":{"type":"number","minimum":0,"maximum":1,"default":0.95}}
+            ],
+            "responses": {
+              "200": {"description":"region lifted onto the target assembly; target is null when less than min_match of the bases map"},
+              "304": {"description":"not modified"},
+              "400": {"description":"invalid region or min_match", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}},
+              "404": {"description":"dataset has no liftover chain to to_assembly", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}},
+              "503": {"description":"liftover chains unavailable", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}}
             }
           }
         },
@@ -269,7 +368,11 @@ pub fn openapi_v1_spec() -> Value {
               {"name":"release","in":"query","required":true,"schema":{"type":"string"}},
               {"name":"species","in":"query","required":true,"schema":{"type":"string"}},
               {"name":"assembly","in":"query","required":true,"schema":{"type":"string"}},
-              {"name":"region","in":"query","required":true,"schema":{"type":"string","pattern":"^[^:]+:[0-9]+-[0-9]+$"}},
+              {"name":"region","in":"query","required":true,"schema":{"type":"string","pattern":"^[^:]+:[0-9]+-[0-9]+$","description":"seqid may be any contig alias recorded at ingest; the stored name is reported in data.contig"}},
+              {"name":"strand","in":"query","schema":{"type":"string","enum":["plus","minus"],"default":"plus"}},
+              {"name":"upstream","in":"query","schema":{"type":"integer","minimum":0}},
+              {"name":"downstream","in":"query","schema":{"type":"integer","minimum":0}},
+              {"name":"mask","in":"query","schema":{"type":"string","enum":["preserve","upper","hard"],"default":"preserve"}},
               {"name":"include_stats","in":"query","schema":{"type":"boolean"}}
             ],
             "responses": {
@@ -282,6 +385,45 @@ pub fn openapi_v1_spec() -> Value {
             }
           }
         },
+        "/v1/sequence/batch": {
+          "post": {
+            "requestBody": {
+              "required": true,
+              "content": {"application/json": {"schema": {
+                "type":"object",
+                "additionalProperties":false,
+                "required":["release","species","assembly","items"],
+                "properties": {
+                  "release":{"type":"string"},
+                  "species":{"type":"string"},
+                  "assembly":{"type":"string"},
+                  "format":{"type":"string","enum":["fasta","jsonl"],"default":"fasta"},
+                  "items":{"type":"array","minItems":1,"items":{
+                    "type":"object",
+                    "additionalProperties":false,
+                    "properties": {
+                      "region":{"type":"string","pattern":"^[^:]+:[0-9]+-[0-9]+$"},
+                      "gene_id":{"type":"string"},
+                      "strand":{"type":"string","enum":["plus","minus"],"default":"plus"},
+                      "upstream":{"type":"integer","minimum":0},
+                      "downstream":{"type":"integer","minimum":0},
+                      "flank":{"type":"integer","minimum":0},
+                      "mask":{"type":"string","enum":["preserve","upper","hard"],"default":"preserve"}
+                    }
+                  }}
+                }
+              }}}
+            },
+            "responses": {
+              "200": {"description":"multi-record FASTA or JSONL stream in input order; failed items are reported inline", "content":{"text/x-fasta":{"schema":{"type":"string"}},"application/x-ndjson":{"schema":{"type":"string"}}}},
+              "400": {"description":"invalid dataset", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}},
+              "401": {"description":"api key required for large sequence request", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}},
+              "422": {"description":"item count or total bases budget exceeded", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}},
+              "429": {"description":"rate limited or heavy worker pool saturated", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}},
+              "503": {"description":"dataset or sequence inputs unavailable", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}}
+            }
+          }
+        },
         "/v1/genes/{gene_id}/sequence": {
           "get": {
             "parameters": [
@@ -290,10 +432,15 @@ pub fn openapi_v1_spec() -> Value {
               {"name":"species","in":"query","required":true,"schema":{"type":"string"}},
               {"name":"assembly","in":"query","required":true,"schema":{"type":"string"}},
               {"name":"flank","in":"query","schema":{"type":"integer","minimum":0}},
+              {"name":"strand","in":"query","schema":{"type":"string","enum":["plus","minus"],"default":"plus"}},
+              {"name":"upstream","in":"query","schema":{"type":"integer","minimum":0}},
+              {"name":"downstream","in":"query","schema":{"type":"integer","minimum":0}},
+              {"name":"mask","in":"query","schema":{"type":"string","enum":["preserve","upper","hard"],"default":"preserve"}},
               {"name":"include_stats","in":"query","schema":{"type":"boolean"}}
             ],
             "responses": {
               "200": {"description":"gene sequence payload"},
+              "400": {"description":"invalid query", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}},
               "404": {"description":"gene not found", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}},
               "422": {"description":"region policy rejection", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}}
             }
@@ -310,10 +457,11 @@ pub fn openapi_v1_spec() -> Value {
               {"name":"cursor","in":"query","schema":{"type":"string"}},
               {"name":"biotype","in":"query","schema":{"type":"string"}},
               {"name":"type","in":"query","schema":{"type":"string"}},
-              {"name":"region","in":"query","schema":{"type":"string","pattern":"^[^:]+:[0-9]+-[0-9]+$"}}
+              {"name":"region","in":"query","schema":{"type":"string","pattern":"^[^:]+:[0-9]+-[0-9]+$"}},
+              {"name":"format","in":"query","schema":{"type":"string","enum":["bed","gtf","gff3"],"description":"annotation records with exon and CDS features instead of the JSON envelope"}}
             ],
             "responses": {
-              "200": {"description":"transcript page"},
+              "200": {"description":"transcript page", "content":{"application/json":{"schema":{"type":"object"}},"application/vnd.apache.arrow.stream":{"schema":{"type":"string","format":"binary","description":"Arrow IPC stream of data.rows; the page cursor is returned in x-atlas-next-cursor"}},"text/x-bed":{"schema":{"type":"string","description":"BED12 with one block per exon"}},"text/x-gtf":{"schema":{"type":"string"}},"text/x-gff3":{"schema":{"type":"string"}}}},
               "400": {"description":"invalid query", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}},
               "429": {"description":"bulkhead saturated", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}},
               "503": {"description":"dataset unavailable", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}}
@@ -336,6 +484,105 @@ pub fn openapi_v1_spec() -> Value {
             }
           }
         },
+        "/v1/transcripts/{tx_id}/sequence": {
+          "get": {
+            "parameters": [
+              {"name":"tx_id","in":"path","required":true,"schema":{"type":"string"}},
+              {"name":"release","in":"query","required":true,"schema":{"type":"string"}},
+              {"name":"species","in":"query","required":true,"schema":{"type":"string"}},
+              {"name":"assembly","in":"query","required":true,"schema":{"type":"string"}},
+              {"name":"kind","in":"query","schema":{"type":"string","enum":["cdna","cds","protein"],"default":"cdna"}},
+              {"name":"genetic_code","in":"query","schema":{"type":"integer","enum":[1,2,3,4,5,6,9,10,11,12,13,14,21],"description":"defaults to 2 on mitochondrial contigs and 1 elsewhere"}},
+              {"name":"include_stats","in":"query","schema":{"type":"boolean"}}
+            ],
+            "responses": {
+              "200": {"description":"spliced transcript, CDS or protein sequence payload"},
+              "304": {"description":"not modified"},
+              "400": {"description":"invalid query", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}},
+              "404": {"description":"transcript not found", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}},
+              "422": {"description":"no CDS segments or sequence policy rejection", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}},
+              "429": {"description":"rate limited", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}},
+              "503": {"description":"dataset or sequence inputs unavailable", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}}
+            }
+          }
+        },
+        "/v1/transcripts/{tx_id}/exons": {
+          "get": {
+            "parameters": [
+              {"name":"tx_id","in":"path","required":true,"schema":{"type":"string"}},
+              {"name":"release","in":"query","required":true,"schema":{"type":"string"}},
+              {"name":"species","in":"query","required":true,"schema":{"type":"string"}},
+              {"name":"assembly","in":"query","required":true,"schema":{"type":"string"}}
+            ],
+            "responses": {
+              "200": {"description":"exons in 5' to 3' order with rank"},
+              "400": {"description":"invalid query", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}},
+              "404": {"description":"transcript not found", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}},
+              "429": {"description":"bulkhead saturated", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}},
+              "503": {"description":"dataset unavailable", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}}
+            }
+          }
+        },
+        "/v1/transcripts/{tx_id}/introns": {
+          "get": {
+            "parameters": [
+              {"name":"tx_id","in":"path","required":true,"schema":{"type":"string"}},
+              {"name":"release","in":"query","required":true,"schema":{"type":"string"}},
+              {"name":"species","in":"query","required":true,"schema":{"type":"string"}},
+              {"name":"assembly","in":"query","required":true,"schema":{"type":"string"}}
+            ],
+            "responses": {
+              "200": {"description":"introns derived from exon gaps, 5' to 3'"},
+              "400": {"description":"invalid query", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}},
+              "404": {"description":"transcript not found", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}},
+              "429": {"description":"bulkhead saturated", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}},
+              "503": {"description":"dataset unavailable", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}}
+            }
+          }
+        },
+        "/v1/transcripts/{tx_id}/utrs": {
+          "get": {
+            "parameters": [
+              {"name":"tx_id","in":"path","required":true,"schema":{"type":"string"}},
+              {"name":"release","in":"query","required":true,"schema":{"type":"string"}},
+              {"name":"species","in":"query","required":true,"schema":{"type":"string"}},
+              {"name":"assembly","in":"query","required":true,"schema":{"type":"string"}}
+            ],
+            "responses": {
+              "200": {"description":"five_prime and three_prime UTRs derived from exons outside the CDS"},
+              "400": {"description":"invalid query", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}},
+              "404": {"description":"transcript not found", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}},
+              "429": {"description":"bulkhead saturated", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}},
+              "503": {"description":"dataset unavailable", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}}
+            }
+          }
+        },
+        "/v1/exons": {
+          "get": {
+            "parameters": [
+              {"name":"release","in":"query","required":true,"schema":{"type":"string"}},
+              {"name":"species","in":"query","required":true,"schema":{"type":"string"}},
+              {"name":"assembly","in":"query","required":true,"schema":{"type":"string"}},
+              {"name":"region","in":"query","required":true,"schema":{"type":"string","pattern":"^[^:]+:[0-9]+-[0-9]+$"}},
+              {"name":"limit","in":"query","schema":{"type":"integer","minimum":1,"default":100}}
+            ],
+            "responses": {
+              "200": {"description":"exons overlapping the region in genomic order"},
+              "400": {"description":"invalid region, limit or span too large", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}},
+              "429": {"description":"bulkhead saturated", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}},
+              "503": {"description":"dataset unavailable", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}}
+            }
+          }
+        },
+        "/v1/usage": {
+          "get": {
+            "responses": {
+              "200": {"description":"caller's quota tier, request bucket and daily sequence_bases and rows_returned budgets; metered is false when no tier applies"},
+              "401": {"description":"authentication required", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}},
+              "429": {"description":"request bucket exhausted; see Retry-After", "content":{"application/json":{"schema":{"$ref":"#/components/schemas/ApiError"}}}}
+            }
+          }
+        },
         "/debug/datasets": {
           "get": {
             "responses": {
@@ -439,6 +686,41 @@ pub fn openapi_v1_spec() -> Value {
             }
           }
         },
+        "/debug/authz/explain": {
+          "get": {
+            "parameters": [
+              {"name": "route", "in": "query", "required": true, "schema": {"type": "string"}},
+              {"name": "principal", "in": "query", "schema": {"type": "string"}},
+              {"name": "subject", "in": "query", "schema": {"type": "string"}},
+              {"name": "groups", "in": "query", "schema": {"type": "string"}},
+              {"name": "api_key_owner", "in": "query", "schema": {"type": "string"}}
+            ],
+            "responses": {
+              "200": {"description": "role bindings, permission and rule that decide the route"},
+              "400": {"description": "missing or relative route"},
+              "404": {"description": "disabled"},
+              "503": {"description": "no role bindings are active"}
+            }
+          }
+        },
+        "/debug/api-keys": {
+          "get": {
+            "responses": {
+              "200": {"description": "api key registry entries without key digests"},
+              "404": {"description": "disabled"},
+              "503": {"description": "no api key registry is active"}
+            }
+          }
+        },
+        "/debug/api-keys/reload": {
+          "post": {
+            "responses": {
+              "200": {"description": "api key registry re-read; reports whether it changed"},
+              "404": {"description": "disabled"},
+              "503": {"description": "registry failed to load; active keys unchanged"}
+            }
+          }
+        },
         "/v1/_debug/echo": {
           "get": {
             "responses": {
@@ -1,10 +1,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::errors::ApiError;
-use crate::domain::query::RegionFilter;
+use crate::domain::query::{
+    split_xref, AnnotationFormat, ContigAliases, ContigResolution, RegionFilter,
+};
+use serde::Deserialize;
 use std::collections::{BTreeMap, BTreeSet};
 
-pub const ALLOWED_INCLUDE: [&str; 4] = ["coords", "biotype", "counts", "length"];
+pub const ALLOWED_INCLUDE: [&str; 5] = ["coords", "biotype", "counts", "length", "strand"];
 pub const MAX_CURSOR_BYTES: usize = 4096;
 pub const MAX_FILTER_COUNT: usize = 6;
 pub const MAX_RANGE_SPAN: u64 = 5_000_000;
@@ -15,6 +18,7 @@ pub enum IncludeField {
     Biotype,
     Counts,
     Length,
+    Strand,
 }
 
 impl IncludeField {
@@ -24,6 +28,7 @@ impl IncludeField {
             "biotype" => Some(Self::Biotype),
             "counts" => Some(Self::Counts),
             "length" => Some(Self::Length),
+            "strand" => Some(Self::Strand),
             _ => None,
         }
     }
@@ -103,7 +108,11 @@ pub struct ListGenesParams {
     pub include: Option<Vec<IncludeField>>,
     pub interval_mode: Option<IntervalMode>,
     pub strand: Option<StrandMode>,
+    pub xref: Option<String>,
+    pub alias: Option<String>,
     pub pretty: bool,
+    /// Annotation rendering instead of the JSON envelope.
+    pub format: Option<AnnotationFormat>,
 }
 
 pub fn parse_list_genes_params(
@@ -121,47 +130,11 @@ pub fn parse_list_genes_params_with_limit(
     if query.contains_key("fields") {
         return Err(ApiError::invalid_param(
             "fields",
-            "unsupported; use include=coords,biotype,counts,length",
+            "unsupported; use include=coords,biotype,counts,length,strand",
         ));
     }
     let dataset_selector = query.get("dataset").cloned();
-    let selector_dims = parse_dataset_selector(dataset_selector.as_deref())?;
-    let (release, species, assembly) =
-        if let Some((sel_release, sel_species, sel_assembly)) = selector_dims {
-            let release = query
-                .get("release")
-                .cloned()
-                .unwrap_or_else(|| sel_release.clone());
-            let species = query
-                .get("species")
-                .cloned()
-                .unwrap_or_else(|| sel_species.clone());
-            let assembly = query
-                .get("assembly")
-                .cloned()
-                .unwrap_or_else(|| sel_assembly.clone());
-            if release != sel_release || species != sel_species || assembly != sel_assembly {
-                return Err(ApiError::invalid_param(
-                    "dataset",
-                    "dataset selector conflicts with release/species/assembly",
-                ));
-            }
-            (release, species, assembly)
-        } else {
-            let release = query
-                .get("release")
-                .cloned()
-                .ok_or_else(|| ApiError::missing_dataset_dim("release"))?;
-            let species = query
-                .get("species")
-                .cloned()
-                .ok_or_else(|| ApiError::missing_dataset_dim("species"))?;
-            let assembly = query
-                .get("assembly")
-                .cloned()
-                .ok_or_else(|| ApiError::missing_dataset_dim("assembly"))?;
-            (release, species, assembly)
-        };
+    let (release, species, assembly) = resolve_dataset_dims(query)?;
 
     let limit = if let Some(raw) = query.get("limit") {
         let value = raw
@@ -212,19 +185,31 @@ pub fn parse_list_genes_params_with_limit(
         } else {
             None
         };
-    let name_like = query.get("name_like").cloned();
-    if let Some(pattern) = &name_like {
-        if pattern.starts_with('*')
-            || pattern.contains('%')
-            || pattern.contains('?')
-            || (!pattern.ends_with('*') && pattern.contains('*'))
-        {
+    let format = if let Some(raw_format) = query.get("format") {
+        Some(
+            AnnotationFormat::parse(raw_format)
+                .ok_or_else(|| ApiError::invalid_param("format", "allowed: bed,gtf,gff3"))?,
+        )
+    } else {
+        None
+    };
+    let xref = query.get("xref").cloned();
+    if let Some(raw_xref) = &xref {
+        if split_xref(raw_xref).is_none() {
             return Err(ApiError::invalid_param(
-                "name_like",
-                "only prefix wildcard is supported (example: BRCA*)",
+                "xref",
+                "expected NAMESPACE:VALUE (example: HGNC:1100)",
             ));
         }
     }
+    let alias = query.get("alias").cloned();
+    if alias.as_deref().is_some_and(|a| a.trim().is_empty()) {
+        return Err(ApiError::invalid_param("alias", "must not be empty"));
+    }
+    let name_like = query.get("name_like").cloned();
+    if let Some(pattern) = &name_like {
+        validate_name_like(pattern)?;
+    }
     let min_transcripts = parse_u64_opt(query, "min_transcripts")?;
     let max_transcripts = parse_u64_opt(query, "max_transcripts")?;
     if let (Some(min), Some(max)) = (min_transcripts, max_transcripts) {
@@ -245,6 +230,8 @@ pub fn parse_list_genes_params_with_limit(
         query.get("range").is_some() || query.get("region").is_some(),
         min_transcripts.is_some(),
         max_transcripts.is_some(),
+        xref.is_some(),
+        alias.is_some(),
     ]
     .into_iter()
     .filter(|active| *active)
@@ -302,13 +289,244 @@ pub fn parse_list_genes_params_with_limit(
         sort,
         interval_mode,
         strand,
+        xref,
+        alias,
         include,
         pretty: query
             .get("pretty")
             .is_some_and(|v| v == "1" || v.eq_ignore_ascii_case("true")),
+        format,
     })
 }
 
+/// A filter value given either as one string or as a list of alternatives.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum OneOrMany {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl OneOrMany {
+    fn into_vec(self) -> Vec<String> {
+        match self {
+            Self::One(value) => vec![value],
+            Self::Many(values) => values,
+        }
+    }
+}
+
+/// Body of `POST /v1/genes/query`.
+///
+/// Filters mirror the `/v1/genes` query parameters; `gene_id`, `biotype` and
+/// `region` also accept lists, ORed within the list and ANDed with the rest.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GeneQueryBody {
+    pub dataset: Option<String>,
+    pub release: Option<String>,
+    pub species: Option<String>,
+    pub assembly: Option<String>,
+    #[serde(default)]
+    pub filter: GeneQueryBodyFilter,
+    #[serde(default)]
+    pub include: Vec<String>,
+    pub sort: Option<String>,
+    pub interval_mode: Option<String>,
+    #[serde(default)]
+    pub page: GeneQueryBodyPage,
+    #[serde(default)]
+    pub explain: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GeneQueryBodyFilter {
+    pub gene_id: Option<OneOrMany>,
+    pub biotype: Option<OneOrMany>,
+    pub region: Option<OneOrMany>,
+    pub name: Option<String>,
+    pub name_like: Option<String>,
+    pub strand: Option<String>,
+    pub xref: Option<String>,
+    pub alias: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GeneQueryBodyPage {
+    pub limit: Option<usize>,
+    pub cursor: Option<String>,
+}
+
+/// Validated `POST /v1/genes/query` body; list filters are sorted and deduplicated
+/// so the cursor binding does not depend on input order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneQueryParams {
+    pub release: String,
+    pub species: String,
+    pub assembly: String,
+    pub limit: usize,
+    pub cursor: Option<String>,
+    pub gene_ids: Vec<String>,
+    pub biotypes: Vec<String>,
+    pub regions: Vec<RegionFilter>,
+    pub name: Option<String>,
+    pub name_like: Option<String>,
+    pub sort: Option<SortKey>,
+    pub include: Option<Vec<IncludeField>>,
+    pub interval_mode: Option<IntervalMode>,
+    pub strand: Option<StrandMode>,
+    pub xref: Option<String>,
+    pub alias: Option<String>,
+}
+
+pub fn parse_gene_query_body(
+    body: GeneQueryBody,
+    default_limit: usize,
+    max_limit: usize,
+    max_values: usize,
+) -> Result<GeneQueryParams, ApiError> {
+    let mut dims = BTreeMap::new();
+    for (key, value) in [
+        ("dataset", body.dataset),
+        ("release", body.release),
+        ("species", body.species),
+        ("assembly", body.assembly),
+    ] {
+        if let Some(value) = value {
+            dims.insert(key.to_string(), value);
+        }
+    }
+    let (release, species, assembly) = resolve_dataset_dims(&dims)?;
+
+    let limit = body.page.limit.unwrap_or(default_limit);
+    if limit == 0 || limit > max_limit {
+        return Err(ApiError::invalid_param("page.limit", &limit.to_string()));
+    }
+    let cursor = body.page.cursor;
+    if let Some(value) = &cursor {
+        if value.len() > MAX_CURSOR_BYTES {
+            return Err(ApiError::invalid_cursor(value));
+        }
+    }
+
+    let filter = body.filter;
+    let gene_ids = list_filter("filter.gene_id", filter.gene_id, max_values)?;
+    let biotypes = list_filter("filter.biotype", filter.biotype, max_values)?;
+    let mut regions = Vec::new();
+    for raw in list_filter("filter.region", filter.region, max_values)? {
+        regions.extend(parse_region_filter(Some(raw))?);
+    }
+    regions.sort_by(|a, b| (&a.seqid, a.start, a.end).cmp(&(&b.seqid, b.start, b.end)));
+    regions.dedup();
+
+    if let Some(pattern) = &filter.name_like {
+        validate_name_like(pattern)?;
+    }
+    if filter.name.is_some() && filter.name_like.is_some() {
+        return Err(ApiError::invalid_param(
+            "name",
+            "name cannot be combined with name_like",
+        ));
+    }
+    if let Some(raw_xref) = &filter.xref {
+        if split_xref(raw_xref).is_none() {
+            return Err(ApiError::invalid_param(
+                "xref",
+                "expected NAMESPACE:VALUE (example: HGNC:1100)",
+            ));
+        }
+    }
+    if filter.alias.as_deref().is_some_and(|a| a.trim().is_empty()) {
+        return Err(ApiError::invalid_param("alias", "must not be empty"));
+    }
+    let strand = filter
+        .strand
+        .as_deref()
+        .map(|raw| {
+            StrandMode::parse(raw)
+                .ok_or_else(|| ApiError::invalid_param("strand", "allowed: any,plus,minus,unknown"))
+        })
+        .transpose()?;
+    let sort = body
+        .sort
+        .as_deref()
+        .map(|raw| {
+            SortKey::parse(raw)
+                .ok_or_else(|| ApiError::invalid_param("sort", "allowed: gene_id:asc,region:asc"))
+        })
+        .transpose()?;
+    let interval_mode = body
+        .interval_mode
+        .as_deref()
+        .map(|raw| {
+            IntervalMode::parse(raw).ok_or_else(|| {
+                ApiError::invalid_param(
+                    "interval_mode",
+                    "allowed: overlap,containment,boundary_touch",
+                )
+            })
+        })
+        .transpose()?;
+    if interval_mode.is_some() && regions.is_empty() {
+        return Err(ApiError::invalid_param(
+            "interval_mode",
+            "interval_mode requires filter.region",
+        ));
+    }
+    if matches!(sort, Some(SortKey::RegionAsc)) && regions.is_empty() {
+        return Err(ApiError::invalid_param(
+            "sort",
+            "sort=region:asc requires filter.region",
+        ));
+    }
+    let include = if body.include.is_empty() {
+        None
+    } else {
+        Some(parse_include(&body.include.join(","))?)
+    };
+
+    Ok(GeneQueryParams {
+        release,
+        species,
+        assembly,
+        limit,
+        cursor,
+        gene_ids,
+        biotypes,
+        regions,
+        name: filter.name,
+        name_like: filter.name_like,
+        sort,
+        include,
+        interval_mode,
+        strand,
+        xref: filter.xref,
+        alias: filter.alias,
+    })
+}
+
+fn list_filter(
+    name: &str,
+    raw: Option<OneOrMany>,
+    max_values: usize,
+) -> Result<Vec<String>, ApiError> {
+    let mut values = raw.map(OneOrMany::into_vec).unwrap_or_default();
+    if values.len() > max_values {
+        return Err(ApiError::invalid_param(
+            name,
+            &format!("{} values; max {max_values}", values.len()),
+        ));
+    }
+    if values.iter().any(|v| v.trim().is_empty()) {
+        return Err(ApiError::invalid_param(name, "values must not be empty"));
+    }
+    values.sort();
+    values.dedup();
+    Ok(values)
+}
+
 pub fn parse_region_filter(raw: Option<String>) -> Result<Option<RegionFilter>, ApiError> {
     let Some(value) = raw else {
         return Ok(None);
@@ -347,6 +565,33 @@ pub fn parse_region_filter(raw: Option<String>) -> Result<Option<RegionFilter>,
     }))
 }
 
+/// Moves a parsed region onto the seqid the dataset stores for its contig.
+pub fn resolve_region_alias(
+    region: &mut RegionFilter,
+    aliases: &ContigAliases,
+) -> ContigResolution {
+    let resolution = aliases.resolve(&region.seqid);
+    region.seqid.clone_from(&resolution.seqid);
+    resolution
+}
+
+/// [`resolve_region_alias`] over a region list, keeping the list sorted and
+/// free of the duplicates two spellings of one contig can produce.
+pub fn resolve_region_aliases(
+    regions: &mut Vec<RegionFilter>,
+    aliases: &ContigAliases,
+) -> Vec<ContigResolution> {
+    let mut resolutions: Vec<ContigResolution> = regions
+        .iter_mut()
+        .map(|r| resolve_region_alias(r, aliases))
+        .collect();
+    regions.sort_by(|a, b| (&a.seqid, a.start, a.end).cmp(&(&b.seqid, b.start, b.end)));
+    regions.dedup();
+    resolutions.sort_by(|a, b| a.requested.cmp(&b.requested));
+    resolutions.dedup();
+    resolutions
+}
+
 pub fn parse_range_filter(raw: Option<String>) -> Result<Option<RegionFilter>, ApiError> {
     let Some(value) = raw else {
         return Ok(None);
@@ -392,6 +637,63 @@ pub fn parse_range_filter(raw: Option<String>) -> Result<Option<RegionFilter>, A
     }))
 }
 
+/// Resolves `release`/`species`/`assembly`, either given directly or via a
+/// `dataset=release/species/assembly` selector that must agree with them.
+fn resolve_dataset_dims(
+    query: &BTreeMap<String, String>,
+) -> Result<(String, String, String), ApiError> {
+    let selector_dims = parse_dataset_selector(query.get("dataset").map(String::as_str))?;
+    if let Some((sel_release, sel_species, sel_assembly)) = selector_dims {
+        let release = query
+            .get("release")
+            .cloned()
+            .unwrap_or_else(|| sel_release.clone());
+        let species = query
+            .get("species")
+            .cloned()
+            .unwrap_or_else(|| sel_species.clone());
+        let assembly = query
+            .get("assembly")
+            .cloned()
+            .unwrap_or_else(|| sel_assembly.clone());
+        if release != sel_release || species != sel_species || assembly != sel_assembly {
+            return Err(ApiError::invalid_param(
+                "dataset",
+                "dataset selector conflicts with release/species/assembly",
+            ));
+        }
+        Ok((release, species, assembly))
+    } else {
+        let release = query
+            .get("release")
+            .cloned()
+            .ok_or_else(|| ApiError::missing_dataset_dim("release"))?;
+        let species = query
+            .get("species")
+            .cloned()
+            .ok_or_else(|| ApiError::missing_dataset_dim("species"))?;
+        let assembly = query
+            .get("assembly")
+            .cloned()
+            .ok_or_else(|| ApiError::missing_dataset_dim("assembly"))?;
+        Ok((release, species, assembly))
+    }
+}
+
+fn validate_name_like(pattern: &str) -> Result<(), ApiError> {
+    if pattern.starts_with('*')
+        || pattern.contains('%')
+        || pattern.contains('?')
+        || (!pattern.ends_with('*') && pattern.contains('*'))
+    {
+        return Err(ApiError::invalid_param(
+            "name_like",
+            "only prefix wildcard is supported (example: BRCA*)",
+        ));
+    }
+    Ok(())
+}
+
 fn parse_include(raw_include: &str) -> Result<Vec<IncludeField>, ApiError> {
     let mut ordered_fields = Vec::new();
     let mut seen = BTreeSet::new();
@@ -423,7 +725,7 @@ fn parse_u64_opt(
 }
 
 fn validate_known_filters(query: &BTreeMap<String, String>) -> Result<(), ApiError> {
-    const ALLOWED_PARAMS: [&str; 22] = [
+    const ALLOWED_PARAMS: [&str; 25] = [
         "dataset",
         "release",
         "species",
@@ -443,9 +745,12 @@ fn validate_known_filters(query: &BTreeMap<String, String>) -> Result<(), ApiErr
         "sort",
         "interval_mode",
         "strand",
+        "xref",
+        "alias",
         "pretty",
         "explain",
         "fields",
+        "format",
     ];
     let mut unknown = query
         .keys()
@@ -459,7 +764,7 @@ fn validate_known_filters(query: &BTreeMap<String, String>) -> Result<(), ApiErr
     Err(ApiError::invalid_param(
         "filter",
         &format!(
-            "unknown filter(s): {}; allowed: dataset,gene_id,name,name_like,biotype,contig,range,min_transcripts,max_transcripts,sort,interval_mode,strand",
+            "unknown filter(s): {}; allowed: dataset,gene_id,name,name_like,biotype,contig,range,min_transcripts,max_transcripts,sort,interval_mode,strand,xref,alias",
             unknown.join(",")
         ),
     ))
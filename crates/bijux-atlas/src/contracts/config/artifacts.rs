@@ -110,6 +110,7 @@ fn redact_known_secrets(config_json: &mut serde_json::Value) {
         "token_signing_secret",
         "s3_bearer",
         "http_bearer",
+        "encryption_keys",
     ];
     for &key in SECRET_FIELD_DENYLIST {
         if obj.contains_key(key) {
@@ -145,7 +146,9 @@ pub fn effective_runtime_config_payload(
             "pod_id": runtime.pod_id,
             "policy_mode": runtime.policy_mode,
             "shutdown_drain_ms": runtime.shutdown_drain_ms,
-            "tcp_keepalive_enabled": runtime.tcp_keepalive_enabled
+            "tcp_keepalive_enabled": runtime.tcp_keepalive_enabled,
+            "tls": runtime.tls,
+            "tls_reload_interval_secs": runtime.tls_reload_interval_secs
         }
     });
     if let Some(api_json) = payload.get_mut("api") {
@@ -17,6 +17,9 @@ pub struct ArtifactChecksums {
     pub fasta_sha256: String,
     pub fai_sha256: String,
     pub sqlite_sha256: String,
+    /// Digest of the `.gzi` block index published beside a BGZF FASTA.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub fasta_gzi_sha256: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -27,6 +30,10 @@ pub struct ManifestInputHashes {
     pub fasta_sha256: String,
     pub fai_sha256: String,
     pub policy_sha256: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub gff3_decompressed_sha256: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub fasta_decompressed_sha256: String,
 }
 
 impl ManifestInputHashes {
@@ -42,8 +49,22 @@ impl ManifestInputHashes {
             fasta_sha256,
             fai_sha256,
             policy_sha256,
+            gff3_decompressed_sha256: String::new(),
+            fasta_decompressed_sha256: String::new(),
         }
     }
+
+    /// Records digests of the decoded GFF3 and FASTA streams for compressed inputs.
+    #[must_use]
+    pub fn with_decompressed(
+        mut self,
+        gff3_decompressed_sha256: String,
+        fasta_decompressed_sha256: String,
+    ) -> Self {
+        self.gff3_decompressed_sha256 = gff3_decompressed_sha256;
+        self.fasta_decompressed_sha256 = fasta_decompressed_sha256;
+        self
+    }
 }
 
 impl ArtifactChecksums {
@@ -59,8 +80,16 @@ impl ArtifactChecksums {
             fasta_sha256,
             fai_sha256,
             sqlite_sha256,
+            fasta_gzi_sha256: String::new(),
         }
     }
+
+    /// Records the digest of the FASTA's `.gzi` index.
+    #[must_use]
+    pub fn with_fasta_gzi(mut self, fasta_gzi_sha256: String) -> Self {
+        self.fasta_gzi_sha256 = fasta_gzi_sha256;
+        self
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -205,6 +234,8 @@ impl ArtifactManifest {
                 fasta_sha256: "unknown".to_string(),
                 fai_sha256: "unknown".to_string(),
                 policy_sha256: "unknown".to_string(),
+                gff3_decompressed_sha256: String::new(),
+                fasta_decompressed_sha256: String::new(),
             },
             stats,
             identity: DatasetIdentity::default(),
@@ -481,6 +512,7 @@ pub struct ArtifactPaths {
     pub gff3: PathBuf,
     pub fasta: PathBuf,
     pub fai: PathBuf,
+    pub fasta_gzi: PathBuf,
     pub sqlite: PathBuf,
     pub manifest: PathBuf,
     pub anomaly_report: PathBuf,
@@ -491,8 +523,10 @@ pub struct ArtifactPaths {
     pub dataset_stats: PathBuf,
     pub artifact_inventory: PathBuf,
     pub evidence_bundle: PathBuf,
+    pub ingest_resources: PathBuf,
     pub scientific_profile: PathBuf,
     pub release_gene_index: PathBuf,
+    pub liftover_chains: PathBuf,
 }
 
 #[must_use]
@@ -510,6 +544,7 @@ pub fn artifact_paths(root: &Path, dataset: &DatasetId) -> ArtifactPaths {
         gff3: inputs.join("genes.gff3.bgz"),
         fasta: inputs.join("genome.fa.bgz"),
         fai: inputs.join("genome.fa.bgz.fai"),
+        fasta_gzi: inputs.join("genome.fa.bgz.gzi"),
         sqlite: derived.join("gene_summary.sqlite"),
         manifest: derived.join("manifest.json"),
         anomaly_report: derived.join("anomaly_report.json"),
@@ -520,8 +555,10 @@ pub fn artifact_paths(root: &Path, dataset: &DatasetId) -> ArtifactPaths {
         dataset_stats: derived.join("dataset_stats.json"),
         artifact_inventory: derived.join("artifact_inventory.json"),
         evidence_bundle: derived.join("evidence_bundle.lock.json"),
+        ingest_resources: derived.join("ingest_resources.json"),
         scientific_profile: derived.join("scientific_profile.json"),
         release_gene_index: derived.join("release_gene_index.json"),
+        liftover_chains: derived.join("liftover_chains.json"),
     }
 }
 
@@ -1,13 +1,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use super::extract::{ExonRecord, ExtractResult, GeneRecord, TranscriptRecord};
+use super::extract::{ExonRecord, GeneRecord, TranscriptRecord};
 use super::gff3::Gff3Record;
+use super::spool::{RowSpool, SPILLED_ARRAY_SLOT};
 use super::IngestError;
-use crate::domain::canonical;
+use crate::domain::query::{derive_introns, derive_utrs, FeatureSpan, Strand, UtrKind};
 use crate::domain::sha256_hex;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{BufWriter, Write};
+use std::path::Path;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -16,6 +20,8 @@ pub enum CanonicalFeatureType {
     Transcript,
     Exon,
     Cds,
+    Intron,
+    Utr,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
@@ -80,6 +86,30 @@ pub struct CanonicalCds {
     pub lineage: Vec<LineageRecord>,
 }
 
+/// Intron derived from the gaps between a transcript's exons.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CanonicalIntron {
+    pub transcript_id: String,
+    pub seqid: String,
+    pub start: u64,
+    pub end: u64,
+    pub intron_rank: u64,
+    pub feature_type: CanonicalFeatureType,
+}
+
+/// UTR derived from the exon parts outside a transcript's CDS envelope.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CanonicalUtr {
+    pub transcript_id: String,
+    pub seqid: String,
+    pub kind: UtrKind,
+    pub start: u64,
+    pub end: u64,
+    pub feature_type: CanonicalFeatureType,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct CanonicalTranscript {
@@ -101,6 +131,10 @@ pub struct CanonicalTranscript {
     pub completeness: AnnotationCompleteness,
     pub exons: Vec<CanonicalExon>,
     pub cds_segments: Vec<CanonicalCds>,
+    #[serde(default)]
+    pub introns: Vec<CanonicalIntron>,
+    #[serde(default)]
+    pub utrs: Vec<CanonicalUtr>,
     pub lineage: Vec<LineageRecord>,
 }
 
@@ -125,7 +159,7 @@ pub struct CanonicalGene {
     pub lineage: Vec<LineageRecord>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct CanonicalSummary {
     pub genes: u64,
@@ -158,90 +192,140 @@ pub struct CanonicalModel {
     pub hashes: CanonicalHashes,
 }
 
-#[derive(Debug, Clone)]
-pub struct CanonicalBuildOutput {
-    pub model: CanonicalModel,
-    pub query_semantic_payload: serde_json::Value,
+/// Canonical model figures the manifest, QC report and source facts record;
+/// the genes themselves are streamed to `canonical_features.json`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CanonicalModelDigest {
+    pub schema_version: u64,
+    pub summary: CanonicalSummary,
+    pub hashes: CanonicalHashes,
 }
 
-pub fn build_canonical_model(
-    records: &[Gff3Record],
-    extract: &ExtractResult,
-) -> Result<CanonicalBuildOutput, IngestError> {
-    let lineage = build_lineage_index(records)?;
-    let mut exons_by_transcript: HashMap<String, Vec<CanonicalExon>> = HashMap::new();
-    let mut cds_by_transcript: HashMap<String, Vec<CanonicalCds>> = HashMap::new();
+/// Records lineage and CDS segments of streamed GFF3 records in the
+/// [`RowSpool`] so the canonical model can be built without retaining raw rows.
+pub struct CanonicalModelBuilder<'a> {
+    rows: &'a RowSpool,
+}
 
-    for exon in &extract.exon_rows {
-        let lineage_records = lineage_for_exon(&lineage, exon);
-        exons_by_transcript
-            .entry(exon.transcript_id.clone())
-            .or_default()
-            .push(canonical_exon(exon, lineage_records));
+impl<'a> CanonicalModelBuilder<'a> {
+    pub fn new(rows: &'a RowSpool) -> Self {
+        Self { rows }
     }
-    for rec in records.iter().filter(|r| r.feature_type == "CDS") {
-        for parent in split_parents(rec.attrs.get("Parent")) {
-            cds_by_transcript
-                .entry(parent.to_string())
-                .or_default()
-                .push(canonical_cds(rec, parent));
+
+    pub fn observe(&self, rec: &Gff3Record) -> Result<(), IngestError> {
+        self.rows.push_lineage(
+            &rec.feature_type,
+            &rec.seqid,
+            rec.start,
+            rec.end,
+            &lineage_from_record(rec),
+        )?;
+        if rec.feature_type == "CDS" {
+            for parent in split_parents(rec.attrs.get("Parent")) {
+                self.rows.push_cds(&canonical_cds(rec, parent))?;
+            }
         }
+        Ok(())
     }
+}
 
-    let mut transcripts_by_gene: HashMap<String, Vec<CanonicalTranscript>> = HashMap::new();
-    for tx in &extract.transcript_rows {
-        let mut tx_exons = exons_by_transcript
-            .remove(&tx.transcript_id)
-            .unwrap_or_default();
-        tx_exons.sort_by(compare_exon);
-        let mut tx_cds = cds_by_transcript
-            .remove(&tx.transcript_id)
-            .unwrap_or_default();
-        tx_cds.sort_by(compare_cds);
-        let tx_lineage = lineage_for_transcript(&lineage, tx);
-        transcripts_by_gene
-            .entry(tx.parent_gene_id.clone())
-            .or_default()
-            .push(canonical_transcript(tx, tx_exons, tx_cds, tx_lineage));
-    }
+/// Builds the canonical model one gene at a time from the spooled rows and
+/// writes `canonical_features.json` and `canonical_summary.json`. The model's
+/// CDS segments, introns and UTRs are recorded back in the spool for the
+/// SQLite writers.
+pub fn write_canonical_model(
+    rows: &RowSpool,
+    features_path: &Path,
+    summary_path: &Path,
+) -> Result<CanonicalModelDigest, IngestError> {
+    let mut genes = rows.spill_array("canonical_genes.json")?;
+    let mut semantic_genes = rows.spill_array("canonical_semantic_genes.json")?;
+    let mut summary = CanonicalSummary::default();
+    rows.in_transaction(|| {
+        rows.for_each_gene_in_contig_order(|gene| {
+            let gene = build_canonical_gene(rows, &gene)?;
+            tally_gene(&mut summary, &gene);
+            for tx in &gene.transcripts {
+                for cds in &tx.cds_segments {
+                    rows.push_canonical_cds(cds)?;
+                }
+                for intron in &tx.introns {
+                    rows.push_canonical_intron(intron)?;
+                }
+                for utr in &tx.utrs {
+                    rows.push_canonical_utr(utr)?;
+                }
+            }
+            semantic_genes.push(&semantic_gene(&gene))?;
+            genes.push(&gene)
+        })
+    })?;
+    summary.contigs = summary.contig_gene_counts.len() as u64;
+    let genes = genes.finish()?;
+    let semantic_genes = semantic_genes.finish()?;
 
-    let mut genes: Vec<CanonicalGene> = Vec::new();
-    for gene in &extract.gene_rows {
-        let mut transcripts = transcripts_by_gene
-            .remove(&gene.gene_id)
-            .unwrap_or_default();
-        transcripts.sort_by(compare_transcript);
-        let gene_lineage = lineage_for_gene(&lineage, gene);
-        genes.push(canonical_gene(gene, transcripts, gene_lineage));
-    }
-    genes.sort_by(compare_gene);
-
-    let summary = summarize_model(&genes);
-    let query_semantic_payload = semantic_payload(&genes, &summary);
-    let query_semantic_sha256 = {
-        let bytes = canonical::stable_json_bytes(&query_semantic_payload)
-            .map_err(|e| IngestError(e.to_string()))?;
-        sha256_hex(&bytes)
-    };
-    let lineage_sensitive_sha256 = {
-        let bytes = canonical::stable_json_bytes(&genes).map_err(|e| IngestError(e.to_string()))?;
-        sha256_hex(&bytes)
+    let slot = serde_json::Value::String(SPILLED_ARRAY_SLOT.to_string());
+    let query_semantic_payload = serde_json::json!({
+        "schema_version": 1,
+        "summary": summary,
+        "genes": slot
+    });
+    let hashes = CanonicalHashes {
+        query_semantic_sha256: semantic_genes.splice_sha256(&query_semantic_payload)?,
+        lineage_sensitive_sha256: genes.splice_sha256(&slot)?,
     };
-    let model = CanonicalModel {
+    let io = |e: std::io::Error| IngestError(e.to_string());
+    let mut out = BufWriter::new(fs::File::create(features_path).map_err(io)?);
+    genes.splice_into(
+        &serde_json::json!({
+            "schema_version": 1,
+            "genes": slot,
+            "summary": summary,
+            "hashes": hashes
+        }),
+        &mut out,
+    )?;
+    out.flush().map_err(io)?;
+    let mut out = BufWriter::new(fs::File::create(summary_path).map_err(io)?);
+    semantic_genes.splice_into(
+        &serde_json::json!({
+            "schema_version": 1,
+            "summary": summary,
+            "hashes": hashes,
+            "query_semantic_payload": query_semantic_payload
+        }),
+        &mut out,
+    )?;
+    out.flush().map_err(io)?;
+    Ok(CanonicalModelDigest {
         schema_version: 1,
-        genes,
         summary,
-        hashes: CanonicalHashes {
-            query_semantic_sha256,
-            lineage_sensitive_sha256,
-        },
-    };
-    Ok(CanonicalBuildOutput {
-        model,
-        query_semantic_payload,
+        hashes,
     })
 }
 
+fn build_canonical_gene(rows: &RowSpool, gene: &GeneRecord) -> Result<CanonicalGene, IngestError> {
+    let strand = Strand::parse(&gene.strand).unwrap_or(Strand::Unknown);
+    let mut transcripts = Vec::new();
+    for tx in rows.transcripts_of_gene(&gene.gene_id)? {
+        let mut tx_exons = Vec::new();
+        for exon in rows.exons_of_transcript(&tx.transcript_id)? {
+            let lineage_records = lineage_for_exon(rows, &exon)?;
+            tx_exons.push(canonical_exon(&exon, lineage_records));
+        }
+        tx_exons.sort_by(compare_exon);
+        let mut tx_cds = rows.cds_of_transcript(&tx.transcript_id)?;
+        tx_cds.sort_by(compare_cds);
+        let tx_lineage = lineage_for_transcript(rows, &tx)?;
+        transcripts.push(canonical_transcript(
+            &tx, strand, tx_exons, tx_cds, tx_lineage,
+        ));
+    }
+    transcripts.sort_by(compare_transcript);
+    let gene_lineage = lineage_for_gene(rows, gene)?;
+    Ok(canonical_gene(gene, transcripts, gene_lineage))
+}
+
 #[must_use]
 pub fn contig_order_rank(seqid: &str) -> (u32, u64, String) {
     let trimmed = seqid.trim();
@@ -304,6 +388,7 @@ fn canonical_gene(
 
 fn canonical_transcript(
     tx: &TranscriptRecord,
+    strand: Strand,
     exons: Vec<CanonicalExon>,
     cds_segments: Vec<CanonicalCds>,
     lineage: Vec<LineageRecord>,
@@ -320,6 +405,44 @@ fn canonical_transcript(
     } else {
         AnnotationCompleteness::Complete
     };
+    let exon_spans: Vec<FeatureSpan> = exons
+        .iter()
+        .map(|ex| FeatureSpan {
+            start: ex.start,
+            end: ex.end,
+            phase: None,
+        })
+        .collect();
+    let cds_spans: Vec<FeatureSpan> = cds_segments
+        .iter()
+        .map(|cds| FeatureSpan {
+            start: cds.start,
+            end: cds.end,
+            phase: None,
+        })
+        .collect();
+    let introns = derive_introns(&exon_spans, strand)
+        .into_iter()
+        .map(|intron| CanonicalIntron {
+            transcript_id: tx.transcript_id.clone(),
+            seqid: tx.seqid.clone(),
+            start: intron.start,
+            end: intron.end,
+            intron_rank: intron.rank,
+            feature_type: CanonicalFeatureType::Intron,
+        })
+        .collect();
+    let utrs = derive_utrs(&exon_spans, &cds_spans, strand)
+        .into_iter()
+        .map(|utr| CanonicalUtr {
+            transcript_id: tx.transcript_id.clone(),
+            seqid: tx.seqid.clone(),
+            kind: utr.kind,
+            start: utr.start,
+            end: utr.end,
+            feature_type: CanonicalFeatureType::Utr,
+        })
+        .collect();
     CanonicalTranscript {
         transcript_id: tx.transcript_id.clone(),
         parent_gene_id: tx.parent_gene_id.clone(),
@@ -339,6 +462,8 @@ fn canonical_transcript(
         completeness,
         exons,
         cds_segments,
+        introns,
+        utrs,
         lineage,
     }
 }
@@ -381,14 +506,6 @@ fn canonical_cds(rec: &Gff3Record, transcript_id: &str) -> CanonicalCds {
     }
 }
 
-fn compare_gene(a: &CanonicalGene, b: &CanonicalGene) -> Ordering {
-    contig_order_rank(&a.seqid)
-        .cmp(&contig_order_rank(&b.seqid))
-        .then(a.start.cmp(&b.start))
-        .then(a.end.cmp(&b.end))
-        .then(a.gene_id.cmp(&b.gene_id))
-}
-
 fn compare_transcript(a: &CanonicalTranscript, b: &CanonicalTranscript) -> Ordering {
     contig_order_rank(&a.seqid)
         .cmp(&contig_order_rank(&b.seqid))
@@ -413,29 +530,6 @@ fn compare_cds(a: &CanonicalCds, b: &CanonicalCds) -> Ordering {
         .then(a.cds_id.cmp(&b.cds_id))
 }
 
-fn build_lineage_index(
-    records: &[Gff3Record],
-) -> Result<BTreeMap<(String, String, u64, u64), Vec<LineageRecord>>, IngestError> {
-    let mut out: BTreeMap<(String, String, u64, u64), Vec<LineageRecord>> = BTreeMap::new();
-    for rec in records {
-        let key = (
-            rec.feature_type.clone(),
-            rec.seqid.clone(),
-            rec.start,
-            rec.end,
-        );
-        out.entry(key).or_default().push(lineage_from_record(rec));
-    }
-    for entries in out.values_mut() {
-        entries.sort_by(|a, b| {
-            a.gff3_line
-                .cmp(&b.gff3_line)
-                .then(a.record_sha256.cmp(&b.record_sha256))
-        });
-    }
-    Ok(out)
-}
-
 fn lineage_from_record(rec: &Gff3Record) -> LineageRecord {
     let parent_ids = split_parents(rec.attrs.get("Parent"))
         .into_iter()
@@ -451,44 +545,28 @@ fn lineage_from_record(rec: &Gff3Record) -> LineageRecord {
     }
 }
 
-fn lineage_for_gene(
-    idx: &BTreeMap<(String, String, u64, u64), Vec<LineageRecord>>,
-    gene: &GeneRecord,
-) -> Vec<LineageRecord> {
-    idx.get(&("gene".to_string(), gene.seqid.clone(), gene.start, gene.end))
-        .cloned()
-        .unwrap_or_default()
+fn lineage_for_gene(rows: &RowSpool, gene: &GeneRecord) -> Result<Vec<LineageRecord>, IngestError> {
+    Ok(rows
+        .lineage("gene", &gene.seqid, gene.start, gene.end)?
+        .unwrap_or_default())
 }
 
 fn lineage_for_transcript(
-    idx: &BTreeMap<(String, String, u64, u64), Vec<LineageRecord>>,
+    rows: &RowSpool,
     tx: &TranscriptRecord,
-) -> Vec<LineageRecord> {
-    idx.get(&(
-        tx.transcript_type.clone(),
-        tx.seqid.clone(),
-        tx.start,
-        tx.end,
-    ))
-    .cloned()
-    .or_else(|| {
-        idx.get(&("transcript".to_string(), tx.seqid.clone(), tx.start, tx.end))
-            .cloned()
-    })
-    .or_else(|| {
-        idx.get(&("mRNA".to_string(), tx.seqid.clone(), tx.start, tx.end))
-            .cloned()
-    })
-    .unwrap_or_default()
+) -> Result<Vec<LineageRecord>, IngestError> {
+    for feature_type in [tx.transcript_type.as_str(), "transcript", "mRNA"] {
+        if let Some(found) = rows.lineage(feature_type, &tx.seqid, tx.start, tx.end)? {
+            return Ok(found);
+        }
+    }
+    Ok(Vec::new())
 }
 
-fn lineage_for_exon(
-    idx: &BTreeMap<(String, String, u64, u64), Vec<LineageRecord>>,
-    exon: &ExonRecord,
-) -> Vec<LineageRecord> {
-    idx.get(&("exon".to_string(), exon.seqid.clone(), exon.start, exon.end))
-        .cloned()
-        .unwrap_or_default()
+fn lineage_for_exon(rows: &RowSpool, exon: &ExonRecord) -> Result<Vec<LineageRecord>, IngestError> {
+    Ok(rows
+        .lineage("exon", &exon.seqid, exon.start, exon.end)?
+        .unwrap_or_default())
 }
 
 fn split_parents(parent: Option<&String>) -> Vec<&str> {
@@ -502,111 +580,89 @@ fn split_parents(parent: Option<&String>) -> Vec<&str> {
         .unwrap_or_default()
 }
 
-fn summarize_model(genes: &[CanonicalGene]) -> CanonicalSummary {
-    let mut transcripts = 0_u64;
-    let mut exons = 0_u64;
-    let mut cds = 0_u64;
-    let mut coding_transcripts = 0_u64;
-    let mut noncoding_transcripts = 0_u64;
-    let mut pseudogenes = 0_u64;
-    let mut partial_genes = 0_u64;
-    let mut partial_transcripts = 0_u64;
-    let mut feature_type_counts = BTreeMap::new();
-    let mut contig_gene_counts = BTreeMap::new();
-
-    for gene in genes {
-        *feature_type_counts.entry("gene".to_string()).or_insert(0) += 1;
-        *contig_gene_counts.entry(gene.seqid.clone()).or_insert(0) += 1;
-        if gene.annotation_class == GeneAnnotationClass::Pseudogene {
-            pseudogenes += 1;
-        }
-        if gene.completeness != AnnotationCompleteness::Complete {
-            partial_genes += 1;
+fn tally_gene(summary: &mut CanonicalSummary, gene: &CanonicalGene) {
+    summary.genes += 1;
+    let feature_type_counts = &mut summary.feature_type_counts;
+    *feature_type_counts.entry("gene".to_string()).or_insert(0) += 1;
+    *summary
+        .contig_gene_counts
+        .entry(gene.seqid.clone())
+        .or_insert(0) += 1;
+    if gene.annotation_class == GeneAnnotationClass::Pseudogene {
+        summary.pseudogenes += 1;
+    }
+    if gene.completeness != AnnotationCompleteness::Complete {
+        summary.partial_genes += 1;
+    }
+    for tx in &gene.transcripts {
+        summary.transcripts += 1;
+        *feature_type_counts
+            .entry("transcript".to_string())
+            .or_insert(0) += 1;
+        if tx.coding_class == FeatureCodingClass::Coding {
+            summary.coding_transcripts += 1;
+        } else {
+            summary.noncoding_transcripts += 1;
         }
-        for tx in &gene.transcripts {
-            transcripts += 1;
-            *feature_type_counts
-                .entry("transcript".to_string())
-                .or_insert(0) += 1;
-            if tx.coding_class == FeatureCodingClass::Coding {
-                coding_transcripts += 1;
-            } else {
-                noncoding_transcripts += 1;
-            }
-            if tx.completeness != AnnotationCompleteness::Complete {
-                partial_transcripts += 1;
-            }
-            exons += tx.exons.len() as u64;
-            cds += tx.cds_segments.len() as u64;
-            *feature_type_counts.entry("exon".to_string()).or_insert(0) += tx.exons.len() as u64;
-            *feature_type_counts.entry("cds".to_string()).or_insert(0) +=
-                tx.cds_segments.len() as u64;
+        if tx.completeness != AnnotationCompleteness::Complete {
+            summary.partial_transcripts += 1;
         }
-    }
-    CanonicalSummary {
-        genes: genes.len() as u64,
-        transcripts,
-        exons,
-        cds,
-        contigs: contig_gene_counts.len() as u64,
-        coding_transcripts,
-        noncoding_transcripts,
-        pseudogenes,
-        partial_genes,
-        partial_transcripts,
-        feature_type_counts,
-        contig_gene_counts,
+        summary.exons += tx.exons.len() as u64;
+        summary.cds += tx.cds_segments.len() as u64;
+        *feature_type_counts.entry("exon".to_string()).or_insert(0) += tx.exons.len() as u64;
+        *feature_type_counts.entry("cds".to_string()).or_insert(0) += tx.cds_segments.len() as u64;
+        *feature_type_counts.entry("intron".to_string()).or_insert(0) += tx.introns.len() as u64;
+        *feature_type_counts.entry("utr".to_string()).or_insert(0) += tx.utrs.len() as u64;
     }
 }
 
-fn semantic_payload(genes: &[CanonicalGene], summary: &CanonicalSummary) -> serde_json::Value {
-    let gene_items = genes
-        .iter()
-        .map(|g| {
-            serde_json::json!({
-                "gene_id": g.gene_id,
-                "biotype": g.biotype,
-                "seqid": g.seqid,
-                "start": g.start,
-                "end": g.end,
-                "annotation_class": g.annotation_class,
-                "completeness": g.completeness,
-                "transcripts": g.transcripts.iter().map(|tx| serde_json::json!({
-                    "transcript_id": tx.transcript_id,
-                    "parent_gene_id": tx.parent_gene_id,
-                    "transcript_type": tx.transcript_type,
-                    "biotype": tx.biotype,
-                    "seqid": tx.seqid,
-                    "start": tx.start,
-                    "end": tx.end,
-                    "coding_class": tx.coding_class,
-                    "completeness": tx.completeness,
-                    "exons": tx.exons.iter().map(|ex| serde_json::json!({
-                        "exon_id": ex.exon_id,
-                        "start": ex.start,
-                        "end": ex.end
-                    })).collect::<Vec<_>>(),
-                    "cds_segments": tx.cds_segments.iter().map(|cds| serde_json::json!({
-                        "cds_id": cds.cds_id,
-                        "start": cds.start,
-                        "end": cds.end,
-                        "phase": cds.phase
-                    })).collect::<Vec<_>>()
-                })).collect::<Vec<_>>()
-            })
-        })
-        .collect::<Vec<_>>();
+fn semantic_gene(g: &CanonicalGene) -> serde_json::Value {
     serde_json::json!({
-        "schema_version": 1,
-        "summary": summary,
-        "genes": gene_items
+        "gene_id": g.gene_id,
+        "biotype": g.biotype,
+        "seqid": g.seqid,
+        "start": g.start,
+        "end": g.end,
+        "annotation_class": g.annotation_class,
+        "completeness": g.completeness,
+        "transcripts": g.transcripts.iter().map(|tx| serde_json::json!({
+            "transcript_id": tx.transcript_id,
+            "parent_gene_id": tx.parent_gene_id,
+            "transcript_type": tx.transcript_type,
+            "biotype": tx.biotype,
+            "seqid": tx.seqid,
+            "start": tx.start,
+            "end": tx.end,
+            "coding_class": tx.coding_class,
+            "completeness": tx.completeness,
+            "exons": tx.exons.iter().map(|ex| serde_json::json!({
+                "exon_id": ex.exon_id,
+                "start": ex.start,
+                "end": ex.end
+            })).collect::<Vec<_>>(),
+            "cds_segments": tx.cds_segments.iter().map(|cds| serde_json::json!({
+                "cds_id": cds.cds_id,
+                "start": cds.start,
+                "end": cds.end,
+                "phase": cds.phase
+            })).collect::<Vec<_>>(),
+            "introns": tx.introns.iter().map(|intron| serde_json::json!({
+                "rank": intron.intron_rank,
+                "start": intron.start,
+                "end": intron.end
+            })).collect::<Vec<_>>(),
+            "utrs": tx.utrs.iter().map(|utr| serde_json::json!({
+                "kind": utr.kind,
+                "start": utr.start,
+                "end": utr.end
+            })).collect::<Vec<_>>()
+        })).collect::<Vec<_>>()
     })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::dataset::IngestAnomalyReport;
 
     #[test]
     fn contig_order_rank_is_stable_for_numeric_and_special_contigs() {
@@ -634,33 +690,25 @@ mod tests {
 
     #[test]
     fn canonical_model_marks_pseudogene_and_missing_transcripts() {
-        let extract = ExtractResult {
-            gene_rows: vec![GeneRecord {
-                gene_id: "g1".to_string(),
-                gene_name: "G1".to_string(),
-                biotype: "pseudogene".to_string(),
-                seqid: "chr1".to_string(),
-                start: 10,
-                end: 20,
-                transcript_count: 0,
-                exon_count: 0,
-                total_exon_span: 0,
-                cds_present: false,
-                sequence_length: 11,
-            }],
-            transcript_rows: vec![],
-            exon_rows: vec![],
-            anomaly: IngestAnomalyReport::default(),
-            biotype_distribution: BTreeMap::new(),
-            contig_distribution: BTreeMap::new(),
-            total_features: 1,
-            unknown_contig_features: 0,
-            max_contig_name_length: 4,
-            cds_feature_count: 0,
-            contig_class_distribution: BTreeMap::new(),
-            seqid_normalization_traces: BTreeMap::new(),
-            biotype_source_counts: BTreeMap::new(),
-        };
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let rows = RowSpool::create(tmp.path()).expect("row spool");
+        rows.push_gene_candidate(&GeneRecord {
+            gene_id: "g1".to_string(),
+            gene_name: "G1".to_string(),
+            biotype: "pseudogene".to_string(),
+            seqid: "chr1".to_string(),
+            start: 10,
+            end: 20,
+            transcript_count: 0,
+            exon_count: 0,
+            total_exon_span: 0,
+            cds_present: false,
+            sequence_length: 11,
+            strand: "+".to_string(),
+            description: None,
+        })
+        .expect("push gene");
+        rows.select_gene_winners(false).expect("select genes");
         let records = vec![Gff3Record {
             line: 1,
             seqid: "chr1".to_string(),
@@ -676,9 +724,20 @@ mod tests {
             duplicate_attr_keys: Default::default(),
             raw_line: "chr1\tsrc\tgene\t10\t20\t.\t+\t.\tID=g1;Name=G1".to_string(),
         }];
-        let model = build_canonical_model(&records, &extract)
-            .expect("build canonical model")
-            .model;
+        let builder = CanonicalModelBuilder::new(&rows);
+        for rec in &records {
+            builder.observe(rec).expect("observe record");
+        }
+        let features_path = tmp.path().join("canonical_features.json");
+        write_canonical_model(
+            &rows,
+            &features_path,
+            &tmp.path().join("canonical_summary.json"),
+        )
+        .expect("write canonical model");
+        let model: CanonicalModel =
+            serde_json::from_slice(&fs::read(&features_path).expect("read model"))
+                .expect("decode model");
         assert_eq!(model.summary.pseudogenes, 1);
         assert_eq!(model.summary.partial_genes, 1);
         assert_eq!(
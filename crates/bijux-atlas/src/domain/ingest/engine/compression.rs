@@ -0,0 +1,375 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::IngestError;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+const GZIP_FLAG_EXTRA: u8 = 0x04;
+
+/// Container format of an ingest input, detected from its leading bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InputCompression {
+    None,
+    Gzip,
+    Bgzf,
+    Zstd,
+}
+
+impl InputCompression {
+    #[must_use]
+    pub fn detect(header: &[u8]) -> Self {
+        if header.starts_with(&ZSTD_MAGIC) {
+            return Self::Zstd;
+        }
+        if !header.starts_with(&GZIP_MAGIC) {
+            return Self::None;
+        }
+        // BGZF is gzip with an FEXTRA "BC" subfield carrying the block size.
+        let is_bgzf = header.len() >= 16
+            && header[3] & GZIP_FLAG_EXTRA != 0
+            && header[12] == b'B'
+            && header[13] == b'C';
+        if is_bgzf {
+            Self::Bgzf
+        } else {
+            Self::Gzip
+        }
+    }
+
+    #[must_use]
+    pub fn is_compressed(self) -> bool {
+        !matches!(self, Self::None)
+    }
+}
+
+pub fn detect_compression(path: &Path) -> Result<InputCompression, IngestError> {
+    let mut file = fs::File::open(path).map_err(|e| IngestError(e.to_string()))?;
+    let mut header = [0_u8; 18];
+    let mut filled = 0;
+    while filled < header.len() {
+        let n = file
+            .read(&mut header[filled..])
+            .map_err(|e| IngestError(e.to_string()))?;
+        if n == 0 {
+            break;
+        }
+        filled += n;
+    }
+    Ok(InputCompression::detect(&header[..filled]))
+}
+
+/// Opens an input and transparently decodes gzip, BGZF or zstd content.
+pub fn open_decoded(path: &Path) -> Result<Box<dyn BufRead>, IngestError> {
+    let compression = detect_compression(path)?;
+    let file = fs::File::open(path).map_err(|e| IngestError(e.to_string()))?;
+    Ok(match compression {
+        InputCompression::None => Box::new(BufReader::new(file)),
+        InputCompression::Gzip | InputCompression::Bgzf => Box::new(BufReader::new(
+            flate2::read::MultiGzDecoder::new(BufReader::new(file)),
+        )),
+        InputCompression::Zstd => Box::new(BufReader::new(
+            zstd::stream::read::Decoder::new(file).map_err(|e| IngestError(e.to_string()))?,
+        )),
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DualDigest {
+    pub compression: InputCompression,
+    pub stored_sha256: String,
+    pub decompressed_sha256: String,
+}
+
+/// Hashes an input both as stored and after decompression in one streaming pass.
+pub fn hash_stored_and_decompressed(path: &Path) -> Result<DualDigest, IngestError> {
+    let compression = detect_compression(path)?;
+    let mut stored = Sha256::new();
+    let mut buf = vec![0_u8; 64 * 1024];
+    let mut file = fs::File::open(path).map_err(|e| IngestError(e.to_string()))?;
+    loop {
+        let n = file
+            .read(&mut buf)
+            .map_err(|e| IngestError(e.to_string()))?;
+        if n == 0 {
+            break;
+        }
+        stored.update(&buf[..n]);
+    }
+    let stored_sha256 = hex::encode(stored.finalize());
+    let decompressed_sha256 = if compression.is_compressed() {
+        let mut decoded = Sha256::new();
+        let mut reader = open_decoded(path)?;
+        loop {
+            let n = reader
+                .read(&mut buf)
+                .map_err(|e| IngestError(format!("decompression failed: {e}")))?;
+            if n == 0 {
+                break;
+            }
+            decoded.update(&buf[..n]);
+        }
+        hex::encode(decoded.finalize())
+    } else {
+        stored_sha256.clone()
+    };
+    Ok(DualDigest {
+        compression,
+        stored_sha256,
+        decompressed_sha256,
+    })
+}
+
+#[must_use]
+pub fn gzi_path_for(path: &Path) -> PathBuf {
+    let mut raw = path.as_os_str().to_os_string();
+    raw.push(".gzi");
+    PathBuf::from(raw)
+}
+
+/// Reads a `.gzi` index: a little-endian entry count followed by
+/// `(compressed_offset, uncompressed_offset)` pairs, one per BGZF block after the first.
+pub fn read_gzi_index(path: &Path) -> Result<Vec<(u64, u64)>, IngestError> {
+    let raw = fs::read(path).map_err(|e| IngestError(e.to_string()))?;
+    let read_u64 = |at: usize| -> Result<u64, IngestError> {
+        raw.get(at..at + 8)
+            .and_then(|b| <[u8; 8]>::try_from(b).ok())
+            .map(u64::from_le_bytes)
+            .ok_or_else(|| IngestError(format!("truncated gzi index: {}", path.display())))
+    };
+    let count = read_u64(0)?;
+    let expected_len = 8_u64.saturating_add(count.saturating_mul(16));
+    if raw.len() as u64 != expected_len {
+        return Err(IngestError(format!(
+            "gzi index size mismatch: {} declares {count} entries",
+            path.display()
+        )));
+    }
+    let mut entries = Vec::with_capacity(count as usize + 1);
+    entries.push((0, 0));
+    for i in 0..count as usize {
+        entries.push((read_u64(8 + i * 16)?, read_u64(16 + i * 16)?));
+    }
+    Ok(entries)
+}
+
+/// Builds the `.gzi` entries of a BGZF file from its block headers, in the
+/// shape [`read_gzi_index`] returns. Empty blocks, such as the EOF marker,
+/// are not listed.
+pub fn build_gzi_index(path: &Path) -> Result<Vec<(u64, u64)>, IngestError> {
+    let io = |e: std::io::Error| IngestError(format!("{}: {e}", path.display()));
+    let not_bgzf = |at: u64| IngestError(format!("{} is not BGZF at byte {at}", path.display()));
+    let mut file = BufReader::new(fs::File::open(path).map_err(io)?);
+    let len = file.get_ref().metadata().map_err(io)?.len();
+    let mut entries = vec![(0, 0)];
+    let (mut compressed, mut decoded) = (0_u64, 0_u64);
+    while compressed < len {
+        let mut header = [0_u8; 12];
+        file.read_exact(&mut header).map_err(io)?;
+        if !header.starts_with(&GZIP_MAGIC) || header[3] & GZIP_FLAG_EXTRA == 0 {
+            return Err(not_bgzf(compressed));
+        }
+        let mut extra = vec![0_u8; usize::from(u16::from_le_bytes([header[10], header[11]]))];
+        file.read_exact(&mut extra).map_err(io)?;
+        let header_len = 12 + extra.len() as u64;
+        let block_len = bgzf_block_len(&extra)
+            .filter(|block_len| *block_len >= header_len + 8)
+            .ok_or_else(|| not_bgzf(compressed))?;
+        let to_isize =
+            i64::try_from(block_len - header_len - 4).map_err(|_| not_bgzf(compressed))?;
+        file.seek_relative(to_isize).map_err(io)?;
+        let mut isize = [0_u8; 4];
+        file.read_exact(&mut isize).map_err(io)?;
+        let block_decoded = u64::from(u32::from_le_bytes(isize));
+        if compressed > 0 && block_decoded > 0 {
+            entries.push((compressed, decoded));
+        }
+        compressed += block_len;
+        decoded += block_decoded;
+    }
+    Ok(entries)
+}
+
+/// Total block length from the `BC` subfield of a BGZF gzip extra field.
+fn bgzf_block_len(extra: &[u8]) -> Option<u64> {
+    let mut rest = extra;
+    while rest.len() >= 4 {
+        let field_len = usize::from(u16::from_le_bytes([rest[2], rest[3]]));
+        let data = rest.get(4..4 + field_len)?;
+        if rest[..2] == *b"BC" && field_len == 2 {
+            return Some(u64::from(u16::from_le_bytes([data[0], data[1]])) + 1);
+        }
+        rest = &rest[4 + field_len..];
+    }
+    None
+}
+
+/// `entries`, as returned by [`build_gzi_index`], in the `.gzi` file layout.
+#[must_use]
+pub fn encode_gzi_index(entries: &[(u64, u64)]) -> Vec<u8> {
+    let listed = entries.iter().filter(|entry| **entry != (0, 0));
+    let mut raw = (listed.clone().count() as u64).to_le_bytes().to_vec();
+    for (compressed, decoded) in listed {
+        raw.extend(compressed.to_le_bytes());
+        raw.extend(decoded.to_le_bytes());
+    }
+    raw
+}
+
+/// Reads `len` decoded bytes starting at decoded `offset`.
+///
+/// Plain files are read with a direct seek. BGZF files seek to the nearest
+/// block listed in a sibling `.gzi` index; without an index, and for other
+/// compressed containers, the stream is decoded from the start.
+pub fn read_decoded_window(path: &Path, offset: u64, len: usize) -> Result<Vec<u8>, IngestError> {
+    let compression = detect_compression(path)?;
+    let mut buf = vec![0_u8; len];
+    if compression == InputCompression::None {
+        let mut file = fs::File::open(path).map_err(|e| IngestError(e.to_string()))?;
+        file.seek(SeekFrom::Start(offset))
+            .map_err(|e| IngestError(e.to_string()))?;
+        file.read_exact(&mut buf)
+            .map_err(|e| IngestError(e.to_string()))?;
+        return Ok(buf);
+    }
+    let gzi = gzi_path_for(path);
+    let (block_compressed, block_decoded) = if compression == InputCompression::Bgzf && gzi.exists()
+    {
+        read_gzi_index(&gzi)?
+            .into_iter()
+            .take_while(|(_, decoded)| *decoded <= offset)
+            .last()
+            .unwrap_or((0, 0))
+    } else {
+        (0, 0)
+    };
+    let mut reader: Box<dyn Read> = if block_compressed == 0 {
+        open_decoded(path)?
+    } else {
+        let mut file = fs::File::open(path).map_err(|e| IngestError(e.to_string()))?;
+        file.seek(SeekFrom::Start(block_compressed))
+            .map_err(|e| IngestError(e.to_string()))?;
+        Box::new(flate2::read::MultiGzDecoder::new(BufReader::new(file)))
+    };
+    let skip = offset - block_decoded;
+    let skipped = std::io::copy(&mut reader.by_ref().take(skip), &mut std::io::sink())
+        .map_err(|e| IngestError(e.to_string()))?;
+    if skipped != skip {
+        return Err(IngestError(format!(
+            "decoded offset {offset} is beyond end of {}",
+            path.display()
+        )));
+    }
+    reader
+        .read_exact(&mut buf)
+        .map_err(|e| IngestError(e.to_string()))?;
+    Ok(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+    use tempfile::tempdir;
+
+    /// Writes `payload` as BGZF-style gzip members of `block` bytes each and
+    /// returns the matching `.gzi` entries.
+    fn write_bgzf(path: &Path, payload: &[u8], block: usize) -> Vec<(u64, u64)> {
+        let mut out = Vec::new();
+        let mut index = Vec::new();
+        for (i, chunk) in payload.chunks(block).enumerate() {
+            if i > 0 {
+                index.push((out.len() as u64, (i * block) as u64));
+            }
+            let mut encoder = flate2::GzBuilder::new()
+                .extra(vec![b'B', b'C', 2, 0, 0, 0])
+                .write(Vec::new(), Compression::default());
+            encoder.write_all(chunk).expect("encode block");
+            let mut block = encoder.finish().expect("finish block");
+            let bsize = u16::try_from(block.len() - 1).expect("block size");
+            block[16..18].copy_from_slice(&bsize.to_le_bytes());
+            out.extend(block);
+        }
+        fs::write(path, out).expect("write bgzf");
+        index
+    }
+
+    #[test]
+    fn detects_gzip_bgzf_zstd_and_plain_inputs() {
+        let tmp = tempdir().expect("tmp");
+        let plain = tmp.path().join("x.fa");
+        fs::write(&plain, ">chr1\nACGT\n").expect("plain");
+        let gz = tmp.path().join("x.fa.gz");
+        let mut enc = GzEncoder::new(Vec::new(), Compression::default());
+        enc.write_all(b">chr1\nACGT\n").expect("gz");
+        fs::write(&gz, enc.finish().expect("gz finish")).expect("write gz");
+        let zst = tmp.path().join("x.fa.zst");
+        fs::write(
+            &zst,
+            zstd::stream::encode_all(&b">chr1\nACGT\n"[..], 3).expect("zstd"),
+        )
+        .expect("write zst");
+        let bgz = tmp.path().join("x.fa.bgz");
+        write_bgzf(&bgz, b">chr1\nACGT\n", 4);
+
+        assert_eq!(
+            detect_compression(&plain).expect("plain"),
+            InputCompression::None
+        );
+        assert_eq!(detect_compression(&gz).expect("gz"), InputCompression::Gzip);
+        assert_eq!(
+            detect_compression(&zst).expect("zst"),
+            InputCompression::Zstd
+        );
+        assert_eq!(
+            detect_compression(&bgz).expect("bgz"),
+            InputCompression::Bgzf
+        );
+        for path in [&plain, &gz, &zst, &bgz] {
+            let digest = hash_stored_and_decompressed(path).expect("digest");
+            assert_eq!(
+                digest.decompressed_sha256,
+                crate::domain::sha256_hex(b">chr1\nACGT\n")
+            );
+        }
+    }
+
+    #[test]
+    fn bgzf_window_reads_match_with_and_without_gzi_index() {
+        let tmp = tempdir().expect("tmp");
+        let payload = (0..4_000).map(|i| b"ACGT"[i % 4]).collect::<Vec<_>>();
+        let bgz = tmp.path().join("genome.fa.bgz");
+        let entries = write_bgzf(&bgz, &payload, 512);
+        let expected = payload[1_500..1_540].to_vec();
+        assert_eq!(
+            read_decoded_window(&bgz, 1_500, 40).expect("scan read"),
+            expected
+        );
+        let built = build_gzi_index(&bgz).expect("build gzi");
+        assert_eq!(built[1..], entries[..]);
+        fs::write(gzi_path_for(&bgz), encode_gzi_index(&built)).expect("write gzi");
+        assert_eq!(read_gzi_index(&gzi_path_for(&bgz)).expect("gzi"), built);
+        assert_eq!(built.len(), 8);
+        assert_eq!(
+            read_decoded_window(&bgz, 1_500, 40).expect("indexed read"),
+            expected
+        );
+    }
+
+    #[test]
+    fn gzi_build_rejects_plain_gzip() {
+        let tmp = tempdir().expect("tmp");
+        let gz = tmp.path().join("x.fa.gz");
+        let mut enc = GzEncoder::new(Vec::new(), Compression::default());
+        enc.write_all(b">chr1\nACGT\n").expect("gz");
+        fs::write(&gz, enc.finish().expect("gz finish")).expect("write gz");
+        let err = build_gzi_index(&gz).expect_err("plain gzip has no block sizes");
+        assert!(err.0.contains("not BGZF"), "{err:?}");
+    }
+}
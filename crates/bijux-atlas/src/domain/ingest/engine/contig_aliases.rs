@@ -0,0 +1,217 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Rows for the `contig_aliases` table.
+//!
+//! A UCSC `chromAlias` file is authoritative: a name it maps onto two dataset
+//! contigs fails ingest. Aliases derived from the inputs (raw FAI and
+//! `##sequence-region` names, FASTA header descriptions and `chr`-prefix
+//! spellings) are best effort, and a derived name that points at two contigs
+//! is dropped rather than guessed.
+
+use super::compression::open_decoded;
+use super::fai::ContigStats;
+use super::gff3::SequenceRegion;
+use super::IngestError;
+use crate::domain::query::{
+    chr_prefix_alternate, ContigAlias, SeqidNormalizationPolicy, CHROM_ALIAS_SOURCE,
+};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::BufRead as _;
+use std::path::Path;
+
+pub fn build_contig_aliases(
+    seqid_policy: &SeqidNormalizationPolicy,
+    contigs: &BTreeMap<String, ContigStats>,
+    sequence_regions: &[SequenceRegion],
+    chrom_alias_path: Option<&Path>,
+) -> Result<Vec<ContigAlias>, IngestError> {
+    let canonical: BTreeSet<String> = contigs.keys().map(|c| seqid_policy.normalize(c)).collect();
+    let mut out: BTreeMap<String, ContigAlias> = BTreeMap::new();
+    if let Some(path) = chrom_alias_path {
+        for alias in read_chrom_alias_file(path, seqid_policy, &canonical)? {
+            out.insert(alias.alias.clone(), alias);
+        }
+    }
+
+    let mut derived: Vec<(String, String, &'static str)> = Vec::new();
+    for (raw, stats) in contigs {
+        let contig = seqid_policy.normalize(raw);
+        derived.push((raw.clone(), contig.clone(), "fasta_header"));
+        for name in stats
+            .description
+            .as_deref()
+            .map(fasta_description_names)
+            .unwrap_or_default()
+        {
+            derived.push((name, contig.clone(), "fasta_header"));
+        }
+    }
+    for region in sequence_regions {
+        let contig = seqid_policy.normalize(&region.seqid);
+        derived.push((region.seqid.clone(), contig, "sequence_region"));
+    }
+    let spelled: Vec<(String, String)> = derived
+        .iter()
+        .map(|(name, contig, _)| (name.clone(), contig.clone()))
+        .chain(canonical.iter().map(|c| (c.clone(), c.clone())))
+        .collect();
+    for (name, contig) in spelled {
+        if let Some(alternate) = chr_prefix_alternate(&name) {
+            derived.push((alternate, contig, "chr_prefix"));
+        }
+    }
+
+    let mut targets: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    for (alias, contig, _) in &derived {
+        targets.entry(alias).or_default().insert(contig);
+    }
+    for (alias, contig, source) in &derived {
+        if canonical.contains(alias) || out.contains_key(alias) || targets[alias.as_str()].len() > 1
+        {
+            continue;
+        }
+        out.insert(
+            alias.clone(),
+            ContigAlias {
+                alias: alias.clone(),
+                contig: contig.clone(),
+                source: (*source).to_string(),
+            },
+        );
+    }
+    Ok(out.into_values().collect())
+}
+
+/// Reads a `chromAlias` file, plain or compressed. Each row lists names of one
+/// sequence; the row's dataset contig is whichever name normalizes to one, and
+/// rows naming no dataset contig are skipped. A leading `#` row names the
+/// columns, which become the alias `source`.
+fn read_chrom_alias_file(
+    path: &Path,
+    seqid_policy: &SeqidNormalizationPolicy,
+    canonical: &BTreeSet<String>,
+) -> Result<Vec<ContigAlias>, IngestError> {
+    let mut columns: Vec<String> = Vec::new();
+    let mut out: BTreeMap<String, ContigAlias> = BTreeMap::new();
+    for (idx, line) in open_decoded(path)?.lines().enumerate() {
+        let line = line.map_err(|e| IngestError(format!("chromAlias read failed: {e}")))?;
+        if let Some(header) = line.strip_prefix('#') {
+            if idx == 0 {
+                columns = header.split_whitespace().map(str::to_string).collect();
+            }
+            continue;
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+        let fields: Vec<&str> = if line.contains('\t') {
+            line.split('\t').map(str::trim).collect()
+        } else {
+            line.split_whitespace().collect()
+        };
+        let contigs: BTreeSet<String> = fields
+            .iter()
+            .filter(|f| !f.is_empty())
+            .map(|f| seqid_policy.normalize(f))
+            .filter(|c| canonical.contains(c))
+            .collect();
+        let mut contigs = contigs.into_iter();
+        let Some(contig) = contigs.next() else {
+            continue;
+        };
+        if let Some(other) = contigs.next() {
+            return Err(IngestError(format!(
+                "CHROM_ALIAS_AMBIGUOUS_ROW line={} names contigs {contig} and {other}",
+                idx + 1
+            )));
+        }
+        for (col, name) in fields.iter().enumerate() {
+            if name.is_empty() || *name == contig {
+                continue;
+            }
+            if let Some(previous) = out.get(*name) {
+                if previous.contig != contig {
+                    return Err(IngestError(format!(
+                        "CHROM_ALIAS_CONFLICT line={} alias={name} maps to {} and {contig}",
+                        idx + 1,
+                        previous.contig
+                    )));
+                }
+                continue;
+            }
+            let source = columns
+                .get(col)
+                .cloned()
+                .unwrap_or_else(|| CHROM_ALIAS_SOURCE.to_string());
+            out.insert(
+                (*name).to_string(),
+                ContigAlias {
+                    alias: (*name).to_string(),
+                    contig: contig.clone(),
+                    source,
+                },
+            );
+        }
+    }
+    Ok(out.into_values().collect())
+}
+
+/// Other names a FASTA header description gives its sequence: the name field
+/// of Ensembl `chromosome:GRCh38:1:1:248956422:1` locations, NCBI
+/// `chromosome 1,` phrases and RefSeq/GenBank accessions such as `NC_000001.11`.
+fn fasta_description_names(description: &str) -> Vec<String> {
+    let tokens: Vec<&str> = description.split_whitespace().collect();
+    let mut out = Vec::new();
+    for (i, token) in tokens.iter().enumerate() {
+        let parts: Vec<&str> = token.split(':').collect();
+        if parts.len() == 6 && !parts[2].is_empty() {
+            out.push(parts[2].to_string());
+        } else if *token == "chromosome" {
+            // Only whole chromosomes: "chromosome 1 unlocalized ..." names a scaffold.
+            if let Some(next) = tokens.get(i + 1) {
+                let is_last = i + 2 == tokens.len();
+                let name = next.trim_end_matches(',');
+                if !name.is_empty() && (is_last || next.ends_with(',')) {
+                    out.push(name.to_string());
+                }
+            }
+        } else if is_accession(token.trim_end_matches(',')) {
+            out.push(token.trim_end_matches(',').to_string());
+        }
+    }
+    out
+}
+
+fn is_accession(token: &str) -> bool {
+    let Some((stem, version)) = token.split_once('.') else {
+        return false;
+    };
+    !version.is_empty()
+        && version.bytes().all(|b| b.is_ascii_digit())
+        && stem.bytes().next().is_some_and(|b| b.is_ascii_uppercase())
+        && stem.bytes().any(|b| b.is_ascii_digit())
+        && stem.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fasta_description_names;
+
+    #[test]
+    fn fasta_descriptions_yield_chromosome_names_and_accessions() {
+        assert_eq!(
+            fasta_description_names("dna:chromosome chromosome:GRCh38:1:1:248956422:1 REF"),
+            vec!["1"]
+        );
+        assert_eq!(
+            fasta_description_names(
+                "Homo sapiens chromosome 1, GRCh38.p14 Primary Assembly NC_000001.11"
+            ),
+            vec!["1", "NC_000001.11"]
+        );
+        assert!(fasta_description_names(
+            "Homo sapiens chromosome 1 unlocalized genomic scaffold, GRCh38.p14"
+        )
+        .is_empty());
+    }
+}
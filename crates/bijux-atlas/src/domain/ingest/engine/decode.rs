@@ -3,19 +3,24 @@
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
 
-use super::canonical_model::{build_canonical_model, CanonicalModel};
-use super::extract::{extract_gene_rows, ExtractResult};
+use super::canonical_model::CanonicalModelBuilder;
+use super::contig_aliases::build_contig_aliases;
+use super::extract::{scan_feature_index, ExtractResult, GeneRowExtractor};
 use super::fai::{self, ContigStats};
-use super::gff3::{parse_gff3_records, parse_sequence_regions, validate_sequence_region_conflicts};
+use super::gff3::{
+    parse_sequence_regions, validate_sequence_region_conflicts, Gff3GeneBlocks, Gff3RecordReader,
+};
 use super::job::IngestJob;
+use super::spool::RowSpool;
 use super::{IngestError, IngestOptions};
-use crate::domain::query::canonical_contig_label;
+use crate::domain::query::{canonical_contig_label, ContigAlias};
 
 pub struct DecodedIngest {
     pub contig_stats: BTreeMap<String, ContigStats>,
+    pub contig_aliases: Vec<ContigAlias>,
     pub extract: ExtractResult,
-    pub canonical_model: CanonicalModel,
-    pub canonical_query_semantic_payload: serde_json::Value,
+    /// Gene, transcript and exon rows spooled to scratch SQLite; removed on drop.
+    pub rows: RowSpool,
 }
 
 pub fn decode_ingest_inputs(job: &IngestJob) -> Result<DecodedIngest, IngestError> {
@@ -52,6 +57,7 @@ pub fn decode_ingest_inputs(job: &IngestJob) -> Result<DecodedIngest, IngestErro
                         length: *v,
                         gc_fraction: None,
                         n_fraction: None,
+                        description: None,
                     },
                 )
             })
@@ -61,29 +67,45 @@ pub fn decode_ingest_inputs(job: &IngestJob) -> Result<DecodedIngest, IngestErro
     let sequence_regions = parse_sequence_regions(&job.inputs.gff3_path)?;
     validate_sequence_region_conflicts(&sequence_regions)?;
     validate_sequence_regions_against_fai(&sequence_regions, &contig_lengths)?;
-    let records = parse_gff3_records(&job.inputs.gff3_path)?;
-    validate_scientific_reference_coherence(&records, opts)?;
-    validate_gff3_reference_names(&records, &contig_lengths)?;
-    let mut extract = extract_gene_rows(records.clone(), &contig_lengths, opts)?;
-    apply_deterministic_ordering(&mut extract);
-    let canonical = build_canonical_model(&records, &extract)?;
+    let contig_aliases = build_contig_aliases(
+        &opts.seqid_policy,
+        &contig_stats,
+        &sequence_regions,
+        opts.chrom_alias_path.as_deref(),
+    )?;
+    let rows = RowSpool::create(&opts.output_root)?;
+    let index = scan_feature_index(Gff3RecordReader::open(&job.inputs.gff3_path)?, opts, &rows)?;
+    validate_scientific_reference_coherence(&index.seqids, opts)?;
+    validate_gff3_reference_names(&index.seqids, &contig_lengths)?;
+    let mut extractor = GeneRowExtractor::new(&rows, &contig_lengths, opts)?;
+    let canonical_builder = CanonicalModelBuilder::new(&rows);
+    for block in Gff3GeneBlocks::new(Gff3RecordReader::open(&job.inputs.gff3_path)?) {
+        let block = block?;
+        rows.in_transaction(|| {
+            for rec in &block {
+                canonical_builder.observe(rec)?;
+            }
+            extractor.push_block(block)
+        })?;
+    }
+    let extract = extractor.finish()?;
 
     Ok(DecodedIngest {
         contig_stats,
+        contig_aliases,
         extract,
-        canonical_model: canonical.model,
-        canonical_query_semantic_payload: canonical.query_semantic_payload,
+        rows,
     })
 }
 
 fn validate_scientific_reference_coherence(
-    records: &[super::gff3::Gff3Record],
+    seqids: &BTreeSet<String>,
     opts: &IngestOptions,
 ) -> Result<(), IngestError> {
     let mut core_sources: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
     let mut core_normalized: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
-    for rec in records {
-        let label = canonical_contig_label(&rec.seqid);
+    for seqid in seqids {
+        let label = canonical_contig_label(seqid);
         let is_core =
             label.parse::<u64>().is_ok() || matches!(label.as_str(), "x" | "y" | "mitochondrial");
         if !is_core {
@@ -92,11 +114,11 @@ fn validate_scientific_reference_coherence(
         core_sources
             .entry(label.clone())
             .or_default()
-            .insert(rec.seqid.clone());
+            .insert(seqid.clone());
         core_normalized
             .entry(label)
             .or_default()
-            .insert(opts.seqid_policy.normalize(&rec.seqid));
+            .insert(opts.seqid_policy.normalize(seqid));
     }
     let mut incoherent = Vec::new();
     for (label, raw_set) in core_sources {
@@ -141,15 +163,14 @@ fn validate_sequence_regions_against_fai(
 }
 
 fn validate_gff3_reference_names(
-    records: &[super::gff3::Gff3Record],
+    seqids: &BTreeSet<String>,
     contig_lengths: &BTreeMap<String, u64>,
 ) -> Result<(), IngestError> {
-    let mut missing = BTreeSet::new();
-    for rec in records {
-        if !contig_lengths.contains_key(&rec.seqid) {
-            missing.insert(rec.seqid.clone());
-        }
-    }
+    let missing = seqids
+        .iter()
+        .filter(|seqid| !contig_lengths.contains_key(*seqid))
+        .cloned()
+        .collect::<BTreeSet<_>>();
     if !missing.is_empty() {
         let refs = missing.into_iter().collect::<Vec<_>>().join(", ");
         return Err(IngestError(format!(
@@ -159,24 +180,3 @@ fn validate_gff3_reference_names(
     }
     Ok(())
 }
-
-fn apply_deterministic_ordering(extract: &mut ExtractResult) {
-    extract.gene_rows.sort_by(|a, b| {
-        a.seqid
-            .cmp(&b.seqid)
-            .then(a.start.cmp(&b.start))
-            .then(a.gene_id.cmp(&b.gene_id))
-    });
-    extract.transcript_rows.sort_by(|a, b| {
-        a.seqid
-            .cmp(&b.seqid)
-            .then(a.start.cmp(&b.start))
-            .then(a.transcript_id.cmp(&b.transcript_id))
-    });
-    extract.exon_rows.sort_by(|a, b| {
-        a.seqid
-            .cmp(&b.seqid)
-            .then(a.start.cmp(&b.start))
-            .then(a.exon_id.cmp(&b.exon_id))
-    });
-}
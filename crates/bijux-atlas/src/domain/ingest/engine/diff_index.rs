@@ -1,12 +1,15 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::extract::GeneRecord;
+use super::spool::{RowSpool, SPILLED_ARRAY_SLOT};
 use super::IngestError;
 use crate::domain::canonical::{self, sha256_hex};
 use crate::domain::dataset::DatasetId;
 use crate::domain::query::{
     GeneId, GeneSignatureInput, ReleaseGeneIndex, ReleaseGeneIndexEntry, SeqId,
 };
+use std::fs;
+use std::io::{BufWriter, Write};
 use std::path::Path;
 
 fn signature_for_gene(row: &GeneRecord) -> Result<String, IngestError> {
@@ -23,23 +26,47 @@ fn signature_for_gene(row: &GeneRecord) -> Result<String, IngestError> {
     Ok(sha256_hex(&bytes))
 }
 
+/// Same fields as [`signature_for_gene`] minus coordinates, so lifted genes can be compared.
+fn attributes_signature_for_gene(row: &GeneRecord) -> Result<String, IngestError> {
+    let payload = serde_json::json!({
+        "gene_id": row.gene_id,
+        "name": row.gene_name,
+        "biotype": row.biotype,
+        "transcript_count": row.transcript_count,
+    });
+    let bytes = canonical::stable_json_bytes(&payload).map_err(|e| IngestError(e.to_string()))?;
+    Ok(sha256_hex(&bytes))
+}
+
+/// Streams entries in gene-id order, the order `ReleaseGeneIndexEntry` sorts by.
 pub fn build_and_write_release_gene_index(
     dataset: &DatasetId,
     output_path: &Path,
-    rows: &[GeneRecord],
+    rows: &RowSpool,
 ) -> Result<(), IngestError> {
-    let mut entries = Vec::with_capacity(rows.len());
-    for row in rows {
-        entries.push(ReleaseGeneIndexEntry::new(
-            GeneId::parse(&row.gene_id).map_err(|e| IngestError(e.to_string()))?,
-            SeqId::parse(&row.seqid).map_err(|e| IngestError(e.to_string()))?,
-            row.start,
-            row.end,
-            signature_for_gene(row)?,
-        ));
-    }
-    entries.sort();
-    let index = ReleaseGeneIndex::new("1".to_string(), dataset.clone(), entries);
-    let bytes = canonical::stable_json_bytes(&index).map_err(|e| IngestError(e.to_string()))?;
-    std::fs::write(output_path, bytes).map_err(|e| IngestError(e.to_string()))
+    let mut entries = rows.spill_array("release_gene_index_entries.json")?;
+    rows.for_each_gene_by_id(|row| {
+        entries.push(
+            &ReleaseGeneIndexEntry::new(
+                GeneId::parse(&row.gene_id).map_err(|e| IngestError(e.to_string()))?,
+                SeqId::parse(&row.seqid).map_err(|e| IngestError(e.to_string()))?,
+                row.start,
+                row.end,
+                signature_for_gene(&row)?,
+            )
+            .with_attributes_sha256(attributes_signature_for_gene(&row)?),
+        )
+    })?;
+    let entries = entries.finish()?;
+    let mut index = serde_json::to_value(ReleaseGeneIndex::new(
+        "1".to_string(),
+        dataset.clone(),
+        Vec::new(),
+    ))
+    .map_err(|e| IngestError(e.to_string()))?;
+    index["entries"] = serde_json::Value::String(SPILLED_ARRAY_SLOT.to_string());
+    let io = |e: std::io::Error| IngestError(e.to_string());
+    let mut out = BufWriter::new(fs::File::create(output_path).map_err(io)?);
+    entries.splice_into(&index, &mut out)?;
+    out.flush().map_err(io)
 }
@@ -1,16 +1,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::gff3::Gff3Record;
+use super::spool::{RowScope, RowSpool};
 use super::{IngestError, IngestOptions};
 use crate::domain::canonical;
 use crate::domain::dataset::{IngestAnomalyReport, IngestRejection};
 use crate::domain::policy::{GeneIdentifierPolicy, StrictnessMode};
 use crate::domain::query::{
-    classify_contig, DuplicateGeneIdPolicy, DuplicateTranscriptIdPolicy, FeatureIdUniquenessPolicy,
-    SeqidNormalizationTrace, UnknownFeaturePolicy,
+    classify_contig, split_xref, DuplicateGeneIdPolicy, DuplicateTranscriptIdPolicy,
+    FeatureIdUniquenessPolicy, SeqidNormalizationTrace, UnknownFeaturePolicy, XREF_ALIAS_NAMESPACE,
 };
 use serde::Serialize;
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
 #[derive(Debug, Clone, Serialize)]
 pub struct GeneRecord {
@@ -25,6 +26,9 @@ pub struct GeneRecord {
     pub total_exon_span: u64,
     pub cds_present: bool,
     pub sequence_length: u64,
+    pub strand: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -54,18 +58,21 @@ pub struct ExonRecord {
     pub exon_length: u64,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum ParentErrorClass {
-    MissingParentAttribute,
-    MultipleParents,
-    MissingReferencedParent,
+/// One `Dbxref`, `Ontology_term` or `Alias` entry of a gene, split into namespace and value.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GeneXrefRecord {
+    pub gene_id: String,
+    pub namespace: String,
+    pub value: String,
 }
 
+/// Extraction outcome; the gene, transcript, exon and cross-reference rows
+/// themselves stay in the [`RowSpool`] they were extracted into.
 #[derive(Debug, Clone)]
 pub struct ExtractResult {
-    pub gene_rows: Vec<GeneRecord>,
-    pub transcript_rows: Vec<TranscriptRecord>,
-    pub exon_rows: Vec<ExonRecord>,
+    pub gene_count: u64,
+    pub transcript_count: u64,
+    pub exon_count: u64,
     pub anomaly: IngestAnomalyReport,
     pub biotype_distribution: BTreeMap<String, u64>,
     pub contig_distribution: BTreeMap<String, u64>,
@@ -76,71 +83,146 @@ pub struct ExtractResult {
     pub contig_class_distribution: BTreeMap<String, u64>,
     pub seqid_normalization_traces: BTreeMap<String, SeqidNormalizationTrace>,
     pub biotype_source_counts: BTreeMap<String, u64>,
+    pub gene_blocks: u64,
+    pub max_gene_block_records: u64,
 }
 
-pub fn extract_gene_rows(
-    records: Vec<Gff3Record>,
-    contig_lengths: &BTreeMap<String, u64>,
+/// Seqids seen in a first pass over the GFF3 stream. The gene and transcript
+/// IDs and the `Parent` edges of that pass go to the [`RowSpool`], so later
+/// gene blocks can resolve references to features anywhere in the file.
+#[derive(Debug, Default)]
+pub struct Gff3FeatureIndex {
+    pub seqids: BTreeSet<String>,
+    pub records: u64,
+}
+
+pub fn scan_feature_index<I>(
+    records: I,
     opts: &IngestOptions,
-) -> Result<ExtractResult, IngestError> {
-    let mut genes: HashMap<String, Vec<GeneRecord>> = HashMap::new();
-    let mut transcript_parents: Vec<(String, ParentErrorClass)> = Vec::new();
-    let mut transcript_rows_pending: Vec<TranscriptRecord> = Vec::new();
-    let mut transcript_exon_counts: HashMap<String, u64> = HashMap::new();
-    let mut transcript_exon_span: HashMap<String, u64> = HashMap::new();
-    let mut transcript_has_cds: HashMap<String, bool> = HashMap::new();
-    let mut transcript_cds_span: HashMap<String, u64> = HashMap::new();
-    let mut exon_rows: Vec<ExonRecord> = Vec::new();
-    let mut anomaly = IngestAnomalyReport::default();
-    let mut total_features = 0_u64;
-    let mut unknown_contig_features = 0_u64;
-    let mut max_contig_name_length = 0_usize;
-    let mut cds_feature_count = 0_u64;
-    let mut biotype_source_counts: BTreeMap<String, u64> = BTreeMap::new();
-
-    let mut seen_feature_ids: HashMap<String, String> = HashMap::new();
-    let mut child_parent_refs: Vec<String> = Vec::new();
-    let mut normalized_seqid_sources: HashMap<String, HashSet<String>> = HashMap::new();
-    let gene_feature_ids: HashSet<String> = records
-        .iter()
-        .filter(|rec| rec.feature_type == "gene")
-        .filter_map(|rec| rec.attrs.get("ID").cloned())
-        .collect();
-    let transcript_feature_ids: HashSet<String> = records
-        .iter()
-        .filter(|rec| opts.transcript_type_policy.accepts(&rec.feature_type))
-        .filter_map(|rec| opts.transcript_id_policy.resolve(&rec.attrs))
-        .collect();
-    let parent_cycles = detect_parent_cycles(&records);
-    if !parent_cycles.is_empty() {
-        anomaly.parent_cycles = parent_cycles;
-        if matches!(opts.strictness, StrictnessMode::Strict) {
-            return Err(IngestError(
-                "cyclic Parent graph detected in GFF3 features".to_string(),
-            ));
+    rows: &RowSpool,
+) -> Result<Gff3FeatureIndex, IngestError>
+where
+    I: IntoIterator<Item = Result<Gff3Record, IngestError>>,
+{
+    let mut index = Gff3FeatureIndex::default();
+    rows.in_transaction(|| {
+        for rec in records {
+            let rec = rec?;
+            index.records += 1;
+            if !index.seqids.contains(&rec.seqid) {
+                index.seqids.insert(rec.seqid.clone());
+            }
+            if rec.feature_type == "gene" {
+                if let Some(id) = rec.attrs.get("ID") {
+                    rows.record_gene_id(id)?;
+                }
+            }
+            if opts.transcript_type_policy.accepts(&rec.feature_type) {
+                if let Some(tx_id) = opts.transcript_id_policy.resolve(&rec.attrs) {
+                    rows.record_transcript_id(&tx_id)?;
+                }
+            }
+            if let Some(id) = rec.attrs.get("ID") {
+                let parents = rec
+                    .attrs
+                    .get("Parent")
+                    .map(|x| {
+                        x.split(',')
+                            .map(str::trim)
+                            .filter(|p| !p.is_empty())
+                            .map(ToString::to_string)
+                            .collect::<Vec<_>>()
+                    })
+                    .unwrap_or_default();
+                rows.record_parent_edges(id, &parents)?;
+            }
+        }
+        Ok(())
+    })?;
+    Ok(index)
+}
+
+/// Incremental gene-row extraction: records are pushed gene block by gene
+/// block and their derived rows are written to the [`RowSpool`], so nothing
+/// grows with the input except the anomaly report and per-contig state.
+pub struct GeneRowExtractor<'a> {
+    rows: &'a RowSpool,
+    contig_lengths: &'a BTreeMap<String, u64>,
+    opts: &'a IngestOptions,
+    anomaly: IngestAnomalyReport,
+    total_features: u64,
+    unknown_contig_features: u64,
+    max_contig_name_length: usize,
+    cds_feature_count: u64,
+    biotype_source_counts: BTreeMap<String, u64>,
+    normalized_seqid_sources: HashMap<String, HashSet<String>>,
+    gene_blocks: u64,
+    max_gene_block_records: u64,
+}
+
+impl<'a> GeneRowExtractor<'a> {
+    pub fn new(
+        rows: &'a RowSpool,
+        contig_lengths: &'a BTreeMap<String, u64>,
+        opts: &'a IngestOptions,
+    ) -> Result<Self, IngestError> {
+        let mut anomaly = IngestAnomalyReport::default();
+        let parent_cycles = detect_parent_cycles(&rows.unresolved_parent_edges()?);
+        if !parent_cycles.is_empty() {
+            anomaly.parent_cycles = parent_cycles;
+            if matches!(opts.strictness, StrictnessMode::Strict) {
+                return Err(IngestError(
+                    "cyclic Parent graph detected in GFF3 features".to_string(),
+                ));
+            }
         }
+        Ok(Self {
+            rows,
+            contig_lengths,
+            opts,
+            anomaly,
+            total_features: 0,
+            unknown_contig_features: 0,
+            max_contig_name_length: 0,
+            cds_feature_count: 0,
+            biotype_source_counts: BTreeMap::new(),
+            normalized_seqid_sources: HashMap::new(),
+            gene_blocks: 0,
+            max_gene_block_records: 0,
+        })
     }
 
-    for rec in records {
-        total_features += 1;
+    pub fn push_block(&mut self, block: Vec<Gff3Record>) -> Result<(), IngestError> {
+        self.gene_blocks += 1;
+        self.max_gene_block_records = self.max_gene_block_records.max(block.len() as u64);
+        for rec in block {
+            self.push_record(rec)?;
+        }
+        Ok(())
+    }
+
+    fn push_record(&mut self, rec: Gff3Record) -> Result<(), IngestError> {
+        self.total_features += 1;
         if rec.feature_type == "CDS" {
-            cds_feature_count += 1;
+            self.cds_feature_count += 1;
         }
-        let seqid_trace = opts.seqid_policy.normalize_with_trace(&rec.seqid);
+        let seqid_trace = self.opts.seqid_policy.normalize_with_trace(&rec.seqid);
         let seqid = seqid_trace.normalized_seqid.clone();
-        max_contig_name_length = max_contig_name_length.max(seqid.len());
-        normalized_seqid_sources
+        self.max_contig_name_length = self.max_contig_name_length.max(seqid.len());
+        self.normalized_seqid_sources
             .entry(seqid.clone())
             .or_default()
             .insert(rec.seqid.clone());
-        if opts.reject_normalized_seqid_collisions
-            && normalized_seqid_sources
+        if self.opts.reject_normalized_seqid_collisions
+            && self
+                .normalized_seqid_sources
                 .get(&seqid)
                 .map(|s| s.len() > 1)
                 .unwrap_or(false)
-            && matches!(opts.strictness, StrictnessMode::Strict)
+            && matches!(self.opts.strictness, StrictnessMode::Strict)
         {
-            let sources = normalized_seqid_sources
+            let sources = self
+                .normalized_seqid_sources
                 .get(&seqid)
                 .cloned()
                 .unwrap_or_default();
@@ -151,39 +233,37 @@ pub fn extract_gene_rows(
         }
 
         for dup in &rec.duplicate_attr_keys {
-            anomaly.overlapping_ids.push(dup.clone());
+            self.anomaly.overlapping_ids.push(dup.clone());
         }
 
         if let Some(fid_raw) = rec.attrs.get("ID") {
             let mut fid = fid_raw.clone();
             if matches!(
-                opts.feature_id_uniqueness_policy,
+                self.opts.feature_id_uniqueness_policy,
                 FeatureIdUniquenessPolicy::NormalizeAsciiLowercaseReject
             ) {
                 fid = fid.to_ascii_lowercase();
             }
             let key = if matches!(
-                opts.feature_id_uniqueness_policy,
+                self.opts.feature_id_uniqueness_policy,
                 FeatureIdUniquenessPolicy::NamespaceByFeatureType
             ) {
                 format!("{}::{fid}", rec.feature_type)
             } else {
                 fid.clone()
             };
-            if let Some(previous_kind) = seen_feature_ids.get(&key) {
-                anomaly.overlapping_ids.push(fid_raw.clone());
+            if let Some(previous_kind) = self.rows.claim_feature_key(&key, &rec.feature_type)? {
+                self.anomaly.overlapping_ids.push(fid_raw.clone());
                 let reject_on_duplicate = !matches!(
-                    opts.feature_id_uniqueness_policy,
+                    self.opts.feature_id_uniqueness_policy,
                     FeatureIdUniquenessPolicy::NamespaceByFeatureType
                 ) && rec.feature_type != "gene";
-                if reject_on_duplicate && matches!(opts.strictness, StrictnessMode::Strict) {
+                if reject_on_duplicate && matches!(self.opts.strictness, StrictnessMode::Strict) {
                     return Err(IngestError(format!(
                         "duplicate feature ID detected: {fid_raw} ({previous_kind}, {})",
                         rec.feature_type
                     )));
                 }
-            } else {
-                seen_feature_ids.insert(key, rec.feature_type.clone());
             }
         }
 
@@ -193,45 +273,46 @@ pub fn extract_gene_rows(
                 .get("ID")
                 .cloned()
                 .ok_or_else(|| IngestError("gene feature missing ID attribute".to_string()))?;
-            let gene_id = opts
+            let gene_id = self
+                .opts
                 .gene_identifier_policy
                 .resolve(
                     &rec.attrs,
                     &gff3_id,
-                    matches!(opts.strictness, StrictnessMode::Strict),
+                    matches!(self.opts.strictness, StrictnessMode::Strict),
                 )
                 .map_err(|e| IngestError(e.to_string()))?;
-            if used_gene_id_fallback(&rec.attrs, opts) {
-                anomaly
+            if used_gene_id_fallback(&rec.attrs, self.opts) {
+                self.anomaly
                     .attribute_fallbacks
                     .push(format!("gene_id_fallback:{gff3_id}"));
             }
 
-            let Some(contig_len) = contig_lengths.get(&seqid) else {
-                anomaly.unknown_contigs.push(seqid.clone());
-                unknown_contig_features += 1;
-                if matches!(opts.strictness, StrictnessMode::Strict) {
+            let Some(contig_len) = self.contig_lengths.get(&seqid) else {
+                self.anomaly.unknown_contigs.push(seqid.clone());
+                self.unknown_contig_features += 1;
+                if matches!(self.opts.strictness, StrictnessMode::Strict) {
                     return Err(IngestError(format!("contig not found in FAI: {seqid}")));
                 }
-                continue;
+                return Ok(());
             };
             if rec.end > *contig_len {
                 let msg = format!(
                     "gene {gene_id} coordinate end {} exceeds contig {seqid} length {contig_len}",
                     rec.end
                 );
-                if matches!(opts.strictness, StrictnessMode::Strict) {
+                if matches!(self.opts.strictness, StrictnessMode::Strict) {
                     return Err(IngestError(msg));
                 }
-                anomaly.unknown_contigs.push(seqid.clone());
-                unknown_contig_features += 1;
-                continue;
+                self.anomaly.unknown_contigs.push(seqid.clone());
+                self.unknown_contig_features += 1;
+                return Ok(());
             }
 
             let record = GeneRecord {
                 gene_id: gene_id.clone(),
-                gene_name: opts.gene_name_policy.resolve(&rec.attrs, &gene_id),
-                biotype: opts.biotype_policy.resolve(&rec.attrs),
+                gene_name: self.opts.gene_name_policy.resolve(&rec.attrs, &gene_id),
+                biotype: self.opts.biotype_policy.resolve(&rec.attrs),
                 seqid,
                 start: rec.start,
                 end: rec.end,
@@ -240,55 +321,68 @@ pub fn extract_gene_rows(
                 total_exon_span: 0,
                 cds_present: false,
                 sequence_length: rec.end - rec.start + 1,
+                strand: rec.strand.clone(),
+                description: rec
+                    .attrs
+                    .get("description")
+                    .map(|v| v.trim())
+                    .filter(|v| !v.is_empty())
+                    .map(str::to_string),
             };
-            *biotype_source_counts
-                .entry(resolve_biotype_source_key(&rec.attrs, opts))
+            *self
+                .biotype_source_counts
+                .entry(resolve_biotype_source_key(&rec.attrs, self.opts))
                 .or_insert(0) += 1;
             if record.gene_name == gene_id {
-                anomaly
+                self.anomaly
                     .attribute_fallbacks
                     .push(format!("gene_name_fallback:{gene_id}"));
             }
-            if record.biotype == opts.biotype_policy.unknown_value {
-                anomaly
+            if record.biotype == self.opts.biotype_policy.unknown_value {
+                self.anomaly
                     .attribute_fallbacks
                     .push(format!("biotype_fallback:{gene_id}"));
-                anomaly
+                self.anomaly
                     .scientific_ambiguities
                     .push(format!("unknown_biotype:{gene_id}"));
             }
-            genes.entry(gene_id).or_default().push(record);
-        } else if opts.transcript_type_policy.accepts(&rec.feature_type) {
-            let Some(tx_id) = opts.transcript_id_policy.resolve(&rec.attrs) else {
+            for (namespace, value) in gene_xrefs_from_attrs(&rec.attrs) {
+                self.rows.push_gene_xref(&gene_id, &namespace, &value)?;
+            }
+            self.rows.push_gene_candidate(&record)?;
+        } else if self.opts.transcript_type_policy.accepts(&rec.feature_type) {
+            let Some(tx_id) = self.opts.transcript_id_policy.resolve(&rec.attrs) else {
                 let missing_key = format!(
                     "missing transcript id for {}:{}-{}",
                     seqid, rec.start, rec.end
                 );
-                anomaly.missing_required_fields.push(missing_key.clone());
-                anomaly.rejections.push(IngestRejection::new(
+                self.anomaly
+                    .missing_required_fields
+                    .push(missing_key.clone());
+                self.anomaly.rejections.push(IngestRejection::new(
                     rec.line,
                     "GFF3_MISSING_TRANSCRIPT_ID".to_string(),
                     rec.raw_line.clone(),
                 ));
-                if matches!(opts.strictness, StrictnessMode::Strict) {
+                if matches!(self.opts.strictness, StrictnessMode::Strict) {
                     return Err(IngestError(missing_key));
                 }
-                continue;
+                return Ok(());
             };
             let Some(parent_attr) = rec.attrs.get("Parent") else {
-                transcript_parents.push((tx_id.clone(), ParentErrorClass::MissingParentAttribute));
-                anomaly.missing_transcript_parents.push(tx_id);
-                anomaly.rejections.push(IngestRejection::new(
+                self.rows.push_transcript_parent(&tx_id, false)?;
+                self.anomaly.missing_transcript_parents.push(tx_id);
+                self.anomaly.rejections.push(IngestRejection::new(
                     rec.line,
                     "GFF3_MISSING_PARENT".to_string(),
                     rec.raw_line.clone(),
                 ));
-                if matches!(opts.strictness, StrictnessMode::Strict) {
+                if matches!(self.opts.strictness, StrictnessMode::Strict) {
                     return Err(IngestError(
                         "transcript feature missing Parent attribute".to_string(),
                     ));
                 }
-                continue;
+                return Ok(());
             };
 
             let parents: Vec<String> = parent_attr
@@ -299,44 +393,44 @@ pub fn extract_gene_rows(
                 .collect();
 
             if parents.len() > 1 {
-                anomaly.multiple_parent_transcripts.push(tx_id.clone());
-                if matches!(opts.strictness, StrictnessMode::Strict) {
+                self.anomaly.multiple_parent_transcripts.push(tx_id.clone());
+                if matches!(self.opts.strictness, StrictnessMode::Strict) {
                     return Err(IngestError(format!(
                         "transcript {tx_id} has multiple Parent references"
                     )));
                 }
-                anomaly.rejections.push(IngestRejection::new(
+                self.anomaly.rejections.push(IngestRejection::new(
                     rec.line,
                     "GFF3_MULTI_PARENT_TRANSCRIPT".to_string(),
                     rec.raw_line.clone(),
                 ));
                 for p in parents {
-                    transcript_parents.push((p, ParentErrorClass::MultipleParents));
+                    self.rows.push_transcript_parent(&p, true)?;
                 }
             } else if let Some(p) = parents.into_iter().next() {
-                if !gene_feature_ids.contains(&p) {
-                    anomaly.missing_parents.push(format!("{tx_id}:{p}"));
-                    anomaly.rejections.push(IngestRejection::new(
+                if !self.rows.is_gene_id(&p)? {
+                    self.anomaly.missing_parents.push(format!("{tx_id}:{p}"));
+                    self.anomaly.rejections.push(IngestRejection::new(
                         rec.line,
                         "GFF3_PARENT_NOT_GENE".to_string(),
                         rec.raw_line.clone(),
                     ));
-                    if matches!(opts.strictness, StrictnessMode::Strict) {
+                    if matches!(self.opts.strictness, StrictnessMode::Strict) {
                         return Err(IngestError(format!(
                             "GFF3_PARENT_NOT_GENE line={} transcript={} parent={} expected_gene_id=true",
                             rec.line, tx_id, p
                         )));
                     }
-                    continue;
+                    return Ok(());
                 }
-                transcript_parents.push((p.clone(), ParentErrorClass::MissingReferencedParent));
+                self.rows.push_transcript_parent(&p, false)?;
                 let transcript_biotype = rec
                     .attrs
                     .get("transcript_biotype")
                     .or_else(|| rec.attrs.get("biotype"))
                     .or_else(|| rec.attrs.get("gene_biotype"))
                     .cloned();
-                transcript_rows_pending.push(TranscriptRecord {
+                self.rows.push_pending_transcript(&TranscriptRecord {
                     transcript_id: tx_id,
                     parent_gene_id: p,
                     transcript_type: rec.feature_type.clone(),
@@ -350,26 +444,26 @@ pub fn extract_gene_rows(
                     sequence_length: rec.end.saturating_sub(rec.start) + 1,
                     spliced_length: None,
                     cds_span_length: None,
-                });
+                })?;
             }
         } else if rec.feature_type == "exon" || rec.feature_type == "CDS" {
             let Some(parent_attr) = rec.attrs.get("Parent") else {
-                anomaly.missing_required_fields.push(format!(
+                self.anomaly.missing_required_fields.push(format!(
                     "{} missing Parent at {}:{}-{}",
                     rec.feature_type, seqid, rec.start, rec.end
                 ));
-                anomaly.rejections.push(IngestRejection::new(
+                self.anomaly.rejections.push(IngestRejection::new(
                     rec.line,
                     "GFF3_MISSING_PARENT".to_string(),
                     rec.raw_line.clone(),
                 ));
-                if matches!(opts.strictness, StrictnessMode::Strict) {
+                if matches!(self.opts.strictness, StrictnessMode::Strict) {
                     return Err(IngestError(format!(
                         "{} feature missing Parent attribute",
                         rec.feature_type
                     )));
                 }
-                continue;
+                return Ok(());
             };
             let parents = parent_attr
                 .split(',')
@@ -377,68 +471,69 @@ pub fn extract_gene_rows(
                 .filter(|x| !x.is_empty())
                 .map(ToString::to_string)
                 .collect::<Vec<_>>();
-            if parents.len() > 1 && matches!(opts.strictness, StrictnessMode::Strict) {
+            if parents.len() > 1 && matches!(self.opts.strictness, StrictnessMode::Strict) {
                 return Err(IngestError(format!(
                     "{} has multiple Parent references",
                     rec.feature_type
                 )));
             }
             if parents.len() > 1 {
-                anomaly.rejections.push(IngestRejection::new(
+                self.anomaly.rejections.push(IngestRejection::new(
                     rec.line,
                     "GFF3_MULTI_PARENT_CHILD".to_string(),
                     rec.raw_line.clone(),
                 ));
             }
             for tx_id in parents {
-                if !transcript_feature_ids.contains(&tx_id) {
-                    anomaly.missing_transcript_parents.push(tx_id.clone());
-                    anomaly.rejections.push(IngestRejection::new(
+                if !self.rows.is_transcript_id(&tx_id)? {
+                    self.anomaly.missing_transcript_parents.push(tx_id.clone());
+                    self.anomaly.rejections.push(IngestRejection::new(
                         rec.line,
                         "GFF3_PARENT_NOT_TRANSCRIPT".to_string(),
                         rec.raw_line.clone(),
                     ));
-                    if matches!(opts.strictness, StrictnessMode::Strict) {
+                    if matches!(self.opts.strictness, StrictnessMode::Strict) {
                         return Err(IngestError(format!(
                             "GFF3_PARENT_NOT_TRANSCRIPT line={} feature_type={} parent={} expected_transcript_id=true",
                             rec.line, rec.feature_type, tx_id
                         )));
                     }
-                    continue;
+                    return Ok(());
                 }
-                child_parent_refs.push(tx_id.clone());
+                self.rows.push_child_parent_ref(&tx_id)?;
+                let span = rec.end.saturating_sub(rec.start) + 1;
                 if rec.feature_type == "exon" {
                     let exon_id = rec
                         .attrs
                         .get("ID")
                         .cloned()
                         .unwrap_or_else(|| format!("line{}_{}", rec.line, tx_id));
-                    exon_rows.push(ExonRecord {
-                        exon_id: exon_id.clone(),
+                    self.rows.push_exon(&ExonRecord {
+                        exon_id,
                         transcript_id: tx_id.clone(),
                         seqid: seqid.clone(),
                         start: rec.start,
                         end: rec.end,
-                        exon_length: rec.end.saturating_sub(rec.start) + 1,
-                    });
-                    *transcript_exon_counts.entry(tx_id.clone()).or_insert(0) += 1;
-                    *transcript_exon_span.entry(tx_id).or_insert(0) +=
-                        rec.end.saturating_sub(rec.start) + 1;
+                        exon_length: span,
+                    })?;
+                    self.rows.push_child_span(&tx_id, false, span)?;
                 } else if rec.feature_type == "CDS" {
-                    transcript_has_cds.insert(tx_id.clone(), true);
-                    *transcript_cds_span.entry(tx_id).or_insert(0) +=
-                        rec.end.saturating_sub(rec.start) + 1;
+                    self.rows.push_child_span(&tx_id, true, span)?;
                 }
             }
         } else {
-            anomaly.unknown_feature_types.push(rec.feature_type.clone());
-            anomaly.rejections.push(IngestRejection::new(
+            self.anomaly
+                .unknown_feature_types
+                .push(rec.feature_type.clone());
+            self.anomaly.rejections.push(IngestRejection::new(
                 rec.line,
                 "GFF3_UNKNOWN_FEATURE".to_string(),
                 rec.raw_line.clone(),
             ));
-            if matches!(opts.unknown_feature_policy, UnknownFeaturePolicy::Reject)
-                && matches!(opts.strictness, StrictnessMode::Strict)
+            if matches!(
+                self.opts.unknown_feature_policy,
+                UnknownFeaturePolicy::Reject
+            ) && matches!(self.opts.strictness, StrictnessMode::Strict)
             {
                 return Err(IngestError(format!(
                     "unknown GFF3 feature type: {}",
@@ -446,267 +541,208 @@ pub fn extract_gene_rows(
                 )));
             }
         }
+        Ok(())
     }
 
-    let mut deduped: HashMap<String, GeneRecord> = HashMap::new();
-    let mut keys: Vec<String> = genes.keys().cloned().collect();
-    keys.sort();
-    for key in keys {
-        let Some(mut candidates) = genes.remove(&key) else {
-            continue;
-        };
-        if candidates.len() > 1 {
-            anomaly.duplicate_gene_ids.push(key.clone());
-            let seqids: std::collections::BTreeSet<String> =
-                candidates.iter().map(|x| x.seqid.clone()).collect();
-            if seqids.len() > 1 {
-                anomaly
-                    .overlapping_gene_ids_across_contigs
-                    .push(key.clone());
-                if !opts.allow_overlap_gene_ids_across_contigs
-                    && matches!(opts.strictness, StrictnessMode::Strict)
-                {
-                    return Err(IngestError(format!(
-                        "gene_id {key} appears across multiple contigs: {:?}",
-                        seqids
-                    )));
-                }
-            }
-            match opts.duplicate_gene_id_policy {
-                DuplicateGeneIdPolicy::Fail => {
-                    if matches!(opts.strictness, StrictnessMode::Strict) {
-                        return Err(IngestError(format!("duplicate gene_id: {key}")));
+    pub fn finish(self) -> Result<ExtractResult, IngestError> {
+        let opts = self.opts;
+        let rows = self.rows;
+        let normalized_seqid_sources = self.normalized_seqid_sources;
+        let mut anomaly = self.anomaly;
+        let strict = matches!(opts.strictness, StrictnessMode::Strict);
+
+        rows.in_transaction(|| {
+            rows.for_each_duplicate_gene(|key, seqids| {
+                anomaly.duplicate_gene_ids.push(key.clone());
+                if seqids.len() > 1 {
+                    anomaly
+                        .overlapping_gene_ids_across_contigs
+                        .push(key.clone());
+                    if !opts.allow_overlap_gene_ids_across_contigs && strict {
+                        let seqids: BTreeSet<String> = seqids.into_iter().collect();
+                        return Err(IngestError(format!(
+                            "gene_id {key} appears across multiple contigs: {:?}",
+                            seqids
+                        )));
                     }
                 }
-                DuplicateGeneIdPolicy::DedupeKeepLexicographicallySmallest => {
-                    candidates.sort_by(|a, b| {
-                        a.seqid
-                            .cmp(&b.seqid)
-                            .then(a.start.cmp(&b.start))
-                            .then(a.end.cmp(&b.end))
-                            .then(a.gene_name.cmp(&b.gene_name))
-                            .then(a.biotype.cmp(&b.biotype))
-                    });
+                if matches!(opts.duplicate_gene_id_policy, DuplicateGeneIdPolicy::Fail) && strict {
+                    return Err(IngestError(format!("duplicate gene_id: {key}")));
                 }
-            }
-        }
-        if let Some(first) = candidates.into_iter().next() {
-            deduped.insert(key, first);
-        }
-    }
+                Ok(())
+            })?;
+            rows.select_gene_winners(matches!(
+                opts.duplicate_gene_id_policy,
+                DuplicateGeneIdPolicy::DedupeKeepLexicographicallySmallest
+            ))?;
 
-    for (parent, class) in transcript_parents {
-        if let Some(gene) = deduped.get_mut(&parent) {
-            gene.transcript_count += 1;
-            if class == ParentErrorClass::MultipleParents {
-                // valid but tracked as anomaly for QC visibility
-                anomaly
-                    .missing_parents
-                    .push(format!("multiple_parent:{parent}"));
-            }
-        } else {
-            anomaly.missing_parents.push(parent.clone());
-            anomaly.missing_transcript_parents.push(parent.clone());
-            if matches!(opts.strictness, StrictnessMode::Strict) {
-                return Err(IngestError(format!(
-                    "transcript parent {parent} does not reference a known gene"
-                )));
-            }
-        }
-    }
+            rows.for_each_transcript_parent(|parent, multiple, known| {
+                if known {
+                    if multiple {
+                        // valid but tracked as anomaly for QC visibility
+                        anomaly
+                            .missing_parents
+                            .push(format!("multiple_parent:{parent}"));
+                    }
+                } else {
+                    anomaly.missing_parents.push(parent.clone());
+                    anomaly.missing_transcript_parents.push(parent.clone());
+                    if strict {
+                        return Err(IngestError(format!(
+                            "transcript parent {parent} does not reference a known gene"
+                        )));
+                    }
+                }
+                Ok(())
+            })?;
+            rows.count_gene_transcripts()?;
 
-    for tx in &mut transcript_rows_pending {
-        tx.exon_count = transcript_exon_counts
-            .get(&tx.transcript_id)
-            .copied()
-            .unwrap_or(0);
-        tx.total_exon_span = transcript_exon_span
-            .get(&tx.transcript_id)
-            .copied()
-            .unwrap_or(0);
-        tx.cds_present = transcript_has_cds
-            .get(&tx.transcript_id)
-            .copied()
-            .unwrap_or(false);
-        if opts.compute_transcript_spliced_length {
-            tx.spliced_length = Some(tx.total_exon_span);
-        }
-        if opts.compute_transcript_cds_length {
-            tx.cds_span_length = Some(
-                transcript_cds_span
-                    .get(&tx.transcript_id)
-                    .copied()
-                    .unwrap_or(0),
-            );
-        }
-    }
-    let mut retained = Vec::with_capacity(transcript_rows_pending.len());
-    let transcript_ids: HashSet<String> = transcript_rows_pending
-        .iter()
-        .map(|tx| tx.transcript_id.clone())
-        .collect();
-    for parent_tx in child_parent_refs {
-        if !transcript_ids.contains(&parent_tx) {
-            anomaly.orphan_transcripts.push(parent_tx);
-        }
-    }
-    for tx in transcript_rows_pending {
-        if deduped.contains_key(&tx.parent_gene_id) {
-            retained.push(tx);
-        } else {
-            anomaly.orphan_transcripts.push(tx.transcript_id);
-        }
-    }
-    let mut transcript_rows_pending = retained;
-    if !transcript_rows_pending.is_empty() {
-        let mut by_tx: HashMap<String, Vec<TranscriptRecord>> = HashMap::new();
-        for tx in transcript_rows_pending {
-            by_tx.entry(tx.transcript_id.clone()).or_default().push(tx);
-        }
-        let mut merged = Vec::new();
-        let mut keys: Vec<String> = by_tx.keys().cloned().collect();
-        keys.sort();
-        for k in keys {
-            let mut group = by_tx.remove(&k).unwrap_or_default();
-            if group.len() > 1 {
+            rows.for_each_orphan_child_ref(|parent_tx| {
+                anomaly.orphan_transcripts.push(parent_tx);
+                Ok(())
+            })?;
+            rows.for_each_orphan_transcript(|tx_id| {
+                anomaly.orphan_transcripts.push(tx_id);
+                Ok(())
+            })?;
+            rows.for_each_duplicate_transcript(|k| {
                 if matches!(
                     opts.duplicate_transcript_id_policy,
                     DuplicateTranscriptIdPolicy::Reject
-                ) && matches!(opts.strictness, StrictnessMode::Strict)
+                ) && strict
                 {
                     return Err(IngestError(format!("duplicate transcript_id: {k}")));
                 }
-                group.sort_by(|a, b| {
-                    a.seqid
-                        .cmp(&b.seqid)
-                        .then(a.start.cmp(&b.start))
-                        .then(a.end.cmp(&b.end))
-                        .then(a.parent_gene_id.cmp(&b.parent_gene_id))
-                });
                 anomaly.rejections.push(IngestRejection::new(
                     0,
                     "GFF3_DUPLICATE_TRANSCRIPT_ID".to_string(),
-                    k.clone(),
+                    k,
+                ));
+                Ok(())
+            })?;
+            rows.select_transcript_winners(
+                opts.compute_transcript_spliced_length,
+                opts.compute_transcript_cds_length,
+            )?;
+            rows.aggregate_gene_children()
+        })?;
+
+        anomaly.missing_parents =
+            canonical::stable_sort_by_key(anomaly.missing_parents, |x| x.clone());
+        anomaly.missing_transcript_parents =
+            canonical::stable_sort_by_key(anomaly.missing_transcript_parents, |x| x.clone());
+        anomaly.multiple_parent_transcripts =
+            canonical::stable_sort_by_key(anomaly.multiple_parent_transcripts, |x| x.clone());
+        anomaly.unknown_contigs =
+            canonical::stable_sort_by_key(anomaly.unknown_contigs, |x| x.clone());
+        anomaly.overlapping_ids =
+            canonical::stable_sort_by_key(anomaly.overlapping_ids, |x| x.clone());
+        anomaly.duplicate_gene_ids =
+            canonical::stable_sort_by_key(anomaly.duplicate_gene_ids, |x| x.clone());
+        anomaly.overlapping_gene_ids_across_contigs =
+            canonical::stable_sort_by_key(anomaly.overlapping_gene_ids_across_contigs, |x| {
+                x.clone()
+            });
+        anomaly.orphan_transcripts =
+            canonical::stable_sort_by_key(anomaly.orphan_transcripts, |x| x.clone());
+        anomaly.parent_cycles = canonical::stable_sort_by_key(anomaly.parent_cycles, |x| x.clone());
+        anomaly.attribute_fallbacks =
+            canonical::stable_sort_by_key(anomaly.attribute_fallbacks, |x| x.clone());
+        anomaly.unknown_feature_types =
+            canonical::stable_sort_by_key(anomaly.unknown_feature_types, |x| x.clone());
+        anomaly.missing_required_fields =
+            canonical::stable_sort_by_key(anomaly.missing_required_fields, |x| x.clone());
+        anomaly.scientific_ambiguities =
+            canonical::stable_sort_by_key(anomaly.scientific_ambiguities, |x| x.clone());
+        anomaly.rejections = canonical::stable_sort_by_key(anomaly.rejections, |x| {
+            (x.line, x.code.clone(), x.sample.clone())
+        });
+        anomaly.missing_parents.dedup();
+        anomaly.missing_transcript_parents.dedup();
+        anomaly.multiple_parent_transcripts.dedup();
+        anomaly.unknown_contigs.dedup();
+        anomaly.overlapping_ids.dedup();
+        anomaly.duplicate_gene_ids.dedup();
+        anomaly.overlapping_gene_ids_across_contigs.dedup();
+        anomaly.orphan_transcripts.dedup();
+        anomaly.parent_cycles.dedup();
+        anomaly.attribute_fallbacks.dedup();
+        anomaly.unknown_feature_types.dedup();
+        anomaly.missing_required_fields.dedup();
+        anomaly.scientific_ambiguities.dedup();
+        anomaly
+            .rejections
+            .dedup_by(|a, b| a.line == b.line && a.code == b.code && a.sample == b.sample);
+
+        let mut biotype_distribution: BTreeMap<String, u64> = BTreeMap::new();
+        let mut contig_distribution: BTreeMap<String, u64> = BTreeMap::new();
+        let mut contig_class_distribution: BTreeMap<String, u64> = BTreeMap::new();
+        rows.for_each_gene(RowScope::Dataset, |g| {
+            *biotype_distribution.entry(g.biotype.clone()).or_insert(0) += 1;
+            *contig_distribution.entry(g.seqid.clone()).or_insert(0) += 1;
+            *contig_class_distribution
+                .entry(format!("{:?}", classify_contig(&g.seqid)).to_ascii_lowercase())
+                .or_insert(0) += 1;
+            Ok(())
+        })?;
+        let mut seqid_normalization_traces = BTreeMap::new();
+        for (normalized, sources) in &normalized_seqid_sources {
+            let mut source_list: Vec<String> = sources.iter().cloned().collect();
+            source_list.sort();
+            let source = source_list
+                .first()
+                .cloned()
+                .unwrap_or_else(|| normalized.clone());
+            let trace = opts.seqid_policy.normalize_with_trace(&source);
+            seqid_normalization_traces.insert(normalized.clone(), trace);
+            if source_list.len() > 1 {
+                anomaly.scientific_ambiguities.push(format!(
+                    "multiple_source_seqids_for_normalized:{}:{:?}",
+                    normalized, source_list
                 ));
             }
-            if let Some(first) = group.into_iter().next() {
-                merged.push(first);
-            }
-        }
-        transcript_rows_pending = merged;
-    }
-    transcript_rows_pending.sort_by(|a, b| {
-        a.seqid
-            .cmp(&b.seqid)
-            .then(a.start.cmp(&b.start))
-            .then(a.transcript_id.cmp(&b.transcript_id))
-    });
-
-    for tx in &transcript_rows_pending {
-        if let Some(gene) = deduped.get_mut(&tx.parent_gene_id) {
-            gene.exon_count += tx.exon_count;
-            gene.total_exon_span += tx.total_exon_span;
-            gene.cds_present = gene.cds_present || tx.cds_present;
         }
+        anomaly.scientific_ambiguities =
+            canonical::stable_sort_by_key(anomaly.scientific_ambiguities, |x| x.clone());
+        anomaly.scientific_ambiguities.dedup();
+
+        Ok(ExtractResult {
+            gene_count: rows.gene_count()?,
+            transcript_count: rows.transcript_count()?,
+            exon_count: rows.exon_count()?,
+            anomaly,
+            biotype_distribution,
+            contig_distribution,
+            total_features: self.total_features,
+            unknown_contig_features: self.unknown_contig_features,
+            max_contig_name_length: self.max_contig_name_length,
+            cds_feature_count: self.cds_feature_count,
+            contig_class_distribution,
+            seqid_normalization_traces,
+            biotype_source_counts: self.biotype_source_counts,
+            gene_blocks: self.gene_blocks,
+            max_gene_block_records: self.max_gene_block_records,
+        })
     }
+}
 
-    anomaly.missing_parents = canonical::stable_sort_by_key(anomaly.missing_parents, |x| x.clone());
-    anomaly.missing_transcript_parents =
-        canonical::stable_sort_by_key(anomaly.missing_transcript_parents, |x| x.clone());
-    anomaly.multiple_parent_transcripts =
-        canonical::stable_sort_by_key(anomaly.multiple_parent_transcripts, |x| x.clone());
-    anomaly.unknown_contigs = canonical::stable_sort_by_key(anomaly.unknown_contigs, |x| x.clone());
-    anomaly.overlapping_ids = canonical::stable_sort_by_key(anomaly.overlapping_ids, |x| x.clone());
-    anomaly.duplicate_gene_ids =
-        canonical::stable_sort_by_key(anomaly.duplicate_gene_ids, |x| x.clone());
-    anomaly.overlapping_gene_ids_across_contigs =
-        canonical::stable_sort_by_key(anomaly.overlapping_gene_ids_across_contigs, |x| x.clone());
-    anomaly.orphan_transcripts =
-        canonical::stable_sort_by_key(anomaly.orphan_transcripts, |x| x.clone());
-    anomaly.parent_cycles = canonical::stable_sort_by_key(anomaly.parent_cycles, |x| x.clone());
-    anomaly.attribute_fallbacks =
-        canonical::stable_sort_by_key(anomaly.attribute_fallbacks, |x| x.clone());
-    anomaly.unknown_feature_types =
-        canonical::stable_sort_by_key(anomaly.unknown_feature_types, |x| x.clone());
-    anomaly.missing_required_fields =
-        canonical::stable_sort_by_key(anomaly.missing_required_fields, |x| x.clone());
-    anomaly.scientific_ambiguities =
-        canonical::stable_sort_by_key(anomaly.scientific_ambiguities, |x| x.clone());
-    anomaly.rejections = canonical::stable_sort_by_key(anomaly.rejections, |x| {
-        (x.line, x.code.clone(), x.sample.clone())
-    });
-    anomaly.missing_parents.dedup();
-    anomaly.missing_transcript_parents.dedup();
-    anomaly.multiple_parent_transcripts.dedup();
-    anomaly.unknown_contigs.dedup();
-    anomaly.overlapping_ids.dedup();
-    anomaly.duplicate_gene_ids.dedup();
-    anomaly.overlapping_gene_ids_across_contigs.dedup();
-    anomaly.orphan_transcripts.dedup();
-    anomaly.parent_cycles.dedup();
-    anomaly.attribute_fallbacks.dedup();
-    anomaly.unknown_feature_types.dedup();
-    anomaly.missing_required_fields.dedup();
-    anomaly.scientific_ambiguities.dedup();
-    anomaly
-        .rejections
-        .dedup_by(|a, b| a.line == b.line && a.code == b.code && a.sample == b.sample);
-
-    let mut gene_rows: Vec<GeneRecord> = deduped.into_values().collect();
-    gene_rows.sort_by(|a, b| {
-        a.seqid
-            .cmp(&b.seqid)
-            .then(a.start.cmp(&b.start))
-            .then(a.end.cmp(&b.end))
-            .then(a.gene_id.cmp(&b.gene_id))
-    });
-
-    let mut biotype_distribution: BTreeMap<String, u64> = BTreeMap::new();
-    let mut contig_distribution: BTreeMap<String, u64> = BTreeMap::new();
-    let mut contig_class_distribution: BTreeMap<String, u64> = BTreeMap::new();
-    for g in &gene_rows {
-        *biotype_distribution.entry(g.biotype.clone()).or_insert(0) += 1;
-        *contig_distribution.entry(g.seqid.clone()).or_insert(0) += 1;
-        *contig_class_distribution
-            .entry(format!("{:?}", classify_contig(&g.seqid)).to_ascii_lowercase())
-            .or_insert(0) += 1;
+/// Collects cross-references from the multi-valued `Dbxref`, `Ontology_term` and `Alias` attributes.
+fn gene_xrefs_from_attrs(attrs: &BTreeMap<String, String>) -> BTreeSet<(String, String)> {
+    let mut out = BTreeSet::new();
+    for key in ["Dbxref", "Ontology_term"] {
+        for entry in attrs.get(key).into_iter().flat_map(|v| v.split(',')) {
+            if let Some(pair) = split_xref(entry) {
+                out.insert(pair);
+            }
+        }
     }
-    let mut seqid_normalization_traces = BTreeMap::new();
-    for (normalized, sources) in &normalized_seqid_sources {
-        let mut source_list: Vec<String> = sources.iter().cloned().collect();
-        source_list.sort();
-        let source = source_list
-            .first()
-            .cloned()
-            .unwrap_or_else(|| normalized.clone());
-        let trace = opts.seqid_policy.normalize_with_trace(&source);
-        seqid_normalization_traces.insert(normalized.clone(), trace);
-        if source_list.len() > 1 {
-            anomaly.scientific_ambiguities.push(format!(
-                "multiple_source_seqids_for_normalized:{}:{:?}",
-                normalized, source_list
-            ));
+    for alias in attrs.get("Alias").into_iter().flat_map(|v| v.split(',')) {
+        let alias = alias.trim();
+        if !alias.is_empty() {
+            out.insert((XREF_ALIAS_NAMESPACE.to_string(), alias.to_string()));
         }
     }
-    anomaly.scientific_ambiguities =
-        canonical::stable_sort_by_key(anomaly.scientific_ambiguities, |x| x.clone());
-    anomaly.scientific_ambiguities.dedup();
-
-    Ok(ExtractResult {
-        gene_rows,
-        transcript_rows: transcript_rows_pending,
-        exon_rows,
-        anomaly,
-        biotype_distribution,
-        contig_distribution,
-        total_features,
-        unknown_contig_features,
-        max_contig_name_length,
-        cds_feature_count,
-        contig_class_distribution,
-        seqid_normalization_traces,
-        biotype_source_counts,
-    })
+    out
 }
 
 fn resolve_biotype_source_key(attrs: &BTreeMap<String, String>, opts: &IngestOptions) -> String {
@@ -742,26 +778,7 @@ fn used_gene_id_fallback(
     }
 }
 
-fn detect_parent_cycles(records: &[Gff3Record]) -> Vec<String> {
-    let mut edges: HashMap<String, Vec<String>> = HashMap::new();
-    for rec in records {
-        let Some(id) = rec.attrs.get("ID").cloned() else {
-            continue;
-        };
-        let parents = rec
-            .attrs
-            .get("Parent")
-            .map(|x| {
-                x.split(',')
-                    .map(str::trim)
-                    .filter(|p| !p.is_empty())
-                    .map(ToString::to_string)
-                    .collect::<Vec<_>>()
-            })
-            .unwrap_or_default();
-        edges.insert(id, parents);
-    }
-
+fn detect_parent_cycles(edges: &HashMap<String, Vec<String>>) -> Vec<String> {
     #[derive(Clone, Copy, PartialEq, Eq)]
     enum Color {
         White,
@@ -798,7 +815,7 @@ fn detect_parent_cycles(records: &[Gff3Record]) -> Vec<String> {
     let keys: Vec<String> = edges.keys().cloned().collect();
     for k in keys {
         if color.get(&k) == Some(&Color::White) {
-            dfs(&k, &edges, &mut color, &mut Vec::new(), &mut cycles);
+            dfs(&k, edges, &mut color, &mut Vec::new(), &mut cycles);
         }
     }
     cycles
@@ -1,5 +1,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use super::compression::open_decoded;
 use super::IngestError;
 use serde::Serialize;
 use std::collections::BTreeMap;
@@ -50,8 +51,7 @@ pub fn write_fai_from_fasta(fasta_path: &Path, fai_path: &Path) -> Result<(), In
 }
 
 pub fn read_fasta_contig_lengths(path: &Path) -> Result<BTreeMap<String, u64>, IngestError> {
-    let file = fs::File::open(path).map_err(|e| IngestError(e.to_string()))?;
-    let reader = BufReader::new(file);
+    let reader = open_decoded(path)?;
     let mut out = BTreeMap::new();
     let mut current: Option<String> = None;
     for line in reader.lines() {
@@ -93,6 +93,9 @@ pub struct ContigStats {
     pub length: u64,
     pub gc_fraction: Option<f64>,
     pub n_fraction: Option<f64>,
+    /// FASTA header text after the contig name, when the FASTA was scanned.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
 }
 
 pub fn read_fasta_contig_stats(
@@ -100,23 +103,27 @@ pub fn read_fasta_contig_stats(
     compute_fractions: bool,
     max_total_bases: u64,
 ) -> Result<BTreeMap<String, ContigStats>, IngestError> {
-    let file = fs::File::open(path).map_err(|e| IngestError(e.to_string()))?;
-    let reader = BufReader::new(file);
+    let reader = open_decoded(path)?;
     let mut out: BTreeMap<String, (u64, u64, u64)> = BTreeMap::new(); // len, gc, n
+    let mut descriptions: BTreeMap<String, String> = BTreeMap::new();
     let mut total_bases: u64 = 0;
     let mut current: Option<String> = None;
     for line in reader.lines() {
         let line = line.map_err(|e| IngestError(e.to_string()))?;
         if let Some(rest) = line.strip_prefix('>') {
-            let name = validate_fasta_contig_name(
-                rest.split_whitespace().next().unwrap_or("").trim(),
-                out.len() + 1,
-            )?;
+            let rest = rest.trim();
+            let (raw_name, description) = rest
+                .split_once(char::is_whitespace)
+                .map_or((rest, ""), |(n, d)| (n, d.trim()));
+            let name = validate_fasta_contig_name(raw_name, out.len() + 1)?;
             if out.contains_key(&name) {
                 return Err(IngestError(format!(
                     "duplicate FASTA contig header detected: {name}"
                 )));
             }
+            if !description.is_empty() {
+                descriptions.insert(name.clone(), description.to_string());
+            }
             current = Some(name.clone());
             out.insert(name, (0, 0, 0));
             continue;
@@ -158,12 +165,14 @@ pub fn read_fasta_contig_stats(
             } else {
                 (None, None)
             };
+            let description = descriptions.remove(&k);
             (
                 k,
                 ContigStats {
                     length: len,
                     gc_fraction,
                     n_fraction,
+                    description,
                 },
             )
         })
@@ -1,9 +1,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use super::compression::open_decoded;
 use super::IngestError;
 use std::collections::{BTreeMap, BTreeSet};
-use std::fs;
-use std::io::{BufRead, BufReader};
+use std::io::BufRead;
 use std::path::Path;
 use unicode_normalization::UnicodeNormalization;
 
@@ -33,104 +33,184 @@ pub struct SequenceRegion {
     pub end: u64,
 }
 
+#[cfg(test)]
 pub fn parse_gff3_records(path: &Path) -> Result<Vec<Gff3Record>, IngestError> {
-    let file = fs::File::open(path).map_err(|e| IngestError(e.to_string()))?;
-    let reader = BufReader::new(file);
-    let mut out = Vec::new();
+    Gff3RecordReader::open(path)?.collect()
+}
 
-    for (line_idx, line) in reader.lines().enumerate() {
-        let line = line.map_err(|e| IngestError(e.to_string()))?;
-        if line.is_empty() || line.starts_with('#') {
-            continue;
-        }
-        if line.len() > MAX_GFF3_LINE_BYTES {
-            return Err(IngestError(format!(
-                "gff3 line exceeds max byte length {MAX_GFF3_LINE_BYTES}"
-            )));
-        }
+/// Streaming GFF3 reader that yields one validated feature row at a time.
+pub struct Gff3RecordReader<R: BufRead> {
+    lines: std::io::Lines<R>,
+    line_idx: usize,
+}
 
-        let cols: Vec<&str> = line.split('\t').collect();
-        if cols.len() != 9 {
-            return Err(IngestError(format!(
-                "invalid GFF3 row (expected 9 columns): {line}"
-            )));
-        }
+impl Gff3RecordReader<Box<dyn BufRead>> {
+    pub fn open(path: &Path) -> Result<Self, IngestError> {
+        Ok(Self::new(open_decoded(path)?))
+    }
+}
 
-        let start: u64 = cols[3].parse().map_err(|_| {
-            IngestError(format!(
-                "GFF3_INVALID_START_COORDINATE line={} value={} sample={line}",
-                line_idx + 1,
-                cols[3]
-            ))
-        })?;
-        let end: u64 = cols[4].parse().map_err(|_| {
-            IngestError(format!(
-                "GFF3_INVALID_END_COORDINATE line={} value={} sample={line}",
-                line_idx + 1,
-                cols[4]
-            ))
-        })?;
-        if start == 0 || end < start {
-            return Err(IngestError(format!(
-                "GFF3_INVALID_COORDINATE_SPAN line={} start={} end={} sample={line}",
-                line_idx + 1,
-                start,
-                end
-            )));
+impl<R: BufRead> Gff3RecordReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            lines: reader.lines(),
+            line_idx: 0,
         }
+    }
+}
 
-        let seqid = cols[0].trim().to_string();
-        if seqid.is_empty() {
-            return Err(IngestError(format!(
-                "GFF3_MISSING_REQUIRED_FIELD line={} field=seqid sample={line}",
-                line_idx + 1
-            )));
-        }
-        let feature_type = cols[2].trim().to_string();
-        if feature_type.is_empty() {
-            return Err(IngestError(format!(
-                "GFF3_MISSING_REQUIRED_FIELD line={} field=feature_type sample={line}",
-                line_idx + 1
-            )));
+impl<R: BufRead> Iterator for Gff3RecordReader<R> {
+    type Item = Result<Gff3Record, IngestError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(e) => return Some(Err(IngestError(e.to_string()))),
+            };
+            self.line_idx += 1;
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            return Some(parse_gff3_line(line, self.line_idx));
         }
-        let strand = cols[6].trim().to_string();
-        if !matches!(strand.as_str(), "+" | "-" | ".") {
-            return Err(IngestError(format!(
-                "GFF3_INVALID_STRAND line={} strand={} sample={line}",
-                line_idx + 1,
-                strand
-            )));
+    }
+}
+
+/// Groups a record stream into gene blocks: a top-level feature (no `Parent`)
+/// followed by every child row up to the next top-level feature.
+///
+/// Blocks are only the unit in which rows are flushed to the row spool; they
+/// do not assume a gene's children follow it. A child listed away from its
+/// gene, or before any gene, lands in whichever block is open, and its
+/// `Parent` is resolved against the feature index of the whole file.
+pub struct Gff3GeneBlocks<I> {
+    records: I,
+    pending: Option<Gff3Record>,
+}
+
+impl<I> Gff3GeneBlocks<I>
+where
+    I: Iterator<Item = Result<Gff3Record, IngestError>>,
+{
+    pub fn new(records: I) -> Self {
+        Self {
+            records,
+            pending: None,
         }
-        let phase = cols[7].trim().to_string();
-        if feature_type == "CDS" && !matches!(phase.as_str(), "0" | "1" | "2" | ".") {
-            return Err(IngestError(format!(
-                "GFF3_INVALID_PHASE line={} phase={} sample={line}",
-                line_idx + 1,
-                phase
-            )));
+    }
+}
+
+impl<I> Iterator for Gff3GeneBlocks<I>
+where
+    I: Iterator<Item = Result<Gff3Record, IngestError>>,
+{
+    type Item = Result<Vec<Gff3Record>, IngestError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = match self.pending.take() {
+            Some(rec) => rec,
+            None => match self.records.next()? {
+                Ok(rec) => rec,
+                Err(err) => return Some(Err(err)),
+            },
+        };
+        let mut block = vec![first];
+        for next in self.records.by_ref() {
+            let rec = match next {
+                Ok(rec) => rec,
+                Err(err) => return Some(Err(err)),
+            };
+            if rec.attrs.contains_key("Parent") {
+                block.push(rec);
+            } else {
+                self.pending = Some(rec);
+                break;
+            }
         }
+        Some(Ok(block))
+    }
+}
 
-        let (attrs, duplicate_attr_keys) = parse_attributes(cols[8], line_idx + 1)?;
-        out.push(Gff3Record {
-            line: line_idx + 1,
-            seqid,
-            feature_type,
-            strand,
-            phase,
-            start,
-            end,
-            attrs,
-            duplicate_attr_keys,
-            raw_line: line,
-        });
+fn parse_gff3_line(line: String, line_no: usize) -> Result<Gff3Record, IngestError> {
+    if line.len() > MAX_GFF3_LINE_BYTES {
+        return Err(IngestError(format!(
+            "gff3 line exceeds max byte length {MAX_GFF3_LINE_BYTES}"
+        )));
+    }
+
+    let cols: Vec<&str> = line.split('\t').collect();
+    if cols.len() != 9 {
+        return Err(IngestError(format!(
+            "invalid GFF3 row (expected 9 columns): {line}"
+        )));
+    }
+
+    let start: u64 = cols[3].parse().map_err(|_| {
+        IngestError(format!(
+            "GFF3_INVALID_START_COORDINATE line={} value={} sample={line}",
+            line_no, cols[3]
+        ))
+    })?;
+    let end: u64 = cols[4].parse().map_err(|_| {
+        IngestError(format!(
+            "GFF3_INVALID_END_COORDINATE line={} value={} sample={line}",
+            line_no, cols[4]
+        ))
+    })?;
+    if start == 0 || end < start {
+        return Err(IngestError(format!(
+            "GFF3_INVALID_COORDINATE_SPAN line={} start={} end={} sample={line}",
+            line_no, start, end
+        )));
     }
 
-    Ok(out)
+    let seqid = cols[0].trim().to_string();
+    if seqid.is_empty() {
+        return Err(IngestError(format!(
+            "GFF3_MISSING_REQUIRED_FIELD line={} field=seqid sample={line}",
+            line_no
+        )));
+    }
+    let feature_type = cols[2].trim().to_string();
+    if feature_type.is_empty() {
+        return Err(IngestError(format!(
+            "GFF3_MISSING_REQUIRED_FIELD line={} field=feature_type sample={line}",
+            line_no
+        )));
+    }
+    let strand = cols[6].trim().to_string();
+    if !matches!(strand.as_str(), "+" | "-" | ".") {
+        return Err(IngestError(format!(
+            "GFF3_INVALID_STRAND line={} strand={} sample={line}",
+            line_no, strand
+        )));
+    }
+    let phase = cols[7].trim().to_string();
+    if feature_type == "CDS" && !matches!(phase.as_str(), "0" | "1" | "2" | ".") {
+        return Err(IngestError(format!(
+            "GFF3_INVALID_PHASE line={} phase={} sample={line}",
+            line_no, phase
+        )));
+    }
+
+    let (attrs, duplicate_attr_keys) = parse_attributes(cols[8], line_no)?;
+    Ok(Gff3Record {
+        line: line_no,
+        seqid,
+        feature_type,
+        strand,
+        phase,
+        start,
+        end,
+        attrs,
+        duplicate_attr_keys,
+        raw_line: line,
+    })
 }
 
 pub fn parse_sequence_regions(path: &Path) -> Result<Vec<SequenceRegion>, IngestError> {
-    let file = fs::File::open(path).map_err(|e| IngestError(e.to_string()))?;
-    let reader = BufReader::new(file);
+    let reader = open_decoded(path)?;
     let mut regions = Vec::new();
 
     for (line_idx, line) in reader.lines().enumerate() {
@@ -298,7 +378,10 @@ fn parse_attributes(
 
 #[cfg(test)]
 mod tests {
-    use super::{parse_gff3_records, parse_sequence_regions, validate_sequence_region_conflicts};
+    use super::{
+        parse_gff3_records, parse_sequence_regions, validate_sequence_region_conflicts,
+        Gff3GeneBlocks, Gff3RecordReader,
+    };
     use proptest::prelude::*;
     use std::fs;
     use tempfile::tempdir;
@@ -362,6 +445,51 @@ mod tests {
         assert_eq!(rows.len(), 25_000);
     }
 
+    #[test]
+    fn gene_blocks_group_children_under_top_level_features() {
+        let raw = "##gff-version 3\n\
+chr1\tsrc\tgene\t1\t100\t.\t+\t.\tID=g1\n\
+chr1\tsrc\tmRNA\t1\t100\t.\t+\t.\tID=t1;Parent=g1\n\
+chr1\tsrc\texon\t1\t50\t.\t+\t.\tParent=t1\n\
+###\n\
+chr1\tsrc\tgene\t200\t300\t.\t-\t.\tID=g2\n\
+chr1\tsrc\tmRNA\t200\t300\t.\t-\t.\tID=t2;Parent=g2\n";
+        let blocks = Gff3GeneBlocks::new(Gff3RecordReader::new(raw.as_bytes()))
+            .collect::<Result<Vec<_>, _>>()
+            .expect("blocks");
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].len(), 3);
+        assert_eq!(blocks[1].len(), 2);
+        assert_eq!(blocks[1][0].attrs.get("ID").map(String::as_str), Some("g2"));
+        assert_eq!(blocks[0][2].line, 4);
+    }
+
+    #[test]
+    fn gene_blocks_keep_out_of_order_children_in_the_open_block() {
+        let raw = "##gff-version 3\n\
+chr1\tsrc\texon\t1\t50\t.\t+\t.\tParent=t1\n\
+chr1\tsrc\tgene\t200\t300\t.\t-\t.\tID=g2\n\
+chr1\tsrc\tmRNA\t1\t100\t.\t+\t.\tID=t1;Parent=g1\n\
+chr1\tsrc\tgene\t1\t100\t.\t+\t.\tID=g1\n";
+        let blocks = Gff3GeneBlocks::new(Gff3RecordReader::new(raw.as_bytes()))
+            .collect::<Result<Vec<_>, _>>()
+            .expect("blocks");
+        let lines = blocks
+            .iter()
+            .map(|block| block.iter().map(|rec| rec.line).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(lines, vec![vec![2], vec![3, 4], vec![5]]);
+    }
+
+    #[test]
+    fn record_reader_surfaces_errors_with_original_line_numbers() {
+        let raw = "# header\nchr1\tsrc\tgene\t1\t10\t.\t+\t.\tID=g1\nchr1\tsrc\tgene\tx\t10\t.\t+\t.\tID=g2\n";
+        let mut reader = Gff3RecordReader::new(raw.as_bytes());
+        assert_eq!(reader.next().expect("first").expect("ok").line, 2);
+        let err = reader.next().expect("second").expect_err("bad start");
+        assert!(err.0.contains("GFF3_INVALID_START_COORDINATE line=3"));
+    }
+
     #[test]
     fn parser_rejects_attribute_token_explosion() {
         let tmp = tempdir().expect("tempdir");
@@ -1,10 +1,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::fs;
+use std::io::Read;
 use std::path::Path;
 
-use crate::domain::sha256_hex;
+use sha2::{Digest, Sha256};
 
+use super::compression::hash_stored_and_decompressed;
 use super::IngestError;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -12,11 +14,25 @@ pub struct InputHashes {
     pub gff3_sha256: String,
     pub fasta_sha256: String,
     pub fai_sha256: String,
+    pub gff3_decompressed_sha256: String,
+    pub fasta_decompressed_sha256: String,
 }
 
+/// Hashes a file in fixed-size chunks so large artifacts are never read whole.
 pub fn hash_file(path: &Path) -> Result<String, IngestError> {
-    let bytes = fs::read(path).map_err(|e| IngestError(e.to_string()))?;
-    Ok(sha256_hex(&bytes))
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0_u8; 64 * 1024];
+    let mut file = fs::File::open(path).map_err(|e| IngestError(e.to_string()))?;
+    loop {
+        let n = file
+            .read(&mut buf)
+            .map_err(|e| IngestError(e.to_string()))?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(hex::encode(hasher.finalize()))
 }
 
 pub fn compute_input_hashes(
@@ -24,9 +40,13 @@ pub fn compute_input_hashes(
     fasta: &Path,
     fai: &Path,
 ) -> Result<InputHashes, IngestError> {
+    let gff3_digest = hash_stored_and_decompressed(gff3)?;
+    let fasta_digest = hash_stored_and_decompressed(fasta)?;
     Ok(InputHashes {
-        gff3_sha256: hash_file(gff3)?,
-        fasta_sha256: hash_file(fasta)?,
+        gff3_sha256: gff3_digest.stored_sha256,
+        fasta_sha256: fasta_digest.stored_sha256,
         fai_sha256: hash_file(fai)?,
+        gff3_decompressed_sha256: gff3_digest.decompressed_sha256,
+        fasta_decompressed_sha256: fasta_digest.decompressed_sha256,
     })
 }
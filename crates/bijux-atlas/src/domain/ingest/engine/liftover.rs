@@ -0,0 +1,41 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::compression::open_decoded;
+use super::IngestError;
+use crate::domain::canonical;
+use crate::domain::dataset::{Assembly, DatasetId};
+use crate::domain::query::{parse_chain_file, LiftoverChainSet, SeqidNormalizationPolicy};
+use std::io::Read as _;
+use std::path::{Path, PathBuf};
+
+/// UCSC chain file lifting the ingested assembly onto `target_assembly`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiftoverChainInput {
+    pub path: PathBuf,
+    pub target_assembly: Assembly,
+}
+
+/// Parses the chain file, plain or compressed, and writes it as a
+/// [`LiftoverChainSet`]. Source seqids go through the ingest seqid policy so
+/// they match the dataset's gene coordinates.
+pub fn build_and_write_liftover_chains(
+    dataset: &DatasetId,
+    seqid_policy: &SeqidNormalizationPolicy,
+    input: &LiftoverChainInput,
+    output_path: &Path,
+) -> Result<(), IngestError> {
+    let mut text = String::new();
+    open_decoded(&input.path)?
+        .read_to_string(&mut text)
+        .map_err(|e| IngestError(format!("liftover chain read failed: {e}")))?;
+    let chains = parse_chain_file(&text, |raw| seqid_policy.normalize(raw))
+        .map_err(|e| IngestError(format!("liftover chain invalid: {e}")))?;
+    if chains.is_empty() {
+        return Err(IngestError("liftover chain file has no chains".to_string()));
+    }
+    let set = LiftoverChainSet::new(dataset.clone(), input.target_assembly.clone(), chains);
+    set.validate()
+        .map_err(|e| IngestError(format!("liftover chain invalid: {e}")))?;
+    let bytes = canonical::stable_json_bytes(&set).map_err(|e| IngestError(e.to_string()))?;
+    std::fs::write(output_path, bytes).map_err(|e| IngestError(e.to_string()))
+}
@@ -1,6 +1,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use super::compression::gzi_path_for;
 use super::extract::ExtractResult;
+use super::hashing::{hash_file, InputHashes};
+use super::resources::IngestResourceUsage;
+use super::spool::{RowScope, RowSpool};
 use super::IngestError;
 use crate::domain::canonical;
 use crate::domain::dataset::{
@@ -9,7 +13,7 @@ use crate::domain::dataset::{
 };
 use crate::domain::sha256_hex;
 use serde_json::json;
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -25,16 +29,19 @@ pub struct BuildManifestArgs<'a> {
     pub gff3_path: &'a Path,
     pub fasta_path: &'a Path,
     pub fai_path: &'a Path,
+    pub input_hashes: &'a InputHashes,
     pub sqlite_path: &'a Path,
     pub manifest_path: &'a Path,
     pub anomaly_path: &'a Path,
     pub extract: &'a ExtractResult,
+    pub rows: &'a RowSpool,
     pub contig_aliases: &'a BTreeMap<String, String>,
     pub sharding_plan: ShardingPlan,
     pub canonical_model_schema_version: u64,
     pub canonical_query_semantic_sha256: &'a str,
     pub canonical_lineage_sha256: &'a str,
     pub canonical_feature_counts: &'a BTreeMap<String, u64>,
+    pub resource_usage: &'a IngestResourceUsage,
 }
 
 pub fn build_and_write_manifest_and_reports(
@@ -46,41 +53,46 @@ pub fn build_and_write_manifest_and_reports(
         gff3_path,
         fasta_path,
         fai_path,
+        input_hashes,
         sqlite_path,
         manifest_path,
         anomaly_path,
         extract,
+        rows,
         contig_aliases,
         sharding_plan,
         canonical_model_schema_version,
         canonical_query_semantic_sha256,
         canonical_lineage_sha256,
         canonical_feature_counts,
+        resource_usage,
     } = args;
-    let mut total_transcripts = 0_u64;
-    let mut contigs = BTreeSet::new();
-    for g in &extract.gene_rows {
-        total_transcripts += g.transcript_count;
-        contigs.insert(g.seqid.clone());
-    }
+    let total_transcripts = rows.gene_transcript_total()?;
 
+    let fasta_gzi = gzi_path_for(fasta_path);
+    let fasta_gzi_sha256 = if fasta_gzi.exists() {
+        hash_file(&fasta_gzi)?
+    } else {
+        String::new()
+    };
     let mut manifest = ArtifactManifest::new(
         "1".to_string(),
         "1".to_string(),
         dataset.clone(),
         ArtifactChecksums::new(
-            sha256_hex(&fs::read(gff3_path).map_err(|e| IngestError(e.to_string()))?),
-            sha256_hex(&fs::read(fasta_path).map_err(|e| IngestError(e.to_string()))?),
-            sha256_hex(&fs::read(fai_path).map_err(|e| IngestError(e.to_string()))?),
-            sha256_hex(&fs::read(sqlite_path).map_err(|e| IngestError(e.to_string()))?),
-        ),
+            hash_file(gff3_path)?,
+            hash_file(fasta_path)?,
+            hash_file(fai_path)?,
+            hash_file(sqlite_path)?,
+        )
+        .with_fasta_gzi(fasta_gzi_sha256),
         ManifestStats::new(
-            extract.gene_rows.len() as u64,
+            extract.gene_count,
             total_transcripts,
-            contigs.len() as u64,
+            extract.contig_distribution.len() as u64,
         ),
     );
-    manifest.dataset_signature_sha256 = dataset_signature_merkle(extract)?;
+    manifest.dataset_signature_sha256 = dataset_signature_merkle(rows)?;
     let policy_hash = sha256_hex(
         &fs::read(workspace_file(
             "configs/sources/governance/policy/policy.json",
@@ -92,6 +104,10 @@ pub fn build_and_write_manifest_and_reports(
         manifest.checksums.fasta_sha256.clone(),
         manifest.checksums.fai_sha256.clone(),
         policy_hash.clone(),
+    )
+    .with_decompressed(
+        input_hashes.gff3_decompressed_sha256.clone(),
+        input_hashes.fasta_decompressed_sha256.clone(),
     );
     manifest.source_gff3_filename = gff3_path
         .file_name()
@@ -177,6 +193,7 @@ pub fn build_and_write_manifest_and_reports(
             "lineage_sensitive_sha256": canonical_lineage_sha256,
             "feature_counts": canonical_feature_counts
         })),
+        resource_usage,
         false,
     )?;
     let qc_bytes =
@@ -338,13 +355,10 @@ pub fn write_qc_and_anomaly_reports_only(
     dataset: &DatasetId,
     anomaly_path: &Path,
     extract: &ExtractResult,
+    rows: &RowSpool,
+    resource_usage: &IngestResourceUsage,
 ) -> Result<PathBuf, IngestError> {
-    let mut total_transcripts = 0_u64;
-    let mut contigs = BTreeSet::new();
-    for g in &extract.gene_rows {
-        total_transcripts += g.transcript_count;
-        contigs.insert(g.seqid.clone());
-    }
+    let total_transcripts = rows.gene_transcript_total()?;
 
     let anomaly_bytes =
         canonical::stable_json_bytes(&extract.anomaly).map_err(|e| IngestError(e.to_string()))?;
@@ -354,8 +368,9 @@ pub fn write_qc_and_anomaly_reports_only(
         dataset,
         extract,
         total_transcripts,
-        Some(dataset_signature_merkle(extract)?),
+        Some(dataset_signature_merkle(rows)?),
         None,
+        resource_usage,
         true,
     )?;
     let qc_bytes =
@@ -377,6 +392,7 @@ fn build_qc_report_json(
     total_transcripts: u64,
     manifest_signature: Option<String>,
     canonical_summary: Option<&serde_json::Value>,
+    resource_usage: &IngestResourceUsage,
     report_only: bool,
 ) -> Result<serde_json::Value, IngestError> {
     let class_counts = extract.anomaly.anomaly_class_counts();
@@ -419,9 +435,9 @@ fn build_qc_report_json(
         "report_only": report_only,
         "manifest_signature_sha256": manifest_signature.unwrap_or_default(),
         "counts": {
-            "genes": extract.gene_rows.len(),
+            "genes": extract.gene_count,
             "transcripts": total_transcripts,
-            "exons": extract.exon_rows.len(),
+            "exons": extract.exon_count,
             "cds": extract.cds_feature_count
         },
         "orphan_counts": {
@@ -439,6 +455,13 @@ fn build_qc_report_json(
             "max_contig_name_length": extract.max_contig_name_length,
             "total_features": extract.total_features
         },
+        "streaming": {
+            "gene_blocks": extract.gene_blocks,
+            "max_gene_block_records": extract.max_gene_block_records
+        },
+        "resources": {
+            "peak_rss_bytes": resource_usage.peak_rss_bytes
+        },
         "biotype_distribution_top_n": top_biotypes,
         "biotype_distribution": extract.biotype_distribution,
         "anomalies": {
@@ -467,52 +490,83 @@ fn build_qc_report_json(
     }))
 }
 
-fn dataset_signature_merkle(extract: &ExtractResult) -> Result<String, IngestError> {
-    let gene_hashes: Vec<String> = extract
-        .gene_rows
-        .iter()
-        .map(|row| canonical::stable_json_bytes(row).map(|b| sha256_hex(&b)))
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| IngestError(e.to_string()))?;
-    let tx_hashes: Vec<String> = extract
-        .transcript_rows
-        .iter()
-        .map(|row| canonical::stable_json_bytes(row).map(|b| sha256_hex(&b)))
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| IngestError(e.to_string()))?;
+fn dataset_signature_merkle(rows: &RowSpool) -> Result<String, IngestError> {
+    let mut genes = MerkleAccumulator::default();
+    rows.for_each_gene(RowScope::Dataset, |row| {
+        let bytes = canonical::stable_json_bytes(&row).map_err(|e| IngestError(e.to_string()))?;
+        genes.push(sha256_hex(&bytes));
+        Ok(())
+    })?;
+    let mut transcripts = MerkleAccumulator::default();
+    rows.for_each_transcript(RowScope::Dataset, |row| {
+        let bytes = canonical::stable_json_bytes(&row).map_err(|e| IngestError(e.to_string()))?;
+        transcripts.push(sha256_hex(&bytes));
+        Ok(())
+    })?;
     let root_payload = json!({
-        "gene_table_hash": merkle_root(&gene_hashes),
-        "transcript_table_hash": merkle_root(&tx_hashes),
-        "gene_count": extract.gene_rows.len(),
-        "transcript_count": extract.transcript_rows.len(),
+        "gene_count": genes.leaves,
+        "transcript_count": transcripts.leaves,
+        "gene_table_hash": genes.root(),
+        "transcript_table_hash": transcripts.root(),
     });
     let bytes =
         canonical::stable_json_bytes(&root_payload).map_err(|e| IngestError(e.to_string()))?;
     Ok(sha256_hex(&bytes))
 }
 
-fn merkle_root(leaves: &[String]) -> String {
-    if leaves.is_empty() {
-        return sha256_hex(b"");
+/// Merkle root over leaves pushed one at a time, keeping one pending node
+/// per level. Pairs hash as `sha256(left || right)`; an unpaired last node
+/// of a level is paired with itself.
+#[derive(Default)]
+struct MerkleAccumulator {
+    pending: Vec<Option<String>>,
+    leaves: u64,
+}
+
+impl MerkleAccumulator {
+    fn push(&mut self, leaf: String) {
+        self.leaves += 1;
+        let mut node = leaf;
+        let mut level = 0;
+        loop {
+            if level == self.pending.len() {
+                self.pending.push(None);
+            }
+            match self.pending[level].take() {
+                Some(left) => {
+                    node = sha256_hex(format!("{left}{node}").as_bytes());
+                    level += 1;
+                }
+                None => {
+                    self.pending[level] = Some(node);
+                    return;
+                }
+            }
+        }
     }
-    let mut level = leaves.to_vec();
-    while level.len() > 1 {
-        let mut next = Vec::with_capacity(level.len().div_ceil(2));
-        let mut i = 0usize;
-        while i < level.len() {
-            let left = &level[i];
-            let right = if i + 1 < level.len() {
-                &level[i + 1]
-            } else {
-                left
+
+    fn root(mut self) -> String {
+        if self.leaves == 0 {
+            return sha256_hex(b"");
+        }
+        let mut width = self.leaves;
+        let mut carry: Option<String> = None;
+        let mut level = 0;
+        loop {
+            let pending = self.pending.get_mut(level).and_then(Option::take);
+            let node = match (pending, carry.take()) {
+                (Some(left), Some(right)) => Some(sha256_hex(format!("{left}{right}").as_bytes())),
+                (Some(last), None) | (None, Some(last)) => {
+                    if width == 1 {
+                        return last;
+                    }
+                    Some(sha256_hex(format!("{last}{last}").as_bytes()))
+                }
+                (None, None) => None,
             };
-            let mut joined = String::with_capacity(left.len() + right.len());
-            joined.push_str(left);
-            joined.push_str(right);
-            next.push(sha256_hex(joined.as_bytes()));
-            i += 2;
+            carry = node;
+            width = width.div_ceil(2);
+            level += 1;
         }
-        level = next;
     }
-    level[0].clone()
 }
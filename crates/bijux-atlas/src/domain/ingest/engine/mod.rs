@@ -3,6 +3,8 @@
 #![forbid(unsafe_code)]
 
 mod canonical_model;
+mod compression;
+mod contig_aliases;
 mod decode;
 mod diff_index;
 mod extract;
@@ -10,9 +12,12 @@ mod fai;
 mod gff3;
 mod hashing;
 mod job;
+mod liftover;
 mod logging;
 mod manifest;
 mod normalized;
+mod resources;
+mod spool;
 mod sqlite;
 mod write;
 
@@ -33,10 +38,17 @@ use std::path::{Path, PathBuf};
 
 pub const CRATE_NAME: &str = "bijux-atlas";
 
+pub use compression::{
+    build_gzi_index, detect_compression, encode_gzi_index, gzi_path_for, open_decoded,
+    read_decoded_window, InputCompression,
+};
 pub use hashing::{compute_input_hashes, hash_file, InputHashes};
 pub use job::{IngestInputs, IngestJob};
+pub use liftover::LiftoverChainInput;
 pub use logging::{IngestEvent, IngestLog, IngestStage};
 pub use normalized::ReplayCounts;
+pub use resources::IngestResourceUsage;
+pub use write::evidence_sha256;
 
 #[derive(Debug)]
 pub struct IngestError(pub String);
@@ -87,6 +99,9 @@ pub struct IngestOptions {
     pub normalized_replay_mode: bool,
     pub prod_mode: bool,
     pub timestamp_policy: TimestampPolicy,
+    pub liftover_chain: Option<LiftoverChainInput>,
+    /// UCSC `chromAlias` file naming each contig in other conventions.
+    pub chrom_alias_path: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -137,6 +152,8 @@ impl IngestOptions {
             normalized_replay_mode: false,
             prod_mode: false,
             timestamp_policy: TimestampPolicy::DeterministicZero,
+            liftover_chain: None,
+            chrom_alias_path: None,
         }
     }
 }
@@ -153,6 +170,7 @@ pub struct IngestResult {
     pub shard_catalog: Option<ShardCatalog>,
     pub manifest: ArtifactManifest,
     pub anomaly_report: IngestAnomalyReport,
+    pub resource_usage: IngestResourceUsage,
     pub events: Vec<IngestEvent>,
 }
 
@@ -233,6 +251,23 @@ pub fn ingest_dataset_with_events(
         "ingest.persist.complete",
         std::collections::BTreeMap::new(),
     );
+    log.emit(
+        logging::IngestStage::Finalize,
+        "ingest.resources",
+        std::collections::BTreeMap::from([
+            (
+                "peak_rss_bytes".to_string(),
+                result
+                    .resource_usage
+                    .peak_rss_bytes
+                    .map_or_else(|| "unavailable".to_string(), |v| v.to_string()),
+            ),
+            (
+                "max_gene_block_records".to_string(),
+                result.resource_usage.max_gene_block_records.to_string(),
+            ),
+        ]),
+    );
     result.events = log.events().to_vec();
     Ok((result, log.events().to_vec()))
 }
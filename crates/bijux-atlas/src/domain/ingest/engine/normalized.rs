@@ -1,6 +1,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use super::extract::{ExonRecord, GeneRecord, TranscriptRecord};
+use super::spool::RowSpool;
 use super::IngestError;
 use crate::domain::canonical;
 use serde::{Deserialize, Serialize};
@@ -30,72 +30,59 @@ pub struct ReplayCounts {
     pub exons: u64,
 }
 
-pub fn write_normalized_jsonl_zst(
-    out_path: &Path,
-    genes: &[GeneRecord],
-    transcripts: &[TranscriptRecord],
-    exons: &[ExonRecord],
-) -> Result<(), IngestError> {
+/// Writes exons, then genes, then transcripts, each by span and record id,
+/// streaming rows from the spool.
+pub fn write_normalized_jsonl_zst(out_path: &Path, rows: &RowSpool) -> Result<(), IngestError> {
     if let Some(parent) = out_path.parent() {
         fs::create_dir_all(parent).map_err(|e| IngestError(e.to_string()))?;
     }
-    let mut items = Vec::<NormalizedRecord>::new();
-    for g in genes {
-        items.push(NormalizedRecord {
+    let file = fs::File::create(out_path).map_err(|e| IngestError(e.to_string()))?;
+    let mut encoder =
+        zstd::stream::write::Encoder::new(file, 3).map_err(|e| IngestError(e.to_string()))?;
+    let mut emit = |item: NormalizedRecord| -> Result<(), IngestError> {
+        let mut line =
+            canonical::stable_json_bytes(&item).map_err(|e| IngestError(e.to_string()))?;
+        line.push(b'\n');
+        encoder
+            .write_all(&line)
+            .map_err(|e| IngestError(e.to_string()))
+    };
+    rows.for_each_exon_by_span(|e| {
+        emit(NormalizedRecord {
+            schema_version: NORMALIZED_SCHEMA_VERSION,
+            kind: "exon".to_string(),
+            record_id: format!(
+                "exon:{}:{}:{}:{}",
+                e.transcript_id, e.exon_id, e.start, e.end
+            ),
+            seqid: e.seqid.clone(),
+            start: e.start,
+            end: e.end,
+            payload: serde_json::to_value(&e).map_err(|e| IngestError(e.to_string()))?,
+        })
+    })?;
+    rows.for_each_gene_by_span(|g| {
+        emit(NormalizedRecord {
             schema_version: NORMALIZED_SCHEMA_VERSION,
             kind: "gene".to_string(),
             record_id: format!("gene:{}", g.gene_id),
             seqid: g.seqid.clone(),
             start: g.start,
             end: g.end,
-            payload: serde_json::to_value(g).map_err(|e| IngestError(e.to_string()))?,
-        });
-    }
-    for t in transcripts {
-        items.push(NormalizedRecord {
+            payload: serde_json::to_value(&g).map_err(|e| IngestError(e.to_string()))?,
+        })
+    })?;
+    rows.for_each_transcript_by_span(|t| {
+        emit(NormalizedRecord {
             schema_version: NORMALIZED_SCHEMA_VERSION,
             kind: "transcript".to_string(),
             record_id: format!("transcript:{}", t.transcript_id),
             seqid: t.seqid.clone(),
             start: t.start,
             end: t.end,
-            payload: serde_json::to_value(t).map_err(|e| IngestError(e.to_string()))?,
-        });
-    }
-    for e in exons {
-        items.push(NormalizedRecord {
-            schema_version: NORMALIZED_SCHEMA_VERSION,
-            kind: "exon".to_string(),
-            record_id: format!(
-                "exon:{}:{}:{}:{}",
-                e.transcript_id, e.exon_id, e.start, e.end
-            ),
-            seqid: e.seqid.clone(),
-            start: e.start,
-            end: e.end,
-            payload: serde_json::to_value(e).map_err(|e| IngestError(e.to_string()))?,
-        });
-    }
-    items.sort_by(|a, b| {
-        a.kind
-            .cmp(&b.kind)
-            .then(a.seqid.cmp(&b.seqid))
-            .then(a.start.cmp(&b.start))
-            .then(a.end.cmp(&b.end))
-            .then(a.record_id.cmp(&b.record_id))
-    });
-
-    let file = fs::File::create(out_path).map_err(|e| IngestError(e.to_string()))?;
-    let mut encoder =
-        zstd::stream::write::Encoder::new(file, 3).map_err(|e| IngestError(e.to_string()))?;
-    for item in items {
-        let mut line =
-            canonical::stable_json_bytes(&item).map_err(|e| IngestError(e.to_string()))?;
-        line.push(b'\n');
-        encoder
-            .write_all(&line)
-            .map_err(|e| IngestError(e.to_string()))?;
-    }
+            payload: serde_json::to_value(&t).map_err(|e| IngestError(e.to_string()))?,
+        })
+    })?;
     let _ = encoder.finish().map_err(|e| IngestError(e.to_string()))?;
     Ok(())
 }
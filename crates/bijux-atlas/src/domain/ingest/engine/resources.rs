@@ -0,0 +1,37 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::Serialize;
+
+/// Resource usage observed during an ingest run.
+///
+/// Peak RSS is a runtime measurement: it is written to its own sidecar and the
+/// QC report's `resources` section, and never folded into the deterministic
+/// evidence bundle.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct IngestResourceUsage {
+    pub peak_rss_bytes: Option<u64>,
+    pub gene_blocks: u64,
+    pub max_gene_block_records: u64,
+}
+
+impl IngestResourceUsage {
+    #[must_use]
+    pub fn sample(gene_blocks: u64, max_gene_block_records: u64) -> Self {
+        Self {
+            peak_rss_bytes: peak_rss_bytes(),
+            gene_blocks,
+            max_gene_block_records,
+        }
+    }
+}
+
+pub(super) fn peak_rss_bytes() -> Option<u64> {
+    let content = std::fs::read_to_string("/proc/self/status").ok()?;
+    for line in content.lines() {
+        if let Some(rest) = line.strip_prefix("VmHWM:") {
+            let kb = rest.split_whitespace().next()?.parse::<u64>().ok()?;
+            return Some(kb.saturating_mul(1024));
+        }
+    }
+    None
+}
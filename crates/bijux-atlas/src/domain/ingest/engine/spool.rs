@@ -0,0 +1,1184 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::canonical_model::{
+    contig_order_rank, CanonicalCds, CanonicalIntron, CanonicalUtr, LineageRecord,
+};
+use super::extract::{ExonRecord, GeneRecord, GeneXrefRecord, TranscriptRecord};
+use super::IngestError;
+use crate::domain::canonical;
+use rusqlite::{params, Connection, OptionalExtension, Params, Row};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Placeholder a document carries where a [`SpilledArray`] is spliced in.
+pub const SPILLED_ARRAY_SLOT: &str = "\u{0}spilled-array\u{0}";
+
+const SPOOL_SCHEMA: &str = "
+PRAGMA journal_mode=OFF;
+PRAGMA synchronous=OFF;
+PRAGMA locking_mode=EXCLUSIVE;
+PRAGMA temp_store=FILE;
+CREATE TABLE gene_ids (id TEXT PRIMARY KEY) WITHOUT ROWID;
+CREATE TABLE transcript_ids (id TEXT PRIMARY KEY) WITHOUT ROWID;
+CREATE TABLE feature_nodes (
+  id TEXT PRIMARY KEY,
+  resolved INTEGER NOT NULL DEFAULT 0
+) WITHOUT ROWID;
+CREATE TABLE parent_edges (id TEXT NOT NULL, parent TEXT NOT NULL);
+CREATE INDEX parent_edges_id ON parent_edges(id);
+CREATE TABLE feature_keys (key TEXT PRIMARY KEY, feature_type TEXT NOT NULL) WITHOUT ROWID;
+CREATE TABLE gene_candidates (
+  seq INTEGER PRIMARY KEY,
+  gene_id TEXT NOT NULL,
+  gene_name TEXT NOT NULL,
+  biotype TEXT NOT NULL,
+  seqid TEXT NOT NULL,
+  start INTEGER NOT NULL,
+  end INTEGER NOT NULL,
+  sequence_length INTEGER NOT NULL,
+  strand TEXT NOT NULL,
+  description TEXT,
+  contig_rank INTEGER NOT NULL,
+  contig_number TEXT NOT NULL,
+  contig_label TEXT NOT NULL
+);
+CREATE INDEX gene_candidates_gene_id ON gene_candidates(gene_id);
+CREATE TABLE gene_xrefs (
+  gene_id TEXT NOT NULL,
+  namespace TEXT NOT NULL,
+  value TEXT NOT NULL,
+  PRIMARY KEY (gene_id, namespace, value)
+) WITHOUT ROWID;
+CREATE TABLE transcript_parents (
+  seq INTEGER PRIMARY KEY,
+  parent TEXT NOT NULL,
+  multiple INTEGER NOT NULL
+);
+CREATE INDEX transcript_parents_parent ON transcript_parents(parent);
+CREATE TABLE pending_transcripts (
+  seq INTEGER PRIMARY KEY,
+  transcript_id TEXT NOT NULL,
+  parent_gene_id TEXT NOT NULL,
+  transcript_type TEXT NOT NULL,
+  biotype TEXT,
+  seqid TEXT NOT NULL,
+  start INTEGER NOT NULL,
+  end INTEGER NOT NULL,
+  sequence_length INTEGER NOT NULL
+);
+CREATE INDEX pending_transcripts_id ON pending_transcripts(transcript_id);
+CREATE TABLE child_spans (transcript_id TEXT NOT NULL, is_cds INTEGER NOT NULL, span INTEGER NOT NULL);
+CREATE INDEX child_spans_transcript ON child_spans(transcript_id, is_cds);
+CREATE TABLE child_parent_refs (transcript_id TEXT NOT NULL);
+CREATE TABLE exons (
+  seq INTEGER PRIMARY KEY,
+  exon_id TEXT NOT NULL,
+  transcript_id TEXT NOT NULL,
+  seqid TEXT NOT NULL,
+  start INTEGER NOT NULL,
+  end INTEGER NOT NULL,
+  exon_length INTEGER NOT NULL
+);
+CREATE INDEX exons_transcript ON exons(transcript_id);
+CREATE INDEX exons_position ON exons(seqid, start, exon_id);
+CREATE TABLE lineage (
+  seq INTEGER PRIMARY KEY,
+  feature_type TEXT NOT NULL,
+  seqid TEXT NOT NULL,
+  start INTEGER NOT NULL,
+  end INTEGER NOT NULL,
+  gff3_line INTEGER NOT NULL,
+  record_sha256 TEXT NOT NULL,
+  record TEXT NOT NULL
+);
+CREATE INDEX lineage_key ON lineage(feature_type, seqid, start, end);
+CREATE TABLE cds (seq INTEGER PRIMARY KEY, transcript_id TEXT NOT NULL, record TEXT NOT NULL);
+CREATE INDEX cds_transcript ON cds(transcript_id);
+CREATE TABLE genes (
+  gene_id TEXT PRIMARY KEY,
+  gene_name TEXT NOT NULL,
+  biotype TEXT NOT NULL,
+  seqid TEXT NOT NULL,
+  start INTEGER NOT NULL,
+  end INTEGER NOT NULL,
+  transcript_count INTEGER NOT NULL DEFAULT 0,
+  exon_count INTEGER NOT NULL DEFAULT 0,
+  total_exon_span INTEGER NOT NULL DEFAULT 0,
+  cds_present INTEGER NOT NULL DEFAULT 0,
+  sequence_length INTEGER NOT NULL,
+  strand TEXT NOT NULL,
+  description TEXT,
+  contig_rank INTEGER NOT NULL,
+  contig_number TEXT NOT NULL,
+  contig_label TEXT NOT NULL
+);
+CREATE INDEX genes_position ON genes(seqid, start, gene_id);
+CREATE INDEX genes_contig_order ON genes(contig_rank, contig_number, contig_label, start, end, gene_id);
+CREATE TABLE transcripts (
+  transcript_id TEXT PRIMARY KEY,
+  parent_gene_id TEXT NOT NULL,
+  transcript_type TEXT NOT NULL,
+  biotype TEXT,
+  seqid TEXT NOT NULL,
+  start INTEGER NOT NULL,
+  end INTEGER NOT NULL,
+  exon_count INTEGER NOT NULL,
+  total_exon_span INTEGER NOT NULL,
+  cds_present INTEGER NOT NULL,
+  sequence_length INTEGER NOT NULL,
+  spliced_length INTEGER,
+  cds_span_length INTEGER
+);
+CREATE INDEX transcripts_parent ON transcripts(parent_gene_id);
+CREATE INDEX transcripts_position ON transcripts(seqid, start, transcript_id);
+CREATE TABLE canonical_cds (seq INTEGER PRIMARY KEY, seqid TEXT NOT NULL, record TEXT NOT NULL);
+CREATE TABLE canonical_introns (seq INTEGER PRIMARY KEY, record TEXT NOT NULL);
+CREATE TABLE canonical_utrs (seq INTEGER PRIMARY KEY, record TEXT NOT NULL);
+";
+
+const GENE_COLUMNS: &str = "gene_id, gene_name, biotype, seqid, start, end, transcript_count, \
+     exon_count, total_exon_span, cds_present, sequence_length, strand, description";
+const TRANSCRIPT_COLUMNS: &str = "transcript_id, parent_gene_id, transcript_type, biotype, \
+     seqid, start, end, exon_count, total_exon_span, cds_present, sequence_length, \
+     spliced_length, cds_span_length";
+const EXON_COLUMNS: &str = "exon_id, transcript_id, seqid, start, end, exon_length";
+
+static SPOOL_SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+fn db_err(e: rusqlite::Error) -> IngestError {
+    IngestError(e.to_string())
+}
+
+fn io_err(e: std::io::Error) -> IngestError {
+    IngestError(e.to_string())
+}
+
+/// Rows a writer reads: the whole dataset, or the genes, transcripts, CDS
+/// segments and cross-references of one contig shard.
+#[derive(Debug, Clone, Copy)]
+pub enum RowScope<'a> {
+    Dataset,
+    Shard(&'a [String]),
+}
+
+/// Scratch SQLite store behind the streaming ingest.
+///
+/// Each gene block's rows are written here before the next block is read, and
+/// the artifact writers read them back in output order, so ingest memory is
+/// bounded by one gene block rather than by the input. The store lives in a
+/// hidden directory under the output root and is removed when dropped.
+pub struct RowSpool {
+    conn: Connection,
+    // Declared after the connection so the database is closed before its
+    // directory is removed.
+    dir: ScratchDir,
+}
+
+impl std::fmt::Debug for RowSpool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RowSpool")
+            .field("dir", &self.dir.0)
+            .finish_non_exhaustive()
+    }
+}
+
+struct ScratchDir(PathBuf);
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+impl RowSpool {
+    pub fn create(parent: &Path) -> Result<Self, IngestError> {
+        let dir = parent.join(format!(
+            ".ingest-spool-{}-{}",
+            std::process::id(),
+            SPOOL_SEQUENCE.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(&dir).map_err(io_err)?;
+        let dir = ScratchDir(dir);
+        let conn = Connection::open(dir.0.join("rows.sqlite")).map_err(db_err)?;
+        conn.execute_batch(SPOOL_SCHEMA).map_err(db_err)?;
+        conn.set_prepared_statement_cache_capacity(64);
+        Ok(Self { conn, dir })
+    }
+
+    /// Runs `f` in one transaction; a gene block is flushed as a unit.
+    pub fn in_transaction<T>(
+        &self,
+        f: impl FnOnce() -> Result<T, IngestError>,
+    ) -> Result<T, IngestError> {
+        self.conn.execute_batch("BEGIN").map_err(db_err)?;
+        match f() {
+            Ok(value) => {
+                self.conn.execute_batch("COMMIT").map_err(db_err)?;
+                Ok(value)
+            }
+            Err(err) => {
+                let _ = self.conn.execute_batch("ROLLBACK");
+                Err(err)
+            }
+        }
+    }
+
+    pub fn spill_array(&self, name: &str) -> Result<ArraySpill, IngestError> {
+        let path = self.dir.0.join(name);
+        let file = fs::File::create(&path).map_err(io_err)?;
+        Ok(ArraySpill {
+            path,
+            out: BufWriter::new(file),
+            empty: true,
+        })
+    }
+
+    fn execute<P: Params>(&self, sql: &str, params: P) -> Result<usize, IngestError> {
+        self.conn
+            .prepare_cached(sql)
+            .and_then(|mut stmt| stmt.execute(params))
+            .map_err(db_err)
+    }
+
+    fn exists<P: Params>(&self, sql: &str, params: P) -> Result<bool, IngestError> {
+        self.conn
+            .prepare_cached(sql)
+            .and_then(|mut stmt| stmt.exists(params))
+            .map_err(db_err)
+    }
+
+    fn count(&self, sql: &str) -> Result<u64, IngestError> {
+        self.conn
+            .prepare_cached(sql)
+            .and_then(|mut stmt| stmt.query_row([], |r| u64_at(r, 0)))
+            .map_err(db_err)
+    }
+
+    fn for_each_row<P, T>(
+        &self,
+        sql: &str,
+        params: P,
+        decode: impl Fn(&Row<'_>) -> rusqlite::Result<T>,
+        mut f: impl FnMut(T) -> Result<(), IngestError>,
+    ) -> Result<(), IngestError>
+    where
+        P: Params,
+    {
+        let mut stmt = self.conn.prepare_cached(sql).map_err(db_err)?;
+        let mut rows = stmt.query(params).map_err(db_err)?;
+        while let Some(row) = rows.next().map_err(db_err)? {
+            f(decode(row).map_err(db_err)?)?;
+        }
+        Ok(())
+    }
+
+    fn for_each_json<P, T>(
+        &self,
+        sql: &str,
+        params: P,
+        mut f: impl FnMut(T) -> Result<(), IngestError>,
+    ) -> Result<(), IngestError>
+    where
+        P: Params,
+        T: DeserializeOwned,
+    {
+        self.for_each_row(
+            sql,
+            params,
+            |r| r.get::<_, String>(0),
+            |raw| f(serde_json::from_str(&raw).map_err(|e| IngestError(e.to_string()))?),
+        )
+    }
+
+    fn collect_rows<P, T>(
+        &self,
+        sql: &str,
+        params: P,
+        decode: impl Fn(&Row<'_>) -> rusqlite::Result<T>,
+    ) -> Result<Vec<T>, IngestError>
+    where
+        P: Params,
+    {
+        let mut out = Vec::new();
+        self.for_each_row(sql, params, decode, |item| {
+            out.push(item);
+            Ok(())
+        })?;
+        Ok(out)
+    }
+
+    pub fn record_gene_id(&self, id: &str) -> Result<(), IngestError> {
+        self.execute(
+            "INSERT OR IGNORE INTO gene_ids (id) VALUES (?1)",
+            params![id],
+        )
+        .map(|_| ())
+    }
+
+    pub fn record_transcript_id(&self, id: &str) -> Result<(), IngestError> {
+        self.execute(
+            "INSERT OR IGNORE INTO transcript_ids (id) VALUES (?1)",
+            params![id],
+        )
+        .map(|_| ())
+    }
+
+    /// Records the `Parent` edges of feature `id`; a repeated ID replaces the
+    /// edges of the earlier record.
+    pub fn record_parent_edges(&self, id: &str, parents: &[String]) -> Result<(), IngestError> {
+        self.execute(
+            "INSERT OR IGNORE INTO feature_nodes (id) VALUES (?1)",
+            params![id],
+        )?;
+        self.execute("DELETE FROM parent_edges WHERE id = ?1", params![id])?;
+        for parent in parents {
+            self.execute(
+                "INSERT INTO parent_edges (id, parent) VALUES (?1, ?2)",
+                params![id, parent],
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn is_gene_id(&self, id: &str) -> Result<bool, IngestError> {
+        self.exists("SELECT 1 FROM gene_ids WHERE id = ?1", params![id])
+    }
+
+    pub fn is_transcript_id(&self, id: &str) -> Result<bool, IngestError> {
+        self.exists("SELECT 1 FROM transcript_ids WHERE id = ?1", params![id])
+    }
+
+    /// Parent edges of the features whose ancestry never reaches a root.
+    ///
+    /// Features are resolved level by level from the roots down, so only the
+    /// features on or below a `Parent` cycle are loaded for cycle detection.
+    pub fn unresolved_parent_edges(&self) -> Result<HashMap<String, Vec<String>>, IngestError> {
+        while self.execute(
+            "UPDATE feature_nodes SET resolved = 1
+             WHERE resolved = 0 AND NOT EXISTS (
+               SELECT 1 FROM parent_edges e JOIN feature_nodes p ON p.id = e.parent
+               WHERE e.id = feature_nodes.id AND p.resolved = 0
+             )",
+            [],
+        )? > 0
+        {}
+        let mut edges: HashMap<String, Vec<String>> = HashMap::new();
+        self.for_each_row(
+            "SELECT n.id, e.parent FROM feature_nodes n
+             LEFT JOIN parent_edges e ON e.id = n.id
+             WHERE n.resolved = 0 ORDER BY n.id, e.rowid",
+            [],
+            |r| Ok((r.get::<_, String>(0)?, r.get::<_, Option<String>>(1)?)),
+            |(id, parent)| {
+                let entry = edges.entry(id).or_default();
+                entry.extend(parent);
+                Ok(())
+            },
+        )?;
+        Ok(edges)
+    }
+
+    /// Claims a feature-ID key, returning the feature type that claimed it first.
+    pub fn claim_feature_key(
+        &self,
+        key: &str,
+        feature_type: &str,
+    ) -> Result<Option<String>, IngestError> {
+        let previous = self
+            .conn
+            .prepare_cached("SELECT feature_type FROM feature_keys WHERE key = ?1")
+            .and_then(|mut stmt| {
+                stmt.query_row(params![key], |r| r.get::<_, String>(0))
+                    .optional()
+            })
+            .map_err(db_err)?;
+        if previous.is_none() {
+            self.execute(
+                "INSERT INTO feature_keys (key, feature_type) VALUES (?1, ?2)",
+                params![key, feature_type],
+            )?;
+        }
+        Ok(previous)
+    }
+
+    pub fn push_gene_candidate(&self, gene: &GeneRecord) -> Result<(), IngestError> {
+        let (contig_rank, contig_number, contig_label) = contig_order_rank(&gene.seqid);
+        self.execute(
+            "INSERT INTO gene_candidates (
+               gene_id, gene_name, biotype, seqid, start, end, sequence_length, strand,
+               description, contig_rank, contig_number, contig_label
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            params![
+                gene.gene_id,
+                gene.gene_name,
+                gene.biotype,
+                gene.seqid,
+                gene.start as i64,
+                gene.end as i64,
+                gene.sequence_length as i64,
+                gene.strand,
+                gene.description,
+                contig_rank,
+                // Zero-padded so the text order matches the numeric order.
+                format!("{contig_number:020}"),
+                contig_label
+            ],
+        )
+        .map(|_| ())
+    }
+
+    pub fn push_gene_xref(
+        &self,
+        gene_id: &str,
+        namespace: &str,
+        value: &str,
+    ) -> Result<(), IngestError> {
+        self.execute(
+            "INSERT OR IGNORE INTO gene_xrefs (gene_id, namespace, value) VALUES (?1, ?2, ?3)",
+            params![gene_id, namespace, value],
+        )
+        .map(|_| ())
+    }
+
+    pub fn push_transcript_parent(&self, parent: &str, multiple: bool) -> Result<(), IngestError> {
+        self.execute(
+            "INSERT INTO transcript_parents (parent, multiple) VALUES (?1, ?2)",
+            params![parent, multiple],
+        )
+        .map(|_| ())
+    }
+
+    pub fn push_pending_transcript(&self, tx: &TranscriptRecord) -> Result<(), IngestError> {
+        self.execute(
+            "INSERT INTO pending_transcripts (
+               transcript_id, parent_gene_id, transcript_type, biotype, seqid, start, end,
+               sequence_length
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                tx.transcript_id,
+                tx.parent_gene_id,
+                tx.transcript_type,
+                tx.biotype,
+                tx.seqid,
+                tx.start as i64,
+                tx.end as i64,
+                tx.sequence_length as i64
+            ],
+        )
+        .map(|_| ())
+    }
+
+    pub fn push_child_span(
+        &self,
+        transcript_id: &str,
+        is_cds: bool,
+        span: u64,
+    ) -> Result<(), IngestError> {
+        self.execute(
+            "INSERT INTO child_spans (transcript_id, is_cds, span) VALUES (?1, ?2, ?3)",
+            params![transcript_id, is_cds, span as i64],
+        )
+        .map(|_| ())
+    }
+
+    pub fn push_child_parent_ref(&self, transcript_id: &str) -> Result<(), IngestError> {
+        self.execute(
+            "INSERT INTO child_parent_refs (transcript_id) VALUES (?1)",
+            params![transcript_id],
+        )
+        .map(|_| ())
+    }
+
+    pub fn push_exon(&self, exon: &ExonRecord) -> Result<(), IngestError> {
+        self.execute(
+            "INSERT INTO exons (exon_id, transcript_id, seqid, start, end, exon_length)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                exon.exon_id,
+                exon.transcript_id,
+                exon.seqid,
+                exon.start as i64,
+                exon.end as i64,
+                exon.exon_length as i64
+            ],
+        )
+        .map(|_| ())
+    }
+
+    pub fn push_lineage(
+        &self,
+        feature_type: &str,
+        seqid: &str,
+        start: u64,
+        end: u64,
+        lineage: &LineageRecord,
+    ) -> Result<(), IngestError> {
+        self.execute(
+            "INSERT INTO lineage (feature_type, seqid, start, end, gff3_line, record_sha256, record)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                feature_type,
+                seqid,
+                start as i64,
+                end as i64,
+                lineage.gff3_line as i64,
+                lineage.record_sha256,
+                json_text(lineage)?
+            ],
+        )
+        .map(|_| ())
+    }
+
+    pub fn push_cds(&self, cds: &CanonicalCds) -> Result<(), IngestError> {
+        self.execute(
+            "INSERT INTO cds (transcript_id, record) VALUES (?1, ?2)",
+            params![cds.transcript_id, json_text(cds)?],
+        )
+        .map(|_| ())
+    }
+
+    /// Gene IDs carried by more than one gene record, in ID order, with the
+    /// distinct seqids of their records.
+    pub fn for_each_duplicate_gene(
+        &self,
+        mut f: impl FnMut(String, Vec<String>) -> Result<(), IngestError>,
+    ) -> Result<(), IngestError> {
+        self.for_each_row(
+            "SELECT gene_id FROM gene_candidates GROUP BY gene_id HAVING COUNT(*) > 1
+             ORDER BY gene_id",
+            [],
+            |r| r.get::<_, String>(0),
+            |gene_id| {
+                let seqids = self.collect_rows(
+                    "SELECT DISTINCT seqid FROM gene_candidates WHERE gene_id = ?1 ORDER BY seqid",
+                    params![gene_id],
+                    |r| r.get(0),
+                )?;
+                f(gene_id, seqids)
+            },
+        )
+    }
+
+    /// Keeps one record per gene ID: the first in input order, or with
+    /// `by_content` the smallest by seqid, coordinates, name and biotype.
+    pub fn select_gene_winners(&self, by_content: bool) -> Result<(), IngestError> {
+        let order = if by_content {
+            "d.seqid, d.start, d.end, d.gene_name, d.biotype, d.seq"
+        } else {
+            "d.seq"
+        };
+        self.conn
+            .execute_batch(&format!(
+                "INSERT INTO genes (
+                   gene_id, gene_name, biotype, seqid, start, end, sequence_length, strand,
+                   description, contig_rank, contig_number, contig_label
+                 )
+                 SELECT gene_id, gene_name, biotype, seqid, start, end, sequence_length, strand,
+                   description, contig_rank, contig_number, contig_label
+                 FROM gene_candidates c
+                 WHERE c.seq = (
+                   SELECT d.seq FROM gene_candidates d WHERE d.gene_id = c.gene_id
+                   ORDER BY {order} LIMIT 1
+                 )"
+            ))
+            .map_err(db_err)
+    }
+
+    /// Transcript parent references in input order, flagged as coming from a
+    /// multi-parent transcript and as naming a kept gene.
+    pub fn for_each_transcript_parent(
+        &self,
+        mut f: impl FnMut(String, bool, bool) -> Result<(), IngestError>,
+    ) -> Result<(), IngestError> {
+        self.for_each_row(
+            "SELECT p.parent, p.multiple, EXISTS (SELECT 1 FROM genes g WHERE g.gene_id = p.parent)
+             FROM transcript_parents p ORDER BY p.seq",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+            |(parent, multiple, known)| f(parent, multiple, known),
+        )
+    }
+
+    pub fn count_gene_transcripts(&self) -> Result<(), IngestError> {
+        self.execute(
+            "UPDATE genes SET transcript_count = (
+               SELECT COUNT(*) FROM transcript_parents p WHERE p.parent = genes.gene_id
+             )",
+            [],
+        )
+        .map(|_| ())
+    }
+
+    /// Exon and CDS parent references that name no transcript record.
+    pub fn for_each_orphan_child_ref(
+        &self,
+        f: impl FnMut(String) -> Result<(), IngestError>,
+    ) -> Result<(), IngestError> {
+        self.for_each_row(
+            "SELECT c.transcript_id FROM child_parent_refs c
+             WHERE NOT EXISTS (
+               SELECT 1 FROM pending_transcripts p WHERE p.transcript_id = c.transcript_id
+             )
+             ORDER BY c.rowid",
+            [],
+            |r| r.get(0),
+            f,
+        )
+    }
+
+    /// Transcript records whose parent gene was not kept.
+    pub fn for_each_orphan_transcript(
+        &self,
+        f: impl FnMut(String) -> Result<(), IngestError>,
+    ) -> Result<(), IngestError> {
+        self.for_each_row(
+            "SELECT p.transcript_id FROM pending_transcripts p
+             WHERE NOT EXISTS (SELECT 1 FROM genes g WHERE g.gene_id = p.parent_gene_id)
+             ORDER BY p.seq",
+            [],
+            |r| r.get(0),
+            f,
+        )
+    }
+
+    /// Transcript IDs carried by more than one kept transcript record, in ID order.
+    pub fn for_each_duplicate_transcript(
+        &self,
+        f: impl FnMut(String) -> Result<(), IngestError>,
+    ) -> Result<(), IngestError> {
+        self.for_each_row(
+            "SELECT p.transcript_id FROM pending_transcripts p
+             WHERE EXISTS (SELECT 1 FROM genes g WHERE g.gene_id = p.parent_gene_id)
+             GROUP BY p.transcript_id HAVING COUNT(*) > 1
+             ORDER BY p.transcript_id",
+            [],
+            |r| r.get(0),
+            f,
+        )
+    }
+
+    /// Keeps one record per transcript ID among those with a kept parent gene,
+    /// the smallest by seqid, coordinates and parent, and attaches its exon and
+    /// CDS totals.
+    pub fn select_transcript_winners(
+        &self,
+        spliced_length: bool,
+        cds_length: bool,
+    ) -> Result<(), IngestError> {
+        self.execute(
+            "INSERT INTO transcripts (
+               transcript_id, parent_gene_id, transcript_type, biotype, seqid, start, end,
+               exon_count, total_exon_span, cds_present, sequence_length, spliced_length,
+               cds_span_length
+             )
+             SELECT t.transcript_id, t.parent_gene_id, t.transcript_type, t.biotype, t.seqid,
+               t.start, t.end, t.exon_count, t.exon_span, t.cds_present, t.sequence_length,
+               CASE WHEN ?1 THEN t.exon_span END,
+               CASE WHEN ?2 THEN t.cds_span END
+             FROM (
+               SELECT p.*,
+                 (SELECT COUNT(*) FROM child_spans c
+                  WHERE c.transcript_id = p.transcript_id AND c.is_cds = 0) AS exon_count,
+                 (SELECT COALESCE(SUM(c.span), 0) FROM child_spans c
+                  WHERE c.transcript_id = p.transcript_id AND c.is_cds = 0) AS exon_span,
+                 EXISTS (SELECT 1 FROM child_spans c
+                  WHERE c.transcript_id = p.transcript_id AND c.is_cds = 1) AS cds_present,
+                 (SELECT COALESCE(SUM(c.span), 0) FROM child_spans c
+                  WHERE c.transcript_id = p.transcript_id AND c.is_cds = 1) AS cds_span
+               FROM pending_transcripts p
+               WHERE p.seq = (
+                 SELECT q.seq FROM pending_transcripts q
+                 JOIN genes g ON g.gene_id = q.parent_gene_id
+                 WHERE q.transcript_id = p.transcript_id
+                 ORDER BY q.seqid, q.start, q.end, q.parent_gene_id, q.seq LIMIT 1
+               )
+             ) t",
+            params![spliced_length, cds_length],
+        )
+        .map(|_| ())
+    }
+
+    pub fn aggregate_gene_children(&self) -> Result<(), IngestError> {
+        self.execute(
+            "UPDATE genes SET
+               exon_count = COALESCE((SELECT SUM(t.exon_count) FROM transcripts t
+                 WHERE t.parent_gene_id = genes.gene_id), 0),
+               total_exon_span = COALESCE((SELECT SUM(t.total_exon_span) FROM transcripts t
+                 WHERE t.parent_gene_id = genes.gene_id), 0),
+               cds_present = EXISTS (SELECT 1 FROM transcripts t
+                 WHERE t.parent_gene_id = genes.gene_id AND t.cds_present = 1)",
+            [],
+        )
+        .map(|_| ())
+    }
+
+    pub fn gene_count(&self) -> Result<u64, IngestError> {
+        self.count("SELECT COUNT(*) FROM genes")
+    }
+
+    pub fn transcript_count(&self) -> Result<u64, IngestError> {
+        self.count("SELECT COUNT(*) FROM transcripts")
+    }
+
+    pub fn exon_count(&self) -> Result<u64, IngestError> {
+        self.count("SELECT COUNT(*) FROM exons")
+    }
+
+    /// Sum of the per-gene transcript counts, multi-parent references included.
+    pub fn gene_transcript_total(&self) -> Result<u64, IngestError> {
+        self.count("SELECT COALESCE(SUM(transcript_count), 0) FROM genes")
+    }
+
+    /// Genes ordered by seqid, start and ID; a shard orders by end before ID.
+    pub fn for_each_gene(
+        &self,
+        scope: RowScope<'_>,
+        f: impl FnMut(GeneRecord) -> Result<(), IngestError>,
+    ) -> Result<(), IngestError> {
+        match scope {
+            RowScope::Dataset => self.for_each_row(
+                &format!("SELECT {GENE_COLUMNS} FROM genes ORDER BY seqid, start, gene_id"),
+                [],
+                gene_from_row,
+                f,
+            ),
+            RowScope::Shard(seqids) => self.for_each_row(
+                &format!(
+                    "SELECT {GENE_COLUMNS} FROM genes
+                     WHERE seqid IN (SELECT value FROM json_each(?1))
+                     ORDER BY seqid, start, end, gene_id"
+                ),
+                params![json_text(&seqids)?],
+                gene_from_row,
+                f,
+            ),
+        }
+    }
+
+    /// Genes in canonical contig order: numbered chromosomes, X, Y, mitochondria, others.
+    pub fn for_each_gene_in_contig_order(
+        &self,
+        f: impl FnMut(GeneRecord) -> Result<(), IngestError>,
+    ) -> Result<(), IngestError> {
+        self.for_each_row(
+            &format!(
+                "SELECT {GENE_COLUMNS} FROM genes
+                 ORDER BY contig_rank, contig_number, contig_label, start, end, gene_id"
+            ),
+            [],
+            gene_from_row,
+            f,
+        )
+    }
+
+    pub fn for_each_gene_by_id(
+        &self,
+        f: impl FnMut(GeneRecord) -> Result<(), IngestError>,
+    ) -> Result<(), IngestError> {
+        self.for_each_row(
+            &format!("SELECT {GENE_COLUMNS} FROM genes ORDER BY gene_id"),
+            [],
+            gene_from_row,
+            f,
+        )
+    }
+
+    pub fn for_each_transcript(
+        &self,
+        scope: RowScope<'_>,
+        f: impl FnMut(TranscriptRecord) -> Result<(), IngestError>,
+    ) -> Result<(), IngestError> {
+        match scope {
+            RowScope::Dataset => self.for_each_row(
+                &format!(
+                    "SELECT {TRANSCRIPT_COLUMNS} FROM transcripts
+                     ORDER BY seqid, start, transcript_id"
+                ),
+                [],
+                transcript_from_row,
+                f,
+            ),
+            RowScope::Shard(seqids) => self.for_each_row(
+                &format!(
+                    "SELECT {TRANSCRIPT_COLUMNS} FROM transcripts
+                     WHERE seqid IN (SELECT value FROM json_each(?1))
+                     ORDER BY seqid, start, transcript_id"
+                ),
+                params![json_text(&seqids)?],
+                transcript_from_row,
+                f,
+            ),
+        }
+    }
+
+    pub fn transcripts_of_gene(&self, gene_id: &str) -> Result<Vec<TranscriptRecord>, IngestError> {
+        self.collect_rows(
+            &format!(
+                "SELECT {TRANSCRIPT_COLUMNS} FROM transcripts WHERE parent_gene_id = ?1
+                 ORDER BY seqid, start, transcript_id"
+            ),
+            params![gene_id],
+            transcript_from_row,
+        )
+    }
+
+    /// Every exon row, transcript kept or not; shards carry no exons.
+    pub fn for_each_exon(
+        &self,
+        scope: RowScope<'_>,
+        f: impl FnMut(ExonRecord) -> Result<(), IngestError>,
+    ) -> Result<(), IngestError> {
+        if matches!(scope, RowScope::Shard(_)) {
+            return Ok(());
+        }
+        self.for_each_row(
+            &format!("SELECT {EXON_COLUMNS} FROM exons ORDER BY seqid, start, exon_id, seq"),
+            [],
+            exon_from_row,
+            f,
+        )
+    }
+
+    /// Genes by span, the order of the normalized debug export.
+    pub fn for_each_gene_by_span(
+        &self,
+        f: impl FnMut(GeneRecord) -> Result<(), IngestError>,
+    ) -> Result<(), IngestError> {
+        self.for_each_row(
+            &format!("SELECT {GENE_COLUMNS} FROM genes ORDER BY seqid, start, end, gene_id"),
+            [],
+            gene_from_row,
+            f,
+        )
+    }
+
+    pub fn for_each_transcript_by_span(
+        &self,
+        f: impl FnMut(TranscriptRecord) -> Result<(), IngestError>,
+    ) -> Result<(), IngestError> {
+        self.for_each_row(
+            &format!(
+                "SELECT {TRANSCRIPT_COLUMNS} FROM transcripts
+                 ORDER BY seqid, start, end, transcript_id"
+            ),
+            [],
+            transcript_from_row,
+            f,
+        )
+    }
+
+    /// Exons by span, ties broken by the normalized `transcript:exon:start:end` record id.
+    pub fn for_each_exon_by_span(
+        &self,
+        f: impl FnMut(ExonRecord) -> Result<(), IngestError>,
+    ) -> Result<(), IngestError> {
+        self.for_each_row(
+            &format!(
+                "SELECT {EXON_COLUMNS} FROM exons
+                 ORDER BY seqid, start, end,
+                   transcript_id || ':' || exon_id || ':' || start || ':' || end, seq"
+            ),
+            [],
+            exon_from_row,
+            f,
+        )
+    }
+
+    pub fn exons_of_transcript(&self, transcript_id: &str) -> Result<Vec<ExonRecord>, IngestError> {
+        self.collect_rows(
+            &format!(
+                "SELECT {EXON_COLUMNS} FROM exons WHERE transcript_id = ?1
+                 ORDER BY seqid, start, exon_id, seq"
+            ),
+            params![transcript_id],
+            exon_from_row,
+        )
+    }
+
+    pub fn cds_of_transcript(&self, transcript_id: &str) -> Result<Vec<CanonicalCds>, IngestError> {
+        let mut out = Vec::new();
+        self.for_each_json(
+            "SELECT record FROM cds WHERE transcript_id = ?1 ORDER BY seq",
+            params![transcript_id],
+            |cds| {
+                out.push(cds);
+                Ok(())
+            },
+        )?;
+        Ok(out)
+    }
+
+    /// Lineage of the GFF3 records at one feature type and span, in line order;
+    /// `None` when no record matches.
+    pub fn lineage(
+        &self,
+        feature_type: &str,
+        seqid: &str,
+        start: u64,
+        end: u64,
+    ) -> Result<Option<Vec<LineageRecord>>, IngestError> {
+        let mut out = Vec::new();
+        self.for_each_json(
+            "SELECT record FROM lineage
+             WHERE feature_type = ?1 AND seqid = ?2 AND start = ?3 AND end = ?4
+             ORDER BY gff3_line, record_sha256, seq",
+            params![feature_type, seqid, start as i64, end as i64],
+            |record| {
+                out.push(record);
+                Ok(())
+            },
+        )?;
+        Ok((!out.is_empty()).then_some(out))
+    }
+
+    /// Cross-references of kept genes, ordered by gene, namespace and value.
+    pub fn for_each_xref(
+        &self,
+        scope: RowScope<'_>,
+        f: impl FnMut(GeneXrefRecord) -> Result<(), IngestError>,
+    ) -> Result<(), IngestError> {
+        let decode = |r: &Row<'_>| {
+            Ok(GeneXrefRecord {
+                gene_id: r.get(0)?,
+                namespace: r.get(1)?,
+                value: r.get(2)?,
+            })
+        };
+        match scope {
+            RowScope::Dataset => self.for_each_row(
+                "SELECT x.gene_id, x.namespace, x.value FROM gene_xrefs x
+                 JOIN genes g ON g.gene_id = x.gene_id
+                 ORDER BY x.gene_id, x.namespace, x.value",
+                [],
+                decode,
+                f,
+            ),
+            RowScope::Shard(seqids) => self.for_each_row(
+                "SELECT x.gene_id, x.namespace, x.value FROM gene_xrefs x
+                 JOIN genes g ON g.gene_id = x.gene_id
+                 WHERE g.seqid IN (SELECT value FROM json_each(?1))
+                 ORDER BY x.gene_id, x.namespace, x.value",
+                params![json_text(&seqids)?],
+                decode,
+                f,
+            ),
+        }
+    }
+
+    /// Values of one namespace for a gene, in value order.
+    pub fn gene_xref_values(
+        &self,
+        gene_id: &str,
+        namespace: &str,
+    ) -> Result<Vec<String>, IngestError> {
+        self.collect_rows(
+            "SELECT value FROM gene_xrefs WHERE gene_id = ?1 AND namespace = ?2 ORDER BY value",
+            params![gene_id, namespace],
+            |r| r.get(0),
+        )
+    }
+
+    pub fn push_canonical_cds(&self, cds: &CanonicalCds) -> Result<(), IngestError> {
+        self.execute(
+            "INSERT INTO canonical_cds (seqid, record) VALUES (?1, ?2)",
+            params![cds.seqid, json_text(cds)?],
+        )
+        .map(|_| ())
+    }
+
+    pub fn push_canonical_intron(&self, intron: &CanonicalIntron) -> Result<(), IngestError> {
+        self.execute(
+            "INSERT INTO canonical_introns (record) VALUES (?1)",
+            params![json_text(intron)?],
+        )
+        .map(|_| ())
+    }
+
+    pub fn push_canonical_utr(&self, utr: &CanonicalUtr) -> Result<(), IngestError> {
+        self.execute(
+            "INSERT INTO canonical_utrs (record) VALUES (?1)",
+            params![json_text(utr)?],
+        )
+        .map(|_| ())
+    }
+
+    /// CDS segments of the canonical model in model order.
+    pub fn for_each_canonical_cds(
+        &self,
+        scope: RowScope<'_>,
+        f: impl FnMut(CanonicalCds) -> Result<(), IngestError>,
+    ) -> Result<(), IngestError> {
+        match scope {
+            RowScope::Dataset => {
+                self.for_each_json("SELECT record FROM canonical_cds ORDER BY seq", [], f)
+            }
+            RowScope::Shard(seqids) => self.for_each_json(
+                "SELECT record FROM canonical_cds
+                 WHERE seqid IN (SELECT value FROM json_each(?1)) ORDER BY seq",
+                params![json_text(&seqids)?],
+                f,
+            ),
+        }
+    }
+
+    /// Introns of the canonical model in model order; shards carry none.
+    pub fn for_each_canonical_intron(
+        &self,
+        scope: RowScope<'_>,
+        f: impl FnMut(CanonicalIntron) -> Result<(), IngestError>,
+    ) -> Result<(), IngestError> {
+        if matches!(scope, RowScope::Shard(_)) {
+            return Ok(());
+        }
+        self.for_each_json("SELECT record FROM canonical_introns ORDER BY seq", [], f)
+    }
+
+    /// UTRs of the canonical model in model order; shards carry none.
+    pub fn for_each_canonical_utr(
+        &self,
+        scope: RowScope<'_>,
+        f: impl FnMut(CanonicalUtr) -> Result<(), IngestError>,
+    ) -> Result<(), IngestError> {
+        if matches!(scope, RowScope::Shard(_)) {
+            return Ok(());
+        }
+        self.for_each_json("SELECT record FROM canonical_utrs ORDER BY seq", [], f)
+    }
+}
+
+fn json_text<T: Serialize + ?Sized>(value: &T) -> Result<String, IngestError> {
+    serde_json::to_string(value).map_err(|e| IngestError(e.to_string()))
+}
+
+/// SQLite integers are signed; coordinates and counts are stored as `i64`.
+fn u64_at(r: &Row<'_>, idx: usize) -> rusqlite::Result<u64> {
+    r.get::<_, i64>(idx).map(|v| v as u64)
+}
+
+fn gene_from_row(r: &Row<'_>) -> rusqlite::Result<GeneRecord> {
+    Ok(GeneRecord {
+        gene_id: r.get(0)?,
+        gene_name: r.get(1)?,
+        biotype: r.get(2)?,
+        seqid: r.get(3)?,
+        start: u64_at(r, 4)?,
+        end: u64_at(r, 5)?,
+        transcript_count: u64_at(r, 6)?,
+        exon_count: u64_at(r, 7)?,
+        total_exon_span: u64_at(r, 8)?,
+        cds_present: r.get(9)?,
+        sequence_length: u64_at(r, 10)?,
+        strand: r.get(11)?,
+        description: r.get(12)?,
+    })
+}
+
+fn transcript_from_row(r: &Row<'_>) -> rusqlite::Result<TranscriptRecord> {
+    Ok(TranscriptRecord {
+        transcript_id: r.get(0)?,
+        parent_gene_id: r.get(1)?,
+        transcript_type: r.get(2)?,
+        biotype: r.get(3)?,
+        seqid: r.get(4)?,
+        start: u64_at(r, 5)?,
+        end: u64_at(r, 6)?,
+        exon_count: u64_at(r, 7)?,
+        total_exon_span: u64_at(r, 8)?,
+        cds_present: r.get(9)?,
+        sequence_length: u64_at(r, 10)?,
+        spliced_length: r.get::<_, Option<i64>>(11)?.map(|v| v as u64),
+        cds_span_length: r.get::<_, Option<i64>>(12)?.map(|v| v as u64),
+    })
+}
+
+fn exon_from_row(r: &Row<'_>) -> rusqlite::Result<ExonRecord> {
+    Ok(ExonRecord {
+        exon_id: r.get(0)?,
+        transcript_id: r.get(1)?,
+        seqid: r.get(2)?,
+        start: u64_at(r, 3)?,
+        end: u64_at(r, 4)?,
+        exon_length: u64_at(r, 5)?,
+    })
+}
+
+/// Stable-JSON array items written to a scratch file one at a time.
+pub struct ArraySpill {
+    path: PathBuf,
+    out: BufWriter<fs::File>,
+    empty: bool,
+}
+
+impl ArraySpill {
+    pub fn push<T: Serialize>(&mut self, item: &T) -> Result<(), IngestError> {
+        let bytes = canonical::stable_json_bytes(item).map_err(|e| IngestError(e.to_string()))?;
+        if !self.empty {
+            self.out.write_all(b",").map_err(io_err)?;
+        }
+        self.out.write_all(&bytes).map_err(io_err)?;
+        self.empty = false;
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> Result<SpilledArray, IngestError> {
+        self.out.flush().map_err(io_err)?;
+        Ok(SpilledArray { path: self.path })
+    }
+}
+
+pub struct SpilledArray {
+    path: PathBuf,
+}
+
+impl SpilledArray {
+    /// Writes `document` as stable JSON with the array in place of the
+    /// [`SPILLED_ARRAY_SLOT`] string it carries. Stable JSON serializes each
+    /// item independently, so the output matches serializing the whole array.
+    pub fn splice_into<W: Write>(
+        &self,
+        document: &serde_json::Value,
+        out: &mut W,
+    ) -> Result<(), IngestError> {
+        let bytes =
+            canonical::stable_json_bytes(document).map_err(|e| IngestError(e.to_string()))?;
+        let slot =
+            serde_json::to_vec(SPILLED_ARRAY_SLOT).map_err(|e| IngestError(e.to_string()))?;
+        let at = bytes
+            .windows(slot.len())
+            .position(|w| w == slot.as_slice())
+            .ok_or_else(|| IngestError("spilled array slot missing from document".to_string()))?;
+        out.write_all(&bytes[..at]).map_err(io_err)?;
+        out.write_all(b"[").map_err(io_err)?;
+        let mut items = fs::File::open(&self.path).map_err(io_err)?;
+        std::io::copy(&mut items, out).map_err(io_err)?;
+        out.write_all(b"]").map_err(io_err)?;
+        out.write_all(&bytes[at + slot.len()..]).map_err(io_err)?;
+        Ok(())
+    }
+
+    /// SHA-256 of what [`SpilledArray::splice_into`] would write.
+    pub fn splice_sha256(&self, document: &serde_json::Value) -> Result<String, IngestError> {
+        let mut hasher = HashWriter(Sha256::new());
+        self.splice_into(document, &mut hasher)?;
+        Ok(hex::encode(hasher.0.finalize()))
+    }
+}
+
+struct HashWriter(Sha256);
+
+impl Write for HashWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
@@ -1,22 +1,23 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use super::extract::{ExonRecord, GeneRecord, TranscriptRecord};
 use super::fai::ContigStats;
+use super::hashing::hash_file;
+use super::spool::{RowScope, RowSpool};
 use super::IngestError;
-use crate::domain::canonical::{self, sha256_hex};
+use crate::domain::canonical;
 use crate::domain::dataset::manifest::ShardId;
 use crate::domain::dataset::{DatasetId, ShardCatalog, ShardEntry, ShardingPlan};
-use crate::domain::query::SeqId;
+use crate::domain::query::{ContigAlias, SeqId, XREF_ALIAS_NAMESPACE};
 use rusqlite::{params, Connection};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
 use std::path::Path;
 
-pub const SQLITE_SCHEMA_VERSION: i64 = 4;
-pub const SQLITE_SCHEMA_SSOT: &str = include_str!("../../../sql/schema_v4.sql");
+pub const SQLITE_SCHEMA_VERSION: i64 = 5;
+pub const SQLITE_SCHEMA_SSOT: &str = include_str!("../../../sql/schema_v5.sql");
 #[allow(dead_code)] // ATLAS-EXC-0001
 pub const SQLITE_SCHEMA_SSOT_SHA256: &str =
-    "a695a4e39b45e4fd87491dd9a55817142059100480d77b59168db0f5fe0a6901";
+    "e727d49d168f3a3b6246839ac817191f9c02201c52a113ea85382b240e0326c3";
 #[allow(dead_code)] // ATLAS-EXC-0001
 pub const SQLITE_REQUIRED_INDEXES: &[&str] = &[
     "idx_gene_summary_gene_id",
@@ -24,25 +25,38 @@ pub const SQLITE_REQUIRED_INDEXES: &[&str] = &[
     "idx_gene_summary_biotype",
     "idx_gene_summary_cover_region",
     "idx_gene_summary_region",
+    "idx_gene_summary_strand",
     "idx_genes_gene_id",
     "idx_genes_name",
     "idx_genes_biotype",
     "idx_genes_order_page",
+    "idx_genes_strand",
+    "idx_gene_xrefs_gene_id",
     "idx_transcripts_parent_gene",
     "idx_exons_transcript",
+    "idx_cds_segments_transcript",
+    "idx_introns_transcript",
+    "idx_utrs_transcript",
+    "idx_contig_aliases_contig",
 ];
 const INGEST_JOURNAL_MODE: &str = "WAL";
 const INGEST_LOCKING_MODE: &str = "EXCLUSIVE";
 const INGEST_PAGE_SIZE: i64 = 4096;
 const INGEST_MMAP_SIZE: i64 = 268_435_456;
+/// Applied to the writing connection after the schema. The schema's page
+/// cache, in-memory temp store and mmap window tune the artifact for serving;
+/// during ingest they would let memory grow with the artifact, since VACUUM
+/// rewrites the whole database through temp storage.
+const INGEST_CONNECTION_PRAGMAS: &str =
+    "PRAGMA cache_size=-2000; PRAGMA temp_store=FILE; PRAGMA mmap_size=0;";
 
 pub struct WriteSqliteInput<'a> {
     pub path: &'a Path,
     pub dataset: &'a DatasetId,
-    pub genes: &'a [GeneRecord],
-    pub transcripts: &'a [TranscriptRecord],
-    pub exons: &'a [ExonRecord],
+    pub rows: &'a RowSpool,
+    pub scope: RowScope<'a>,
     pub contigs: &'a BTreeMap<String, ContigStats>,
+    pub contig_aliases: &'a [ContigAlias],
     pub gff3_sha256: &'a str,
     pub fasta_sha256: &'a str,
     pub fai_sha256: &'a str,
@@ -70,9 +84,110 @@ pub fn migrate_forward_schema(conn: &Connection, target_version: i64) -> Result<
         .map_err(|e| IngestError(e.to_string()))?;
         let _ = conn.execute("UPDATE atlas_meta SET v='4' WHERE k='schema_version'", []);
     }
+    if current < 5 && target_version >= 5 {
+        // v5 adds gene strand, cross-references and the gene search index; rows from
+        // older artifacts keep the GFF3 "unknown" strand marker and an empty xref table,
+        // and are searchable by symbol only. Introns and UTRs are derived at query
+        // time until the artifact is re-ingested, and the contig alias table stays
+        // empty, so regions must name the stored seqids.
+        let had_search_index = table_exists(conn, "gene_search_fts")?;
+        let had_exon_rtree = table_exists(conn, "exon_rtree")?;
+        for (table, index) in [
+            ("gene_summary", "idx_gene_summary_strand"),
+            ("genes", "idx_genes_strand"),
+        ] {
+            if table_exists(conn, table)? && !column_exists(conn, table, "strand")? {
+                conn.execute_batch(&format!(
+                    "ALTER TABLE {table} ADD COLUMN strand TEXT NOT NULL DEFAULT '.';
+                     CREATE INDEX IF NOT EXISTS {index} ON {table}(strand, gene_id);"
+                ))
+                .map_err(|e| IngestError(e.to_string()))?;
+            }
+        }
+        conn.execute_batch(
+            "
+            CREATE TABLE IF NOT EXISTS gene_xrefs (
+              gene_id TEXT NOT NULL,
+              namespace TEXT NOT NULL,
+              value TEXT NOT NULL,
+              PRIMARY KEY (namespace, value, gene_id)
+            ) WITHOUT ROWID;
+            CREATE INDEX IF NOT EXISTS idx_gene_xrefs_gene_id ON gene_xrefs(gene_id);
+            CREATE TABLE IF NOT EXISTS cds_segments (
+              id INTEGER PRIMARY KEY,
+              cds_id TEXT NOT NULL,
+              transcript_id TEXT NOT NULL,
+              seqid TEXT NOT NULL,
+              start INTEGER NOT NULL,
+              end INTEGER NOT NULL,
+              phase INTEGER
+            ) WITHOUT ROWID;
+            CREATE INDEX IF NOT EXISTS idx_cds_segments_transcript ON cds_segments(transcript_id, start);
+            CREATE TABLE IF NOT EXISTS contig_aliases (
+              alias TEXT PRIMARY KEY,
+              contig TEXT NOT NULL,
+              source TEXT NOT NULL
+            ) WITHOUT ROWID;
+            CREATE INDEX IF NOT EXISTS idx_contig_aliases_contig ON contig_aliases(contig);
+            CREATE VIRTUAL TABLE IF NOT EXISTS exon_rtree USING rtree(exon_rowid, start, end);
+            CREATE VIRTUAL TABLE IF NOT EXISTS gene_search_fts USING fts5(
+              gene_id UNINDEXED, symbol, description, aliases,
+              tokenize = 'unicode61 remove_diacritics 2'
+            );
+            CREATE VIRTUAL TABLE IF NOT EXISTS gene_search_trigram USING fts5(
+              gene_id UNINDEXED, symbol, aliases,
+              tokenize = 'trigram'
+            );
+            DELETE FROM schema_version;
+            INSERT INTO schema_version (version) VALUES (5);
+            PRAGMA user_version=5;
+            ",
+        )
+        .map_err(|e| IngestError(e.to_string()))?;
+        if !had_search_index && table_exists(conn, "gene_summary")? {
+            conn.execute_batch(
+                "
+                INSERT INTO gene_search_fts (rowid, gene_id, symbol, description, aliases)
+                SELECT id, gene_id, name, '', '' FROM gene_summary;
+                INSERT INTO gene_search_trigram (rowid, gene_id, symbol, aliases)
+                SELECT id, gene_id, name, '' FROM gene_summary;
+                ",
+            )
+            .map_err(|e| IngestError(e.to_string()))?;
+        }
+        if !had_exon_rtree && table_exists(conn, "exons")? {
+            conn.execute_batch(
+                "INSERT INTO exon_rtree (exon_rowid, start, end) SELECT id, start, end FROM exons;",
+            )
+            .map_err(|e| IngestError(e.to_string()))?;
+        }
+        let _ = conn.execute("UPDATE atlas_meta SET v='5' WHERE k='schema_version'", []);
+    }
     Ok(target_version.max(current))
 }
 
+fn table_exists(conn: &Connection, table: &str) -> Result<bool, IngestError> {
+    let count: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name=?1",
+            params![table],
+            |r| r.get(0),
+        )
+        .map_err(|e| IngestError(e.to_string()))?;
+    Ok(count > 0)
+}
+
+fn column_exists(conn: &Connection, table: &str, column: &str) -> Result<bool, IngestError> {
+    let count: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM pragma_table_info(?1) WHERE name=?2",
+            params![table, column],
+            |r| r.get(0),
+        )
+        .map_err(|e| IngestError(e.to_string()))?;
+    Ok(count > 0)
+}
+
 #[allow(dead_code)] // ATLAS-EXC-0001
 fn detect_schema_version(conn: &Connection) -> Result<i64, IngestError> {
     let has_schema_table: i64 = conn
@@ -102,10 +217,10 @@ pub fn write_sqlite(input: WriteSqliteInput<'_>) -> Result<(), IngestError> {
     let WriteSqliteInput {
         path,
         dataset,
-        genes,
-        transcripts,
-        exons,
+        rows,
+        scope,
         contigs,
+        contig_aliases,
         gff3_sha256,
         fasta_sha256,
         fai_sha256,
@@ -118,14 +233,16 @@ pub fn write_sqlite(input: WriteSqliteInput<'_>) -> Result<(), IngestError> {
         .map_err(|e| IngestError(e.to_string()))?;
     conn.execute_batch(&format!("PRAGMA user_version={};", SQLITE_SCHEMA_VERSION))
         .map_err(|e| IngestError(e.to_string()))?;
+    conn.execute_batch(INGEST_CONNECTION_PRAGMAS)
+        .map_err(|e| IngestError(e.to_string()))?;
 
     let tx = conn.transaction().map_err(|e| IngestError(e.to_string()))?;
     {
         let mut stmt = tx
             .prepare(
                 "INSERT INTO gene_summary (
-                  id, gene_id, name, name_normalized, biotype, seqid, start, end, transcript_count, exon_count, total_exon_span, cds_present, sequence_length
-                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+                  id, gene_id, name, name_normalized, biotype, seqid, start, end, transcript_count, exon_count, total_exon_span, cds_present, sequence_length, strand
+                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
             )
             .map_err(|e| IngestError(e.to_string()))?;
         let mut tx_stmt = tx
@@ -145,16 +262,17 @@ pub fn write_sqlite(input: WriteSqliteInput<'_>) -> Result<(), IngestError> {
         let mut gene_v2_stmt = tx
             .prepare(
                 "INSERT INTO genes (
-                  id, gene_id, name, name_normalized, biotype, seqid, start, end, transcript_count, exon_count, total_exon_span, cds_present, sequence_length
-                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+                  id, gene_id, name, name_normalized, biotype, seqid, start, end, transcript_count, exon_count, total_exon_span, cds_present, sequence_length, strand
+                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
             )
             .map_err(|e| IngestError(e.to_string()))?;
         let mut rtree_stmt = tx
             .prepare("INSERT INTO gene_summary_rtree (gene_rowid, start, end) VALUES (?1, ?2, ?3)")
             .map_err(|e| IngestError(e.to_string()))?;
 
-        for (idx, g) in genes.iter().enumerate() {
-            let rowid = (idx + 1) as i64;
+        let mut rowid = 0_i64;
+        rows.for_each_gene(scope, |g| {
+            rowid += 1;
             stmt.execute(params![
                 rowid,
                 g.gene_id,
@@ -168,7 +286,8 @@ pub fn write_sqlite(input: WriteSqliteInput<'_>) -> Result<(), IngestError> {
                 g.exon_count as i64,
                 g.total_exon_span as i64,
                 if g.cds_present { 1 } else { 0 },
-                g.sequence_length as i64
+                g.sequence_length as i64,
+                g.strand
             ])
             .map_err(|e| IngestError(e.to_string()))?;
             gene_v2_stmt
@@ -185,13 +304,15 @@ pub fn write_sqlite(input: WriteSqliteInput<'_>) -> Result<(), IngestError> {
                     g.exon_count as i64,
                     g.total_exon_span as i64,
                     if g.cds_present { 1 } else { 0 },
-                    g.sequence_length as i64
+                    g.sequence_length as i64,
+                    g.strand
                 ])
                 .map_err(|e| IngestError(e.to_string()))?;
             rtree_stmt
                 .execute(params![rowid, g.start as f64, g.end as f64])
                 .map_err(|e| IngestError(e.to_string()))?;
-        }
+            Ok(())
+        })?;
         let mut contig_stmt = tx
             .prepare("INSERT INTO contigs (name, length, gc_fraction, n_fraction) VALUES (?1, ?2, ?3, ?4)")
             .map_err(|e| IngestError(e.to_string()))?;
@@ -200,9 +321,18 @@ pub fn write_sqlite(input: WriteSqliteInput<'_>) -> Result<(), IngestError> {
                 .execute(params![name, s.length as i64, s.gc_fraction, s.n_fraction])
                 .map_err(|e| IngestError(e.to_string()))?;
         }
+        let mut alias_stmt = tx
+            .prepare("INSERT INTO contig_aliases (alias, contig, source) VALUES (?1, ?2, ?3)")
+            .map_err(|e| IngestError(e.to_string()))?;
+        for a in contig_aliases {
+            alias_stmt
+                .execute(params![a.alias, a.contig, a.source])
+                .map_err(|e| IngestError(e.to_string()))?;
+        }
 
-        for (idx, txrow) in transcripts.iter().enumerate() {
-            let rowid = (idx + 1) as i64;
+        let mut rowid = 0_i64;
+        rows.for_each_transcript(scope, |txrow| {
+            rowid += 1;
             tx_stmt
                 .execute(params![
                     rowid,
@@ -236,7 +366,8 @@ pub fn write_sqlite(input: WriteSqliteInput<'_>) -> Result<(), IngestError> {
                     txrow.cds_span_length.map(|v| v as i64),
                 ])
                 .map_err(|e| IngestError(e.to_string()))?;
-        }
+            Ok(())
+        })?;
 
         let mut exon_stmt = tx
             .prepare(
@@ -246,8 +377,12 @@ pub fn write_sqlite(input: WriteSqliteInput<'_>) -> Result<(), IngestError> {
         let mut tx_exon_map_stmt = tx
             .prepare("INSERT OR IGNORE INTO transcript_exon_map (transcript_id, exon_id) VALUES (?1, ?2)")
             .map_err(|e| IngestError(e.to_string()))?;
-        for (idx, ex) in exons.iter().enumerate() {
-            let rowid = (idx + 1) as i64;
+        let mut exon_rtree_stmt = tx
+            .prepare("INSERT INTO exon_rtree (exon_rowid, start, end) VALUES (?1, ?2, ?3)")
+            .map_err(|e| IngestError(e.to_string()))?;
+        let mut rowid = 0_i64;
+        rows.for_each_exon(scope, |ex| {
+            rowid += 1;
             exon_stmt
                 .execute(params![
                     rowid,
@@ -262,7 +397,118 @@ pub fn write_sqlite(input: WriteSqliteInput<'_>) -> Result<(), IngestError> {
             tx_exon_map_stmt
                 .execute(params![ex.transcript_id, ex.exon_id])
                 .map_err(|e| IngestError(e.to_string()))?;
-        }
+            exon_rtree_stmt
+                .execute(params![rowid, ex.start as f64, ex.end as f64])
+                .map_err(|e| IngestError(e.to_string()))?;
+            Ok(())
+        })?;
+
+        let mut cds_stmt = tx
+            .prepare(
+                "INSERT INTO cds_segments (id, cds_id, transcript_id, seqid, start, end, phase) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            )
+            .map_err(|e| IngestError(e.to_string()))?;
+        let mut rowid = 0_i64;
+        rows.for_each_canonical_cds(scope, |cds| {
+            rowid += 1;
+            cds_stmt
+                .execute(params![
+                    rowid,
+                    cds.cds_id,
+                    cds.transcript_id,
+                    cds.seqid,
+                    cds.start as i64,
+                    cds.end as i64,
+                    cds.phase.as_deref().and_then(|p| p.parse::<i64>().ok())
+                ])
+                .map_err(|e| IngestError(e.to_string()))?;
+            Ok(())
+        })?;
+
+        let mut intron_stmt = tx
+            .prepare(
+                "INSERT INTO introns (id, transcript_id, seqid, start, end, intron_rank) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            )
+            .map_err(|e| IngestError(e.to_string()))?;
+        let mut rowid = 0_i64;
+        rows.for_each_canonical_intron(scope, |intron| {
+            rowid += 1;
+            intron_stmt
+                .execute(params![
+                    rowid,
+                    intron.transcript_id,
+                    intron.seqid,
+                    intron.start as i64,
+                    intron.end as i64,
+                    intron.intron_rank as i64
+                ])
+                .map_err(|e| IngestError(e.to_string()))?;
+            Ok(())
+        })?;
+
+        let mut utr_stmt = tx
+            .prepare(
+                "INSERT INTO utrs (id, transcript_id, seqid, kind, start, end) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            )
+            .map_err(|e| IngestError(e.to_string()))?;
+        let mut rowid = 0_i64;
+        rows.for_each_canonical_utr(scope, |utr| {
+            rowid += 1;
+            utr_stmt
+                .execute(params![
+                    rowid,
+                    utr.transcript_id,
+                    utr.seqid,
+                    utr.kind.as_str(),
+                    utr.start as i64,
+                    utr.end as i64
+                ])
+                .map_err(|e| IngestError(e.to_string()))?;
+            Ok(())
+        })?;
+
+        let mut xref_stmt = tx
+            .prepare(
+                "INSERT OR IGNORE INTO gene_xrefs (gene_id, namespace, value) VALUES (?1, ?2, ?3)",
+            )
+            .map_err(|e| IngestError(e.to_string()))?;
+        rows.for_each_xref(scope, |xref| {
+            xref_stmt
+                .execute(params![xref.gene_id, xref.namespace, xref.value])
+                .map_err(|e| IngestError(e.to_string()))?;
+            Ok(())
+        })?;
+
+        let mut search_stmt = tx
+            .prepare(
+                "INSERT INTO gene_search_fts (rowid, gene_id, symbol, description, aliases) VALUES (?1, ?2, ?3, ?4, ?5)",
+            )
+            .map_err(|e| IngestError(e.to_string()))?;
+        let mut trigram_stmt = tx
+            .prepare(
+                "INSERT INTO gene_search_trigram (rowid, gene_id, symbol, aliases) VALUES (?1, ?2, ?3, ?4)",
+            )
+            .map_err(|e| IngestError(e.to_string()))?;
+        let mut rowid = 0_i64;
+        rows.for_each_gene(scope, |g| {
+            rowid += 1;
+            let aliases = rows
+                .gene_xref_values(&g.gene_id, XREF_ALIAS_NAMESPACE)?
+                .join(" ");
+            search_stmt
+                .execute(params![
+                    rowid,
+                    g.gene_id,
+                    g.gene_name,
+                    g.description.as_deref().unwrap_or(""),
+                    aliases
+                ])
+                .map_err(|e| IngestError(e.to_string()))?;
+            trigram_stmt
+                .execute(params![rowid, g.gene_id, g.gene_name, aliases])
+                .map_err(|e| IngestError(e.to_string()))?;
+            Ok(())
+        })?;
 
         tx.execute(
             "INSERT INTO atlas_meta (k, v) VALUES ('schema_version', ?1)",
@@ -404,13 +650,12 @@ fn assert_region_query_plan_uses_rtree(conn: &Connection) -> Result<(), IngestEr
 pub fn write_sharded_sqlite_catalog(
     derived_dir: &Path,
     dataset: &DatasetId,
-    genes: &[GeneRecord],
-    transcripts: &[TranscriptRecord],
+    rows: &RowSpool,
     sharding_plan: ShardingPlan,
     shard_partitions: usize,
     max_shards: usize,
 ) -> Result<(std::path::PathBuf, ShardCatalog), IngestError> {
-    let mut buckets: BTreeMap<String, Vec<GeneRecord>> = BTreeMap::new();
+    let mut buckets: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
     match sharding_plan {
         ShardingPlan::None => {
             return Err(IngestError(
@@ -418,22 +663,19 @@ pub fn write_sharded_sqlite_catalog(
             ))
         }
         ShardingPlan::Contig => {
-            if shard_partitions == 0 {
-                for g in genes {
-                    buckets.entry(g.seqid.clone()).or_default().push(g.clone());
-                }
-            } else {
-                for g in genes {
+            rows.for_each_gene(RowScope::Dataset, |g| {
+                let bucket = if shard_partitions == 0 {
+                    g.seqid.clone()
+                } else {
                     let shard = (canonical::stable_hash_hex(g.seqid.as_bytes())
                         .bytes()
                         .fold(0_u64, |acc, b| acc.wrapping_add(b as u64))
                         % shard_partitions as u64) as usize;
-                    buckets
-                        .entry(format!("p{:03}", shard))
-                        .or_default()
-                        .push(g.clone());
-                }
-            }
+                    format!("p{:03}", shard)
+                };
+                buckets.entry(bucket).or_default().insert(g.seqid);
+                Ok(())
+            })?;
         }
         ShardingPlan::RegionGrid => {
             return Err(IngestError(
@@ -451,36 +693,18 @@ pub fn write_sharded_sqlite_catalog(
     }
 
     let mut shards = Vec::new();
-    for (bucket, mut rows) in buckets {
-        rows.sort_by(|a, b| {
-            a.seqid
-                .cmp(&b.seqid)
-                .then(a.start.cmp(&b.start))
-                .then(a.end.cmp(&b.end))
-                .then(a.gene_id.cmp(&b.gene_id))
-        });
+    for (bucket, seqids) in buckets {
         let file_name = format!("gene_summary.{bucket}.sqlite");
         let sqlite_path = derived_dir.join(&file_name);
-        let seqids = {
-            let mut s: Vec<String> = rows.iter().map(|g| g.seqid.clone()).collect();
-            s.sort();
-            s.dedup();
-            s
-        };
-        let tx_rows: Vec<TranscriptRecord> = transcripts
-            .iter()
-            .filter(|tx| seqids.contains(&tx.seqid))
-            .cloned()
-            .collect();
-        let ex_rows: Vec<ExonRecord> = Vec::new();
+        let seqids = seqids.into_iter().collect::<Vec<_>>();
         let empty_contigs = BTreeMap::new();
         write_sqlite(WriteSqliteInput {
             path: &sqlite_path,
             dataset,
-            genes: &rows,
-            transcripts: &tx_rows,
-            exons: &ex_rows,
+            rows,
+            scope: RowScope::Shard(&seqids),
             contigs: &empty_contigs,
+            contig_aliases: &[],
             gff3_sha256: "",
             fasta_sha256: "",
             fai_sha256: "",
@@ -506,7 +730,7 @@ pub fn write_sharded_sqlite_catalog(
                 .map(|s| SeqId::parse(s).map_err(|e| IngestError(e.to_string())))
                 .collect::<Result<Vec<_>, _>>()?,
             file_name,
-            sha256_hex(&fs::read(&sqlite_path).map_err(|e| IngestError(e.to_string()))?),
+            hash_file(&sqlite_path)?,
         ));
     }
     shards.sort();
@@ -607,6 +831,43 @@ mod tests {
         assert_eq!(v, 4);
     }
 
+    #[test]
+    fn forward_migration_to_v5_adds_strand_column_with_unknown_default() {
+        let conn = Connection::open_in_memory().expect("conn");
+        conn.execute_batch(
+            "
+            PRAGMA user_version=4;
+            CREATE TABLE schema_version (version INTEGER PRIMARY KEY) WITHOUT ROWID;
+            INSERT INTO schema_version (version) VALUES (4);
+            CREATE TABLE gene_summary (id INTEGER PRIMARY KEY, gene_id TEXT NOT NULL, name TEXT NOT NULL);
+            INSERT INTO gene_summary (id, gene_id, name) VALUES (1, 'g1', 'BRCA1');
+            ",
+        )
+        .expect("v4 schema");
+        assert_eq!(migrate_forward_schema(&conn, 5).expect("migrate"), 5);
+        let strand: String = conn
+            .query_row("SELECT strand FROM gene_summary WHERE id=1", [], |r| {
+                r.get(0)
+            })
+            .expect("strand column");
+        assert_eq!(strand, ".");
+        assert!(column_exists(&conn, "gene_summary", "strand").expect("column lookup"));
+        assert_eq!(detect_schema_version(&conn).expect("version"), 5);
+        let searchable: String = conn
+            .query_row(
+                "SELECT gene_id FROM gene_search_fts WHERE gene_search_fts MATCH 'brca1'",
+                [],
+                |r| r.get(0),
+            )
+            .expect("backfilled search index");
+        assert_eq!(searchable, "g1");
+        migrate_forward_schema(&conn, 5).expect("migration is idempotent");
+        let indexed: i64 = conn
+            .query_row("SELECT COUNT(*) FROM gene_search_trigram", [], |r| r.get(0))
+            .expect("trigram rows");
+        assert_eq!(indexed, 1);
+    }
+
     #[test]
     fn schema_ssot_hash_is_stable() {
         assert_eq!(
@@ -655,7 +916,7 @@ mod tests {
         let digest = sha256_hex(rows.join("\n").as_bytes());
         assert_eq!(
             digest,
-            "996a3e9bdbb5c4e65e9ef3f659a94bfe9bf4282cbd042934e6a60193cf3af41a"
+            "86cb2d789cf71c8aeeecb97b449197c3aafa4a6a0692aabd4e39c65ca9103c01"
         );
     }
 }
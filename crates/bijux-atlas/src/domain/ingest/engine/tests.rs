@@ -99,6 +99,18 @@ fn sqlite_logical_fingerprint(path: &Path) -> String {
             "SELECT transcript_id, exon_id FROM transcript_exon_map \
              ORDER BY transcript_id, exon_id",
         ),
+        (
+            "introns",
+            "SELECT id, transcript_id, seqid, start, end, intron_rank FROM introns ORDER BY id",
+        ),
+        (
+            "utrs",
+            "SELECT id, transcript_id, seqid, kind, start, end FROM utrs ORDER BY id",
+        ),
+        (
+            "gene_xrefs",
+            "SELECT gene_id, namespace, value FROM gene_xrefs ORDER BY gene_id, namespace, value",
+        ),
         (
             "gene_summary_rtree",
             "SELECT gene_rowid, start, end FROM gene_summary_rtree ORDER BY gene_rowid",
@@ -160,6 +172,8 @@ fn opts(root: &Path, strictness: StrictnessMode) -> IngestOptions {
         normalized_replay_mode: false,
         prod_mode: false,
         timestamp_policy: TimestampPolicy::DeterministicZero,
+        liftover_chain: None,
+        chrom_alias_path: None,
     }
 }
 
@@ -174,11 +188,377 @@ fn ingest_is_deterministic_and_matches_contract() {
         run1.manifest.checksums.sqlite_sha256,
         run2.manifest.checksums.sqlite_sha256
     );
+    assert_eq!(
+        run1.manifest.evidence_bundle_sha256,
+        run2.manifest.evidence_bundle_sha256
+    );
     assert_eq!(run1.manifest.stats.gene_count, 2);
     assert_eq!(run1.manifest.stats.transcript_count, 3);
     assert!(run1.release_gene_index_path.exists());
 }
 
+#[test]
+fn liftover_chain_is_ingested_with_dataset_seqids_and_inventoried() {
+    let root = tempdir().expect("tempdir");
+    let chain_path = root.path().join("GRCh38ToGRCh37.over.chain");
+    std::fs::write(
+        &chain_path,
+        "chain 100 1 60 + 0 60 chr1 70 + 5 65 1\n60\n\nchain 50 chr2 44 + 0 44 chr2 44 - 0 44 2\n44\n",
+    )
+    .expect("write chain");
+    let mut options = opts(root.path(), StrictnessMode::Strict);
+    options.seqid_policy =
+        SeqidNormalizationPolicy::from_aliases([("1".to_string(), "chr1".to_string())].into());
+    options.liftover_chain = Some(LiftoverChainInput {
+        path: chain_path,
+        target_assembly: crate::domain::dataset::Assembly::parse("GRCh37").expect("assembly"),
+    });
+    let run = ingest_dataset(&options).expect("ingest with liftover chain");
+
+    let paths = crate::domain::dataset::artifact_paths(root.path(), &options.dataset);
+    let set: crate::domain::query::LiftoverChainSet =
+        serde_json::from_slice(&std::fs::read(&paths.liftover_chains).expect("chains"))
+            .expect("chain set json");
+    let liftover = crate::domain::query::Liftover::new(set);
+    let lifted = liftover.lift("chr1", 1, 10).expect("chr1 lifts");
+    assert_eq!((lifted.start, lifted.end), (6, 15));
+    assert_eq!(liftover.lift("chr2", 1, 4).expect("chr2 lifts").start, 41);
+
+    let inventory: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(&paths.artifact_inventory).expect("inventory"))
+            .expect("inventory json");
+    assert!(inventory["items"]
+        .as_array()
+        .expect("items")
+        .iter()
+        .any(|item| item["role"] == "liftover_chains"));
+    assert!(run.manifest_path.exists());
+}
+
+#[test]
+fn malformed_liftover_chain_fails_ingest() {
+    let root = tempdir().expect("tempdir");
+    let chain_path = root.path().join("broken.chain");
+    std::fs::write(&chain_path, "chain 1 chr1 60 + 0 60 chr1 60 + 0 60 1\n50\n").expect("write");
+    let mut options = opts(root.path(), StrictnessMode::Strict);
+    options.liftover_chain = Some(LiftoverChainInput {
+        path: chain_path,
+        target_assembly: crate::domain::dataset::Assembly::parse("GRCh37").expect("assembly"),
+    });
+    let err = ingest_dataset(&options).expect_err("chain blocks do not cover header span");
+    assert!(err.0.contains("liftover chain invalid"), "{}", err.0);
+}
+
+#[test]
+fn chrom_alias_file_and_derived_spellings_populate_contig_aliases() {
+    let root = tempdir().expect("tempdir");
+    let alias_path = root.path().join("hg38.chromAlias.txt");
+    std::fs::write(
+        &alias_path,
+        "# ucsc\tensembl\trefseq\nchr1\t1\tNC_000001.11\nchrUn_x\tUn_x\tNT_187361.1\n",
+    )
+    .expect("write chromAlias");
+    let mut options = opts(root.path(), StrictnessMode::Strict);
+    options.chrom_alias_path = Some(alias_path);
+    let run = ingest_dataset(&options).expect("ingest with chromAlias");
+
+    let conn = rusqlite::Connection::open(&run.sqlite_path).expect("open sqlite");
+    let aliases = crate::domain::query::query_contig_aliases(&conn).expect("aliases");
+    let refseq = aliases.resolve("NC_000001.11");
+    assert_eq!(
+        (refseq.seqid.as_str(), refseq.alias_applied),
+        ("chr1", true)
+    );
+    assert_eq!(
+        aliases.resolve("2").seqid,
+        "chr2",
+        "chr-prefix spelling is derived"
+    );
+    assert!(!aliases.resolve("chr1").alias_applied);
+    assert!(
+        !aliases.resolve("Un_x").alias_applied,
+        "rows naming no contig are skipped"
+    );
+    let source: String = conn
+        .query_row(
+            "SELECT source FROM contig_aliases WHERE alias = '1'",
+            [],
+            |row| row.get(0),
+        )
+        .expect("alias row");
+    assert_eq!(source, "ensembl");
+}
+
+#[test]
+fn chrom_alias_file_mapping_one_name_to_two_contigs_fails_ingest() {
+    let root = tempdir().expect("tempdir");
+    let alias_path = root.path().join("conflict.chromAlias.txt");
+    std::fs::write(&alias_path, "chr1\tshared\nchr2\tshared\n").expect("write chromAlias");
+    let mut options = opts(root.path(), StrictnessMode::Strict);
+    options.chrom_alias_path = Some(alias_path);
+    let err = ingest_dataset(&options).expect_err("conflicting alias");
+    assert!(err.0.contains("CHROM_ALIAS_CONFLICT"), "{}", err.0);
+}
+
+/// Writes `genes` single-transcript genes on one contig, streaming to disk so
+/// building large inputs does not itself raise the test's peak memory.
+fn write_synthetic_inputs(dir: &Path, genes: u64) -> (PathBuf, PathBuf, PathBuf) {
+    use std::io::Write;
+    let contig_len = genes * 1_020 + 1_020;
+    let gff3 = dir.join("genes.gff3");
+    let fasta = dir.join("genome.fa");
+    let fai = dir.join("genome.fa.fai");
+    let mut gff = std::io::BufWriter::new(std::fs::File::create(&gff3).expect("create gff3"));
+    writeln!(gff, "##gff-version 3").expect("write gff3");
+    for i in 0..genes {
+        let start = 1 + i * 1_000;
+        let end = start + 899;
+        write!(
+            gff,
+            "chr1\tsrc\tgene\t{start}\t{end}\t.\t+\t.\tID=g{i};Name=G{i};biotype=protein_coding\n\
+chr1\tsrc\tmRNA\t{start}\t{end}\t.\t+\t.\tID=t{i};Parent=g{i}\n\
+chr1\tsrc\texon\t{start}\t{}\t.\t+\t.\tID=e{i}a;Parent=t{i}\n\
+chr1\tsrc\texon\t{}\t{end}\t.\t+\t.\tID=e{i}b;Parent=t{i}\n",
+            start + 99,
+            start + 500
+        )
+        .expect("write gff3");
+    }
+    gff.flush().expect("flush gff3");
+    let mut sequence =
+        std::io::BufWriter::new(std::fs::File::create(&fasta).expect("create fasta"));
+    writeln!(sequence, ">chr1").expect("write fasta");
+    let line = "A".repeat(60);
+    for _ in 0..contig_len / 60 {
+        writeln!(sequence, "{line}").expect("write fasta");
+    }
+    sequence.flush().expect("flush fasta");
+    std::fs::write(&fai, format!("chr1\t{contig_len}\t6\t60\t61\n")).expect("write fai");
+    (gff3, fasta, fai)
+}
+
+#[test]
+fn streaming_ingest_resolves_children_listed_away_from_their_gene() {
+    let inputs = tempdir().expect("inputs");
+    let (gff3, fasta, fai) = write_synthetic_inputs(inputs.path(), 50);
+    let ordered = std::fs::read_to_string(&gff3).expect("read gff3");
+    // Every exon first, then every transcript, then every gene.
+    let mut shuffled = String::from("##gff-version 3\n");
+    for feature in ["\texon\t", "\tmRNA\t", "\tgene\t"] {
+        for line in ordered.lines().filter(|line| line.contains(feature)) {
+            shuffled.push_str(line);
+            shuffled.push('\n');
+        }
+    }
+    let shuffled_gff3 = inputs.path().join("shuffled.gff3");
+    std::fs::write(&shuffled_gff3, shuffled).expect("write shuffled gff3");
+
+    let mut runs = Vec::new();
+    for path in [gff3, shuffled_gff3] {
+        let root = tempdir().expect("root");
+        let mut o = opts(root.path(), StrictnessMode::Strict);
+        o.gff3_path = path;
+        o.fasta_path = fasta.clone();
+        o.fai_path = fai.clone();
+        runs.push(ingest_dataset(&o).expect("synthetic ingest"));
+    }
+    let (ordered, shuffled) = (&runs[0], &runs[1]);
+    assert_eq!(shuffled.manifest.stats, ordered.manifest.stats);
+    assert!(shuffled.anomaly_report.missing_parents.is_empty());
+    assert_eq!(
+        shuffled.manifest.dataset_signature_sha256,
+        ordered.manifest.dataset_signature_sha256
+    );
+    assert_eq!(
+        shuffled.manifest.canonical_query_semantic_sha256,
+        ordered.manifest.canonical_query_semantic_sha256
+    );
+}
+
+/// Child half of `streaming_ingest_peak_memory_stays_flat_as_input_grows`:
+/// ingests `ATLAS_PEAK_RSS_PROBE_GENES` synthetic genes in a fresh process
+/// and prints its peak RSS, so the figure belongs to this ingest alone.
+#[test]
+#[ignore = "spawned by streaming_ingest_peak_memory_stays_flat_as_input_grows"]
+fn peak_rss_probe() {
+    let genes = std::env::var("ATLAS_PEAK_RSS_PROBE_GENES")
+        .expect("probe gene count")
+        .parse::<u64>()
+        .expect("numeric probe gene count");
+    let inputs = tempdir().expect("inputs");
+    let (gff3, fasta, fai) = write_synthetic_inputs(inputs.path(), genes);
+    let root = tempdir().expect("root");
+    let mut o = opts(root.path(), StrictnessMode::Strict);
+    o.gff3_path = gff3;
+    o.fasta_path = fasta;
+    o.fai_path = fai;
+    let run = ingest_dataset(&o).expect("synthetic ingest");
+    assert_eq!(run.manifest.stats.gene_count, genes);
+    let peak = super::resources::peak_rss_bytes().expect("peak RSS");
+    println!("PEAK_RSS_BYTES={peak}");
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn streaming_ingest_peak_memory_stays_flat_as_input_grows() {
+    let peak_rss = |genes: u64| -> u64 {
+        let output = std::process::Command::new(std::env::current_exe().expect("test binary"))
+            .args([
+                "--exact",
+                "domain::ingest::engine::tests::peak_rss_probe",
+                "--ignored",
+                "--nocapture",
+                "--test-threads=1",
+            ])
+            .env("ATLAS_PEAK_RSS_PROBE_GENES", genes.to_string())
+            .output()
+            .expect("spawn probe");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(output.status.success(), "probe failed: {stdout}");
+        stdout
+            .lines()
+            .find_map(|line| line.split("PEAK_RSS_BYTES=").nth(1))
+            .expect("probe reports peak RSS")
+            .trim()
+            .parse()
+            .expect("numeric peak RSS")
+    };
+    let small = peak_rss(1_000);
+    let large = peak_rss(8_000);
+    assert!(
+        large < small + small / 5,
+        "peak RSS grew with input: {small} bytes for 1000 genes, {large} bytes for 8000 genes"
+    );
+}
+
+#[test]
+fn streaming_ingest_keeps_gene_blocks_bounded_as_input_grows() {
+    let mut block_maxima = Vec::new();
+    for genes in [10_u64, 400] {
+        let inputs = tempdir().expect("inputs");
+        let (gff3, fasta, fai) = write_synthetic_inputs(inputs.path(), genes);
+        let root = tempdir().expect("root");
+        let mut o = opts(root.path(), StrictnessMode::Strict);
+        o.gff3_path = gff3;
+        o.fasta_path = fasta;
+        o.fai_path = fai;
+        let run = ingest_dataset(&o).expect("synthetic ingest");
+        assert_eq!(run.manifest.stats.gene_count, genes);
+        assert_eq!(run.resource_usage.gene_blocks, genes);
+
+        let qc: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(&run.qc_report_path).expect("read qc"))
+                .expect("parse qc");
+        assert_eq!(qc["streaming"]["gene_blocks"], serde_json::json!(genes));
+        let resources_path = run.qc_report_path.with_file_name("ingest_resources.json");
+        let resources: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(resources_path).expect("read resources"))
+                .expect("parse resources");
+        assert_eq!(
+            qc["resources"]["peak_rss_bytes"],
+            resources["resources"]["peak_rss_bytes"]
+        );
+        if cfg!(target_os = "linux") {
+            let peak_rss = qc["resources"]["peak_rss_bytes"]
+                .as_u64()
+                .expect("qc report records peak RSS");
+            assert!(peak_rss > 0);
+            assert_eq!(run.resource_usage.peak_rss_bytes, Some(peak_rss));
+        }
+        block_maxima.push(run.resource_usage.max_gene_block_records);
+    }
+    assert_eq!(block_maxima, vec![4, 4]);
+}
+
+#[test]
+fn compressed_inputs_ingest_identically_and_record_both_digests() {
+    use std::io::Write;
+
+    let inputs = tempdir().expect("inputs");
+    let (gff3, fasta, fai) = write_synthetic_inputs(inputs.path(), 12);
+    let gff3_plain = std::fs::read(&gff3).expect("read gff3");
+    let fasta_plain = std::fs::read(&fasta).expect("read fasta");
+
+    let root = tempdir().expect("plain root");
+    let mut o = opts(root.path(), StrictnessMode::Strict);
+    o.gff3_path = gff3.clone();
+    o.fasta_path = fasta.clone();
+    o.fai_path = fai.clone();
+    let plain = ingest_dataset(&o).expect("plain ingest");
+    assert_eq!(
+        plain.manifest.input_hashes.gff3_decompressed_sha256,
+        plain.manifest.input_hashes.gff3_sha256
+    );
+
+    let gzip_gff3 = inputs.path().join("genes.gff3.gz");
+    let mut enc = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    enc.write_all(&gff3_plain).expect("gzip gff3");
+    std::fs::write(&gzip_gff3, enc.finish().expect("finish gzip")).expect("write gzip");
+    let bgzf_fasta = inputs.path().join("genome.fa.gz");
+    let mut bgzf = Vec::new();
+    for chunk in fasta_plain.chunks(4_096) {
+        let mut block = flate2::GzBuilder::new()
+            .extra(vec![b'B', b'C', 2, 0, 0, 0])
+            .write(Vec::new(), flate2::Compression::default());
+        block.write_all(chunk).expect("bgzf block");
+        let mut block = block.finish().expect("finish bgzf block");
+        let bsize = u16::try_from(block.len() - 1).expect("bgzf block size");
+        block[16..18].copy_from_slice(&bsize.to_le_bytes());
+        bgzf.extend(block);
+    }
+    std::fs::write(&bgzf_fasta, bgzf).expect("write bgzf");
+    let zstd_gff3 = inputs.path().join("genes.gff3.zst");
+    std::fs::write(
+        &zstd_gff3,
+        zstd::stream::encode_all(&gff3_plain[..], 3).expect("zstd gff3"),
+    )
+    .expect("write zstd");
+
+    for compressed_gff3 in [gzip_gff3, zstd_gff3] {
+        let root = tempdir().expect("compressed root");
+        let mut o = opts(root.path(), StrictnessMode::Strict);
+        o.gff3_path = compressed_gff3;
+        o.fasta_path = bgzf_fasta.clone();
+        o.fai_path = fai.clone();
+        let run = ingest_dataset(&o).expect("compressed ingest");
+        assert_eq!(
+            run.manifest.dataset_signature_sha256,
+            plain.manifest.dataset_signature_sha256
+        );
+        let hashes = &run.manifest.input_hashes;
+        assert_ne!(hashes.gff3_sha256, hashes.gff3_decompressed_sha256);
+        assert_ne!(hashes.fasta_sha256, hashes.fasta_decompressed_sha256);
+        assert_eq!(hashes.gff3_decompressed_sha256, sha256_hex(&gff3_plain));
+        assert_eq!(hashes.fasta_decompressed_sha256, sha256_hex(&fasta_plain));
+        let paths = crate::domain::dataset::artifact_paths(root.path(), &o.dataset);
+        let gzi = std::fs::read(&paths.fasta_gzi).expect("gzi is written beside the BGZF FASTA");
+        assert_eq!(run.manifest.checksums.fasta_gzi_sha256, sha256_hex(&gzi));
+        assert_eq!(
+            read_decoded_window(&paths.fasta, 5_000, 64).expect("indexed window"),
+            fasta_plain[5_000..5_064]
+        );
+    }
+
+    // Plain gzip cannot seek, so it is stored decoded and needs no index.
+    let gzip_fasta = inputs.path().join("genome.fa.plain.gz");
+    let mut enc = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    enc.write_all(&fasta_plain).expect("gzip fasta");
+    std::fs::write(&gzip_fasta, enc.finish().expect("finish gzip")).expect("write gzip");
+    let root = tempdir().expect("gzip fasta root");
+    let mut o = opts(root.path(), StrictnessMode::Strict);
+    o.gff3_path = gff3;
+    o.fasta_path = gzip_fasta;
+    o.fai_path = fai.clone();
+    let run = ingest_dataset(&o).expect("gzip fasta ingest");
+    let paths = crate::domain::dataset::artifact_paths(root.path(), &o.dataset);
+    assert_eq!(
+        std::fs::read(&paths.fasta).expect("stored fasta"),
+        fasta_plain
+    );
+    assert!(!paths.fasta_gzi.exists());
+    assert!(run.manifest.checksums.fasta_gzi_sha256.is_empty());
+}
+
 #[test]
 fn deterministic_across_parallelism_settings() {
     let root = tempdir().expect("tempdir");
@@ -313,6 +693,175 @@ fn normalized_replay_matches_db_content_counts() {
     assert_eq!(replay.exons as i64, exon_count);
 }
 
+#[test]
+fn dbxref_and_alias_attributes_populate_gene_xrefs_and_are_queryable() {
+    use crate::domain::query::{
+        query_genes, GeneFields, GeneFilter, GeneQueryRequest, QueryLimits,
+    };
+
+    let inputs = tempdir().expect("inputs");
+    let (gff3, fasta, fai) = write_synthetic_inputs(inputs.path(), 3);
+    let gff = std::fs::read_to_string(&gff3).expect("read gff3");
+    std::fs::write(
+        &gff3,
+        gff.replace(
+            "ID=g1;Name=G1;",
+            "ID=g1;Name=G1;Dbxref=GeneID:672,HGNC:HGNC:1100;Ontology_term=GO:0005515;Alias=BRCC1,RNF53;",
+        ),
+    )
+    .expect("write gff3");
+    let root = tempdir().expect("root");
+    let mut o = opts(root.path(), StrictnessMode::Strict);
+    o.gff3_path = gff3;
+    o.fasta_path = fasta;
+    o.fai_path = fai;
+    let run = ingest_dataset(&o).expect("ingest");
+
+    let conn = rusqlite::Connection::open(&run.sqlite_path).expect("open sqlite");
+    let mut stmt = conn
+        .prepare("SELECT gene_id, namespace, value FROM gene_xrefs ORDER BY namespace, value")
+        .expect("prepare");
+    let rows = stmt
+        .query_map([], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)))
+        .expect("query")
+        .collect::<Result<Vec<(String, String, String)>, _>>()
+        .expect("rows");
+    let expected = [
+        ("Alias", "BRCC1"),
+        ("Alias", "RNF53"),
+        ("GO", "0005515"),
+        ("GeneID", "672"),
+        ("HGNC", "1100"),
+    ];
+    assert_eq!(rows.len(), expected.len());
+    for ((gene_id, namespace, value), (ns, v)) in rows.iter().zip(expected) {
+        assert_eq!(gene_id, "g1");
+        assert_eq!((namespace.as_str(), value.as_str()), (ns, v));
+    }
+
+    let lookup = |filter: GeneFilter| {
+        let req = GeneQueryRequest {
+            fields: GeneFields::default(),
+            filter,
+            limit: 10,
+            cursor: None,
+            dataset_key: None,
+            allow_full_scan: false,
+        };
+        query_genes(&conn, &req, &QueryLimits::default(), b"s")
+            .expect("query")
+            .rows
+            .into_iter()
+            .map(|r| r.gene_id)
+            .collect::<Vec<_>>()
+    };
+    for xref in ["HGNC:1100", "HGNC:HGNC:1100", "GeneID:672"] {
+        let found = lookup(GeneFilter {
+            xref: Some(xref.to_string()),
+            ..Default::default()
+        });
+        assert_eq!(found, vec!["g1".to_string()], "xref={xref}");
+    }
+    let by_alias = lookup(GeneFilter {
+        alias: Some("RNF53".to_string()),
+        ..Default::default()
+    });
+    assert_eq!(by_alias, vec!["g1".to_string()]);
+    assert!(lookup(GeneFilter {
+        xref: Some("HGNC:9999".to_string()),
+        ..Default::default()
+    })
+    .is_empty());
+}
+
+#[test]
+fn gene_search_ranks_exact_prefix_token_and_fuzzy_hits() {
+    use crate::domain::query::{
+        plan_gene_search, search_genes, GeneSearchRequest, PlanNode, QueryClass, QueryLimits,
+        SearchMatchKind,
+    };
+
+    let inputs = tempdir().expect("inputs");
+    let (gff3, fasta, fai) = write_synthetic_inputs(inputs.path(), 5);
+    let gff = std::fs::read_to_string(&gff3)
+        .expect("read gff3")
+        .replace(
+            "ID=g0;Name=G0;",
+            "ID=g0;Name=BRCA1;description=breast cancer type 1 susceptibility protein;Alias=RNF53;",
+        )
+        .replace("ID=g1;Name=G1;", "ID=g1;Name=BRCA1P1;")
+        .replace(
+            "ID=g2;Name=G2;",
+            "ID=g2;Name=TP53;description=tumor protein p53;",
+        )
+        .replace(
+            "ID=g3;Name=G3;",
+            "ID=g3;Name=NBR2;description=neighbor of BRCA1 lncRNA 2;",
+        );
+    std::fs::write(&gff3, gff).expect("write gff3");
+    let root = tempdir().expect("root");
+    let mut o = opts(root.path(), StrictnessMode::Strict);
+    o.gff3_path = gff3;
+    o.fasta_path = fasta;
+    o.fai_path = fai;
+    let run = ingest_dataset(&o).expect("ingest");
+    let conn = rusqlite::Connection::open(&run.sqlite_path).expect("open sqlite");
+
+    let limits = QueryLimits::default();
+    let search = |q: &str| {
+        let req = GeneSearchRequest {
+            q: q.to_string(),
+            limit: 10,
+            dataset_key: None,
+        };
+        search_genes(&conn, &req, &limits)
+            .expect("search")
+            .hits
+            .into_iter()
+            .map(|h| (h.gene_id, h.match_kind))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        search("brca1"),
+        vec![
+            ("g0".to_string(), SearchMatchKind::Exact),
+            ("g1".to_string(), SearchMatchKind::Prefix),
+            ("g3".to_string(), SearchMatchKind::Token),
+        ]
+    );
+    assert_eq!(
+        search("RNF53"),
+        vec![("g0".to_string(), SearchMatchKind::Exact)]
+    );
+    assert_eq!(
+        search("susceptibility"),
+        vec![("g0".to_string(), SearchMatchKind::Token)]
+    );
+    assert_eq!(
+        search("tpp53"),
+        vec![("g2".to_string(), SearchMatchKind::Fuzzy)]
+    );
+    assert!(search("zzzz").is_empty());
+
+    let plan = plan_gene_search(
+        &GeneSearchRequest {
+            q: "brca".to_string(),
+            limit: 10,
+            dataset_key: None,
+        },
+        &limits,
+    )
+    .expect("plan");
+    assert_eq!(plan.class, QueryClass::Search);
+    assert_eq!(plan.node, PlanNode::TextSearch);
+    let too_short = GeneSearchRequest {
+        q: "b".to_string(),
+        limit: 10,
+        dataset_key: None,
+    };
+    assert!(search_genes(&conn, &too_short, &limits).is_err());
+}
+
 #[test]
 fn normalized_output_is_blocked_in_prod_mode() {
     let root = tempdir().expect("tempdir");
@@ -505,7 +1054,7 @@ fn ingest_sqlite_meta_includes_build_pragmas() {
             |r| r.get(0),
         )
         .expect("journal mode");
-    assert_eq!(schema_version, "4");
+    assert_eq!(schema_version, "5");
     assert_eq!(journal_mode, "WAL");
     let schema_table_version: i64 = conn
         .query_row(
@@ -514,7 +1063,7 @@ fn ingest_sqlite_meta_includes_build_pragmas() {
             |r| r.get(0),
         )
         .expect("schema_version table");
-    assert_eq!(schema_table_version, 4);
+    assert_eq!(schema_table_version, 5);
     let contigs: i64 = conn
         .query_row("SELECT COUNT(*) FROM contigs", [], |r| r.get(0))
         .expect("contigs table count");
@@ -734,9 +1283,9 @@ fn feature_id_uniqueness_policy_normalized_rejects_case_collisions() {
 #[test]
 fn tiny_fixture_matches_cross_machine_golden_hashes() {
     const SQLITE_LOGICAL_FINGERPRINT_SHA256: &str =
-        "67a2095680d2ce06ff89ea15ddeafa3fc13c1552452d44c7a4cd7c0a444b413a";
+        "d1f26295d21045941a45abd311620d10ec19be9d9be2280aa327f1a0b27add3e";
     const DATASET_SIGNATURE_SHA256: &str =
-        "d273f80509add42ab87efe008c5ad2361e0efb411b295e7a9a8371ec281bd9df";
+        "5fcdb66ef05fe14d85759dc0813aa954860a0663270061988644cf57a0d87724";
 
     let root = tempdir().expect("tempdir");
     let run = ingest_dataset(&opts(root.path(), StrictnessMode::Strict)).expect("ingest");
@@ -1044,3 +1593,215 @@ fn unknown_biotype_is_recorded_as_scientific_ambiguity() {
         "scientific ambiguity evidence should be emitted"
     );
 }
+
+#[test]
+fn cds_segments_are_written_and_assemble_spliced_sequences() {
+    use crate::domain::query::{query_transcript_layout, GeneticCode, TranscriptSequenceKind};
+
+    let inputs = tempdir().expect("inputs");
+    let genome = "CCATGCCCCCGCCTAAGTTTAA";
+    let gff = "##gff-version 3\n\
+chr1\tsrc\tgene\t1\t20\t.\t+\t.\tID=gp;Name=GP;biotype=protein_coding\n\
+chr1\tsrc\tmRNA\t1\t20\t.\t+\t.\tID=tp;Parent=gp\n\
+chr1\tsrc\texon\t1\t5\t.\t+\t.\tID=tp.e1;Parent=tp\n\
+chr1\tsrc\texon\t11\t20\t.\t+\t.\tID=tp.e2;Parent=tp\n\
+chr1\tsrc\tCDS\t3\t5\t.\t+\t0\tID=tp.c1;Parent=tp\n\
+chr1\tsrc\tCDS\t11\t16\t.\t+\t1\tID=tp.c2;Parent=tp\n\
+chr1\tsrc\tgene\t1\t20\t.\t-\t.\tID=gm;Name=GM;biotype=protein_coding\n\
+chr1\tsrc\tmRNA\t1\t20\t.\t-\t.\tID=tm;Parent=gm\n\
+chr1\tsrc\texon\t1\t5\t.\t-\t.\tID=tm.e1;Parent=tm\n\
+chr1\tsrc\texon\t11\t20\t.\t-\t.\tID=tm.e2;Parent=tm\n";
+    std::fs::write(inputs.path().join("genes.gff3"), gff).expect("write gff3");
+    std::fs::write(
+        inputs.path().join("genome.fa"),
+        format!(">chr1\n{genome}\n"),
+    )
+    .expect("write fasta");
+    std::fs::write(
+        inputs.path().join("genome.fa.fai"),
+        format!(
+            "chr1\t{}\t6\t{}\t{}\n",
+            genome.len(),
+            genome.len(),
+            genome.len() + 1
+        ),
+    )
+    .expect("write fai");
+
+    let root = tempdir().expect("tempdir");
+    let mut o = opts(root.path(), StrictnessMode::Strict);
+    o.gff3_path = inputs.path().join("genes.gff3");
+    o.fasta_path = inputs.path().join("genome.fa");
+    o.fai_path = inputs.path().join("genome.fa.fai");
+    let run = ingest_dataset(&o).expect("ingest with cds");
+
+    let conn = rusqlite::Connection::open(&run.sqlite_path).expect("open sqlite");
+    let rows: Vec<(String, i64, i64, Option<i64>)> = conn
+        .prepare("SELECT transcript_id, start, end, phase FROM cds_segments ORDER BY transcript_id, start")
+        .expect("prepare")
+        .query_map([], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?)))
+        .expect("query")
+        .collect::<Result<_, _>>()
+        .expect("rows");
+    assert_eq!(
+        rows,
+        vec![
+            ("tp".to_string(), 3, 5, Some(0)),
+            ("tp".to_string(), 11, 16, Some(1)),
+        ]
+    );
+
+    let fetch = |start: u64, end: u64| -> Result<Vec<u8>, String> {
+        Ok(genome.as_bytes()[(start - 1) as usize..end as usize].to_vec())
+    };
+    let code = GeneticCode::by_id(1).expect("standard code");
+    let plus = query_transcript_layout(&conn, "tp")
+        .expect("layout")
+        .expect("tp exists");
+    assert_eq!(
+        plus.assemble(TranscriptSequenceKind::Cds, code, fetch)
+            .expect("cds"),
+        b"ATGGCCTAA"
+    );
+    let minus = query_transcript_layout(&conn, "tm")
+        .expect("layout")
+        .expect("tm exists");
+    assert!(minus.cds.is_empty());
+    assert_eq!(
+        minus
+            .assemble(TranscriptSequenceKind::Cdna, code, fetch)
+            .expect("cdna"),
+        b"AAACTTAGGCCATGG"
+    );
+}
+
+#[test]
+fn introns_and_utrs_are_derived_at_ingest_and_match_query_time_derivation() {
+    use crate::domain::query::{
+        query_exons_in_region, query_transcript_exons, query_transcript_introns,
+        query_transcript_utrs, UtrKind,
+    };
+
+    let inputs = tempdir().expect("inputs");
+    let genome = "CCATGCCCCCGCCTAAGTTTAA";
+    let gff = "##gff-version 3\n\
+chr1\tsrc\tgene\t1\t20\t.\t+\t.\tID=gp;Name=GP;biotype=protein_coding\n\
+chr1\tsrc\tmRNA\t1\t20\t.\t+\t.\tID=tp;Parent=gp\n\
+chr1\tsrc\texon\t1\t5\t.\t+\t.\tID=tp.e1;Parent=tp\n\
+chr1\tsrc\texon\t11\t20\t.\t+\t.\tID=tp.e2;Parent=tp\n\
+chr1\tsrc\tCDS\t3\t5\t.\t+\t0\tID=tp.c1;Parent=tp\n\
+chr1\tsrc\tCDS\t11\t16\t.\t+\t1\tID=tp.c2;Parent=tp\n\
+chr1\tsrc\tgene\t1\t20\t.\t-\t.\tID=gm;Name=GM;biotype=protein_coding\n\
+chr1\tsrc\tmRNA\t1\t20\t.\t-\t.\tID=tm;Parent=gm\n\
+chr1\tsrc\texon\t1\t5\t.\t-\t.\tID=tm.e1;Parent=tm\n\
+chr1\tsrc\texon\t11\t20\t.\t-\t.\tID=tm.e2;Parent=tm\n\
+chr1\tsrc\tCDS\t3\t5\t.\t-\t0\tID=tm.c1;Parent=tm\n\
+chr1\tsrc\tCDS\t11\t16\t.\t-\t0\tID=tm.c2;Parent=tm\n";
+    std::fs::write(inputs.path().join("genes.gff3"), gff).expect("write gff3");
+    std::fs::write(
+        inputs.path().join("genome.fa"),
+        format!(">chr1\n{genome}\n"),
+    )
+    .expect("write fasta");
+    std::fs::write(
+        inputs.path().join("genome.fa.fai"),
+        format!(
+            "chr1\t{}\t6\t{}\t{}\n",
+            genome.len(),
+            genome.len(),
+            genome.len() + 1
+        ),
+    )
+    .expect("write fai");
+
+    let root = tempdir().expect("tempdir");
+    let mut o = opts(root.path(), StrictnessMode::Strict);
+    o.gff3_path = inputs.path().join("genes.gff3");
+    o.fasta_path = inputs.path().join("genome.fa");
+    o.fai_path = inputs.path().join("genome.fa.fai");
+    let run = ingest_dataset(&o).expect("ingest with cds");
+    assert_eq!(
+        run.manifest.canonical_feature_counts.get("intron"),
+        Some(&2)
+    );
+    assert_eq!(run.manifest.canonical_feature_counts.get("utr"), Some(&4));
+
+    let conn = rusqlite::Connection::open(&run.sqlite_path).expect("open sqlite");
+    let stored: Vec<(String, String, i64, i64)> = conn
+        .prepare("SELECT transcript_id, kind, start, end FROM utrs ORDER BY transcript_id, start")
+        .expect("prepare")
+        .query_map([], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?)))
+        .expect("query")
+        .collect::<Result<_, _>>()
+        .expect("rows");
+    assert_eq!(
+        stored,
+        vec![
+            ("tm".to_string(), "three_prime".to_string(), 1, 2),
+            ("tm".to_string(), "five_prime".to_string(), 17, 20),
+            ("tp".to_string(), "five_prime".to_string(), 1, 2),
+            ("tp".to_string(), "three_prime".to_string(), 17, 20),
+        ]
+    );
+
+    let exons = query_transcript_exons(&conn, "tm")
+        .expect("exons")
+        .expect("tm exists");
+    assert_eq!(
+        exons
+            .iter()
+            .map(|e| (e.exon_id.as_str(), e.rank))
+            .collect::<Vec<_>>(),
+        vec![("tm.e2", Some(1)), ("tm.e1", Some(2))]
+    );
+    let introns = query_transcript_introns(&conn, "tp")
+        .expect("introns")
+        .expect("tp exists");
+    assert_eq!(
+        introns
+            .iter()
+            .map(|i| (i.rank, i.start, i.end))
+            .collect::<Vec<_>>(),
+        vec![(1, 6, 10)]
+    );
+    let stored_utrs = query_transcript_utrs(&conn, "tm")
+        .expect("utrs")
+        .expect("tm exists");
+    assert_eq!(stored_utrs[0].kind, UtrKind::FivePrime);
+    assert_eq!((stored_utrs[0].start, stored_utrs[0].end), (17, 20));
+    assert!(query_transcript_utrs(&conn, "missing")
+        .expect("utrs")
+        .is_none());
+
+    let overlapping = query_exons_in_region(&conn, "chr1", 4, 12, 10).expect("region");
+    assert_eq!(
+        overlapping
+            .iter()
+            .map(|e| e.exon_id.as_str())
+            .collect::<Vec<_>>(),
+        vec!["tm.e1", "tp.e1", "tm.e2", "tp.e2"]
+    );
+    assert!(query_exons_in_region(&conn, "chr1", 6, 10, 10)
+        .expect("gap")
+        .is_empty());
+
+    // Artifacts without the derived tables fall back to query-time derivation.
+    conn.execute_batch("DROP TABLE introns; DROP TABLE utrs; DROP TABLE exon_rtree;")
+        .expect("drop derived tables");
+    assert_eq!(
+        query_transcript_introns(&conn, "tp")
+            .expect("introns")
+            .expect("tp exists"),
+        introns
+    );
+    assert_eq!(
+        query_transcript_utrs(&conn, "tm")
+            .expect("utrs")
+            .expect("tm exists"),
+        stored_utrs
+    );
+    assert_eq!(
+        query_exons_in_region(&conn, "chr1", 4, 12, 10).expect("region"),
+        overlapping
+    );
+}
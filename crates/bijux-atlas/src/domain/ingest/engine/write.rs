@@ -1,26 +1,58 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::fs;
+use std::path::Path;
 
 use crate::domain::canonical;
 use crate::domain::dataset::{
-    ArtifactChecksums, ArtifactManifest, IngestAnomalyReport, ManifestStats, ShardCatalog,
-    ShardingPlan,
+    ArtifactChecksums, ArtifactManifest, ArtifactPaths, IngestAnomalyReport, ManifestStats,
+    ShardCatalog, ShardingPlan,
 };
 use crate::domain::sha256_hex;
 use serde_json::json;
 
+use super::canonical_model::{write_canonical_model, CanonicalModelDigest};
+use super::compression::{
+    build_gzi_index, detect_compression, encode_gzi_index, open_decoded, InputCompression,
+};
 use super::decode::DecodedIngest;
 use super::diff_index::build_and_write_release_gene_index;
-use super::hashing::compute_input_hashes;
+use super::hashing::{compute_input_hashes, hash_file};
 use super::job::IngestJob;
+use super::liftover::build_and_write_liftover_chains;
 use super::manifest::{
     build_and_write_manifest_and_reports, write_qc_and_anomaly_reports_only, BuildManifestArgs,
 };
 use super::normalized::{replay_counts_from_normalized, write_normalized_jsonl_zst};
+use super::resources::IngestResourceUsage;
+use super::spool::RowScope;
 use super::sqlite::{write_sharded_sqlite_catalog, write_sqlite, WriteSqliteInput};
 use super::{IngestError, IngestResult};
 
+/// Stores the FASTA so FAI offsets can be served by seeking. BGZF keeps its
+/// blocks and gets a `.gzi` index built from them; gzip and zstd can only be
+/// read from the start, so they are stored decoded.
+fn store_seekable_fasta(input: &Path, paths: &ArtifactPaths) -> Result<(), IngestError> {
+    let io = |e: std::io::Error| IngestError(e.to_string());
+    match detect_compression(input)? {
+        InputCompression::Bgzf => {
+            fs::copy(input, &paths.fasta).map_err(io)?;
+            fs::write(
+                &paths.fasta_gzi,
+                encode_gzi_index(&build_gzi_index(&paths.fasta)?),
+            )
+            .map_err(io)
+        }
+        InputCompression::Gzip | InputCompression::Zstd => {
+            let mut out = fs::File::create(&paths.fasta).map_err(io)?;
+            std::io::copy(&mut open_decoded(input)?, &mut out)
+                .map_err(|e| IngestError(format!("fasta decompression failed: {e}")))?;
+            Ok(())
+        }
+        InputCompression::None => fs::copy(input, &paths.fasta).map(|_| ()).map_err(io),
+    }
+}
+
 pub fn write_ingest_outputs(
     job: &IngestJob,
     decoded: DecodedIngest,
@@ -33,11 +65,14 @@ pub fn write_ingest_outputs(
 
     if opts.report_only {
         write_canonical_evidence(&decoded, &paths.derived_dir)?;
+        let resource_usage = write_resource_usage(paths, &decoded)?;
         let qc_report_path = write_qc_and_anomaly_reports_only(
             &opts.output_root,
             &opts.dataset,
             &paths.anomaly_report,
             &decoded.extract,
+            &decoded.rows,
+            &resource_usage,
         )?;
         let manifest = ArtifactManifest::new(
             "1".to_string(),
@@ -45,13 +80,8 @@ pub fn write_ingest_outputs(
             opts.dataset.clone(),
             ArtifactChecksums::new(String::new(), String::new(), String::new(), String::new()),
             ManifestStats::new(
-                decoded.extract.gene_rows.len() as u64,
-                decoded
-                    .extract
-                    .gene_rows
-                    .iter()
-                    .map(|x| x.transcript_count)
-                    .sum::<u64>(),
+                decoded.extract.gene_count,
+                decoded.rows.gene_transcript_total()?,
                 decoded.extract.contig_distribution.len() as u64,
             ),
         );
@@ -66,25 +96,26 @@ pub fn write_ingest_outputs(
             shard_catalog: None,
             manifest,
             anomaly_report: decoded.extract.anomaly,
+            resource_usage,
             events: Vec::new(),
         });
     }
 
     fs::copy(&job.inputs.gff3_path, &paths.gff3).map_err(|e| IngestError(e.to_string()))?;
-    fs::copy(&job.inputs.fasta_path, &paths.fasta).map_err(|e| IngestError(e.to_string()))?;
+    store_seekable_fasta(&job.inputs.fasta_path, paths)?;
     fs::copy(&job.inputs.fai_path, &paths.fai).map_err(|e| IngestError(e.to_string()))?;
 
     let hashes = compute_input_hashes(&paths.gff3, &paths.fasta, &paths.fai)?;
-    write_canonical_evidence(&decoded, &paths.derived_dir)?;
-    write_source_facts(job, &decoded, &hashes)?;
+    let canonical = write_canonical_evidence(&decoded, &paths.derived_dir)?;
+    write_source_facts(job, &decoded, &canonical, &hashes)?;
 
     write_sqlite(WriteSqliteInput {
         path: &paths.sqlite,
         dataset: &opts.dataset,
-        genes: &decoded.extract.gene_rows,
-        transcripts: &decoded.extract.transcript_rows,
-        exons: &decoded.extract.exon_rows,
+        rows: &decoded.rows,
+        scope: RowScope::Dataset,
         contigs: &decoded.contig_stats,
+        contig_aliases: &decoded.contig_aliases,
         gff3_sha256: &hashes.gff3_sha256,
         fasta_sha256: &hashes.fasta_sha256,
         fai_sha256: &hashes.fai_sha256,
@@ -101,8 +132,7 @@ pub fn write_ingest_outputs(
             let (catalog_path, catalog) = write_sharded_sqlite_catalog(
                 &paths.derived_dir,
                 &opts.dataset,
-                &decoded.extract.gene_rows,
-                &decoded.extract.transcript_rows,
+                &decoded.rows,
                 effective_sharding_plan,
                 opts.shard_partitions,
                 opts.max_shards,
@@ -118,26 +148,21 @@ pub fn write_ingest_outputs(
 
     let normalized_debug_path = if opts.emit_normalized_debug || opts.normalized_replay_mode {
         let path = paths.derived_dir.join("normalized_features.jsonl.zst");
-        write_normalized_jsonl_zst(
-            &path,
-            &decoded.extract.gene_rows,
-            &decoded.extract.transcript_rows,
-            &decoded.extract.exon_rows,
-        )?;
+        write_normalized_jsonl_zst(&path, &decoded.rows)?;
         if opts.normalized_replay_mode {
             let replay = replay_counts_from_normalized(&path)?;
-            if replay.genes != decoded.extract.gene_rows.len() as u64
-                || replay.transcripts != decoded.extract.transcript_rows.len() as u64
-                || replay.exons != decoded.extract.exon_rows.len() as u64
+            if replay.genes != decoded.extract.gene_count
+                || replay.transcripts != decoded.extract.transcript_count
+                || replay.exons != decoded.extract.exon_count
             {
                 return Err(IngestError(format!(
                     "normalized replay mismatch: replay=({},{},{}) extracted=({},{},{})",
                     replay.genes,
                     replay.transcripts,
                     replay.exons,
-                    decoded.extract.gene_rows.len(),
-                    decoded.extract.transcript_rows.len(),
-                    decoded.extract.exon_rows.len()
+                    decoded.extract.gene_count,
+                    decoded.extract.transcript_count,
+                    decoded.extract.exon_count
                 )));
             }
         }
@@ -146,29 +171,41 @@ pub fn write_ingest_outputs(
         None
     };
 
+    let resource_usage = write_resource_usage(paths, &decoded)?;
     let built = build_and_write_manifest_and_reports(BuildManifestArgs {
         output_root: &opts.output_root,
         dataset: &opts.dataset,
         gff3_path: &paths.gff3,
         fasta_path: &paths.fasta,
         fai_path: &paths.fai,
+        input_hashes: &hashes,
         sqlite_path: &paths.sqlite,
         manifest_path: &paths.manifest,
         anomaly_path: &paths.anomaly_report,
         extract: &decoded.extract,
+        rows: &decoded.rows,
         contig_aliases: &opts.seqid_policy.aliases,
         sharding_plan: effective_sharding_plan,
-        canonical_model_schema_version: decoded.canonical_model.schema_version,
-        canonical_query_semantic_sha256: &decoded.canonical_model.hashes.query_semantic_sha256,
-        canonical_lineage_sha256: &decoded.canonical_model.hashes.lineage_sensitive_sha256,
-        canonical_feature_counts: &decoded.canonical_model.summary.feature_type_counts,
+        canonical_model_schema_version: canonical.schema_version,
+        canonical_query_semantic_sha256: &canonical.hashes.query_semantic_sha256,
+        canonical_lineage_sha256: &canonical.hashes.lineage_sensitive_sha256,
+        canonical_feature_counts: &canonical.summary.feature_type_counts,
+        resource_usage: &resource_usage,
     })?;
 
     if opts.compute_gene_signatures {
         build_and_write_release_gene_index(
             &opts.dataset,
             &paths.release_gene_index,
-            &decoded.extract.gene_rows,
+            &decoded.rows,
+        )?;
+    }
+    if let Some(chain) = &opts.liftover_chain {
+        build_and_write_liftover_chains(
+            &opts.dataset,
+            &opts.seqid_policy,
+            chain,
+            &paths.liftover_chains,
         )?;
     }
 
@@ -200,34 +237,63 @@ pub fn write_ingest_outputs(
         shard_catalog,
         manifest,
         anomaly_report: decoded.extract.anomaly,
+        resource_usage,
         events: Vec::new(),
     })
 }
 
+fn write_resource_usage(
+    paths: &crate::domain::dataset::ArtifactPaths,
+    decoded: &DecodedIngest,
+) -> Result<IngestResourceUsage, IngestError> {
+    let usage = IngestResourceUsage::sample(
+        decoded.extract.gene_blocks,
+        decoded.extract.max_gene_block_records,
+    );
+    let bytes = canonical::stable_json_bytes(&json!({
+        "schema_version": 1,
+        "resources": usage
+    }))
+    .map_err(|e| IngestError(e.to_string()))?;
+    fs::write(&paths.ingest_resources, bytes).map_err(|e| IngestError(e.to_string()))?;
+    Ok(usage)
+}
+
+/// Digest a sidecar contributes to the inventory and evidence bundle. The QC
+/// report's `resources` section is a runtime measurement, so it is hashed out
+/// to keep both digests reproducible across runs.
+pub fn evidence_sha256(
+    paths: &crate::domain::dataset::ArtifactPaths,
+    path: &std::path::Path,
+    raw: &[u8],
+) -> Result<String, IngestError> {
+    if path != paths.qc_report {
+        return Ok(sha256_hex(raw));
+    }
+    let mut report: serde_json::Value =
+        serde_json::from_slice(raw).map_err(|e| IngestError(e.to_string()))?;
+    if let Some(fields) = report.as_object_mut() {
+        fields.remove("resources");
+    }
+    let bytes = canonical::stable_json_bytes(&report).map_err(|e| IngestError(e.to_string()))?;
+    Ok(sha256_hex(&bytes))
+}
+
 fn write_canonical_evidence(
     decoded: &DecodedIngest,
     derived_dir: &std::path::Path,
-) -> Result<(), IngestError> {
-    let canonical_path = derived_dir.join("canonical_features.json");
-    let summary_path = derived_dir.join("canonical_summary.json");
-    let model_bytes = canonical::stable_json_bytes(&decoded.canonical_model)
-        .map_err(|e| IngestError(e.to_string()))?;
-    std::fs::write(canonical_path, model_bytes).map_err(|e| IngestError(e.to_string()))?;
-    let summary_payload = serde_json::json!({
-        "schema_version": decoded.canonical_model.schema_version,
-        "summary": decoded.canonical_model.summary,
-        "hashes": decoded.canonical_model.hashes,
-        "query_semantic_payload": decoded.canonical_query_semantic_payload
-    });
-    let summary_bytes =
-        canonical::stable_json_bytes(&summary_payload).map_err(|e| IngestError(e.to_string()))?;
-    std::fs::write(summary_path, summary_bytes).map_err(|e| IngestError(e.to_string()))?;
-    Ok(())
+) -> Result<CanonicalModelDigest, IngestError> {
+    write_canonical_model(
+        &decoded.rows,
+        &derived_dir.join("canonical_features.json"),
+        &derived_dir.join("canonical_summary.json"),
+    )
 }
 
 fn write_source_facts(
     job: &IngestJob,
     decoded: &DecodedIngest,
+    canonical: &CanonicalModelDigest,
     hashes: &super::hashing::InputHashes,
 ) -> Result<(), IngestError> {
     let path = job.output_layout.source_facts.clone();
@@ -257,9 +323,9 @@ fn write_source_facts(
             "biotype_source_keys": decoded.extract.biotype_source_counts
         },
         "canonical_model": {
-            "schema_version": decoded.canonical_model.schema_version,
-            "hashes": decoded.canonical_model.hashes,
-            "summary": decoded.canonical_model.summary,
+            "schema_version": canonical.schema_version,
+            "hashes": canonical.hashes,
+            "summary": canonical.summary,
         },
         "scientific_ambiguities": decoded.extract.anomaly.scientific_ambiguities,
     });
@@ -383,14 +449,24 @@ fn write_evidence_sidecars(
             &paths.derived_dir.join("canonical_summary.json"),
         ),
         ("release_gene_index", &paths.release_gene_index),
+        ("liftover_chains", &paths.liftover_chains),
         ("gff3", &paths.gff3),
         ("fasta", &paths.fasta),
+        ("fasta_gzi", &paths.fasta_gzi),
         ("fai", &paths.fai),
     ] {
         if !path.exists() {
             continue;
         }
-        let raw = fs::read(path).map_err(|e| IngestError(e.to_string()))?;
+        let (sha256, bytes) = if path == &paths.qc_report {
+            let raw = fs::read(path).map_err(|e| IngestError(e.to_string()))?;
+            (evidence_sha256(paths, path, &raw)?, raw.len() as u64)
+        } else {
+            let len = fs::metadata(path)
+                .map_err(|e| IngestError(e.to_string()))?
+                .len();
+            (hash_file(path)?, len)
+        };
         let rel = path
             .strip_prefix(&paths.dataset_root)
             .unwrap_or(path)
@@ -399,8 +475,8 @@ fn write_evidence_sidecars(
         inventory_items.push(json!({
             "role": role,
             "path": rel,
-            "sha256": sha256_hex(&raw),
-            "bytes": raw.len(),
+            "sha256": sha256,
+            "bytes": bytes,
         }));
     }
     inventory_items.sort_by(|a, b| {
@@ -445,7 +521,7 @@ fn write_evidence_sidecars(
             .unwrap_or(path)
             .display()
             .to_string();
-        bundle_files.insert(rel, sha256_hex(&raw));
+        bundle_files.insert(rel, evidence_sha256(paths, path, &raw)?);
     }
     let bundle_payload = json!({
         "schema_version": 1,
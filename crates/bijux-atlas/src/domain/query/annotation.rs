@@ -0,0 +1,379 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! BED, GTF and GFF3 renderings of gene and transcript rows.
+//!
+//! Records are emitted in the order rows are given, which is the page order
+//! fixed by the cursor sort key, so consecutive pages concatenate into one
+//! valid file. Only the first page carries the format header.
+
+use super::engine::{GeneRow, TranscriptRow};
+use super::sequence::{FeatureSpan, TranscriptLayout};
+use std::fmt::Write as _;
+
+/// Value of the GTF/GFF3 `source` column.
+pub const ANNOTATION_SOURCE: &str = "bijux-atlas";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnnotationFormat {
+    /// BED6 for genes, BED12 with one block per exon for transcripts.
+    Bed,
+    Gtf,
+    Gff3,
+}
+
+impl AnnotationFormat {
+    #[must_use]
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "bed" => Some(Self::Bed),
+            "gtf" => Some(Self::Gtf),
+            "gff3" => Some(Self::Gff3),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Bed => "bed",
+            Self::Gtf => "gtf",
+            Self::Gff3 => "gff3",
+        }
+    }
+
+    #[must_use]
+    pub const fn media_type(self) -> &'static str {
+        match self {
+            Self::Bed => "text/x-bed",
+            Self::Gtf => "text/x-gtf",
+            Self::Gff3 => "text/x-gff3",
+        }
+    }
+
+    /// Preamble written once, before the first page of records.
+    #[must_use]
+    pub const fn header(self) -> &'static str {
+        match self {
+            Self::Bed | Self::Gtf => "",
+            Self::Gff3 => "##gff-version 3\n",
+        }
+    }
+}
+
+/// Appends the record for one gene; rows without coordinates cannot be placed and are skipped.
+pub fn write_gene_record(out: &mut String, format: AnnotationFormat, row: &GeneRow) {
+    let (Some(seqid), Some(start), Some(end)) = (row.seqid.as_deref(), row.start, row.end) else {
+        return;
+    };
+    let strand = row.strand.as_deref().unwrap_or(".");
+    match format {
+        AnnotationFormat::Bed => {
+            let _ = writeln!(
+                out,
+                "{seqid}\t{}\t{end}\t{}\t0\t{strand}",
+                start.saturating_sub(1),
+                row.gene_id
+            );
+        }
+        AnnotationFormat::Gtf => {
+            let mut attrs = gtf_attr("gene_id", &row.gene_id);
+            if let Some(name) = &row.name {
+                attrs.push_str(&gtf_attr("gene_name", name));
+            }
+            if let Some(biotype) = &row.biotype {
+                attrs.push_str(&gtf_attr("gene_biotype", biotype));
+            }
+            feature_line(
+                out,
+                seqid,
+                "gene",
+                start,
+                end,
+                strand,
+                ".",
+                attrs.trim_end(),
+            );
+        }
+        AnnotationFormat::Gff3 => {
+            let mut attrs = format!("ID={}", gff3_escape(&row.gene_id));
+            if let Some(name) = &row.name {
+                let _ = write!(attrs, ";Name={}", gff3_escape(name));
+            }
+            if let Some(biotype) = &row.biotype {
+                let _ = write!(attrs, ";biotype={}", gff3_escape(biotype));
+            }
+            feature_line(out, seqid, "gene", start, end, strand, ".", &attrs);
+        }
+    }
+}
+
+/// Appends the records for one transcript and its exon and CDS layout.
+///
+/// BED emits a single BED12 line; GTF and GFF3 emit the transcript followed by
+/// its exons and CDS segments in genomic order.
+pub fn write_transcript_records(
+    out: &mut String,
+    format: AnnotationFormat,
+    row: &TranscriptRow,
+    layout: &TranscriptLayout,
+) {
+    let strand = layout.strand.as_symbol();
+    match format {
+        AnnotationFormat::Bed => write_bed12(out, row, layout),
+        AnnotationFormat::Gtf => {
+            let mut attrs = gtf_attr("gene_id", &row.parent_gene_id);
+            attrs.push_str(&gtf_attr("transcript_id", &row.transcript_id));
+            let base = attrs.clone();
+            if let Some(biotype) = &row.biotype {
+                attrs.push_str(&gtf_attr("transcript_biotype", biotype));
+            }
+            feature_line(
+                out,
+                &row.seqid,
+                "transcript",
+                row.start,
+                row.end,
+                strand,
+                ".",
+                attrs.trim_end(),
+            );
+            let ranks = transcript_ranks(layout);
+            for (exon, rank) in layout.exons.iter().zip(ranks) {
+                let attrs = format!("{base}{}", gtf_attr("exon_number", &rank.to_string()));
+                feature_line(
+                    out,
+                    &row.seqid,
+                    "exon",
+                    exon.start,
+                    exon.end,
+                    strand,
+                    ".",
+                    attrs.trim_end(),
+                );
+            }
+            for cds in &layout.cds {
+                feature_line(
+                    out,
+                    &row.seqid,
+                    "CDS",
+                    cds.start,
+                    cds.end,
+                    strand,
+                    &phase_column(cds),
+                    base.trim_end(),
+                );
+            }
+        }
+        AnnotationFormat::Gff3 => {
+            let mut attrs = format!(
+                "ID={};Parent={}",
+                gff3_escape(&row.transcript_id),
+                gff3_escape(&row.parent_gene_id)
+            );
+            if let Some(biotype) = &row.biotype {
+                let _ = write!(attrs, ";biotype={}", gff3_escape(biotype));
+            }
+            feature_line(
+                out,
+                &row.seqid,
+                &gff3_escape(&row.transcript_type),
+                row.start,
+                row.end,
+                strand,
+                ".",
+                &attrs,
+            );
+            let parent = format!("Parent={}", gff3_escape(&row.transcript_id));
+            for exon in &layout.exons {
+                feature_line(
+                    out, &row.seqid, "exon", exon.start, exon.end, strand, ".", &parent,
+                );
+            }
+            for cds in &layout.cds {
+                feature_line(
+                    out,
+                    &row.seqid,
+                    "CDS",
+                    cds.start,
+                    cds.end,
+                    strand,
+                    &phase_column(cds),
+                    &parent,
+                );
+            }
+        }
+    }
+}
+
+fn write_bed12(out: &mut String, row: &TranscriptRow, layout: &TranscriptLayout) {
+    let chrom_start = row.start.saturating_sub(1);
+    let (thick_start, thick_end) = match (
+        layout.cds.iter().map(|c| c.start).min(),
+        layout.cds.iter().map(|c| c.end).max(),
+    ) {
+        (Some(start), Some(end)) => (start.saturating_sub(1), end),
+        _ => (chrom_start, chrom_start),
+    };
+    let whole = [FeatureSpan {
+        start: row.start,
+        end: row.end,
+        phase: None,
+    }];
+    let blocks = if layout.exons.is_empty() {
+        &whole[..]
+    } else {
+        &layout.exons[..]
+    };
+    let mut sizes = String::new();
+    let mut starts = String::new();
+    for block in blocks {
+        let _ = write!(sizes, "{},", block.len());
+        let _ = write!(starts, "{},", block.start.saturating_sub(row.start));
+    }
+    let _ = writeln!(
+        out,
+        "{}\t{chrom_start}\t{}\t{}\t0\t{}\t{thick_start}\t{thick_end}\t0\t{}\t{sizes}\t{starts}",
+        row.seqid,
+        row.end,
+        row.transcript_id,
+        layout.strand.as_symbol(),
+        blocks.len()
+    );
+}
+
+/// 1-based exon numbers, counted 5' to 3', for exons held in genomic order.
+fn transcript_ranks(layout: &TranscriptLayout) -> Vec<usize> {
+    let n = layout.exons.len();
+    if layout.strand == super::gene::Strand::Minus {
+        (1..=n).rev().collect()
+    } else {
+        (1..=n).collect()
+    }
+}
+
+fn phase_column(span: &FeatureSpan) -> String {
+    span.phase
+        .map_or_else(|| ".".to_string(), |p| p.to_string())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn feature_line(
+    out: &mut String,
+    seqid: &str,
+    feature: &str,
+    start: u64,
+    end: u64,
+    strand: &str,
+    phase: &str,
+    attributes: &str,
+) {
+    let _ = writeln!(
+        out,
+        "{seqid}\t{ANNOTATION_SOURCE}\t{feature}\t{start}\t{end}\t.\t{strand}\t{phase}\t{attributes}"
+    );
+}
+
+fn gtf_attr(key: &str, value: &str) -> String {
+    format!(
+        "{key} \"{}\"; ",
+        value.replace('\\', "\\\\").replace('"', "\\\"")
+    )
+}
+
+/// Percent-encodes the characters GFF3 reserves in column 9.
+fn gff3_escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            ';' | '=' | '&' | ',' | '%' | '\t' | '\n' | '\r' => {
+                let _ = write!(out, "%{:02X}", c as u32);
+            }
+            c if c.is_control() => {
+                let _ = write!(out, "%{:02X}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::query::gene::Strand;
+
+    fn span(start: u64, end: u64, phase: Option<u8>) -> FeatureSpan {
+        FeatureSpan { start, end, phase }
+    }
+
+    fn minus_transcript() -> (TranscriptRow, TranscriptLayout) {
+        let row = TranscriptRow {
+            transcript_id: "tx1".to_string(),
+            parent_gene_id: "g1".to_string(),
+            transcript_type: "mRNA".to_string(),
+            biotype: Some("protein_coding".to_string()),
+            seqid: "chr1".to_string(),
+            start: 101,
+            end: 200,
+            exon_count: 2,
+            total_exon_span: 40,
+            cds_present: true,
+        };
+        let layout = TranscriptLayout {
+            transcript_id: "tx1".to_string(),
+            seqid: "chr1".to_string(),
+            strand: Strand::Minus,
+            exons: vec![span(101, 120, None), span(181, 200, None)],
+            cds: vec![span(111, 120, Some(1)), span(181, 190, Some(0))],
+        };
+        (row, layout)
+    }
+
+    #[test]
+    fn bed12_blocks_are_relative_to_chrom_start_with_cds_as_thick_span() {
+        let (row, layout) = minus_transcript();
+        let mut out = String::new();
+        write_transcript_records(&mut out, AnnotationFormat::Bed, &row, &layout);
+        assert_eq!(
+            out,
+            "chr1\t100\t200\ttx1\t0\t-\t110\t190\t0\t2\t20,20,\t0,80,\n"
+        );
+    }
+
+    #[test]
+    fn gtf_numbers_exons_five_prime_to_three_prime() {
+        let (row, layout) = minus_transcript();
+        let mut out = String::new();
+        write_transcript_records(&mut out, AnnotationFormat::Gtf, &row, &layout);
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert!(lines[0].starts_with("chr1\tbijux-atlas\ttranscript\t101\t200\t.\t-\t.\t"));
+        assert!(lines[1].ends_with("exon_number \"2\";"));
+        assert!(lines[2].ends_with("exon_number \"1\";"));
+        assert!(lines[3].contains("\tCDS\t111\t120\t.\t-\t1\t"));
+    }
+
+    #[test]
+    fn gff3_escapes_reserved_attribute_characters() {
+        let row = GeneRow {
+            gene_id: "g1".to_string(),
+            name: Some("a;b=c".to_string()),
+            seqid: Some("chr1".to_string()),
+            start: Some(5),
+            end: Some(9),
+            biotype: None,
+            transcript_count: None,
+            sequence_length: None,
+            strand: Some("+".to_string()),
+        };
+        let mut out = String::new();
+        write_gene_record(&mut out, AnnotationFormat::Gff3, &row);
+        assert_eq!(
+            out,
+            "chr1\tbijux-atlas\tgene\t5\t9\t.\t+\t.\tID=g1;Name=a%3Bb%3Dc\n"
+        );
+        let mut bed = String::new();
+        write_gene_record(&mut bed, AnnotationFormat::Bed, &row);
+        assert_eq!(bed, "chr1\t4\t9\tg1\t0\t+\n");
+    }
+}
@@ -0,0 +1,141 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Contig name aliases, so `chr1`, `1` and `NC_000001.11` all reach the
+//! seqid a dataset stores.
+//!
+//! Ingest writes one row per alias into the `contig_aliases` table; the
+//! canonical names themselves are never listed as aliases, so a name that is a
+//! stored seqid always resolves to itself.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// `source` of aliases read from a UCSC `chromAlias` file without a header row.
+pub const CHROM_ALIAS_SOURCE: &str = "chrom_alias";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(deny_unknown_fields)]
+pub struct ContigAlias {
+    pub alias: String,
+    /// Seqid the dataset stores for this contig.
+    pub contig: String,
+    /// Where the alias came from: a `chromAlias` column name, `sequence_region`,
+    /// `fasta_header` or `chr_prefix`.
+    pub source: String,
+}
+
+/// How one requested seqid was mapped onto the dataset.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ContigResolution {
+    pub requested: String,
+    pub seqid: String,
+    pub alias_applied: bool,
+}
+
+impl ContigResolution {
+    /// A seqid used as given.
+    #[must_use]
+    pub fn unchanged(seqid: &str) -> Self {
+        Self {
+            requested: seqid.to_string(),
+            seqid: seqid.to_string(),
+            alias_applied: false,
+        }
+    }
+}
+
+/// Alias lookup for one dataset.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContigAliases {
+    by_alias: BTreeMap<String, String>,
+}
+
+impl ContigAliases {
+    #[must_use]
+    pub fn new(aliases: impl IntoIterator<Item = ContigAlias>) -> Self {
+        Self {
+            by_alias: aliases.into_iter().map(|a| (a.alias, a.contig)).collect(),
+        }
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.by_alias.is_empty()
+    }
+
+    /// Canonical seqid for `requested`; names without an alias pass through unchanged.
+    #[must_use]
+    pub fn resolve(&self, requested: &str) -> ContigResolution {
+        match self.by_alias.get(requested) {
+            Some(contig) => ContigResolution {
+                requested: requested.to_string(),
+                seqid: contig.clone(),
+                alias_applied: true,
+            },
+            None => ContigResolution::unchanged(requested),
+        }
+    }
+
+    /// Every alias recorded for `contig`, in name order.
+    pub fn aliases_of<'a>(&'a self, contig: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.by_alias
+            .iter()
+            .filter(move |(_, c)| c.as_str() == contig)
+            .map(|(a, _)| a.as_str())
+    }
+}
+
+/// The other common spelling of `seqid`: UCSC `chr`-prefixed names and
+/// Ensembl bare names, with `chrM` and `MT` paired for the mitochondrion.
+#[must_use]
+pub fn chr_prefix_alternate(seqid: &str) -> Option<String> {
+    match seqid {
+        "" | "chr" => None,
+        "chrM" => Some("MT".to_string()),
+        "MT" => Some("chrM".to_string()),
+        s => match s.strip_prefix("chr") {
+            Some(rest) => Some(rest.to_string()),
+            None => Some(format!("chr{s}")),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_aliases_and_passes_unknown_names_through() {
+        let aliases = ContigAliases::new([
+            ContigAlias {
+                alias: "1".to_string(),
+                contig: "chr1".to_string(),
+                source: "chr_prefix".to_string(),
+            },
+            ContigAlias {
+                alias: "NC_000001.11".to_string(),
+                contig: "chr1".to_string(),
+                source: "refseq".to_string(),
+            },
+        ]);
+        let hit = aliases.resolve("NC_000001.11");
+        assert_eq!(hit.seqid, "chr1");
+        assert!(hit.alias_applied);
+        let miss = aliases.resolve("chr2");
+        assert_eq!(miss.seqid, "chr2");
+        assert!(!miss.alias_applied);
+        assert_eq!(
+            aliases.aliases_of("chr1").collect::<Vec<_>>(),
+            vec!["1", "NC_000001.11"]
+        );
+    }
+
+    #[test]
+    fn chr_prefix_alternate_pairs_ucsc_and_ensembl_names() {
+        assert_eq!(chr_prefix_alternate("chr1").as_deref(), Some("1"));
+        assert_eq!(chr_prefix_alternate("X").as_deref(), Some("chrX"));
+        assert_eq!(chr_prefix_alternate("chrM").as_deref(), Some("MT"));
+        assert_eq!(chr_prefix_alternate("MT").as_deref(), Some("chrM"));
+        assert_eq!(chr_prefix_alternate("chr"), None);
+    }
+}
@@ -51,6 +51,9 @@ pub struct ReleaseGeneIndexEntry {
     pub start: u64,
     pub end: u64,
     pub signature_sha256: String,
+    /// Signature over the coordinate-free fields, comparable across assemblies.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attributes_sha256: Option<String>,
 }
 
 impl ReleaseGeneIndexEntry {
@@ -68,8 +71,15 @@ impl ReleaseGeneIndexEntry {
             start,
             end,
             signature_sha256,
+            attributes_sha256: None,
         }
     }
+
+    #[must_use]
+    pub fn with_attributes_sha256(mut self, attributes_sha256: String) -> Self {
+        self.attributes_sha256 = Some(attributes_sha256);
+        self
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -0,0 +1,37 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{QueryError, QueryErrorCode};
+use crate::domain::query::contigs::{ContigAlias, ContigAliases};
+use rusqlite::Connection;
+
+fn sql_err(e: rusqlite::Error) -> QueryError {
+    QueryError::new(QueryErrorCode::Sql, e.to_string())
+}
+
+/// Alias table of a dataset; artifacts written before aliases were persisted have none.
+pub fn query_contig_aliases(conn: &Connection) -> Result<ContigAliases, QueryError> {
+    let present: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='contig_aliases'",
+            [],
+            |r| r.get(0),
+        )
+        .map_err(sql_err)?;
+    if present == 0 {
+        return Ok(ContigAliases::default());
+    }
+    let rows = conn
+        .prepare("SELECT alias, contig, source FROM contig_aliases ORDER BY alias")
+        .map_err(sql_err)?
+        .query_map([], |r| {
+            Ok(ContigAlias {
+                alias: r.get(0)?,
+                contig: r.get(1)?,
+                source: r.get(2)?,
+            })
+        })
+        .map_err(sql_err)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(sql_err)?;
+    Ok(ContigAliases::new(rows))
+}
@@ -39,13 +39,13 @@ pub fn freeze_query_model(
     let intent = if ast
         .predicates
         .iter()
-        .any(|p| matches!(p, Predicate::GeneId(_)))
+        .any(|p| p.any_leaf(&|p| matches!(p, Predicate::GeneId(_))))
     {
         QueryIntent::ExactIdLookup
     } else if ast
         .predicates
         .iter()
-        .any(|p| matches!(p, Predicate::Region { .. }))
+        .any(|p| p.any_leaf(&|p| matches!(p, Predicate::Region { .. })))
     {
         QueryIntent::IntervalLookup
     } else {
@@ -86,5 +86,10 @@ fn predicate_label(predicate: &Predicate) -> &'static str {
         Predicate::Biotype(_) => "biotype",
         Predicate::Region { .. } => "region",
         Predicate::Strand(_) => "strand",
+        Predicate::Xref { .. } => "xref",
+        Predicate::Alias(_) => "alias",
+        Predicate::Search(_) => "search",
+        // List filters are homogeneous, so the first alternative names the kind.
+        Predicate::AnyOf(alternatives) => alternatives.first().map_or("any_of", predicate_label),
     }
 }
@@ -2,8 +2,8 @@
 
 use super::cursor::{CursorPayload, OrderMode};
 use super::filters::{
-    compile_field_projection, escape_like_prefix, normalize_name_lookup, GeneFields,
-    GeneQueryRequest,
+    compile_field_projection, escape_like_prefix, normalize_name_lookup, split_xref, GeneFields,
+    GeneQueryRequest, IntervalSemantics, RegionFilter, XREF_ALIAS_NAMESPACE,
 };
 use super::planner::QueryClass;
 use super::row_decode::RawGeneRow;
@@ -151,7 +151,7 @@ pub fn order_mode_for(req: &GeneQueryRequest) -> OrderMode {
         super::filters::QuerySort::GeneIdAsc => OrderMode::GeneId,
         super::filters::QuerySort::RegionAsc => OrderMode::Region,
         super::filters::QuerySort::Auto => {
-            if req.filter.region.is_some() {
+            if req.filter.has_region() {
                 OrderMode::Region
             } else {
                 OrderMode::GeneId
@@ -175,6 +175,7 @@ pub fn parse_row_from_sql(
         biotype: raw.biotype,
         transcript_count: raw.transcript_count.map(|v| v as u64),
         sequence_length: raw.sequence_length.map(|v| v as u64),
+        strand: raw.strand,
     })
 }
 
@@ -203,29 +204,20 @@ fn append_base_filters(
     where_parts: &mut Vec<String>,
     params: &mut Vec<Value>,
 ) {
-    if let Some(region) = &req.filter.region {
+    if req.filter.has_region() {
         sql.push_str(" JOIN gene_summary_rtree r ON r.gene_rowid = g.id");
-        where_parts.push("g.seqid = ?".to_string());
-        params.push(Value::Text(region.seqid.clone()));
-        match req.filter.interval {
-            super::filters::IntervalSemantics::Overlap => {
-                where_parts.push("r.start <= ?".to_string());
-                params.push(Value::Real(region.end as f64));
-                where_parts.push("r.end >= ?".to_string());
-                params.push(Value::Real(region.start as f64));
-            }
-            super::filters::IntervalSemantics::Containment => {
-                where_parts.push("r.start >= ?".to_string());
-                params.push(Value::Real(region.start as f64));
-                where_parts.push("r.end <= ?".to_string());
-                params.push(Value::Real(region.end as f64));
-            }
-            super::filters::IntervalSemantics::BoundaryTouch => {
-                where_parts.push("(r.end = ? OR r.start = ?)".to_string());
-                params.push(Value::Real(region.start as f64));
-                params.push(Value::Real(region.end as f64));
-            }
-        }
+    }
+    if let Some(region) = &req.filter.region {
+        where_parts.push(region_clause(region, req.filter.interval, params));
+    }
+    if !req.filter.regions.is_empty() {
+        let alternatives: Vec<String> = req
+            .filter
+            .regions
+            .iter()
+            .map(|region| format!("({})", region_clause(region, req.filter.interval, params)))
+            .collect();
+        where_parts.push(format!("({})", alternatives.join(" OR ")));
     }
     if let Some(gene_id) = &req.filter.gene_id {
         where_parts.push("g.gene_id = ?".to_string());
@@ -246,6 +238,62 @@ fn append_base_filters(
         where_parts.push("g.biotype = ?".to_string());
         params.push(Value::Text(biotype.clone()));
     }
+    if !req.filter.gene_ids.is_empty() {
+        where_parts.push(in_list("g.gene_id", &req.filter.gene_ids, params));
+    }
+    if !req.filter.biotypes.is_empty() {
+        where_parts.push(in_list("g.biotype", &req.filter.biotypes, params));
+    }
+    if let Some(strand) = req.filter.strand.as_symbol() {
+        where_parts.push("g.strand = ?".to_string());
+        params.push(Value::Text(strand.to_string()));
+    }
+    if let Some((namespace, value)) = req.filter.xref.as_deref().and_then(split_xref) {
+        where_parts.push(
+            "g.gene_id IN (SELECT gene_id FROM gene_xrefs WHERE namespace = ? AND value = ?)"
+                .to_string(),
+        );
+        params.push(Value::Text(namespace));
+        params.push(Value::Text(value));
+    }
+    if let Some(alias) = &req.filter.alias {
+        where_parts.push(
+            "g.gene_id IN (SELECT gene_id FROM gene_xrefs WHERE namespace = ? AND value = ?)"
+                .to_string(),
+        );
+        params.push(Value::Text(XREF_ALIAS_NAMESPACE.to_string()));
+        params.push(Value::Text(alias.clone()));
+    }
+}
+
+fn region_clause(
+    region: &RegionFilter,
+    interval: IntervalSemantics,
+    params: &mut Vec<Value>,
+) -> String {
+    params.push(Value::Text(region.seqid.clone()));
+    match interval {
+        IntervalSemantics::Overlap => {
+            params.push(Value::Real(region.end as f64));
+            params.push(Value::Real(region.start as f64));
+            "g.seqid = ? AND r.start <= ? AND r.end >= ?".to_string()
+        }
+        IntervalSemantics::Containment => {
+            params.push(Value::Real(region.start as f64));
+            params.push(Value::Real(region.end as f64));
+            "g.seqid = ? AND r.start >= ? AND r.end <= ?".to_string()
+        }
+        IntervalSemantics::BoundaryTouch => {
+            params.push(Value::Real(region.start as f64));
+            params.push(Value::Real(region.end as f64));
+            "g.seqid = ? AND (r.end = ? OR r.start = ?)".to_string()
+        }
+    }
+}
+
+fn in_list(column: &str, values: &[String], params: &mut Vec<Value>) -> String {
+    params.extend(values.iter().cloned().map(Value::Text));
+    format!("{column} IN ({})", vec!["?"; values.len()].join(", "))
 }
 
 #[must_use]
@@ -260,5 +308,8 @@ pub fn prepared_sql_for_class(class: QueryClass) -> &'static str {
         QueryClass::Heavy => {
             "SELECT g.gene_id, g.name, g.seqid, g.start, g.end, g.biotype, g.transcript_count, g.sequence_length FROM gene_summary g JOIN gene_summary_rtree r ON r.gene_rowid = g.id WHERE g.seqid = ?1 AND r.start <= ?2 AND r.end >= ?3 ORDER BY g.seqid, g.start, g.gene_id LIMIT ?4"
         }
+        QueryClass::Search => {
+            "SELECT gene_id, bm25(gene_search_fts) AS score FROM gene_search_fts WHERE gene_search_fts MATCH ?1 ORDER BY score, gene_id LIMIT ?2"
+        }
     }
 }
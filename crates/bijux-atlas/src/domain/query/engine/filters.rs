@@ -3,6 +3,9 @@
 use serde::{Deserialize, Serialize};
 use unicode_normalization::UnicodeNormalization;
 
+/// `gene_xrefs.namespace` under which GFF3 `Alias` values are stored.
+pub const XREF_ALIAS_NAMESPACE: &str = "Alias";
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub struct GeneFields {
     pub gene_id: bool,
@@ -11,6 +14,9 @@ pub struct GeneFields {
     pub biotype: bool,
     pub transcript_count: bool,
     pub sequence_length: bool,
+    /// Opt-in because artifacts built before schema v5 have no strand column.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub strand: bool,
 }
 
 impl Default for GeneFields {
@@ -22,6 +28,7 @@ impl Default for GeneFields {
             biotype: true,
             transcript_count: true,
             sequence_length: true,
+            strand: false,
         }
     }
 }
@@ -46,6 +53,35 @@ pub struct GeneFilter {
     pub interval: IntervalSemantics,
     #[serde(default)]
     pub strand: StrandMode,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub xref: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+    /// Any-of lists: values within a list are ORed, lists AND with every other filter.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub gene_ids: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub biotypes: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub regions: Vec<RegionFilter>,
+}
+
+impl GeneFilter {
+    /// True when the query is keyed by exact gene ids, scalar or list.
+    #[must_use]
+    pub fn has_gene_id_lookup(&self) -> bool {
+        self.gene_id.is_some() || !self.gene_ids.is_empty()
+    }
+
+    #[must_use]
+    pub fn has_region(&self) -> bool {
+        self.region.is_some() || !self.regions.is_empty()
+    }
+
+    /// Every region constraint, the scalar one first.
+    pub fn region_spans(&self) -> impl Iterator<Item = &RegionFilter> {
+        self.region.iter().chain(&self.regions)
+    }
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
@@ -76,6 +112,19 @@ pub enum StrandMode {
     Unknown,
 }
 
+impl StrandMode {
+    /// GFF3 column 7 symbol stored in the `strand` column, or `None` for `Any`.
+    #[must_use]
+    pub const fn as_symbol(self) -> Option<&'static str> {
+        match self {
+            Self::Any => None,
+            Self::Plus => Some("+"),
+            Self::Minus => Some("-"),
+            Self::Unknown => Some("."),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct GeneQueryRequest {
     pub fields: GeneFields,
@@ -96,6 +145,8 @@ pub struct GeneRow {
     pub biotype: Option<String>,
     pub transcript_count: Option<u64>,
     pub sequence_length: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strand: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -177,6 +228,11 @@ pub fn compile_field_projection(fields: &GeneFields) -> Vec<String> {
     } else {
         "NULL AS sequence_length".to_string()
     });
+    select.push(if fields.strand {
+        "g.strand".to_string()
+    } else {
+        "NULL AS strand".to_string()
+    });
     select
 }
 
@@ -200,3 +256,23 @@ pub fn normalize_name_lookup(input: &str) -> String {
     // Canonical query normalization policy: NFKC + Unicode lowercase.
     input.nfkc().collect::<String>().to_lowercase()
 }
+
+/// Splits a `NAMESPACE:VALUE` cross-reference at the first colon.
+///
+/// A value that repeats its namespace (`HGNC:HGNC:1100`, as written by RefSeq
+/// GFF3) is collapsed so ingest and lookups agree on `("HGNC", "1100")`.
+#[must_use]
+pub fn split_xref(raw: &str) -> Option<(String, String)> {
+    let (namespace, value) = raw.trim().split_once(':')?;
+    let (namespace, mut value) = (namespace.trim(), value.trim());
+    if let Some(rest) = value
+        .strip_prefix(namespace)
+        .and_then(|rest| rest.strip_prefix(':'))
+    {
+        value = rest;
+    }
+    if namespace.is_empty() || value.is_empty() {
+        return None;
+    }
+    Some((namespace.to_string(), value.to_string()))
+}
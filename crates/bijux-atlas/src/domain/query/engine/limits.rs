@@ -15,6 +15,7 @@ pub struct QueryLimits {
     pub max_prefix_len: usize,
     pub max_work_units: u64,
     pub max_serialization_bytes: usize,
+    pub max_filter_values: usize,
 }
 
 impl Default for QueryLimits {
@@ -30,6 +31,7 @@ impl Default for QueryLimits {
             max_prefix_len: 64,
             max_work_units: 2_000,
             max_serialization_bytes: 512 * 1024,
+            max_filter_values: 5_000,
         }
     }
 }
@@ -48,6 +50,7 @@ impl QueryLimits {
             max_prefix_len: policy.query_budget.max_prefix_length as usize,
             max_work_units: 2_000,
             max_serialization_bytes: policy.response_budget.max_serialization_bytes as usize,
+            max_filter_values: 5_000,
         }
     }
 }
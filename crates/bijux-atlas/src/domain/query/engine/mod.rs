@@ -2,6 +2,7 @@
 
 #![forbid(unsafe_code)]
 
+mod contig_aliases;
 mod contract;
 mod cost;
 mod cursor;
@@ -14,7 +15,11 @@ mod parser;
 mod planner;
 mod query_error;
 mod row_decode;
+mod search;
+mod transcript_features;
 
+use crate::domain::query::gene::Strand;
+use crate::domain::query::sequence::{FeatureSpan, TranscriptLayout};
 use cursor::{
     decode_cursor as decode_cursor_inner, encode_cursor as encode_cursor_inner,
     CursorPayload as CursorPayloadInner,
@@ -23,10 +28,12 @@ use db::{assert_index_usage, order_mode_for, parse_row_from_sql, query_gene_id_n
 use normalize::normalized_query_hash;
 use parser::parse_gene_query;
 use planner::{plan_query, validate_request};
-use rusqlite::{params_from_iter, types::Value, Connection};
+use rusqlite::{params_from_iter, types::Value, Connection, OptionalExtension};
+use std::borrow::Cow;
 
 pub const CRATE_NAME: &str = "bijux-atlas-query";
 
+pub use contig_aliases::query_contig_aliases;
 pub use contract::{freeze_query_model, FrozenQueryModel, QueryIntent};
 pub use cost::estimate_prefix_match_cost;
 pub use cursor::{
@@ -38,9 +45,9 @@ pub use db::explain_query_plan as explain_query_plan_internal;
 pub use db::prepared_sql_for_class as prepared_sql_for_class_export;
 pub use executor::execute_gene_query;
 pub use filters::{
-    compile_field_projection, escape_like_prefix, normalize_name_lookup, GeneFields, GeneFilter,
-    GeneRow, IntervalSemantics, QuerySort, RegionFilter, StrandMode, TranscriptFilter,
-    TranscriptQueryRequest, TranscriptQueryResponse, TranscriptRow,
+    compile_field_projection, escape_like_prefix, normalize_name_lookup, split_xref, GeneFields,
+    GeneFilter, GeneRow, IntervalSemantics, QuerySort, RegionFilter, StrandMode, TranscriptFilter,
+    TranscriptQueryRequest, TranscriptQueryResponse, TranscriptRow, XREF_ALIAS_NAMESPACE,
 };
 pub use limits::QueryLimits as QueryLimitsExport;
 pub use normalize::normalized_query_hash as normalized_query_hash_ssot;
@@ -53,6 +60,13 @@ pub use planner::{
 };
 pub use query_error::{QueryError, QueryErrorCode};
 pub use row_decode::RawGeneRow;
+pub use search::{
+    plan_gene_search, search_genes, GeneSearchHit, GeneSearchRequest, GeneSearchResponse,
+    SearchMatchKind,
+};
+pub use transcript_features::{
+    query_exons_in_region, query_transcript_exons, query_transcript_introns, query_transcript_utrs,
+};
 
 pub fn query_genes(
     conn: &Connection,
@@ -61,6 +75,8 @@ pub fn query_genes(
     cursor_secret: &[u8],
 ) -> Result<GeneQueryResponse, QueryError> {
     validate_request(req, limits).map_err(|e| QueryError::new(QueryErrorCode::Validation, e))?;
+    let req = &*align_strand_with_schema(conn, req)?;
+    reject_xref_filter_without_table(conn, req)?;
     reject_impossible_filter_fast(req, limits, conn)?;
     let ast = parse_gene_query(req)?;
     let plan = plan_query(&ast, limits)?;
@@ -74,13 +90,19 @@ pub fn query_gene_by_id_fast(
     gene_id: &str,
     fields: &GeneFields,
 ) -> Result<Option<filters::GeneRow>, QueryError> {
+    let sql = if fields.strand && gene_summary_has_strand(conn)? {
+        "SELECT g.gene_id, g.name, g.seqid, g.start, g.end, g.biotype, g.transcript_count, g.sequence_length, g.strand
+         FROM gene_summary g
+         WHERE g.gene_id = ?1
+         LIMIT 1"
+    } else {
+        "SELECT g.gene_id, g.name, g.seqid, g.start, g.end, g.biotype, g.transcript_count, g.sequence_length, NULL AS strand
+         FROM gene_summary g
+         WHERE g.gene_id = ?1
+         LIMIT 1"
+    };
     let mut stmt = conn
-        .prepare_cached(
-            "SELECT g.gene_id, g.name, g.seqid, g.start, g.end, g.biotype, g.transcript_count, g.sequence_length
-             FROM gene_summary g
-             WHERE g.gene_id = ?1
-             LIMIT 1",
-        )
+        .prepare_cached(sql)
         .map_err(|e| QueryError::new(QueryErrorCode::Sql, e.to_string()))?;
     let mut rows = stmt
         .query([gene_id])
@@ -113,6 +135,61 @@ pub fn query_gene_by_id_fast(
     Ok(Some(parsed))
 }
 
+fn gene_summary_has_strand(conn: &Connection) -> Result<bool, QueryError> {
+    conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('gene_summary') WHERE name='strand'",
+        [],
+        |r| r.get::<_, i64>(0),
+    )
+    .map(|n| n > 0)
+    .map_err(|e| QueryError::new(QueryErrorCode::Sql, e.to_string()))
+}
+
+/// Artifacts built before schema v5 carry no strand column: a strand filter is
+/// rejected for them, while a requested strand projection is silently dropped.
+fn align_strand_with_schema<'a>(
+    conn: &Connection,
+    req: &'a GeneQueryRequest,
+) -> Result<Cow<'a, GeneQueryRequest>, QueryError> {
+    if (req.filter.strand == StrandMode::Any && !req.fields.strand)
+        || gene_summary_has_strand(conn)?
+    {
+        return Ok(Cow::Borrowed(req));
+    }
+    if req.filter.strand != StrandMode::Any {
+        return Err(QueryError::new(
+            QueryErrorCode::Validation,
+            "strand-aware filtering is not available for current dataset schema",
+        ));
+    }
+    let mut without_strand = req.clone();
+    without_strand.fields.strand = false;
+    Ok(Cow::Owned(without_strand))
+}
+
+fn reject_xref_filter_without_table(
+    conn: &Connection,
+    req: &GeneQueryRequest,
+) -> Result<(), QueryError> {
+    if req.filter.xref.is_none() && req.filter.alias.is_none() {
+        return Ok(());
+    }
+    let tables: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='gene_xrefs'",
+            [],
+            |r| r.get(0),
+        )
+        .map_err(|e| QueryError::new(QueryErrorCode::Sql, e.to_string()))?;
+    if tables == 0 {
+        return Err(QueryError::new(
+            QueryErrorCode::Validation,
+            "xref/alias filtering is not available for current dataset schema",
+        ));
+    }
+    Ok(())
+}
+
 pub fn query_gene_id_name_json_minimal_fast(
     conn: &Connection,
     gene_id: &str,
@@ -145,7 +222,8 @@ fn reject_impossible_filter_fast(
             ));
         }
     }
-    if let Some(region) = &req.filter.region {
+    let mut estimated_rows = 0_u64;
+    for region in req.filter.region_spans() {
         let seqid_count: i64 = conn
             .query_row(
                 "SELECT COALESCE((SELECT gene_count FROM dataset_stats WHERE dimension='seqid' AND value=?1), 0)",
@@ -164,16 +242,18 @@ fn reject_impossible_filter_fast(
         }
         let span = region.end.saturating_sub(region.start) + 1;
         let span_ratio = span as f64 / limits.max_region_span as f64;
-        let estimated_rows = ((seqid_count as f64) * span_ratio).ceil() as u64;
-        if estimated_rows > limits.max_region_estimated_rows {
-            return Err(QueryError::new(
-                QueryErrorCode::Validation,
-                format!(
-                    "estimated region rows {} exceeds {}",
-                    estimated_rows, limits.max_region_estimated_rows
-                ),
-            ));
-        }
+        // Regions may overlap; summing keeps the estimate an upper bound.
+        estimated_rows =
+            estimated_rows.saturating_add(((seqid_count as f64) * span_ratio).ceil() as u64);
+    }
+    if estimated_rows > limits.max_region_estimated_rows {
+        return Err(QueryError::new(
+            QueryErrorCode::Validation,
+            format!(
+                "estimated region rows {} exceeds {}",
+                estimated_rows, limits.max_region_estimated_rows
+            ),
+        ));
     }
     Ok(())
 }
@@ -371,7 +451,12 @@ pub fn query_transcripts(
         params.push(Value::Integer(region.start as i64));
     }
     if let Some(cursor) = &req.cursor {
-        where_parts.push("transcript_id > ?".to_string());
+        // Keyset on the full sort key so pages never skip or repeat rows.
+        where_parts.push(
+            "(seqid, start, transcript_id) > \
+             (SELECT seqid, start, transcript_id FROM transcript_summary WHERE transcript_id = ?)"
+                .to_string(),
+        );
         params.push(Value::Text(cursor.clone()));
     }
     if !where_parts.is_empty() {
@@ -471,6 +556,78 @@ pub fn query_transcript_by_id(
     }))
 }
 
+/// Exon and CDS layout of one transcript; the strand is taken from the parent gene.
+///
+/// Artifacts written before CDS segments were persisted yield an empty `cds` list.
+pub fn query_transcript_layout(
+    conn: &Connection,
+    tx_id: &str,
+) -> Result<Option<TranscriptLayout>, QueryError> {
+    let sql_err = |e: rusqlite::Error| QueryError::new(QueryErrorCode::Sql, e.to_string());
+    let strand_expr = if gene_summary_has_strand(conn)? {
+        "COALESCE(g.strand, '.')"
+    } else {
+        "'.'"
+    };
+    let head = conn
+        .query_row(
+            &format!(
+                "SELECT t.seqid, {strand_expr} FROM transcript_summary t \
+                 LEFT JOIN gene_summary g ON g.gene_id = t.parent_gene_id \
+                 WHERE t.transcript_id=?1 LIMIT 1"
+            ),
+            [tx_id],
+            |r| Ok((r.get::<_, String>(0)?, r.get::<_, String>(1)?)),
+        )
+        .optional()
+        .map_err(sql_err)?;
+    let Some((seqid, strand)) = head else {
+        return Ok(None);
+    };
+    let span = |r: &rusqlite::Row<'_>| {
+        Ok(FeatureSpan {
+            start: r.get::<_, i64>(0)? as u64,
+            end: r.get::<_, i64>(1)? as u64,
+            phase: r.get::<_, Option<i64>>(2)?.map(|p| p as u8),
+        })
+    };
+    let exons = conn
+        .prepare_cached(
+            "SELECT start, end, NULL FROM exons WHERE transcript_id=?1 ORDER BY start, end",
+        )
+        .map_err(sql_err)?
+        .query_map([tx_id], span)
+        .map_err(sql_err)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(sql_err)?;
+    let has_cds_table: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='cds_segments'",
+            [],
+            |r| r.get(0),
+        )
+        .map_err(sql_err)?;
+    let cds = if has_cds_table == 0 {
+        Vec::new()
+    } else {
+        conn.prepare_cached(
+            "SELECT start, end, phase FROM cds_segments WHERE transcript_id=?1 ORDER BY start, end",
+        )
+        .map_err(sql_err)?
+        .query_map([tx_id], span)
+        .map_err(sql_err)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(sql_err)?
+    };
+    Ok(Some(TranscriptLayout {
+        transcript_id: tx_id.to_string(),
+        seqid,
+        strand: Strand::parse(&strand).unwrap_or(Strand::Unknown),
+        exons,
+        cds,
+    }))
+}
+
 pub fn explain_transcript_query_plan(
     conn: &Connection,
     req: &TranscriptQueryRequest,
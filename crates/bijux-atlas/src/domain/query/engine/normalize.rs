@@ -45,5 +45,13 @@ fn predicate_sort_key(predicate: &Predicate) -> String {
             semantics,
         } => format!("4:{seqid}:{start}:{end}:{semantics:?}"),
         Predicate::Strand(v) => format!("5:{v:?}"),
+        Predicate::Xref { namespace, value } => format!("6:{namespace}:{value}"),
+        Predicate::Alias(v) => format!("7:{v}"),
+        Predicate::Search(v) => format!("8:{v}"),
+        Predicate::AnyOf(alternatives) => {
+            let mut keys: Vec<String> = alternatives.iter().map(predicate_sort_key).collect();
+            keys.sort();
+            format!("9:[{}]", keys.join("|"))
+        }
     }
 }
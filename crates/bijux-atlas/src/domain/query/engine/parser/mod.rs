@@ -1,6 +1,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use super::filters::{GeneQueryRequest, IntervalSemantics, QuerySort, StrandMode};
+use super::filters::{split_xref, GeneQueryRequest, IntervalSemantics, QuerySort, StrandMode};
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -16,6 +16,24 @@ pub enum Predicate {
         semantics: IntervalSemantics,
     },
     Strand(StrandMode),
+    Xref {
+        namespace: String,
+        value: String,
+    },
+    Alias(String),
+    Search(String),
+    /// Disjunction of same-kind predicates built from a list-valued filter.
+    AnyOf(Vec<Predicate>),
+}
+
+impl Predicate {
+    /// Tests `f` against this predicate and, for `AnyOf`, each alternative.
+    pub fn any_leaf(&self, f: &impl Fn(&Predicate) -> bool) -> bool {
+        match self {
+            Self::AnyOf(alternatives) => alternatives.iter().any(|p| p.any_leaf(f)),
+            leaf => f(leaf),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -40,6 +58,7 @@ pub struct GeneQueryAst {
 pub enum ParseError {
     InvalidLimit,
     InvalidRegionSpan,
+    InvalidXref,
 }
 
 impl std::fmt::Display for ParseError {
@@ -47,6 +66,7 @@ impl std::fmt::Display for ParseError {
         match self {
             Self::InvalidLimit => f.write_str("limit must be > 0"),
             Self::InvalidRegionSpan => f.write_str("region start must be <= end and >= 1"),
+            Self::InvalidXref => f.write_str("xref must be NAMESPACE:VALUE"),
         }
     }
 }
@@ -86,12 +106,45 @@ pub fn parse_gene_query(req: &GeneQueryRequest) -> Result<GeneQueryAst, ParseErr
     if req.filter.strand != StrandMode::Any {
         predicates.push(Predicate::Strand(req.filter.strand));
     }
+    if let Some(v) = &req.filter.xref {
+        let (namespace, value) = split_xref(v).ok_or(ParseError::InvalidXref)?;
+        predicates.push(Predicate::Xref { namespace, value });
+    }
+    if let Some(v) = &req.filter.alias {
+        predicates.push(Predicate::Alias(v.clone()));
+    }
+
+    if !req.filter.gene_ids.is_empty() {
+        predicates.push(any_of(
+            req.filter.gene_ids.iter().cloned().map(Predicate::GeneId),
+        ));
+    }
+    if !req.filter.biotypes.is_empty() {
+        predicates.push(any_of(
+            req.filter.biotypes.iter().cloned().map(Predicate::Biotype),
+        ));
+    }
+    if !req.filter.regions.is_empty() {
+        let mut regions = Vec::with_capacity(req.filter.regions.len());
+        for v in &req.filter.regions {
+            if v.start == 0 || v.end < v.start {
+                return Err(ParseError::InvalidRegionSpan);
+            }
+            regions.push(Predicate::Region {
+                seqid: v.seqid.clone(),
+                start: v.start,
+                end: v.end,
+                semantics: req.filter.interval,
+            });
+        }
+        predicates.push(any_of(regions));
+    }
 
     let sort_key = match req.filter.sort {
         QuerySort::GeneIdAsc => SortKey::GeneId,
         QuerySort::RegionAsc => SortKey::Region,
         QuerySort::Auto => {
-            if req.filter.region.is_some() {
+            if req.filter.has_region() {
                 SortKey::Region
             } else {
                 SortKey::GeneId
@@ -108,3 +161,13 @@ pub fn parse_gene_query(req: &GeneQueryRequest) -> Result<GeneQueryAst, ParseErr
         sort_key,
     })
 }
+
+/// Collapses a single alternative back to the plain predicate.
+fn any_of(alternatives: impl IntoIterator<Item = Predicate>) -> Predicate {
+    let mut alternatives: Vec<Predicate> = alternatives.into_iter().collect();
+    if alternatives.len() == 1 {
+        alternatives.remove(0)
+    } else {
+        Predicate::AnyOf(alternatives)
+    }
+}
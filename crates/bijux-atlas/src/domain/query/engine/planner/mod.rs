@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::cost::estimate_prefix_match_cost;
-use super::filters::{GeneQueryRequest, StrandMode};
+use super::filters::{split_xref, GeneQueryRequest};
 use super::limits::QueryLimits;
 use super::normalize::normalized_ast_format;
 use super::parser::{GeneQueryAst, Predicate, SortKey};
@@ -15,6 +15,7 @@ pub enum QueryClass {
     Cheap,
     Medium,
     Heavy,
+    Search,
 }
 
 #[derive(
@@ -37,9 +38,11 @@ impl QueryCost {
 pub enum PlanNode {
     PointLookup,
     NameLookup,
+    XrefLookup,
     PrefixSearch,
     RegionScan,
     FilteredScan,
+    TextSearch,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -78,49 +81,65 @@ impl std::error::Error for PlanError {}
 
 #[must_use]
 pub fn classify_query(req: &GeneQueryRequest) -> QueryClass {
-    if req.filter.gene_id.is_some() {
+    if req.filter.has_gene_id_lookup() {
         QueryClass::Cheap
-    } else if req.filter.region.is_some() || req.filter.name_prefix.is_some() {
+    } else if req.filter.has_region() || req.filter.name_prefix.is_some() {
         QueryClass::Heavy
     } else {
         QueryClass::Medium
     }
 }
 
+fn has_predicate(ast: &GeneQueryAst, f: impl Fn(&Predicate) -> bool) -> bool {
+    ast.predicates.iter().any(|p| p.any_leaf(&f))
+}
+
 #[must_use]
 pub fn classify_ast(ast: &GeneQueryAst) -> QueryClass {
-    if ast
-        .predicates
-        .iter()
-        .any(|p| matches!(p, Predicate::GeneId(_)))
-    {
+    if has_predicate(ast, |p| matches!(p, Predicate::Search(_))) {
+        QueryClass::Search
+    } else if has_predicate(ast, |p| matches!(p, Predicate::GeneId(_))) {
         QueryClass::Cheap
-    } else if ast
-        .predicates
-        .iter()
-        .any(|p| matches!(p, Predicate::Region { .. } | Predicate::NamePrefix(_)))
-    {
+    } else if has_predicate(ast, |p| {
+        matches!(p, Predicate::Region { .. } | Predicate::NamePrefix(_))
+    }) {
         QueryClass::Heavy
     } else {
         QueryClass::Medium
     }
 }
 
+fn region_cost_units(spans: impl Iterator<Item = (u64, u64)>) -> u64 {
+    spans
+        .map(|(start, end)| (end.saturating_sub(start) + 1) / 10_000)
+        .fold(0, u64::saturating_add)
+}
+
+fn ast_region_spans(predicate: &Predicate, out: &mut Vec<(u64, u64)>) {
+    match predicate {
+        Predicate::Region { start, end, .. } => out.push((*start, *end)),
+        Predicate::AnyOf(alternatives) => {
+            for p in alternatives {
+                ast_region_spans(p, out);
+            }
+        }
+        _ => {}
+    }
+}
+
 #[must_use]
 pub fn estimate_ast_cost(ast: &GeneQueryAst) -> QueryCost {
     let base = match classify_ast(ast) {
         QueryClass::Cheap => 20_u64,
         QueryClass::Medium => 200_u64,
         QueryClass::Heavy => 1200_u64,
+        QueryClass::Search => 400_u64,
     };
-    let region_cost = ast
-        .predicates
-        .iter()
-        .find_map(|p| match p {
-            Predicate::Region { start, end, .. } => Some((end.saturating_sub(*start) + 1) / 10_000),
-            _ => None,
-        })
-        .unwrap_or(0);
+    let mut spans = Vec::new();
+    for p in &ast.predicates {
+        ast_region_spans(p, &mut spans);
+    }
+    let region_cost = region_cost_units(spans.into_iter());
     QueryCost::new(base + (ast.limit as u64) + region_cost)
 }
 
@@ -134,10 +153,7 @@ pub fn plan_query(ast: &GeneQueryAst, limits: &QueryLimits) -> Result<QueryPlan,
 
     let class = classify_ast(ast);
     let cost = estimate_ast_cost(ast);
-    if !ast
-        .predicates
-        .iter()
-        .any(|p| matches!(p, Predicate::GeneId(_)))
+    if !has_predicate(ast, |p| matches!(p, Predicate::GeneId(_)))
         && cost.work_units > limits.max_work_units
     {
         return Err(PlanError::Validation(format!(
@@ -146,30 +162,20 @@ pub fn plan_query(ast: &GeneQueryAst, limits: &QueryLimits) -> Result<QueryPlan,
         )));
     }
 
-    let node = if ast
-        .predicates
-        .iter()
-        .any(|p| matches!(p, Predicate::GeneId(_)))
-    {
+    let node = if has_predicate(ast, |p| matches!(p, Predicate::Search(_))) {
+        PlanNode::TextSearch
+    } else if has_predicate(ast, |p| matches!(p, Predicate::GeneId(_))) {
         PlanNode::PointLookup
-    } else if ast
-        .predicates
-        .iter()
-        .any(|p| matches!(p, Predicate::NamePrefix(_)))
-    {
+    } else if has_predicate(ast, |p| matches!(p, Predicate::NamePrefix(_))) {
         PlanNode::PrefixSearch
-    } else if ast
-        .predicates
-        .iter()
-        .any(|p| matches!(p, Predicate::Region { .. }))
-    {
+    } else if has_predicate(ast, |p| matches!(p, Predicate::Region { .. })) {
         PlanNode::RegionScan
-    } else if ast
-        .predicates
-        .iter()
-        .any(|p| matches!(p, Predicate::NameEquals(_)))
-    {
+    } else if has_predicate(ast, |p| matches!(p, Predicate::NameEquals(_))) {
         PlanNode::NameLookup
+    } else if has_predicate(ast, |p| {
+        matches!(p, Predicate::Xref { .. } | Predicate::Alias(_))
+    }) {
+        PlanNode::XrefLookup
     } else {
         PlanNode::FilteredScan
     };
@@ -201,12 +207,9 @@ pub fn estimate_query_cost(req: &GeneQueryRequest) -> QueryCost {
         QueryClass::Cheap => 20_u64,
         QueryClass::Medium => 200_u64,
         QueryClass::Heavy => 1200_u64,
+        QueryClass::Search => 400_u64,
     };
-    let region_cost = req
-        .filter
-        .region
-        .as_ref()
-        .map_or(0_u64, |r| (r.end.saturating_sub(r.start) + 1) / 10_000);
+    let region_cost = region_cost_units(req.filter.region_spans().map(|r| (r.start, r.end)));
     QueryCost::new(base + (req.limit as u64) + region_cost)
 }
 
@@ -237,7 +240,43 @@ pub fn validate_request(req: &GeneQueryRequest, limits: &QueryLimits) -> Result<
         }
     }
 
-    if let Some(region) = &req.filter.region {
+    if let Some(xref) = &req.filter.xref {
+        if split_xref(xref).is_none() {
+            return Err("xref must be NAMESPACE:VALUE".to_string());
+        }
+    }
+    if req
+        .filter
+        .alias
+        .as_deref()
+        .is_some_and(|a| a.trim().is_empty())
+    {
+        return Err("alias must not be empty".to_string());
+    }
+
+    for (field, count) in [
+        ("gene_ids", req.filter.gene_ids.len()),
+        ("biotypes", req.filter.biotypes.len()),
+        ("regions", req.filter.regions.len()),
+    ] {
+        if count > limits.max_filter_values {
+            return Err(format!(
+                "{field} has {count} values; filter value limit is {}",
+                limits.max_filter_values
+            ));
+        }
+    }
+    if req
+        .filter
+        .gene_ids
+        .iter()
+        .chain(&req.filter.biotypes)
+        .any(|v| v.trim().is_empty())
+    {
+        return Err("list filter values must not be empty".to_string());
+    }
+
+    for region in req.filter.region_spans() {
         if region.start == 0 || region.end < region.start {
             return Err("invalid region span".to_string());
         }
@@ -247,17 +286,14 @@ pub fn validate_request(req: &GeneQueryRequest, limits: &QueryLimits) -> Result<
         }
     }
 
-    if req.filter.strand != StrandMode::Any {
-        return Err(
-            "strand-aware filtering is not available for current dataset schema".to_string(),
-        );
-    }
-
-    let has_any_filter = req.filter.gene_id.is_some()
+    let has_any_filter = req.filter.has_gene_id_lookup()
         || req.filter.name.is_some()
         || req.filter.name_prefix.is_some()
         || req.filter.biotype.is_some()
-        || req.filter.region.is_some();
+        || !req.filter.biotypes.is_empty()
+        || req.filter.has_region()
+        || req.filter.xref.is_some()
+        || req.filter.alias.is_some();
     if !has_any_filter && !req.allow_full_scan {
         return Err(
             "full table scan is forbidden without explicit allow_full_scan=true".to_string(),
@@ -266,7 +302,7 @@ pub fn validate_request(req: &GeneQueryRequest, limits: &QueryLimits) -> Result<
 
     let cost = estimate_query_cost(req);
     // Exact gene_id lookups are contractually "cheap" and always allowed.
-    if req.filter.gene_id.is_none() && cost.work_units > limits.max_work_units {
+    if !req.filter.has_gene_id_lookup() && cost.work_units > limits.max_work_units {
         return Err(format!(
             "estimated query cost {} exceeds max_work_units {}",
             cost.work_units, limits.max_work_units
@@ -277,11 +313,13 @@ pub fn validate_request(req: &GeneQueryRequest, limits: &QueryLimits) -> Result<
 
 #[must_use]
 pub fn select_shards_for_request(req: &GeneQueryRequest, catalog: &ShardCatalog) -> Vec<String> {
-    if let Some(region) = &req.filter.region {
+    if req.filter.has_region() {
         let mut selected = BTreeSet::new();
-        for shard in &catalog.shards {
-            if shard.seqids.iter().any(|x| x.as_str() == region.seqid) {
-                selected.insert(shard.sqlite_path.clone());
+        for region in req.filter.region_spans() {
+            for shard in &catalog.shards {
+                if shard.seqids.iter().any(|x| x.as_str() == region.seqid) {
+                    selected.insert(shard.sqlite_path.clone());
+                }
             }
         }
         if !selected.is_empty() {
@@ -41,6 +41,7 @@ fn fast_path_gene_lookup_returns_single_row_without_cursor() {
         biotype: false,
         transcript_count: false,
         sequence_length: false,
+        strand: false,
     };
     let row = query_gene_by_id_fast(&conn, "gene1", &fields)
         .expect("fast query")
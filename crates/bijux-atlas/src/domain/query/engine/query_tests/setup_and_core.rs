@@ -232,6 +232,37 @@ fn transcript_query_uses_indexes_and_paginates() {
     assert_eq!(page2.rows.len(), 1);
 }
 
+#[test]
+fn transcript_cursor_follows_region_sort_key() {
+    let conn = setup_db();
+    conn.execute(
+        "INSERT INTO transcript_summary (transcript_id,parent_gene_id,transcript_type,biotype,seqid,start,end,exon_count,total_exon_span,cds_present)
+         VALUES ('tx0','gene2','transcript','protein_coding','chr1',60,90,1,31,0)",
+        [],
+    )
+    .expect("insert transcript row");
+    let mut req = TranscriptQueryRequest {
+        filter: TranscriptFilter {
+            parent_gene_id: Some("gene2".to_string()),
+            biotype: None,
+            transcript_type: None,
+            region: None,
+        },
+        limit: 1,
+        cursor: None,
+    };
+    let mut seen = Vec::new();
+    loop {
+        let page = query_transcripts(&conn, &req).expect("page");
+        seen.extend(page.rows.into_iter().map(|r| r.transcript_id));
+        match page.next_cursor {
+            Some(cursor) => req.cursor = Some(cursor),
+            None => break,
+        }
+    }
+    assert_eq!(seen, vec!["tx3", "tx0"]);
+}
+
 pub(super) fn limits() -> QueryLimits {
     QueryLimits {
         max_limit: 500,
@@ -244,6 +275,7 @@ pub(super) fn limits() -> QueryLimits {
         max_prefix_len: 64,
         max_work_units: 2_000,
         max_serialization_bytes: 512 * 1024,
+        max_filter_values: 5_000,
     }
 }
 
@@ -504,6 +536,7 @@ fn projection_specific_query_uses_covering_name_index() {
             biotype: false,
             transcript_count: false,
             sequence_length: false,
+            strand: false,
         },
         filter: GeneFilter {
             name_prefix: Some("BR".to_string()),
@@ -599,7 +632,7 @@ fn region_estimated_rows_budget_rejects_large_scans() {
 }
 
 #[test]
-fn strand_filter_is_explicitly_rejected_until_schema_support_exists() {
+fn strand_filter_is_rejected_for_datasets_without_strand_column() {
     let conn = setup_db();
     let req = GeneQueryRequest {
         fields: GeneFields::default(),
@@ -610,9 +643,177 @@ fn strand_filter_is_explicitly_rejected_until_schema_support_exists() {
         limit: 10,
         cursor: None,
         dataset_key: None,
-        allow_full_scan: false,
+        allow_full_scan: true,
     };
     let err = query_genes(&conn, &req, &limits(), b"s").expect_err("strand rejected");
     assert_eq!(err.code, QueryErrorCode::Validation);
     assert!(err.message.contains("strand-aware filtering"));
 }
+
+#[test]
+fn strand_filter_and_projection_use_v5_strand_column() {
+    let conn = setup_db();
+    conn.execute_batch(
+        "ALTER TABLE gene_summary ADD COLUMN strand TEXT NOT NULL DEFAULT '.';
+         CREATE INDEX idx_gene_summary_strand ON gene_summary(strand, gene_id);
+         UPDATE gene_summary SET strand='+' WHERE gene_id IN ('gene1','gene3');
+         UPDATE gene_summary SET strand='-' WHERE gene_id='gene2';",
+    )
+    .expect("add strand column");
+    let req_for = |strand| GeneQueryRequest {
+        fields: GeneFields {
+            strand: true,
+            ..GeneFields::default()
+        },
+        filter: GeneFilter {
+            strand,
+            ..Default::default()
+        },
+        limit: 10,
+        cursor: None,
+        dataset_key: None,
+        allow_full_scan: true,
+    };
+    // A strand matches about half the table, so it does not waive the full-scan guard.
+    let err = query_genes(
+        &conn,
+        &GeneQueryRequest {
+            allow_full_scan: false,
+            ..req_for(StrandMode::Plus)
+        },
+        &limits(),
+        b"s",
+    )
+    .expect_err("strand alone is a full scan");
+    assert!(err.message.contains("full table scan"), "{}", err.message);
+    let plus = query_genes(&conn, &req_for(StrandMode::Plus), &limits(), b"s").expect("plus");
+    assert_eq!(
+        plus.rows
+            .iter()
+            .map(|r| r.gene_id.as_str())
+            .collect::<Vec<_>>(),
+        vec!["gene1", "gene3"]
+    );
+    assert!(plus.rows.iter().all(|r| r.strand.as_deref() == Some("+")));
+    let minus = query_genes(&conn, &req_for(StrandMode::Minus), &limits(), b"s").expect("minus");
+    assert_eq!(minus.rows.len(), 1);
+    assert_eq!(minus.rows[0].gene_id, "gene2");
+    assert_eq!(minus.rows[0].strand.as_deref(), Some("-"));
+}
+
+#[test]
+fn xref_filters_are_validated_and_rejected_without_xref_table() {
+    let conn = setup_db();
+    let req_for = |xref: &str| GeneQueryRequest {
+        fields: GeneFields::default(),
+        filter: GeneFilter {
+            xref: Some(xref.to_string()),
+            ..Default::default()
+        },
+        limit: 10,
+        cursor: None,
+        dataset_key: None,
+        allow_full_scan: false,
+    };
+    let err = query_genes(&conn, &req_for("HGNC"), &limits(), b"s").expect_err("malformed");
+    assert_eq!(err.code, QueryErrorCode::Validation);
+    assert!(err.message.contains("NAMESPACE:VALUE"));
+    let err = query_genes(&conn, &req_for("HGNC:1100"), &limits(), b"s").expect_err("no table");
+    assert_eq!(err.code, QueryErrorCode::Validation);
+    assert!(err
+        .message
+        .contains("xref/alias filtering is not available"));
+    assert_eq!(
+        split_xref("HGNC:HGNC:1100"),
+        Some(("HGNC".to_string(), "1100".to_string()))
+    );
+}
+
+#[test]
+fn list_filters_or_within_a_list_and_and_across_filters() {
+    let conn = setup_db();
+    let ids = |resp: &GeneQueryResponse| {
+        resp.rows
+            .iter()
+            .map(|r| r.gene_id.clone())
+            .collect::<Vec<_>>()
+    };
+    let req_for = |filter: GeneFilter, limit: usize, cursor: Option<String>| GeneQueryRequest {
+        fields: GeneFields::default(),
+        filter,
+        limit,
+        cursor,
+        dataset_key: None,
+        allow_full_scan: false,
+    };
+
+    let batch = req_for(
+        GeneFilter {
+            gene_ids: vec![
+                "gene1".into(),
+                "gene3".into(),
+                "missing".into(),
+                "gene6".into(),
+            ],
+            ..Default::default()
+        },
+        2,
+        None,
+    );
+    assert_eq!(classify_query(&batch), QueryClass::Cheap);
+    let first = query_genes(&conn, &batch, &limits(), b"s").expect("first page");
+    assert_eq!(ids(&first), vec!["gene1", "gene3"]);
+    let second = query_genes(
+        &conn,
+        &req_for(batch.filter.clone(), 2, first.next_cursor.clone()),
+        &limits(),
+        b"s",
+    )
+    .expect("second page");
+    assert_eq!(ids(&second), vec!["gene6"]);
+    assert!(second.next_cursor.is_none());
+
+    let biotypes = req_for(
+        GeneFilter {
+            biotypes: vec!["lncRNA".into(), "unknown".into()],
+            regions: vec![
+                RegionFilter {
+                    seqid: "chr2".to_string(),
+                    start: 1,
+                    end: 10,
+                },
+                RegionFilter {
+                    seqid: "chr2".to_string(),
+                    start: 55,
+                    end: 70,
+                },
+                RegionFilter {
+                    seqid: "chr1".to_string(),
+                    start: 1,
+                    end: 100,
+                },
+            ],
+            ..Default::default()
+        },
+        10,
+        None,
+    );
+    let resp = query_genes(&conn, &biotypes, &limits(), b"s").expect("biotype and regions");
+    assert_eq!(ids(&resp), vec!["gene3", "gene5"]);
+
+    let too_many = req_for(
+        GeneFilter {
+            gene_ids: vec!["gene1".into(), "gene2".into(), "gene3".into()],
+            ..Default::default()
+        },
+        10,
+        None,
+    );
+    let tight = QueryLimits {
+        max_filter_values: 2,
+        ..limits()
+    };
+    let err = query_genes(&conn, &too_many, &tight, b"s").expect_err("list cap");
+    assert_eq!(err.code, QueryErrorCode::Validation);
+    assert!(err.message.contains("filter value limit"));
+}
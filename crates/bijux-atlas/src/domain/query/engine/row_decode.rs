@@ -10,6 +10,7 @@ pub struct RawGeneRow {
     pub biotype: Option<String>,
     pub transcript_count: Option<i64>,
     pub sequence_length: Option<i64>,
+    pub strand: Option<String>,
 }
 
 impl RawGeneRow {
@@ -23,6 +24,7 @@ impl RawGeneRow {
             biotype: row.get::<_, Option<String>>(5)?,
             transcript_count: row.get::<_, Option<i64>>(6)?,
             sequence_length: row.get::<_, Option<i64>>(7)?,
+            strand: row.get::<_, Option<String>>(8)?,
         })
     }
 }
@@ -0,0 +1,321 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::filters::{escape_like_prefix, normalize_name_lookup, XREF_ALIAS_NAMESPACE};
+use super::limits::QueryLimits;
+use super::parser::{GeneQueryAst, Predicate, SortKey};
+use super::planner::{plan_query, QueryPlan};
+use super::query_error::{QueryError, QueryErrorCode};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
+
+/// Minimum trigram Jaccard similarity for a fuzzy hit.
+const FUZZY_MIN_SIMILARITY: f64 = 0.3;
+/// Trigram candidates fetched per requested hit before similarity filtering.
+const FUZZY_CANDIDATES_PER_HIT: usize = 8;
+const FUZZY_MAX_CANDIDATES: usize = 400;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GeneSearchRequest {
+    pub q: String,
+    pub limit: usize,
+    pub dataset_key: Option<String>,
+}
+
+/// Ranking tier of a search hit; tiers are returned in declaration order.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchMatchKind {
+    Exact,
+    Prefix,
+    Token,
+    Fuzzy,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GeneSearchHit {
+    pub gene_id: String,
+    pub name: String,
+    pub biotype: String,
+    pub seqid: String,
+    pub start: u64,
+    pub end: u64,
+    pub match_kind: SearchMatchKind,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GeneSearchResponse {
+    pub hits: Vec<GeneSearchHit>,
+}
+
+pub fn plan_gene_search(
+    req: &GeneSearchRequest,
+    limits: &QueryLimits,
+) -> Result<QueryPlan, QueryError> {
+    let q = req.q.trim();
+    if q.chars().count() < limits.min_prefix_len {
+        return Err(QueryError::new(
+            QueryErrorCode::Validation,
+            format!("q length must be >= {}", limits.min_prefix_len),
+        ));
+    }
+    if q.len() > limits.max_prefix_len {
+        return Err(QueryError::new(
+            QueryErrorCode::Validation,
+            format!("q length exceeds {}", limits.max_prefix_len),
+        ));
+    }
+    if req.limit == 0 {
+        return Err(QueryError::new(
+            QueryErrorCode::Validation,
+            format!("limit must be between 1 and {}", limits.max_limit),
+        ));
+    }
+    let ast = GeneQueryAst {
+        predicates: vec![Predicate::Search(normalize_name_lookup(q))],
+        limit: req.limit,
+        dataset_key: req.dataset_key.clone(),
+        allow_full_scan: false,
+        has_cursor: false,
+        sort_key: SortKey::GeneId,
+    };
+    plan_query(&ast, limits).map_err(Into::into)
+}
+
+/// Ranks genes matching `q` by symbol, description and aliases.
+///
+/// Hits are grouped by tier: exact symbol/alias/id match, symbol prefix,
+/// token match against the `gene_search_fts` index, and finally trigram
+/// similarity from `gene_search_trigram`. A gene appears once, at its best tier.
+pub fn search_genes(
+    conn: &Connection,
+    req: &GeneSearchRequest,
+    limits: &QueryLimits,
+) -> Result<GeneSearchResponse, QueryError> {
+    plan_gene_search(req, limits)?;
+    reject_search_without_index(conn)?;
+    let q = req.q.trim();
+    let normalized = normalize_name_lookup(q);
+    let mut ranked: Vec<(String, SearchMatchKind)> = Vec::new();
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut push_tier = |ids: Vec<String>, kind: SearchMatchKind| {
+        for id in ids {
+            if ranked.len() >= req.limit {
+                break;
+            }
+            if seen.insert(id.clone()) {
+                ranked.push((id, kind));
+            }
+        }
+    };
+
+    push_tier(exact_matches(conn, q, &normalized)?, SearchMatchKind::Exact);
+    push_tier(
+        prefix_matches(conn, &normalized, req.limit)?,
+        SearchMatchKind::Prefix,
+    );
+    if let Some(expr) = token_match_expression(&normalized) {
+        push_tier(
+            token_matches(conn, &expr, req.limit)?,
+            SearchMatchKind::Token,
+        );
+    }
+    if let Some(expr) = trigram_match_expression(&normalized) {
+        push_tier(
+            fuzzy_matches(conn, &expr, &normalized, req.limit)?,
+            SearchMatchKind::Fuzzy,
+        );
+    }
+
+    let mut stmt = conn
+        .prepare_cached(
+            "SELECT name, biotype, seqid, start, end FROM gene_summary WHERE gene_id = ?1 LIMIT 1",
+        )
+        .map_err(sql_error)?;
+    let mut hits = Vec::with_capacity(ranked.len());
+    for (gene_id, match_kind) in ranked {
+        let (name, biotype, seqid, start, end) = stmt
+            .query_row([&gene_id], |r| {
+                Ok((
+                    r.get::<_, String>(0)?,
+                    r.get::<_, String>(1)?,
+                    r.get::<_, String>(2)?,
+                    r.get::<_, i64>(3)?,
+                    r.get::<_, i64>(4)?,
+                ))
+            })
+            .map_err(sql_error)?;
+        hits.push(GeneSearchHit {
+            gene_id,
+            name,
+            biotype,
+            seqid,
+            start: start as u64,
+            end: end as u64,
+            match_kind,
+        });
+    }
+    Ok(GeneSearchResponse { hits })
+}
+
+fn sql_error(e: rusqlite::Error) -> QueryError {
+    QueryError::new(QueryErrorCode::Sql, e.to_string())
+}
+
+fn reject_search_without_index(conn: &Connection) -> Result<(), QueryError> {
+    let tables: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name IN ('gene_search_fts','gene_search_trigram')",
+            [],
+            |r| r.get(0),
+        )
+        .map_err(sql_error)?;
+    if tables < 2 {
+        return Err(QueryError::new(
+            QueryErrorCode::Validation,
+            "gene search is not available for current dataset schema",
+        ));
+    }
+    Ok(())
+}
+
+fn collect_ids(
+    conn: &Connection,
+    sql: &str,
+    params: impl rusqlite::Params,
+) -> Result<Vec<String>, QueryError> {
+    let mut stmt = conn.prepare_cached(sql).map_err(sql_error)?;
+    let rows = stmt
+        .query_map(params, |r| r.get::<_, String>(0))
+        .map_err(sql_error)?;
+    rows.collect::<Result<Vec<_>, _>>().map_err(sql_error)
+}
+
+fn exact_matches(conn: &Connection, q: &str, normalized: &str) -> Result<Vec<String>, QueryError> {
+    collect_ids(
+        conn,
+        "SELECT gene_id FROM gene_summary WHERE gene_id = ?1 OR name_normalized = ?2
+         UNION
+         SELECT gene_id FROM gene_xrefs WHERE namespace = ?3 AND value = ?1
+         ORDER BY gene_id",
+        params![q, normalized, XREF_ALIAS_NAMESPACE],
+    )
+}
+
+fn prefix_matches(
+    conn: &Connection,
+    normalized: &str,
+    limit: usize,
+) -> Result<Vec<String>, QueryError> {
+    collect_ids(
+        conn,
+        "SELECT gene_id FROM gene_summary WHERE name_normalized LIKE ?1 ESCAPE '!'
+         ORDER BY length(name_normalized), name_normalized, gene_id LIMIT ?2",
+        params![format!("{}%", escape_like_prefix(normalized)), limit as i64],
+    )
+}
+
+fn token_matches(conn: &Connection, expr: &str, limit: usize) -> Result<Vec<String>, QueryError> {
+    // Symbol hits outweigh alias hits, which outweigh free-text description hits.
+    collect_ids(
+        conn,
+        "SELECT gene_id FROM gene_search_fts WHERE gene_search_fts MATCH ?1
+         ORDER BY bm25(gene_search_fts, 0.0, 10.0, 1.0, 5.0), gene_id LIMIT ?2",
+        params![expr, limit as i64],
+    )
+}
+
+fn fuzzy_matches(
+    conn: &Connection,
+    expr: &str,
+    normalized: &str,
+    limit: usize,
+) -> Result<Vec<String>, QueryError> {
+    let candidates = limit
+        .saturating_mul(FUZZY_CANDIDATES_PER_HIT)
+        .min(FUZZY_MAX_CANDIDATES);
+    let mut stmt = conn
+        .prepare_cached(
+            "SELECT gene_id, symbol, aliases FROM gene_search_trigram WHERE gene_search_trigram MATCH ?1
+             ORDER BY bm25(gene_search_trigram), gene_id LIMIT ?2",
+        )
+        .map_err(sql_error)?;
+    let rows = stmt
+        .query_map(params![expr, candidates as i64], |r| {
+            Ok((
+                r.get::<_, String>(0)?,
+                r.get::<_, String>(1)?,
+                r.get::<_, String>(2)?,
+            ))
+        })
+        .map_err(sql_error)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(sql_error)?;
+    let query_grams = padded_trigrams(normalized);
+    let mut scored: Vec<(f64, String)> = rows
+        .into_iter()
+        .filter_map(|(gene_id, symbol, aliases)| {
+            let best = std::iter::once(symbol.as_str())
+                .chain(aliases.split_whitespace())
+                .map(|term| {
+                    trigram_similarity(&query_grams, &padded_trigrams(&term.to_lowercase()))
+                })
+                .fold(0.0_f64, f64::max);
+            (best >= FUZZY_MIN_SIMILARITY).then_some((best, gene_id))
+        })
+        .collect();
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+    Ok(scored.into_iter().map(|(_, id)| id).collect())
+}
+
+fn fts_quote(term: &str) -> String {
+    format!("\"{}\"", term.replace('"', "\"\""))
+}
+
+/// All tokens must match; the last one as a prefix so partially typed words hit.
+fn token_match_expression(normalized: &str) -> Option<String> {
+    let tokens: Vec<&str> = normalized
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .collect();
+    let (last, head) = tokens.split_last()?;
+    let mut parts: Vec<String> = head.iter().map(|t| fts_quote(t)).collect();
+    parts.push(format!("{}*", fts_quote(last)));
+    Some(parts.join(" "))
+}
+
+/// Any shared trigram makes a candidate; similarity is scored afterwards.
+fn trigram_match_expression(normalized: &str) -> Option<String> {
+    let grams = trigrams(normalized);
+    if grams.is_empty() {
+        return None;
+    }
+    Some(
+        grams
+            .iter()
+            .map(|g| fts_quote(g))
+            .collect::<Vec<_>>()
+            .join(" OR "),
+    )
+}
+
+fn trigrams(term: &str) -> BTreeSet<String> {
+    let chars: Vec<char> = term.chars().collect();
+    chars
+        .windows(3)
+        .map(|w| w.iter().collect::<String>())
+        .collect()
+}
+
+/// Pads like `pg_trgm` so short symbols that share a start or end still score.
+fn padded_trigrams(term: &str) -> BTreeSet<String> {
+    trigrams(&format!("  {term} "))
+}
+
+fn trigram_similarity(a: &BTreeSet<String>, b: &BTreeSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
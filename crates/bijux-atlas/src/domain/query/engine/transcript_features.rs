@@ -0,0 +1,202 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Exon, intron and UTR lookups. Artifacts written before introns and UTRs
+//! were persisted derive them from the stored exon and CDS layout instead, so
+//! the answer is the same either way.
+
+use super::{query_transcript_layout, QueryError, QueryErrorCode};
+use crate::domain::query::features::{ExonFeature, IntronFeature, UtrFeature, UtrKind};
+use crate::domain::query::gene::Strand;
+use rusqlite::{params, Connection};
+
+fn sql_err(e: rusqlite::Error) -> QueryError {
+    QueryError::new(QueryErrorCode::Sql, e.to_string())
+}
+
+fn table_exists(conn: &Connection, table: &str) -> Result<bool, QueryError> {
+    conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE name=?1",
+        [table],
+        |r| r.get::<_, i64>(0),
+    )
+    .map(|n| n > 0)
+    .map_err(sql_err)
+}
+
+/// Puts genomically sorted rows in 5' to 3' order for `strand`.
+fn transcript_order<T>(mut rows: Vec<T>, strand: Strand) -> Vec<T> {
+    if strand == Strand::Minus {
+        rows.reverse();
+    }
+    rows
+}
+
+/// Exons of one transcript, 5' to 3', ranked from 1; `None` for an unknown transcript.
+pub fn query_transcript_exons(
+    conn: &Connection,
+    tx_id: &str,
+) -> Result<Option<Vec<ExonFeature>>, QueryError> {
+    let Some(layout) = query_transcript_layout(conn, tx_id)? else {
+        return Ok(None);
+    };
+    let rows = conn
+        .prepare_cached(
+            "SELECT exon_id, seqid, start, end, exon_length FROM exons \
+             WHERE transcript_id=?1 ORDER BY start, end, exon_id",
+        )
+        .map_err(sql_err)?
+        .query_map([tx_id], |r| {
+            Ok(ExonFeature {
+                exon_id: r.get(0)?,
+                transcript_id: tx_id.to_string(),
+                seqid: r.get(1)?,
+                start: r.get::<_, i64>(2)? as u64,
+                end: r.get::<_, i64>(3)? as u64,
+                length: r.get::<_, i64>(4)? as u64,
+                rank: None,
+            })
+        })
+        .map_err(sql_err)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(sql_err)?;
+    let mut rows = transcript_order(rows, layout.strand);
+    for (idx, row) in rows.iter_mut().enumerate() {
+        row.rank = Some(idx as u64 + 1);
+    }
+    Ok(Some(rows))
+}
+
+/// Introns of one transcript, 5' to 3'; `None` for an unknown transcript.
+pub fn query_transcript_introns(
+    conn: &Connection,
+    tx_id: &str,
+) -> Result<Option<Vec<IntronFeature>>, QueryError> {
+    let Some(layout) = query_transcript_layout(conn, tx_id)? else {
+        return Ok(None);
+    };
+    let rows = if table_exists(conn, "introns")? {
+        conn.prepare_cached(
+            "SELECT seqid, start, end, intron_rank FROM introns \
+             WHERE transcript_id=?1 ORDER BY start, end",
+        )
+        .map_err(sql_err)?
+        .query_map([tx_id], |r| {
+            let start = r.get::<_, i64>(1)? as u64;
+            let end = r.get::<_, i64>(2)? as u64;
+            Ok(IntronFeature {
+                transcript_id: tx_id.to_string(),
+                seqid: r.get(0)?,
+                start,
+                end,
+                length: end - start + 1,
+                rank: r.get::<_, i64>(3)? as u64,
+            })
+        })
+        .map_err(sql_err)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(sql_err)?
+    } else {
+        layout
+            .introns()
+            .into_iter()
+            .map(|intron| IntronFeature {
+                transcript_id: layout.transcript_id.clone(),
+                seqid: layout.seqid.clone(),
+                start: intron.start,
+                end: intron.end,
+                length: intron.end - intron.start + 1,
+                rank: intron.rank,
+            })
+            .collect()
+    };
+    Ok(Some(transcript_order(rows, layout.strand)))
+}
+
+/// UTRs of one transcript, 5' to 3'; `None` for an unknown transcript.
+pub fn query_transcript_utrs(
+    conn: &Connection,
+    tx_id: &str,
+) -> Result<Option<Vec<UtrFeature>>, QueryError> {
+    let Some(layout) = query_transcript_layout(conn, tx_id)? else {
+        return Ok(None);
+    };
+    let rows = if table_exists(conn, "utrs")? {
+        conn.prepare_cached(
+            "SELECT seqid, kind, start, end FROM utrs \
+             WHERE transcript_id=?1 ORDER BY start, end, kind",
+        )
+        .map_err(sql_err)?
+        .query_map([tx_id], |r| {
+            let kind: String = r.get(1)?;
+            let start = r.get::<_, i64>(2)? as u64;
+            let end = r.get::<_, i64>(3)? as u64;
+            Ok(UtrFeature {
+                transcript_id: tx_id.to_string(),
+                seqid: r.get(0)?,
+                kind: UtrKind::parse(&kind).unwrap_or(UtrKind::Unoriented),
+                start,
+                end,
+                length: end - start + 1,
+            })
+        })
+        .map_err(sql_err)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(sql_err)?
+    } else {
+        layout
+            .utrs()
+            .into_iter()
+            .map(|utr| UtrFeature {
+                transcript_id: layout.transcript_id.clone(),
+                seqid: layout.seqid.clone(),
+                kind: utr.kind,
+                start: utr.start,
+                end: utr.end,
+                length: utr.end - utr.start + 1,
+            })
+            .collect()
+    };
+    Ok(Some(transcript_order(rows, layout.strand)))
+}
+
+/// Exons overlapping `seqid:start-end`, in genomic order.
+///
+/// The R-tree stores 32-bit float bounds, so its candidates are re-checked
+/// against the exact integer coordinates.
+pub fn query_exons_in_region(
+    conn: &Connection,
+    seqid: &str,
+    start: u64,
+    end: u64,
+    limit: usize,
+) -> Result<Vec<ExonFeature>, QueryError> {
+    let sql = if table_exists(conn, "exon_rtree")? {
+        "SELECT e.exon_id, e.transcript_id, e.seqid, e.start, e.end, e.exon_length \
+         FROM exons e JOIN exon_rtree r ON r.exon_rowid = e.id \
+         WHERE e.seqid = ?1 AND r.start <= ?2 AND r.end >= ?3 AND e.start <= ?2 AND e.end >= ?3 \
+         ORDER BY e.seqid, e.start, e.exon_id, e.transcript_id LIMIT ?4"
+    } else {
+        "SELECT e.exon_id, e.transcript_id, e.seqid, e.start, e.end, e.exon_length \
+         FROM exons e WHERE e.seqid = ?1 AND e.start <= ?2 AND e.end >= ?3 \
+         ORDER BY e.seqid, e.start, e.exon_id, e.transcript_id LIMIT ?4"
+    };
+    conn.prepare_cached(sql)
+        .map_err(sql_err)?
+        .query_map(
+            params![seqid, end as i64, start as i64, limit as i64],
+            |r| {
+                Ok(ExonFeature {
+                    exon_id: r.get(0)?,
+                    transcript_id: r.get(1)?,
+                    seqid: r.get(2)?,
+                    start: r.get::<_, i64>(3)? as u64,
+                    end: r.get::<_, i64>(4)? as u64,
+                    length: r.get::<_, i64>(5)? as u64,
+                    rank: None,
+                })
+            },
+        )
+        .map_err(sql_err)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(sql_err)
+}
@@ -0,0 +1,235 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Transcript sub-features: exons as annotated, and the introns and UTRs
+//! derived from them. Derivation is pure so ingest and query agree on the
+//! exact rows for a given exon and CDS layout.
+
+use super::gene::Strand;
+use super::sequence::{FeatureSpan, TranscriptLayout};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UtrKind {
+    FivePrime,
+    ThreePrime,
+    /// Untranslated exon part of a transcript whose strand is unknown.
+    Unoriented,
+}
+
+impl UtrKind {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "five_prime" => Some(Self::FivePrime),
+            "three_prime" => Some(Self::ThreePrime),
+            "unoriented" => Some(Self::Unoriented),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::FivePrime => "five_prime",
+            Self::ThreePrime => "three_prime",
+            Self::Unoriented => "unoriented",
+        }
+    }
+}
+
+/// Gap between two exons; `rank` counts from the 5' end of the transcript.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntronSpan {
+    pub rank: u64,
+    pub start: u64,
+    pub end: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UtrSpan {
+    pub kind: UtrKind,
+    pub start: u64,
+    pub end: u64,
+}
+
+impl TranscriptLayout {
+    /// Introns between non-overlapping, non-adjacent exons, in genomic order.
+    #[must_use]
+    pub fn introns(&self) -> Vec<IntronSpan> {
+        derive_introns(&self.exons, self.strand)
+    }
+
+    /// Exon parts outside the CDS envelope, in genomic order.
+    ///
+    /// Non-coding transcripts have no UTRs.
+    #[must_use]
+    pub fn utrs(&self) -> Vec<UtrSpan> {
+        derive_utrs(&self.exons, &self.cds, self.strand)
+    }
+}
+
+#[must_use]
+pub fn derive_introns(exons: &[FeatureSpan], strand: Strand) -> Vec<IntronSpan> {
+    let mut sorted: Vec<&FeatureSpan> = exons.iter().collect();
+    sorted.sort_by_key(|span| (span.start, span.end));
+    let mut out = Vec::new();
+    let mut covered_end: Option<u64> = None;
+    for span in sorted {
+        if let Some(prev_end) = covered_end {
+            if span.start > prev_end.saturating_add(1) {
+                out.push(IntronSpan {
+                    rank: 0,
+                    start: prev_end + 1,
+                    end: span.start - 1,
+                });
+            }
+        }
+        covered_end = Some(covered_end.map_or(span.end, |end| end.max(span.end)));
+    }
+    let count = out.len() as u64;
+    for (idx, intron) in out.iter_mut().enumerate() {
+        intron.rank = if strand == Strand::Minus {
+            count - idx as u64
+        } else {
+            idx as u64 + 1
+        };
+    }
+    out
+}
+
+#[must_use]
+pub fn derive_utrs(exons: &[FeatureSpan], cds: &[FeatureSpan], strand: Strand) -> Vec<UtrSpan> {
+    let (Some(cds_start), Some(cds_end)) = (
+        cds.iter().map(|span| span.start).min(),
+        cds.iter().map(|span| span.end).max(),
+    ) else {
+        return Vec::new();
+    };
+    let (left, right) = match strand {
+        Strand::Plus => (UtrKind::FivePrime, UtrKind::ThreePrime),
+        Strand::Minus => (UtrKind::ThreePrime, UtrKind::FivePrime),
+        Strand::Unknown => (UtrKind::Unoriented, UtrKind::Unoriented),
+    };
+    let mut out = Vec::new();
+    for span in exons {
+        if span.start < cds_start {
+            out.push(UtrSpan {
+                kind: left,
+                start: span.start,
+                end: span.end.min(cds_start - 1),
+            });
+        }
+        if span.end > cds_end {
+            out.push(UtrSpan {
+                kind: right,
+                start: span.start.max(cds_end + 1),
+                end: span.end,
+            });
+        }
+    }
+    out.sort_by_key(|utr| (utr.start, utr.end, utr.kind));
+    out.dedup();
+    out
+}
+
+/// One exon row; `rank` is set when listed per transcript.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExonFeature {
+    pub exon_id: String,
+    pub transcript_id: String,
+    pub seqid: String,
+    pub start: u64,
+    pub end: u64,
+    pub length: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rank: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntronFeature {
+    pub transcript_id: String,
+    pub seqid: String,
+    pub start: u64,
+    pub end: u64,
+    pub length: u64,
+    pub rank: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UtrFeature {
+    pub transcript_id: String,
+    pub seqid: String,
+    pub kind: UtrKind,
+    pub start: u64,
+    pub end: u64,
+    pub length: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(start: u64, end: u64) -> FeatureSpan {
+        FeatureSpan {
+            start,
+            end,
+            phase: None,
+        }
+    }
+
+    #[test]
+    fn introns_skip_overlapping_and_adjacent_exons_and_rank_from_five_prime() {
+        let exons = [span(50, 60), span(1, 10), span(11, 20), span(15, 30)];
+        let plus = derive_introns(&exons, Strand::Plus);
+        assert_eq!(
+            plus,
+            vec![IntronSpan {
+                rank: 1,
+                start: 31,
+                end: 49
+            }]
+        );
+        let minus = derive_introns(&[span(1, 10), span(20, 30), span(40, 50)], Strand::Minus);
+        assert_eq!(
+            minus
+                .iter()
+                .map(|i| (i.rank, i.start, i.end))
+                .collect::<Vec<_>>(),
+            vec![(2, 11, 19), (1, 31, 39)]
+        );
+    }
+
+    #[test]
+    fn utrs_follow_strand_and_clip_to_cds_envelope() {
+        let exons = [span(1, 10), span(20, 30), span(40, 50)];
+        let cds = [span(5, 10), span(20, 30), span(40, 44)];
+        let plus = derive_utrs(&exons, &cds, Strand::Plus);
+        assert_eq!(
+            plus,
+            vec![
+                UtrSpan {
+                    kind: UtrKind::FivePrime,
+                    start: 1,
+                    end: 4
+                },
+                UtrSpan {
+                    kind: UtrKind::ThreePrime,
+                    start: 45,
+                    end: 50
+                },
+            ]
+        );
+        let minus = derive_utrs(&exons, &cds, Strand::Minus);
+        assert_eq!(minus[0].kind, UtrKind::ThreePrime);
+        assert_eq!(minus[1].kind, UtrKind::FivePrime);
+        assert!(derive_utrs(&exons, &[], Strand::Plus).is_empty());
+        let whole_exon = derive_utrs(&[span(1, 3), span(10, 20)], &[span(12, 20)], Strand::Plus);
+        assert_eq!(
+            whole_exon
+                .iter()
+                .map(|u| (u.start, u.end))
+                .collect::<Vec<_>>(),
+            vec![(1, 3), (10, 11)]
+        );
+    }
+}
@@ -0,0 +1,394 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Coordinate translation between assemblies from UCSC chain alignments.
+//!
+//! A chain file aligns a reference ("t", here the source assembly) to a query
+//! ("q", here the target assembly) as ungapped blocks. Ingest parses it into a
+//! [`LiftoverChainSet`] stored next to the source dataset; lookups go through a
+//! [`Liftover`] that indexes chains by source seqid.
+
+use super::gene::Strand;
+use crate::domain::dataset::{Assembly, DatasetId, ModelVersion, ValidationError};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Fraction of source bases that must map for a lift to count, as in UCSC `liftOver -minMatch`.
+pub const DEFAULT_LIFTOVER_MIN_MATCH: f64 = 0.95;
+
+/// One ungapped aligned block; starts are 0-based, the target start on the chain's target strand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChainBlock {
+    pub source_start: u64,
+    pub target_start: u64,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[non_exhaustive]
+pub struct LiftoverChain {
+    pub id: u64,
+    pub score: u64,
+    pub source_seqid: String,
+    pub source_size: u64,
+    pub source_start: u64,
+    pub source_end: u64,
+    pub target_seqid: String,
+    pub target_size: u64,
+    pub target_strand: Strand,
+    pub target_start: u64,
+    pub target_end: u64,
+    pub blocks: Vec<ChainBlock>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[non_exhaustive]
+pub struct LiftoverChainSet {
+    #[serde(default)]
+    pub model_version: ModelVersion,
+    pub schema_version: String,
+    pub dataset: DatasetId,
+    pub target_assembly: Assembly,
+    pub chains: Vec<LiftoverChain>,
+}
+
+impl LiftoverChainSet {
+    #[must_use]
+    pub fn new(dataset: DatasetId, target_assembly: Assembly, chains: Vec<LiftoverChain>) -> Self {
+        Self {
+            model_version: ModelVersion::V1,
+            schema_version: "1".to_string(),
+            dataset,
+            target_assembly,
+            chains,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if self.schema_version.trim().is_empty() {
+            return Err(ValidationError(
+                "liftover chain set schema_version must not be empty".to_string(),
+            ));
+        }
+        if self.target_assembly == self.dataset.assembly {
+            return Err(ValidationError(
+                "liftover target assembly must differ from the dataset assembly".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// A source interval translated onto the target assembly; coordinates are 1-based inclusive.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct LiftedInterval {
+    pub seqid: String,
+    pub start: u64,
+    pub end: u64,
+    pub strand: Strand,
+    pub chain_id: u64,
+    pub mapped_bases: u64,
+}
+
+impl LiftedInterval {
+    /// Share of the `source_len` requested bases that fall inside aligned blocks.
+    #[must_use]
+    pub fn mapped_fraction(&self, source_len: u64) -> f64 {
+        if source_len == 0 {
+            return 0.0;
+        }
+        self.mapped_bases as f64 / source_len as f64
+    }
+}
+
+/// Chains of one [`LiftoverChainSet`] grouped by source seqid for lookup.
+#[derive(Debug, Clone)]
+pub struct Liftover {
+    set: LiftoverChainSet,
+    by_seqid: BTreeMap<String, Vec<usize>>,
+}
+
+impl Liftover {
+    #[must_use]
+    pub fn new(set: LiftoverChainSet) -> Self {
+        let mut by_seqid: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+        for (idx, chain) in set.chains.iter().enumerate() {
+            by_seqid
+                .entry(chain.source_seqid.clone())
+                .or_default()
+                .push(idx);
+        }
+        Self { set, by_seqid }
+    }
+
+    #[must_use]
+    pub fn chain_set(&self) -> &LiftoverChainSet {
+        &self.set
+    }
+
+    /// Lifts the 1-based inclusive `start..=end` on `seqid` through the chain
+    /// covering the most bases, breaking ties by chain score then id. Gaps inside
+    /// the interval are spanned, so the result is the hull of the mapped pieces.
+    #[must_use]
+    pub fn lift(&self, seqid: &str, start: u64, end: u64) -> Option<LiftedInterval> {
+        if start == 0 || end < start {
+            return None;
+        }
+        let (from, to) = (start - 1, end);
+        let mut best: Option<(&LiftoverChain, u64, u64, u64)> = None;
+        for chain in self
+            .by_seqid
+            .get(seqid)?
+            .iter()
+            .map(|idx| &self.set.chains[*idx])
+        {
+            if chain.source_end <= from || to <= chain.source_start {
+                continue;
+            }
+            let Some((mapped, lo, hi)) = project(chain, from, to) else {
+                continue;
+            };
+            let better = match best {
+                None => true,
+                Some((current, current_mapped, _, _)) => {
+                    (mapped, chain.score, std::cmp::Reverse(chain.id))
+                        > (current_mapped, current.score, std::cmp::Reverse(current.id))
+                }
+            };
+            if better {
+                best = Some((chain, mapped, lo, hi));
+            }
+        }
+        let (chain, mapped_bases, lo, hi) = best?;
+        let (lo, hi) = if chain.target_strand == Strand::Minus {
+            (chain.target_size - hi, chain.target_size - lo)
+        } else {
+            (lo, hi)
+        };
+        Some(LiftedInterval {
+            seqid: chain.target_seqid.clone(),
+            start: lo + 1,
+            end: hi,
+            strand: chain.target_strand,
+            chain_id: chain.id,
+            mapped_bases,
+        })
+    }
+
+    /// [`Self::lift`], keeping only results that map at least `min_match` of the interval.
+    #[must_use]
+    pub fn lift_with_min_match(
+        &self,
+        seqid: &str,
+        start: u64,
+        end: u64,
+        min_match: f64,
+    ) -> Option<LiftedInterval> {
+        self.lift(seqid, start, end)
+            .filter(|lifted| lifted.mapped_fraction(end - start + 1) >= min_match)
+    }
+}
+
+/// Mapped base count and 0-based half-open target hull of `[from, to)` through one chain.
+fn project(chain: &LiftoverChain, from: u64, to: u64) -> Option<(u64, u64, u64)> {
+    let first = chain
+        .blocks
+        .partition_point(|b| b.source_start + b.size <= from);
+    let mut mapped = 0_u64;
+    let mut lo = u64::MAX;
+    let mut hi = 0_u64;
+    for block in chain.blocks[first..]
+        .iter()
+        .take_while(|b| b.source_start < to)
+    {
+        let overlap_start = from.max(block.source_start);
+        let overlap_end = to.min(block.source_start + block.size);
+        if overlap_start >= overlap_end {
+            continue;
+        }
+        let target_start = block.target_start + (overlap_start - block.source_start);
+        mapped += overlap_end - overlap_start;
+        lo = lo.min(target_start);
+        hi = hi.max(target_start + (overlap_end - overlap_start));
+    }
+    (mapped > 0).then_some((mapped, lo, hi))
+}
+
+/// Parses a UCSC chain file. `normalize_source` maps source seqids onto the
+/// dataset's own naming so lookups use the same seqids as its genes.
+pub fn parse_chain_file(
+    text: &str,
+    normalize_source: impl Fn(&str) -> String,
+) -> Result<Vec<LiftoverChain>, ValidationError> {
+    let mut chains = Vec::new();
+    let mut lines = text.lines().enumerate().peekable();
+    while let Some((line_no, line)) = lines.next() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let header: Vec<&str> = line.split_whitespace().collect();
+        if header.first() != Some(&"chain") || !(12..=13).contains(&header.len()) {
+            return Err(chain_error(line_no, "expected a chain header line"));
+        }
+        let num = |idx: usize| {
+            header[idx]
+                .parse::<u64>()
+                .map_err(|_| chain_error(line_no, "chain header field is not an integer"))
+        };
+        if header[4] != "+" {
+            return Err(chain_error(line_no, "source strand must be '+'"));
+        }
+        let target_strand = match header[9] {
+            "+" => Strand::Plus,
+            "-" => Strand::Minus,
+            _ => return Err(chain_error(line_no, "target strand must be '+' or '-'")),
+        };
+        let mut chain = LiftoverChain {
+            id: if header.len() == 13 {
+                num(12)?
+            } else {
+                chains.len() as u64 + 1
+            },
+            score: header[1]
+                .parse::<f64>()
+                .map(|s| s.max(0.0).round() as u64)
+                .map_err(|_| chain_error(line_no, "chain score is not a number"))?,
+            source_seqid: normalize_source(header[2]),
+            source_size: num(3)?,
+            source_start: num(5)?,
+            source_end: num(6)?,
+            target_seqid: header[7].to_string(),
+            target_size: num(8)?,
+            target_strand,
+            target_start: num(10)?,
+            target_end: num(11)?,
+            blocks: Vec::new(),
+        };
+        let (mut source_pos, mut target_pos) = (chain.source_start, chain.target_start);
+        loop {
+            let Some((block_line_no, block_line)) = lines.next() else {
+                return Err(chain_error(line_no, "chain ends without a final block"));
+            };
+            let fields: Vec<u64> = block_line
+                .split_whitespace()
+                .map(str::parse::<u64>)
+                .collect::<Result<_, _>>()
+                .map_err(|_| chain_error(block_line_no, "block field is not an integer"))?;
+            let (size, gaps) = match fields.as_slice() {
+                [size] => (*size, None),
+                [size, source_gap, target_gap] => (*size, Some((*source_gap, *target_gap))),
+                _ => {
+                    return Err(chain_error(
+                        block_line_no,
+                        "block line must have 1 or 3 fields",
+                    ))
+                }
+            };
+            if size > 0 {
+                chain.blocks.push(ChainBlock {
+                    source_start: source_pos,
+                    target_start: target_pos,
+                    size,
+                });
+            }
+            source_pos += size;
+            target_pos += size;
+            let Some((source_gap, target_gap)) = gaps else {
+                break;
+            };
+            source_pos += source_gap;
+            target_pos += target_gap;
+        }
+        if source_pos != chain.source_end || target_pos != chain.target_end {
+            return Err(chain_error(
+                line_no,
+                "chain blocks do not add up to the header span",
+            ));
+        }
+        if chain.source_end > chain.source_size || chain.target_end > chain.target_size {
+            return Err(chain_error(line_no, "chain span exceeds sequence size"));
+        }
+        chains.push(chain);
+    }
+    chains.sort_by(|a, b| {
+        (&a.source_seqid, a.source_start, a.id).cmp(&(&b.source_seqid, b.source_start, b.id))
+    });
+    Ok(chains)
+}
+
+fn chain_error(line_idx: usize, message: &str) -> ValidationError {
+    ValidationError(format!("chain line {}: {message}", line_idx + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHAINS: &str = "\
+chain 1000 chr1 1000 + 100 200 chr1 2000 + 1100 1210 1
+50 10 20
+40
+
+chain 500 chr2 500 + 0 100 chr2 400 - 0 100 2
+100
+";
+
+    fn liftover() -> Liftover {
+        let chains = parse_chain_file(CHAINS, |raw| raw.trim_start_matches("chr").to_string())
+            .expect("parse");
+        let dataset = DatasetId::new("75", "homo_sapiens", "GRCh37").expect("dataset");
+        let target = Assembly::parse("GRCh38").expect("assembly");
+        Liftover::new(LiftoverChainSet::new(dataset, target, chains))
+    }
+
+    #[test]
+    fn blocks_follow_gaps_and_source_seqids_are_normalized() {
+        let lift = liftover();
+        let chain = &lift.chain_set().chains[0];
+        assert_eq!(chain.source_seqid, "1");
+        assert_eq!(
+            chain.blocks,
+            vec![
+                ChainBlock {
+                    source_start: 100,
+                    target_start: 1100,
+                    size: 50
+                },
+                ChainBlock {
+                    source_start: 160,
+                    target_start: 1170,
+                    size: 40
+                },
+            ]
+        );
+        let inside = lift.lift("1", 101, 150).expect("lift");
+        assert_eq!(
+            (inside.start, inside.end, inside.mapped_bases),
+            (1101, 1150, 50)
+        );
+        let across_gap = lift.lift("1", 141, 170).expect("lift");
+        assert_eq!((across_gap.start, across_gap.end), (1141, 1180));
+        assert_eq!(across_gap.mapped_bases, 20);
+        assert!(lift.lift_with_min_match("1", 141, 170, 0.95).is_none());
+        assert!(lift.lift("1", 1, 100).is_none());
+    }
+
+    #[test]
+    fn minus_strand_targets_are_reported_in_forward_coordinates() {
+        let lifted = liftover().lift("2", 1, 10).expect("lift");
+        assert_eq!(lifted.seqid, "chr2");
+        assert_eq!(lifted.strand, Strand::Minus);
+        assert_eq!((lifted.start, lifted.end), (391, 400));
+    }
+
+    #[test]
+    fn inconsistent_block_sums_are_rejected() {
+        let err = parse_chain_file("chain 1 a 100 + 0 50 b 100 + 0 50 1\n40\n", str::to_string)
+            .expect_err("blocks short of header span");
+        assert!(err.0.contains("line 1"), "{}", err.0);
+    }
+}
@@ -1,14 +1,29 @@
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod annotation;
+pub mod contigs;
 pub mod diff;
 pub mod engine;
+pub mod features;
 pub mod gene;
+pub mod liftover;
+pub mod sequence;
 
+pub use annotation::{
+    write_gene_record, write_transcript_records, AnnotationFormat, ANNOTATION_SOURCE,
+};
+pub use contigs::{
+    chr_prefix_alternate, ContigAlias, ContigAliases, ContigResolution, CHROM_ALIAS_SOURCE,
+};
 pub use diff::{
     DiffPage, DiffRecord, DiffScope, DiffStatus, GeneSignatureInput, ReleaseGeneIndex,
     ReleaseGeneIndexEntry,
 };
 pub use engine::*;
+pub use features::{
+    derive_introns, derive_utrs, ExonFeature, IntronFeature, IntronSpan, UtrFeature, UtrKind,
+    UtrSpan,
+};
 pub use gene::{
     canonical_contig_label, classify_contig, BiotypePolicy, ContigClass, DuplicateGeneIdPolicy,
     DuplicateTranscriptIdPolicy, FeatureIdUniquenessPolicy, GeneId, GeneNamePolicy, GeneOrderKey,
@@ -16,3 +31,12 @@ pub use gene::{
     Strand, TranscriptId, TranscriptIdPolicy, TranscriptOrderKey, TranscriptTypePolicy,
     UnknownFeaturePolicy, ID_MAX_LEN, NAME_MAX_LEN, SEQID_MAX_LEN,
 };
+pub use liftover::{
+    parse_chain_file, ChainBlock, LiftedInterval, Liftover, LiftoverChain, LiftoverChainSet,
+    DEFAULT_LIFTOVER_MIN_MATCH,
+};
+pub use sequence::{
+    fasta_record, parse_fai_index, reverse_complement, FaiRecord, FeatureSpan, FlankedSpan,
+    GeneticCode, RegionTransform, SoftMaskMode, TranscriptLayout, TranscriptSequenceKind,
+    DEFAULT_GENETIC_CODE, FASTA_LINE_WIDTH, GENETIC_CODES, MITOCHONDRIAL_GENETIC_CODE,
+};
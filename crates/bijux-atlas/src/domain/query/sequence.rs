@@ -0,0 +1,681 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Sequence assembly: exon splicing in strand order, CDS phase handling,
+//! translation with NCBI genetic code tables, and the strand, flank and
+//! soft-mask transforms applied to genomic slices.
+
+use super::gene::{classify_contig, ContigClass, Strand};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscriptSequenceKind {
+    /// Spliced exons (mature transcript).
+    Cdna,
+    /// Spliced coding segments, starting at the first complete codon.
+    Cds,
+    /// Translated CDS without the terminal stop.
+    Protein,
+}
+
+impl TranscriptSequenceKind {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "cdna" => Some(Self::Cdna),
+            "cds" => Some(Self::Cds),
+            "protein" => Some(Self::Protein),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Cdna => "cdna",
+            Self::Cds => "cds",
+            Self::Protein => "protein",
+        }
+    }
+}
+
+/// NCBI translation table; amino acids are listed in TCAG codon order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GeneticCode {
+    pub id: u8,
+    pub name: &'static str,
+    amino_acids: &'static [u8; 64],
+}
+
+pub const DEFAULT_GENETIC_CODE: u8 = 1;
+pub const MITOCHONDRIAL_GENETIC_CODE: u8 = 2;
+
+pub const GENETIC_CODES: &[GeneticCode] = &[
+    GeneticCode {
+        id: 1,
+        name: "Standard",
+        amino_acids: b"FFLLSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+    },
+    GeneticCode {
+        id: 2,
+        name: "Vertebrate Mitochondrial",
+        amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSS**VVVVAAAADDEEGGGG",
+    },
+    GeneticCode {
+        id: 3,
+        name: "Yeast Mitochondrial",
+        amino_acids: b"FFLLSSSSYY**CCWWTTTTPPPPHHQQRRRRIIMMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+    },
+    GeneticCode {
+        id: 4,
+        name: "Mold, Protozoan, and Coelenterate Mitochondrial; Mycoplasma/Spiroplasma",
+        amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+    },
+    GeneticCode {
+        id: 5,
+        name: "Invertebrate Mitochondrial",
+        amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSSSSVVVVAAAADDEEGGGG",
+    },
+    GeneticCode {
+        id: 6,
+        name: "Ciliate, Dasycladacean and Hexamita Nuclear",
+        amino_acids: b"FFLLSSSSYYQQCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+    },
+    GeneticCode {
+        id: 9,
+        name: "Echinoderm and Flatworm Mitochondrial",
+        amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNNKSSSSVVVVAAAADDEEGGGG",
+    },
+    GeneticCode {
+        id: 10,
+        name: "Euplotid Nuclear",
+        amino_acids: b"FFLLSSSSYY**CCCWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+    },
+    GeneticCode {
+        id: 11,
+        name: "Bacterial, Archaeal and Plant Plastid",
+        amino_acids: b"FFLLSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+    },
+    GeneticCode {
+        id: 12,
+        name: "Alternative Yeast Nuclear",
+        amino_acids: b"FFLLSSSSYY**CC*WLLLSPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+    },
+    GeneticCode {
+        id: 13,
+        name: "Ascidian Mitochondrial",
+        amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSSGGVVVVAAAADDEEGGGG",
+    },
+    GeneticCode {
+        id: 14,
+        name: "Alternative Flatworm Mitochondrial",
+        amino_acids: b"FFLLSSSSYYY*CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNNKSSSSVVVVAAAADDEEGGGG",
+    },
+    GeneticCode {
+        id: 21,
+        name: "Trematode Mitochondrial",
+        amino_acids: b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNNKSSSSVVVVAAAADDEEGGGG",
+    },
+];
+
+impl GeneticCode {
+    #[must_use]
+    pub fn by_id(id: u8) -> Option<&'static Self> {
+        GENETIC_CODES.iter().find(|code| code.id == id)
+    }
+
+    /// Table used when a request names none: vertebrate mitochondrial on
+    /// mitochondrial contigs, standard everywhere else.
+    #[must_use]
+    pub fn default_for_contig(seqid: &str) -> &'static Self {
+        let id = if classify_contig(seqid) == ContigClass::Mitochondrial {
+            MITOCHONDRIAL_GENETIC_CODE
+        } else {
+            DEFAULT_GENETIC_CODE
+        };
+        Self::by_id(id).unwrap_or(&GENETIC_CODES[0])
+    }
+
+    /// Amino acid for one codon; codons with ambiguous bases translate to `X`.
+    #[must_use]
+    pub fn translate_codon(&self, codon: &[u8]) -> u8 {
+        let mut idx = 0_usize;
+        for base in codon {
+            let rank = match base.to_ascii_uppercase() {
+                b'T' | b'U' => 0,
+                b'C' => 1,
+                b'A' => 2,
+                b'G' => 3,
+                _ => return b'X',
+            };
+            idx = idx * 4 + rank;
+        }
+        self.amino_acids[idx]
+    }
+
+    /// Translates complete codons; a trailing partial codon and a single terminal stop are dropped.
+    #[must_use]
+    pub fn translate(&self, cds: &[u8]) -> Vec<u8> {
+        let mut protein: Vec<u8> = cds
+            .chunks_exact(3)
+            .map(|codon| self.translate_codon(codon))
+            .collect();
+        if protein.last() == Some(&b'*') {
+            protein.pop();
+        }
+        protein
+    }
+}
+
+/// IUPAC-aware reverse complement that preserves soft-masking case.
+#[must_use]
+pub fn reverse_complement(seq: &[u8]) -> Vec<u8> {
+    seq.iter().rev().map(|base| complement(*base)).collect()
+}
+
+fn complement(base: u8) -> u8 {
+    let upper = match base.to_ascii_uppercase() {
+        b'A' => b'T',
+        b'T' | b'U' => b'A',
+        b'C' => b'G',
+        b'G' => b'C',
+        b'R' => b'Y',
+        b'Y' => b'R',
+        b'K' => b'M',
+        b'M' => b'K',
+        b'B' => b'V',
+        b'V' => b'B',
+        b'D' => b'H',
+        b'H' => b'D',
+        other => other,
+    };
+    if base.is_ascii_lowercase() {
+        upper.to_ascii_lowercase()
+    } else {
+        upper
+    }
+}
+
+/// Bases per line in FASTA output.
+pub const FASTA_LINE_WIDTH: usize = 60;
+
+/// One FASTA record: `>header` followed by the sequence wrapped at [`FASTA_LINE_WIDTH`].
+#[must_use]
+pub fn fasta_record(header: &str, sequence: &[u8]) -> String {
+    let mut out = String::with_capacity(
+        header.len() + sequence.len() + sequence.len() / FASTA_LINE_WIDTH + 3,
+    );
+    out.push('>');
+    out.push_str(header);
+    out.push('\n');
+    for line in sequence.chunks(FASTA_LINE_WIDTH) {
+        out.push_str(&String::from_utf8_lossy(line));
+        out.push('\n');
+    }
+    out
+}
+
+/// One `.fai` line: contig length, byte offset of the first base and line layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FaiRecord {
+    pub len: u64,
+    pub offset: u64,
+    pub line_bases: u64,
+    pub line_bytes: u64,
+}
+
+impl FaiRecord {
+    /// Byte windows of the decoded FASTA holding bases `start..=end` (1-based),
+    /// one per line touched, so line terminators are never read.
+    #[must_use]
+    pub fn byte_windows(&self, start: u64, end: u64) -> Vec<(u64, usize)> {
+        let mut out = Vec::new();
+        let mut pos = start;
+        while pos <= end {
+            let zero = pos - 1;
+            let col = zero % self.line_bases;
+            let want = (end - pos + 1).min(self.line_bases - col);
+            out.push((
+                self.offset + (zero / self.line_bases) * self.line_bytes + col,
+                want as usize,
+            ));
+            pos += want;
+        }
+        out
+    }
+
+    /// Reads bases `start..=end` through `read(offset, len)` on the decoded FASTA.
+    pub fn read_bases<E>(
+        &self,
+        start: u64,
+        end: u64,
+        mut read: impl FnMut(u64, usize) -> Result<Vec<u8>, E>,
+    ) -> Result<Vec<u8>, E> {
+        let mut out = Vec::with_capacity((end - start + 1) as usize);
+        for (offset, len) in self.byte_windows(start, end) {
+            out.extend(read(offset, len)?);
+        }
+        Ok(out)
+    }
+}
+
+/// Parses a samtools `.fai` index keyed by contig name. Lines with fewer than
+/// five columns are skipped; numeric columns that do not parse are an error.
+pub fn parse_fai_index(content: &str) -> Result<HashMap<String, FaiRecord>, String> {
+    let mut out = HashMap::new();
+    for line in content.lines() {
+        let parts: Vec<&str> = line.split('\t').collect();
+        if parts.len() < 5 {
+            continue;
+        }
+        let field = |idx: usize| {
+            parts[idx]
+                .parse::<u64>()
+                .map_err(|e| format!("invalid fai entry for {}: {e}", parts[0]))
+        };
+        let record = FaiRecord {
+            len: field(1)?,
+            offset: field(2)?,
+            line_bases: field(3)?,
+            line_bytes: field(4)?,
+        };
+        if record.line_bases == 0 {
+            return Err(format!(
+                "invalid fai entry for {}: zero line width",
+                parts[0]
+            ));
+        }
+        out.insert(parts[0].to_string(), record);
+    }
+    Ok(out)
+}
+
+/// How soft-masked (lowercase) bases are rendered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SoftMaskMode {
+    /// Keep the case stored in the FASTA.
+    #[default]
+    Preserve,
+    /// Uppercase everything, dropping the repeat annotation.
+    Upper,
+    /// Replace soft-masked bases with `N`.
+    Hard,
+}
+
+impl SoftMaskMode {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "preserve" => Some(Self::Preserve),
+            "upper" => Some(Self::Upper),
+            "hard" => Some(Self::Hard),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Preserve => "preserve",
+            Self::Upper => "upper",
+            Self::Hard => "hard",
+        }
+    }
+
+    pub fn apply(self, seq: &mut [u8]) {
+        match self {
+            Self::Preserve => {}
+            Self::Upper => seq.make_ascii_uppercase(),
+            Self::Hard => {
+                for base in seq.iter_mut().filter(|b| b.is_ascii_lowercase()) {
+                    *base = b'N';
+                }
+            }
+        }
+    }
+}
+
+/// Strand, flanks and masking requested for a genomic slice.
+///
+/// `upstream` and `downstream` are relative to the requested strand, so on
+/// the minus strand upstream extends towards higher coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegionTransform {
+    pub strand: Strand,
+    pub upstream: u64,
+    pub downstream: u64,
+    pub mask: SoftMaskMode,
+}
+
+impl Default for RegionTransform {
+    fn default() -> Self {
+        Self {
+            strand: Strand::Plus,
+            upstream: 0,
+            downstream: 0,
+            mask: SoftMaskMode::Preserve,
+        }
+    }
+}
+
+/// Genomic span actually read once flanks are clamped to the contig.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FlankedSpan {
+    pub start: u64,
+    pub end: u64,
+    pub upstream: u64,
+    pub downstream: u64,
+}
+
+impl RegionTransform {
+    /// Flank bases requested before clamping; an upper bound for size limits.
+    #[must_use]
+    pub fn requested_flank_bases(&self) -> u64 {
+        self.upstream.saturating_add(self.downstream)
+    }
+
+    /// Extends `start..=end` by the flanks, clamped to `1..=contig_len`.
+    #[must_use]
+    pub fn flank(&self, start: u64, end: u64, contig_len: u64) -> FlankedSpan {
+        let (left, right) = if self.strand == Strand::Minus {
+            (self.downstream, self.upstream)
+        } else {
+            (self.upstream, self.downstream)
+        };
+        let flanked_start = start.saturating_sub(left).max(1);
+        let flanked_end = end.saturating_add(right).min(contig_len.max(end));
+        let (upstream, downstream) = if self.strand == Strand::Minus {
+            (flanked_end - end, start - flanked_start)
+        } else {
+            (start - flanked_start, flanked_end - end)
+        };
+        FlankedSpan {
+            start: flanked_start,
+            end: flanked_end,
+            upstream,
+            downstream,
+        }
+    }
+
+    /// Reverse-complements for the minus strand, then applies the mask mode.
+    #[must_use]
+    pub fn apply(&self, seq: Vec<u8>) -> Vec<u8> {
+        let mut out = if self.strand == Strand::Minus {
+            reverse_complement(&seq)
+        } else {
+            seq
+        };
+        self.mask.apply(&mut out);
+        out
+    }
+
+    /// FASTA header describing the slice and every transform applied to it.
+    #[must_use]
+    pub fn fasta_header(&self, seqid: &str, span: &FlankedSpan) -> String {
+        format!(
+            "{seqid}:{}-{} strand={} upstream={} downstream={} mask={}",
+            span.start,
+            span.end,
+            self.strand.as_symbol(),
+            span.upstream,
+            span.downstream,
+            self.mask.as_str()
+        )
+    }
+}
+
+/// One exon or CDS segment in 1-based inclusive genomic coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeatureSpan {
+    pub start: u64,
+    pub end: u64,
+    /// GFF3 phase; only meaningful for CDS segments.
+    pub phase: Option<u8>,
+}
+
+impl FeatureSpan {
+    #[must_use]
+    pub fn len(&self) -> u64 {
+        self.end.saturating_sub(self.start) + 1
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.end < self.start
+    }
+}
+
+/// Exon and CDS structure of one transcript, enough to assemble its sequences.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranscriptLayout {
+    pub transcript_id: String,
+    pub seqid: String,
+    pub strand: Strand,
+    pub exons: Vec<FeatureSpan>,
+    pub cds: Vec<FeatureSpan>,
+}
+
+impl TranscriptLayout {
+    /// Segments for `kind` in transcript (5' to 3') order.
+    #[must_use]
+    pub fn segments(&self, kind: TranscriptSequenceKind) -> Vec<FeatureSpan> {
+        let mut out = match kind {
+            TranscriptSequenceKind::Cdna => self.exons.clone(),
+            TranscriptSequenceKind::Cds | TranscriptSequenceKind::Protein => self.cds.clone(),
+        };
+        out.sort_by_key(|span| (span.start, span.end));
+        if self.strand == Strand::Minus {
+            out.reverse();
+        }
+        out
+    }
+
+    /// Genomic bases read to assemble `kind`, before phase trimming.
+    #[must_use]
+    pub fn genomic_bases(&self, kind: TranscriptSequenceKind) -> u64 {
+        self.segments(kind).iter().map(FeatureSpan::len).sum()
+    }
+
+    /// Envelope of all segments, used for contig bounds checks.
+    #[must_use]
+    pub fn bounds(&self) -> Option<(u64, u64)> {
+        let spans = self.exons.iter().chain(&self.cds);
+        let start = spans.clone().map(|span| span.start).min()?;
+        let end = spans.map(|span| span.end).max()?;
+        Some((start, end))
+    }
+
+    /// Assembles the sequence of `kind`, reading plus-strand genomic slices through `fetch`.
+    ///
+    /// Minus-strand segments are reverse-complemented; coding sequence skips the
+    /// leading `phase` bases of the 5'-most CDS segment so translation starts on
+    /// a codon boundary.
+    pub fn assemble<E>(
+        &self,
+        kind: TranscriptSequenceKind,
+        code: &GeneticCode,
+        mut fetch: impl FnMut(u64, u64) -> Result<Vec<u8>, E>,
+    ) -> Result<Vec<u8>, E> {
+        let segments = self.segments(kind);
+        let mut out =
+            Vec::with_capacity(segments.iter().map(FeatureSpan::len).sum::<u64>() as usize);
+        for span in &segments {
+            let slice = fetch(span.start, span.end)?;
+            if self.strand == Strand::Minus {
+                out.extend(reverse_complement(&slice));
+            } else {
+                out.extend(slice);
+            }
+        }
+        if kind == TranscriptSequenceKind::Cdna {
+            return Ok(out);
+        }
+        let phase = segments
+            .first()
+            .and_then(|span| span.phase)
+            .map_or(0, usize::from)
+            .min(out.len());
+        out.drain(..phase);
+        if kind == TranscriptSequenceKind::Protein {
+            return Ok(code.translate(&out));
+        }
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout(strand: Strand) -> TranscriptLayout {
+        TranscriptLayout {
+            transcript_id: "tx1".to_string(),
+            seqid: "chr1".to_string(),
+            strand,
+            exons: vec![
+                FeatureSpan {
+                    start: 11,
+                    end: 20,
+                    phase: None,
+                },
+                FeatureSpan {
+                    start: 1,
+                    end: 5,
+                    phase: None,
+                },
+            ],
+            cds: vec![
+                FeatureSpan {
+                    start: 3,
+                    end: 5,
+                    phase: Some(0),
+                },
+                FeatureSpan {
+                    start: 11,
+                    end: 16,
+                    phase: Some(1),
+                },
+            ],
+        }
+    }
+
+    const GENOME: &[u8] = b"CCATGCCCCCGCCTAAGTTTAA";
+
+    fn fetch(start: u64, end: u64) -> Result<Vec<u8>, ()> {
+        Ok(GENOME[(start - 1) as usize..end as usize].to_vec())
+    }
+
+    #[test]
+    fn genetic_code_tables_are_complete_and_differ_where_expected() {
+        for code in GENETIC_CODES {
+            assert_eq!(code.amino_acids.len(), 64, "table {}", code.id);
+        }
+        let standard = GeneticCode::by_id(1).expect("standard");
+        let mito = GeneticCode::by_id(2).expect("vertebrate mito");
+        assert_eq!(standard.translate_codon(b"TGA"), b'*');
+        assert_eq!(mito.translate_codon(b"TGA"), b'W');
+        assert_eq!(standard.translate_codon(b"AGA"), b'R');
+        assert_eq!(mito.translate_codon(b"AGA"), b'*');
+        assert_eq!(standard.translate_codon(b"aug"), b'M');
+        assert_eq!(standard.translate_codon(b"ANG"), b'X');
+        assert!(GeneticCode::by_id(7).is_none());
+        assert_eq!(GeneticCode::default_for_contig("chrM").id, 2);
+        assert_eq!(GeneticCode::default_for_contig("MT").id, 2);
+        assert_eq!(GeneticCode::default_for_contig("chr1").id, 1);
+        assert_eq!(standard.translate(b"ATGGCCTAAG"), b"MA".to_vec());
+    }
+
+    #[test]
+    fn fai_windows_skip_line_terminators() {
+        let index = parse_fai_index("chr1\t130\t6\t60\t61\nshort\n").expect("fai");
+        let rec = index["chr1"];
+        assert_eq!(rec.byte_windows(1, 3), vec![(6, 3)]);
+        assert_eq!(rec.byte_windows(59, 62), vec![(64, 2), (67, 2)]);
+        assert_eq!(rec.byte_windows(121, 130), vec![(128, 10)]);
+        assert!(!index.contains_key("short"));
+        assert!(parse_fai_index("chr1\t10\t0\t0\t1\n").is_err());
+        assert!(parse_fai_index("chr1\tten\t0\t60\t61\n").is_err());
+    }
+
+    #[test]
+    fn reverse_complement_handles_iupac_and_soft_mask() {
+        assert_eq!(reverse_complement(b"ACGTN"), b"NACGT".to_vec());
+        assert_eq!(reverse_complement(b"acgRY"), b"RYcgt".to_vec());
+    }
+
+    #[test]
+    fn plus_strand_transcript_splices_exons_and_translates_cds() {
+        let tx = layout(Strand::Plus);
+        let code = GeneticCode::by_id(1).expect("code");
+        let cdna = tx
+            .assemble(TranscriptSequenceKind::Cdna, code, fetch)
+            .expect("cdna");
+        assert_eq!(cdna, b"CCATGGCCTAAGTTT".to_vec());
+        let cds = tx
+            .assemble(TranscriptSequenceKind::Cds, code, fetch)
+            .expect("cds");
+        assert_eq!(cds, b"ATGGCCTAA".to_vec());
+        let protein = tx
+            .assemble(TranscriptSequenceKind::Protein, code, fetch)
+            .expect("protein");
+        assert_eq!(protein, b"MA".to_vec());
+        assert_eq!(tx.genomic_bases(TranscriptSequenceKind::Cdna), 15);
+        assert_eq!(tx.bounds(), Some((1, 20)));
+    }
+
+    #[test]
+    fn minus_strand_transcript_reverse_complements_and_honors_leading_phase() {
+        let tx = layout(Strand::Minus);
+        let code = GeneticCode::by_id(1).expect("code");
+        let cdna = tx
+            .assemble(TranscriptSequenceKind::Cdna, code, fetch)
+            .expect("cdna");
+        assert_eq!(cdna, b"AAACTTAGGCCATGG".to_vec());
+        // The 5'-most CDS on the minus strand is 11..16 with phase 1, so one base is skipped.
+        let cds = tx
+            .assemble(TranscriptSequenceKind::Cds, code, fetch)
+            .expect("cds");
+        assert_eq!(cds, b"TAGGCCAT".to_vec());
+        let protein = tx
+            .assemble(TranscriptSequenceKind::Protein, code, fetch)
+            .expect("protein");
+        assert_eq!(protein, b"*A".to_vec());
+    }
+
+    #[test]
+    fn minus_strand_flanks_swap_sides_and_clamp_to_contig() {
+        let transform = RegionTransform {
+            strand: Strand::Minus,
+            upstream: 5,
+            downstream: 2,
+            mask: SoftMaskMode::Hard,
+        };
+        let span = transform.flank(3, 18, 20);
+        assert_eq!(
+            span,
+            FlankedSpan {
+                start: 1,
+                end: 20,
+                upstream: 2,
+                downstream: 2,
+            }
+        );
+        assert_eq!(transform.requested_flank_bases(), 7);
+        assert_eq!(transform.apply(b"ACgtN".to_vec()), b"NNNGT".to_vec());
+        assert_eq!(
+            transform.fasta_header("chr1", &span),
+            "chr1:1-20 strand=- upstream=2 downstream=2 mask=hard"
+        );
+        let plus = RegionTransform {
+            upstream: 5,
+            mask: SoftMaskMode::Upper,
+            ..RegionTransform::default()
+        };
+        assert_eq!(plus.flank(10, 12, 20).start, 5);
+        assert_eq!(plus.apply(b"acGT".to_vec()), b"ACGT".to_vec());
+        assert_eq!(
+            fasta_record("r", &[b'A'; 61]),
+            format!(">r\n{}\nA\n", "A".repeat(60))
+        );
+    }
+}
@@ -1,12 +1,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::collections::{BTreeMap, BTreeSet};
-use std::time::{SystemTime, UNIX_EPOCH};
 
 use base64::Engine;
 use serde::{Deserialize, Serialize};
 
 use crate::domain::canonical::sha256_hex;
+use crate::domain::dataset::DatasetId;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RequestIdentity {
@@ -32,6 +32,66 @@ pub struct ApiKeyRecord {
     pub key_hash: String,
     pub expires_at_unix_s: Option<u64>,
     pub revoked_at_unix_s: Option<u64>,
+    #[serde(default)]
+    pub created_at_unix_s: Option<u64>,
+    /// Route actions the key may perform, such as `dataset.read`; empty
+    /// leaves the decision to role bindings alone.
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    /// `release/species/assembly` patterns where `*` matches any segment;
+    /// empty allows every dataset.
+    #[serde(default)]
+    pub datasets: Vec<String>,
+    #[serde(default)]
+    pub rate_limit_tier: Option<String>,
+}
+
+impl ApiKeyRecord {
+    #[must_use]
+    pub fn is_active(&self, now_unix_s: u64) -> bool {
+        self.revoked_at_unix_s.is_none()
+            && self
+                .expires_at_unix_s
+                .is_none_or(|expiry| now_unix_s <= expiry)
+    }
+
+    #[must_use]
+    pub fn allows_action(&self, action: &str) -> bool {
+        self.scopes.is_empty() || self.scopes.iter().any(|scope| scope == action)
+    }
+
+    /// Whether the key may read `dataset`. Only a fully resolved dataset is
+    /// judged, so a restricted key cannot match by leaving coordinates out.
+    #[must_use]
+    pub fn allows_dataset(&self, dataset: &DatasetId) -> bool {
+        if self.datasets.is_empty() {
+            return true;
+        }
+        let coordinates = [
+            dataset.release.as_str(),
+            dataset.species.as_str(),
+            dataset.assembly.as_str(),
+        ];
+        self.datasets.iter().any(|pattern| {
+            let segments: Vec<&str> = pattern.split('/').collect();
+            segments.len() == 3
+                && coordinates
+                    .iter()
+                    .zip(&segments)
+                    .all(|(value, segment)| *segment == "*" || value == segment)
+        })
+    }
+}
+
+/// Checks a `release/species/assembly` dataset restriction pattern.
+pub fn validate_dataset_pattern(pattern: &str) -> Result<(), String> {
+    let segments: Vec<&str> = pattern.split('/').collect();
+    if segments.len() != 3 || segments.iter().any(|segment| segment.trim().is_empty()) {
+        return Err(format!(
+            "dataset pattern {pattern} must be release/species/assembly"
+        ));
+    }
+    Ok(())
 }
 
 #[derive(Debug, Clone, Default)]
@@ -49,6 +109,15 @@ impl ApiKeyStore {
         self.records.insert(record.key_id.clone(), record);
     }
 
+    #[must_use]
+    pub fn get(&self, key_id: &str) -> Option<&ApiKeyRecord> {
+        self.records.get(key_id)
+    }
+
+    pub fn records(&self) -> impl Iterator<Item = &ApiKeyRecord> {
+        self.records.values()
+    }
+
     pub fn revoke(&mut self, key_id: &str, revoked_at_unix_s: u64) -> bool {
         if let Some(record) = self.records.get_mut(key_id) {
             record.revoked_at_unix_s = Some(revoked_at_unix_s);
@@ -61,12 +130,7 @@ impl ApiKeyStore {
     pub fn list_active_ids(&self, now_unix_s: u64) -> Vec<String> {
         self.records
             .values()
-            .filter(|record| {
-                record.revoked_at_unix_s.is_none()
-                    && record
-                        .expires_at_unix_s
-                        .is_none_or(|expiry| now_unix_s <= expiry)
-            })
+            .filter(|record| record.is_active(now_unix_s))
             .map(|record| record.key_id.clone())
             .collect()
     }
@@ -107,6 +171,8 @@ pub struct TokenClaims {
     pub iat: Option<u64>,
     pub jti: Option<String>,
     pub scope: Option<String>,
+    #[serde(default)]
+    pub groups: Vec<String>,
 }
 
 impl TokenClaims {
@@ -155,6 +221,30 @@ pub enum AuthValidationError {
     TokenAudienceInvalid,
     TokenScopeDenied,
     TokenRevoked,
+    TokenAlgorithmRejected,
+    TokenKeyUnknown,
+}
+
+impl AuthValidationError {
+    /// Stable reason code for metrics and error details.
+    #[must_use]
+    pub const fn as_code(&self) -> &'static str {
+        match self {
+            Self::ApiKeyInvalid => "api_key_invalid",
+            Self::ApiKeyExpired => "api_key_expired",
+            Self::ApiKeyRevoked => "api_key_revoked",
+            Self::TokenMalformed => "token_malformed",
+            Self::TokenSignatureInvalid => "token_signature_invalid",
+            Self::TokenExpired => "token_expired",
+            Self::TokenNotYetValid => "token_not_yet_valid",
+            Self::TokenIssuerInvalid => "token_issuer_invalid",
+            Self::TokenAudienceInvalid => "token_audience_invalid",
+            Self::TokenScopeDenied => "token_scope_missing",
+            Self::TokenRevoked => "token_revoked",
+            Self::TokenAlgorithmRejected => "token_algorithm_rejected",
+            Self::TokenKeyUnknown => "token_key_unknown",
+        }
+    }
 }
 
 #[must_use]
@@ -162,14 +252,17 @@ pub fn hash_api_key(raw_key: &str) -> String {
     sha256_hex(raw_key.as_bytes())
 }
 
-#[must_use]
-pub fn generate_api_key(key_id: &str, owner: &str) -> String {
-    let now = unix_now_s();
-    let material = format!("atlas-key:{key_id}:{owner}:{now}");
-    let digest = sha256_hex(material.as_bytes());
-    format!("atlas_{digest}")
+/// A fresh `atlas_`-prefixed key drawn from the operating-system CSPRNG.
+pub fn generate_api_key() -> Result<String, String> {
+    let mut material = [0_u8; 32];
+    ring::rand::SecureRandom::fill(&ring::rand::SystemRandom::new(), &mut material)
+        .map_err(|_| "api key generation failed: system random source unavailable".to_string())?;
+    Ok(format!("atlas_{}", hex::encode(material)))
 }
 
+/// Registers `next_key_id` with a fresh key carrying the scopes, dataset
+/// restrictions and tier of `current_key_id`, which stays valid for
+/// `overlap_ttl_secs`. Returns the new raw key.
 pub fn rotate_api_key(
     store: &mut ApiKeyStore,
     current_key_id: &str,
@@ -177,19 +270,39 @@ pub fn rotate_api_key(
     owner: &str,
     overlap_ttl_secs: u64,
     now_unix_s: u64,
-) -> String {
-    let next_raw = generate_api_key(next_key_id, owner);
+) -> Result<String, String> {
+    let next_raw = generate_api_key()?;
+    let (scopes, datasets, rate_limit_tier) = store
+        .records
+        .get(current_key_id)
+        .map(|record| {
+            (
+                record.scopes.clone(),
+                record.datasets.clone(),
+                record.rate_limit_tier.clone(),
+            )
+        })
+        .unwrap_or_default();
     store.upsert(ApiKeyRecord {
         key_id: next_key_id.to_string(),
         owner: owner.to_string(),
         key_hash: hash_api_key(&next_raw),
         expires_at_unix_s: None,
         revoked_at_unix_s: None,
+        created_at_unix_s: Some(now_unix_s),
+        scopes,
+        datasets,
+        rate_limit_tier,
     });
     if let Some(current) = store.records.get_mut(current_key_id) {
-        current.expires_at_unix_s = Some(now_unix_s.saturating_add(overlap_ttl_secs));
+        let overlap_end = now_unix_s.saturating_add(overlap_ttl_secs);
+        current.expires_at_unix_s = Some(
+            current
+                .expires_at_unix_s
+                .map_or(overlap_end, |expiry| expiry.min(overlap_end)),
+        );
     }
-    next_raw
+    Ok(next_raw)
 }
 
 pub fn mint_signed_token(claims: &TokenClaims, signing_secret: &str) -> Result<String, String> {
@@ -323,19 +436,14 @@ fn sign_token_payload(payload_b64: &str, signing_secret: &str) -> String {
     sha256_hex(material.as_bytes())
 }
 
-fn unix_now_s() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map_or(0, |elapsed| elapsed.as_secs())
-}
-
 #[cfg(test)]
 mod tests {
     use super::{
         authentication_context_from_token, extract_request_identity, hash_api_key,
-        mint_signed_token, rotate_api_key, validate_signed_token, ApiKeyRecord, ApiKeyStore,
-        AuthValidationError, TokenClaims, TokenValidationPolicy,
+        mint_signed_token, rotate_api_key, validate_dataset_pattern, validate_signed_token,
+        ApiKeyRecord, ApiKeyStore, AuthValidationError, TokenClaims, TokenValidationPolicy,
     };
+    use crate::domain::dataset::DatasetId;
     use std::collections::{BTreeMap, BTreeSet};
 
     #[test]
@@ -347,6 +455,10 @@ mod tests {
             key_hash: hash_api_key("atlas_secret"),
             expires_at_unix_s: Some(200),
             revoked_at_unix_s: None,
+            created_at_unix_s: None,
+            scopes: Vec::new(),
+            datasets: Vec::new(),
+            rate_limit_tier: None,
         });
         assert!(store.validate_raw_key("atlas_secret", 150).is_ok());
         assert_eq!(
@@ -369,6 +481,10 @@ mod tests {
             key_hash: hash_api_key("atlas_old"),
             expires_at_unix_s: None,
             revoked_at_unix_s: None,
+            created_at_unix_s: None,
+            scopes: vec!["dataset.read".to_string()],
+            datasets: vec!["110/homo_sapiens/*".to_string()],
+            rate_limit_tier: Some("bulk".to_string()),
         });
         let new_key = rotate_api_key(
             &mut store,
@@ -377,11 +493,63 @@ mod tests {
             "svc:query",
             300,
             1_000,
+        )
+        .expect("rotate");
+        assert!(new_key.starts_with("atlas_"));
+        assert_eq!(
+            store.validate_raw_key(&new_key, 1_001).map(|record| (
+                record.scopes.clone(),
+                record.datasets.clone(),
+                record.rate_limit_tier.clone()
+            )),
+            Ok((
+                vec!["dataset.read".to_string()],
+                vec!["110/homo_sapiens/*".to_string()],
+                Some("bulk".to_string())
+            ))
         );
-        assert!(store.validate_raw_key(&new_key, 1_001).is_ok());
         let active = store.list_active_ids(1_100);
         assert!(active.iter().any(|id| id == "atlas-key-a"));
         assert!(active.iter().any(|id| id == "atlas-key-b"));
+        assert_eq!(
+            store.validate_raw_key("atlas_old", 1_301),
+            Err(AuthValidationError::ApiKeyExpired)
+        );
+    }
+
+    #[test]
+    fn api_key_scopes_and_dataset_patterns_restrict_requests() {
+        let record = ApiKeyRecord {
+            key_id: "atlas-key-c".to_string(),
+            owner: "svc:curation".to_string(),
+            key_hash: hash_api_key("atlas_scoped"),
+            expires_at_unix_s: None,
+            revoked_at_unix_s: None,
+            created_at_unix_s: None,
+            scopes: vec!["dataset.read".to_string()],
+            datasets: vec![
+                "110/homo_sapiens/*".to_string(),
+                "*/mus_musculus/GRCm39".to_string(),
+            ],
+            rate_limit_tier: None,
+        };
+        assert!(record.allows_action("dataset.read"));
+        assert!(!record.allows_action("ops.admin"));
+        let dataset = |release, species, assembly| {
+            DatasetId::new(release, species, assembly).expect("dataset")
+        };
+        assert!(record.allows_dataset(&dataset("110", "homo_sapiens", "GRCh38")));
+        assert!(record.allows_dataset(&dataset("111", "mus_musculus", "GRCm39")));
+        assert!(!record.allows_dataset(&dataset("111", "homo_sapiens", "GRCh38")));
+        assert!(!record.allows_dataset(&dataset("110", "danio_rerio", "GRCz11")));
+        assert!(!record.allows_dataset(&dataset("111", "mus_musculus", "GRCm38")));
+        let unrestricted = ApiKeyRecord {
+            datasets: Vec::new(),
+            ..record.clone()
+        };
+        assert!(unrestricted.allows_dataset(&dataset("111", "danio_rerio", "GRCz11")));
+        assert!(validate_dataset_pattern("110/homo_sapiens/*").is_ok());
+        assert!(validate_dataset_pattern("110/homo_sapiens").is_err());
     }
 
     #[test]
@@ -395,6 +563,7 @@ mod tests {
             iat: Some(1_000),
             jti: Some("jti-1".to_string()),
             scope: Some("dataset.read profile".to_string()),
+            groups: Vec::new(),
         };
         let token = mint_signed_token(&claims, "secret").expect("token");
         let mut policy = TokenValidationPolicy::new("atlas-auth", "atlas-api");
@@ -432,6 +601,7 @@ mod tests {
             iat: None,
             jti: None,
             scope: Some("dataset.read".to_string()),
+            groups: Vec::new(),
         };
         let context = authentication_context_from_token(&claims, identity);
         assert_eq!(context.principal, "user");
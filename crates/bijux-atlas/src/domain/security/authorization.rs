@@ -4,6 +4,8 @@ use std::collections::{BTreeMap, BTreeSet};
 
 use serde::{Deserialize, Serialize};
 
+use crate::domain::dataset::DatasetId;
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PermissionDefinition {
     pub id: String,
@@ -56,10 +58,194 @@ pub struct RoleCatalog {
     pub roles: Vec<RoleDefinition>,
 }
 
+/// One role grant. Exactly one of `principal`, `subject`, `group` or
+/// `api_key_owner` names who receives `role_id`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoleAssignment {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub principal: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key_owner: Option<String>,
+    pub role_id: String,
+}
+
+impl RoleAssignment {
+    pub fn selector(&self) -> Result<RoleBindingSelector, String> {
+        let selectors = [
+            self.principal.clone().map(RoleBindingSelector::Principal),
+            self.subject.clone().map(RoleBindingSelector::Subject),
+            self.group.clone().map(RoleBindingSelector::Group),
+            self.api_key_owner
+                .clone()
+                .map(RoleBindingSelector::ApiKeyOwner),
+        ];
+        let mut present = selectors.into_iter().flatten();
+        match (present.next(), present.next()) {
+            (Some(selector), None) => Ok(selector),
+            _ => Err(format!(
+                "assignment for {} must name exactly one of principal, subject, group or api_key_owner",
+                self.role_id
+            )),
+        }
+    }
+}
+
+/// Restricts datasets of the listed species and/or releases to callers that
+/// hold one of `roles`. An empty list matches every value on that axis.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DatasetScopeRule {
+    pub id: String,
+    #[serde(default)]
+    pub species: Vec<String>,
+    #[serde(default)]
+    pub releases: Vec<String>,
+    pub roles: Vec<String>,
+}
+
+impl DatasetScopeRule {
+    /// Scopes only ever judge a fully resolved dataset, so a request cannot
+    /// slip past one by leaving a coordinate out.
+    #[must_use]
+    pub fn applies_to(&self, dataset: &DatasetId) -> bool {
+        let axis = |values: &[String], actual: &str| {
+            values.is_empty() || values.iter().any(|value| value == actual)
+        };
+        axis(&self.releases, dataset.release.as_str())
+            && axis(&self.species, dataset.species.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoleAssignmentCatalog {
+    pub schema_version: u64,
+    pub assignments: Vec<RoleAssignment>,
+    #[serde(default)]
+    pub dataset_scopes: Vec<DatasetScopeRule>,
+}
+
+impl RoleAssignmentCatalog {
+    /// Rejects assignments and scopes that name unknown roles, so a typo in
+    /// a binding file cannot silently lock callers out.
+    pub fn validate(&self, roles: &RoleCatalog) -> Result<(), String> {
+        let known = |role_id: &str| roles.roles.iter().any(|role| role.id == role_id);
+        for assignment in &self.assignments {
+            assignment.selector()?;
+            if !known(&assignment.role_id) {
+                return Err(format!("unknown role {}", assignment.role_id));
+            }
+        }
+        let mut scope_ids = BTreeSet::new();
+        for scope in &self.dataset_scopes {
+            if !scope_ids.insert(scope.id.as_str()) {
+                return Err(format!("duplicate dataset scope {}", scope.id));
+            }
+            if scope.species.is_empty() && scope.releases.is_empty() {
+                return Err(format!(
+                    "dataset scope {} must list species or releases",
+                    scope.id
+                ));
+            }
+            if scope.roles.is_empty() {
+                return Err(format!("dataset scope {} grants no roles", scope.id));
+            }
+            if let Some(unknown) = scope.roles.iter().find(|role_id| !known(role_id)) {
+                return Err(format!(
+                    "dataset scope {} names unknown role {unknown}",
+                    scope.id
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RoleBindingSelector {
+    Principal(String),
+    Subject(String),
+    Group(String),
+    ApiKeyOwner(String),
+}
+
+impl RoleBindingSelector {
+    #[must_use]
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            Self::Principal(_) => "principal",
+            Self::Subject(_) => "subject",
+            Self::Group(_) => "group",
+            Self::ApiKeyOwner(_) => "api_key_owner",
+        }
+    }
+
+    #[must_use]
+    pub fn value(&self) -> &str {
+        match self {
+            Self::Principal(value)
+            | Self::Subject(value)
+            | Self::Group(value)
+            | Self::ApiKeyOwner(value) => value,
+        }
+    }
+
+    fn matches(&self, identity: &AuthorizationIdentity<'_>) -> bool {
+        match self {
+            Self::Principal(value) => value == identity.principal,
+            Self::Subject(value) => value == identity.subject,
+            Self::Group(value) => identity.groups.iter().any(|group| group == value),
+            Self::ApiKeyOwner(value) => identity.api_key_owner == Some(value.as_str()),
+        }
+    }
+}
+
+/// Who is asking: the principal class the authentication mode assigned, plus
+/// the identity attributes role bindings can select on.
+#[derive(Debug, Clone, Copy)]
+pub struct AuthorizationIdentity<'a> {
+    pub principal: &'a str,
+    pub subject: &'a str,
+    pub groups: &'a [String],
+    pub api_key_owner: Option<&'a str>,
+}
+
+impl<'a> AuthorizationIdentity<'a> {
+    #[must_use]
+    pub const fn principal(principal: &'a str) -> Self {
+        Self {
+            principal,
+            subject: "",
+            groups: &[],
+            api_key_owner: None,
+        }
+    }
+}
+
+/// One authorization question. `dataset` is `None` while only the route is
+/// known; dataset scopes are judged once the handler has resolved it.
+#[derive(Debug, Clone, Copy)]
+pub struct AuthorizationRequest<'a> {
+    pub identity: AuthorizationIdentity<'a>,
+    pub action: &'a str,
+    pub resource_kind: &'a str,
+    pub route: &'a str,
+    pub dataset: Option<&'a DatasetId>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RoleBindingMatch {
+    pub kind: &'static str,
+    pub value: String,
+    pub role_id: String,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct RoleRegistry {
     roles: BTreeMap<String, RoleDefinition>,
-    principal_roles: BTreeMap<String, BTreeSet<String>>,
+    bindings: BTreeMap<RoleBindingSelector, BTreeSet<String>>,
 }
 
 impl RoleRegistry {
@@ -73,20 +259,42 @@ impl RoleRegistry {
     }
 
     pub fn assign_role(&mut self, principal: &str, role_id: &str) {
-        self.principal_roles
-            .entry(principal.to_string())
+        self.bind(
+            RoleBindingSelector::Principal(principal.to_string()),
+            role_id,
+        );
+    }
+
+    pub fn bind(&mut self, selector: RoleBindingSelector, role_id: &str) {
+        self.bindings
+            .entry(selector)
             .or_default()
             .insert(role_id.to_string());
     }
 
     #[must_use]
     pub fn roles_for_principal(&self, principal: &str) -> BTreeSet<String> {
-        self.principal_roles
-            .get(principal)
+        self.bindings
+            .get(&RoleBindingSelector::Principal(principal.to_string()))
             .cloned()
             .unwrap_or_default()
     }
 
+    #[must_use]
+    pub fn bindings_for(&self, identity: &AuthorizationIdentity<'_>) -> Vec<RoleBindingMatch> {
+        self.bindings
+            .iter()
+            .filter(|(selector, _)| selector.matches(identity))
+            .flat_map(|(selector, role_ids)| {
+                role_ids.iter().map(|role_id| RoleBindingMatch {
+                    kind: selector.kind(),
+                    value: selector.value().to_string(),
+                    role_id: role_id.clone(),
+                })
+            })
+            .collect()
+    }
+
     fn collect_effective_roles(&self, assigned: &BTreeSet<String>) -> BTreeSet<String> {
         let mut effective = BTreeSet::new();
         let mut queue = assigned.iter().cloned().collect::<Vec<_>>();
@@ -140,25 +348,55 @@ impl PermissionEvaluator {
         action: &str,
         resource_kind: &str,
     ) -> bool {
-        permission_ids.iter().any(|id| {
-            self.by_id.get(id).is_some_and(|permission| {
-                permission.action == action && permission.resource_kind == resource_kind
+        self.matching_permission(permission_ids, action, resource_kind)
+            .is_some()
+    }
+
+    #[must_use]
+    pub fn matching_permission<'a>(
+        &self,
+        permission_ids: &'a BTreeSet<String>,
+        action: &str,
+        resource_kind: &str,
+    ) -> Option<&'a str> {
+        permission_ids
+            .iter()
+            .find(|id| {
+                self.by_id.get(*id).is_some_and(|permission| {
+                    permission.action == action && permission.resource_kind == resource_kind
+                })
             })
-        })
+            .map(String::as_str)
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum AuthorizationDecision {
     Allow,
     Deny,
 }
 
+/// Why a request was allowed or denied: the bindings that granted roles, the
+/// permission that covered the action, and the policy rule or dataset scope
+/// that decided.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AuthorizationExplanation {
+    pub decision: AuthorizationDecision,
+    pub reason: &'static str,
+    pub bindings: Vec<RoleBindingMatch>,
+    pub roles: Vec<String>,
+    pub permission: Option<String>,
+    pub policy_rule: Option<String>,
+    pub dataset_scopes: Vec<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct AuthorizationEngine {
     role_registry: RoleRegistry,
     permission_evaluator: PermissionEvaluator,
     policy: AuthorizationPolicy,
+    dataset_scopes: Vec<DatasetScopeRule>,
 }
 
 impl AuthorizationEngine {
@@ -172,9 +410,16 @@ impl AuthorizationEngine {
             role_registry,
             permission_evaluator,
             policy,
+            dataset_scopes: Vec::new(),
         }
     }
 
+    #[must_use]
+    pub fn with_dataset_scopes(mut self, dataset_scopes: Vec<DatasetScopeRule>) -> Self {
+        self.dataset_scopes = dataset_scopes;
+        self
+    }
+
     #[must_use]
     pub fn evaluate(
         &self,
@@ -183,48 +428,102 @@ impl AuthorizationEngine {
         resource_kind: &str,
         route: &str,
     ) -> AuthorizationDecision {
-        let assigned = self.role_registry.roles_for_principal(principal);
-        let permissions = self.role_registry.resolve_permissions(&assigned);
-        if !self
-            .permission_evaluator
-            .has_matching_permission(&permissions, action, resource_kind)
-        {
-            return AuthorizationDecision::Deny;
-        }
+        self.explain(&AuthorizationRequest {
+            identity: AuthorizationIdentity::principal(principal),
+            action,
+            resource_kind,
+            route,
+            dataset: None,
+        })
+        .decision
+    }
 
-        for rule in &self.policy.rules {
-            let principal_match = rule.principals.iter().any(|value| value == principal);
-            let action_match = rule.actions.iter().any(|value| value == action);
-            let resource_match = rule
-                .resources
-                .kinds
+    #[must_use]
+    pub fn explain(&self, request: &AuthorizationRequest<'_>) -> AuthorizationExplanation {
+        let bindings = self.role_registry.bindings_for(&request.identity);
+        let assigned = bindings
+            .iter()
+            .map(|binding| binding.role_id.clone())
+            .collect::<BTreeSet<_>>();
+        let effective = self.role_registry.collect_effective_roles(&assigned);
+        let permissions = self.role_registry.resolve_permissions(&assigned);
+        let mut explanation = AuthorizationExplanation {
+            decision: AuthorizationDecision::Deny,
+            reason: "no_matching_permission",
+            bindings,
+            roles: effective.iter().cloned().collect(),
+            permission: None,
+            policy_rule: None,
+            dataset_scopes: Vec::new(),
+        };
+        let Some(permission) = self.permission_evaluator.matching_permission(
+            &permissions,
+            request.action,
+            request.resource_kind,
+        ) else {
+            return explanation;
+        };
+        explanation.permission = Some(permission.to_string());
+
+        let rule = self.policy.rules.iter().find(|rule| {
+            rule.principals
                 .iter()
-                .any(|value| value == resource_kind);
-            let route_match = rule.routes.iter().any(|prefix| route.starts_with(prefix));
-            if principal_match && action_match && resource_match && route_match {
-                return if rule.effect == "allow" {
-                    AuthorizationDecision::Allow
-                } else {
-                    AuthorizationDecision::Deny
-                };
+                .any(|value| value == request.identity.principal)
+                && rule.actions.iter().any(|value| value == request.action)
+                && rule
+                    .resources
+                    .kinds
+                    .iter()
+                    .any(|value| value == request.resource_kind)
+                && rule
+                    .routes
+                    .iter()
+                    .any(|prefix| request.route.starts_with(prefix))
+        });
+        let allowed = match rule {
+            Some(rule) => {
+                explanation.reason = "policy_rule";
+                explanation.policy_rule = Some(rule.id.clone());
+                rule.effect == "allow"
             }
+            None => {
+                explanation.reason = "policy_default";
+                self.policy.default_decision == "allow"
+            }
+        };
+        if !allowed {
+            return explanation;
         }
 
-        if self.policy.default_decision == "allow" {
-            AuthorizationDecision::Allow
-        } else {
-            AuthorizationDecision::Deny
+        for scope in self.dataset_scopes.iter().filter(|scope| {
+            request
+                .dataset
+                .is_some_and(|dataset| scope.applies_to(dataset))
+        }) {
+            explanation.dataset_scopes.push(scope.id.clone());
+            if !scope
+                .roles
+                .iter()
+                .any(|role_id| effective.contains(role_id))
+            {
+                explanation.reason = "dataset_scope";
+                return explanation;
+            }
         }
+        explanation.decision = AuthorizationDecision::Allow;
+        explanation
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::{
-        AuthorizationDecision, AuthorizationEngine, AuthorizationPolicy, AuthorizationPolicyRule,
-        AuthorizationResources, PermissionCatalog, PermissionDefinition, PermissionEvaluator,
-        RoleCatalog, RoleDefinition, RoleRegistry,
+        AuthorizationDecision, AuthorizationEngine, AuthorizationIdentity, AuthorizationPolicy,
+        AuthorizationPolicyRule, AuthorizationRequest, AuthorizationResources, DatasetScopeRule,
+        PermissionCatalog, PermissionDefinition, PermissionEvaluator, RoleAssignment,
+        RoleAssignmentCatalog, RoleBindingSelector, RoleCatalog, RoleDefinition, RoleRegistry,
     };
+    use crate::domain::dataset::DatasetId;
 
     fn permission_catalog() -> PermissionCatalog {
         PermissionCatalog {
@@ -306,6 +605,19 @@ mod tests {
         }
     }
 
+    fn catalog_request<'a>(
+        identity: AuthorizationIdentity<'a>,
+        dataset: Option<&'a DatasetId>,
+    ) -> AuthorizationRequest<'a> {
+        AuthorizationRequest {
+            identity,
+            action: "catalog.read",
+            resource_kind: "namespace",
+            route: "/v1/datasets",
+            dataset,
+        }
+    }
+
     #[test]
     fn authorization_engine_denies_principal_without_permission() {
         let evaluator = PermissionEvaluator::new(permission_catalog());
@@ -363,4 +675,101 @@ mod tests {
             AuthorizationDecision::Deny
         );
     }
+
+    #[test]
+    fn group_and_api_key_owner_bindings_grant_roles_and_dataset_scopes_restrict_them() {
+        let evaluator = PermissionEvaluator::new(permission_catalog());
+        let mut registry = RoleRegistry::new();
+        for role in role_catalog().roles {
+            registry.upsert_role(role);
+        }
+        registry.bind(
+            RoleBindingSelector::Group("genomics-admins".to_string()),
+            "role.operator.admin",
+        );
+        registry.bind(
+            RoleBindingSelector::ApiKeyOwner("ingest-bot".to_string()),
+            "role.user.readonly",
+        );
+        let engine =
+            AuthorizationEngine::new(registry, evaluator, policy()).with_dataset_scopes(vec![
+                DatasetScopeRule {
+                    id: "SCOPE-HUMAN".to_string(),
+                    species: vec!["homo_sapiens".to_string()],
+                    releases: Vec::new(),
+                    roles: vec!["role.operator.admin".to_string()],
+                },
+            ]);
+        let admins = ["genomics-admins".to_string()];
+        let human = DatasetId::new("110", "homo_sapiens", "GRCh38").expect("human dataset");
+        let mouse = DatasetId::new("110", "mus_musculus", "GRCm39").expect("mouse dataset");
+
+        let admin = AuthorizationIdentity {
+            principal: "user",
+            subject: "alice",
+            groups: &admins,
+            api_key_owner: None,
+        };
+        let explained = engine.explain(&catalog_request(admin, Some(&human)));
+        assert_eq!(explained.decision, AuthorizationDecision::Allow);
+        assert_eq!(explained.bindings[0].kind, "group");
+        assert_eq!(explained.policy_rule.as_deref(), Some("AUTHZ-CATALOG-READ"));
+        assert_eq!(explained.dataset_scopes, vec!["SCOPE-HUMAN".to_string()]);
+
+        let bot = AuthorizationIdentity {
+            principal: "user",
+            subject: "service",
+            groups: &[],
+            api_key_owner: Some("ingest-bot"),
+        };
+        assert_eq!(
+            engine.explain(&catalog_request(bot, Some(&mouse))).decision,
+            AuthorizationDecision::Allow
+        );
+        let scoped_out = engine.explain(&catalog_request(bot, Some(&human)));
+        assert_eq!(scoped_out.decision, AuthorizationDecision::Deny);
+        assert_eq!(scoped_out.reason, "dataset_scope");
+        assert_eq!(scoped_out.permission.as_deref(), Some("perm.catalog.read"));
+        // Without a resolved dataset only the route is judged; the handler
+        // asks again once it knows which dataset the request reads.
+        let route_only = engine.explain(&catalog_request(bot, None));
+        assert_eq!(route_only.decision, AuthorizationDecision::Allow);
+        assert!(route_only.dataset_scopes.is_empty());
+
+        let nobody = engine.explain(&catalog_request(
+            AuthorizationIdentity::principal("user"),
+            Some(&mouse),
+        ));
+        assert_eq!(nobody.decision, AuthorizationDecision::Deny);
+        assert_eq!(nobody.reason, "no_matching_permission");
+    }
+
+    #[test]
+    fn role_assignment_catalog_rejects_ambiguous_selectors_and_unknown_roles() {
+        let assignment = |group: Option<&str>, role_id: &str| RoleAssignment {
+            principal: Some("user".to_string()),
+            subject: None,
+            group: group.map(ToString::to_string),
+            api_key_owner: None,
+            role_id: role_id.to_string(),
+        };
+        let catalog = |assignments| RoleAssignmentCatalog {
+            schema_version: 1,
+            assignments,
+            dataset_scopes: Vec::new(),
+        };
+        assert!(catalog(vec![assignment(None, "role.user.readonly")])
+            .validate(&role_catalog())
+            .is_ok());
+        assert!(
+            catalog(vec![assignment(Some("admins"), "role.user.readonly")])
+                .validate(&role_catalog())
+                .expect_err("two selectors")
+                .contains("exactly one")
+        );
+        assert!(catalog(vec![assignment(None, "role.missing")])
+            .validate(&role_catalog())
+            .expect_err("unknown role")
+            .contains("role.missing"));
+    }
 }
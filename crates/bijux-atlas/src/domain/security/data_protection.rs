@@ -4,9 +4,13 @@ use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::signature::{Ed25519KeyPair, KeyPair, UnparsedPublicKey, ED25519};
 use serde::{Deserialize, Serialize};
 
-use crate::domain::canonical::sha256_hex;
+use super::runtime::{KeyManager, SecretsProvider};
+use crate::domain::canonical::{sha256_hex, stable_json_bytes};
+use crate::domain::dataset::{ArtifactManifest, DatasetId};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct DataProtectionPolicy {
@@ -136,38 +140,344 @@ pub fn tls_handshake_allowed(
     )
 }
 
+/// Leading bytes of every artifact sealed by [`AeadEncryption`].
+pub const ENCRYPTED_ARTIFACT_MAGIC: &[u8; 4] = b"ATLE";
+pub const ENCRYPTED_ARTIFACT_VERSION: u8 = 2;
+/// Plaintext bytes per sealed segment.
+const SEGMENT_LEN: usize = 64 * 1024;
+const ALGORITHM_AES_256_GCM: u8 = 1;
+const AES_256_KEY_LEN: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EncryptionError {
+    NoActiveKey,
+    UnknownKeyId(String),
+    InvalidKey(String),
+    MalformedHeader(String),
+    UnsupportedVersion(u8),
+    Authentication,
+    NonceGeneration,
+    Io(String),
+}
+
+impl std::fmt::Display for EncryptionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoActiveKey => write!(f, "no active encryption key"),
+            Self::UnknownKeyId(key_id) => write!(f, "unknown encryption key id: {key_id}"),
+            Self::InvalidKey(message) => write!(f, "invalid encryption key: {message}"),
+            Self::MalformedHeader(message) => write!(f, "malformed ciphertext header: {message}"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported ciphertext version: {version}")
+            }
+            Self::Authentication => write!(f, "ciphertext authentication failed"),
+            Self::NonceGeneration => write!(f, "nonce generation failed"),
+            Self::Io(message) => write!(f, "sealed artifact i/o failed: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for EncryptionError {}
+
+/// Source of per-object nonces; kept injectable so this module stays deterministic.
+pub trait NonceSource {
+    fn fill(&self, nonce: &mut [u8]) -> Result<(), EncryptionError>;
+}
+
 pub trait EncryptionAtRest {
-    fn encrypt(&self, plaintext: &[u8]) -> Vec<u8>;
-    fn decrypt(&self, ciphertext: &[u8]) -> Vec<u8>;
+    fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, EncryptionError>;
+    fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>, EncryptionError>;
+
+    /// Opens the sealed file `sealed` into `plain`, returning the plaintext
+    /// length. The default reads the whole object; implementations with a
+    /// segmented format override it to stream.
+    fn decrypt_file(&self, sealed: &Path, plain: &Path) -> Result<u64, EncryptionError> {
+        let ciphertext = fs::read(sealed).map_err(|e| EncryptionError::Io(e.to_string()))?;
+        let plaintext = self.decrypt(&ciphertext)?;
+        fs::write(plain, &plaintext).map_err(|e| EncryptionError::Io(e.to_string()))?;
+        Ok(plaintext.len() as u64)
+    }
 }
 
-#[derive(Debug, Clone)]
-pub struct XorEncryption {
-    key: Vec<u8>,
+#[must_use]
+pub fn is_encrypted_artifact(bytes: &[u8]) -> bool {
+    bytes.starts_with(ENCRYPTED_ARTIFACT_MAGIC)
 }
 
-impl XorEncryption {
-    #[must_use]
-    pub fn new(key: &[u8]) -> Self {
-        Self { key: key.to_vec() }
+/// AES-256-GCM with a fresh random nonce per object.
+///
+/// Sealed layout: `magic | version | algorithm | key_id_len | key_id | nonce |
+/// segments`. Each segment is up to 64 KiB of ciphertext followed by its tag,
+/// sealed under the object nonce with the segment index XORed into its last
+/// four bytes. The header, the segment index and a final-segment flag are the
+/// associated data, so segments cannot be reordered, dropped or truncated,
+/// and a reader can open the object one segment at a time. Version 1 objects
+/// are a single ciphertext+tag and still open. New objects are sealed with
+/// the active key of the [`KeyManager`]; any registered key opens objects
+/// tagged with its id, so artifacts written before a rotation stay readable.
+pub struct AeadEncryption {
+    keys: KeyManager,
+    material: BTreeMap<String, LessSafeKey>,
+    nonces: Box<dyn NonceSource + Send + Sync>,
+}
+
+impl std::fmt::Debug for AeadEncryption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AeadEncryption")
+            .field("keys", &self.keys)
+            .field("key_ids", &self.material.keys().collect::<Vec<_>>())
+            .finish()
     }
 }
 
-impl EncryptionAtRest for XorEncryption {
-    fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
-        if self.key.is_empty() {
-            return plaintext.to_vec();
+impl AeadEncryption {
+    pub fn new(
+        keys: KeyManager,
+        material: BTreeMap<String, Vec<u8>>,
+        nonces: Box<dyn NonceSource + Send + Sync>,
+    ) -> Result<Self, EncryptionError> {
+        let mut sealed = BTreeMap::new();
+        for (key_id, bytes) in material {
+            if keys.get(&key_id).is_none() {
+                return Err(EncryptionError::UnknownKeyId(key_id));
+            }
+            if key_id.is_empty() || key_id.len() > usize::from(u8::MAX) {
+                return Err(EncryptionError::InvalidKey(format!(
+                    "key id must be 1..=255 bytes: {key_id}"
+                )));
+            }
+            if bytes.len() != AES_256_KEY_LEN {
+                return Err(EncryptionError::InvalidKey(format!(
+                    "{key_id} must be {AES_256_KEY_LEN} bytes"
+                )));
+            }
+            let unbound = UnboundKey::new(&AES_256_GCM, &bytes)
+                .map_err(|_| EncryptionError::InvalidKey(key_id.clone()))?;
+            sealed.insert(key_id, LessSafeKey::new(unbound));
+        }
+        Ok(Self {
+            keys,
+            material: sealed,
+            nonces,
+        })
+    }
+
+    /// Resolves each key's material as a hex secret referenced by its key id.
+    pub fn from_secrets(
+        keys: KeyManager,
+        secrets: &dyn SecretsProvider,
+        key_ids: &[&str],
+        nonces: Box<dyn NonceSource + Send + Sync>,
+    ) -> Result<Self, EncryptionError> {
+        let mut material = BTreeMap::new();
+        for key_id in key_ids {
+            let secret = secrets
+                .get_secret(key_id)
+                .ok_or_else(|| EncryptionError::UnknownKeyId((*key_id).to_string()))?;
+            let bytes = hex::decode(secret.trim())
+                .map_err(|_| EncryptionError::InvalidKey(format!("{key_id} is not hex")))?;
+            material.insert((*key_id).to_string(), bytes);
+        }
+        Self::new(keys, material, nonces)
+    }
+
+    fn key(&self, key_id: &str) -> Result<&LessSafeKey, EncryptionError> {
+        self.keys
+            .get(key_id)
+            .and_then(|_| self.material.get(key_id))
+            .ok_or_else(|| EncryptionError::UnknownKeyId(key_id.to_string()))
+    }
+}
+
+impl EncryptionAtRest for AeadEncryption {
+    fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        let key_id = self
+            .keys
+            .active_key()
+            .map(|record| record.key_id.as_str())
+            .ok_or(EncryptionError::NoActiveKey)?;
+        let key = self.key(key_id)?;
+        let mut nonce = [0_u8; NONCE_LEN];
+        self.nonces.fill(&mut nonce)?;
+
+        let tag_len = key.algorithm().tag_len();
+        let segments = plaintext.len().div_ceil(SEGMENT_LEN).max(1);
+        let mut out =
+            Vec::with_capacity(7 + key_id.len() + NONCE_LEN + plaintext.len() + segments * tag_len);
+        out.extend_from_slice(ENCRYPTED_ARTIFACT_MAGIC);
+        out.push(ENCRYPTED_ARTIFACT_VERSION);
+        out.push(ALGORITHM_AES_256_GCM);
+        out.push(key_id.len() as u8);
+        out.extend_from_slice(key_id.as_bytes());
+        out.extend_from_slice(&nonce);
+        let header = out.clone();
+        for index in 0..segments {
+            let chunk = &plaintext[(index * SEGMENT_LEN).min(plaintext.len())
+                ..((index + 1) * SEGMENT_LEN).min(plaintext.len())];
+            let start = out.len();
+            out.extend_from_slice(chunk);
+            let tag = key
+                .seal_in_place_separate_tag(
+                    segment_nonce(nonce, index)?,
+                    Aad::from(segment_aad(&header, index, index + 1 == segments)?),
+                    &mut out[start..],
+                )
+                .map_err(|_| EncryptionError::Authentication)?;
+            out.extend_from_slice(tag.as_ref());
+        }
+        Ok(out)
+    }
+
+    fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        let header = parse_header(ciphertext)?;
+        let key = self.key(header.key_id)?;
+        let tag_len = key.algorithm().tag_len();
+        let body = &ciphertext[header.len..];
+        if body.len() < tag_len {
+            return Err(EncryptionError::MalformedHeader("truncated".to_string()));
+        }
+        let mut plaintext = Vec::with_capacity(body.len());
+        let segments: Vec<&[u8]> = body.chunks(SEGMENT_LEN + tag_len).collect();
+        for (index, segment) in segments.iter().enumerate() {
+            let mut segment = segment.to_vec();
+            let opened = open_segment(
+                key,
+                &header,
+                &ciphertext[..header.len],
+                index,
+                index + 1 == segments.len(),
+                &mut segment,
+            )?;
+            plaintext.extend_from_slice(opened);
+        }
+        Ok(plaintext)
+    }
+
+    /// Streams the object one segment at a time, so memory stays at one
+    /// segment whatever the artifact size.
+    fn decrypt_file(&self, sealed: &Path, plain: &Path) -> Result<u64, EncryptionError> {
+        use std::io::{BufRead as _, BufReader, Write as _};
+        let io = |e: std::io::Error| EncryptionError::Io(e.to_string());
+        let mut reader = BufReader::new(fs::File::open(sealed).map_err(io)?);
+        let mut raw_header = vec![0_u8; ENCRYPTED_ARTIFACT_MAGIC.len() + 3];
+        read_up_to(&mut reader, &mut raw_header).map_err(io)?;
+        let key_id_len = raw_header.last().copied().map_or(0, usize::from);
+        raw_header.resize(raw_header.len() + key_id_len + NONCE_LEN, 0);
+        let fixed = ENCRYPTED_ARTIFACT_MAGIC.len() + 3;
+        let read = read_up_to(&mut reader, &mut raw_header[fixed..]).map_err(io)?;
+        raw_header.truncate(fixed + read);
+        let header = parse_header(&raw_header)?;
+        let key = self.key(header.key_id)?;
+        let mut out = std::io::BufWriter::new(fs::File::create(plain).map_err(io)?);
+        let mut segment = vec![0_u8; SEGMENT_LEN + key.algorithm().tag_len()];
+        let mut written = 0_u64;
+        for index in 0.. {
+            let n = read_up_to(&mut reader, &mut segment).map_err(io)?;
+            let last = n < segment.len() || reader.fill_buf().map_err(io)?.is_empty();
+            if n < key.algorithm().tag_len() {
+                return Err(EncryptionError::MalformedHeader("truncated".to_string()));
+            }
+            let opened = open_segment(key, &header, &raw_header, index, last, &mut segment[..n])?;
+            out.write_all(opened).map_err(io)?;
+            written += opened.len() as u64;
+            if last {
+                break;
+            }
+        }
+        out.into_inner()
+            .map_err(|e| io(e.into_error()))?
+            .sync_all()
+            .map_err(io)?;
+        Ok(written)
+    }
+}
+
+/// Fills `buf` unless the reader ends first; returns the bytes read.
+fn read_up_to(reader: &mut impl std::io::Read, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..])? {
+            0 => break,
+            n => filled += n,
         }
-        plaintext
-            .iter()
-            .enumerate()
-            .map(|(idx, value)| value ^ self.key[idx % self.key.len()])
-            .collect()
     }
+    Ok(filled)
+}
 
-    fn decrypt(&self, ciphertext: &[u8]) -> Vec<u8> {
-        self.encrypt(ciphertext)
+fn segment_nonce(nonce: [u8; NONCE_LEN], index: usize) -> Result<Nonce, EncryptionError> {
+    let index = u32::try_from(index)
+        .map_err(|_| EncryptionError::MalformedHeader("too many segments".to_string()))?;
+    let mut nonce = nonce;
+    for (byte, counter) in nonce[NONCE_LEN - 4..].iter_mut().zip(index.to_be_bytes()) {
+        *byte ^= counter;
     }
+    Ok(Nonce::assume_unique_for_key(nonce))
+}
+
+fn segment_aad(header: &[u8], index: usize, last: bool) -> Result<Vec<u8>, EncryptionError> {
+    let index = u32::try_from(index)
+        .map_err(|_| EncryptionError::MalformedHeader("too many segments".to_string()))?;
+    let mut aad = Vec::with_capacity(header.len() + 5);
+    aad.extend_from_slice(header);
+    aad.extend_from_slice(&index.to_be_bytes());
+    aad.push(u8::from(last));
+    Ok(aad)
+}
+
+fn open_segment<'a>(
+    key: &LessSafeKey,
+    header: &CiphertextHeader<'_>,
+    raw_header: &[u8],
+    index: usize,
+    last: bool,
+    segment: &'a mut [u8],
+) -> Result<&'a [u8], EncryptionError> {
+    let opened = key
+        .open_in_place(
+            segment_nonce(header.nonce, index)?,
+            Aad::from(segment_aad(raw_header, index, last)?),
+            segment,
+        )
+        .map_err(|_| EncryptionError::Authentication)?;
+    Ok(opened)
+}
+
+struct CiphertextHeader<'a> {
+    key_id: &'a str,
+    nonce: [u8; NONCE_LEN],
+    len: usize,
+}
+
+fn parse_header(bytes: &[u8]) -> Result<CiphertextHeader<'_>, EncryptionError> {
+    if !is_encrypted_artifact(bytes) {
+        return Err(EncryptionError::MalformedHeader(
+            "missing magic".to_string(),
+        ));
+    }
+    let fixed = ENCRYPTED_ARTIFACT_MAGIC.len();
+    let [version, algorithm, key_id_len] = bytes
+        .get(fixed..fixed + 3)
+        .and_then(|slice| <[u8; 3]>::try_from(slice).ok())
+        .ok_or_else(|| EncryptionError::MalformedHeader("truncated".to_string()))?;
+    if version != ENCRYPTED_ARTIFACT_VERSION {
+        return Err(EncryptionError::UnsupportedVersion(version));
+    }
+    if algorithm != ALGORITHM_AES_256_GCM {
+        return Err(EncryptionError::MalformedHeader(format!(
+            "unknown algorithm {algorithm}"
+        )));
+    }
+    let key_id_start = fixed + 3;
+    let nonce_start = key_id_start + usize::from(key_id_len);
+    let len = nonce_start + NONCE_LEN;
+    if bytes.len() < len {
+        return Err(EncryptionError::MalformedHeader("truncated".to_string()));
+    }
+    let key_id = std::str::from_utf8(&bytes[key_id_start..nonce_start])
+        .map_err(|_| EncryptionError::MalformedHeader("key id is not utf-8".to_string()))?;
+    let mut nonce = [0_u8; NONCE_LEN];
+    nonce.copy_from_slice(&bytes[nonce_start..len]);
+    Ok(CiphertextHeader { key_id, nonce, len })
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -202,6 +512,8 @@ pub fn verify_artifact_checksum(bytes: &[u8], expected_sha256: &str) -> bool {
     sha256_hex(bytes) == expected_sha256
 }
 
+/// Keyed digest check; the verifier holds the secret and could forge, so
+/// published datasets use [`TrustedSigningKeys`] instead.
 #[must_use]
 pub fn verify_artifact_signature(
     artifact_sha256: &str,
@@ -212,6 +524,195 @@ pub fn verify_artifact_signature(
     sha256_hex(payload.as_bytes()) == expected_signature
 }
 
+pub const MANIFEST_SIGNATURE_SCHEMA_VERSION: u64 = 1;
+pub const MANIFEST_SIGNATURE_ALGORITHM: &str = "ed25519";
+const ED25519_SEED_LEN: usize = 32;
+const ED25519_PUBLIC_KEY_LEN: usize = 32;
+
+/// Detached Ed25519 signature published next to `manifest.json` and `manifest.lock`.
+///
+/// The signed payload binds the dataset id, the SHA-256 of the canonical
+/// manifest JSON, and the SQLite digest recorded in the manifest lock.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestSignature {
+    pub schema_version: u64,
+    pub algorithm: String,
+    pub key_id: String,
+    pub manifest_sha256: String,
+    pub sqlite_sha256: String,
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignatureError {
+    InvalidKey(String),
+    Missing,
+    UntrustedKey(String),
+    PayloadMismatch(String),
+    Invalid,
+}
+
+impl std::fmt::Display for SignatureError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidKey(message) => write!(f, "invalid signing key: {message}"),
+            Self::Missing => write!(f, "manifest signature missing"),
+            Self::UntrustedKey(key_id) => write!(f, "manifest signed by untrusted key: {key_id}"),
+            Self::PayloadMismatch(field) => {
+                write!(f, "manifest signature does not cover this dataset: {field}")
+            }
+            Self::Invalid => write!(f, "manifest signature verification failed"),
+        }
+    }
+}
+
+impl std::error::Error for SignatureError {}
+
+/// Key id is the first 16 hex chars of the SHA-256 of the raw public key.
+#[must_use]
+pub fn signing_key_id(public_key: &[u8]) -> String {
+    sha256_hex(public_key)[..16].to_string()
+}
+
+pub fn canonical_manifest_sha256(manifest: &ArtifactManifest) -> Result<String, SignatureError> {
+    let bytes = stable_json_bytes(manifest)
+        .map_err(|e| SignatureError::PayloadMismatch(format!("manifest: {e}")))?;
+    Ok(sha256_hex(&bytes))
+}
+
+fn manifest_signing_payload(
+    dataset: &DatasetId,
+    manifest_sha256: &str,
+    sqlite_sha256: &str,
+) -> Result<Vec<u8>, SignatureError> {
+    stable_json_bytes(&serde_json::json!({
+        "schema_version": MANIFEST_SIGNATURE_SCHEMA_VERSION,
+        "dataset": dataset,
+        "manifest_sha256": manifest_sha256,
+        "sqlite_sha256": sqlite_sha256,
+    }))
+    .map_err(|e| SignatureError::PayloadMismatch(format!("payload: {e}")))
+}
+
+pub struct ArtifactSigningKey {
+    key_pair: Ed25519KeyPair,
+    key_id: String,
+}
+
+impl std::fmt::Debug for ArtifactSigningKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArtifactSigningKey")
+            .field("key_id", &self.key_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ArtifactSigningKey {
+    /// Parses a hex-encoded 32-byte Ed25519 seed.
+    pub fn from_seed_hex(seed_hex: &str) -> Result<Self, SignatureError> {
+        let seed = hex::decode(seed_hex.trim())
+            .map_err(|_| SignatureError::InvalidKey("seed is not hex".to_string()))?;
+        if seed.len() != ED25519_SEED_LEN {
+            return Err(SignatureError::InvalidKey(format!(
+                "seed must be {ED25519_SEED_LEN} bytes"
+            )));
+        }
+        let key_pair = Ed25519KeyPair::from_seed_unchecked(&seed)
+            .map_err(|e| SignatureError::InvalidKey(e.to_string()))?;
+        let key_id = signing_key_id(key_pair.public_key().as_ref());
+        Ok(Self { key_pair, key_id })
+    }
+
+    #[must_use]
+    pub fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    #[must_use]
+    pub fn public_key_hex(&self) -> String {
+        hex::encode(self.key_pair.public_key().as_ref())
+    }
+
+    pub fn sign_manifest(
+        &self,
+        manifest: &ArtifactManifest,
+        sqlite_sha256: &str,
+    ) -> Result<ManifestSignature, SignatureError> {
+        let manifest_sha256 = canonical_manifest_sha256(manifest)?;
+        let payload = manifest_signing_payload(&manifest.dataset, &manifest_sha256, sqlite_sha256)?;
+        Ok(ManifestSignature {
+            schema_version: MANIFEST_SIGNATURE_SCHEMA_VERSION,
+            algorithm: MANIFEST_SIGNATURE_ALGORITHM.to_string(),
+            key_id: self.key_id.clone(),
+            manifest_sha256,
+            sqlite_sha256: sqlite_sha256.to_string(),
+            signature: hex::encode(self.key_pair.sign(&payload).as_ref()),
+        })
+    }
+}
+
+/// Public keys a verifier accepts, indexed by [`signing_key_id`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrustedSigningKeys {
+    keys: BTreeMap<String, Vec<u8>>,
+}
+
+impl TrustedSigningKeys {
+    pub fn from_public_keys_hex<S: AsRef<str>>(keys: &[S]) -> Result<Self, SignatureError> {
+        let mut trusted = BTreeMap::new();
+        for key in keys {
+            let bytes = hex::decode(key.as_ref().trim())
+                .map_err(|_| SignatureError::InvalidKey("public key is not hex".to_string()))?;
+            if bytes.len() != ED25519_PUBLIC_KEY_LEN {
+                return Err(SignatureError::InvalidKey(format!(
+                    "public key must be {ED25519_PUBLIC_KEY_LEN} bytes"
+                )));
+            }
+            trusted.insert(signing_key_id(&bytes), bytes);
+        }
+        Ok(Self { keys: trusted })
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn verify_manifest(
+        &self,
+        manifest: &ArtifactManifest,
+        sqlite_sha256: &str,
+        signature: Option<&ManifestSignature>,
+    ) -> Result<(), SignatureError> {
+        let signature = signature.ok_or(SignatureError::Missing)?;
+        if signature.schema_version != MANIFEST_SIGNATURE_SCHEMA_VERSION
+            || signature.algorithm != MANIFEST_SIGNATURE_ALGORITHM
+        {
+            return Err(SignatureError::Invalid);
+        }
+        let public_key = self
+            .keys
+            .get(&signature.key_id)
+            .ok_or_else(|| SignatureError::UntrustedKey(signature.key_id.clone()))?;
+        let manifest_sha256 = canonical_manifest_sha256(manifest)?;
+        if manifest_sha256 != signature.manifest_sha256 {
+            return Err(SignatureError::PayloadMismatch(
+                "manifest_sha256".to_string(),
+            ));
+        }
+        if sqlite_sha256 != signature.sqlite_sha256 {
+            return Err(SignatureError::PayloadMismatch("sqlite_sha256".to_string()));
+        }
+        let payload = manifest_signing_payload(&manifest.dataset, &manifest_sha256, sqlite_sha256)?;
+        let raw_signature =
+            hex::decode(&signature.signature).map_err(|_| SignatureError::Invalid)?;
+        UnparsedPublicKey::new(&ED25519, public_key)
+            .verify(&payload, &raw_signature)
+            .map_err(|_| SignatureError::Invalid)
+    }
+}
+
 #[must_use]
 pub fn detect_tampering(
     expected_checksum: &str,
@@ -232,13 +733,46 @@ pub fn detect_tampering(
 #[cfg(test)]
 mod tests {
     use super::{
-        calculate_manifest_checksum, detect_tampering, https_enforced, load_certificate_bundle,
-        tls_handshake_allowed, validate_certificate_bundle, verify_artifact_checksum,
-        verify_artifact_signature, verify_dataset_manifest_integrity, CertificateRotationState,
-        DatasetManifestIntegrity, EncryptionAtRest, TlsConfig, XorEncryption,
+        calculate_manifest_checksum, detect_tampering, https_enforced, is_encrypted_artifact,
+        load_certificate_bundle, tls_handshake_allowed, validate_certificate_bundle,
+        verify_artifact_checksum, verify_artifact_signature, verify_dataset_manifest_integrity,
+        AeadEncryption, ArtifactSigningKey, CertificateRotationState, DatasetManifestIntegrity,
+        EncryptionAtRest, EncryptionError, SignatureError, TlsConfig, TrustedSigningKeys,
+        SEGMENT_LEN,
+    };
+    use crate::domain::dataset::{ArtifactChecksums, ArtifactManifest, DatasetId, ManifestStats};
+    use crate::domain::security::runtime::{
+        KeyManager, KeyRecord, StaticSecretsProvider, SystemNonceSource,
     };
     use std::collections::BTreeMap;
 
+    fn key_manager(active: &str, key_ids: &[&str]) -> KeyManager {
+        KeyManager::new(
+            key_ids
+                .iter()
+                .map(|key_id| KeyRecord {
+                    key_id: (*key_id).to_string(),
+                    purpose: "encryption".to_string(),
+                    active: *key_id == active,
+                })
+                .collect(),
+        )
+    }
+
+    fn cipher(active: &str, keys: &[(&str, u8)]) -> AeadEncryption {
+        let ids: Vec<&str> = keys.iter().map(|(key_id, _)| *key_id).collect();
+        let material = keys
+            .iter()
+            .map(|(key_id, fill)| ((*key_id).to_string(), vec![*fill; 32]))
+            .collect();
+        AeadEncryption::new(
+            key_manager(active, &ids),
+            material,
+            Box::new(SystemNonceSource::default()),
+        )
+        .expect("cipher")
+    }
+
     #[test]
     fn tls_bundle_load_validate_and_rotation_work() {
         let temp = tempfile::tempdir().expect("temp");
@@ -289,12 +823,33 @@ mod tests {
 
     #[test]
     fn encryption_integrity_signature_and_tamper_detection_work() {
-        let cipher = XorEncryption::new(b"atlas-key");
+        let cipher = cipher("k1", &[("k1", 7)]);
         let plaintext = b"dataset bytes";
-        let encrypted = cipher.encrypt(plaintext);
-        assert_ne!(encrypted, plaintext);
-        let decrypted = cipher.decrypt(&encrypted);
+        let encrypted = cipher.encrypt(plaintext).expect("encrypt");
+        assert!(is_encrypted_artifact(&encrypted));
+        assert_ne!(&encrypted[encrypted.len() - plaintext.len()..], plaintext);
+        let decrypted = cipher.decrypt(&encrypted).expect("decrypt");
         assert_eq!(decrypted, plaintext);
+        assert_ne!(
+            cipher.encrypt(plaintext).expect("encrypt"),
+            encrypted,
+            "nonces must be per object"
+        );
+
+        let mut tampered_body = encrypted.clone();
+        let last = tampered_body.len() - 1;
+        tampered_body[last] ^= 0x01;
+        assert_eq!(
+            cipher.decrypt(&tampered_body),
+            Err(EncryptionError::Authentication)
+        );
+        let mut tampered_header = encrypted.clone();
+        tampered_header[8] ^= 0x01;
+        assert!(cipher.decrypt(&tampered_header).is_err());
+        assert!(matches!(
+            cipher.decrypt(plaintext),
+            Err(EncryptionError::MalformedHeader(_))
+        ));
 
         let checksum = super::sha256_hex(plaintext);
         assert!(verify_artifact_checksum(plaintext, &checksum));
@@ -335,12 +890,199 @@ mod tests {
 
     #[test]
     fn encryption_operations_have_bounded_runtime() {
-        let cipher = XorEncryption::new(b"atlas-performance-key");
+        let cipher = cipher("perf", &[("perf", 0x42)]);
         let payload = vec![0x55_u8; 2 * 1024 * 1024];
         let started = std::time::Instant::now();
-        let encrypted = cipher.encrypt(&payload);
-        let decrypted = cipher.decrypt(&encrypted);
+        let encrypted = cipher.encrypt(&payload).expect("encrypt");
+        let decrypted = cipher.decrypt(&encrypted).expect("decrypt");
         assert_eq!(decrypted, payload);
         assert!(started.elapsed() < std::time::Duration::from_millis(250));
     }
+
+    #[test]
+    fn segmented_objects_stream_to_files_and_reject_reordered_or_dropped_segments() {
+        let cipher = cipher("k1", &[("k1", 3)]);
+        let dir = tempfile::tempdir().expect("temp");
+        let (sealed_path, plain_path) = (dir.path().join("sealed"), dir.path().join("plain"));
+        let payload: Vec<u8> = (0..2 * SEGMENT_LEN + 100)
+            .map(|i| (i % 251) as u8)
+            .collect();
+        let sealed = cipher.encrypt(&payload).expect("encrypt");
+        std::fs::write(&sealed_path, &sealed).expect("write sealed");
+        assert_eq!(
+            cipher
+                .decrypt_file(&sealed_path, &plain_path)
+                .expect("stream"),
+            payload.len() as u64
+        );
+        assert_eq!(std::fs::read(&plain_path).expect("plain"), payload);
+        assert_eq!(cipher.decrypt(&sealed).expect("decrypt"), payload);
+
+        let header_len = sealed.len() - payload.len() - 3 * 16;
+        let segment = SEGMENT_LEN + 16;
+        let dropped = &sealed[..header_len + 2 * segment];
+        std::fs::write(&sealed_path, dropped).expect("write truncated");
+        assert_eq!(
+            cipher.decrypt_file(&sealed_path, &plain_path),
+            Err(EncryptionError::Authentication)
+        );
+        assert_eq!(
+            cipher.decrypt(dropped),
+            Err(EncryptionError::Authentication)
+        );
+        let mut swapped = sealed[..header_len].to_vec();
+        swapped.extend_from_slice(&sealed[header_len + segment..header_len + 2 * segment]);
+        swapped.extend_from_slice(&sealed[header_len..header_len + segment]);
+        swapped.extend_from_slice(&sealed[header_len + 2 * segment..]);
+        assert_eq!(
+            cipher.decrypt(&swapped),
+            Err(EncryptionError::Authentication)
+        );
+
+        let empty = cipher.encrypt(b"").expect("encrypt empty");
+        std::fs::write(&sealed_path, &empty).expect("write empty");
+        assert_eq!(cipher.decrypt_file(&sealed_path, &plain_path), Ok(0));
+    }
+
+    #[test]
+    fn encryption_key_rotation_keeps_old_ciphertext_readable() {
+        let before = cipher("k1", &[("k1", 1), ("k2", 2)]);
+        let sealed_with_k1 = before.encrypt(b"v1").expect("encrypt");
+
+        let mut rotated_keys = key_manager("k1", &["k1", "k2"]);
+        assert!(rotated_keys.rotate("k2"));
+        let after = AeadEncryption::new(
+            rotated_keys,
+            BTreeMap::from([
+                ("k1".to_string(), vec![1; 32]),
+                ("k2".to_string(), vec![2; 32]),
+            ]),
+            Box::new(SystemNonceSource::default()),
+        )
+        .expect("cipher");
+        let sealed_with_k2 = after.encrypt(b"v2").expect("encrypt");
+        assert_eq!(after.decrypt(&sealed_with_k1).expect("old"), b"v1");
+        assert_eq!(after.decrypt(&sealed_with_k2).expect("new"), b"v2");
+
+        let k1_only = cipher("k1", &[("k1", 1)]);
+        assert_eq!(
+            k1_only.decrypt(&sealed_with_k2),
+            Err(EncryptionError::UnknownKeyId("k2".to_string()))
+        );
+        let wrong_material = cipher("k2", &[("k2", 9)]);
+        assert_eq!(
+            wrong_material.decrypt(&sealed_with_k2),
+            Err(EncryptionError::Authentication)
+        );
+    }
+
+    #[test]
+    fn encryption_keys_resolve_from_secrets_and_reject_bad_material() {
+        let secrets = StaticSecretsProvider {
+            values: BTreeMap::from([
+                ("k1".to_string(), "ab".repeat(32)),
+                ("short".to_string(), "ab".repeat(8)),
+            ]),
+        };
+        let cipher = AeadEncryption::from_secrets(
+            key_manager("k1", &["k1"]),
+            &secrets,
+            &["k1"],
+            Box::new(SystemNonceSource::default()),
+        )
+        .expect("from secrets");
+        let sealed = cipher.encrypt(b"payload").expect("encrypt");
+        assert_eq!(cipher.decrypt(&sealed).expect("decrypt"), b"payload");
+
+        assert!(matches!(
+            AeadEncryption::from_secrets(
+                key_manager("short", &["short"]),
+                &secrets,
+                &["short"],
+                Box::new(SystemNonceSource::default()),
+            ),
+            Err(EncryptionError::InvalidKey(_))
+        ));
+        let no_active = AeadEncryption::new(
+            key_manager("", &["k1"]),
+            BTreeMap::new(),
+            Box::new(SystemNonceSource::default()),
+        )
+        .expect("cipher");
+        assert_eq!(no_active.encrypt(b"x"), Err(EncryptionError::NoActiveKey));
+    }
+
+    fn signed_fixture() -> (ArtifactManifest, String) {
+        let dataset = DatasetId::new("110", "homo_sapiens", "GRCh38").expect("dataset id");
+        let manifest = ArtifactManifest::new(
+            "1".to_string(),
+            "1".to_string(),
+            dataset,
+            ArtifactChecksums::new(
+                "a".repeat(64),
+                "b".repeat(64),
+                "c".repeat(64),
+                "d".repeat(64),
+            ),
+            ManifestStats::new(1, 1, 1),
+        );
+        (manifest, "d".repeat(64))
+    }
+
+    #[test]
+    fn manifest_signature_round_trips_and_rejects_tampering() {
+        let key = ArtifactSigningKey::from_seed_hex(&"11".repeat(32)).expect("signing key");
+        let trusted =
+            TrustedSigningKeys::from_public_keys_hex(&[key.public_key_hex()]).expect("trusted");
+        let (manifest, sqlite_sha) = signed_fixture();
+        let signature = key
+            .sign_manifest(&manifest, &sqlite_sha)
+            .expect("sign manifest");
+        assert_eq!(signature.key_id, key.key_id());
+        trusted
+            .verify_manifest(&manifest, &sqlite_sha, Some(&signature))
+            .expect("verify manifest");
+
+        let mut edited = manifest.clone();
+        edited.stats.gene_count += 1;
+        assert!(matches!(
+            trusted.verify_manifest(&edited, &sqlite_sha, Some(&signature)),
+            Err(SignatureError::PayloadMismatch(_))
+        ));
+        assert!(matches!(
+            trusted.verify_manifest(&manifest, &"e".repeat(64), Some(&signature)),
+            Err(SignatureError::PayloadMismatch(_))
+        ));
+
+        let mut forged = signature.clone();
+        forged.signature = "00".repeat(64);
+        assert_eq!(
+            trusted.verify_manifest(&manifest, &sqlite_sha, Some(&forged)),
+            Err(SignatureError::Invalid)
+        );
+        assert_eq!(
+            trusted.verify_manifest(&manifest, &sqlite_sha, None),
+            Err(SignatureError::Missing)
+        );
+    }
+
+    #[test]
+    fn manifest_signature_from_untrusted_key_is_rejected() {
+        let signer = ArtifactSigningKey::from_seed_hex(&"11".repeat(32)).expect("signing key");
+        let other = ArtifactSigningKey::from_seed_hex(&"22".repeat(32)).expect("other key");
+        let trusted =
+            TrustedSigningKeys::from_public_keys_hex(&[other.public_key_hex()]).expect("trusted");
+        let (manifest, sqlite_sha) = signed_fixture();
+        let signature = signer
+            .sign_manifest(&manifest, &sqlite_sha)
+            .expect("sign manifest");
+        assert_eq!(
+            trusted.verify_manifest(&manifest, &sqlite_sha, Some(&signature)),
+            Err(SignatureError::UntrustedKey(signer.key_id().to_string()))
+        );
+        assert!(matches!(
+            TrustedSigningKeys::from_public_keys_hex(&["abcd"]),
+            Err(SignatureError::InvalidKey(_))
+        ));
+    }
 }
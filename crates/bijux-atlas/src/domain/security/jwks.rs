@@ -0,0 +1,400 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! OIDC bearer validation: RS256 and ES256 JWTs checked against a JSON Web
+//! Key Set, then against the same [`TokenValidationPolicy`] as atlas tokens.
+
+use base64::Engine;
+use ring::signature::{
+    RsaPublicKeyComponents, UnparsedPublicKey, ECDSA_P256_SHA256_FIXED, RSA_PKCS1_2048_8192_SHA256,
+};
+use serde::{Deserialize, Serialize};
+
+use super::auth::{AuthValidationError, TokenClaims, TokenValidationPolicy};
+
+pub const SUPPORTED_JWT_ALGORITHMS: [&str; 2] = ["RS256", "ES256"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Jwk {
+    pub kty: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kid: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alg: Option<String>,
+    #[serde(default, rename = "use", skip_serializing_if = "Option::is_none")]
+    pub key_use: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub n: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub e: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crv: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub x: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub y: Option<String>,
+}
+
+impl Jwk {
+    /// Whether this key may check a JWT signed with `alg`; keys for other
+    /// algorithms, curves or uses (such as `enc`) never match.
+    fn accepts(&self, alg: &str) -> bool {
+        let family = match alg {
+            "RS256" => self.kty == "RSA",
+            "ES256" => self.kty == "EC" && self.crv.as_deref() == Some("P-256"),
+            _ => false,
+        };
+        family
+            && self.alg.as_deref().is_none_or(|value| value == alg)
+            && self.key_use.as_deref().is_none_or(|value| value == "sig")
+    }
+
+    fn verify(&self, alg: &str, message: &[u8], signature: &[u8]) -> bool {
+        match alg {
+            "RS256" => {
+                let (Some(n), Some(e)) = (
+                    self.n.as_deref().and_then(b64url),
+                    self.e.as_deref().and_then(b64url),
+                ) else {
+                    return false;
+                };
+                RsaPublicKeyComponents { n, e }
+                    .verify(&RSA_PKCS1_2048_8192_SHA256, message, signature)
+                    .is_ok()
+            }
+            "ES256" => {
+                let (Some(x), Some(y)) = (
+                    self.x.as_deref().and_then(b64url),
+                    self.y.as_deref().and_then(b64url),
+                ) else {
+                    return false;
+                };
+                if x.len() != 32 || y.len() != 32 {
+                    return false;
+                }
+                let mut point = Vec::with_capacity(65);
+                point.push(0x04);
+                point.extend_from_slice(&x);
+                point.extend_from_slice(&y);
+                UnparsedPublicKey::new(&ECDSA_P256_SHA256_FIXED, point)
+                    .verify(message, signature)
+                    .is_ok()
+            }
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JwkSet {
+    pub keys: Vec<Jwk>,
+}
+
+impl JwkSet {
+    pub fn from_json(bytes: &[u8]) -> Result<Self, String> {
+        serde_json::from_slice(bytes).map_err(|err| format!("invalid JWKS document: {err}"))
+    }
+
+    /// Whether a key with this `kid` is published.
+    #[must_use]
+    pub fn contains_kid(&self, kid: &str) -> bool {
+        self.keys.iter().any(|key| key.kid.as_deref() == Some(kid))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct JwtHeader {
+    alg: String,
+    #[serde(default)]
+    kid: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum OneOrMany {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl OneOrMany {
+    fn values(&self) -> Vec<&str> {
+        match self {
+            Self::One(value) => value.split_whitespace().collect(),
+            Self::Many(values) => values.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct JwtPayload {
+    sub: Option<String>,
+    iss: Option<String>,
+    aud: Option<OneOrMany>,
+    exp: Option<u64>,
+    nbf: Option<u64>,
+    iat: Option<u64>,
+    jti: Option<String>,
+    scope: Option<String>,
+    scp: Option<OneOrMany>,
+    groups: Option<Vec<String>>,
+}
+
+/// `kid` named by a JWT header, so callers can refresh a key set that lacks it.
+#[must_use]
+pub fn jwt_key_id(token: &str) -> Option<String> {
+    let header = token.split('.').next().and_then(b64url)?;
+    serde_json::from_slice::<JwtHeader>(&header).ok()?.kid
+}
+
+/// Checks a compact JWT against `keys` and `policy`. `aud` may be a string or
+/// a list and must contain the required audience; scopes come from `scope`
+/// or `scp`. `leeway_s` absorbs clock skew on `exp` and `nbf`. On success the
+/// claims carry the matched audience and the space-joined scopes.
+pub fn validate_jwt(
+    token: &str,
+    keys: &JwkSet,
+    policy: &TokenValidationPolicy,
+    now_unix_s: u64,
+    leeway_s: u64,
+) -> Result<TokenClaims, AuthValidationError> {
+    let mut parts = token.split('.');
+    let (Some(header_b64), Some(payload_b64), Some(signature_b64), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(AuthValidationError::TokenMalformed);
+    };
+    let header: JwtHeader = b64url(header_b64)
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .ok_or(AuthValidationError::TokenMalformed)?;
+    if !SUPPORTED_JWT_ALGORITHMS.contains(&header.alg.as_str()) {
+        return Err(AuthValidationError::TokenAlgorithmRejected);
+    }
+    let signature = b64url(signature_b64).ok_or(AuthValidationError::TokenMalformed)?;
+    let candidates: Vec<&Jwk> = keys
+        .keys
+        .iter()
+        .filter(|key| header.kid.is_none() || key.kid == header.kid)
+        .filter(|key| key.accepts(&header.alg))
+        .collect();
+    if candidates.is_empty() {
+        return Err(AuthValidationError::TokenKeyUnknown);
+    }
+    let signed = &token[..header_b64.len() + 1 + payload_b64.len()];
+    if !candidates
+        .iter()
+        .any(|key| key.verify(&header.alg, signed.as_bytes(), &signature))
+    {
+        return Err(AuthValidationError::TokenSignatureInvalid);
+    }
+
+    let payload: JwtPayload = b64url(payload_b64)
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .ok_or(AuthValidationError::TokenMalformed)?;
+    let exp = payload.exp.ok_or(AuthValidationError::TokenMalformed)?;
+    if now_unix_s > exp.saturating_add(leeway_s) {
+        return Err(AuthValidationError::TokenExpired);
+    }
+    if payload
+        .nbf
+        .is_some_and(|nbf| now_unix_s.saturating_add(leeway_s) < nbf)
+    {
+        return Err(AuthValidationError::TokenNotYetValid);
+    }
+    let iss = payload.iss.unwrap_or_default();
+    if iss != policy.required_issuer {
+        return Err(AuthValidationError::TokenIssuerInvalid);
+    }
+    if !payload.aud.as_ref().is_some_and(|aud| {
+        aud.values()
+            .iter()
+            .any(|value| *value == policy.required_audience)
+    }) {
+        return Err(AuthValidationError::TokenAudienceInvalid);
+    }
+    let mut scopes: Vec<&str> = payload
+        .scope
+        .as_deref()
+        .unwrap_or_default()
+        .split_whitespace()
+        .collect();
+    for scope in payload
+        .scp
+        .as_ref()
+        .map(OneOrMany::values)
+        .unwrap_or_default()
+    {
+        if !scopes.contains(&scope) {
+            scopes.push(scope);
+        }
+    }
+    if policy
+        .required_scopes
+        .iter()
+        .any(|required| !scopes.contains(&required.as_str()))
+    {
+        return Err(AuthValidationError::TokenScopeDenied);
+    }
+    if payload
+        .jti
+        .as_ref()
+        .is_some_and(|jti| policy.revoked_token_ids.contains(jti))
+    {
+        return Err(AuthValidationError::TokenRevoked);
+    }
+    let sub = payload
+        .sub
+        .filter(|value| !value.trim().is_empty())
+        .ok_or(AuthValidationError::TokenMalformed)?;
+    Ok(TokenClaims {
+        sub,
+        iss,
+        aud: policy.required_audience.clone(),
+        exp,
+        nbf: payload.nbf,
+        iat: payload.iat,
+        jti: payload.jti,
+        scope: (!scopes.is_empty()).then(|| scopes.join(" ")),
+        groups: payload.groups.unwrap_or_default(),
+    })
+}
+
+fn b64url(value: &str) -> Option<Vec<u8>> {
+    base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(value.trim_end_matches('='))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{jwt_key_id, validate_jwt, JwkSet};
+    use crate::domain::security::auth::{AuthValidationError, TokenValidationPolicy};
+    use base64::Engine;
+    use ring::rand::SystemRandom;
+    use ring::signature::{
+        EcdsaKeyPair, RsaKeyPair, ECDSA_P256_SHA256_FIXED_SIGNING, RSA_PKCS1_SHA256,
+    };
+
+    const JWKS: &[u8] = include_bytes!("../../../tests/fixtures/oidc/jwks.json");
+    const RS256_KEY: &[u8] = include_bytes!("../../../tests/fixtures/oidc/rs256-signing-key.pk8");
+    const ES256_KEY: &[u8] = include_bytes!("../../../tests/fixtures/oidc/es256-signing-key.pk8");
+
+    fn b64(bytes: &[u8]) -> String {
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes)
+    }
+
+    fn sign(alg: &str, kid: &str, claims: &serde_json::Value) -> String {
+        let header = b64(&serde_json::to_vec(
+            &serde_json::json!({"alg": alg, "kid": kid, "typ": "JWT"}),
+        )
+        .expect("header"));
+        let payload = b64(&serde_json::to_vec(claims).expect("claims"));
+        let signed = format!("{header}.{payload}");
+        let rng = SystemRandom::new();
+        let signature = if alg == "RS256" {
+            let key = RsaKeyPair::from_pkcs8(RS256_KEY).expect("rsa key");
+            let mut sig = vec![0; key.public().modulus_len()];
+            key.sign(&RSA_PKCS1_SHA256, &rng, signed.as_bytes(), &mut sig)
+                .expect("rsa sign");
+            sig
+        } else {
+            let key = EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, ES256_KEY, &rng)
+                .expect("ec key");
+            key.sign(&rng, signed.as_bytes())
+                .expect("ec sign")
+                .as_ref()
+                .to_vec()
+        };
+        format!("{signed}.{}", b64(&signature))
+    }
+
+    fn policy() -> TokenValidationPolicy {
+        let mut policy = TokenValidationPolicy::new("https://idp.example", "atlas-api");
+        policy.required_scopes = vec!["dataset.read".to_string()];
+        policy
+    }
+
+    fn claims() -> serde_json::Value {
+        serde_json::json!({
+            "sub": "user:alice",
+            "iss": "https://idp.example",
+            "aud": ["account", "atlas-api"],
+            "exp": 2_000,
+            "nbf": 1_000,
+            "jti": "jti-1",
+            "scp": ["dataset.read", "profile"]
+        })
+    }
+
+    #[test]
+    fn rs256_and_es256_tokens_validate_against_the_key_set() {
+        let keys = JwkSet::from_json(JWKS).expect("jwks");
+        for (alg, kid) in [("RS256", "atlas-test-rs256"), ("ES256", "atlas-test-es256")] {
+            let token = sign(alg, kid, &claims());
+            assert_eq!(jwt_key_id(&token).as_deref(), Some(kid));
+            let validated = validate_jwt(&token, &keys, &policy(), 1_500, 0).expect(alg);
+            assert_eq!(validated.sub, "user:alice");
+            assert_eq!(validated.aud, "atlas-api");
+            assert_eq!(validated.scopes(), vec!["dataset.read", "profile"]);
+        }
+    }
+
+    #[test]
+    fn registered_claims_and_key_binding_are_enforced() {
+        let keys = JwkSet::from_json(JWKS).expect("jwks");
+        let token = sign("RS256", "atlas-test-rs256", &claims());
+        assert_eq!(
+            validate_jwt(&token, &keys, &policy(), 2_100, 0),
+            Err(AuthValidationError::TokenExpired)
+        );
+        assert!(validate_jwt(&token, &keys, &policy(), 2_030, 60).is_ok());
+        assert_eq!(
+            validate_jwt(&token, &keys, &policy(), 900, 0),
+            Err(AuthValidationError::TokenNotYetValid)
+        );
+        let mut wrong_audience = policy();
+        wrong_audience.required_audience = "other-api".to_string();
+        assert_eq!(
+            validate_jwt(&token, &keys, &wrong_audience, 1_500, 0),
+            Err(AuthValidationError::TokenAudienceInvalid)
+        );
+        let mut wrong_issuer = policy();
+        wrong_issuer.required_issuer = "https://evil.example".to_string();
+        assert_eq!(
+            validate_jwt(&token, &keys, &wrong_issuer, 1_500, 0),
+            Err(AuthValidationError::TokenIssuerInvalid)
+        );
+        let mut revoked = policy();
+        revoked.revoked_token_ids.insert("jti-1".to_string());
+        assert_eq!(
+            validate_jwt(&token, &keys, &revoked, 1_500, 0),
+            Err(AuthValidationError::TokenRevoked)
+        );
+
+        // The RSA signature does not verify under the EC key's kid.
+        let swapped = sign("RS256", "atlas-test-es256", &claims());
+        assert_eq!(
+            validate_jwt(&swapped, &keys, &policy(), 1_500, 0),
+            Err(AuthValidationError::TokenKeyUnknown)
+        );
+        let rotated = sign("RS256", "atlas-next-rs256", &claims());
+        assert_eq!(
+            validate_jwt(&rotated, &keys, &policy(), 1_500, 0),
+            Err(AuthValidationError::TokenKeyUnknown)
+        );
+        let (head, rest) = token.split_once('.').expect("token parts");
+        let tampered = format!("{head}.{}", rest.replacen('e', "f", 1));
+        assert!(validate_jwt(&tampered, &keys, &policy(), 1_500, 0).is_err());
+    }
+
+    #[test]
+    fn unsigned_and_symmetric_algorithms_are_rejected() {
+        let keys = JwkSet::from_json(JWKS).expect("jwks");
+        let payload = b64(&serde_json::to_vec(&claims()).expect("claims"));
+        for alg in ["none", "HS256"] {
+            let header = b64(format!(r#"{{"alg":"{alg}"}}"#).as_bytes());
+            assert_eq!(
+                validate_jwt(&format!("{header}.{payload}."), &keys, &policy(), 1_500, 0),
+                Err(AuthValidationError::TokenAlgorithmRejected)
+            );
+        }
+    }
+}
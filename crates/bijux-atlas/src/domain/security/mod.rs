@@ -3,4 +3,5 @@
 pub mod auth;
 pub mod authorization;
 pub mod data_protection;
+pub mod jwks;
 pub mod runtime;
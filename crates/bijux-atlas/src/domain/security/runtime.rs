@@ -5,6 +5,8 @@ use std::path::Path;
 
 use serde::{Deserialize, Serialize};
 
+use super::data_protection::{EncryptionError, NonceSource};
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SecurityIdentityConfig {
     pub principal_source: String,
@@ -95,6 +97,27 @@ impl SecretsProvider for EnvSecretsProvider {
     }
 }
 
+/// Operating-system CSPRNG for AEAD nonces.
+#[derive(Debug)]
+pub struct SystemNonceSource {
+    rng: ring::rand::SystemRandom,
+}
+
+impl Default for SystemNonceSource {
+    fn default() -> Self {
+        Self {
+            rng: ring::rand::SystemRandom::new(),
+        }
+    }
+}
+
+impl NonceSource for SystemNonceSource {
+    fn fill(&self, nonce: &mut [u8]) -> Result<(), EncryptionError> {
+        ring::rand::SecureRandom::fill(&self.rng, nonce)
+            .map_err(|_| EncryptionError::NonceGeneration)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct KeyRecord {
     pub key_id: String,
@@ -131,6 +154,11 @@ impl KeyManager {
     pub fn active_key(&self) -> Option<&KeyRecord> {
         self.keys.values().find(|record| record.active)
     }
+
+    #[must_use]
+    pub fn get(&self, key_id: &str) -> Option<&KeyRecord> {
+        self.keys.get(key_id)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -37,7 +37,8 @@ pub mod version;
 pub(crate) mod version_support;
 
 pub(crate) use crate::adapters::inbound::http::request_policies::{
-    chrono_like_unix_millis, record_shed_reason, route_sli_class,
+    chrono_like_unix_millis, dataset_access_denial, enforce_dataset_access, record_shed_reason,
+    route_sli_class, DatasetAccess,
 };
 pub(crate) use crate::app::cache::{CacheError, RegistrySourceHealth};
 pub(crate) use crate::app::ports::{CatalogFetch, DatasetStoreBackend};
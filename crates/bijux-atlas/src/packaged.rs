@@ -8,11 +8,14 @@ pub(crate) const ERROR_CODES_JSON: &str =
 pub(crate) const AUTH_POLICY_YAML: &str = include_str!("../resources/security/policy.yaml");
 pub(crate) const PERMISSIONS_YAML: &str = include_str!("../resources/security/permissions.yaml");
 pub(crate) const ROLES_YAML: &str = include_str!("../resources/security/roles.yaml");
+pub(crate) const ROLE_ASSIGNMENTS_YAML: &str =
+    include_str!("../resources/security/role-assignments.yaml");
 
 #[cfg(test)]
 mod tests {
     use super::{
         AUTH_POLICY_YAML, ENV_CONTRACT_SCHEMA_JSON, ERROR_CODES_JSON, PERMISSIONS_YAML, ROLES_YAML,
+        ROLE_ASSIGNMENTS_YAML,
     };
     use std::fs;
     use std::path::PathBuf;
@@ -58,6 +61,14 @@ mod tests {
         assert_matches_repo_source("configs/sources/security/roles.yaml", ROLES_YAML);
     }
 
+    #[test]
+    fn packaged_role_assignments_match_repo_source() {
+        assert_matches_repo_source(
+            "configs/sources/security/role-assignments.yaml",
+            ROLE_ASSIGNMENTS_YAML,
+        );
+    }
+
     #[test]
     fn packaged_env_contract_matches_repo_source() {
         assert_matches_repo_source(
@@ -1,6 +1,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::domain::dataset::DatasetId;
+use crate::domain::security::data_protection::TlsConfig;
 use crate::packaged::ENV_CONTRACT_SCHEMA_JSON;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
@@ -17,7 +18,7 @@ pub use crate::contracts::config::{
     runtime_startup_config_docs_markdown, runtime_startup_config_schema_json,
 };
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RateLimitConfig {
     pub capacity: f64,
     pub refill_per_sec: f64,
@@ -108,7 +109,17 @@ pub struct StoreConfig {
     pub s3_base_url: Option<String>,
     pub s3_presigned_base_url: Option<String>,
     pub s3_bearer: Option<String>,
+    /// Bucket appended to `s3_base_url` per `s3_addressing`; unset when the
+    /// base URL already names it.
+    pub s3_bucket: Option<String>,
+    /// `path` or `virtual-host`.
+    pub s3_addressing: String,
+    /// SigV4 signing region; keys come from `ATLAS_STORE_ACCESS_KEY_ID`,
+    /// `ATLAS_STORE_SECRET_ACCESS_KEY` and `ATLAS_STORE_SESSION_TOKEN`.
+    pub s3_region: String,
     pub http_bearer: Option<String>,
+    /// `key_id:hex` pairs for sealed artifacts; the first entry is the active key.
+    pub encryption_keys: Option<String>,
     pub allow_private_hosts: bool,
     pub retry: StoreRetryConfig,
     pub registry_sources: Vec<RegistrySourceSpec>,
@@ -145,6 +156,10 @@ pub struct RuntimeConfig {
     pub policy_mode: String,
     pub shutdown_drain_ms: u64,
     pub tcp_keepalive_enabled: bool,
+    /// In-process TLS termination; `ca_path` turns on client certificate
+    /// verification for `auth_mode=mtls`.
+    pub tls: TlsConfig,
+    pub tls_reload_interval_secs: u64,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -281,6 +296,8 @@ pub struct ApiConfig {
     pub continue_download_on_request_timeout_for_warmup: bool,
     pub max_sequence_bases: usize,
     pub sequence_api_key_required_bases: usize,
+    pub max_sequence_batch_items: usize,
+    pub max_sequence_batch_bases: usize,
     pub sequence_rate_limit_per_ip: RateLimitConfig,
     pub sequence_ttl: Duration,
     pub adaptive_rate_limit_factor: f64,
@@ -305,6 +322,28 @@ pub struct ApiConfig {
     pub token_required_audience: Option<String>,
     pub token_required_scopes: Vec<String>,
     pub token_revoked_ids: Vec<String>,
+    /// JWKS endpoint for `auth_mode=oidc`; bearer JWTs are checked against
+    /// `token_required_issuer`, `token_required_audience` and the scope and
+    /// revocation lists above.
+    pub oidc_jwks_url: Option<String>,
+    /// Local JWKS document, used instead of `oidc_jwks_url`.
+    pub oidc_jwks_path: Option<String>,
+    pub oidc_jwks_ttl_secs: u64,
+    pub oidc_clock_skew_secs: u64,
+    /// Accept `x-forwarded-user`/`x-atlas-oidc-subject` from a verifying proxy
+    /// instead of validating JWTs in process.
+    pub oidc_trust_proxy_headers: bool,
+    /// Accept `x-forwarded-client-cert`/`x-atlas-mtls-subject` from a
+    /// terminating proxy instead of verifying client certificates in process.
+    pub mtls_trust_proxy_headers: bool,
+    /// Role bindings and dataset scopes that replace the packaged
+    /// `role-assignments.yaml`; re-read every `authz_reload_interval_secs`.
+    pub authz_role_bindings_path: Option<String>,
+    pub authz_reload_interval_secs: u64,
+    /// Hashed key registry managed by `bijux-atlas keys`, used instead of
+    /// `allowed_api_keys`; re-read every `api_key_registry_reload_interval_secs`.
+    pub api_key_registry_path: Option<String>,
+    pub api_key_registry_reload_interval_secs: u64,
 }
 
 impl Default for ApiConfig {
@@ -359,6 +398,8 @@ impl Default for ApiConfig {
             continue_download_on_request_timeout_for_warmup: true,
             max_sequence_bases: 20_000,
             sequence_api_key_required_bases: 5_000,
+            max_sequence_batch_items: 10_000,
+            max_sequence_batch_bases: 2_000_000,
             sequence_rate_limit_per_ip: RateLimitConfig {
                 capacity: 15.0,
                 refill_per_sec: 5.0,
@@ -391,6 +432,16 @@ impl Default for ApiConfig {
             token_required_audience: None,
             token_required_scopes: Vec::new(),
             token_revoked_ids: Vec::new(),
+            oidc_jwks_url: None,
+            oidc_jwks_path: None,
+            oidc_jwks_ttl_secs: 300,
+            oidc_clock_skew_secs: 60,
+            oidc_trust_proxy_headers: false,
+            mtls_trust_proxy_headers: false,
+            authz_role_bindings_path: None,
+            authz_reload_interval_secs: 30,
+            api_key_registry_path: None,
+            api_key_registry_reload_interval_secs: 30,
         }
     }
 }
@@ -418,8 +469,17 @@ pub fn validate_startup_config_contract(
     if cache.max_concurrent_downloads == 0 {
         return Err("max concurrent downloads must be > 0".to_string());
     }
-    if api.require_api_key && api.allowed_api_keys.is_empty() {
-        return Err("require_api_key=true requires at least one allowed api key".to_string());
+    if api.require_api_key && api.allowed_api_keys.is_empty() && api.api_key_registry_path.is_none()
+    {
+        return Err(
+            "require_api_key=true requires at least one allowed api key or an api key registry"
+                .to_string(),
+        );
+    }
+    if api.api_key_registry_path.is_some() && !api.allowed_api_keys.is_empty() {
+        return Err(
+            "allowed_api_keys and api_key_registry_path are mutually exclusive".to_string(),
+        );
     }
     if api.api_key_expiration_days == 0 {
         return Err("api_key_expiration_days must be greater than 0".to_string());
@@ -592,6 +652,81 @@ pub fn load_runtime_startup_config(
     )
 }
 
+/// `auth_mode=oidc` validates JWTs against exactly one JWKS source and a fixed
+/// issuer and audience, unless a verifying proxy is explicitly trusted.
+fn validate_oidc_config(api: &ApiConfig) -> Result<(), RuntimeConfigError> {
+    let invalid = |message: &str| {
+        Err(RuntimeConfigError::InvalidValue {
+            message: message.to_string(),
+        })
+    };
+    if api.oidc_jwks_url.is_some() && api.oidc_jwks_path.is_some() {
+        return invalid("ATLAS_OIDC_JWKS_URL and ATLAS_OIDC_JWKS_PATH are mutually exclusive");
+    }
+    if api.auth_mode != AuthMode::Oidc {
+        return Ok(());
+    }
+    if api.oidc_jwks_url.is_none() && api.oidc_jwks_path.is_none() {
+        if api.oidc_trust_proxy_headers {
+            return Ok(());
+        }
+        return invalid(
+            "ATLAS_AUTH_MODE=oidc requires ATLAS_OIDC_JWKS_URL or ATLAS_OIDC_JWKS_PATH \
+             (or ATLAS_OIDC_TRUST_PROXY_HEADERS=true behind a verifying proxy)",
+        );
+    }
+    if api
+        .token_required_issuer
+        .as_deref()
+        .is_none_or(str::is_empty)
+        || api
+            .token_required_audience
+            .as_deref()
+            .is_none_or(str::is_empty)
+    {
+        return invalid(
+            "ATLAS_AUTH_MODE=oidc requires ATLAS_TOKEN_REQUIRED_ISSUER and ATLAS_TOKEN_REQUIRED_AUDIENCE",
+        );
+    }
+    Ok(())
+}
+
+/// TLS needs a certificate and key and a floor rustls can honour; in-process
+/// `auth_mode=mtls` needs a client CA, unless a terminating proxy is trusted.
+fn validate_tls_config(runtime: &RuntimeConfig) -> Result<(), RuntimeConfigError> {
+    let invalid = |message: &str| {
+        Err(RuntimeConfigError::InvalidValue {
+            message: message.to_string(),
+        })
+    };
+    let tls = &runtime.tls;
+    if tls.enabled {
+        if tls.cert_path.as_os_str().is_empty() || tls.key_path.as_os_str().is_empty() {
+            return invalid(
+                "ATLAS_TLS_ENABLED=true requires ATLAS_TLS_CERT_PATH and ATLAS_TLS_KEY_PATH",
+            );
+        }
+        if !matches!(tls.min_version.as_str(), "1.2" | "1.3") {
+            return invalid("ATLAS_TLS_MIN_VERSION must be one of: 1.2, 1.3");
+        }
+        if runtime.tls_reload_interval_secs == 0 {
+            return invalid("ATLAS_TLS_RELOAD_INTERVAL_SECS must be > 0");
+        }
+    } else if tls.ca_path.is_some() {
+        return invalid("ATLAS_TLS_CLIENT_CA_PATH requires ATLAS_TLS_ENABLED=true");
+    }
+    if runtime.api.auth_mode == AuthMode::Mtls
+        && !runtime.api.mtls_trust_proxy_headers
+        && !(tls.enabled && tls.ca_path.is_some())
+    {
+        return invalid(
+            "ATLAS_AUTH_MODE=mtls requires ATLAS_TLS_ENABLED=true with ATLAS_TLS_CLIENT_CA_PATH \
+             (or ATLAS_MTLS_TRUST_PROXY_HEADERS=true behind a terminating proxy)",
+        );
+    }
+    Ok(())
+}
+
 fn validate_runtime_config_contract(runtime: &RuntimeConfig) -> Result<(), RuntimeConfigError> {
     validate_startup_config_contract(&runtime.api, &runtime.cache)
         .map_err(|message| RuntimeConfigError::InvalidValue { message })?;
@@ -601,6 +736,32 @@ fn validate_runtime_config_contract(runtime: &RuntimeConfig) -> Result<(), Runti
                 .to_string(),
         });
     }
+    validate_oidc_config(&runtime.api)?;
+    validate_tls_config(runtime)?;
+    if !matches!(
+        runtime.store.s3_addressing.as_str(),
+        "path" | "virtual-host"
+    ) {
+        return Err(RuntimeConfigError::InvalidValue {
+            message: "ATLAS_STORE_S3_ADDRESSING must be one of: path, virtual-host".to_string(),
+        });
+    }
+    if runtime.store.s3_addressing == "virtual-host" && runtime.store.s3_bucket.is_none() {
+        return Err(RuntimeConfigError::InvalidValue {
+            message: "ATLAS_STORE_S3_ADDRESSING=virtual-host requires ATLAS_STORE_S3_BUCKET"
+                .to_string(),
+        });
+    }
+    if runtime.api.authz_reload_interval_secs == 0 {
+        return Err(RuntimeConfigError::InvalidValue {
+            message: "ATLAS_AUTHZ_RELOAD_INTERVAL_SECS must be > 0".to_string(),
+        });
+    }
+    if runtime.api.api_key_registry_reload_interval_secs == 0 {
+        return Err(RuntimeConfigError::InvalidValue {
+            message: "ATLAS_API_KEY_REGISTRY_RELOAD_INTERVAL_SECS must be > 0".to_string(),
+        });
+    }
     if runtime.warm_coordination_enabled {
         if runtime.warm_coordination_lock_ttl_secs == 0 {
             return Err(RuntimeConfigError::InvalidValue {
@@ -651,6 +812,12 @@ fn validate_runtime_config_contract(runtime: &RuntimeConfig) -> Result<(), Runti
             message: "ATLAS_TRACE_EXPORTER must be one of: otlp, jaeger, file, none".to_string(),
         });
     }
+    crate::domain::security::data_protection::TrustedSigningKeys::from_public_keys_hex(
+        &runtime.cache.trusted_signing_keys,
+    )
+    .map_err(|e| RuntimeConfigError::InvalidValue {
+        message: format!("ATLAS_TRUSTED_SIGNING_KEYS: {e}"),
+    })?;
     if runtime.env_name.eq_ignore_ascii_case("prod") {
         if runtime.startup.bind_addr.contains("127.0.0.1")
             || runtime.startup.bind_addr.contains("localhost")
@@ -669,10 +836,18 @@ fn validate_runtime_config_contract(runtime: &RuntimeConfig) -> Result<(), Runti
                 message: "ATLAS_ENV=prod requires ATLAS_REDIS_URL".to_string(),
             });
         }
-        if runtime.api.require_api_key && runtime.api.allowed_api_keys.is_empty() {
+        if runtime.cache.trusted_signing_keys.is_empty() {
+            return Err(RuntimeConfigError::InvalidValue {
+                message: "ATLAS_ENV=prod requires ATLAS_TRUSTED_SIGNING_KEYS".to_string(),
+            });
+        }
+        if runtime.api.require_api_key
+            && runtime.api.allowed_api_keys.is_empty()
+            && runtime.api.api_key_registry_path.is_none()
+        {
             return Err(RuntimeConfigError::InvalidValue {
                 message:
-                    "ATLAS_ENV=prod requires non-empty ATLAS_ALLOWED_API_KEYS when api key auth is enabled"
+                    "ATLAS_ENV=prod requires ATLAS_ALLOWED_API_KEYS or ATLAS_API_KEY_REGISTRY_PATH when api key auth is enabled"
                         .to_string(),
             });
         }
@@ -757,6 +932,8 @@ impl RuntimeConfig {
             quarantine_retry_ttl: env_duration_ms("ATLAS_QUARANTINE_RETRY_TTL_MS", 300_000)?,
             registry_ttl: env_duration_ms("ATLAS_REGISTRY_TTL_MS", 15_000)?,
             registry_freeze_mode: env_bool("ATLAS_REGISTRY_FREEZE_MODE", false)?,
+            trusted_signing_keys: env_list("ATLAS_TRUSTED_SIGNING_KEYS"),
+            require_signed_datasets: env_name.eq_ignore_ascii_case("prod"),
             ..crate::DatasetCacheConfig::default()
         };
 
@@ -777,6 +954,15 @@ impl RuntimeConfig {
             .filter(|value| !value.is_empty());
         let token_required_scopes = env_list("ATLAS_TOKEN_REQUIRED_SCOPES");
         let token_revoked_ids = env_list("ATLAS_TOKEN_REVOKED_IDS");
+        let oidc_jwks_url = std::env::var("ATLAS_OIDC_JWKS_URL")
+            .ok()
+            .filter(|value| !value.is_empty());
+        if let Some(value) = oidc_jwks_url.as_deref() {
+            validate_url("ATLAS_OIDC_JWKS_URL", value, true)?;
+        }
+        let oidc_jwks_path = std::env::var("ATLAS_OIDC_JWKS_PATH")
+            .ok()
+            .filter(|value| !value.is_empty());
         let audit_sink = match std::env::var("ATLAS_AUDIT_SINK") {
             Ok(value) => match value.as_str() {
                 "stdout" => AuditSink::Stdout,
@@ -919,6 +1105,8 @@ impl RuntimeConfig {
                 "ATLAS_SEQUENCE_API_KEY_REQUIRED_BASES",
                 5_000,
             )?,
+            max_sequence_batch_items: env_usize("ATLAS_MAX_SEQUENCE_BATCH_ITEMS", 10_000)?,
+            max_sequence_batch_bases: env_usize("ATLAS_MAX_SEQUENCE_BATCH_BASES", 2_000_000)?,
             sequence_rate_limit_per_ip: RateLimitConfig {
                 capacity: env_f64("ATLAS_SEQUENCE_RATE_LIMIT_CAPACITY", 15.0)?,
                 refill_per_sec: env_f64("ATLAS_SEQUENCE_RATE_LIMIT_REFILL_PER_SEC", 5.0)?,
@@ -954,6 +1142,23 @@ impl RuntimeConfig {
             token_required_audience,
             token_required_scopes,
             token_revoked_ids,
+            oidc_jwks_url,
+            oidc_jwks_path,
+            oidc_jwks_ttl_secs: env_u64("ATLAS_OIDC_JWKS_TTL_SECS", 300)?,
+            oidc_clock_skew_secs: env_u64("ATLAS_OIDC_CLOCK_SKEW_SECS", 60)?,
+            oidc_trust_proxy_headers: env_bool("ATLAS_OIDC_TRUST_PROXY_HEADERS", false)?,
+            mtls_trust_proxy_headers: env_bool("ATLAS_MTLS_TRUST_PROXY_HEADERS", false)?,
+            authz_role_bindings_path: std::env::var("ATLAS_AUTHZ_ROLE_BINDINGS_PATH")
+                .ok()
+                .filter(|value| !value.is_empty()),
+            authz_reload_interval_secs: env_u64("ATLAS_AUTHZ_RELOAD_INTERVAL_SECS", 30)?,
+            api_key_registry_path: std::env::var("ATLAS_API_KEY_REGISTRY_PATH")
+                .ok()
+                .filter(|value| !value.is_empty()),
+            api_key_registry_reload_interval_secs: env_u64(
+                "ATLAS_API_KEY_REGISTRY_RELOAD_INTERVAL_SECS",
+                30,
+            )?,
             ..ApiConfig::default()
         };
 
@@ -966,7 +1171,17 @@ impl RuntimeConfig {
         let s3_base_url = std::env::var("ATLAS_STORE_S3_BASE_URL").ok();
         let s3_presigned_base_url = std::env::var("ATLAS_STORE_S3_PRESIGNED_BASE_URL").ok();
         let s3_bearer = std::env::var("ATLAS_STORE_S3_BEARER").ok();
+        let s3_bucket = std::env::var("ATLAS_STORE_S3_BUCKET")
+            .ok()
+            .filter(|value| !value.is_empty());
+        let s3_addressing =
+            std::env::var("ATLAS_STORE_S3_ADDRESSING").unwrap_or_else(|_| "path".to_string());
+        let s3_region =
+            std::env::var("ATLAS_STORE_S3_REGION").unwrap_or_else(|_| "us-east-1".to_string());
         let http_bearer = std::env::var("ATLAS_STORE_HTTP_BEARER").ok();
+        let encryption_keys = std::env::var("ATLAS_STORE_ENCRYPTION_KEYS")
+            .ok()
+            .filter(|value| !value.is_empty());
         let allow_private_hosts = env_bool("ATLAS_ALLOW_PRIVATE_STORE_HOSTS", false)?;
         if let Some(value) = s3_base_url.as_deref() {
             validate_url("ATLAS_STORE_S3_BASE_URL", value, true)?;
@@ -992,7 +1207,11 @@ impl RuntimeConfig {
             s3_base_url,
             s3_presigned_base_url,
             s3_bearer,
+            s3_bucket,
+            s3_addressing,
+            s3_region,
             http_bearer,
+            encryption_keys,
             allow_private_hosts,
             retry,
             registry_sources,
@@ -1032,6 +1251,22 @@ impl RuntimeConfig {
             policy_mode: default_runtime_policy_mode(),
             shutdown_drain_ms: env_u64("ATLAS_SHUTDOWN_DRAIN_MS", 5000)?,
             tcp_keepalive_enabled: env_bool("ATLAS_TCP_KEEPALIVE_ENABLED", true)?,
+            tls: TlsConfig {
+                enabled: env_bool("ATLAS_TLS_ENABLED", false)?,
+                min_version: std::env::var("ATLAS_TLS_MIN_VERSION")
+                    .unwrap_or_else(|_| "1.2".to_string()),
+                cert_path: std::env::var("ATLAS_TLS_CERT_PATH")
+                    .map(PathBuf::from)
+                    .unwrap_or_default(),
+                key_path: std::env::var("ATLAS_TLS_KEY_PATH")
+                    .map(PathBuf::from)
+                    .unwrap_or_default(),
+                ca_path: std::env::var("ATLAS_TLS_CLIENT_CA_PATH")
+                    .ok()
+                    .filter(|value| !value.is_empty())
+                    .map(PathBuf::from),
+            },
+            tls_reload_interval_secs: env_u64("ATLAS_TLS_RELOAD_INTERVAL_SECS", 30)?,
             startup,
             api,
             cache,
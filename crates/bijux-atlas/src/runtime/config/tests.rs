@@ -477,19 +477,212 @@ fn runtime_config_accepts_explicit_api_key_auth_mode() {
 
 #[test]
 fn runtime_config_accepts_proxy_verified_auth_modes() {
-    for auth_mode in ["oidc", "mtls"] {
-        with_runtime_env(&[("ATLAS_AUTH_MODE", auth_mode)], || {
+    let cases: [&[(&str, &str)]; 2] = [
+        &[
+            ("ATLAS_AUTH_MODE", "oidc"),
+            ("ATLAS_OIDC_TRUST_PROXY_HEADERS", "true"),
+        ],
+        &[
+            ("ATLAS_AUTH_MODE", "mtls"),
+            ("ATLAS_MTLS_TRUST_PROXY_HEADERS", "true"),
+        ],
+    ];
+    for pairs in cases {
+        with_runtime_env(pairs, || {
             let startup = RuntimeStartupConfig {
                 bind_addr: DEFAULT_BIND_ADDR.to_string(),
                 store_root: PathBuf::from(DEFAULT_STORE_ROOT),
                 cache_root: PathBuf::from(DEFAULT_CACHE_ROOT),
             };
             let runtime = RuntimeConfig::from_env(startup).expect("proxy auth mode");
-            assert_eq!(runtime.api.auth_mode.as_str(), auth_mode);
+            assert_eq!(runtime.api.auth_mode.as_str(), pairs[0].1);
         });
     }
 }
 
+#[test]
+fn runtime_config_requires_in_process_client_verification_for_mtls() {
+    let startup = || RuntimeStartupConfig {
+        bind_addr: DEFAULT_BIND_ADDR.to_string(),
+        store_root: PathBuf::from(DEFAULT_STORE_ROOT),
+        cache_root: PathBuf::from(DEFAULT_CACHE_ROOT),
+    };
+    with_runtime_env(&[("ATLAS_AUTH_MODE", "mtls")], || {
+        let err = RuntimeConfig::from_env(startup()).expect_err("mtls without tls");
+        assert!(err.to_string().contains("ATLAS_TLS_CLIENT_CA_PATH"));
+    });
+    with_runtime_env(&[("ATLAS_TLS_ENABLED", "true")], || {
+        let err = RuntimeConfig::from_env(startup()).expect_err("tls without cert");
+        assert!(err.to_string().contains("ATLAS_TLS_CERT_PATH"));
+    });
+    with_runtime_env(
+        &[
+            ("ATLAS_TLS_ENABLED", "true"),
+            ("ATLAS_TLS_CERT_PATH", "/etc/atlas/tls/tls.crt"),
+            ("ATLAS_TLS_KEY_PATH", "/etc/atlas/tls/tls.key"),
+            ("ATLAS_TLS_MIN_VERSION", "1.1"),
+        ],
+        || {
+            let err = RuntimeConfig::from_env(startup()).expect_err("tls 1.1 floor");
+            assert!(err.to_string().contains("ATLAS_TLS_MIN_VERSION"));
+        },
+    );
+    with_runtime_env(
+        &[
+            ("ATLAS_AUTH_MODE", "mtls"),
+            ("ATLAS_TLS_ENABLED", "true"),
+            ("ATLAS_TLS_CERT_PATH", "/etc/atlas/tls/tls.crt"),
+            ("ATLAS_TLS_KEY_PATH", "/etc/atlas/tls/tls.key"),
+            ("ATLAS_TLS_CLIENT_CA_PATH", "/etc/atlas/tls/ca.crt"),
+            ("ATLAS_TLS_MIN_VERSION", "1.3"),
+        ],
+        || {
+            let runtime = RuntimeConfig::from_env(startup()).expect("mtls with client ca");
+            assert_eq!(runtime.api.auth_mode, AuthMode::Mtls);
+            assert_eq!(runtime.tls.min_version, "1.3");
+            assert_eq!(runtime.tls_reload_interval_secs, 30);
+            assert!(runtime.tls.ca_path.is_some());
+        },
+    );
+}
+
+#[test]
+fn runtime_config_reads_role_binding_file_and_rejects_zero_reload_interval() {
+    let startup = || RuntimeStartupConfig {
+        bind_addr: DEFAULT_BIND_ADDR.to_string(),
+        store_root: PathBuf::from(DEFAULT_STORE_ROOT),
+        cache_root: PathBuf::from(DEFAULT_CACHE_ROOT),
+    };
+    with_runtime_env(
+        &[(
+            "ATLAS_AUTHZ_ROLE_BINDINGS_PATH",
+            "/etc/atlas/authz/role-bindings.yaml",
+        )],
+        || {
+            let runtime = RuntimeConfig::from_env(startup()).expect("role bindings path");
+            assert_eq!(
+                runtime.api.authz_role_bindings_path.as_deref(),
+                Some("/etc/atlas/authz/role-bindings.yaml")
+            );
+            assert_eq!(runtime.api.authz_reload_interval_secs, 30);
+        },
+    );
+    with_runtime_env(&[("ATLAS_AUTHZ_RELOAD_INTERVAL_SECS", "0")], || {
+        let err = RuntimeConfig::from_env(startup()).expect_err("zero reload interval");
+        assert!(err.to_string().contains("ATLAS_AUTHZ_RELOAD_INTERVAL_SECS"));
+    });
+}
+
+#[test]
+fn runtime_config_reads_api_key_registry_and_rejects_legacy_key_list_alongside_it() {
+    let startup = || RuntimeStartupConfig {
+        bind_addr: DEFAULT_BIND_ADDR.to_string(),
+        store_root: PathBuf::from(DEFAULT_STORE_ROOT),
+        cache_root: PathBuf::from(DEFAULT_CACHE_ROOT),
+    };
+    with_runtime_env(
+        &[
+            ("ATLAS_AUTH_MODE", "api-key"),
+            (
+                "ATLAS_API_KEY_REGISTRY_PATH",
+                "/etc/atlas/keys/api-keys.json",
+            ),
+        ],
+        || {
+            let runtime = RuntimeConfig::from_env(startup()).expect("api key registry");
+            assert!(runtime.api.require_api_key);
+            assert_eq!(
+                runtime.api.api_key_registry_path.as_deref(),
+                Some("/etc/atlas/keys/api-keys.json")
+            );
+            assert_eq!(runtime.api.api_key_registry_reload_interval_secs, 30);
+        },
+    );
+    with_runtime_env(
+        &[
+            (
+                "ATLAS_API_KEY_REGISTRY_PATH",
+                "/etc/atlas/keys/api-keys.json",
+            ),
+            ("ATLAS_ALLOWED_API_KEYS", "legacy-key"),
+        ],
+        || {
+            let err = RuntimeConfig::from_env(startup()).expect_err("both key sources");
+            assert!(err.to_string().contains("mutually exclusive"));
+        },
+    );
+    with_runtime_env(
+        &[("ATLAS_API_KEY_REGISTRY_RELOAD_INTERVAL_SECS", "0")],
+        || {
+            let err = RuntimeConfig::from_env(startup()).expect_err("zero reload interval");
+            assert!(err
+                .to_string()
+                .contains("ATLAS_API_KEY_REGISTRY_RELOAD_INTERVAL_SECS"));
+        },
+    );
+}
+
+#[test]
+fn runtime_config_requires_jwks_issuer_and_audience_for_oidc() {
+    let startup = || RuntimeStartupConfig {
+        bind_addr: DEFAULT_BIND_ADDR.to_string(),
+        store_root: PathBuf::from(DEFAULT_STORE_ROOT),
+        cache_root: PathBuf::from(DEFAULT_CACHE_ROOT),
+    };
+    with_runtime_env(&[("ATLAS_AUTH_MODE", "oidc")], || {
+        let err = RuntimeConfig::from_env(startup()).expect_err("oidc without jwks");
+        assert!(err.to_string().contains("ATLAS_OIDC_JWKS_URL"));
+    });
+    with_runtime_env(
+        &[
+            ("ATLAS_AUTH_MODE", "oidc"),
+            (
+                "ATLAS_OIDC_JWKS_URL",
+                "https://idp.example/.well-known/jwks.json",
+            ),
+        ],
+        || {
+            let err = RuntimeConfig::from_env(startup()).expect_err("oidc without issuer");
+            assert!(err.to_string().contains("ATLAS_TOKEN_REQUIRED_ISSUER"));
+        },
+    );
+    with_runtime_env(
+        &[
+            ("ATLAS_AUTH_MODE", "oidc"),
+            (
+                "ATLAS_OIDC_JWKS_URL",
+                "https://idp.example/.well-known/jwks.json",
+            ),
+            ("ATLAS_OIDC_JWKS_PATH", "/etc/atlas/jwks.json"),
+            ("ATLAS_TOKEN_REQUIRED_ISSUER", "https://idp.example"),
+            ("ATLAS_TOKEN_REQUIRED_AUDIENCE", "atlas-api"),
+        ],
+        || {
+            let err = RuntimeConfig::from_env(startup()).expect_err("two jwks sources");
+            assert!(err.to_string().contains("mutually exclusive"));
+        },
+    );
+    with_runtime_env(
+        &[
+            ("ATLAS_AUTH_MODE", "oidc"),
+            (
+                "ATLAS_OIDC_JWKS_URL",
+                "https://idp.example/.well-known/jwks.json",
+            ),
+            ("ATLAS_OIDC_JWKS_TTL_SECS", "120"),
+            ("ATLAS_TOKEN_REQUIRED_ISSUER", "https://idp.example"),
+            ("ATLAS_TOKEN_REQUIRED_AUDIENCE", "atlas-api"),
+        ],
+        || {
+            let runtime = RuntimeConfig::from_env(startup()).expect("oidc with jwks");
+            assert_eq!(runtime.api.auth_mode, AuthMode::Oidc);
+            assert_eq!(runtime.api.oidc_jwks_ttl_secs, 120);
+            assert_eq!(runtime.api.oidc_clock_skew_secs, 60);
+            assert!(!runtime.api.oidc_trust_proxy_headers);
+        },
+    );
+}
+
 #[test]
 fn runtime_config_accepts_token_auth_mode() {
     with_runtime_env(
@@ -573,6 +766,38 @@ fn runtime_config_rejects_invalid_audit_sink() {
     });
 }
 
+#[test]
+fn runtime_config_reads_s3_addressing_and_rejects_virtual_host_without_bucket() {
+    let startup = || RuntimeStartupConfig {
+        bind_addr: DEFAULT_BIND_ADDR.to_string(),
+        store_root: PathBuf::from(DEFAULT_STORE_ROOT),
+        cache_root: PathBuf::from(DEFAULT_CACHE_ROOT),
+    };
+    with_runtime_env(
+        &[
+            ("ATLAS_STORE_S3_BUCKET", "atlas"),
+            ("ATLAS_STORE_S3_ADDRESSING", "virtual-host"),
+            ("ATLAS_STORE_S3_REGION", "eu-west-1"),
+        ],
+        || {
+            let runtime = RuntimeConfig::from_env(startup()).expect("runtime");
+            assert_eq!(runtime.store.s3_bucket.as_deref(), Some("atlas"));
+            assert_eq!(runtime.store.s3_addressing, "virtual-host");
+            assert_eq!(runtime.store.s3_region, "eu-west-1");
+        },
+    );
+    with_runtime_env(&[("ATLAS_STORE_S3_ADDRESSING", "virtual-host")], || {
+        let err = RuntimeConfig::from_env(startup()).expect_err("missing bucket");
+        assert!(err.to_string().contains("requires ATLAS_STORE_S3_BUCKET"));
+    });
+    with_runtime_env(&[("ATLAS_STORE_S3_ADDRESSING", "dns")], || {
+        let err = RuntimeConfig::from_env(startup()).expect_err("invalid addressing");
+        assert!(err
+            .to_string()
+            .contains("ATLAS_STORE_S3_ADDRESSING must be one of: path, virtual-host"));
+    });
+}
+
 #[test]
 fn effective_runtime_config_redacts_secrets() {
     with_runtime_env(
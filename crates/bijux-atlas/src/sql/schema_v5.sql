@@ -1,4 +1,4 @@
--- SSOT: bijux-atlas-ingest SQLite schema v4
+-- SSOT: bijux-atlas-ingest SQLite schema v5
 PRAGMA journal_mode=WAL;
 PRAGMA synchronous=OFF;
 PRAGMA locking_mode=EXCLUSIVE;
@@ -20,7 +20,8 @@ CREATE TABLE gene_summary (
   exon_count INTEGER NOT NULL DEFAULT 0,
   total_exon_span INTEGER NOT NULL DEFAULT 0,
   cds_present INTEGER NOT NULL DEFAULT 0,
-  sequence_length INTEGER NOT NULL
+  sequence_length INTEGER NOT NULL,
+  strand TEXT NOT NULL DEFAULT '.'
 ) WITHOUT ROWID;
 
 CREATE TABLE transcript_summary (
@@ -50,7 +51,8 @@ CREATE TABLE genes (
   exon_count INTEGER NOT NULL DEFAULT 0,
   total_exon_span INTEGER NOT NULL DEFAULT 0,
   cds_present INTEGER NOT NULL DEFAULT 0,
-  sequence_length INTEGER NOT NULL
+  sequence_length INTEGER NOT NULL,
+  strand TEXT NOT NULL DEFAULT '.'
 ) WITHOUT ROWID;
 
 CREATE TABLE transcripts (
@@ -80,6 +82,41 @@ CREATE TABLE exons (
   exon_length INTEGER NOT NULL
 ) WITHOUT ROWID;
 
+CREATE TABLE cds_segments (
+  id INTEGER PRIMARY KEY,
+  cds_id TEXT NOT NULL,
+  transcript_id TEXT NOT NULL,
+  seqid TEXT NOT NULL,
+  start INTEGER NOT NULL,
+  end INTEGER NOT NULL,
+  phase INTEGER
+) WITHOUT ROWID;
+
+CREATE TABLE introns (
+  id INTEGER PRIMARY KEY,
+  transcript_id TEXT NOT NULL,
+  seqid TEXT NOT NULL,
+  start INTEGER NOT NULL,
+  end INTEGER NOT NULL,
+  intron_rank INTEGER NOT NULL
+) WITHOUT ROWID;
+
+CREATE TABLE utrs (
+  id INTEGER PRIMARY KEY,
+  transcript_id TEXT NOT NULL,
+  seqid TEXT NOT NULL,
+  kind TEXT NOT NULL,
+  start INTEGER NOT NULL,
+  end INTEGER NOT NULL
+) WITHOUT ROWID;
+
+CREATE TABLE gene_xrefs (
+  gene_id TEXT NOT NULL,
+  namespace TEXT NOT NULL,
+  value TEXT NOT NULL,
+  PRIMARY KEY (namespace, value, gene_id)
+) WITHOUT ROWID;
+
 CREATE TABLE transcript_exon_map (
   transcript_id TEXT NOT NULL,
   exon_id TEXT NOT NULL,
@@ -109,12 +146,39 @@ CREATE TABLE contigs (
   n_fraction REAL
 ) WITHOUT ROWID;
 
+CREATE TABLE contig_aliases (
+  alias TEXT PRIMARY KEY,
+  contig TEXT NOT NULL,
+  source TEXT NOT NULL
+) WITHOUT ROWID;
+
 CREATE VIRTUAL TABLE gene_summary_rtree USING rtree(
   gene_rowid,
   start,
   end
 );
 
+CREATE VIRTUAL TABLE exon_rtree USING rtree(
+  exon_rowid,
+  start,
+  end
+);
+
+CREATE VIRTUAL TABLE gene_search_fts USING fts5(
+  gene_id UNINDEXED,
+  symbol,
+  description,
+  aliases,
+  tokenize = 'unicode61 remove_diacritics 2'
+);
+
+CREATE VIRTUAL TABLE gene_search_trigram USING fts5(
+  gene_id UNINDEXED,
+  symbol,
+  aliases,
+  tokenize = 'trigram'
+);
+
 CREATE INDEX idx_gene_summary_gene_id ON gene_summary(gene_id);
 CREATE INDEX idx_gene_summary_name ON gene_summary(name);
 CREATE INDEX idx_gene_summary_name_normalized ON gene_summary(name_normalized);
@@ -122,6 +186,7 @@ CREATE INDEX idx_gene_summary_biotype ON gene_summary(biotype);
 CREATE INDEX idx_gene_summary_region ON gene_summary(seqid, start, end);
 CREATE INDEX idx_gene_summary_cover_lookup ON gene_summary(gene_id, name, seqid, start, end, biotype, transcript_count, sequence_length);
 CREATE INDEX idx_gene_summary_cover_region ON gene_summary(seqid, start, gene_id, end, name, biotype, transcript_count, sequence_length);
+CREATE INDEX idx_gene_summary_strand ON gene_summary(strand, gene_id);
 
 CREATE INDEX idx_transcript_summary_transcript_id ON transcript_summary(transcript_id);
 CREATE INDEX idx_transcript_summary_parent_gene_id ON transcript_summary(parent_gene_id);
@@ -133,6 +198,9 @@ CREATE INDEX idx_genes_gene_id ON genes(gene_id);
 CREATE INDEX idx_genes_name ON genes(name);
 CREATE INDEX idx_genes_biotype ON genes(biotype);
 CREATE INDEX idx_genes_order_page ON genes(seqid, start, gene_id);
+CREATE INDEX idx_genes_strand ON genes(strand, gene_id);
+
+CREATE INDEX idx_gene_xrefs_gene_id ON gene_xrefs(gene_id);
 
 CREATE INDEX idx_transcripts_tx_id ON transcripts(transcript_id);
 CREATE INDEX idx_transcripts_parent_gene ON transcripts(parent_gene_id);
@@ -140,3 +208,10 @@ CREATE INDEX idx_transcripts_order_page ON transcripts(seqid, start, transcript_
 
 CREATE INDEX idx_exons_transcript ON exons(transcript_id);
 CREATE INDEX idx_exons_region ON exons(seqid, start, end);
+
+CREATE INDEX idx_cds_segments_transcript ON cds_segments(transcript_id, start);
+
+CREATE INDEX idx_introns_transcript ON introns(transcript_id, start);
+CREATE INDEX idx_utrs_transcript ON utrs(transcript_id, start);
+
+CREATE INDEX idx_contig_aliases_contig ON contig_aliases(contig);
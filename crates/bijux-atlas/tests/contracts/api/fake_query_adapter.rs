@@ -40,8 +40,11 @@ fn base_params() -> ListGenesParams {
         sort: None,
         interval_mode: None,
         strand: None,
+        xref: None,
+        alias: None,
         include: None,
         pretty: false,
+        format: None,
     }
 }
 
@@ -57,6 +60,7 @@ fn wire_response_uses_omit_vs_null_policy() {
             biotype: None,
             transcript_count: Some(2),
             sequence_length: Some(11),
+            strand: None,
         }],
         next_cursor: Some("v1.cursor".to_string()),
     };
@@ -84,6 +88,7 @@ fn wire_response_golden_for_default_projection() {
             biotype: Some("protein_coding".to_string()),
             transcript_count: Some(2),
             sequence_length: Some(11),
+            strand: None,
         }],
         next_cursor: None,
     };
@@ -123,6 +128,7 @@ fn include_flags_are_additive_and_keep_base_fields() {
             biotype: Some("protein_coding".to_string()),
             transcript_count: Some(2),
             sequence_length: Some(11),
+            strand: None,
         }],
         next_cursor: None,
     };
@@ -148,6 +154,7 @@ fn include_does_not_change_row_order_or_cursor() {
                 biotype: Some("pc".to_string()),
                 transcript_count: Some(1),
                 sequence_length: Some(2),
+                strand: None,
             },
             GeneRow {
                 gene_id: "g2".to_string(),
@@ -158,6 +165,7 @@ fn include_does_not_change_row_order_or_cursor() {
                 biotype: Some("pc".to_string()),
                 transcript_count: Some(1),
                 sequence_length: Some(2),
+                strand: None,
             },
         ],
         next_cursor: Some("v1.cursor.stable".to_string()),
@@ -113,6 +113,7 @@ fn limits() -> QueryLimits {
         max_prefix_len: 64,
         max_work_units: 2_000,
         max_serialization_bytes: 512 * 1024,
+        max_filter_values: 5_000,
     }
 }
 
@@ -6,7 +6,9 @@ use bijux_atlas::adapters::outbound::store::{ArtifactStore, LocalFsStore};
 use bijux_atlas::domain::dataset::{
     ArtifactChecksums, ArtifactManifest, DatasetId, DatasetLifecycleTransition, ManifestStats,
 };
+use bijux_atlas::domain::security::data_protection::{ArtifactSigningKey, TrustedSigningKeys};
 use bijux_atlas::domain::sha256_hex;
+use std::sync::Arc;
 use tempfile::tempdir;
 
 fn dataset() -> DatasetId {
@@ -90,6 +92,64 @@ fn local_backend_roundtrip_is_hermetic() {
     entries[0].validate().expect("transition validate");
 }
 
+#[test]
+fn local_backend_verifies_manifest_signatures_on_fetch() {
+    let root = tempdir().expect("tmp");
+    let signer = ArtifactSigningKey::from_seed_hex(&"11".repeat(32)).expect("signing key");
+    let other = ArtifactSigningKey::from_seed_hex(&"22".repeat(32)).expect("other key");
+    let signed_ds = dataset();
+    let unsigned_ds = DatasetId::new("111", "homo_sapiens", "GRCh38").expect("dataset");
+    let sqlite_bytes = b"sqlite".to_vec();
+
+    for (ds, store) in [
+        (
+            signed_ds.clone(),
+            LocalFsStore::new(root.path().to_path_buf()).with_signing_key(Arc::new(signer)),
+        ),
+        (
+            unsigned_ds.clone(),
+            LocalFsStore::new(root.path().to_path_buf()),
+        ),
+    ] {
+        let manifest_bytes = serde_json::to_vec(&manifest(ds.clone())).expect("manifest bytes");
+        store
+            .put_dataset(
+                &ds,
+                &manifest_bytes,
+                &sqlite_bytes,
+                &sha256_hex(&manifest_bytes),
+                &sha256_hex(&sqlite_bytes),
+            )
+            .expect("publish");
+    }
+
+    let signer_public = ArtifactSigningKey::from_seed_hex(&"11".repeat(32))
+        .expect("signing key")
+        .public_key_hex();
+    let verifying = LocalFsStore::new(root.path().to_path_buf()).with_trusted_keys(
+        TrustedSigningKeys::from_public_keys_hex(&[signer_public]).expect("trusted"),
+    );
+    assert_eq!(
+        verifying
+            .get_manifest(&signed_ds)
+            .expect("signed manifest")
+            .dataset,
+        signed_ds
+    );
+    let unsigned = verifying
+        .get_manifest(&unsigned_ds)
+        .expect_err("unsigned manifest must be rejected");
+    assert!(unsigned.message.contains("signature missing"), "{unsigned}");
+
+    let wrong_key = LocalFsStore::new(root.path().to_path_buf()).with_trusted_keys(
+        TrustedSigningKeys::from_public_keys_hex(&[other.public_key_hex()]).expect("trusted"),
+    );
+    let untrusted = wrong_key
+        .get_manifest(&signed_ds)
+        .expect_err("foreign signature must be rejected");
+    assert!(untrusted.message.contains("untrusted key"), "{untrusted}");
+}
+
 #[test]
 #[cfg(feature = "backend-s3")]
 fn http_backend_reads_from_hermetic_cached_objects() {
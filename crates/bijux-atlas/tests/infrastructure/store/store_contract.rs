@@ -5,16 +5,23 @@ use bijux_atlas::adapters::outbound::store::{
     ArtifactStore, BackendKind, LocalFsStore, StoreErrorCode, StoreMetricsCollector,
 };
 #[cfg(feature = "backend-s3")]
-use bijux_atlas::adapters::outbound::store::{HttpReadonlyStore, S3LikeStore};
+use bijux_atlas::adapters::outbound::store::{
+    dataset_publish_lock_key, HttpReadonlyStore, MultipartConfig, PublishLockConfig, S3LikeStore,
+    SigV4Credentials, SigV4Signer,
+};
 use bijux_atlas::domain::dataset::{
     ArtifactChecksums, ArtifactManifest, Catalog, CatalogEntry, DatasetId, ManifestStats,
 };
 use bijux_atlas::domain::sha256_hex;
+#[cfg(feature = "backend-s3")]
+use std::collections::HashMap;
 use std::fs;
 #[cfg(feature = "backend-s3")]
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 #[cfg(feature = "backend-s3")]
+use std::sync::Mutex;
+#[cfg(feature = "backend-s3")]
 use std::thread;
 use tempfile::tempdir;
 #[cfg(feature = "backend-s3")]
@@ -423,6 +430,731 @@ fn s3_store_uses_etag_cache_and_handles_304_for_catalog() {
     handle.join().expect("server thread");
 }
 
+#[cfg(feature = "backend-s3")]
+fn sigv4_credentials(secret: &str) -> SigV4Credentials {
+    SigV4Credentials {
+        access_key_id: "AKIDATLASTEST".to_string(),
+        secret_access_key: secret.to_string(),
+        session_token: Some("session-token".to_string()),
+    }
+}
+
+/// Object store stand-in that recomputes every request's SigV4 signature
+/// and answers 403 when it does not match.
+#[cfg(feature = "backend-s3")]
+fn spawn_sigv4_store_server() -> (String, Arc<AtomicUsize>, thread::JoinHandle<()>) {
+    let server = Server::http("127.0.0.1:0").expect("http server");
+    let base = format!("http://{}", server.server_addr());
+    let verifier = SigV4Signer::new(sigv4_credentials("server-secret"), "eu-west-1");
+    let rejected = Arc::new(AtomicUsize::new(0));
+    let rejected_clone = Arc::clone(&rejected);
+    let objects: Mutex<HashMap<String, Vec<u8>>> = Mutex::new(HashMap::new());
+    let handle = thread::spawn(move || loop {
+        let mut req = match server.recv_timeout(std::time::Duration::from_millis(500)) {
+            Ok(Some(req)) => req,
+            Ok(None) | Err(_) => break,
+        };
+        let header = |name: &'static str| {
+            req.headers()
+                .iter()
+                .find(|h| h.field.equiv(name))
+                .map(|h| h.value.as_str().to_string())
+        };
+        let host = header("Host").unwrap_or_default();
+        let authorization = header("Authorization").unwrap_or_default();
+        let amz_date = header("X-Amz-Date").unwrap_or_default();
+        let content_sha = header("X-Amz-Content-Sha256").unwrap_or_default();
+        let signed_values: Vec<(&str, String)> = ["range", "if-match", "if-none-match"]
+            .into_iter()
+            .filter_map(|name| header(name).map(|value| (name, value)))
+            .collect();
+        let method = req.method().as_str().to_string();
+        let path = req.url().to_string();
+        let mut body = Vec::new();
+        std::io::Read::read_to_end(req.as_reader(), &mut body).expect("request body");
+
+        let url = reqwest::Url::parse(&format!("http://{host}{path}")).expect("request url");
+        let signed_headers: Vec<(&str, &str)> = signed_values
+            .iter()
+            .map(|(name, value)| (*name, value.as_str()))
+            .collect();
+        let expected = verifier
+            .sign(&method, &url, &signed_headers, &content_sha, &amz_date)
+            .expect("sign");
+        let authorized = expected
+            .iter()
+            .any(|(name, value)| name == "authorization" && value == &authorization)
+            && content_sha == sha256_hex(&body);
+        if !authorized {
+            rejected_clone.fetch_add(1, Ordering::Relaxed);
+            let _ = req.respond(Response::empty(StatusCode(403)));
+            continue;
+        }
+        let mut objects = objects.lock().expect("objects");
+        match req.method() {
+            Method::Put => {
+                objects.insert(path, body);
+                let _ = req.respond(Response::empty(StatusCode(200)));
+            }
+            Method::Delete => {
+                objects.remove(&path);
+                let _ = req.respond(Response::empty(StatusCode(204)));
+            }
+            Method::Get => match objects.get(&path) {
+                Some(bytes) => {
+                    let _ = req.respond(Response::from_data(bytes.clone()));
+                }
+                None => {
+                    let _ = req.respond(Response::empty(StatusCode(404)));
+                }
+            },
+            _ => {
+                let _ = req.respond(Response::empty(StatusCode(405)));
+            }
+        }
+    });
+    (base, rejected, handle)
+}
+
+#[test]
+#[cfg(feature = "backend-s3")]
+fn s3_store_signs_requests_with_sigv4() {
+    let (base, rejected, handle) = spawn_sigv4_store_server();
+    let dataset = mk_dataset();
+    let manifest_bytes = serde_json::to_vec(&mk_manifest(dataset.clone())).expect("manifest");
+    let sqlite_bytes = b"sqlite-bytes".to_vec();
+
+    let store = S3LikeStore::new(base.clone(), "atlas".to_string()).with_sigv4(Some(
+        SigV4Signer::new(sigv4_credentials("server-secret"), "eu-west-1"),
+    ));
+    store
+        .put_dataset(
+            &dataset,
+            &manifest_bytes,
+            &sqlite_bytes,
+            &sha256_hex(&manifest_bytes),
+            &sha256_hex(&sqlite_bytes),
+        )
+        .expect("signed publish");
+    let loaded = store.get_manifest(&dataset).expect("signed manifest read");
+    assert_eq!(loaded.dataset, dataset);
+    assert_eq!(
+        store
+            .get_sqlite_bytes(&dataset)
+            .expect("signed sqlite read"),
+        sqlite_bytes
+    );
+    assert_eq!(rejected.load(Ordering::Relaxed), 0);
+
+    let wrong_secret = S3LikeStore::new(base, "atlas".to_string()).with_sigv4(Some(
+        SigV4Signer::new(sigv4_credentials("wrong-secret"), "eu-west-1"),
+    ));
+    let err = wrong_secret
+        .get_manifest(&dataset)
+        .expect_err("bad signature must be rejected");
+    assert!(err.message.contains("403"), "{err}");
+    assert_eq!(rejected.load(Ordering::Relaxed), 1);
+
+    drop(store);
+    drop(wrong_secret);
+    handle.join().expect("server thread");
+}
+
+/// Requests seen by the multipart stand-in, for asserting retry and resume behaviour.
+#[cfg(feature = "backend-s3")]
+#[derive(Default)]
+struct MultipartLog {
+    initiated: AtomicUsize,
+    /// `(object path, part number)` for every accepted part upload.
+    parts: Mutex<Vec<(String, u64)>>,
+    /// Part number -> number of upcoming attempts to answer with 500.
+    part_failures: Mutex<HashMap<u64, usize>>,
+    ranges: Mutex<Vec<String>>,
+}
+
+/// Object store stand-in speaking the multipart upload protocol and ranged GETs.
+#[cfg(feature = "backend-s3")]
+fn spawn_multipart_store_server() -> (String, Arc<MultipartLog>, thread::JoinHandle<()>) {
+    use base64::Engine as _;
+    use sha2::{Digest, Sha256};
+
+    let server = Server::http("127.0.0.1:0").expect("http server");
+    let base = format!("http://{}", server.server_addr());
+    let log = Arc::new(MultipartLog::default());
+    let log_clone = Arc::clone(&log);
+    let handle = thread::spawn(move || {
+        let mut objects: HashMap<String, Vec<u8>> = HashMap::new();
+        let mut uploads: HashMap<String, HashMap<u64, Vec<u8>>> = HashMap::new();
+        loop {
+            let mut req = match server.recv_timeout(std::time::Duration::from_millis(500)) {
+                Ok(Some(req)) => req,
+                Ok(None) | Err(_) => break,
+            };
+            let header = |name: &'static str| {
+                req.headers()
+                    .iter()
+                    .find(|h| h.field.equiv(name))
+                    .map(|h| h.value.as_str().to_string())
+            };
+            let checksum = header("x-amz-checksum-sha256");
+            let checksum_algorithm = header("x-amz-checksum-algorithm");
+            let range = header("Range");
+            let url = reqwest::Url::parse(&format!("http://mock{}", req.url())).expect("url");
+            let path = url.path().to_string();
+            let query: HashMap<String, String> = url.query_pairs().into_owned().collect();
+            let mut body = Vec::new();
+            std::io::Read::read_to_end(req.as_reader(), &mut body).expect("request body");
+
+            let response = match (req.method(), query.get("uploadId")) {
+                (Method::Post, None) if query.contains_key("uploads") => {
+                    assert_eq!(
+                        checksum_algorithm.as_deref(),
+                        Some("SHA256"),
+                        "part checksums are only verified when declared on initiate"
+                    );
+                    let n = log_clone.initiated.fetch_add(1, Ordering::Relaxed) + 1;
+                    let id = format!("upload-{n}");
+                    uploads.insert(id.clone(), HashMap::new());
+                    Response::from_string(format!(
+                        "<InitiateMultipartUploadResult><UploadId>{id}</UploadId></InitiateMultipartUploadResult>"
+                    ))
+                }
+                (Method::Put, Some(id)) => {
+                    let part: u64 = query["partNumber"].parse().expect("part number");
+                    let mut failures = log_clone.part_failures.lock().expect("failures");
+                    let expected =
+                        base64::engine::general_purpose::STANDARD.encode(Sha256::digest(&body));
+                    if let Some(left) = failures.get_mut(&part).filter(|left| **left > 0) {
+                        *left -= 1;
+                        Response::from_string("").with_status_code(500)
+                    } else if checksum.as_deref() != Some(expected.as_str()) {
+                        Response::from_string("").with_status_code(400)
+                    } else if let Some(parts) = uploads.get_mut(id) {
+                        parts.insert(part, body);
+                        log_clone
+                            .parts
+                            .lock()
+                            .expect("parts")
+                            .push((path.clone(), part));
+                        Response::from_string("").with_header(
+                            Header::from_bytes("ETag", format!("\"etag-{part}\""))
+                                .expect("etag header"),
+                        )
+                    } else {
+                        Response::from_string("").with_status_code(404)
+                    }
+                }
+                (Method::Post, Some(id)) => match uploads.remove(id) {
+                    Some(mut parts) => {
+                        let body = String::from_utf8(body).expect("complete body");
+                        let mut object = Vec::new();
+                        for chunk in body.split("<PartNumber>").skip(1) {
+                            let number: u64 =
+                                chunk[..chunk.find('<').expect("tag")].parse().expect("n");
+                            assert!(chunk.contains(&format!("&quot;etag-{number}&quot;")));
+                            let part = parts.remove(&number).expect("uploaded part");
+                            let checksum = base64::engine::general_purpose::STANDARD
+                                .encode(Sha256::digest(&part));
+                            assert!(
+                                chunk.contains(&format!(
+                                    "<ChecksumSHA256>{checksum}</ChecksumSHA256>"
+                                )),
+                                "part {number} checksum missing from completion"
+                            );
+                            object.extend(part);
+                        }
+                        objects.insert(path, object);
+                        Response::from_string("<CompleteMultipartUploadResult/>")
+                    }
+                    None => Response::from_string("").with_status_code(404),
+                },
+                (Method::Put, None) => {
+                    objects.insert(path, body);
+                    Response::from_string("")
+                }
+                (Method::Get, None) => match (objects.get(&path), range) {
+                    (Some(bytes), Some(range)) => {
+                        log_clone.ranges.lock().expect("ranges").push(range.clone());
+                        let (start, end) = range
+                            .trim_start_matches("bytes=")
+                            .split_once('-')
+                            .expect("range");
+                        let start: usize = start.parse().expect("start");
+                        let end = end
+                            .parse::<usize>()
+                            .map_or(bytes.len(), |end| (end + 1).min(bytes.len()));
+                        if start >= bytes.len() {
+                            Response::from_string("").with_status_code(416).with_header(
+                                Header::from_bytes(
+                                    "Content-Range",
+                                    format!("bytes */{}", bytes.len()),
+                                )
+                                .expect("content-range"),
+                            )
+                        } else {
+                            Response::from_data(bytes[start..end].to_vec())
+                                .with_status_code(206)
+                                .with_header(
+                                    Header::from_bytes(
+                                        "Content-Range",
+                                        format!("bytes {start}-{}/{}", end - 1, bytes.len()),
+                                    )
+                                    .expect("content-range"),
+                                )
+                        }
+                    }
+                    (Some(bytes), None) => Response::from_data(bytes.clone()),
+                    (None, _) => Response::from_string("").with_status_code(404),
+                },
+                _ => Response::from_string("").with_status_code(405),
+            };
+            let _ = req.respond(response);
+        }
+    });
+    (base, log, handle)
+}
+
+/// Five 4 KiB parts; the manifest and lock stay under the threshold.
+#[cfg(feature = "backend-s3")]
+fn multipart_sqlite_bytes() -> Vec<u8> {
+    (0..18_000_u32).map(|i| (i % 251) as u8).collect()
+}
+
+#[cfg(feature = "backend-s3")]
+fn multipart_store(base: String, state_dir: Option<std::path::PathBuf>) -> S3LikeStore {
+    let mut store = S3LikeStore::new(base, "atlas".to_string()).with_multipart(MultipartConfig {
+        part_size: 4096,
+        threshold: 8192,
+        state_dir,
+    });
+    store.retry.max_attempts = 2;
+    store.retry.base_backoff_ms = 1;
+    store
+}
+
+#[test]
+#[cfg(feature = "backend-s3")]
+fn s3_store_uploads_large_objects_in_checksummed_parts_and_streams_ranges() {
+    let (base, log, handle) = spawn_multipart_store_server();
+    let dataset = mk_dataset();
+    let manifest_bytes = serde_json::to_vec(&mk_manifest(dataset.clone())).expect("manifest");
+    let sqlite_bytes = multipart_sqlite_bytes();
+    log.part_failures.lock().expect("failures").insert(2, 1);
+
+    let store = multipart_store(base, None);
+    store
+        .put_dataset(
+            &dataset,
+            &manifest_bytes,
+            &sqlite_bytes,
+            &sha256_hex(&manifest_bytes),
+            &sha256_hex(&sqlite_bytes),
+        )
+        .expect("multipart publish");
+    assert_eq!(log.initiated.load(Ordering::Relaxed), 2);
+    let parts = log.parts.lock().expect("parts").clone();
+    let final_parts: Vec<u64> = parts
+        .iter()
+        .filter(|(path, _)| path.ends_with("/gene_summary.sqlite"))
+        .map(|(_, part)| *part)
+        .collect();
+    assert_eq!(final_parts, vec![1, 2, 3, 4, 5]);
+    assert!(
+        parts
+            .iter()
+            .all(|(path, _)| path.contains("gene_summary.sqlite")),
+        "small objects must use a single PUT"
+    );
+
+    let out = tempdir().expect("download dir");
+    let dest = out.path().join("gene_summary.sqlite");
+    fs::write(&dest, &sqlite_bytes[..6000]).expect("seed partial download");
+    let written = store
+        .download_sqlite_to(&dataset, &dest)
+        .expect("streaming download");
+    assert_eq!(written, sqlite_bytes.len() as u64);
+    assert_eq!(fs::read(&dest).expect("downloaded"), sqlite_bytes);
+    let ranges = log.ranges.lock().expect("ranges").clone();
+    assert_eq!(ranges.first().map(String::as_str), Some("bytes=6000-10095"));
+    assert_eq!(ranges.len(), 3);
+
+    // A partial left over from a different object resumes into garbage.
+    let mut stale = sqlite_bytes[..6000].to_vec();
+    stale[0] ^= 0xff;
+    fs::write(&dest, &stale).expect("seed stale partial");
+    let err = store
+        .download_sqlite_to(&dataset, &dest)
+        .expect_err("digest mismatch");
+    assert_eq!(err.code, StoreErrorCode::Validation);
+    assert!(!dest.exists(), "a corrupt download must not be left behind");
+
+    drop(store);
+    handle.join().expect("server thread");
+}
+
+#[test]
+#[cfg(feature = "backend-s3")]
+fn s3_store_resumes_interrupted_multipart_upload_without_resending_parts() {
+    let (base, log, handle) = spawn_multipart_store_server();
+    let dataset = mk_dataset();
+    let manifest_bytes = serde_json::to_vec(&mk_manifest(dataset.clone())).expect("manifest");
+    let sqlite_bytes = multipart_sqlite_bytes();
+    let state = tempdir().expect("state dir");
+    let sqlite_file = state.path().join("source.sqlite");
+    fs::write(&sqlite_file, &sqlite_bytes).expect("source sqlite");
+    // Part 4 fails on both attempts of the first run.
+    log.part_failures.lock().expect("failures").insert(4, 2);
+
+    let store = multipart_store(base, Some(state.path().join("uploads")));
+    let publish = |store: &S3LikeStore| {
+        store.put_dataset_from_path(
+            &dataset,
+            &manifest_bytes,
+            &sqlite_file,
+            &sha256_hex(&manifest_bytes),
+            &sha256_hex(&sqlite_bytes),
+        )
+    };
+    let err = publish(&store).expect_err("first publish is interrupted");
+    assert_eq!(err.code, StoreErrorCode::Network);
+    assert_eq!(
+        fs::read_dir(state.path().join("uploads"))
+            .expect("upload state")
+            .count(),
+        1
+    );
+
+    publish(&store).expect("resumed publish");
+    assert_eq!(log.initiated.load(Ordering::Relaxed), 2);
+    let tmp_parts: Vec<u64> = log
+        .parts
+        .lock()
+        .expect("parts")
+        .iter()
+        .filter(|(path, _)| path.ends_with("/gene_summary.sqlite.tmp"))
+        .map(|(_, part)| *part)
+        .collect();
+    assert_eq!(tmp_parts, vec![1, 2, 3, 4, 5]);
+    assert_eq!(
+        fs::read_dir(state.path().join("uploads"))
+            .expect("upload state")
+            .count(),
+        0
+    );
+    assert_eq!(
+        store.get_sqlite_bytes(&dataset).expect("published sqlite"),
+        sqlite_bytes
+    );
+
+    drop(store);
+    handle.join().expect("server thread");
+}
+
+/// Faults the conditional stand-in injects on lease writes.
+#[cfg(feature = "backend-s3")]
+#[derive(Default)]
+struct LeaseFaults {
+    /// Upcoming PUTs that store the object but answer 500.
+    lost_put_replies: AtomicUsize,
+    /// Answer PUTs without an ETag, like stores that omit it.
+    omit_put_etag: std::sync::atomic::AtomicBool,
+}
+
+/// Object store stand-in honouring `If-None-Match: *` and `If-Match` on PUT and DELETE.
+#[cfg(feature = "backend-s3")]
+fn spawn_conditional_store_server() -> (String, Arc<LeaseFaults>, thread::JoinHandle<()>) {
+    let server = Server::http("127.0.0.1:0").expect("http server");
+    let base = format!("http://{}", server.server_addr());
+    let faults = Arc::new(LeaseFaults::default());
+    let faults_clone = Arc::clone(&faults);
+    let handle = thread::spawn(move || {
+        let mut objects: HashMap<String, (Vec<u8>, String)> = HashMap::new();
+        let mut version = 0_u64;
+        loop {
+            let mut req = match server.recv_timeout(std::time::Duration::from_millis(500)) {
+                Ok(Some(req)) => req,
+                Ok(None) | Err(_) => break,
+            };
+            let header = |name: &'static str| {
+                req.headers()
+                    .iter()
+                    .find(|h| h.field.equiv(name))
+                    .map(|h| h.value.as_str().to_string())
+            };
+            let if_match = header("If-Match");
+            let if_none_match = header("If-None-Match");
+            let path = req.url().to_string();
+            let mut body = Vec::new();
+            std::io::Read::read_to_end(req.as_reader(), &mut body).expect("request body");
+            let current = objects.get(&path).map(|(_, etag)| etag.clone());
+            let precondition_failed = (if_none_match.as_deref() == Some("*") && current.is_some())
+                || if_match.is_some_and(|etag| current.as_deref() != Some(etag.as_str()));
+            let response = match req.method() {
+                _ if precondition_failed => Response::from_string("").with_status_code(412),
+                Method::Put => {
+                    version += 1;
+                    let etag = format!("\"v{version}\"");
+                    objects.insert(path, (body, etag.clone()));
+                    let lost = faults_clone
+                        .lost_put_replies
+                        .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1))
+                        .is_ok();
+                    if lost {
+                        Response::from_string("").with_status_code(500)
+                    } else if faults_clone.omit_put_etag.load(Ordering::Relaxed) {
+                        Response::from_string("")
+                    } else {
+                        Response::from_string("")
+                            .with_header(Header::from_bytes("ETag", etag).expect("etag header"))
+                    }
+                }
+                Method::Get => match objects.get(&path) {
+                    Some((bytes, etag)) => Response::from_data(bytes.clone()).with_header(
+                        Header::from_bytes("ETag", etag.as_str()).expect("etag header"),
+                    ),
+                    None => Response::from_string("").with_status_code(404),
+                },
+                Method::Delete => {
+                    objects.remove(&path);
+                    Response::from_string("").with_status_code(204)
+                }
+                _ => Response::from_string("").with_status_code(405),
+            };
+            let _ = req.respond(response);
+        }
+    });
+    (base, faults, handle)
+}
+
+#[cfg(feature = "backend-s3")]
+fn lock_store(base: &str, owner: &str, ttl: std::time::Duration) -> S3LikeStore {
+    S3LikeStore::new(base.to_string(), "atlas".to_string()).with_publish_lock(PublishLockConfig {
+        ttl,
+        owner: owner.to_string(),
+    })
+}
+
+#[test]
+#[cfg(feature = "backend-s3")]
+fn s3_publish_lock_is_exclusive_and_released_on_drop() {
+    let (base, _, handle) = spawn_conditional_store_server();
+    let dataset = mk_dataset();
+    let ttl = std::time::Duration::from_secs(600);
+    let first = lock_store(&base, "publisher-a", ttl);
+    let second = lock_store(&base, "publisher-b", ttl);
+
+    let guard = first.acquire_publish_lock(&dataset).expect("first lock");
+    let err = second
+        .acquire_publish_lock(&dataset)
+        .expect_err("lock is held");
+    assert_eq!(err.code, StoreErrorCode::Conflict);
+    assert!(err.message.contains("publisher-a"), "{err}");
+
+    let manifest_bytes = serde_json::to_vec(&mk_manifest(dataset.clone())).expect("manifest");
+    let sqlite_bytes = b"sqlite-bytes".to_vec();
+    let err = second
+        .put_dataset(
+            &dataset,
+            &manifest_bytes,
+            &sqlite_bytes,
+            &sha256_hex(&manifest_bytes),
+            &sha256_hex(&sqlite_bytes),
+        )
+        .expect_err("publish must wait for the lock");
+    assert_eq!(err.code, StoreErrorCode::Conflict);
+
+    drop(guard);
+    let guard = second
+        .acquire_publish_lock(&dataset)
+        .expect("lock released on drop");
+    drop(guard);
+
+    drop(first);
+    drop(second);
+    handle.join().expect("server thread");
+}
+
+#[test]
+#[cfg(feature = "backend-s3")]
+fn s3_publish_lock_takes_over_expired_lease() {
+    let (base, _, handle) = spawn_conditional_store_server();
+    let dataset = mk_dataset();
+    let crashed = lock_store(&base, "publisher-a", std::time::Duration::ZERO);
+    let stale_guard = crashed.acquire_publish_lock(&dataset).expect("stale lock");
+
+    let ttl = std::time::Duration::from_secs(600);
+    let taker = lock_store(&base, "publisher-b", ttl);
+    let guard = taker
+        .acquire_publish_lock(&dataset)
+        .expect("expired lease is taken over");
+
+    // The stale holder's release must not delete the new lease.
+    drop(stale_guard);
+    let err = lock_store(&base, "publisher-c", ttl)
+        .acquire_publish_lock(&dataset)
+        .expect_err("takeover lease is held");
+    assert!(err.message.contains("publisher-b"), "{err}");
+
+    drop(guard);
+    drop(crashed);
+    drop(taker);
+    handle.join().expect("server thread");
+}
+
+#[test]
+#[cfg(feature = "backend-s3")]
+fn s3_publish_lock_renews_while_held() {
+    let (base, _, handle) = spawn_conditional_store_server();
+    let dataset = mk_dataset();
+    let holder = lock_store(&base, "publisher-a", std::time::Duration::from_secs(2));
+    let guard = holder.acquire_publish_lock(&dataset).expect("lock");
+
+    // Well past the original two-second lease: only renewal keeps it held.
+    let other = lock_store(&base, "publisher-b", std::time::Duration::from_secs(600));
+    for _ in 0..14 {
+        thread::sleep(std::time::Duration::from_millis(250));
+        let err = other
+            .acquire_publish_lock(&dataset)
+            .expect_err("renewed lease is still held");
+        assert!(err.message.contains("publisher-a"), "{err}");
+    }
+
+    drop(guard);
+    drop(other.acquire_publish_lock(&dataset).expect("released"));
+    drop(holder);
+    drop(other);
+    handle.join().expect("server thread");
+}
+
+#[test]
+#[cfg(feature = "backend-s3")]
+fn s3_publish_lock_recognises_its_own_write_when_the_reply_is_lost() {
+    let (base, faults, handle) = spawn_conditional_store_server();
+    let dataset = mk_dataset();
+    let ttl = std::time::Duration::from_secs(600);
+    let mut store = lock_store(&base, "publisher-a", ttl);
+    store.retry.base_backoff_ms = 1;
+    faults.lost_put_replies.store(1, Ordering::Relaxed);
+    let guard = store
+        .acquire_publish_lock(&dataset)
+        .expect("the retried create found our own lease");
+    let err = lock_store(&base, "publisher-b", ttl)
+        .acquire_publish_lock(&dataset)
+        .expect_err("lease is held");
+    assert!(err.message.contains("publisher-a"), "{err}");
+    drop(guard);
+    drop(store);
+    handle.join().expect("server thread");
+}
+
+#[test]
+#[cfg(feature = "backend-s3")]
+fn s3_publish_lock_without_etags_releases_only_its_own_lease() {
+    let (base, faults, handle) = spawn_conditional_store_server();
+    faults.omit_put_etag.store(true, Ordering::Relaxed);
+    let dataset = mk_dataset();
+    let crashed = lock_store(&base, "publisher-a", std::time::Duration::ZERO);
+    let stale_guard = crashed.acquire_publish_lock(&dataset).expect("stale lock");
+    let ttl = std::time::Duration::from_secs(600);
+    let taker = lock_store(&base, "publisher-b", ttl);
+    let guard = taker.acquire_publish_lock(&dataset).expect("takeover");
+
+    drop(stale_guard);
+    let err = lock_store(&base, "publisher-c", ttl)
+        .acquire_publish_lock(&dataset)
+        .expect_err("stale release left the takeover lease alone");
+    assert!(err.message.contains("publisher-b"), "{err}");
+
+    drop(guard);
+    drop(
+        lock_store(&base, "publisher-c", ttl)
+            .acquire_publish_lock(&dataset)
+            .expect("owner released its lease"),
+    );
+    drop(crashed);
+    drop(taker);
+    handle.join().expect("server thread");
+}
+
+#[test]
+#[cfg(feature = "backend-s3")]
+fn s3_publish_lock_tells_apart_publishers_with_the_same_owner() {
+    let (base, _, handle) = spawn_conditional_store_server();
+    let dataset = mk_dataset();
+    let ttl = std::time::Duration::from_secs(600);
+    // Containers without HOSTNAME, both pid 1, starting in the same second.
+    let first = lock_store(&base, "atlas:1", ttl);
+    let second = lock_store(&base, "atlas:1", ttl);
+    let guard = first.acquire_publish_lock(&dataset).expect("first lock");
+    let err = second
+        .acquire_publish_lock(&dataset)
+        .expect_err("an identical owner must not win the held lock");
+    assert_eq!(err.code, StoreErrorCode::Conflict);
+    assert!(guard.is_held());
+    drop(guard);
+    drop(first);
+    drop(second);
+    handle.join().expect("server thread");
+}
+
+#[test]
+#[cfg(feature = "backend-s3")]
+fn s3_publish_lock_guard_reports_a_lease_taken_over_while_held() {
+    let (base, _, handle) = spawn_conditional_store_server();
+    let dataset = mk_dataset();
+    let holder = lock_store(&base, "publisher-a", std::time::Duration::from_secs(1));
+    let guard = holder.acquire_publish_lock(&dataset).expect("lock");
+    assert!(guard.is_held());
+
+    // Another publisher overwrites the lease; the next renewal notices.
+    let lease = serde_json::json!({
+        "owner": "publisher-b",
+        "token": "00".repeat(16),
+        "acquired_at_unix": 0,
+        "expires_at_unix": u64::MAX,
+    });
+    let url = format!("{base}/atlas/{}", dataset_publish_lock_key(&dataset));
+    let status = reqwest::blocking::Client::new()
+        .put(url)
+        .body(lease.to_string())
+        .send()
+        .expect("overwrite lease")
+        .status();
+    assert!(status.is_success(), "{status}");
+    thread::sleep(std::time::Duration::from_millis(800));
+    assert!(!guard.is_held());
+
+    drop(guard);
+    drop(holder);
+    handle.join().expect("server thread");
+}
+
+#[test]
+#[cfg(feature = "backend-s3")]
+fn s3_publish_stops_before_the_manifest_once_the_lease_is_gone() {
+    let (base, _, handle) = spawn_conditional_store_server();
+    let dataset = mk_dataset();
+    // A zero TTL lease has expired by the time the first object is written.
+    let store = lock_store(&base, "publisher-a", std::time::Duration::ZERO);
+    let manifest_bytes = serde_json::to_vec(&mk_manifest(dataset.clone())).expect("manifest");
+    let sqlite_bytes = b"sqlite-bytes".to_vec();
+    let err = store
+        .put_dataset(
+            &dataset,
+            &manifest_bytes,
+            &sqlite_bytes,
+            &sha256_hex(&manifest_bytes),
+            &sha256_hex(&sqlite_bytes),
+        )
+        .expect_err("publish without a live lease");
+    assert_eq!(err.code, StoreErrorCode::Conflict);
+    assert!(err.message.contains("lost"), "{err}");
+    assert!(!store.exists(&dataset).expect("exists"));
+
+    drop(store);
+    handle.join().expect("server thread");
+}
+
 #[test]
 fn random_publish_failures_do_not_create_partial_dataset() {
     let root = tempdir().expect("tempdir");
@@ -1,5 +1,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
+#[path = "client/async_client.rs"]
+mod async_client;
 #[path = "client/integration_client.rs"]
 mod integration_client;
 #[path = "client/metrics_and_schema.rs"]
@@ -0,0 +1,112 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#![cfg(feature = "client-async")]
+
+use bijux_atlas::adapters::inbound::client::{
+    AsyncAtlasClient, ClientConfig, DiffQuery, ErrorClass, InMemoryMetrics,
+};
+use bijux_atlas::contracts::api::ApiErrorCode;
+use futures::TryStreamExt;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Serves `responses` in order, one per connection, recording each request line.
+fn spawn_scripted_server(responses: Vec<(u16, &'static str)>) -> (String, Arc<Mutex<Vec<String>>>) {
+    let listener = match TcpListener::bind("127.0.0.1:0") {
+        Ok(listener) => listener,
+        Err(error) => panic!("failed to bind test server: {error}"),
+    };
+    let addr = match listener.local_addr() {
+        Ok(address) => address,
+        Err(error) => panic!("failed to get local test addr: {error}"),
+    };
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let record = Arc::clone(&seen);
+    thread::spawn(move || {
+        for (status, body) in responses {
+            let Ok((mut stream, _)) = listener.accept() else {
+                return;
+            };
+            let mut buf = [0_u8; 2048];
+            let read = stream.read(&mut buf).unwrap_or(0);
+            let request = String::from_utf8_lossy(&buf[..read]);
+            if let (Some(line), Ok(mut guard)) = (request.lines().next(), record.lock()) {
+                guard.push(line.to_string());
+            }
+            let response = format!(
+                "HTTP/1.1 {status} TEST\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+    (format!("http://{addr}"), seen)
+}
+
+fn client(base_url: String) -> AsyncAtlasClient {
+    let config = ClientConfig {
+        base_url,
+        retry_attempts: 1,
+        ..ClientConfig::default()
+    };
+    match AsyncAtlasClient::new(config) {
+        Ok(client) => client,
+        Err(error) => panic!("client init failed: {error}"),
+    }
+}
+
+#[tokio::test]
+async fn diff_pages_stream_follows_cursor_until_exhausted() {
+    let first = r#"{"api_version":"v1","contract_version":"v1","dataset":null,"page":{"next_cursor":"c1"},"data":{"diff":[{"gene_id":"g1","status":"added","seqid":"chr1","start":1,"end":10}]},"links":{"next_cursor":"c1"}}"#;
+    let second = r#"{"api_version":"v1","contract_version":"v1","dataset":null,"page":{"next_cursor":null},"data":{"diff":[{"gene_id":"g2","status":"removed"}],"qc":{}},"links":null}"#;
+    let (base_url, seen) = spawn_scripted_server(vec![(200, first), (200, second)]);
+    let metrics = InMemoryMetrics::default();
+    let client = client(base_url).with_metrics(Arc::new(metrics.clone()));
+    let query = DiffQuery::new("homo_sapiens", "GRCh38", "109", "110")
+        .with_region("chr1:1-100")
+        .with_limit(1);
+    let pages = match client.diff_pages(&query).try_collect::<Vec<_>>().await {
+        Ok(pages) => pages,
+        Err(error) => panic!("diff stream failed: {error}"),
+    };
+    let genes = pages
+        .iter()
+        .flat_map(|page| page.items.iter().map(|row| row.gene_id.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(genes, vec!["g1", "g2"]);
+    assert!(pages[1].next.is_none());
+    let requests = seen.lock().map(|guard| guard.clone()).unwrap_or_default();
+    assert_eq!(requests.len(), 2);
+    assert!(requests[0].starts_with("GET /v1/diff/region?"));
+    assert!(!requests[0].contains("cursor="));
+    assert!(requests[1].contains("cursor=c1"));
+    assert_eq!(metrics.snapshot().len(), 2);
+}
+
+#[tokio::test]
+async fn error_envelope_is_decoded_and_ids_are_path_encoded() {
+    let body = r#"{"error":{"code":"GeneNotFound","message":"gene not found","details":{"gene_id":"a/b"},"request_id":"req-1"}}"#;
+    let (base_url, seen) = spawn_scripted_server(vec![(404, body)]);
+    let client = client(base_url);
+    let dataset = match bijux_atlas::contracts::api::DatasetKeyDto::new(
+        "110".to_string(),
+        "homo_sapiens".to_string(),
+        "GRCh38".to_string(),
+    ) {
+        Ok(dataset) => dataset,
+        Err(error) => panic!("dataset key: {error}"),
+    };
+    let err = match client.gene_transcripts(&dataset, "a/b", 10, None).await {
+        Ok(_) => panic!("expected not found"),
+        Err(error) => error,
+    };
+    assert_eq!(err.class, ErrorClass::Client);
+    assert_eq!(
+        err.api_error.as_ref().map(|api| api.code),
+        Some(ApiErrorCode::GeneNotFound)
+    );
+    let requests = seen.lock().map(|guard| guard.clone()).unwrap_or_default();
+    assert!(requests[0].starts_with("GET /v1/genes/a%2Fb/transcripts?"));
+}
@@ -4,10 +4,16 @@
 mod api_contracts;
 #[path = "server/api_contracts_support/mod.rs"]
 mod api_contracts_support;
+#[path = "server/api_key_registry.rs"]
+mod api_key_registry;
 #[path = "server/api_surface_snapshots.rs"]
 mod api_surface_snapshots;
+#[path = "server/async_client_contract.rs"]
+mod async_client_contract;
 #[path = "server/async_runtime_contract.rs"]
 mod async_runtime_contract;
+#[path = "server/authz_role_bindings.rs"]
+mod authz_role_bindings;
 #[path = "server/core_route_contracts.rs"]
 mod core_route_contracts;
 #[path = "server/download_then_serve.rs"]
@@ -34,3 +40,7 @@ mod s3_backend;
 mod schema_evolution_regression;
 #[path = "server/security_input_resilience.rs"]
 mod security_input_resilience;
+#[path = "server/tenant_quotas.rs"]
+mod tenant_quotas;
+#[path = "server/tls_termination.rs"]
+mod tls_termination;
@@ -14,7 +14,8 @@ use sha2::Sha256;
 use tempfile::tempdir;
 
 use super::api_contracts_support::{
-    fixture_fasta_and_fai, fixture_release_index, mk_dataset, send_raw, send_raw_with_method,
+    decode_chunked, fixture_fasta_and_fai, fixture_release_index, mk_dataset, send_raw,
+    send_raw_bytes, send_raw_with_method,
 };
 
 fn header_value(headers: &str, name: &str) -> Option<String> {
@@ -146,12 +147,12 @@ async fn error_contract_and_etag_behaviors() {
 
     let (status, _, body) = send_raw(
         addr,
-        "/v1/genes?release=110&species=homo_sapiens&assembly=GRCh38&strand=plus",
+        "/v1/genes?release=110&species=homo_sapiens&assembly=GRCh38&biotype=protein_coding&strand=plus",
         &[],
     )
     .await;
     assert_eq!(status, 400);
-    assert!(body.contains("strand filter is explicit but unsupported"));
+    assert!(body.contains("strand-aware filtering is not available"));
 
     let (status, _, body) = send_raw(
         addr,
@@ -802,6 +803,401 @@ async fn genes_count_applies_filters_consistently() {
     assert_eq!(payload.get("gene_count").and_then(Value::as_i64), Some(0));
 }
 
+fn gene_batch_sqlite() -> Vec<u8> {
+    let dir = tempdir().expect("tempdir");
+    let db = dir.path().join("batch.sqlite");
+    let conn = rusqlite::Connection::open(&db).expect("open sqlite");
+    conn.execute_batch(
+        "CREATE TABLE gene_summary(id INTEGER PRIMARY KEY, gene_id TEXT, name TEXT, name_normalized TEXT, biotype TEXT, seqid TEXT, start INT, end INT, transcript_count INT, exon_count INT DEFAULT 0, total_exon_span INT DEFAULT 0, cds_present INT DEFAULT 0, sequence_length INT);
+         CREATE TABLE dataset_stats(dimension TEXT NOT NULL, value TEXT NOT NULL, gene_count INTEGER NOT NULL, PRIMARY KEY (dimension, value));
+         CREATE INDEX idx_gene_summary_gene_id ON gene_summary(gene_id);
+         CREATE INDEX idx_gene_summary_biotype ON gene_summary(biotype);
+         INSERT INTO gene_summary(id,gene_id,name,name_normalized,biotype,seqid,start,end,transcript_count,sequence_length) VALUES
+           (1,'g1','G1','g1','pc','chr1',1,10,1,10),
+           (2,'g2','G2','g2','lnc','chr1',5,12,1,8),
+           (3,'g3','G3','g3','pc','chr1',11,20,1,10),
+           (4,'g4','G4','g4','pseudo','chr1',15,20,1,6);
+         INSERT INTO dataset_stats(dimension,value,gene_count) VALUES ('biotype','pc',2), ('biotype','lnc',1), ('biotype','pseudo',1), ('seqid','chr1',4);",
+    )
+    .expect("seed sqlite");
+    std::fs::read(db).expect("read sqlite bytes")
+}
+
+#[tokio::test]
+async fn genes_query_body_accepts_list_filters_and_pages_with_cursor() {
+    let ds = DatasetId::new("110", "homo_sapiens", "GRCh38").expect("dataset id");
+    let sqlite = gene_batch_sqlite();
+    let (fasta, fai) = fixture_fasta_and_fai();
+    let manifest = ArtifactManifest::new(
+        "1".to_string(),
+        "1".to_string(),
+        ds.clone(),
+        ArtifactChecksums::new(
+            "a".repeat(64),
+            sha256_hex(&fasta),
+            sha256_hex(&fai),
+            sha256_hex(&sqlite),
+        ),
+        ManifestStats::new(4, 4, 4),
+    );
+    let store = Arc::new(FakeStore::default());
+    store.manifest.lock().await.insert(ds.clone(), manifest);
+    store.sqlite.lock().await.insert(ds.clone(), sqlite);
+    let tmp = tempdir().expect("tempdir");
+    let cfg = DatasetCacheConfig {
+        disk_root: tmp.path().to_path_buf(),
+        ..Default::default()
+    };
+    let app = build_router(AppState::with_config(
+        DatasetCacheManager::new(cfg, store),
+        ApiConfig::default(),
+        Default::default(),
+    ));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind listener");
+    let addr = listener.local_addr().expect("local addr");
+    tokio::spawn(async move { axum::serve(listener, app).await.expect("serve app") });
+
+    let query = |filter: &str, page: &str| {
+        format!(r#"{{"dataset":"110/homo_sapiens/GRCh38","filter":{filter},"page":{page}}}"#)
+    };
+    let gene_ids = |body: &str| -> Vec<String> {
+        let json: Value = serde_json::from_str(body).expect("genes json");
+        json["data"]["rows"]
+            .as_array()
+            .expect("rows")
+            .iter()
+            .map(|row| row["gene_id"].as_str().expect("gene_id").to_string())
+            .collect()
+    };
+
+    let batch = r#"{"gene_id":["g4","g1","missing","g3"]}"#;
+    let (status, head, body) = send_raw_with_method(
+        addr,
+        "POST",
+        "/v1/genes/query",
+        &[],
+        Some(&query(batch, r#"{"limit":2}"#)),
+    )
+    .await;
+    assert_eq!(status, 200, "{body}");
+    assert_eq!(
+        header_value(&head, "x-atlas-query-class").as_deref(),
+        Some("cheap")
+    );
+    assert_eq!(gene_ids(&body), vec!["g1", "g3"]);
+    let json: Value = serde_json::from_str(&body).expect("genes json");
+    let cursor = json["page"]["next_cursor"].as_str().expect("next cursor");
+
+    let (status, _, body) = send_raw_with_method(
+        addr,
+        "POST",
+        "/v1/genes/query",
+        &[],
+        Some(&query(
+            batch,
+            &format!(r#"{{"limit":2,"cursor":"{cursor}"}}"#),
+        )),
+    )
+    .await;
+    assert_eq!(status, 200, "{body}");
+    assert_eq!(gene_ids(&body), vec!["g4"]);
+    let json: Value = serde_json::from_str(&body).expect("genes json");
+    assert!(json["page"]["next_cursor"].is_null());
+
+    let (status, _, body) = send_raw_with_method(
+        addr,
+        "POST",
+        "/v1/genes/query",
+        &[],
+        Some(&query(
+            r#"{"gene_id":["g1","g2","g3"],"biotype":["pc","pseudo"]}"#,
+            r#"{"limit":10}"#,
+        )),
+    )
+    .await;
+    assert_eq!(status, 200, "{body}");
+    assert_eq!(gene_ids(&body), vec!["g1", "g3"]);
+
+    let (status, _, body) = send_raw_with_method(
+        addr,
+        "POST",
+        "/v1/genes/query",
+        &[],
+        Some(&query(r#"{"gene_id":"g2"}"#, "{}")),
+    )
+    .await;
+    assert_eq!(status, 200, "{body}");
+    assert_eq!(gene_ids(&body), vec!["g2"]);
+
+    for bad in [
+        query(r#"{"gene_id":[]}"#, "{}"),
+        query(r#"{"gene_id":["g1",""]}"#, "{}"),
+        query(r#"{"region":["chr1:10-1"]}"#, "{}"),
+        query(r#"{"gene_ids":["g1"]}"#, "{}"),
+        query(r#"{"gene_id":["g1"]}"#, r#"{"limit":0}"#),
+        r#"{"filter":{"gene_id":["g1"]}}"#.to_string(),
+    ] {
+        let (status, _, body) =
+            send_raw_with_method(addr, "POST", "/v1/genes/query", &[], Some(&bad)).await;
+        assert!(status == 400 || status == 422, "{bad}: {status} {body}");
+    }
+}
+
+fn arrow_sqlite() -> Vec<u8> {
+    let dir = tempdir().expect("tempdir");
+    let db = dir.path().join("arrow.sqlite");
+    let conn = rusqlite::Connection::open(&db).expect("open sqlite");
+    conn.execute_batch(
+        "CREATE TABLE gene_summary(id INTEGER PRIMARY KEY, gene_id TEXT, name TEXT, name_normalized TEXT, biotype TEXT, seqid TEXT, start INT, end INT, transcript_count INT, exon_count INT DEFAULT 0, total_exon_span INT DEFAULT 0, cds_present INT DEFAULT 0, sequence_length INT);
+         CREATE TABLE transcript_summary(id INTEGER PRIMARY KEY, transcript_id TEXT, parent_gene_id TEXT, transcript_type TEXT, biotype TEXT, seqid TEXT, start INT, end INT, exon_count INT, total_exon_span INT, cds_present INT);
+         CREATE TABLE dataset_stats(dimension TEXT NOT NULL, value TEXT NOT NULL, gene_count INTEGER NOT NULL, PRIMARY KEY (dimension, value));
+         CREATE INDEX idx_gene_summary_gene_id ON gene_summary(gene_id);
+         CREATE INDEX idx_gene_summary_biotype ON gene_summary(biotype);
+         CREATE INDEX idx_transcript_summary_transcript_id ON transcript_summary(transcript_id);
+         CREATE INDEX idx_transcript_summary_parent_gene_id ON transcript_summary(parent_gene_id);
+         INSERT INTO gene_summary(id,gene_id,name,name_normalized,biotype,seqid,start,end,transcript_count,sequence_length) VALUES
+           (1,'g1','G1','g1','pc','chr1',1,10,2,10),
+           (2,'g2',NULL,'g2','pc','chr1',5,12,1,8);
+         INSERT INTO transcript_summary(id,transcript_id,parent_gene_id,transcript_type,biotype,seqid,start,end,exon_count,total_exon_span,cds_present) VALUES
+           (1,'tx1','g1','transcript','pc','chr1',1,10,1,10,1),
+           (2,'tx2','g1','transcript',NULL,'chr1',2,9,2,6,0);
+         INSERT INTO dataset_stats(dimension,value,gene_count) VALUES ('biotype','pc',2), ('seqid','chr1',2);",
+    )
+    .expect("seed sqlite");
+    std::fs::read(db).expect("read sqlite bytes")
+}
+
+#[tokio::test]
+async fn genes_and_transcripts_negotiate_arrow_stream() {
+    let ds = DatasetId::new("110", "homo_sapiens", "GRCh38").expect("dataset id");
+    let sqlite = arrow_sqlite();
+    let (fasta, fai) = fixture_fasta_and_fai();
+    let manifest = ArtifactManifest::new(
+        "1".to_string(),
+        "1".to_string(),
+        ds.clone(),
+        ArtifactChecksums::new(
+            "a".repeat(64),
+            sha256_hex(&fasta),
+            sha256_hex(&fai),
+            sha256_hex(&sqlite),
+        ),
+        ManifestStats::new(2, 2, 2),
+    );
+    let store = Arc::new(FakeStore::default());
+    store.manifest.lock().await.insert(ds.clone(), manifest);
+    store.sqlite.lock().await.insert(ds.clone(), sqlite);
+    let tmp = tempdir().expect("tempdir");
+    let cfg = DatasetCacheConfig {
+        disk_root: tmp.path().to_path_buf(),
+        ..Default::default()
+    };
+    let app = build_router(AppState::with_config(
+        DatasetCacheManager::new(cfg, store),
+        ApiConfig::default(),
+        Default::default(),
+    ));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind listener");
+    let addr = listener.local_addr().expect("local addr");
+    tokio::spawn(async move { axum::serve(listener, app).await.expect("serve app") });
+
+    let arrow = [("Accept", "application/vnd.apache.arrow.stream")];
+    let contains =
+        |haystack: &[u8], needle: &[u8]| haystack.windows(needle.len()).any(|w| w == needle);
+    // Decoded with the Arrow reader so the body is what DuckDB or Polars would load.
+    let column_strings = |body: &[u8], name: &str| -> Vec<String> {
+        let reader =
+            arrow_ipc::reader::StreamReader::try_new(std::io::Cursor::new(body.to_vec()), None)
+                .expect("arrow stream");
+        assert!(reader.schema().field_with_name(name).is_ok(), "{name}");
+        let mut values = Vec::new();
+        for batch in reader {
+            let batch = batch.expect("record batch");
+            let column = batch.column_by_name(name).expect("column");
+            let strings = column
+                .as_any()
+                .downcast_ref::<arrow_array::StringArray>()
+                .expect("utf8 column");
+            values.extend(strings.iter().flatten().map(str::to_string));
+        }
+        values
+    };
+
+    let genes = "/v1/genes?release=110&species=homo_sapiens&assembly=GRCh38&biotype=pc&include=coords&limit=1";
+    let (status, head, body) = send_raw_bytes(addr, genes, &arrow).await;
+    assert_eq!(status, 200, "{}", String::from_utf8_lossy(&body));
+    assert_eq!(
+        header_value(&head, "content-type").as_deref(),
+        Some("application/vnd.apache.arrow.stream")
+    );
+    assert!(header_value(&head, "vary").is_some_and(|v| v.contains("accept")));
+    let cursor = header_value(&head, "x-atlas-next-cursor").expect("arrow page cursor");
+    assert_eq!(column_strings(&body, "gene_id"), vec!["g1"]);
+    assert!(
+        !contains(&body, b"\"rows\""),
+        "arrow body carries no json envelope"
+    );
+
+    let (status, head, body) =
+        send_raw_bytes(addr, &format!("{genes}&cursor={cursor}"), &arrow).await;
+    assert_eq!(status, 200);
+    assert!(header_value(&head, "x-atlas-next-cursor").is_none());
+    assert_eq!(column_strings(&body, "gene_id"), vec!["g2"]);
+
+    let (status, head, body) = send_raw(addr, genes, &[]).await;
+    assert_eq!(status, 200);
+    assert!(header_value(&head, "content-type").is_some_and(|v| v.contains("application/json")));
+    assert!(body.contains("\"rows\""));
+
+    let transcripts = "/v1/genes/g1/transcripts?release=110&species=homo_sapiens&assembly=GRCh38";
+    let (status, head, body) = send_raw_bytes(addr, transcripts, &arrow).await;
+    assert_eq!(status, 200, "{}", String::from_utf8_lossy(&body));
+    assert_eq!(
+        header_value(&head, "content-type").as_deref(),
+        Some("application/vnd.apache.arrow.stream")
+    );
+    assert_eq!(column_strings(&body, "transcript_id"), vec!["tx1", "tx2"]);
+}
+
+fn annotation_sqlite() -> Vec<u8> {
+    let dir = tempdir().expect("tempdir");
+    let db = dir.path().join("annotation.sqlite");
+    let conn = rusqlite::Connection::open(&db).expect("open sqlite");
+    conn.execute_batch(
+        "CREATE TABLE gene_summary(id INTEGER PRIMARY KEY, gene_id TEXT, name TEXT, name_normalized TEXT, biotype TEXT, seqid TEXT, start INT, end INT, transcript_count INT, exon_count INT DEFAULT 0, total_exon_span INT DEFAULT 0, cds_present INT DEFAULT 0, sequence_length INT, strand TEXT);
+         CREATE TABLE transcript_summary(id INTEGER PRIMARY KEY, transcript_id TEXT, parent_gene_id TEXT, transcript_type TEXT, biotype TEXT, seqid TEXT, start INT, end INT, exon_count INT, total_exon_span INT, cds_present INT);
+         CREATE TABLE exons(id INTEGER PRIMARY KEY, exon_id TEXT, transcript_id TEXT, seqid TEXT, start INT, end INT, exon_length INT);
+         CREATE TABLE cds_segments(id INTEGER PRIMARY KEY, cds_id TEXT, transcript_id TEXT, seqid TEXT, start INT, end INT, phase INT);
+         CREATE TABLE dataset_stats(dimension TEXT NOT NULL, value TEXT NOT NULL, gene_count INTEGER NOT NULL, PRIMARY KEY (dimension, value));
+         CREATE INDEX idx_gene_summary_gene_id ON gene_summary(gene_id);
+         CREATE INDEX idx_gene_summary_biotype ON gene_summary(biotype);
+         CREATE INDEX idx_transcript_summary_transcript_id ON transcript_summary(transcript_id);
+         CREATE INDEX idx_transcript_summary_parent_gene_id ON transcript_summary(parent_gene_id);
+         INSERT INTO gene_summary(id,gene_id,name,name_normalized,biotype,seqid,start,end,transcript_count,sequence_length,strand) VALUES
+           (1,'g1','G1','g1','pc','chr1',101,300,2,200,'-'),
+           (2,'g2','G2','g2','pc','chr2',11,40,0,30,'+');
+         INSERT INTO transcript_summary(id,transcript_id,parent_gene_id,transcript_type,biotype,seqid,start,end,exon_count,total_exon_span,cds_present) VALUES
+           (1,'txB','g1','mRNA','pc','chr1',101,200,2,40,1),
+           (2,'txA','g1','mRNA','pc','chr1',151,300,1,150,0);
+         INSERT INTO exons VALUES (1,'e1','txB','chr1',101,120,20), (2,'e2','txB','chr1',181,200,20), (3,'e3','txA','chr1',151,300,150);
+         INSERT INTO cds_segments VALUES (1,'c1','txB','chr1',111,120,1), (2,'c2','txB','chr1',181,190,0);
+         INSERT INTO dataset_stats(dimension,value,gene_count) VALUES ('biotype','pc',2), ('seqid','chr1',1), ('seqid','chr2',1);",
+    )
+    .expect("seed sqlite");
+    std::fs::read(db).expect("read sqlite bytes")
+}
+
+#[tokio::test]
+async fn genes_and_transcripts_render_bed_gtf_gff3_pages_that_concatenate() {
+    let ds = DatasetId::new("110", "homo_sapiens", "GRCh38").expect("dataset id");
+    let sqlite = annotation_sqlite();
+    let (fasta, fai) = fixture_fasta_and_fai();
+    let manifest = ArtifactManifest::new(
+        "1".to_string(),
+        "1".to_string(),
+        ds.clone(),
+        ArtifactChecksums::new(
+            "a".repeat(64),
+            sha256_hex(&fasta),
+            sha256_hex(&fai),
+            sha256_hex(&sqlite),
+        ),
+        ManifestStats::new(2, 2, 2),
+    );
+    let store = Arc::new(FakeStore::default());
+    store.manifest.lock().await.insert(ds.clone(), manifest);
+    store.sqlite.lock().await.insert(ds.clone(), sqlite);
+    let tmp = tempdir().expect("tempdir");
+    let cfg = DatasetCacheConfig {
+        disk_root: tmp.path().to_path_buf(),
+        ..Default::default()
+    };
+    let app = build_router(AppState::with_config(
+        DatasetCacheManager::new(cfg, store),
+        ApiConfig::default(),
+        Default::default(),
+    ));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind listener");
+    let addr = listener.local_addr().expect("local addr");
+    tokio::spawn(async move { axum::serve(listener, app).await.expect("serve app") });
+
+    let dataset = "release=110&species=homo_sapiens&assembly=GRCh38";
+    let (status, head, body) = send_raw(
+        addr,
+        &format!("/v1/genes?{dataset}&biotype=pc&format=bed"),
+        &[],
+    )
+    .await;
+    assert_eq!(status, 200, "{body}");
+    assert_eq!(
+        header_value(&head, "content-type").as_deref(),
+        Some("text/x-bed")
+    );
+    assert_eq!(body, "chr1\t100\t300\tg1\t0\t-\nchr2\t10\t40\tg2\t0\t+\n");
+
+    let (status, _, body) = send_raw(
+        addr,
+        &format!("/v1/genes?{dataset}&gene_id=g1&format=gff3"),
+        &[],
+    )
+    .await;
+    assert_eq!(status, 200, "{body}");
+    assert_eq!(
+        body,
+        "##gff-version 3\nchr1\tbijux-atlas\tgene\t101\t300\t.\t-\t.\tID=g1;Name=G1;biotype=pc\n"
+    );
+
+    let transcripts = format!("/v1/genes/g1/transcripts?{dataset}");
+    let (status, head, bed) = send_raw(addr, &format!("{transcripts}&format=bed"), &[]).await;
+    assert_eq!(status, 200, "{bed}");
+    assert_eq!(
+        header_value(&head, "content-type").as_deref(),
+        Some("text/x-bed")
+    );
+    assert_eq!(
+        bed,
+        "chr1\t100\t200\ttxB\t0\t-\t110\t190\t0\t2\t20,20,\t0,80,\n\
+         chr1\t150\t300\ttxA\t0\t-\t150\t150\t0\t1\t150,\t0,\n"
+    );
+
+    for format in ["bed", "gtf", "gff3"] {
+        let (_, _, whole) = send_raw(addr, &format!("{transcripts}&format={format}"), &[]).await;
+        let (status, head, first) =
+            send_raw(addr, &format!("{transcripts}&format={format}&limit=1"), &[]).await;
+        assert_eq!(status, 200, "{first}");
+        let cursor = header_value(&head, "x-atlas-next-cursor").expect("page cursor");
+        let (_, head, second) = send_raw(
+            addr,
+            &format!("{transcripts}&format={format}&limit=1&cursor={cursor}"),
+            &[],
+        )
+        .await;
+        assert!(header_value(&head, "x-atlas-next-cursor").is_none());
+        assert_eq!(
+            format!("{first}{second}"),
+            whole,
+            "{format} pages concatenate"
+        );
+    }
+
+    let (_, _, gtf) = send_raw(addr, &format!("{transcripts}&format=gtf"), &[]).await;
+    assert!(gtf.contains("\tCDS\t111\t120\t.\t-\t1\tgene_id \"g1\"; transcript_id \"txB\";"));
+
+    for bad in [
+        format!("/v1/genes?{dataset}&gene_id=g1&format=vcf"),
+        format!("{transcripts}&format=vcf"),
+    ] {
+        let (status, _, body) = send_raw(addr, &bad, &[]).await;
+        assert_eq!(status, 400, "{bad}: {body}");
+    }
+}
+
 #[tokio::test]
 async fn transport_not_found_and_method_not_allowed_use_error_envelope() {
     let store = Arc::new(FakeStore::default());
@@ -967,7 +1363,559 @@ async fn sequence_endpoint_boundary_conditions_are_enforced() {
     assert!(body.contains("\"gc_fraction\""));
 }
 
+#[tokio::test]
+async fn sequence_region_applies_strand_flanks_and_mask() {
+    let (ds, manifest, sqlite) = mk_dataset();
+    let (fasta, fai) = fixture_fasta_and_fai();
+    let store = Arc::new(FakeStore::default());
+    store.manifest.lock().await.insert(ds.clone(), manifest);
+    store.sqlite.lock().await.insert(ds.clone(), sqlite);
+    store.fasta.lock().await.insert(ds.clone(), fasta);
+    store.fai.lock().await.insert(ds.clone(), fai);
+    let tmp = tempdir().expect("tempdir");
+    let cfg = DatasetCacheConfig {
+        disk_root: tmp.path().to_path_buf(),
+        ..Default::default()
+    };
+    let api = ApiConfig {
+        max_sequence_bases: 20,
+        sequence_api_key_required_bases: 12,
+        ..ApiConfig::default()
+    };
+    let app = build_router(AppState::with_config(
+        DatasetCacheManager::new(cfg, store),
+        api,
+        Default::default(),
+    ));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind listener");
+    let addr = listener.local_addr().expect("local addr");
+    tokio::spawn(async move { axum::serve(listener, app).await.expect("serve app") });
+    let base = "release=110&species=homo_sapiens&assembly=GRCh38";
+
+    // chr1 = ACGTACGTAC GGGGnnnnTT; on the minus strand upstream extends to the right.
+    let (status, _, body) = send_raw(
+        addr,
+        &format!("/v1/sequence/region?{base}&region=chr1:9-12&strand=minus&upstream=3&downstream=1&mask=upper"),
+        &[],
+    )
+    .await;
+    assert_eq!(status, 200);
+    let json: Value = serde_json::from_str(&body).expect("json body");
+    assert_eq!(json["data"]["sequence"], "NCCCCGTA");
+    assert_eq!(json["data"]["region"]["start"], 8);
+    assert_eq!(json["data"]["region"]["end"], 15);
+    assert_eq!(json["data"]["region"]["strand"], "-");
+    assert_eq!(json["data"]["region"]["upstream"], 3);
+    assert_eq!(json["data"]["region"]["downstream"], 1);
+
+    let (status, _, body) = send_raw(
+        addr,
+        &format!("/v1/sequence/region?{base}&region=chr1:18-20&downstream=5&mask=hard"),
+        &[],
+    )
+    .await;
+    assert_eq!(status, 200);
+    let json: Value = serde_json::from_str(&body).expect("json body");
+    assert_eq!(json["data"]["sequence"], "NTT");
+    assert_eq!(json["data"]["region"]["end"], 20);
+    assert_eq!(json["data"]["region"]["downstream"], 0);
+
+    let (status, _, body) = send_raw(
+        addr,
+        &format!("/v1/sequence/region?{base}&region=chr1:1-3&strand=minus"),
+        &[("accept", "text/plain")],
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(
+        body,
+        ">chr1:1-3 strand=- upstream=0 downstream=0 mask=preserve\nCGT\n"
+    );
+
+    // Unclamped flanks count towards the api-key threshold.
+    let (status, _, _) = send_raw(
+        addr,
+        &format!("/v1/sequence/region?{base}&region=chr1:18-20&downstream=10"),
+        &[],
+    )
+    .await;
+    assert_eq!(status, 401);
+
+    let (status, _, body) = send_raw(
+        addr,
+        &format!("/v1/genes/g1/sequence?{base}&flank=2&upstream=3&strand=minus"),
+        &[("x-api-key", "k1")],
+    )
+    .await;
+    assert_eq!(status, 200);
+    let json: Value = serde_json::from_str(&body).expect("json body");
+    assert_eq!(json["data"]["sequence"], "nCCCCGTACGTACGT");
+    assert_eq!(json["data"]["region"]["upstream"], 5);
+    assert_eq!(json["data"]["region"]["downstream"], 0);
+
+    for bad in ["strand=sideways", "mask=soft", "upstream=-1"] {
+        let (status, _, _) = send_raw(
+            addr,
+            &format!("/v1/sequence/region?{base}&region=chr1:1-3&{bad}"),
+            &[],
+        )
+        .await;
+        assert_eq!(status, 400, "{bad}");
+    }
+}
+
+/// `payload` as BGZF blocks of `block` bytes plus the EOF block, with the
+/// matching `.gzi` index.
+fn bgzf_with_gzi(payload: &[u8], block: usize) -> (Vec<u8>, Vec<u8>) {
+    use std::io::Write as _;
+    let mut out = Vec::new();
+    let mut index = Vec::new();
+    for (i, chunk) in payload.chunks(block).chain([&[][..]]).enumerate() {
+        if i > 0 && !chunk.is_empty() {
+            index.push((out.len() as u64, (i * block) as u64));
+        }
+        let mut encoder = flate2::GzBuilder::new()
+            .extra(vec![b'B', b'C', 2, 0, 0, 0])
+            .write(Vec::new(), flate2::Compression::default());
+        encoder.write_all(chunk).expect("encode block");
+        let mut bytes = encoder.finish().expect("finish block");
+        let bsize = u16::try_from(bytes.len() - 1).expect("block size");
+        bytes[16..18].copy_from_slice(&bsize.to_le_bytes());
+        out.extend(bytes);
+    }
+    let mut gzi = (index.len() as u64).to_le_bytes().to_vec();
+    for (compressed, decoded) in index {
+        gzi.extend(compressed.to_le_bytes());
+        gzi.extend(decoded.to_le_bytes());
+    }
+    (out, gzi)
+}
+
+fn find_file(root: &std::path::Path, name: &str) -> Option<std::path::PathBuf> {
+    for entry in std::fs::read_dir(root).ok()?.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            if let Some(found) = find_file(&path, name) {
+                return Some(found);
+            }
+        } else if path.file_name().is_some_and(|n| n == name) {
+            return Some(path);
+        }
+    }
+    None
+}
+
+#[tokio::test]
+async fn sequence_windows_seek_through_the_gzi_of_a_bgzf_artifact() {
+    let bases: Vec<u8> = (0..3_000_u32)
+        .map(|i| b"ACGT"[(i * 7 % 11 % 4) as usize])
+        .collect();
+    let mut fasta_plain = b">chr1\n".to_vec();
+    for line in bases.chunks(60) {
+        fasta_plain.extend(line);
+        fasta_plain.push(b'\n');
+    }
+    let fai = b"chr1\t3000\t6\t60\t61\n".to_vec();
+    let (fasta, gzi) = bgzf_with_gzi(&fasta_plain, 512);
+    let expected = String::from_utf8(bases[2_099..2_140].to_vec()).expect("ascii");
+
+    for published in [true, false] {
+        let (ds, mut manifest, sqlite) = mk_dataset();
+        let mut checksums = ArtifactChecksums::new(
+            "a".repeat(64),
+            sha256_hex(&fasta),
+            sha256_hex(&fai),
+            manifest.checksums.sqlite_sha256.clone(),
+        );
+        let store = Arc::new(FakeStore::default());
+        if published {
+            checksums = checksums.with_fasta_gzi(sha256_hex(&gzi));
+            store.fasta_gzi.lock().await.insert(ds.clone(), gzi.clone());
+        }
+        manifest.checksums = checksums;
+        store.manifest.lock().await.insert(ds.clone(), manifest);
+        store.sqlite.lock().await.insert(ds.clone(), sqlite);
+        store.fasta.lock().await.insert(ds.clone(), fasta.clone());
+        store.fai.lock().await.insert(ds.clone(), fai.clone());
+        let tmp = tempdir().expect("tempdir");
+        let cfg = DatasetCacheConfig {
+            disk_root: tmp.path().to_path_buf(),
+            ..Default::default()
+        };
+        let app = build_router(AppState::with_config(
+            DatasetCacheManager::new(cfg, store),
+            ApiConfig::default(),
+            Default::default(),
+        ));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind listener");
+        let addr = listener.local_addr().expect("local addr");
+        tokio::spawn(async move { axum::serve(listener, app).await.expect("serve app") });
+
+        let (status, _, body) = send_raw(
+            addr,
+            "/v1/sequence/region?release=110&species=homo_sapiens&assembly=GRCh38&region=chr1:2100-2140",
+            &[],
+        )
+        .await;
+        assert_eq!(status, 200, "{body}");
+        let json: Value = serde_json::from_str(&body).expect("json body");
+        assert_eq!(json["data"]["sequence"], expected.as_str());
+        // Published or rebuilt from the blocks, the cached index is the same.
+        let cached = find_file(tmp.path(), "genome.fa.bgz.gzi").expect("cached gzi");
+        assert_eq!(
+            std::fs::read(cached).expect("read gzi"),
+            gzi,
+            "published={published}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn sequence_batch_streams_records_in_order_with_inline_errors() {
+    let (ds, manifest, sqlite) = mk_dataset();
+    let (fasta, fai) = fixture_fasta_and_fai();
+    let store = Arc::new(FakeStore::default());
+    store.manifest.lock().await.insert(ds.clone(), manifest);
+    store.sqlite.lock().await.insert(ds.clone(), sqlite);
+    store.fasta.lock().await.insert(ds.clone(), fasta);
+    store.fai.lock().await.insert(ds.clone(), fai);
+    let tmp = tempdir().expect("tempdir");
+    let cfg = DatasetCacheConfig {
+        disk_root: tmp.path().to_path_buf(),
+        ..Default::default()
+    };
+    let api = ApiConfig {
+        max_sequence_bases: 12,
+        max_sequence_batch_bases: 20,
+        sequence_api_key_required_bases: 20,
+        ..ApiConfig::default()
+    };
+    let app = build_router(AppState::with_config(
+        DatasetCacheManager::new(cfg, store),
+        api,
+        Default::default(),
+    ));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind listener");
+    let addr = listener.local_addr().expect("local addr");
+    tokio::spawn(async move { axum::serve(listener, app).await.expect("serve app") });
+
+    // chr1 = ACGTACGTAC GGGGnnnnTT; g1 spans chr1:1-10.
+    let items = r#"[
+        {"region":"chr1:1-3","strand":"minus"},
+        {"gene_id":"g1","upstream":2,"mask":"upper"},
+        {"region":"chrX:1-2"},
+        {"gene_id":"nope"},
+        {"region":"chr1:15-17","mask":"hard"}
+    ]"#;
+    let body = |format: &str, items: &str| {
+        format!(
+            r#"{{"release":"110","species":"homo_sapiens","assembly":"GRCh38","format":"{format}","items":{items}}}"#
+        )
+    };
+
+    let (status, head, raw) = send_raw_with_method(
+        addr,
+        "POST",
+        "/v1/sequence/batch",
+        &[("x-api-key", "k1")],
+        Some(&body("fasta", items)),
+    )
+    .await;
+    assert_eq!(status, 200, "{raw}");
+    assert_eq!(
+        header_value(&head, "content-type").as_deref(),
+        Some("text/x-fasta; charset=utf-8")
+    );
+    assert_eq!(
+        decode_chunked(&raw),
+        ">chr1:1-3 strand=- upstream=0 downstream=0 mask=preserve\nCGT\n\
+         >g1 chr1:1-10 strand=+ upstream=0 downstream=0 mask=upper\nACGTACGTAC\n\
+         >chrX:1-2 error=InvalidQueryParameter message=\"contig not found\"\n\
+         >nope error=GeneNotFound message=\"gene not found\"\n\
+         >chr1:15-17 strand=+ upstream=0 downstream=0 mask=hard\nNNN\n"
+    );
+
+    let (status, _, raw) = send_raw_with_method(
+        addr,
+        "POST",
+        "/v1/sequence/batch",
+        &[("x-api-key", "k1")],
+        Some(&body("jsonl", items)),
+    )
+    .await;
+    assert_eq!(status, 200);
+    let lines: Vec<Value> = decode_chunked(&raw)
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("jsonl line"))
+        .collect();
+    assert_eq!(lines.len(), 5);
+    assert_eq!(lines[0]["sequence"], "CGT");
+    assert_eq!(lines[1]["gene_id"], "g1");
+    assert_eq!(lines[1]["region"]["start"], 1);
+    assert_eq!(lines[2]["error"]["code"], "InvalidQueryParameter");
+    assert_eq!(lines[3]["index"], 3);
+    assert_eq!(lines[3]["error"]["code"], "GeneNotFound");
+    assert_eq!(lines[4]["sequence"], "NNN");
+
+    // 20 requested bases meet the api-key threshold.
+    let (status, _, _) = send_raw_with_method(
+        addr,
+        "POST",
+        "/v1/sequence/batch",
+        &[],
+        Some(&body("fasta", items)),
+    )
+    .await;
+    assert_eq!(status, 401);
+
+    let (status, _, raw) = send_raw_with_method(
+        addr,
+        "POST",
+        "/v1/sequence/batch",
+        &[("x-api-key", "k1")],
+        Some(&body(
+            "fasta",
+            r#"[{"region":"chr1:1-12"},{"region":"chr1:1-12"}]"#,
+        )),
+    )
+    .await;
+    assert_eq!(status, 422);
+    assert!(raw.contains("batch exceeds total bases budget"));
+
+    let (status, _, _) = send_raw_with_method(
+        addr,
+        "POST",
+        "/v1/sequence/batch",
+        &[],
+        Some(&body("fasta", "[]")),
+    )
+    .await;
+    assert_eq!(status, 422);
+}
+
+fn transcript_sequence_sqlite() -> Vec<u8> {
+    let dir = tempdir().expect("tempdir");
+    let db = dir.path().join("tx.sqlite");
+    let conn = rusqlite::Connection::open(&db).expect("open sqlite");
+    conn.execute_batch(
+        "CREATE TABLE gene_summary(id INTEGER PRIMARY KEY, gene_id TEXT, name TEXT, seqid TEXT, start INT, end INT, strand TEXT);
+         CREATE TABLE transcript_summary(id INTEGER PRIMARY KEY, transcript_id TEXT, parent_gene_id TEXT, seqid TEXT, start INT, end INT);
+         CREATE TABLE exons(id INTEGER PRIMARY KEY, exon_id TEXT, transcript_id TEXT, seqid TEXT, start INT, end INT, exon_length INT);
+         CREATE TABLE cds_segments(id INTEGER PRIMARY KEY, cds_id TEXT, transcript_id TEXT, seqid TEXT, start INT, end INT, phase INT);
+         INSERT INTO gene_summary VALUES (1,'g1','G1','chr1',1,14,'+'), (2,'g2','G2','chr1',1,14,'-'), (3,'g3','G3','chr1',1,14,'+');
+         INSERT INTO transcript_summary VALUES (1,'tx1','g1','chr1',1,14), (2,'tx2','g2','chr1',1,14), (3,'tx3','g3','chr1',1,14);
+         INSERT INTO exons VALUES (1,'e1','tx1','chr1',9,14,6), (2,'e2','tx1','chr1',1,4,4),
+                                  (3,'e3','tx2','chr1',1,4,4), (4,'e4','tx2','chr1',9,14,6),
+                                  (5,'e5','tx3','chr1',1,14,14);
+         INSERT INTO cds_segments VALUES (1,'c1','tx1','chr1',2,4,0), (2,'c2','tx1','chr1',9,14,2),
+                                         (3,'c3','tx2','chr1',1,3,2), (4,'c4','tx2','chr1',9,14,1);",
+    )
+    .expect("seed sqlite");
+    std::fs::read(db).expect("read sqlite bytes")
+}
+
+#[tokio::test]
+async fn transcript_sequence_endpoint_splices_strands_and_translates() {
+    let ds = DatasetId::new("110", "homo_sapiens", "GRCh38").expect("dataset id");
+    let sqlite = transcript_sequence_sqlite();
+    let (fasta, fai) = fixture_fasta_and_fai();
+    let manifest = ArtifactManifest::new(
+        "1".to_string(),
+        "1".to_string(),
+        ds.clone(),
+        ArtifactChecksums::new(
+            "a".repeat(64),
+            sha256_hex(&fasta),
+            sha256_hex(&fai),
+            sha256_hex(&sqlite),
+        ),
+        ManifestStats::new(3, 3, 3),
+    );
+    let store = Arc::new(FakeStore::default());
+    store.manifest.lock().await.insert(ds.clone(), manifest);
+    store.sqlite.lock().await.insert(ds.clone(), sqlite);
+    store.fasta.lock().await.insert(ds.clone(), fasta);
+    store.fai.lock().await.insert(ds.clone(), fai);
+    let tmp = tempdir().expect("tempdir");
+    let cfg = DatasetCacheConfig {
+        disk_root: tmp.path().to_path_buf(),
+        ..Default::default()
+    };
+    let api = ApiConfig {
+        sequence_api_key_required_bases: usize::MAX,
+        ..ApiConfig::default()
+    };
+    let app = build_router(AppState::with_config(
+        DatasetCacheManager::new(cfg, store),
+        api,
+        Default::default(),
+    ));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind listener");
+    let addr = listener.local_addr().expect("local addr");
+    tokio::spawn(async move { axum::serve(listener, app).await.expect("serve app") });
+
+    // chr1 = ACGTACGTAC GGGGnnnnTT
+    let fetch = |tx: &'static str, query: &'static str| async move {
+        let (status, _, body) = send_raw(
+            addr,
+            &format!(
+                "/v1/transcripts/{tx}/sequence?release=110&species=homo_sapiens&assembly=GRCh38{query}"
+            ),
+            &[],
+        )
+        .await;
+        (
+            status,
+            serde_json::from_str::<Value>(&body).expect("json body"),
+        )
+    };
+    let sequence = |json: &Value| json["data"]["sequence"].as_str().map(ToString::to_string);
+
+    let (status, json) = fetch("tx1", "").await;
+    assert_eq!(status, 200);
+    assert_eq!(sequence(&json).as_deref(), Some("ACGTACGGGG"));
+    assert_eq!(json["data"]["transcript"]["kind"], "cdna");
+    assert_eq!(json["data"]["transcript"]["strand"], "+");
+
+    let (status, json) = fetch("tx1", "&kind=cds").await;
+    assert_eq!(status, 200);
+    assert_eq!(sequence(&json).as_deref(), Some("CGTACGGGG"));
+
+    let (status, json) = fetch("tx1", "&kind=protein&genetic_code=2").await;
+    assert_eq!(status, 200);
+    assert_eq!(sequence(&json).as_deref(), Some("RTG"));
+    assert_eq!(json["data"]["transcript"]["genetic_code"], 2);
+
+    let (status, json) = fetch("tx2", "").await;
+    assert_eq!(status, 200);
+    assert_eq!(sequence(&json).as_deref(), Some("CCCCGTACGT"));
+    assert_eq!(json["data"]["transcript"]["strand"], "-");
+
+    // Minus strand: the 5'-most CDS is 9..14 with phase 1.
+    let (status, json) = fetch("tx2", "&kind=cds").await;
+    assert_eq!(status, 200);
+    assert_eq!(sequence(&json).as_deref(), Some("CCCGTCGT"));
+    let (status, json) = fetch("tx2", "&kind=protein").await;
+    assert_eq!(status, 200);
+    assert_eq!(sequence(&json).as_deref(), Some("PV"));
+
+    let (status, json) = fetch("tx3", "&kind=protein").await;
+    assert_eq!(status, 422);
+    assert_eq!(json["error"]["message"], "transcript has no CDS segments");
+    let (status, _) = fetch("tx1", "&kind=protein&genetic_code=7").await;
+    assert_eq!(status, 400);
+    let (status, _) = fetch("tx1", "&kind=rna").await;
+    assert_eq!(status, 400);
+    let (status, json) = fetch("missing", "").await;
+    assert_eq!(status, 404);
+    assert_eq!(json["error"]["code"], "GeneNotFound");
+}
+
 #[path = "api-contracts/advanced_contracts.rs"]
 mod advanced_contracts;
 #[path = "api-contracts/sqlite_query_guarantees.rs"]
 mod sqlite_query_guarantees;
+
+#[tokio::test]
+async fn transcript_feature_endpoints_list_exons_introns_utrs_and_region_exons() {
+    let ds = DatasetId::new("110", "homo_sapiens", "GRCh38").expect("dataset id");
+    // No introns, utrs or exon_rtree tables: served through query-time derivation.
+    let sqlite = transcript_sequence_sqlite();
+    let manifest = ArtifactManifest::new(
+        "1".to_string(),
+        "1".to_string(),
+        ds.clone(),
+        ArtifactChecksums::new(
+            "a".repeat(64),
+            "b".repeat(64),
+            "c".repeat(64),
+            sha256_hex(&sqlite),
+        ),
+        ManifestStats::new(3, 3, 3),
+    );
+    let store = Arc::new(FakeStore::default());
+    store.manifest.lock().await.insert(ds.clone(), manifest);
+    store.sqlite.lock().await.insert(ds.clone(), sqlite);
+    let tmp = tempdir().expect("tempdir");
+    let cfg = DatasetCacheConfig {
+        disk_root: tmp.path().to_path_buf(),
+        ..Default::default()
+    };
+    let app = build_router(AppState::new(DatasetCacheManager::new(cfg, store)));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind listener");
+    let addr = listener.local_addr().expect("local addr");
+    tokio::spawn(async move { axum::serve(listener, app).await.expect("serve app") });
+
+    let fetch = |path: String| async move {
+        let (status, _, body) = send_raw(
+            addr,
+            &format!("{path}release=110&species=homo_sapiens&assembly=GRCh38"),
+            &[],
+        )
+        .await;
+        (
+            status,
+            serde_json::from_str::<Value>(&body).expect("json body"),
+        )
+    };
+    let spans = |rows: &Value| {
+        rows.as_array()
+            .expect("rows")
+            .iter()
+            .map(|r| {
+                (
+                    r["start"].as_u64().unwrap_or(0),
+                    r["end"].as_u64().unwrap_or(0),
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // tx2 is on the minus strand, so its 3'-most genomic exon is ranked first.
+    let (status, json) = fetch("/v1/transcripts/tx2/exons?".to_string()).await;
+    assert_eq!(status, 200);
+    assert_eq!(json["data"]["transcript_id"], "tx2");
+    assert_eq!(json["data"]["exons"][0]["exon_id"], "e4");
+    assert_eq!(json["data"]["exons"][0]["rank"], 1);
+    assert_eq!(spans(&json["data"]["exons"]), vec![(9, 14), (1, 4)]);
+
+    let (status, json) = fetch("/v1/transcripts/tx1/introns?".to_string()).await;
+    assert_eq!(status, 200);
+    assert_eq!(spans(&json["data"]["introns"]), vec![(5, 8)]);
+    assert_eq!(json["data"]["introns"][0]["length"], 4);
+
+    let (status, json) = fetch("/v1/transcripts/tx1/utrs?".to_string()).await;
+    assert_eq!(status, 200);
+    assert_eq!(json["data"]["utrs"][0]["kind"], "five_prime");
+    assert_eq!(spans(&json["data"]["utrs"]), vec![(1, 1)]);
+    let (_, json) = fetch("/v1/transcripts/tx3/utrs?".to_string()).await;
+    assert_eq!(json["data"]["utrs"], serde_json::json!([]));
+
+    let (status, json) = fetch("/v1/transcripts/nope/introns?".to_string()).await;
+    assert_eq!(status, 404);
+    assert_eq!(json["error"]["code"], "InvalidQueryParameter");
+
+    let (status, json) = fetch("/v1/exons?region=chr1:3-9&limit=3&".to_string()).await;
+    assert_eq!(status, 200);
+    let ids: Vec<&str> = json["data"]["exons"]
+        .as_array()
+        .expect("exons")
+        .iter()
+        .filter_map(|e| e["exon_id"].as_str())
+        .collect();
+    assert_eq!(ids, vec!["e2", "e3", "e5"]);
+    assert_eq!(json["data"]["truncated"], true);
+
+    let (status, _) = fetch("/v1/exons?region=chr1:9-3&".to_string()).await;
+    assert_eq!(status, 400);
+}
@@ -1,7 +1,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::*;
-use bijux_atlas::domain::dataset::{Catalog, CatalogEntry};
+use bijux_atlas::domain::dataset::{Assembly, Catalog, CatalogEntry};
+use bijux_atlas::domain::query::{
+    parse_chain_file, GeneId, LiftoverChainSet, ReleaseGeneIndex, ReleaseGeneIndexEntry, SeqId,
+};
 use hmac::KeyInit;
 
 #[tokio::test]
@@ -116,6 +119,247 @@ async fn diff_endpoints_return_added_removed_changed_and_support_latest_alias()
     );
 }
 
+fn attributed_release_index(
+    dataset: &DatasetId,
+    seqid: &str,
+    rows: Vec<(&str, u64, u64, &str, &str)>,
+) -> Vec<u8> {
+    let mut entries: Vec<ReleaseGeneIndexEntry> = rows
+        .into_iter()
+        .map(|(gene_id, start, end, sig, attrs)| {
+            ReleaseGeneIndexEntry::new(
+                GeneId::parse(gene_id).expect("gene id"),
+                SeqId::parse(seqid).expect("seqid"),
+                start,
+                end,
+                sig.to_string(),
+            )
+            .with_attributes_sha256(attrs.to_string())
+        })
+        .collect();
+    entries.sort();
+    serde_json::to_vec(&ReleaseGeneIndex::new(
+        "1".to_string(),
+        dataset.clone(),
+        entries,
+    ))
+    .expect("index json")
+}
+
+/// Serves GRCh37 release 75 with UCSC-named chains onto GRCh38 release 110,
+/// whose contig 1 is stored as `target_seqid`.
+async fn spawn_liftover_server(target_seqid: &str) -> std::net::SocketAddr {
+    let (_, _, sqlite_37) = mk_dataset();
+    // Distinct artifact bytes keep the two datasets in separate cache directories.
+    let sqlite_38 = {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("grch38.sqlite");
+        std::fs::write(&path, &sqlite_37).expect("write sqlite");
+        rusqlite::Connection::open(&path)
+            .expect("open sqlite")
+            .execute_batch(&format!(
+                "INSERT INTO dataset_stats(dimension,value,gene_count) VALUES ('seqid','chr2',0);
+                 CREATE TABLE contig_aliases (alias TEXT PRIMARY KEY, contig TEXT NOT NULL, source TEXT NOT NULL);
+                 INSERT INTO contig_aliases VALUES ('{alias}','{target_seqid}','chr_prefix');",
+                alias = if target_seqid == "chr1" { "1" } else { "chr1" },
+            ))
+            .expect("seed sqlite");
+        std::fs::read(&path).expect("read sqlite")
+    };
+    let (fasta, fai) = fixture_fasta_and_fai();
+    let ds_37 = DatasetId::new("75", "homo_sapiens", "GRCh37").expect("dataset id");
+    let ds_38 = DatasetId::new("110", "homo_sapiens", "GRCh38").expect("dataset id");
+    let store = Arc::new(FakeStore::default());
+    for (ds, sqlite) in [(&ds_37, &sqlite_37), (&ds_38, &sqlite_38)] {
+        let manifest = ArtifactManifest::new(
+            "1".to_string(),
+            "1".to_string(),
+            ds.clone(),
+            ArtifactChecksums::new(
+                "a".repeat(64),
+                sha256_hex(&fasta),
+                sha256_hex(&fai),
+                sha256_hex(sqlite),
+            ),
+            ManifestStats::new(1, 1, 1),
+        );
+        store.manifest.lock().await.insert(ds.clone(), manifest);
+        store.sqlite.lock().await.insert(ds.clone(), sqlite.clone());
+    }
+    // chr1:101-150 lifts to 1101-1150 and chr1:161-200 to 1171-1210, with an unaligned gap between.
+    let chains = parse_chain_file(
+        "chain 1000 chr1 1000 + 100 200 chr1 2000 + 1100 1210 1\n50 10 20\n40\n",
+        str::to_string,
+    )
+    .expect("chain");
+    let chain_set = LiftoverChainSet::new(
+        ds_37.clone(),
+        Assembly::parse("GRCh38").expect("assembly"),
+        chains,
+    );
+    store.liftover_chains.lock().await.insert(
+        ds_37.clone(),
+        serde_json::to_vec(&chain_set).expect("chain json"),
+    );
+    store.release_gene_index.lock().await.insert(
+        ds_37.clone(),
+        attributed_release_index(
+            &ds_37,
+            "chr1",
+            vec![
+                ("gA", 101, 150, "sig-a37", "attr-a"),
+                ("gB", 161, 190, "sig-b37", "attr-b1"),
+                ("gR", 105, 120, "sig-r37", "attr-r"),
+            ],
+        ),
+    );
+    store.release_gene_index.lock().await.insert(
+        ds_38.clone(),
+        attributed_release_index(
+            &ds_38,
+            target_seqid,
+            vec![
+                ("gA", 1101, 1150, "sig-a38", "attr-a"),
+                ("gB", 1171, 1200, "sig-b38", "attr-b2"),
+                ("gN", 1130, 1140, "sig-n38", "attr-n"),
+            ],
+        ),
+    );
+    *store.catalog.lock().await = Catalog::new(vec![
+        CatalogEntry::new(ds_37.clone(), "m1".to_string(), "s1".to_string()),
+        CatalogEntry::new(ds_38.clone(), "m2".to_string(), "s2".to_string()),
+    ]);
+    *store.etag.lock().await = "catalog-liftover".to_string();
+
+    let tmp = tempdir().expect("tempdir");
+    let cfg = DatasetCacheConfig {
+        disk_root: tmp.path().to_path_buf(),
+        ..Default::default()
+    };
+    let app = build_router(AppState::with_config(
+        DatasetCacheManager::new(cfg, store),
+        ApiConfig::default(),
+        Default::default(),
+    ));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind listener");
+    let addr = listener.local_addr().expect("local addr");
+    tokio::spawn(async move { axum::serve(listener, app).await.expect("serve app") });
+    addr
+}
+
+async fn fetch_json(addr: std::net::SocketAddr, path: String) -> (u16, Value) {
+    let (status, _, body) = send_raw(addr, &path, &[]).await;
+    let json: Value = serde_json::from_str(&body).unwrap_or(Value::Null);
+    (status, json)
+}
+
+fn diff_rows(json: &Value) -> Vec<(String, String)> {
+    json["data"]["diff"]
+        .as_array()
+        .expect("diff rows")
+        .iter()
+        .map(|r| {
+            (
+                r["gene_id"].as_str().unwrap_or_default().to_string(),
+                r["status"].as_str().unwrap_or_default().to_string(),
+            )
+        })
+        .collect()
+}
+
+const LIFTOVER_DIMS: &str = "release=75&species=homo_sapiens&assembly=GRCh37";
+const CROSS_ASSEMBLY_DIFF: &str =
+    "from_release=75&to_release=110&species=homo_sapiens&assembly=GRCh37&to_assembly=GRCh38";
+
+#[tokio::test]
+async fn liftover_endpoint_and_region_diff_across_assemblies() {
+    let addr = spawn_liftover_server("chr1").await;
+    let fetch = |path: String| fetch_json(addr, path);
+    let dims = LIFTOVER_DIMS;
+
+    let (status, json) = fetch(format!("/v1/liftover?{dims}&region=chr1:101-150")).await;
+    assert_eq!(status, 200, "{json}");
+    let lifted = &json["data"]["liftover"];
+    assert_eq!(lifted["target_assembly"], "GRCh38");
+    assert_eq!(lifted["target"]["seqid"], "chr1");
+    assert_eq!(lifted["target"]["start"], 1101);
+    assert_eq!(lifted["target"]["end"], 1150);
+
+    let (status, json) = fetch(format!("/v1/liftover?{dims}&region=chr1:141-170")).await;
+    assert_eq!(status, 200);
+    assert_eq!(json["data"]["liftover"]["mapped"], false);
+    let (_, json) = fetch(format!(
+        "/v1/liftover?{dims}&region=chr1:141-170&min_match=0.5"
+    ))
+    .await;
+    assert_eq!(json["data"]["liftover"]["target"]["end"], 1180);
+
+    let (status, _) = fetch(format!(
+        "/v1/liftover?{dims}&region=chr1:1-10&to_assembly=T2T"
+    ))
+    .await;
+    assert_eq!(status, 404);
+    let (status, _) = fetch(format!("/v1/liftover?{dims}&region=chr1:1-10&min_match=2")).await;
+    assert_eq!(status, 400);
+
+    let diff = CROSS_ASSEMBLY_DIFF;
+    let (status, json) = fetch(format!("/v1/diff/region?{diff}&region=chr1:1100-1210")).await;
+    assert_eq!(status, 200, "{json}");
+    assert_eq!(
+        diff_rows(&json),
+        vec![
+            ("gB".to_string(), "changed".to_string()),
+            ("gN".to_string(), "added".to_string()),
+            ("gR".to_string(), "removed".to_string()),
+        ]
+    );
+    assert_eq!(json["data"]["qc"]["liftover"]["unmapped_genes"], 0);
+
+    let (status, _) = fetch(format!("/v1/diff/genes?{diff}")).await;
+    assert_eq!(status, 400);
+    let (status, _) = fetch(
+        "/v1/diff/region?from_release=110&to_release=75&species=homo_sapiens&assembly=GRCh38&to_assembly=GRCh37&region=chr1:1-200"
+            .to_string(),
+    )
+    .await;
+    assert_eq!(status, 404);
+}
+
+#[tokio::test]
+async fn lifted_seqids_take_the_target_dataset_contig_names() {
+    // The chains say chr1 on both sides; GRCh38 here stores the Ensembl name 1.
+    let addr = spawn_liftover_server("1").await;
+
+    let (status, json) = fetch_json(
+        addr,
+        format!("/v1/liftover?{LIFTOVER_DIMS}&region=chr1:101-150"),
+    )
+    .await;
+    assert_eq!(status, 200, "{json}");
+    assert_eq!(json["data"]["liftover"]["target"]["seqid"], "1");
+    assert_eq!(json["data"]["liftover"]["target"]["start"], 1101);
+
+    for region in ["chr1:1100-1210", "1:1100-1210"] {
+        let (status, json) = fetch_json(
+            addr,
+            format!("/v1/diff/region?{CROSS_ASSEMBLY_DIFF}&region={region}"),
+        )
+        .await;
+        assert_eq!(status, 200, "{json}");
+        assert_eq!(
+            diff_rows(&json),
+            vec![
+                ("gB".to_string(), "changed".to_string()),
+                ("gN".to_string(), "added".to_string()),
+                ("gR".to_string(), "removed".to_string()),
+            ],
+            "{region}"
+        );
+    }
+}
+
 fn sign_hmac(secret: &str, method: &str, uri: &str, ts: &str) -> String {
     let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("hmac init");
     let payload = format!("{method}\n{uri}\n{ts}\n");
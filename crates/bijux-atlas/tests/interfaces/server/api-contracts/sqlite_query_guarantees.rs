@@ -132,3 +132,64 @@ async fn sequence_region_is_stable_under_short_burst() {
         assert!(body.contains("\"sequence\""));
     }
 }
+
+#[tokio::test]
+async fn region_parsers_resolve_contig_aliases_and_report_the_stored_name() {
+    let (ds, mut manifest, sqlite) = mk_dataset();
+    let dir = tempdir().expect("tempdir");
+    let db = dir.path().join("aliased.sqlite");
+    std::fs::write(&db, &sqlite).expect("write sqlite");
+    let conn = rusqlite::Connection::open(&db).expect("open sqlite");
+    conn.execute_batch(
+        "CREATE TABLE contig_aliases(alias TEXT PRIMARY KEY, contig TEXT NOT NULL, source TEXT NOT NULL) WITHOUT ROWID;
+         INSERT INTO contig_aliases VALUES ('1','chr1','chr_prefix');
+         INSERT INTO contig_aliases VALUES ('NC_000001.11','chr1','refseq');
+         CREATE VIRTUAL TABLE gene_summary_rtree USING rtree(gene_rowid, start, end);
+         INSERT INTO gene_summary_rtree VALUES (1,1,10);",
+    )
+    .expect("seed aliases");
+    drop(conn);
+    let sqlite = std::fs::read(&db).expect("read sqlite");
+    manifest.checksums.sqlite_sha256 = sha256_hex(&sqlite);
+    let (fasta, fai) = fixture_fasta_and_fai();
+    let store = Arc::new(FakeStore::default());
+    store.manifest.lock().await.insert(ds.clone(), manifest);
+    store.sqlite.lock().await.insert(ds.clone(), sqlite);
+    store.fasta.lock().await.insert(ds.clone(), fasta);
+    store.fai.lock().await.insert(ds, fai);
+    let (addr, _tmp) = spawn_with_store(store, ApiConfig::default()).await;
+
+    let (status, _, body) = send_raw(
+        addr,
+        "/v1/genes?release=110&species=homo_sapiens&assembly=GRCh38&range=NC_000001.11:1-20",
+        &[],
+    )
+    .await;
+    assert_eq!(status, 200, "body={body}");
+    let json = serde_json::from_str::<Value>(&body).expect("genes json");
+    assert_eq!(json["data"]["rows"][0]["gene_id"], "g1");
+    assert_eq!(
+        json["data"]["contigs"],
+        serde_json::json!([{"requested": "NC_000001.11", "seqid": "chr1", "alias_applied": true}])
+    );
+
+    let (status, _, body) = send_raw(
+        addr,
+        "/v1/sequence/region?release=110&species=homo_sapiens&assembly=GRCh38&region=1:1-8",
+        &[],
+    )
+    .await;
+    assert_eq!(status, 200, "body={body}");
+    let json = serde_json::from_str::<Value>(&body).expect("sequence json");
+    assert_eq!(json["data"]["sequence"], "ACGTACGT");
+    assert_eq!(json["data"]["contig"]["seqid"], "chr1");
+    assert_eq!(json["data"]["contig"]["requested"], "1");
+
+    let (status, _, body) = send_raw(
+        addr,
+        "/v1/sequence/region?release=110&species=homo_sapiens&assembly=GRCh38&region=chr9:1-8",
+        &[],
+    )
+    .await;
+    assert_eq!(status, 422, "unknown names still fail: {body}");
+}
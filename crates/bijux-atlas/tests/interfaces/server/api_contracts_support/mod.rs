@@ -1,9 +1,16 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use std::sync::Arc;
+
+use bijux_atlas::adapters::inbound::http::router::build_router;
+use bijux_atlas::adapters::outbound::store::testing::FakeStore;
+use bijux_atlas::app::server::{AppState, DatasetCacheConfig, DatasetCacheManager};
 use bijux_atlas::domain::dataset::{ArtifactChecksums, ArtifactManifest, DatasetId, ManifestStats};
 use bijux_atlas::domain::query::{GeneId, ReleaseGeneIndex, ReleaseGeneIndexEntry, SeqId};
 use bijux_atlas::domain::sha256_hex;
+use bijux_atlas::runtime::config::ApiConfig;
 use rusqlite::Connection;
+use tempfile::TempDir;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
 pub fn fixture_sqlite() -> Vec<u8> {
@@ -80,6 +87,31 @@ pub fn fixture_release_index(
     .expect("index json")
 }
 
+/// Serves the [`mk_dataset`] fixture from a fake store under `api`; keep the
+/// returned cache dir alive for as long as the server runs.
+pub async fn spawn_fixture_server(api: ApiConfig) -> (std::net::SocketAddr, AppState, TempDir) {
+    let (ds, manifest, sqlite) = mk_dataset();
+    let store = Arc::new(FakeStore::default());
+    store.manifest.lock().await.insert(ds.clone(), manifest);
+    store.sqlite.lock().await.insert(ds, sqlite);
+    let cache_dir = tempfile::tempdir().expect("cache dir");
+    let cache = DatasetCacheManager::new(
+        DatasetCacheConfig {
+            disk_root: cache_dir.path().to_path_buf(),
+            ..DatasetCacheConfig::default()
+        },
+        store,
+    );
+    let state = AppState::with_config(cache, api, Default::default());
+    let app = build_router(state.clone());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind listener");
+    let addr = listener.local_addr().expect("local addr");
+    tokio::spawn(async move { axum::serve(listener, app).await.expect("serve app") });
+    (addr, state, cache_dir)
+}
+
 pub async fn send_raw(
     addr: std::net::SocketAddr,
     path: &str,
@@ -130,3 +162,55 @@ pub async fn send_raw_with_method(
         .expect("http status");
     (status, head.to_string(), body.to_string())
 }
+
+/// GET variant of [`send_raw`] for binary bodies such as Arrow IPC streams.
+pub async fn send_raw_bytes(
+    addr: std::net::SocketAddr,
+    path: &str,
+    headers: &[(&str, &str)],
+) -> (u16, String, Vec<u8>) {
+    let mut stream = tokio::net::TcpStream::connect(addr)
+        .await
+        .expect("connect server");
+    let mut req = format!("GET {path} HTTP/1.1\r\nHost: {addr}\r\nConnection: close\r\n");
+    for (k, v) in headers {
+        req.push_str(&format!("{k}: {v}\r\n"));
+    }
+    req.push_str("\r\n");
+    stream
+        .write_all(req.as_bytes())
+        .await
+        .expect("write request");
+    let mut response = Vec::new();
+    stream
+        .read_to_end(&mut response)
+        .await
+        .expect("read response");
+    let split = response
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .expect("http response must have separator");
+    let head = String::from_utf8_lossy(&response[..split]).to_string();
+    let status = head
+        .lines()
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|s| s.parse::<u16>().ok())
+        .expect("http status");
+    (status, head, response[split + 4..].to_vec())
+}
+
+/// Joins the chunks of a `Transfer-Encoding: chunked` body read off the wire.
+pub fn decode_chunked(raw: &str) -> String {
+    let mut out = String::new();
+    let mut rest = raw;
+    while let Some((size_line, tail)) = rest.split_once("\r\n") {
+        let size = usize::from_str_radix(size_line.trim(), 16).expect("chunk size");
+        if size == 0 {
+            break;
+        }
+        out.push_str(&tail[..size]);
+        rest = &tail[size + 2..];
+    }
+    out
+}
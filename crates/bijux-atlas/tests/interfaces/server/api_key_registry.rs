@@ -0,0 +1,183 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use bijux_atlas::adapters::outbound::api_keys::{ApiKeyRegistry, NewApiKey, QuotaTier};
+use bijux_atlas::app::server::AppState;
+use bijux_atlas::runtime::config::{ApiConfig, AuthMode, RateLimitConfig};
+use tempfile::{tempdir, TempDir};
+
+use super::api_contracts_support::{send_raw, send_raw_with_method, spawn_fixture_server};
+
+const HUMAN_GENES: &str =
+    "/v1/genes?release=110&species=homo_sapiens&assembly=GRCh38&gene_id=g1&limit=1";
+
+struct IssuedKeys {
+    human: String,
+    mouse: String,
+    catalog_only: String,
+    trickle: String,
+}
+
+fn issue_keys(registry: &mut ApiKeyRegistry) -> IssuedKeys {
+    registry.tiers.insert(
+        "trickle".to_string(),
+        QuotaTier::from(RateLimitConfig {
+            capacity: 1.0,
+            refill_per_sec: 0.001,
+        }),
+    );
+    let mut issue = |key_id: &str, scopes: &[&str], datasets: &[&str], tier: Option<&str>| {
+        registry
+            .create(
+                NewApiKey {
+                    key_id: key_id.to_string(),
+                    owner: format!("{key_id}-team"),
+                    scopes: scopes.iter().map(ToString::to_string).collect(),
+                    datasets: datasets.iter().map(ToString::to_string).collect(),
+                    rate_limit_tier: tier.map(ToString::to_string),
+                    expires_at_unix_s: None,
+                },
+                1,
+            )
+            .expect("create key")
+    };
+    IssuedKeys {
+        human: issue("human", &[], &["110/homo_sapiens/*"], None),
+        mouse: issue("mouse", &[], &["*/mus_musculus/*"], None),
+        catalog_only: issue("catalog", &["catalog.read"], &[], None),
+        trickle: issue("trickle", &[], &[], Some("trickle")),
+    }
+}
+
+async fn spawn_server(registry_dir: &TempDir) -> (std::net::SocketAddr, AppState, TempDir) {
+    spawn_fixture_server(ApiConfig {
+        auth_mode: AuthMode::ApiKey,
+        require_api_key: true,
+        enable_admin_endpoints: true,
+        enable_debug_datasets: true,
+        api_key_registry_path: Some(
+            registry_dir
+                .path()
+                .join("api-keys.json")
+                .display()
+                .to_string(),
+        ),
+        ..ApiConfig::default()
+    })
+    .await
+}
+
+#[tokio::test]
+async fn registry_keys_enforce_restrictions_tiers_and_live_revocation() {
+    let registry_dir = tempdir().expect("registry dir");
+    let registry_path = registry_dir.path().join("api-keys.json");
+    let mut registry = ApiKeyRegistry::default();
+    let keys = issue_keys(&mut registry);
+    registry.write(&registry_path).expect("write registry");
+    let (addr, state, _cache_dir) = spawn_server(&registry_dir).await;
+
+    let (status, _, body) = send_raw(addr, HUMAN_GENES, &[("x-api-key", &keys.human)]).await;
+    assert_eq!(status, 200, "{body}");
+    let (status, _, body) = send_raw(addr, HUMAN_GENES, &[("x-api-key", &keys.mouse)]).await;
+    assert_eq!(status, 403, "{body}");
+    assert!(body.contains("api_key_dataset"), "{body}");
+    // A dataset restriction holds however the request names the dataset.
+    let mouse = [("x-api-key", keys.mouse.as_str())];
+    for path in [
+        "/v1/genes?dataset=110/homo_sapiens/GRCh38&gene_id=g1&limit=1",
+        "/v1/genes?release=110&species=homo%5Fsapiens&assembly=GRCh38&gene_id=g1&limit=1",
+        "/v1/genes/search?release=110&species=homo_sapiens&assembly=GRCh38&q=g1",
+    ] {
+        let (status, _, body) = send_raw(addr, path, &mouse).await;
+        assert_eq!(status, 403, "{path}: {body}");
+        assert!(body.contains("api_key_dataset"), "{path}: {body}");
+    }
+    for (path, payload) in [
+        (
+            "/v1/genes/query",
+            r#"{"release":"110","species":"homo_sapiens","assembly":"GRCh38","filter":{"gene_id":"g1"}}"#,
+        ),
+        (
+            "/v1/sequence/batch",
+            r#"{"release":"110","species":"homo_sapiens","assembly":"GRCh38","items":[{"region":"chr1:1-10"}]}"#,
+        ),
+    ] {
+        let (status, _, body) =
+            send_raw_with_method(addr, "POST", path, &mouse, Some(payload)).await;
+        assert_eq!(status, 403, "{path}: {body}");
+        assert!(body.contains("api_key_dataset"), "{path}: {body}");
+    }
+    let (status, _, body) = send_raw(
+        addr,
+        "/v1/genes?gene_id=g1&limit=1",
+        &[("x-api-key", &keys.human)],
+    )
+    .await;
+    assert_eq!(status, 400, "{body}");
+    let (status, _, body) = send_raw(addr, HUMAN_GENES, &[("x-api-key", &keys.catalog_only)]).await;
+    assert_eq!(status, 403, "{body}");
+    assert!(body.contains("api_key_scope"), "{body}");
+    let (status, _, body) = send_raw(addr, HUMAN_GENES, &[("x-api-key", "atlas_unknown")]).await;
+    assert_eq!(status, 401, "{body}");
+
+    let (status, _, _) = send_raw(addr, HUMAN_GENES, &[("x-api-key", &keys.trickle)]).await;
+    assert_eq!(status, 200);
+    let (status, _, body) = send_raw(addr, HUMAN_GENES, &[("x-api-key", &keys.trickle)]).await;
+    assert_eq!(status, 429, "{body}");
+
+    // Revoking in the file takes effect without a restart.
+    state.api_keys.spawn_reload_task(Duration::from_millis(50));
+    registry.revoke("human", 2).expect("revoke");
+    registry.write(&registry_path).expect("rewrite registry");
+    let mut response = (0, String::new());
+    for _ in 0..100 {
+        let (status, _, body) = send_raw(addr, HUMAN_GENES, &[("x-api-key", &keys.human)]).await;
+        response = (status, body);
+        if status == 401 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    assert_eq!(response.0, 401);
+    assert!(response.1.contains("api_key_revoked"), "{}", response.1);
+
+    let (status, _, body) = send_raw(addr, "/debug/api-keys", &[("x-api-key", &keys.mouse)]).await;
+    assert_eq!(status, 200, "{body}");
+    assert!(!body.contains("key_hash"), "{body}");
+    let listed: serde_json::Value = serde_json::from_str(&body).expect("keys json");
+    let human = listed["keys"]
+        .as_array()
+        .expect("keys")
+        .iter()
+        .find(|key| key["key_id"] == "human")
+        .expect("human key");
+    assert_eq!(human["status"], "revoked");
+
+    let (status, _, body) = send_raw_with_method(
+        addr,
+        "POST",
+        "/debug/api-keys/reload",
+        &[("x-api-key", &keys.mouse)],
+        None,
+    )
+    .await;
+    assert_eq!(status, 200, "{body}");
+    assert!(body.contains("\"reloaded\":false"), "{body}");
+    // Explain runs the key checks the request path runs.
+    for (key_id, reason) in [("mouse", "api_key_dataset"), ("catalog", "api_key_scope")] {
+        let (status, _, body) = send_raw(
+            addr,
+            &format!(
+                "/debug/authz/explain?route=%2Fv1%2Fgenes%2Fquery&dataset=110%2Fhomo_sapiens%2FGRCh38\
+                 &principal=service-account&api_key_owner={key_id}-team&api_key_id={key_id}"
+            ),
+            &[("x-api-key", &keys.mouse)],
+        )
+        .await;
+        assert_eq!(status, 200, "{body}");
+        let explained: serde_json::Value = serde_json::from_str(&body).expect("explain json");
+        assert_eq!(explained["explanation"]["decision"], "deny", "{body}");
+        assert_eq!(explained["explanation"]["reason"], reason, "{body}");
+    }
+}
@@ -0,0 +1,122 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#![cfg(feature = "client-async")]
+
+use std::sync::Arc;
+
+use bijux_atlas::adapters::inbound::client::{
+    AsyncAtlasClient, ClientConfig, DatasetQuery, ErrorClass, InMemoryMetrics, SequenceQuery,
+};
+use bijux_atlas::adapters::inbound::http::router::build_router;
+use bijux_atlas::adapters::outbound::store::testing::FakeStore;
+use bijux_atlas::app::server::{AppState, DatasetCacheConfig, DatasetCacheManager};
+use bijux_atlas::contracts::api::DatasetKeyDto;
+use bijux_atlas::domain::dataset::{Catalog, CatalogEntry};
+use bijux_atlas::runtime::config::ApiConfig;
+use futures::TryStreamExt;
+use tempfile::tempdir;
+
+use super::api_contracts_support::{fixture_fasta_and_fai, mk_dataset};
+
+#[tokio::test]
+async fn async_client_decodes_live_router_payloads_into_contract_dtos() {
+    let (ds, manifest, sqlite) = mk_dataset();
+    let (fasta, fai) = fixture_fasta_and_fai();
+    let store = Arc::new(FakeStore::default());
+    *store.catalog.lock().await = Catalog::new(vec![CatalogEntry::new(
+        ds.clone(),
+        "manifest.json".to_string(),
+        "gene_summary.sqlite".to_string(),
+    )]);
+    store.manifest.lock().await.insert(ds.clone(), manifest);
+    store.sqlite.lock().await.insert(ds.clone(), sqlite);
+    store.fasta.lock().await.insert(ds.clone(), fasta);
+    store.fai.lock().await.insert(ds.clone(), fai);
+    let tmp = tempdir().expect("tempdir");
+    let cfg = DatasetCacheConfig {
+        disk_root: tmp.path().to_path_buf(),
+        ..Default::default()
+    };
+    let api = ApiConfig {
+        sequence_api_key_required_bases: usize::MAX,
+        ..ApiConfig::default()
+    };
+    let app = build_router(AppState::with_config(
+        DatasetCacheManager::new(cfg, store),
+        api,
+        Default::default(),
+    ));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind listener");
+    let addr = listener.local_addr().expect("local addr");
+    tokio::spawn(async move { axum::serve(listener, app).await.expect("serve app") });
+
+    let metrics = InMemoryMetrics::default();
+    let client = AsyncAtlasClient::new(ClientConfig {
+        base_url: format!("http://{addr}"),
+        retry_attempts: 1,
+        ..ClientConfig::default()
+    })
+    .expect("client")
+    .with_metrics(Arc::new(metrics.clone()));
+    let key = DatasetKeyDto::new(
+        "110".to_string(),
+        "homo_sapiens".to_string(),
+        "GRCh38".to_string(),
+    )
+    .expect("dataset key");
+
+    let version = client.version().await.expect("version");
+    assert_eq!(version.plugin.name, "bijux-atlas");
+    assert_eq!(version.api_version, "v1");
+
+    let query = DatasetQuery::new("110", "homo_sapiens", "GRCh38")
+        .with_limit(1)
+        .with_gene_id("g1")
+        .include_coords();
+    let pages = client
+        .gene_pages(&query)
+        .try_collect::<Vec<_>>()
+        .await
+        .expect("gene pages");
+    assert_eq!(pages.len(), 1);
+    assert_eq!(pages[0].items[0].gene_id, "g1");
+    assert_eq!(pages[0].items[0].seqid.as_deref(), Some("chr1"));
+
+    let transcripts = client
+        .gene_transcripts(&key, "g1", 10, None)
+        .await
+        .expect("gene transcripts");
+    assert_eq!(transcripts.items[0].transcript_id, "tx1");
+    let transcript = client.transcript(&key, "tx1").await.expect("transcript");
+    assert_eq!(transcript.data.transcript.parent_gene_id, "g1");
+    assert_eq!(transcript.dataset.as_ref(), Some(&key));
+
+    let sequence = client
+        .region_sequence(&key, "chr1:1-10", &SequenceQuery::default())
+        .await
+        .expect("region sequence");
+    assert_eq!(sequence.data.sequence, "ACGTACGTAC");
+    assert_eq!(sequence.data.length, 10);
+    assert_eq!(
+        sequence.data.region.as_ref().map(|r| r.seqid.as_str()),
+        Some("chr1")
+    );
+
+    let datasets = client.datasets(10, None).await.expect("datasets");
+    assert_eq!(datasets.items[0].dataset, key);
+    let detail = client.dataset(&key).await.expect("dataset detail");
+    assert_eq!(detail.data.item.dataset, key);
+
+    let missing = client
+        .transcript(&key, "nope")
+        .await
+        .expect_err("unknown transcript");
+    assert_eq!(missing.class, ErrorClass::Client);
+    assert!(missing.api_error.is_some());
+    assert!(metrics
+        .snapshot()
+        .iter()
+        .any(|(endpoint, _, ok)| endpoint == "/v1/transcripts/{tx_id}" && !ok));
+}
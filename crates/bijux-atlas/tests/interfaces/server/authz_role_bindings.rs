@@ -0,0 +1,166 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use bijux_atlas::app::server::AppState;
+use bijux_atlas::domain::sha256_hex;
+use bijux_atlas::runtime::config::{ApiConfig, AuthMode};
+use tempfile::{tempdir, TempDir};
+
+use super::api_contracts_support::{send_raw, send_raw_with_method, spawn_fixture_server};
+
+const LAB_KEY: &str = "atlas_lab_key";
+const CURATION_KEY: &str = "atlas_curation_key";
+const HUMAN_GENES: &str =
+    "/v1/genes?release=110&species=homo_sapiens&assembly=GRCh38&gene_id=g1&limit=1";
+
+const BINDINGS: &str = r#"schema_version: 1
+assignments:
+  - api_key_owner: genomics-lab
+    role_id: role.service.readonly
+  - api_key_owner: curation-team
+    role_id: role.automation.release
+dataset_scopes:
+  - id: SCOPE-HUMAN-110
+    species: [homo_sapiens]
+    releases: ["110"]
+    roles: [role.automation.release]
+"#;
+
+async fn spawn_server(bindings_dir: &TempDir) -> (std::net::SocketAddr, AppState, TempDir) {
+    let bindings_path = bindings_dir.path().join("role-bindings.yaml");
+    std::fs::write(&bindings_path, BINDINGS).expect("write bindings");
+    spawn_fixture_server(ApiConfig {
+        auth_mode: AuthMode::ApiKey,
+        require_api_key: true,
+        allowed_api_keys: vec![
+            format!("hash={}|owner=genomics-lab", sha256_hex(LAB_KEY.as_bytes())),
+            format!(
+                "hash={}|owner=curation-team",
+                sha256_hex(CURATION_KEY.as_bytes())
+            ),
+        ],
+        enable_admin_endpoints: true,
+        enable_debug_datasets: true,
+        authz_role_bindings_path: Some(bindings_path.display().to_string()),
+        ..ApiConfig::default()
+    })
+    .await
+}
+
+#[tokio::test]
+async fn api_key_owner_bindings_and_dataset_scopes_decide_access_and_reload_live() {
+    let bindings_dir = tempdir().expect("bindings dir");
+    let (addr, state, _cache_dir) = spawn_server(&bindings_dir).await;
+
+    let (status, _, _) = send_raw(addr, "/v1/datasets", &[("x-api-key", LAB_KEY)]).await;
+    assert_eq!(status, 200);
+    let (status, _, body) = send_raw(addr, HUMAN_GENES, &[("x-api-key", LAB_KEY)]).await;
+    assert_eq!(status, 403, "{body}");
+    let (status, _, body) = send_raw(addr, HUMAN_GENES, &[("x-api-key", CURATION_KEY)]).await;
+    assert_eq!(status, 200, "{body}");
+
+    let (status, _, body) = send_raw(
+        addr,
+        "/debug/authz/explain?route=%2Fv1%2Fgenes%3Frelease%3D110%26species%3Dhomo_sapiens%26assembly%3DGRCh38\
+         &principal=service-account&api_key_owner=genomics-lab",
+        &[("x-api-key", CURATION_KEY)],
+    )
+    .await;
+    assert_eq!(status, 200, "{body}");
+    let explained: serde_json::Value = serde_json::from_str(&body).expect("explain json");
+    let explanation = &explained["explanation"];
+    assert_eq!(explanation["decision"], "deny");
+    assert_eq!(explanation["reason"], "dataset_scope");
+    assert_eq!(explanation["policy_rule"], "AUTH-POLICY-READ");
+    assert_eq!(explanation["dataset_scopes"][0], "SCOPE-HUMAN-110");
+    assert_eq!(explanation["bindings"][0]["kind"], "api_key_owner");
+    assert_eq!(
+        explanation["bindings"][0]["role_id"],
+        "role.service.readonly"
+    );
+
+    // Widening the scope in the file takes effect without a restart.
+    state
+        .role_bindings
+        .spawn_reload_task(Duration::from_millis(50));
+    std::fs::write(
+        bindings_dir.path().join("role-bindings.yaml"),
+        BINDINGS.replace(
+            "roles: [role.automation.release]",
+            "roles: [role.automation.release, role.service.readonly]",
+        ),
+    )
+    .expect("rewrite bindings");
+    let mut status = 0;
+    for _ in 0..100 {
+        status = send_raw(addr, HUMAN_GENES, &[("x-api-key", LAB_KEY)])
+            .await
+            .0;
+        if status == 200 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    assert_eq!(status, 200);
+
+    let (status, _, _) = send_raw(
+        addr,
+        "/debug/authz/explain?route=v1/genes",
+        &[("x-api-key", CURATION_KEY)],
+    )
+    .await;
+    assert_eq!(status, 400);
+}
+
+#[tokio::test]
+async fn dataset_scopes_hold_for_every_way_a_request_can_name_the_dataset() {
+    let bindings_dir = tempdir().expect("bindings dir");
+    let (addr, _state, _cache_dir) = spawn_server(&bindings_dir).await;
+    let lab = [("x-api-key", LAB_KEY)];
+
+    for path in [
+        "/v1/genes?dataset=110/homo_sapiens/GRCh38&gene_id=g1&limit=1",
+        "/v1/genes?release=110&species=homo%5Fsapiens&assembly=GRCh38&gene_id=g1&limit=1",
+        "/v1/genes/count?release=110&species=homo_sapiens&assembly=GRCh38",
+        "/v1/genes/g1/transcripts?release=110&species=homo_sapiens&assembly=GRCh38",
+        "/v1/datasets/110/homo_sapiens/GRCh38",
+    ] {
+        let (status, _, body) = send_raw(addr, path, &lab).await;
+        assert_eq!(status, 403, "{path}: {body}");
+        assert!(body.contains("dataset_scope"), "{path}: {body}");
+    }
+
+    let gene_query = r#"{"release":"110","species":"homo_sapiens","assembly":"GRCh38","filter":{"gene_id":"g1"}}"#;
+    let sequence_batch = r#"{"release":"110","species":"homo_sapiens","assembly":"GRCh38","items":[{"region":"chr1:1-10"}]}"#;
+    for (path, body) in [
+        ("/v1/genes/query", gene_query),
+        ("/v1/sequence/batch", sequence_batch),
+    ] {
+        let (status, _, resp) = send_raw_with_method(addr, "POST", path, &lab, Some(body)).await;
+        assert_eq!(status, 403, "{path}: {resp}");
+        assert!(resp.contains("dataset_scope"), "{path}: {resp}");
+        let (status, _, resp) = send_raw_with_method(
+            addr,
+            "POST",
+            path,
+            &[("x-api-key", CURATION_KEY)],
+            Some(body),
+        )
+        .await;
+        assert_ne!(status, 403, "{path}: {resp}");
+    }
+
+    // Explain refuses to approve a dataset route whose dataset it cannot resolve.
+    let (status, _, body) = send_raw(
+        addr,
+        "/debug/authz/explain?route=%2Fv1%2Fgenes%3Frelease%3D110%26species%3Dhomo_sapiens\
+         &principal=service-account&api_key_owner=curation-team",
+        &[("x-api-key", CURATION_KEY)],
+    )
+    .await;
+    assert_eq!(status, 200, "{body}");
+    let explained: serde_json::Value = serde_json::from_str(&body).expect("explain json");
+    assert_eq!(explained["explanation"]["decision"], "deny");
+    assert_eq!(explained["explanation"]["reason"], "dataset_unresolved");
+}
@@ -220,6 +220,31 @@ fn startup_rejects_loopback_bind_addresses_when_atlas_env_is_prod() {
     );
 }
 
+#[test]
+fn startup_requires_trusted_signing_keys_when_atlas_env_is_prod() {
+    let store_root = tempdir().expect("store root tempdir");
+    let cache_root = tempdir().expect("cache root tempdir");
+    let output = sanitized_server_command()
+        .arg("--validate-config")
+        .env("ATLAS_ENV", "prod")
+        .env("ATLAS_BIND", "0.0.0.0:8080")
+        .env("ATLAS_REDIS_URL", "redis://cache.internal:6379")
+        .env("ATLAS_STORE_ROOT", store_root.path())
+        .env("ATLAS_CACHE_ROOT", cache_root.path())
+        .output()
+        .expect("run bijux-atlas-server");
+
+    assert!(
+        !output.status.success(),
+        "prod startup must reject a missing trusted key set"
+    );
+    let stderr = String::from_utf8(output.stderr).expect("stderr utf8");
+    assert!(
+        stderr.contains("ATLAS_ENV=prod requires ATLAS_TRUSTED_SIGNING_KEYS"),
+        "prod signing guard must fail explicitly:\n{stderr}"
+    );
+}
+
 #[test]
 fn startup_logs_the_redacted_effective_config_during_validation() {
     let store_root = tempdir().expect("store root tempdir");
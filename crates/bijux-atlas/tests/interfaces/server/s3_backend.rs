@@ -3,7 +3,9 @@
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
-use bijux_atlas::adapters::outbound::store::{LocalFsBackend, RetryPolicy, S3LikeBackend};
+use bijux_atlas::adapters::outbound::store::{
+    AddressingStyle, LocalFsBackend, RetryPolicy, S3LikeBackend, SigV4Credentials, SigV4Signer,
+};
 use bijux_atlas::app::ports::{CatalogFetch, DatasetStoreBackend};
 use bijux_atlas::domain::dataset::{
     ArtifactChecksums, ArtifactManifest, Catalog, CatalogEntry, DatasetId, ManifestStats,
@@ -225,6 +227,204 @@ async fn s3_like_backend_supports_retry_and_resume_download() {
         .await
         .expect("sqlite bytes via resume");
     assert_eq!(sqlite, sqlite_bytes);
+
+    // A partial file left by an interrupted download is resumed with a ranged GET.
+    let dir = tempfile::tempdir().expect("download dir");
+    let dest = dir.path().join("gene_summary.sqlite.partial");
+    std::fs::write(&dest, &sqlite_bytes[..8]).expect("seed partial download");
+    let written = backend
+        .fetch_sqlite_to_path(&m.dataset, &dest)
+        .await
+        .expect("sqlite streamed to path");
+    assert_eq!(written, sqlite_bytes.len() as u64);
+    assert_eq!(
+        std::fs::read(&dest).expect("downloaded sqlite"),
+        sqlite_bytes
+    );
+}
+
+fn sigv4_signer(secret: &str) -> SigV4Signer {
+    SigV4Signer::new(
+        SigV4Credentials {
+            access_key_id: "AKIDATLASTEST".to_string(),
+            secret_access_key: secret.to_string(),
+            session_token: Some("session-token".to_string()),
+        },
+        "eu-west-1",
+    )
+}
+
+/// Serves `catalog.json` under `/atlas/` for path-style requests and at the
+/// root for `atlas.localhost`, answering 403 unless the request carries a
+/// valid SigV4 signature. Returns the `(host, path)` of every accepted request.
+async fn spawn_signed_catalog_server(
+    catalog_json: Vec<u8>,
+) -> (
+    std::net::SocketAddr,
+    Arc<std::sync::Mutex<Vec<(String, String)>>>,
+) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind listener");
+    let addr = listener.local_addr().expect("addr");
+    let accepted = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let accepted_bg = Arc::clone(&accepted);
+    let verifier = sigv4_signer("server-secret");
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let mut req = vec![0u8; 8192];
+            let n = stream.read(&mut req).await.unwrap_or(0);
+            let text = String::from_utf8_lossy(&req[..n]).to_string();
+            let mut lines = text.split("\r\n");
+            let mut request_line = lines.next().unwrap_or_default().split(' ');
+            let method = request_line.next().unwrap_or_default().to_string();
+            let path = request_line.next().unwrap_or_default().to_string();
+            let headers: Vec<(String, String)> = lines
+                .take_while(|line| !line.is_empty())
+                .filter_map(|line| line.split_once(':'))
+                .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+                .collect();
+            let header = |name: &str| {
+                headers
+                    .iter()
+                    .find(|(key, _)| key == name)
+                    .map(|(_, value)| value.clone())
+                    .unwrap_or_default()
+            };
+            let host = header("host");
+            let authorization = header("authorization");
+            let signed_names = authorization
+                .split("SignedHeaders=")
+                .nth(1)
+                .and_then(|rest| rest.split(',').next())
+                .unwrap_or_default()
+                .to_string();
+            let covered: Vec<(&str, String)> = signed_names
+                .split(';')
+                .filter(|name| *name != "host" && !name.starts_with("x-amz-"))
+                .map(|name| (name, header(name)))
+                .collect();
+            let covered: Vec<(&str, &str)> = covered
+                .iter()
+                .map(|(name, value)| (*name, value.as_str()))
+                .collect();
+            let url = reqwest::Url::parse(&format!("http://{host}{path}")).expect("request url");
+            let expected = verifier
+                .sign(
+                    &method,
+                    &url,
+                    &covered,
+                    &header("x-amz-content-sha256"),
+                    &header("x-amz-date"),
+                )
+                .expect("sign");
+            let authorized = header("x-amz-security-token") == "session-token"
+                && expected
+                    .iter()
+                    .any(|(name, value)| name == "authorization" && *value == authorization);
+            if !authorized {
+                let _ = stream
+                    .write_all(b"HTTP/1.1 403 Forbidden\r\nContent-Length: 0\r\n\r\n")
+                    .await;
+                continue;
+            }
+            accepted_bg
+                .lock()
+                .expect("accepted")
+                .push((host.clone(), path.clone()));
+            let catalog_path = if host.starts_with("atlas.") {
+                "/catalog.json"
+            } else {
+                "/atlas/catalog.json"
+            };
+            if path == catalog_path {
+                let header = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nETag: \"c1\"\r\n\r\n",
+                    catalog_json.len()
+                );
+                let _ = stream.write_all(header.as_bytes()).await;
+                let _ = stream.write_all(&catalog_json).await;
+            } else {
+                let _ = stream
+                    .write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n")
+                    .await;
+            }
+        }
+    });
+    (addr, accepted)
+}
+
+#[tokio::test]
+async fn s3_like_backend_signs_reads_with_sigv4_in_both_addressing_styles() {
+    let ds = DatasetId::new("110", "homo_sapiens", "GRCh38").expect("dataset");
+    let catalog = Catalog::new(vec![CatalogEntry::new(
+        ds,
+        "x".to_string(),
+        "y".to_string(),
+    )]);
+    let catalog_json = serde_json::to_vec(&catalog).expect("catalog json");
+    let (addr, accepted) = spawn_signed_catalog_server(catalog_json).await;
+    let retry = RetryPolicy {
+        max_attempts: 1,
+        base_backoff_ms: 1,
+    };
+    let backend = |endpoint: String, secret: &str, addressing: AddressingStyle| {
+        S3LikeBackend::new(endpoint, None, None, retry.clone(), true)
+            .with_bucket("atlas", addressing)
+            .with_sigv4(Some(sigv4_signer(secret)))
+            .with_client(
+                reqwest::Client::builder()
+                    .resolve("atlas.localhost", addr)
+                    .build()
+                    .expect("client"),
+            )
+    };
+
+    let path_style = backend(
+        format!("http://127.0.0.1:{}", addr.port()),
+        "server-secret",
+        AddressingStyle::Path,
+    );
+    match path_style
+        .fetch_catalog(None)
+        .await
+        .expect("path-style catalog")
+    {
+        CatalogFetch::Updated { catalog, .. } => assert_eq!(catalog.datasets.len(), 1),
+        _ => panic!("expected updated catalog"),
+    }
+    let virtual_host = backend(
+        format!("http://localhost:{}", addr.port()),
+        "server-secret",
+        AddressingStyle::VirtualHost,
+    );
+    match virtual_host
+        .fetch_catalog(None)
+        .await
+        .expect("virtual-host catalog")
+    {
+        CatalogFetch::Updated { catalog, .. } => assert_eq!(catalog.datasets.len(), 1),
+        _ => panic!("expected updated catalog"),
+    }
+    let hosts: Vec<(String, String)> = accepted.lock().expect("accepted").clone();
+    assert!(hosts.contains(&(
+        format!("127.0.0.1:{}", addr.port()),
+        "/atlas/catalog.json".to_string()
+    )));
+    assert!(hosts.contains(&(
+        format!("atlas.localhost:{}", addr.port()),
+        "/catalog.json".to_string()
+    )));
+
+    let wrong_secret = backend(
+        format!("http://127.0.0.1:{}", addr.port()),
+        "wrong-secret",
+        AddressingStyle::Path,
+    );
+    let Err(err) = wrong_secret.fetch_catalog(None).await else {
+        panic!("bad signature must be rejected");
+    };
+    assert!(err.to_string().contains("403"), "{err}");
 }
 
 #[tokio::test]
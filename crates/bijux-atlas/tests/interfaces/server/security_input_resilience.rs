@@ -133,3 +133,81 @@ async fn fuzz_authorization_and_overload_paths_keep_policy_responses() {
         assert_ne!(s, 500);
     }
 }
+
+fn mint_rs256_token(claims: &serde_json::Value) -> String {
+    use base64::Engine as _;
+    use ring::signature::{RsaKeyPair, RSA_PKCS1_SHA256};
+    let b64 = |bytes: &[u8]| base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes);
+    let header = serde_json::json!({"alg": "RS256", "kid": "atlas-test-rs256", "typ": "JWT"});
+    let signed = format!(
+        "{}.{}",
+        b64(&serde_json::to_vec(&header).expect("header")),
+        b64(&serde_json::to_vec(claims).expect("claims"))
+    );
+    let key = RsaKeyPair::from_pkcs8(include_bytes!("../../fixtures/oidc/rs256-signing-key.pk8"))
+        .expect("rsa key");
+    let mut signature = vec![0; key.public().modulus_len()];
+    key.sign(
+        &RSA_PKCS1_SHA256,
+        &ring::rand::SystemRandom::new(),
+        signed.as_bytes(),
+        &mut signature,
+    )
+    .expect("sign");
+    format!("{signed}.{}", b64(&signature))
+}
+
+#[tokio::test]
+async fn oidc_mode_validates_bearer_jwts_against_local_jwks() {
+    let api = ApiConfig {
+        auth_mode: bijux_atlas::runtime::config::AuthMode::Oidc,
+        oidc_jwks_path: Some(
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/oidc/jwks.json").to_string(),
+        ),
+        token_required_issuer: Some("https://idp.example".to_string()),
+        token_required_audience: Some("atlas-api".to_string()),
+        ..ApiConfig::default()
+    };
+    let addr = spawn_server(api).await;
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("clock")
+        .as_secs();
+    let claims = |aud: &str, exp: u64| {
+        serde_json::json!({
+            "sub": "alice", "iss": "https://idp.example", "aud": aud,
+            "exp": exp, "scope": "dataset.read"
+        })
+    };
+
+    let valid = format!(
+        "Bearer {}",
+        mint_rs256_token(&claims("atlas-api", now + 300))
+    );
+    let (status, _, _) = send_raw(addr, "/v1/datasets", &[("authorization", &valid)]).await;
+    assert_eq!(status, 200);
+
+    // The proxy identity headers no longer stand in for a token.
+    let (status, _, body) =
+        send_raw(addr, "/v1/datasets", &[("x-atlas-oidc-subject", "alice")]).await;
+    assert_eq!(status, 401, "{body}");
+
+    for (token, reason) in [
+        (
+            mint_rs256_token(&claims("other-api", now + 300)),
+            "token_audience_invalid",
+        ),
+        (
+            mint_rs256_token(&claims("atlas-api", now - 3600)),
+            "token_expired",
+        ),
+    ] {
+        let header = format!("Bearer {token}");
+        let (status, _, body) = send_raw(addr, "/v1/datasets", &[("authorization", &header)]).await;
+        assert_eq!(status, 401, "{body}");
+        assert!(body.contains(reason), "{body}");
+    }
+
+    let (status, _, _) = send_raw(addr, "/healthz", &[]).await;
+    assert_eq!(status, 200);
+}
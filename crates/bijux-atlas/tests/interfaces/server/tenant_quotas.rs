@@ -0,0 +1,157 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use bijux_atlas::adapters::outbound::api_keys::{ApiKeyRegistry, NewApiKey, QuotaTier};
+use bijux_atlas::runtime::config::{ApiConfig, AuthMode, RateLimitConfig};
+use tempfile::{tempdir, TempDir};
+
+use super::api_contracts_support::{send_raw, spawn_fixture_server};
+
+const HUMAN_GENES: &str =
+    "/v1/genes?release=110&species=homo_sapiens&assembly=GRCh38&gene_id=g1&limit=1";
+
+fn header<'a>(head: &'a str, name: &str) -> Option<&'a str> {
+    head.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        key.eq_ignore_ascii_case(name).then(|| value.trim())
+    })
+}
+
+fn registry_with_keys() -> (ApiKeyRegistry, String, String, String, String) {
+    let mut registry = ApiKeyRegistry::default();
+    registry.tiers.insert(
+        "metered".to_string(),
+        QuotaTier {
+            requests: RateLimitConfig {
+                capacity: 5.0,
+                refill_per_sec: 0.5,
+            },
+            daily_sequence_bases: Some(10_000),
+            daily_rows_returned: Some(1),
+        },
+    );
+    registry.tiers.insert(
+        "burst".to_string(),
+        QuotaTier::from(RateLimitConfig {
+            capacity: 1.0,
+            refill_per_sec: 0.001,
+        }),
+    );
+    let mut issue = |key_id: &str, tier: Option<&str>| {
+        registry
+            .create(
+                NewApiKey {
+                    key_id: key_id.to_string(),
+                    owner: format!("{key_id}-team"),
+                    rate_limit_tier: tier.map(ToString::to_string),
+                    ..NewApiKey::default()
+                },
+                1,
+            )
+            .expect("create key")
+    };
+    let analyst = issue("analyst", Some("metered"));
+    let burst = issue("burst", Some("burst"));
+    let plain = issue("plain", None);
+    let scout = issue("scout", Some("metered"));
+    (registry, analyst, burst, plain, scout)
+}
+
+async fn spawn_server(registry_dir: &TempDir) -> (std::net::SocketAddr, TempDir) {
+    let (addr, _, cache_dir) = spawn_fixture_server(ApiConfig {
+        auth_mode: AuthMode::ApiKey,
+        require_api_key: true,
+        api_key_registry_path: Some(
+            registry_dir
+                .path()
+                .join("api-keys.json")
+                .display()
+                .to_string(),
+        ),
+        ..ApiConfig::default()
+    })
+    .await;
+    (addr, cache_dir)
+}
+
+#[tokio::test]
+async fn tenant_quotas_publish_ratelimit_headers_and_enforce_daily_budgets() {
+    let registry_dir = tempdir().expect("registry dir");
+    let (registry, analyst, burst, plain, _) = registry_with_keys();
+    registry
+        .write(&registry_dir.path().join("api-keys.json"))
+        .expect("write registry");
+    let (addr, _cache_dir) = spawn_server(&registry_dir).await;
+
+    let (status, head, body) = send_raw(addr, HUMAN_GENES, &[("x-api-key", &analyst)]).await;
+    assert_eq!(status, 200, "{body}");
+    assert_eq!(
+        header(&head, "ratelimit-policy"),
+        Some("\"metered\";q=5;w=10")
+    );
+    assert_eq!(header(&head, "ratelimit"), Some("\"metered\";r=4;t=2"));
+
+    // The first response spent the one-row daily budget.
+    let (status, head, body) = send_raw(addr, HUMAN_GENES, &[("x-api-key", &analyst)]).await;
+    assert_eq!(status, 429, "{body}");
+    assert!(body.contains("daily quota exhausted"), "{body}");
+    assert!(body.contains("rows_returned"), "{body}");
+    let retry_after: u64 = header(&head, "retry-after")
+        .and_then(|value| value.parse().ok())
+        .expect("retry-after");
+    assert!((1..=86_400).contains(&retry_after), "{retry_after}");
+
+    let (status, head, body) = send_raw(addr, "/v1/usage", &[("x-api-key", &analyst)]).await;
+    assert_eq!(status, 200, "{body}");
+    assert!(header(&head, "ratelimit").is_some(), "{head}");
+    let usage: serde_json::Value = serde_json::from_str(&body).expect("usage json");
+    assert_eq!(usage["metered"], true);
+    assert_eq!(usage["tenant"], "key:analyst");
+    assert_eq!(usage["tier"], "metered");
+    assert_eq!(usage["requests"]["limit"], 5);
+    assert_eq!(usage["daily"]["rows_returned"]["used"], 1);
+    assert_eq!(usage["daily"]["rows_returned"]["remaining"], 0);
+    assert_eq!(usage["daily"]["sequence_bases"]["remaining"], 10_000);
+
+    let (status, head, body) = send_raw(addr, "/v1/usage", &[("x-api-key", &plain)]).await;
+    assert_eq!(status, 200, "{body}");
+    assert!(header(&head, "ratelimit").is_none(), "{head}");
+    assert!(body.contains("\"metered\":false"), "{body}");
+
+    let (status, _, _) = send_raw(addr, "/v1/usage", &[("x-api-key", &burst)]).await;
+    assert_eq!(status, 200);
+    let (status, head, body) = send_raw(addr, "/v1/usage", &[("x-api-key", &burst)]).await;
+    assert_eq!(status, 429, "{body}");
+    assert_eq!(header(&head, "retry-after"), Some("2"));
+    assert_eq!(header(&head, "ratelimit"), Some("\"burst\";r=0;t=2"));
+}
+
+#[tokio::test]
+async fn every_list_route_draws_from_the_rows_budget() {
+    let registry_dir = tempdir().expect("registry dir");
+    let (registry, _, _, _, scout) = registry_with_keys();
+    registry
+        .write(&registry_dir.path().join("api-keys.json"))
+        .expect("write registry");
+    let (addr, _cache_dir) = spawn_server(&registry_dir).await;
+    let key = [("x-api-key", scout.as_str())];
+    let dims = "release=110&species=homo_sapiens&assembly=GRCh38";
+
+    // The one transcript row spends the one-row daily budget.
+    let (status, _, body) = send_raw(addr, &format!("/v1/genes/g1/transcripts?{dims}"), &key).await;
+    assert_eq!(status, 200, "{body}");
+    assert!(body.contains("tx1"), "{body}");
+
+    for path in [
+        format!("/v1/genes/search?{dims}&q=g1"),
+        format!("/v1/exons?{dims}&region=chr1:1-10"),
+        format!("/v1/transcripts/tx1/exons?{dims}"),
+    ] {
+        let (status, _, body) = send_raw(addr, &path, &key).await;
+        assert_eq!(status, 429, "{path}: {body}");
+        assert!(body.contains("rows_returned"), "{path}: {body}");
+    }
+
+    // Aggregates only charge the request bucket.
+    let (status, _, body) = send_raw(addr, &format!("/v1/genes/count?{dims}"), &key).await;
+    assert_eq!(status, 200, "{body}");
+}
@@ -0,0 +1,282 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use bijux_atlas::adapters::inbound::serve_server_router_tls_with_shutdown;
+use bijux_atlas::adapters::inbound::tls::{TlsListener, TlsReloader};
+use bijux_atlas::adapters::outbound::api_keys::{ApiKeyRegistry, QuotaTier};
+use bijux_atlas::adapters::outbound::store::testing::FakeStore;
+use bijux_atlas::app::server::{AppState, DatasetCacheConfig, DatasetCacheManager};
+use bijux_atlas::domain::security::data_protection::TlsConfig;
+use bijux_atlas::runtime::config::{ApiConfig, AuthMode, RateLimitConfig};
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName};
+use rustls::{ClientConfig, RootCertStore};
+use tempfile::{tempdir, TempDir};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio_rustls::TlsConnector;
+
+use super::api_contracts_support::mk_dataset;
+
+fn fixture_path(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/tls")
+        .join(name)
+}
+
+struct TlsServer {
+    addr: std::net::SocketAddr,
+    reloader: Arc<TlsReloader>,
+    tls_dir: TempDir,
+    _cache_dir: TempDir,
+}
+
+async fn spawn_tls_server(api: ApiConfig, min_version: &str) -> TlsServer {
+    let tls_dir = tempdir().expect("tls dir");
+    for name in ["server.pem", "server-key.pem", "ca.pem"] {
+        std::fs::copy(fixture_path(name), tls_dir.path().join(name)).expect("copy tls fixture");
+    }
+    let reloader = Arc::new(
+        TlsReloader::load(&TlsConfig {
+            enabled: true,
+            min_version: min_version.to_string(),
+            cert_path: tls_dir.path().join("server.pem"),
+            key_path: tls_dir.path().join("server-key.pem"),
+            ca_path: Some(tls_dir.path().join("ca.pem")),
+        })
+        .expect("load tls"),
+    );
+
+    let (ds, manifest, sqlite) = mk_dataset();
+    let store = Arc::new(FakeStore::default());
+    store.manifest.lock().await.insert(ds.clone(), manifest);
+    store.sqlite.lock().await.insert(ds, sqlite);
+    let cache_dir = tempdir().expect("cache dir");
+    let cache = DatasetCacheManager::new(
+        DatasetCacheConfig {
+            disk_root: cache_dir.path().to_path_buf(),
+            ..DatasetCacheConfig::default()
+        },
+        store,
+    );
+    let state = AppState::with_config(cache, api, Default::default());
+    let tcp = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind listener");
+    let addr = tcp.local_addr().expect("local addr");
+    let listener = TlsListener::new(tcp, Arc::clone(&reloader)).expect("tls listener");
+    tokio::spawn(async move {
+        serve_server_router_tls_with_shutdown(listener, state, std::future::pending())
+            .await
+            .expect("serve tls");
+    });
+    TlsServer {
+        addr,
+        reloader,
+        tls_dir,
+        _cache_dir: cache_dir,
+    }
+}
+
+fn client_config(client_cert: Option<&str>, tls12_only: bool) -> ClientConfig {
+    let mut roots = RootCertStore::empty();
+    let ca = std::fs::read(fixture_path("ca.pem")).expect("ca");
+    for cert in CertificateDer::pem_slice_iter(&ca) {
+        roots.add(cert.expect("ca cert")).expect("add root");
+    }
+    let versions: &[&'static rustls::SupportedProtocolVersion] = if tls12_only {
+        &[&rustls::version::TLS12]
+    } else {
+        &[&rustls::version::TLS13, &rustls::version::TLS12]
+    };
+    let builder =
+        ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_protocol_versions(versions)
+            .expect("client versions")
+            .with_root_certificates(roots);
+    match client_cert {
+        Some(name) => {
+            let chain = CertificateDer::pem_file_iter(fixture_path(&format!("{name}.pem")))
+                .expect("client cert")
+                .collect::<Result<Vec<_>, _>>()
+                .expect("client chain");
+            let key = PrivateKeyDer::from_pem_file(fixture_path(&format!("{name}-key.pem")))
+                .expect("client key");
+            builder
+                .with_client_auth_cert(chain, key)
+                .expect("client auth")
+        }
+        None => builder.with_no_client_auth(),
+    }
+}
+
+/// Status code plus the leaf certificate the server presented, or the error
+/// that ended the exchange.
+async fn https_get(
+    addr: std::net::SocketAddr,
+    path: &str,
+    config: ClientConfig,
+) -> Result<(u16, Vec<u8>), String> {
+    https_exchange(addr, path, config)
+        .await
+        .map(|(status, leaf, _)| (status, leaf))
+}
+
+/// [`https_get`] that also returns the response body.
+async fn https_exchange(
+    addr: std::net::SocketAddr,
+    path: &str,
+    config: ClientConfig,
+) -> Result<(u16, Vec<u8>, String), String> {
+    let tcp = tokio::net::TcpStream::connect(addr)
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut stream = TlsConnector::from(Arc::new(config))
+        .connect(ServerName::try_from("localhost").expect("server name"), tcp)
+        .await
+        .map_err(|e| format!("handshake: {e}"))?;
+    let leaf = stream
+        .get_ref()
+        .1
+        .peer_certificates()
+        .and_then(|chain| chain.first())
+        .map(|cert| cert.as_ref().to_vec())
+        .unwrap_or_default();
+    let request = format!("GET {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n");
+    stream
+        .write_all(request.as_bytes())
+        .await
+        .map_err(|e| format!("write: {e}"))?;
+    let mut response = Vec::new();
+    stream
+        .read_to_end(&mut response)
+        .await
+        .map_err(|e| format!("read: {e}"))?;
+    let text = String::from_utf8_lossy(&response);
+    let status = text
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse::<u16>().ok())
+        .ok_or_else(|| format!("no http status in response: {text}"))?;
+    let body = text
+        .split_once("\r\n\r\n")
+        .map(|(_, body)| body.to_string())
+        .unwrap_or_default();
+    Ok((status, leaf, body))
+}
+
+fn pem_der(name: &str) -> Vec<u8> {
+    let pem = std::fs::read(fixture_path(name)).expect("pem");
+    CertificateDer::from_pem_slice(&pem)
+        .expect("cert")
+        .as_ref()
+        .to_vec()
+}
+
+#[tokio::test]
+async fn mtls_mode_takes_the_principal_from_verified_client_certificates() {
+    // A default quota tier meters every caller by subject, so `/v1/usage`
+    // reports the principal the certificate resolved to.
+    let registry_dir = tempdir().expect("registry dir");
+    let mut registry = ApiKeyRegistry::default();
+    registry.tiers.insert(
+        "service".to_string(),
+        QuotaTier::from(RateLimitConfig {
+            capacity: 100.0,
+            refill_per_sec: 10.0,
+        }),
+    );
+    registry.default_tier = Some("service".to_string());
+    let registry_path = registry_dir.path().join("api-keys.json");
+    registry.write(&registry_path).expect("write registry");
+    let server = spawn_tls_server(
+        ApiConfig {
+            auth_mode: AuthMode::Mtls,
+            api_key_registry_path: Some(registry_path.display().to_string()),
+            ..ApiConfig::default()
+        },
+        "1.2",
+    )
+    .await;
+
+    let (status, leaf) = https_get(
+        server.addr,
+        "/v1/datasets",
+        client_config(Some("client"), false),
+    )
+    .await
+    .expect("mtls request");
+    assert_eq!(status, 200);
+    assert_eq!(leaf, pem_der("server.pem"));
+    let (status, _, body) = https_exchange(
+        server.addr,
+        "/v1/usage",
+        client_config(Some("client"), false),
+    )
+    .await
+    .expect("usage request");
+    assert_eq!(status, 200, "{body}");
+    let usage: serde_json::Value = serde_json::from_str(&body).expect("usage json");
+    assert_eq!(usage["tenant"], "sub:spiffe://atlas.test/ingest-bot");
+
+    // Probes reach exempt routes without a certificate; data routes do not.
+    let (status, _) = https_get(server.addr, "/healthz", client_config(None, false))
+        .await
+        .expect("probe request");
+    assert_eq!(status, 200);
+    let (status, _) = https_get(server.addr, "/v1/datasets", client_config(None, false))
+        .await
+        .expect("anonymous request");
+    assert_eq!(status, 401);
+
+    // A certificate from another CA never reaches the router.
+    let rogue = https_get(
+        server.addr,
+        "/v1/datasets",
+        client_config(Some("untrusted-client"), false),
+    )
+    .await;
+    assert!(
+        rogue.is_err(),
+        "untrusted client certificate was served: {rogue:?}"
+    );
+}
+
+#[tokio::test]
+async fn certificate_files_hot_reload_and_min_version_is_enforced() {
+    let server = spawn_tls_server(ApiConfig::default(), "1.3").await;
+    let before = server.reloader.active_fingerprint();
+    assert!(!server
+        .reloader
+        .reload_if_changed()
+        .expect("unchanged bundle"));
+
+    for (from, to) in [
+        ("server-rotated.pem", "server.pem"),
+        ("server-rotated-key.pem", "server-key.pem"),
+    ] {
+        std::fs::copy(fixture_path(from), server.tls_dir.path().join(to)).expect("rotate");
+    }
+    assert!(server.reloader.reload_if_changed().expect("rotated bundle"));
+    assert_ne!(server.reloader.active_fingerprint(), before);
+    let (status, leaf) = https_get(server.addr, "/healthz", client_config(None, false))
+        .await
+        .expect("request after rotation");
+    assert_eq!(status, 200);
+    assert_eq!(leaf, pem_der("server-rotated.pem"));
+
+    // A broken key file leaves the rotated bundle serving.
+    std::fs::write(server.tls_dir.path().join("server-key.pem"), "not a key").expect("break");
+    assert!(server.reloader.reload_if_changed().is_err());
+    let (status, _) = https_get(server.addr, "/healthz", client_config(None, false))
+        .await
+        .expect("request after failed reload");
+    assert_eq!(status, 200);
+
+    let downgraded = https_get(server.addr, "/healthz", client_config(None, true)).await;
+    assert!(
+        downgraded.is_err(),
+        "tls 1.2 client accepted: {downgraded:?}"
+    );
+}
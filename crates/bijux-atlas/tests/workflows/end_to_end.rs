@@ -1,6 +1,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use arrow_ipc::reader::StreamReader;
 use assert_cmd::Command;
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use parquet::record::RowAccessor;
 use serde_json::Value;
 use std::path::PathBuf;
 
@@ -287,6 +290,92 @@ fn cli_fixture_workflow_covers_dataset_create_validate_query_and_refusal() {
     assert!(export_rows.status.success());
     assert!(export_path.exists());
 
+    let parquet_path = tmp.path().join("rows.parquet");
+    let export_parquet = Command::new(env!("CARGO_BIN_EXE_bijux-atlas"))
+        .current_dir(&root)
+        .args(["--json", "export", "query", "--db"])
+        .arg(&db)
+        .args(["--out"])
+        .arg(&parquet_path)
+        .args(["--gene-id", "gene1", "--format", "parquet"])
+        .output()
+        .expect("run export query parquet");
+    assert!(export_parquet.status.success());
+    let parquet =
+        SerializedFileReader::new(std::fs::File::open(&parquet_path).expect("open parquet export"))
+            .expect("read parquet export");
+    let parquet_rows: Vec<_> = parquet
+        .get_row_iter(None)
+        .expect("parquet rows")
+        .map(|row| row.expect("parquet row"))
+        .collect();
+    assert_eq!(parquet_rows.len(), 1);
+    assert_eq!(parquet_rows[0].get_string(0).expect("gene_id"), "gene1");
+
+    let gtf_path = tmp.path().join("rows.gtf");
+    let export_gtf = Command::new(env!("CARGO_BIN_EXE_bijux-atlas"))
+        .current_dir(&root)
+        .args(["--json", "export", "query", "--db"])
+        .arg(&db)
+        .args(["--out"])
+        .arg(&gtf_path)
+        .args(["--gene-id", "gene1", "--format", "gtf"])
+        .output()
+        .expect("run export query gtf");
+    assert!(export_gtf.status.success());
+    let gtf = std::fs::read_to_string(&gtf_path).expect("read gtf export");
+    assert!(gtf.contains("\tgene\t") && gtf.contains("gene_id \"gene1\";"));
+
+    let columnar_dir = tmp.path().join("columnar");
+    let dataset_export = Command::new(env!("CARGO_BIN_EXE_bijux-atlas"))
+        .current_dir(&root)
+        .args([
+            "--json",
+            "dataset",
+            "export",
+            "--root",
+            source_root.to_str().expect("source_root"),
+            "--release",
+            "110",
+            "--species",
+            "homo_sapiens",
+            "--assembly",
+            "GRCh38",
+            "--out-dir",
+            columnar_dir.to_str().expect("columnar_dir"),
+            "--format",
+            "arrow",
+        ])
+        .output()
+        .expect("run dataset export");
+    assert!(dataset_export.status.success());
+    let dataset_export_payload: Value =
+        serde_json::from_slice(&dataset_export.stdout).expect("dataset export payload");
+    let tables = dataset_export_payload["tables"].as_array().expect("tables");
+    let gene_table = tables
+        .iter()
+        .find(|t| t["table"].as_str() == Some("gene_summary"))
+        .expect("gene_summary exported");
+    let gene_stream = StreamReader::try_new(
+        std::fs::File::open(columnar_dir.join("gene_summary.arrows")).expect("open arrow export"),
+        None,
+    )
+    .expect("read arrow export");
+    assert!(gene_stream.schema().field_with_name("gene_id").is_ok());
+    let gene_rows: usize = gene_stream
+        .map(|batch| batch.expect("record batch").num_rows())
+        .sum();
+    assert_eq!(
+        Some(gene_rows as u64),
+        gene_table["rows"].as_u64(),
+        "{gene_table}"
+    );
+    assert!(gene_table["columns"]
+        .as_array()
+        .expect("columns")
+        .iter()
+        .any(|c| c["name"].as_str() == Some("start") && c["type"].as_str() == Some("int64")));
+
     let publish_dry_run = Command::new(env!("CARGO_BIN_EXE_bijux-atlas"))
         .current_dir(&root)
         .args([